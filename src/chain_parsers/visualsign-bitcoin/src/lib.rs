@@ -0,0 +1,604 @@
+use visualsign::{
+    AnnotatedPayloadField, SignablePayload, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+    capabilities::ParserCapabilities,
+    encodings::SupportedEncodings,
+    errors::{ParserError, ParserErrorKind},
+    field_builders::create_text_field,
+    fixed_point::format_fixed_point,
+    parser_info::ParserInfo,
+    sender::{create_sender_field, resolve_sender},
+    vsptrait::{
+        Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
+        VisualSignError, VisualSignOptions,
+    },
+};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as b64};
+
+pub mod lightning;
+pub mod psbt;
+pub mod script;
+pub mod taproot;
+
+use psbt::{Psbt, PsbtInput, PsbtOutput, TxOut};
+use script::ScriptType;
+use taproot::{SpendPath, classify_spend, describe_sighash, effective_sighash};
+
+const CHAIN_NAME: &str = "Bitcoin";
+
+/// Lock times below this are block heights, at or above it Unix timestamps
+const LOCK_TIME_THRESHOLD: u32 = 500_000_000;
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BitcoinParserError {
+    #[error("Invalid PSBT: {0}")]
+    InvalidPsbt(String),
+    #[error("Truncated input: {0}")]
+    Truncated(String),
+    #[error("Unsupported format: {0}")]
+    UnsupportedVersion(String),
+    #[error("Invalid Lightning invoice: {0}")]
+    InvalidInvoice(String),
+}
+
+impl From<BitcoinParserError> for TransactionParseError {
+    fn from(error: BitcoinParserError) -> Self {
+        let (kind, reason) = match &error {
+            BitcoinParserError::InvalidPsbt(_) => (ParserErrorKind::Encoding, "InvalidPsbt"),
+            BitcoinParserError::Truncated(_) => (ParserErrorKind::Truncated, "Truncated"),
+            BitcoinParserError::UnsupportedVersion(_) => {
+                (ParserErrorKind::UnsupportedVariant, "UnsupportedVersion")
+            }
+            BitcoinParserError::InvalidInvoice(_) => (ParserErrorKind::Encoding, "InvalidInvoice"),
+        };
+        TransactionParseError::Parser(ParserError::new(
+            kind,
+            CHAIN_NAME,
+            reason,
+            error.to_string(),
+        ))
+    }
+}
+
+/// Wrapper for partially signed Bitcoin transactions
+///
+/// Accepts a version 0 or version 2 PSBT as base64, the usual encoding, or hex.
+#[derive(Debug, Clone)]
+pub struct BitcoinPsbtWrapper {
+    psbt: Psbt,
+}
+
+impl Transaction for BitcoinPsbtWrapper {
+    fn from_string(data: &str) -> Result<Self, TransactionParseError> {
+        let data = data.trim();
+        let bytes = match SupportedEncodings::detect(data) {
+            SupportedEncodings::Hex => hex::decode(data).map_err(|e| {
+                BitcoinParserError::InvalidPsbt(format!("Failed to decode hex: {e}"))
+            })?,
+            SupportedEncodings::Base64 => b64.decode(data).map_err(|e| {
+                BitcoinParserError::InvalidPsbt(format!("Failed to decode base64: {e}"))
+            })?,
+        };
+        Ok(Self::new(psbt::parse_psbt(&bytes)?))
+    }
+
+    fn transaction_type(&self) -> String {
+        CHAIN_NAME.to_string()
+    }
+}
+
+impl BitcoinPsbtWrapper {
+    pub fn new(psbt: Psbt) -> Self {
+        Self { psbt }
+    }
+
+    pub fn inner(&self) -> &Psbt {
+        &self.psbt
+    }
+}
+
+/// Converter for Bitcoin PSBTs
+pub struct BitcoinVisualSignConverter;
+
+impl VisualSignConverter<BitcoinPsbtWrapper> for BitcoinVisualSignConverter {
+    fn to_visual_sign_payload(
+        &self,
+        transaction_wrapper: BitcoinPsbtWrapper,
+        options: VisualSignOptions,
+    ) -> Result<SignablePayload, VisualSignError> {
+        convert_to_visual_sign_payload(transaction_wrapper.psbt, options)
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        ParserCapabilities::new(&["base64", "hex"], &["PSBT v0", "PSBT v2"])
+            .with_feature("psbt_v0", true)
+            .with_feature("psbt_v2", true)
+            .with_feature("taproot", true)
+    }
+}
+
+impl VisualSignConverterFromString<BitcoinPsbtWrapper> for BitcoinVisualSignConverter {}
+
+fn convert_to_visual_sign_payload(
+    psbt: Psbt,
+    options: VisualSignOptions,
+) -> Result<SignablePayload, VisualSignError> {
+    let mut fields = vec![text_field("Network", CHAIN_NAME)];
+
+    // Inputs may belong to several wallets, so there is no sender to derive
+    if let Some(sender) = resolve_sender(None, options.sender.as_deref(), |derived, provided| {
+        derived == provided
+    })? {
+        fields.push(create_sender_field(&sender));
+    }
+
+    fields.push(text_field("PSBT Version", &psbt.version.to_string()));
+    fields.push(text_field(
+        "Transaction Version",
+        &psbt.tx_version.to_string(),
+    ));
+    // Version 2 inputs can require a lock time, which then replaces the fallback
+    let lock_time_label = if psbt.version == 2 {
+        "Fallback Lock Time"
+    } else {
+        "Lock Time"
+    };
+    fields.push(text_field(lock_time_label, &lock_time_text(psbt.lock_time)));
+
+    let spent_outputs: Vec<Option<TxOut>> =
+        psbt.inputs.iter().map(PsbtInput::spent_output).collect();
+    let total_output: u128 = psbt
+        .outputs
+        .iter()
+        .map(|output| u128::from(output.amount))
+        .sum();
+    let total_input: Option<u128> = spent_outputs
+        .iter()
+        .map(|spent| spent.as_ref().map(|spent| u128::from(spent.value)))
+        .sum();
+    match total_input {
+        Some(total_input) => {
+            fields.push(text_field("Total Input", &btc(total_input)));
+            fields.push(text_field("Total Output", &btc(total_output)));
+            let fee = match total_input.checked_sub(total_output) {
+                Some(fee) => btc(fee),
+                None => format!(
+                    "Invalid: outputs exceed inputs by {}",
+                    btc(total_output - total_input)
+                ),
+            };
+            fields.push(text_field("Fee", &fee));
+        }
+        None => {
+            fields.push(text_field("Total Output", &btc(total_output)));
+            fields.push(text_field(
+                "Fee",
+                "Unknown (the PSBT does not include every spent output)",
+            ));
+        }
+    }
+
+    for (index, (input, spent)) in psbt.inputs.iter().zip(&spent_outputs).enumerate() {
+        fields.push(input_section(index + 1, input, spent.as_ref())?);
+    }
+    for (index, output) in psbt.outputs.iter().enumerate() {
+        fields.push(output_section(index + 1, output)?);
+    }
+
+    let title = options
+        .transaction_name
+        .unwrap_or_else(|| "Bitcoin Transaction".to_string());
+    Ok(SignablePayload::new(
+        0,
+        title,
+        None,
+        fields,
+        "BitcoinPsbt".to_string(),
+    ))
+}
+
+// One preview section per input, with how it is spent and what its signature covers
+fn input_section(
+    number: usize,
+    input: &PsbtInput,
+    spent: Option<&TxOut>,
+) -> Result<SignablePayloadField, VisualSignError> {
+    let script_type = spent.map(|spent| ScriptType::from_script(&spent.script_pubkey));
+    // Without the spent output, the Taproot signing fields still mark a Taproot input
+    let taproot = script_type.map_or_else(
+        || {
+            input.tap_key_sig.is_some()
+                || input.tap_internal_key.is_some()
+                || !input.tap_leaf_scripts.is_empty()
+        },
+        |script_type| script_type == ScriptType::P2tr,
+    );
+    let outpoint = format!("{}:{}", txid_text(&input.previous_txid), input.output_index);
+    let amount = spent.map_or_else(|| "Unknown".to_string(), |spent| btc(spent.value.into()));
+    let sighash = create_text_field(
+        "Sighash",
+        &describe_sighash(effective_sighash(input, taproot), taproot),
+    )?;
+
+    let mut condensed = Vec::new();
+    let mut expanded = vec![
+        create_text_field("Previous Output", &outpoint)?,
+        create_text_field("Amount", &amount)?,
+        create_text_field(
+            "Script Type",
+            script_type.map_or("Unknown", |script_type| script_type.name()),
+        )?,
+    ];
+    if taproot {
+        let spend = classify_spend(input);
+        let spend_path = match &spend.path {
+            SpendPath::KeyPath => "Key path (a signature for the output key)".to_string(),
+            SpendPath::ScriptPath(leaves) if leaves.len() > 1 => {
+                format!("Script path (one of {} leaf scripts)", leaves.len())
+            }
+            SpendPath::ScriptPath(_) => "Script path (a leaf script of the tree)".to_string(),
+        };
+        let spend_path = create_text_field("Spend Path", &spend_path)?;
+        condensed.push(spend_path.clone());
+        expanded.push(spend_path);
+        if let SpendPath::ScriptPath(leaves) = &spend.path {
+            for leaf in leaves {
+                expanded.push(create_text_field(
+                    "Leaf Script",
+                    &format!("0x{}", hex::encode(&leaf.script)),
+                )?);
+                expanded.push(create_text_field(
+                    "Leaf Version",
+                    &format!("0x{:02x}", leaf.leaf_version),
+                )?);
+                expanded.push(create_text_field(
+                    "Merkle Depth",
+                    &leaf.merkle_depth().to_string(),
+                )?);
+            }
+        }
+        let annex = spend.annex.map_or_else(
+            || "None".to_string(),
+            |annex| format!("Present ({} bytes): 0x{}", annex.len(), hex::encode(&annex)),
+        );
+        expanded.push(create_text_field("Annex", &annex)?);
+    }
+    condensed.push(sighash.clone());
+    expanded.push(sighash);
+    if let Some(sequence) = input.sequence {
+        expanded.push(create_text_field("Sequence", &sequence_text(sequence))?);
+    }
+    if let Some(lock_time) = input.required_time_lock_time {
+        expanded.push(create_text_field(
+            "Required Time Lock",
+            &format!("Unix time {lock_time}"),
+        )?);
+    }
+    if let Some(lock_time) = input.required_height_lock_time {
+        expanded.push(create_text_field(
+            "Required Height Lock",
+            &format!("Block height {lock_time}"),
+        )?);
+    }
+
+    Ok(preview_section(
+        format!("Input {number}"),
+        format!("Spend {amount}"),
+        outpoint,
+        condensed,
+        expanded,
+    ))
+}
+
+fn output_section(
+    number: usize,
+    output: &PsbtOutput,
+) -> Result<SignablePayloadField, VisualSignError> {
+    let amount = btc(output.amount.into());
+    let script_type = ScriptType::from_script(&output.script_pubkey);
+    let script = format!("0x{}", hex::encode(&output.script_pubkey));
+    let mut expanded = vec![
+        create_text_field("Amount", &amount)?,
+        create_text_field("Script Type", script_type.name())?,
+        create_text_field("Script", &script)?,
+    ];
+    if let Some(internal_key) = &output.tap_internal_key {
+        expanded.push(create_text_field(
+            "Taproot Internal Key",
+            &format!("0x{}", hex::encode(internal_key)),
+        )?);
+    }
+    if output.has_tap_tree {
+        expanded.push(create_text_field("Taproot Script Tree", "Present")?);
+    }
+
+    Ok(preview_section(
+        format!("Output {number}"),
+        amount,
+        format!("{} {script}", script_type.name()),
+        vec![create_text_field("Script Type", script_type.name())?],
+        expanded,
+    ))
+}
+
+fn preview_section(
+    label: String,
+    title: String,
+    subtitle: String,
+    condensed: Vec<AnnotatedPayloadField>,
+    expanded: Vec<AnnotatedPayloadField>,
+) -> SignablePayloadField {
+    SignablePayloadField::PreviewLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text: title.clone(),
+            label,
+        },
+        preview_layout: SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 { text: title }),
+            subtitle: Some(SignablePayloadFieldTextV2 { text: subtitle }),
+            condensed: Some(SignablePayloadFieldListLayout { fields: condensed }),
+            expanded: Some(SignablePayloadFieldListLayout { fields: expanded }),
+        },
+    }
+}
+
+fn text_field(label: &str, text: &str) -> SignablePayloadField {
+    SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: text.to_string(),
+            label: label.to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 {
+            text: text.to_string(),
+        },
+    }
+}
+
+fn btc(sats: u128) -> String {
+    format!("{} BTC", format_fixed_point(sats, 8))
+}
+
+// Txids are displayed in the reverse of their byte order
+fn txid_text(txid: &[u8; 32]) -> String {
+    let mut reversed = *txid;
+    reversed.reverse();
+    hex::encode(reversed)
+}
+
+fn lock_time_text(lock_time: u32) -> String {
+    match lock_time {
+        0 => "None".to_string(),
+        height if height < LOCK_TIME_THRESHOLD => format!("Block height {height}"),
+        time => format!("Unix time {time}"),
+    }
+}
+
+fn sequence_text(sequence: u32) -> String {
+    let meaning = match sequence {
+        0xffff_ffff => "final",
+        0xffff_fffe => "enables the lock time",
+        _ => "signals replace-by-fee",
+    };
+    format!("0x{sequence:08x} ({meaning})")
+}
+
+// Public API functions
+pub fn transaction_to_visual_sign(
+    psbt: Psbt,
+    options: VisualSignOptions,
+) -> Result<SignablePayload, VisualSignError> {
+    BitcoinVisualSignConverter.to_visual_sign_payload(BitcoinPsbtWrapper::new(psbt), options)
+}
+
+pub fn transaction_string_to_visual_sign(
+    transaction_data: &str,
+    options: VisualSignOptions,
+) -> Result<SignablePayload, VisualSignError> {
+    BitcoinVisualSignConverter.to_visual_sign_payload_from_string(transaction_data, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P2TR_SCRIPT: [u8; 34] = {
+        let mut script = [0x33; 34];
+        script[0] = 0x51;
+        script[1] = 0x20;
+        script
+    };
+
+    fn pair(out: &mut Vec<u8>, key: &[u8], value: &[u8]) {
+        out.push(u8::try_from(key.len()).unwrap());
+        out.extend(key);
+        out.push(u8::try_from(value.len()).unwrap());
+        out.extend(value);
+    }
+
+    fn tx_out(value: u64, script: &[u8]) -> Vec<u8> {
+        let mut out = value.to_le_bytes().to_vec();
+        out.push(u8::try_from(script.len()).unwrap());
+        out.extend(script);
+        out
+    }
+
+    fn p2wpkh() -> Vec<u8> {
+        [vec![0x00, 0x14], vec![0x22; 20]].concat()
+    }
+
+    // A version 0 PSBT spending a Taproot output through the key path
+    fn v0_psbt() -> Vec<u8> {
+        let mut tx = 2i32.to_le_bytes().to_vec();
+        tx.push(1);
+        tx.extend([0x11; 32]);
+        tx.extend(1u32.to_le_bytes());
+        tx.push(0);
+        tx.extend(0xffff_fffdu32.to_le_bytes());
+        tx.push(1);
+        tx.extend(tx_out(90_000, &p2wpkh()));
+        tx.extend(0u32.to_le_bytes());
+
+        let mut psbt = psbt::PSBT_MAGIC.to_vec();
+        pair(&mut psbt, &[0x00], &tx);
+        psbt.push(0);
+        pair(&mut psbt, &[0x01], &tx_out(100_000, &P2TR_SCRIPT));
+        pair(&mut psbt, &[0x13], &[0x44; 64]);
+        psbt.push(0);
+        psbt.push(0);
+        psbt
+    }
+
+    // A version 2 PSBT with a finalized script-path spend carrying an annex
+    fn v2_psbt() -> Vec<u8> {
+        let mut psbt = psbt::PSBT_MAGIC.to_vec();
+        pair(&mut psbt, &[0x02], &2i32.to_le_bytes());
+        pair(&mut psbt, &[0x03], &850_000u32.to_le_bytes());
+        pair(&mut psbt, &[0x04], &[1]);
+        pair(&mut psbt, &[0x05], &[1]);
+        pair(&mut psbt, &[0xfb], &2u32.to_le_bytes());
+        psbt.push(0);
+
+        pair(&mut psbt, &[0x0e], &[0x11; 32]);
+        pair(&mut psbt, &[0x0f], &0u32.to_le_bytes());
+        pair(&mut psbt, &[0x01], &tx_out(50_000, &P2TR_SCRIPT));
+        pair(&mut psbt, &[0x03], &0x83u32.to_le_bytes());
+        let control_block = [vec![0xc1], vec![0x55; 64]].concat();
+        let mut witness = vec![4, 64];
+        witness.extend([0x66; 64]);
+        witness.extend([2, 0x51, 0xac]);
+        witness.push(65);
+        witness.extend(&control_block);
+        witness.extend([2, 0x50, 0x01]);
+        pair(&mut psbt, &[0x08], &witness);
+        psbt.push(0);
+
+        pair(&mut psbt, &[0x03], &49_000u64.to_le_bytes());
+        pair(&mut psbt, &[0x04], &p2wpkh());
+        psbt.push(0);
+        psbt
+    }
+
+    fn labelled(fields: &[SignablePayloadField]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|field| format!("{}: {}", field.label(), field.fallback_text()))
+            .collect()
+    }
+
+    fn expanded(field: &SignablePayloadField) -> Vec<String> {
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("expected a preview layout");
+        };
+        let fields: Vec<SignablePayloadField> = preview_layout
+            .expanded
+            .as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|field| field.signable_payload_field.clone())
+            .collect();
+        labelled(&fields)
+    }
+
+    #[test]
+    fn test_v0_key_path_spend() {
+        let payload =
+            transaction_string_to_visual_sign(&b64.encode(v0_psbt()), VisualSignOptions::default())
+                .unwrap();
+        assert_eq!(payload.payload_type, "BitcoinPsbt");
+        assert_eq!(
+            labelled(&payload.fields),
+            vec![
+                "Network: Bitcoin",
+                "PSBT Version: 0",
+                "Transaction Version: 2",
+                "Lock Time: None",
+                "Total Input: 0.001 BTC",
+                "Total Output: 0.0009 BTC",
+                "Fee: 0.0001 BTC",
+                "Input 1: Spend 0.001 BTC",
+                "Output 1: 0.0009 BTC",
+            ]
+        );
+        assert_eq!(
+            expanded(&payload.fields[7]),
+            vec![
+                format!("Previous Output: {}:1", "11".repeat(32)),
+                "Amount: 0.001 BTC".to_string(),
+                "Script Type: P2TR (Taproot)".to_string(),
+                "Spend Path: Key path (a signature for the output key)".to_string(),
+                "Annex: None".to_string(),
+                "Sighash: DEFAULT (signs all inputs and all outputs)".to_string(),
+                "Sequence: 0xfffffffd (signals replace-by-fee)".to_string(),
+            ]
+        );
+        assert!(payload.validate_charset().is_ok());
+    }
+
+    #[test]
+    fn test_v2_script_path_spend_with_annex() {
+        let payload = transaction_string_to_visual_sign(
+            &hex::encode(v2_psbt()),
+            VisualSignOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(payload.fields[1].fallback_text(), "2");
+        assert_eq!(payload.fields[3].label(), "Fallback Lock Time");
+        assert_eq!(payload.fields[3].fallback_text(), "Block height 850000");
+        assert_eq!(payload.fields[6].fallback_text(), "0.00001 BTC");
+        assert_eq!(
+            expanded(&payload.fields[7]),
+            vec![
+                format!("Previous Output: {}:0", "11".repeat(32)),
+                "Amount: 0.0005 BTC".to_string(),
+                "Script Type: P2TR (Taproot)".to_string(),
+                "Spend Path: Script path (a leaf script of the tree)".to_string(),
+                "Leaf Script: 0x51ac".to_string(),
+                "Leaf Version: 0xc0".to_string(),
+                "Merkle Depth: 1".to_string(),
+                "Annex: Present (2 bytes): 0x5001".to_string(),
+                "Sighash: SINGLE|ANYONECANPAY (signs only this input, so others can be added, and only the output at this input's index)".to_string(),
+            ]
+        );
+        assert_eq!(
+            expanded(&payload.fields[8]),
+            vec![
+                "Amount: 0.00049 BTC".to_string(),
+                "Script Type: P2WPKH".to_string(),
+                format!("Script: 0x0014{}", "22".repeat(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_psbts() {
+        let error = |bytes: &[u8]| {
+            BitcoinPsbtWrapper::from_string(&hex::encode(bytes))
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(b"psbx\xff\x00").contains("Missing PSBT magic"));
+        assert!(error(&v0_psbt()[..40]).contains("Truncated"));
+
+        // A version 0 PSBT must not carry version 2 fields
+        let mut mixed = v0_psbt();
+        mixed.splice(5..5, [1, 0x04, 1, 1]);
+        assert!(error(&mixed).contains("PSBT_GLOBAL_INPUT_COUNT"));
+
+        let mut v1 = psbt::PSBT_MAGIC.to_vec();
+        pair(&mut v1, &[0xfb], &1u32.to_le_bytes());
+        v1.push(0);
+        assert!(error(&v1).contains("PSBT version 1"));
+
+        let mut duplicate = psbt::PSBT_MAGIC.to_vec();
+        pair(&mut duplicate, &[0xfb], &0u32.to_le_bytes());
+        pair(&mut duplicate, &[0xfb], &0u32.to_le_bytes());
+        duplicate.push(0);
+        assert!(error(&duplicate).contains("Duplicate key fb in global map"));
+    }
+}
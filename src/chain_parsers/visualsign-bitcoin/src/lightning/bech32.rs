@@ -0,0 +1,175 @@
+//! Bech32 (BIP-173) as Lightning uses it.
+//!
+//! BOLT11 invoices are bech32 with a checksum but without the 90-character limit of
+//! addresses, and BOLT12 strings are bech32 without a checksum, so neither fits a generic
+//! address decoder.
+
+use crate::BitcoinParserError;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATORS: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+const CHECKSUM_LEN: usize = 6;
+
+fn invalid(message: String) -> BitcoinParserError {
+    BitcoinParserError::InvalidInvoice(message)
+}
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ u32::from(value);
+        for (bit, generator) in GENERATORS.iter().enumerate() {
+            if (top >> bit) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut expanded: Vec<u8> = hrp.bytes().map(|byte| byte >> 5).collect();
+    expanded.push(0);
+    expanded.extend(hrp.bytes().map(|byte| byte & 0x1f));
+    expanded
+}
+
+/// Splits `text` into its human-readable part and 5-bit data groups, verifying and removing
+/// the checksum when `checksum` is set.
+pub fn decode(text: &str, checksum: bool) -> Result<(String, Vec<u8>), BitcoinParserError> {
+    if text.bytes().any(|byte| byte.is_ascii_lowercase())
+        && text.bytes().any(|byte| byte.is_ascii_uppercase())
+    {
+        return Err(invalid(
+            "Bech32 string mixes upper and lower case".to_string(),
+        ));
+    }
+    let text = text.to_ascii_lowercase();
+    let (hrp, data) = text
+        .rsplit_once('1')
+        .ok_or_else(|| invalid("Bech32 string has no separator".to_string()))?;
+    if hrp.is_empty() {
+        return Err(invalid(
+            "Bech32 string has no human-readable part".to_string(),
+        ));
+    }
+
+    let mut groups = data
+        .bytes()
+        .map(|byte| {
+            CHARSET
+                .iter()
+                .position(|ch| *ch == byte)
+                .and_then(|position| u8::try_from(position).ok())
+                .ok_or_else(|| invalid(format!("Invalid bech32 character {:?}", char::from(byte))))
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+    if checksum {
+        if groups.len() < CHECKSUM_LEN
+            || polymod(hrp_expand(hrp).into_iter().chain(groups.iter().copied())) != 1
+        {
+            return Err(invalid("Invalid bech32 checksum".to_string()));
+        }
+        groups.truncate(groups.len() - CHECKSUM_LEN);
+    }
+    Ok((hrp.to_string(), groups))
+}
+
+/// Regroups 5-bit groups into bytes. Leftover bits are dropped, or with `pad` zero-padded
+/// into a last byte.
+pub fn to_bytes(groups: &[u8], pad: bool) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(groups.len() * 5 / 8 + 1);
+    let mut accumulator = 0u32;
+    let mut bits = 0;
+    for group in groups {
+        accumulator = (accumulator << 5) | u32::from(*group);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+    if pad && bits > 0 {
+        bytes.push((accumulator << (8 - bits)) as u8);
+    }
+    bytes
+}
+
+/// Reads 5-bit groups as a big-endian integer.
+pub fn to_u64(groups: &[u8]) -> Result<u64, BitcoinParserError> {
+    if groups.len() > 12 {
+        return Err(invalid(format!(
+            "Integer of {} groups is too large",
+            groups.len()
+        )));
+    }
+    Ok(groups
+        .iter()
+        .fold(0u64, |value, group| (value << 5) | u64::from(*group)))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // Test-only encoder, so invoices can be built and signed in tests
+    pub(crate) fn encode(hrp: &str, groups: &[u8], checksum: bool) -> String {
+        let mut data = groups.to_vec();
+        if checksum {
+            let check = polymod(
+                hrp_expand(hrp)
+                    .into_iter()
+                    .chain(groups.iter().copied())
+                    .chain([0; CHECKSUM_LEN]),
+            ) ^ 1;
+            data.extend((0..CHECKSUM_LEN).map(|i| ((check >> (5 * (5 - i))) & 0x1f) as u8));
+        }
+        let data: String = data
+            .iter()
+            .map(|group| char::from(CHARSET[usize::from(*group)]))
+            .collect();
+        format!("{hrp}1{data}")
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Vec<u8> {
+        let mut groups = Vec::new();
+        let mut accumulator = 0u32;
+        let mut bits = 0;
+        for byte in bytes {
+            accumulator = (accumulator << 8) | u32::from(*byte);
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                groups.push(((accumulator >> bits) & 0x1f) as u8);
+            }
+        }
+        if bits > 0 {
+            groups.push(((accumulator << (5 - bits)) & 0x1f) as u8);
+        }
+        groups
+    }
+
+    #[test]
+    fn test_bip173_vectors() {
+        // Valid strings from BIP-173
+        for valid in ["A12UEL5L", "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"] {
+            assert!(decode(valid, true).is_ok(), "{valid}");
+        }
+        assert_eq!(decode("A12UEL5L", true).unwrap(), ("a".to_string(), vec![]));
+        assert!(decode("A12UEL5l", true).is_err());
+        assert!(decode("a12uel5m", true).is_err());
+
+        let groups = from_bytes(b"lightning");
+        assert_eq!(to_bytes(&groups, false), b"lightning");
+        let (hrp, decoded) = decode(&encode("lnbc", &groups, true), true).unwrap();
+        assert_eq!((hrp.as_str(), decoded), ("lnbc", groups));
+    }
+}
@@ -0,0 +1,383 @@
+//! BOLT11 invoices.
+//!
+//! An invoice is bech32: the human-readable part is `ln`, a currency prefix and an optional
+//! amount, and the data is a 35-bit timestamp, tagged fields and a 65-byte recoverable
+//! signature. The payee's node key is either a tagged field or recovered from the
+//! signature; when it is a tagged field, the signature has to recover to it.
+
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use super::bech32;
+use crate::BitcoinParserError;
+
+/// Expiry in seconds when the invoice has no `x` field
+pub const DEFAULT_EXPIRY: u64 = 3600;
+/// `min_final_cltv_expiry_delta` in blocks when the invoice has no `c` field
+pub const DEFAULT_MIN_FINAL_CLTV_EXPIRY: u64 = 18;
+
+const TIMESTAMP_GROUPS: usize = 7;
+const SIGNATURE_GROUPS: usize = 104;
+
+// Field tags, as the value of their bech32 character
+const TAG_PAYMENT_HASH: u8 = 1; // p
+const TAG_ROUTE_HINT: u8 = 3; // r
+const TAG_EXPIRY: u8 = 6; // x
+const TAG_FALLBACK: u8 = 9; // f
+const TAG_DESCRIPTION: u8 = 13; // d
+const TAG_PAYMENT_SECRET: u8 = 16; // s
+const TAG_PAYEE: u8 = 19; // n
+const TAG_DESCRIPTION_HASH: u8 = 23; // h
+const TAG_MIN_FINAL_CLTV_EXPIRY: u8 = 24; // c
+
+/// Bytes of one hop of a route hint: node key, channel id, fees and CLTV delta
+const ROUTE_HINT_HOP_LEN: usize = 51;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bolt11Invoice {
+    pub network: &'static str,
+    /// Requested amount; `None` lets the payer choose
+    pub amount_msat: Option<u64>,
+    /// Creation time in seconds since the Unix epoch
+    pub timestamp: u64,
+    pub payment_hash: [u8; 32],
+    pub payment_secret: Option<[u8; 32]>,
+    pub description: Option<String>,
+    /// SHA-256 of a description that is not part of the invoice
+    pub description_hash: Option<[u8; 32]>,
+    /// Compressed key of the node being paid
+    pub payee: [u8; 33],
+    pub expiry: u64,
+    pub min_final_cltv_expiry: u64,
+    /// On-chain fallbacks, as (witness version or address type, program or hash)
+    pub fallbacks: Vec<(u8, Vec<u8>)>,
+    /// Hops of each private route hint
+    pub route_hints: Vec<usize>,
+}
+
+fn invalid(message: String) -> BitcoinParserError {
+    BitcoinParserError::InvalidInvoice(message)
+}
+
+/// Parses a BOLT11 invoice and recovers its payee from the signature.
+pub fn parse_invoice(text: &str) -> Result<Bolt11Invoice, BitcoinParserError> {
+    let (hrp, data) = bech32::decode(text, true)?;
+    let (network, amount_msat) = parse_hrp(&hrp)?;
+    if data.len() < TIMESTAMP_GROUPS + SIGNATURE_GROUPS {
+        return Err(BitcoinParserError::Truncated(
+            "Invoice is too short for a timestamp and signature".to_string(),
+        ));
+    }
+    let (signed, signature) = data.split_at(data.len() - SIGNATURE_GROUPS);
+    let timestamp = bech32::to_u64(&signed[..TIMESTAMP_GROUPS])?;
+
+    let mut payment_hash = None;
+    let mut payment_secret = None;
+    let mut description = None;
+    let mut description_hash = None;
+    let mut payee = None;
+    let mut expiry = None;
+    let mut min_final_cltv_expiry = None;
+    let mut fallbacks = Vec::new();
+    let mut route_hints = Vec::new();
+    let mut fields = &signed[TIMESTAMP_GROUPS..];
+    while !fields.is_empty() {
+        let [tag, high, low, rest @ ..] = fields else {
+            return Err(BitcoinParserError::Truncated(
+                "Tagged field header is cut off".to_string(),
+            ));
+        };
+        let len = (usize::from(*high) << 5) | usize::from(*low);
+        if rest.len() < len {
+            return Err(BitcoinParserError::Truncated(format!(
+                "Tagged field of type {tag} is cut off"
+            )));
+        }
+        let (value, remaining) = rest.split_at(len);
+        fields = remaining;
+
+        // Readers skip p, h, s and n fields of the wrong length rather than failing
+        match *tag {
+            TAG_PAYMENT_HASH if len == 52 => payment_hash = Some(hash(value)),
+            TAG_PAYMENT_SECRET if len == 52 => payment_secret = Some(hash(value)),
+            TAG_DESCRIPTION_HASH if len == 52 => description_hash = Some(hash(value)),
+            TAG_PAYEE if len == 53 => {
+                payee = Some(
+                    <[u8; 33]>::try_from(bech32::to_bytes(value, false))
+                        .map_err(|_| invalid("Invalid payee key".to_string()))?,
+                );
+            }
+            TAG_DESCRIPTION => {
+                let bytes = bech32::to_bytes(value, false);
+                description = Some(
+                    String::from_utf8(bytes)
+                        .map_err(|_| invalid("Description is not UTF-8".to_string()))?,
+                );
+            }
+            TAG_EXPIRY => expiry = Some(bech32::to_u64(value)?),
+            TAG_MIN_FINAL_CLTV_EXPIRY => min_final_cltv_expiry = Some(bech32::to_u64(value)?),
+            TAG_FALLBACK => {
+                if let [version, program @ ..] = value {
+                    fallbacks.push((*version, bech32::to_bytes(program, false)));
+                }
+            }
+            TAG_ROUTE_HINT => {
+                route_hints.push(bech32::to_bytes(value, false).len() / ROUTE_HINT_HOP_LEN);
+            }
+            _ => {}
+        }
+    }
+
+    let payment_hash =
+        payment_hash.ok_or_else(|| invalid("Invoice has no payment hash".to_string()))?;
+    if description.is_some() == description_hash.is_some() {
+        return Err(invalid(
+            "Invoice must have exactly one of a description and a description hash".to_string(),
+        ));
+    }
+    let recovered = recover_payee(&hrp, signed, signature)?;
+    if payee.is_some_and(|payee| payee != recovered) {
+        return Err(invalid(
+            "Invoice signature does not match its payee node".to_string(),
+        ));
+    }
+
+    Ok(Bolt11Invoice {
+        network,
+        amount_msat,
+        timestamp,
+        payment_hash,
+        payment_secret,
+        description,
+        description_hash,
+        payee: recovered,
+        expiry: expiry.unwrap_or(DEFAULT_EXPIRY),
+        min_final_cltv_expiry: min_final_cltv_expiry.unwrap_or(DEFAULT_MIN_FINAL_CLTV_EXPIRY),
+        fallbacks,
+        route_hints,
+    })
+}
+
+fn hash(groups: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bech32::to_bytes(groups, false)[..32]);
+    hash
+}
+
+// The human-readable part is `ln` + currency + optional amount and multiplier
+fn parse_hrp(hrp: &str) -> Result<(&'static str, Option<u64>), BitcoinParserError> {
+    let rest = hrp
+        .strip_prefix("ln")
+        .ok_or_else(|| invalid(format!("{hrp:?} is not a Lightning invoice prefix")))?;
+    // Longer prefixes first, since `bc` starts `bcrt` and `tb` starts `tbs`
+    let (network, amount) = [
+        ("bcrt", "Bitcoin Regtest"),
+        ("tbs", "Bitcoin Signet"),
+        ("bc", "Bitcoin"),
+        ("tb", "Bitcoin Testnet"),
+    ]
+    .into_iter()
+    .find_map(|(prefix, network)| Some((network, rest.strip_prefix(prefix)?)))
+    .ok_or_else(|| invalid(format!("Unknown invoice currency in {hrp:?}")))?;
+    if amount.is_empty() {
+        return Ok((network, None));
+    }
+
+    let (digits, multiplier) = match amount.char_indices().last() {
+        Some((index, multiplier)) if !multiplier.is_ascii_digit() => {
+            (&amount[..index], Some(multiplier))
+        }
+        _ => (amount, None),
+    };
+    let overflow = || invalid(format!("Invoice amount {amount} is too large"));
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid(format!("Invalid invoice amount {amount:?}")));
+    }
+    let value: u64 = digits.parse().map_err(|_| overflow())?;
+    // Amounts are in bitcoin, scaled by the multiplier; one bitcoin is 10^11 millisatoshi
+    let amount_msat = match multiplier {
+        None => value.checked_mul(100_000_000_000),
+        Some('m') => value.checked_mul(100_000_000),
+        Some('u') => value.checked_mul(100_000),
+        Some('n') => value.checked_mul(100),
+        Some('p') if value % 10 == 0 => Some(value / 10),
+        Some('p') => {
+            return Err(invalid(format!(
+                "Invoice amount {amount} is not a whole millisatoshi"
+            )));
+        }
+        Some(other) => return Err(invalid(format!("Unknown amount multiplier {other:?}"))),
+    }
+    .ok_or_else(overflow)?;
+    Ok((network, Some(amount_msat)))
+}
+
+// The signature covers SHA-256 of the human-readable part and the data up to the signature
+fn recover_payee(
+    hrp: &str,
+    signed: &[u8],
+    signature: &[u8],
+) -> Result<[u8; 33], BitcoinParserError> {
+    let mut message = hrp.as_bytes().to_vec();
+    message.extend(bech32::to_bytes(signed, true));
+    let digest = Sha256::digest(&message);
+
+    let signature = bech32::to_bytes(signature, false);
+    let (signature, recovery_id) = signature.split_at(64);
+    let bad_signature = || invalid("Invalid invoice signature".to_string());
+    let signature = Signature::from_slice(signature).map_err(|_| bad_signature())?;
+    let recovery_id = RecoveryId::from_byte(recovery_id[0]).ok_or_else(bad_signature)?;
+    let key = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+        .map_err(|_| bad_signature())?;
+    <[u8; 33]>::try_from(key.to_encoded_point(true).as_bytes()).map_err(|_| bad_signature())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::lightning::bech32::tests::{encode, from_bytes};
+    use k256::ecdsa::SigningKey;
+
+    pub(crate) const PAYMENT_HASH: [u8; 32] = [0x01; 32];
+
+    fn tagged(tag: u8, groups: &[u8]) -> Vec<u8> {
+        let len = groups.len();
+        let mut field = vec![tag, (len >> 5) as u8, (len & 0x1f) as u8];
+        field.extend(groups);
+        field
+    }
+
+    fn int_groups(mut value: u64) -> Vec<u8> {
+        let mut groups = Vec::new();
+        while value > 0 {
+            groups.insert(0, (value & 0x1f) as u8);
+            value >>= 5;
+        }
+        groups
+    }
+
+    pub(crate) fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&[0x42; 32]).unwrap()
+    }
+
+    /// Builds and signs an invoice from its human-readable part and tagged fields.
+    pub(crate) fn sign_invoice(hrp: &str, timestamp: u64, fields: &[Vec<u8>]) -> String {
+        let mut data = int_groups(timestamp);
+        while data.len() < TIMESTAMP_GROUPS {
+            data.insert(0, 0);
+        }
+        for field in fields {
+            data.extend(field);
+        }
+        let mut message = hrp.as_bytes().to_vec();
+        message.extend(bech32::to_bytes(&data, true));
+        let (signature, recovery_id) = signing_key()
+            .sign_prehash_recoverable(&Sha256::digest(&message))
+            .unwrap();
+        let mut signature = signature.to_bytes().to_vec();
+        signature.push(recovery_id.to_byte());
+        data.extend(from_bytes(&signature));
+        encode(hrp, &data, true)
+    }
+
+    pub(crate) fn example_invoice() -> String {
+        sign_invoice(
+            "lnbc2500u",
+            1_700_000_000,
+            &[
+                tagged(TAG_PAYMENT_HASH, &from_bytes(&PAYMENT_HASH)),
+                tagged(TAG_PAYMENT_SECRET, &from_bytes(&[0x02; 32])),
+                tagged(TAG_DESCRIPTION, &from_bytes(b"1 cup coffee")),
+                tagged(TAG_EXPIRY, &int_groups(60)),
+            ],
+        )
+    }
+
+    fn payee() -> [u8; 33] {
+        signing_key()
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_invoice() {
+        let invoice = parse_invoice(&example_invoice()).unwrap();
+        assert_eq!(invoice.network, "Bitcoin");
+        assert_eq!(invoice.amount_msat, Some(250_000_000));
+        assert_eq!(invoice.timestamp, 1_700_000_000);
+        assert_eq!(invoice.payment_hash, PAYMENT_HASH);
+        assert_eq!(invoice.description.as_deref(), Some("1 cup coffee"));
+        assert_eq!(invoice.expiry, 60);
+        assert_eq!(invoice.min_final_cltv_expiry, DEFAULT_MIN_FINAL_CLTV_EXPIRY);
+        assert_eq!(invoice.payee, payee());
+
+        // An explicit payee has to match the signature
+        let with_payee = sign_invoice(
+            "lntb",
+            1,
+            &[
+                tagged(TAG_PAYMENT_HASH, &from_bytes(&PAYMENT_HASH)),
+                tagged(TAG_DESCRIPTION_HASH, &from_bytes(&[0x03; 32])),
+                tagged(TAG_PAYEE, &from_bytes(&payee())),
+            ],
+        );
+        let invoice = parse_invoice(&with_payee).unwrap();
+        assert_eq!(invoice.network, "Bitcoin Testnet");
+        assert_eq!(invoice.amount_msat, None);
+        assert_eq!(invoice.description_hash, Some([0x03; 32]));
+        let mut other_payee = payee();
+        other_payee[1] ^= 1;
+        let forged = sign_invoice(
+            "lntb",
+            1,
+            &[
+                tagged(TAG_PAYMENT_HASH, &from_bytes(&PAYMENT_HASH)),
+                tagged(TAG_DESCRIPTION_HASH, &from_bytes(&[0x03; 32])),
+                tagged(TAG_PAYEE, &from_bytes(&other_payee)),
+            ],
+        );
+        assert!(
+            parse_invoice(&forged)
+                .unwrap_err()
+                .to_string()
+                .contains("does not match")
+        );
+    }
+
+    #[test]
+    fn test_amounts_and_invalid_invoices() {
+        assert_eq!(parse_hrp("lnbc").unwrap(), ("Bitcoin", None));
+        assert_eq!(
+            parse_hrp("lnbcrt1m").unwrap(),
+            ("Bitcoin Regtest", Some(100_000_000))
+        );
+        assert_eq!(
+            parse_hrp("lntbs20n").unwrap(),
+            ("Bitcoin Signet", Some(2_000))
+        );
+        assert_eq!(parse_hrp("lnbc10p").unwrap().1, Some(1));
+        assert!(parse_hrp("lnbc11p").is_err());
+        assert!(parse_hrp("lnbc1x").is_err());
+        assert!(parse_hrp("lnxyz").is_err());
+
+        // Neither a description nor a description hash
+        let no_description = sign_invoice(
+            "lnbc",
+            1,
+            &[tagged(TAG_PAYMENT_HASH, &from_bytes(&PAYMENT_HASH))],
+        );
+        assert!(parse_invoice(&no_description).is_err());
+
+        let mut corrupted = example_invoice();
+        corrupted.replace_range(20..21, if &corrupted[20..21] == "q" { "p" } else { "q" });
+        assert!(
+            parse_invoice(&corrupted)
+                .unwrap_err()
+                .to_string()
+                .contains("checksum")
+        );
+    }
+}
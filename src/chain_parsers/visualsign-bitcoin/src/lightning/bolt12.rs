@@ -0,0 +1,357 @@
+//! BOLT12 offers (`lno1...`) and invoices (`lni1...`).
+//!
+//! Both are a TLV stream, bech32 encoded without a checksum and optionally split with `+`
+//! and whitespace. An offer is a reusable request for payment; the invoice answering an
+//! invoice request repeats the offer's fields and adds the amount, payment hash and node to
+//! pay. Signatures are over a merkle tree of the TLV records and are not checked here.
+
+use super::bech32;
+use crate::BitcoinParserError;
+
+/// Chain hash of Bitcoin mainnet, the chain when an offer lists none
+pub const BITCOIN_CHAIN_HASH: [u8; 32] = [
+    0x6f, 0xe2, 0x8c, 0x0a, 0xb6, 0xf1, 0xb3, 0x72, 0xc1, 0xa6, 0xa2, 0x46, 0xae, 0x63, 0xf7, 0x4f,
+    0x93, 0x1e, 0x83, 0x65, 0xe1, 0x5a, 0x08, 0x9c, 0x68, 0xd6, 0x19, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// Seconds until an invoice expires when it has no relative expiry
+pub const DEFAULT_RELATIVE_EXPIRY: u64 = 7200;
+
+// TLV types
+const OFFER_CHAINS: u64 = 2;
+const OFFER_METADATA: u64 = 4;
+const OFFER_CURRENCY: u64 = 6;
+const OFFER_AMOUNT: u64 = 8;
+const OFFER_DESCRIPTION: u64 = 10;
+const OFFER_FEATURES: u64 = 12;
+const OFFER_ABSOLUTE_EXPIRY: u64 = 14;
+const OFFER_PATHS: u64 = 16;
+const OFFER_ISSUER: u64 = 18;
+const OFFER_QUANTITY_MAX: u64 = 20;
+const OFFER_ISSUER_ID: u64 = 22;
+const INVREQ_METADATA: u64 = 0;
+const INVREQ_CHAIN: u64 = 80;
+const INVREQ_AMOUNT: u64 = 82;
+const INVREQ_FEATURES: u64 = 84;
+const INVREQ_QUANTITY: u64 = 86;
+const INVREQ_PAYER_ID: u64 = 88;
+const INVREQ_PAYER_NOTE: u64 = 89;
+const INVOICE_PATHS: u64 = 160;
+const INVOICE_BLINDEDPAY: u64 = 162;
+const INVOICE_CREATED_AT: u64 = 164;
+const INVOICE_RELATIVE_EXPIRY: u64 = 166;
+const INVOICE_PAYMENT_HASH: u64 = 168;
+const INVOICE_AMOUNT: u64 = 170;
+const INVOICE_FALLBACKS: u64 = 172;
+const INVOICE_FEATURES: u64 = 174;
+const INVOICE_NODE_ID: u64 = 176;
+const SIGNATURE: u64 = 240;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bolt12Kind {
+    Offer,
+    Invoice,
+}
+
+impl Bolt12Kind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Bolt12Kind::Offer => "BOLT12 offer",
+            Bolt12Kind::Invoice => "BOLT12 invoice",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bolt12Message {
+    pub kind: Bolt12Kind,
+    pub chains: Vec<[u8; 32]>,
+    /// ISO 4217 code the offer amount is in; millisatoshi when unset
+    pub currency: Option<String>,
+    pub offer_amount: Option<u64>,
+    pub description: Option<String>,
+    pub issuer: Option<String>,
+    /// Seconds since the Unix epoch after which the offer is void
+    pub absolute_expiry: Option<u64>,
+    /// Most items one payment may buy; 0 for no limit
+    pub quantity_max: Option<u64>,
+    pub issuer_id: Option<[u8; 33]>,
+    pub has_paths: bool,
+    pub quantity: Option<u64>,
+    pub payer_note: Option<String>,
+    pub created_at: Option<u64>,
+    pub relative_expiry: Option<u64>,
+    pub payment_hash: Option<[u8; 32]>,
+    pub invoice_amount_msat: Option<u64>,
+    pub node_id: Option<[u8; 33]>,
+}
+
+fn invalid(message: String) -> BitcoinParserError {
+    BitcoinParserError::InvalidInvoice(message)
+}
+
+/// Parses a BOLT12 offer or invoice string.
+pub fn parse(text: &str) -> Result<Bolt12Message, BitcoinParserError> {
+    // `+` joins chunks of a long string, and may be followed by whitespace
+    let text: String = text.split('+').map(str::trim).collect::<Vec<_>>().concat();
+    let (hrp, groups) = bech32::decode(&text, false)?;
+    let kind = match hrp.as_str() {
+        "lno" => Bolt12Kind::Offer,
+        "lni" => Bolt12Kind::Invoice,
+        other => {
+            return Err(BitcoinParserError::UnsupportedVersion(format!(
+                "BOLT12 string with prefix {other:?}"
+            )));
+        }
+    };
+    let bytes = bech32::to_bytes(&groups, false);
+
+    let mut message = Bolt12Message {
+        kind,
+        chains: Vec::new(),
+        currency: None,
+        offer_amount: None,
+        description: None,
+        issuer: None,
+        absolute_expiry: None,
+        quantity_max: None,
+        issuer_id: None,
+        has_paths: false,
+        quantity: None,
+        payer_note: None,
+        created_at: None,
+        relative_expiry: None,
+        payment_hash: None,
+        invoice_amount_msat: None,
+        node_id: None,
+    };
+    let mut reader = TlvReader {
+        data: &bytes,
+        pos: 0,
+    };
+    let mut last_type = None;
+    while !reader.is_empty() {
+        let record_type = reader.big_size()?;
+        if last_type.is_some_and(|last| record_type <= last) {
+            return Err(invalid(format!("TLV type {record_type} is out of order")));
+        }
+        last_type = Some(record_type);
+        let len = usize::try_from(reader.big_size()?)
+            .map_err(|_| invalid("TLV length is too large".to_string()))?;
+        let value = reader.bytes(len)?;
+
+        match record_type {
+            OFFER_CHAINS => {
+                if value.is_empty() || value.len() % 32 != 0 {
+                    return Err(invalid("Invalid offer chains".to_string()));
+                }
+                message.chains = value
+                    .chunks_exact(32)
+                    .map(|chunk| {
+                        let mut chain = [0u8; 32];
+                        chain.copy_from_slice(chunk);
+                        chain
+                    })
+                    .collect();
+            }
+            OFFER_CURRENCY => message.currency = Some(utf8(value, "currency")?),
+            OFFER_AMOUNT => message.offer_amount = Some(truncated_u64(value)?),
+            OFFER_DESCRIPTION => message.description = Some(utf8(value, "description")?),
+            OFFER_ABSOLUTE_EXPIRY => message.absolute_expiry = Some(truncated_u64(value)?),
+            OFFER_PATHS => message.has_paths = !value.is_empty(),
+            OFFER_ISSUER => message.issuer = Some(utf8(value, "issuer")?),
+            OFFER_QUANTITY_MAX => message.quantity_max = Some(truncated_u64(value)?),
+            OFFER_ISSUER_ID => message.issuer_id = Some(point(value)?),
+            INVREQ_QUANTITY => message.quantity = Some(truncated_u64(value)?),
+            INVREQ_PAYER_NOTE => message.payer_note = Some(utf8(value, "payer note")?),
+            INVOICE_CREATED_AT => message.created_at = Some(truncated_u64(value)?),
+            INVOICE_RELATIVE_EXPIRY => message.relative_expiry = Some(truncated_u64(value)?),
+            INVOICE_PAYMENT_HASH => {
+                message.payment_hash = Some(
+                    <[u8; 32]>::try_from(value)
+                        .map_err(|_| invalid("Invalid payment hash".to_string()))?,
+                );
+            }
+            INVOICE_AMOUNT => message.invoice_amount_msat = Some(truncated_u64(value)?),
+            INVOICE_NODE_ID => message.node_id = Some(point(value)?),
+            // Known records this view does not show
+            OFFER_METADATA | OFFER_FEATURES | INVREQ_METADATA | INVREQ_CHAIN | INVREQ_AMOUNT
+            | INVREQ_FEATURES | INVREQ_PAYER_ID | INVOICE_PATHS | INVOICE_BLINDEDPAY
+            | INVOICE_FALLBACKS | INVOICE_FEATURES | SIGNATURE => {}
+            // An unknown even type is one the reader is required to understand
+            unknown if unknown % 2 == 0 => {
+                return Err(BitcoinParserError::UnsupportedVersion(format!(
+                    "Unknown required TLV type {unknown}"
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    match kind {
+        Bolt12Kind::Offer => {
+            if message.offer_amount.is_some() && message.description.is_none() {
+                return Err(invalid(
+                    "Offer with an amount has no description".to_string(),
+                ));
+            }
+            if message.currency.is_some() && message.offer_amount.is_none() {
+                return Err(invalid("Offer with a currency has no amount".to_string()));
+            }
+            if message.issuer_id.is_none() && !message.has_paths {
+                return Err(invalid(
+                    "Offer has neither an issuer id nor blinded paths".to_string(),
+                ));
+            }
+        }
+        Bolt12Kind::Invoice => {
+            let missing = |name: &str| invalid(format!("Invoice has no {name}"));
+            if message.created_at.is_none() {
+                return Err(missing("creation time"));
+            }
+            if message.payment_hash.is_none() {
+                return Err(missing("payment hash"));
+            }
+            if message.invoice_amount_msat.is_none() {
+                return Err(missing("amount"));
+            }
+            if message.node_id.is_none() {
+                return Err(missing("node id"));
+            }
+        }
+    }
+    Ok(message)
+}
+
+struct TlvReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TlvReader<'a> {
+    fn is_empty(&self) -> bool {
+        self.pos == self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BitcoinParserError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| {
+                BitcoinParserError::Truncated(format!("needed {len} bytes at offset {}", self.pos))
+            })?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    // BigSize: like Bitcoin's CompactSize, but big-endian and minimally encoded
+    fn big_size(&mut self) -> Result<u64, BitcoinParserError> {
+        let (value, minimum) = match self.bytes(1)?[0] {
+            0xfd => (be_u64(self.bytes(2)?), 0xfd),
+            0xfe => (be_u64(self.bytes(4)?), 0x1_0000),
+            0xff => (be_u64(self.bytes(8)?), 0x1_0000_0000),
+            small => return Ok(u64::from(small)),
+        };
+        if value < minimum {
+            return Err(invalid("BigSize is not minimally encoded".to_string()));
+        }
+        Ok(value)
+    }
+}
+
+fn be_u64(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | u64::from(*byte))
+}
+
+// A big-endian integer of up to 8 bytes without leading zeros
+fn truncated_u64(value: &[u8]) -> Result<u64, BitcoinParserError> {
+    if value.len() > 8 || value.first() == Some(&0) {
+        return Err(invalid("Integer is not minimally encoded".to_string()));
+    }
+    Ok(be_u64(value))
+}
+
+fn utf8(value: &[u8], name: &str) -> Result<String, BitcoinParserError> {
+    String::from_utf8(value.to_vec()).map_err(|_| invalid(format!("The {name} is not UTF-8")))
+}
+
+fn point(value: &[u8]) -> Result<[u8; 33], BitcoinParserError> {
+    <[u8; 33]>::try_from(value)
+        .ok()
+        .filter(|point| matches!(point[0], 0x02 | 0x03))
+        .ok_or_else(|| invalid("Invalid node key".to_string()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::lightning::bech32::tests::{encode, from_bytes};
+
+    pub(crate) const ISSUER_ID: [u8; 33] = [0x02; 33];
+
+    pub(crate) fn record(out: &mut Vec<u8>, record_type: u8, value: &[u8]) {
+        out.push(record_type);
+        out.push(u8::try_from(value.len()).unwrap());
+        out.extend(value);
+    }
+
+    pub(crate) fn offer_string(records: &[u8]) -> String {
+        encode("lno", &from_bytes(records), false)
+    }
+
+    pub(crate) fn example_offer() -> String {
+        let mut records = Vec::new();
+        record(&mut records, 8, &[0x27, 0x10]);
+        record(&mut records, 10, b"Coffee subscription");
+        record(&mut records, 18, b"Example Cafe");
+        record(&mut records, 22, &ISSUER_ID);
+        offer_string(&records)
+    }
+
+    #[test]
+    fn test_parse_offer() {
+        let offer = parse(&example_offer()).unwrap();
+        assert_eq!(offer.kind, Bolt12Kind::Offer);
+        assert_eq!(offer.offer_amount, Some(10_000));
+        assert_eq!(offer.currency, None);
+        assert_eq!(offer.description.as_deref(), Some("Coffee subscription"));
+        assert_eq!(offer.issuer.as_deref(), Some("Example Cafe"));
+        assert_eq!(offer.issuer_id, Some(ISSUER_ID));
+
+        // Split with `+` and whitespace, as long offers are printed
+        let text = example_offer();
+        let split = format!("{}+\n  {}", &text[..20], &text[20..]);
+        assert_eq!(parse(&split).unwrap(), offer);
+    }
+
+    #[test]
+    fn test_invalid_offers() {
+        let error = |records: &[u8]| parse(&offer_string(records)).unwrap_err().to_string();
+
+        let mut out_of_order = Vec::new();
+        record(&mut out_of_order, 22, &ISSUER_ID);
+        record(&mut out_of_order, 10, b"x");
+        assert!(error(&out_of_order).contains("out of order"));
+
+        let mut unknown_even = Vec::new();
+        record(&mut unknown_even, 22, &ISSUER_ID);
+        record(&mut unknown_even, 24, &[1]);
+        assert!(error(&unknown_even).contains("Unknown required TLV type 24"));
+
+        let mut padded_amount = Vec::new();
+        record(&mut padded_amount, 8, &[0x00, 0x10]);
+        record(&mut padded_amount, 10, b"x");
+        record(&mut padded_amount, 22, &ISSUER_ID);
+        assert!(error(&padded_amount).contains("minimally encoded"));
+
+        let mut no_issuer = Vec::new();
+        record(&mut no_issuer, 10, b"x");
+        assert!(error(&no_issuer).contains("neither an issuer id"));
+
+        assert!(parse("lnr1qqqq").is_err());
+    }
+}
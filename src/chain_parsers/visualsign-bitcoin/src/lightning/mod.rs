@@ -0,0 +1,383 @@
+//! Lightning payment requests: BOLT11 invoices and BOLT12 offers and invoices.
+//!
+//! Approving a payment request commits the signer's node to pay it, so the payload shows
+//! what the request asks for: the amount, what it is for (the description, or only its hash
+//! when the description lives elsewhere), when it expires and which node is paid.
+
+use visualsign::{
+    SignablePayload, SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2,
+    capabilities::ParserCapabilities,
+    fixed_point::format_fixed_point,
+    parser_info::ParserInfo,
+    sender::{create_sender_field, resolve_sender},
+    vsptrait::{
+        Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
+        VisualSignError, VisualSignOptions,
+    },
+};
+
+use crate::BitcoinParserError;
+
+pub mod bech32;
+pub mod bolt11;
+pub mod bolt12;
+
+use bolt11::Bolt11Invoice;
+use bolt12::{BITCOIN_CHAIN_HASH, Bolt12Kind, Bolt12Message, DEFAULT_RELATIVE_EXPIRY};
+
+/// Registry name of the Lightning converter, which has no chain of its own in the proto enum
+pub const CHAIN_NAME: &str = "Lightning";
+
+/// Millisatoshi in one bitcoin is 10^11
+const MSAT_DECIMALS: u8 = 11;
+
+/// A Lightning payment request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LightningInvoice {
+    Bolt11(Bolt11Invoice),
+    Bolt12(Bolt12Message),
+}
+
+impl Transaction for LightningInvoice {
+    fn from_string(data: &str) -> Result<Self, TransactionParseError> {
+        let data = data.trim();
+        // Payment links carry the request after a `lightning:` scheme
+        let data = match data.get(..10) {
+            Some(scheme) if scheme.eq_ignore_ascii_case("lightning:") => &data[10..],
+            _ => data,
+        };
+        let lowercase = data.to_ascii_lowercase();
+        if lowercase.starts_with("lno1") || lowercase.starts_with("lni1") {
+            Ok(Self::Bolt12(bolt12::parse(data)?))
+        } else if lowercase.starts_with("ln") {
+            Ok(Self::Bolt11(bolt11::parse_invoice(data)?))
+        } else {
+            Err(
+                BitcoinParserError::InvalidInvoice("Not a Lightning invoice or offer".to_string())
+                    .into(),
+            )
+        }
+    }
+
+    fn transaction_type(&self) -> String {
+        CHAIN_NAME.to_string()
+    }
+}
+
+/// Converter for Lightning invoices and offers
+#[derive(Debug, Default)]
+pub struct LightningInvoiceConverter;
+
+impl VisualSignConverter<LightningInvoice> for LightningInvoiceConverter {
+    fn to_visual_sign_payload(
+        &self,
+        invoice: LightningInvoice,
+        options: VisualSignOptions,
+    ) -> Result<SignablePayload, VisualSignError> {
+        let (mut fields, default_title, payload_type) = match &invoice {
+            LightningInvoice::Bolt11(invoice) => (
+                bolt11_fields(invoice),
+                "Lightning Invoice",
+                "LightningInvoice",
+            ),
+            LightningInvoice::Bolt12(message) if message.kind == Bolt12Kind::Offer => {
+                (bolt12_fields(message), "Lightning Offer", "LightningOffer")
+            }
+            LightningInvoice::Bolt12(message) => (
+                bolt12_fields(message),
+                "Lightning Invoice",
+                "LightningInvoice",
+            ),
+        };
+
+        // The paying node is not part of the request
+        if let Some(sender) =
+            resolve_sender(None, options.sender.as_deref(), |derived, provided| {
+                derived == provided
+            })?
+        {
+            fields.insert(2, create_sender_field(&sender));
+        }
+
+        let title = options
+            .transaction_name
+            .unwrap_or_else(|| default_title.to_string());
+        Ok(SignablePayload::new(
+            0,
+            title,
+            None,
+            fields,
+            payload_type.to_string(),
+        ))
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        ParserCapabilities::new(
+            &["bech32"],
+            &[
+                "BOLT11 invoice",
+                Bolt12Kind::Offer.name(),
+                Bolt12Kind::Invoice.name(),
+            ],
+        )
+        .with_feature("bolt11", true)
+        .with_feature("bolt12", true)
+        // BOLT11 payees are recovered from the signature; BOLT12 signatures are not checked
+        .with_feature("bolt12_signature_verification", false)
+    }
+}
+
+impl VisualSignConverterFromString<LightningInvoice> for LightningInvoiceConverter {}
+
+fn bolt11_fields(invoice: &Bolt11Invoice) -> Vec<SignablePayloadField> {
+    let mut fields = vec![
+        text_field("Network", invoice.network),
+        text_field("Invoice Type", "BOLT11 invoice"),
+        text_field("Amount", &amount_text(invoice.amount_msat)),
+    ];
+    match (&invoice.description, &invoice.description_hash) {
+        (Some(description), _) => {
+            fields.push(text_field("Description", &display_text(description)))
+        }
+        (None, Some(hash)) => fields.push(text_field(
+            "Description Hash",
+            &format!(
+                "{} (the description is not in the invoice)",
+                hex::encode(hash)
+            ),
+        )),
+        (None, None) => {}
+    }
+    fields.push(text_field("Destination Node", &hex::encode(invoice.payee)));
+    fields.push(text_field(
+        "Payment Hash",
+        &hex::encode(invoice.payment_hash),
+    ));
+    fields.push(text_field(
+        "Created",
+        &format!("Unix time {}", invoice.timestamp),
+    ));
+    fields.push(text_field(
+        "Expiry",
+        &relative_expiry_text(invoice.timestamp, invoice.expiry),
+    ));
+    fields.push(text_field(
+        "Min Final CLTV Expiry",
+        &format!("{} blocks", invoice.min_final_cltv_expiry),
+    ));
+    for (version, program) in &invoice.fallbacks {
+        let fallback = match version {
+            17 => format!("P2PKH hash {}", hex::encode(program)),
+            18 => format!("P2SH hash {}", hex::encode(program)),
+            version => format!("Witness v{version} program {}", hex::encode(program)),
+        };
+        fields.push(text_field("On-chain Fallback", &fallback));
+    }
+    if !invoice.route_hints.is_empty() {
+        fields.push(text_field(
+            "Route Hints",
+            &format!("{} private routes", invoice.route_hints.len()),
+        ));
+    }
+    fields
+}
+
+fn bolt12_fields(message: &Bolt12Message) -> Vec<SignablePayloadField> {
+    let network = if message.chains.is_empty() {
+        "Bitcoin".to_string()
+    } else {
+        message
+            .chains
+            .iter()
+            .map(|chain| {
+                if *chain == BITCOIN_CHAIN_HASH {
+                    "Bitcoin".to_string()
+                } else {
+                    format!("Chain {}", hex::encode(chain))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let amount = match (message.kind, &message.currency) {
+        (Bolt12Kind::Invoice, _) => amount_text(message.invoice_amount_msat),
+        (Bolt12Kind::Offer, Some(currency)) => match message.offer_amount {
+            Some(amount) => format!(
+                "{amount} {} (in the currency's smallest unit)",
+                display_text(currency)
+            ),
+            None => amount_text(None),
+        },
+        (Bolt12Kind::Offer, None) => amount_text(message.offer_amount),
+    };
+
+    let mut fields = vec![
+        text_field("Network", &network),
+        text_field("Invoice Type", message.kind.name()),
+        text_field("Amount", &amount),
+    ];
+    if let Some(quantity) = message.quantity {
+        fields.push(text_field("Quantity", &quantity.to_string()));
+    }
+    if let Some(quantity_max) = message.quantity_max {
+        let quantity_max = match quantity_max {
+            0 => "Unlimited".to_string(),
+            max => max.to_string(),
+        };
+        fields.push(text_field("Max Quantity", &quantity_max));
+    }
+    if let Some(description) = &message.description {
+        fields.push(text_field("Description", &display_text(description)));
+    }
+    if let Some(issuer) = &message.issuer {
+        fields.push(text_field("Issuer", &display_text(issuer)));
+    }
+    if let Some(payer_note) = &message.payer_note {
+        fields.push(text_field("Payer Note", &display_text(payer_note)));
+    }
+    let destination = match (message.node_id, message.issuer_id) {
+        (Some(node), _) | (None, Some(node)) => hex::encode(node),
+        (None, None) => "Hidden behind blinded paths".to_string(),
+    };
+    fields.push(text_field("Destination Node", &destination));
+    if let Some(payment_hash) = message.payment_hash {
+        fields.push(text_field("Payment Hash", &hex::encode(payment_hash)));
+    }
+    match (message.created_at, message.absolute_expiry) {
+        (Some(created_at), _) => {
+            fields.push(text_field("Created", &format!("Unix time {created_at}")));
+            fields.push(text_field(
+                "Expiry",
+                &relative_expiry_text(
+                    created_at,
+                    message.relative_expiry.unwrap_or(DEFAULT_RELATIVE_EXPIRY),
+                ),
+            ));
+        }
+        (None, Some(expiry)) => fields.push(text_field("Expiry", &format!("Unix time {expiry}"))),
+        (None, None) => fields.push(text_field("Expiry", "Never")),
+    }
+    fields
+}
+
+fn amount_text(amount_msat: Option<u64>) -> String {
+    match amount_msat {
+        Some(msat) => format!(
+            "{} BTC",
+            format_fixed_point(u128::from(msat), MSAT_DECIMALS)
+        ),
+        None => "Any amount (chosen by the payer)".to_string(),
+    }
+}
+
+fn relative_expiry_text(created_at: u64, expiry: u64) -> String {
+    format!(
+        "{expiry} seconds (until Unix time {})",
+        created_at.saturating_add(expiry)
+    )
+}
+
+// Helper function to keep requester-chosen text printable, showing anything else as hex
+fn display_text(text: &str) -> String {
+    if text.chars().all(|ch| ch.is_ascii_graphic() || ch == ' ') {
+        text.to_string()
+    } else {
+        format!("0x{}", hex::encode(text))
+    }
+}
+
+fn text_field(label: &str, text: &str) -> SignablePayloadField {
+    SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: text.to_string(),
+            label: label.to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 {
+            text: text.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labelled(payload: &SignablePayload) -> Vec<String> {
+        payload
+            .fields
+            .iter()
+            .map(|field| format!("{}: {}", field.label(), field.fallback_text()))
+            .collect()
+    }
+
+    #[test]
+    fn test_bolt11_payload() {
+        let invoice = format!(
+            "LIGHTNING:{}",
+            bolt11::tests::example_invoice().to_uppercase()
+        );
+        let payload = LightningInvoiceConverter
+            .to_visual_sign_payload_from_string(&invoice, VisualSignOptions::default())
+            .unwrap();
+        assert_eq!(payload.title, "Lightning Invoice");
+        let payee = hex::encode(
+            bolt11::tests::signing_key()
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes(),
+        );
+        assert_eq!(
+            labelled(&payload),
+            vec![
+                "Network: Bitcoin".to_string(),
+                "Invoice Type: BOLT11 invoice".to_string(),
+                "Amount: 0.0025 BTC".to_string(),
+                "Description: 1 cup coffee".to_string(),
+                format!("Destination Node: {payee}"),
+                format!("Payment Hash: {}", "01".repeat(32)),
+                "Created: Unix time 1700000000".to_string(),
+                "Expiry: 60 seconds (until Unix time 1700000060)".to_string(),
+                "Min Final CLTV Expiry: 18 blocks".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bolt12_offer_payload() {
+        let options = VisualSignOptions {
+            sender: Some("03aa".to_string()),
+            ..VisualSignOptions::default()
+        };
+        let payload = LightningInvoiceConverter
+            .to_visual_sign_payload_from_string(&bolt12::tests::example_offer(), options)
+            .unwrap();
+        assert_eq!(payload.title, "Lightning Offer");
+        assert_eq!(payload.payload_type, "LightningOffer");
+        let labels: Vec<&str> = payload
+            .fields
+            .iter()
+            .map(|field| field.label().as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Network",
+                "Invoice Type",
+                "From",
+                "Amount",
+                "Description",
+                "Issuer",
+                "Destination Node",
+                "Expiry",
+            ]
+        );
+        assert_eq!(payload.fields[3].fallback_text(), "0.0000001 BTC");
+        assert_eq!(payload.fields[7].fallback_text(), "Never");
+
+        assert!(LightningInvoice::from_string("bc1qexample").is_err());
+    }
+}
@@ -0,0 +1,595 @@
+//! Partially signed Bitcoin transactions, versions 0 (BIP-174) and 2 (BIP-370).
+//!
+//! A PSBT is the magic `psbt\xff` followed by a global map, one map per input and one per
+//! output; each map is a list of `<key length><key type><key data><value length><value>` pairs
+//! ended by a zero byte. Version 0 carries the unsigned transaction in the global map, while
+//! version 2 spreads the same data over the per-input and per-output maps. Both decode to
+//! [`Psbt`], keeping the Taproot fields (BIP-371) that decide how each input is spent.
+
+use std::collections::HashSet;
+
+use crate::BitcoinParserError;
+
+pub const PSBT_MAGIC: &[u8; 5] = b"psbt\xff";
+
+// Global key types
+const PSBT_GLOBAL_UNSIGNED_TX: u64 = 0x00;
+const PSBT_GLOBAL_TX_VERSION: u64 = 0x02;
+const PSBT_GLOBAL_FALLBACK_LOCKTIME: u64 = 0x03;
+const PSBT_GLOBAL_INPUT_COUNT: u64 = 0x04;
+const PSBT_GLOBAL_OUTPUT_COUNT: u64 = 0x05;
+const PSBT_GLOBAL_VERSION: u64 = 0xfb;
+
+// Input key types
+const PSBT_IN_NON_WITNESS_UTXO: u64 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u64 = 0x01;
+const PSBT_IN_SIGHASH_TYPE: u64 = 0x03;
+const PSBT_IN_FINAL_SCRIPTWITNESS: u64 = 0x08;
+const PSBT_IN_PREVIOUS_TXID: u64 = 0x0e;
+const PSBT_IN_OUTPUT_INDEX: u64 = 0x0f;
+const PSBT_IN_SEQUENCE: u64 = 0x10;
+const PSBT_IN_REQUIRED_TIME_LOCKTIME: u64 = 0x11;
+const PSBT_IN_REQUIRED_HEIGHT_LOCKTIME: u64 = 0x12;
+const PSBT_IN_TAP_KEY_SIG: u64 = 0x13;
+const PSBT_IN_TAP_SCRIPT_SIG: u64 = 0x14;
+const PSBT_IN_TAP_LEAF_SCRIPT: u64 = 0x15;
+const PSBT_IN_TAP_INTERNAL_KEY: u64 = 0x17;
+const PSBT_IN_TAP_MERKLE_ROOT: u64 = 0x18;
+
+// Output key types
+const PSBT_OUT_AMOUNT: u64 = 0x03;
+const PSBT_OUT_SCRIPT: u64 = 0x04;
+const PSBT_OUT_TAP_INTERNAL_KEY: u64 = 0x05;
+const PSBT_OUT_TAP_TREE: u64 = 0x06;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    /// Txid in internal byte order; displayed reversed
+    pub previous_txid: [u8; 32],
+    pub output_index: u32,
+    pub sequence: u32,
+}
+
+/// A transaction in network serialization, as in `PSBT_GLOBAL_UNSIGNED_TX` and
+/// `PSBT_IN_NON_WITNESS_UTXO`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinTransaction {
+    pub version: i32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+}
+
+/// A leaf of the script tree a Taproot input may be spent through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TapLeafScript {
+    pub control_block: Vec<u8>,
+    pub script: Vec<u8>,
+    pub leaf_version: u8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PsbtInput {
+    pub previous_txid: [u8; 32],
+    pub output_index: u32,
+    pub sequence: Option<u32>,
+    pub non_witness_utxo: Option<BitcoinTransaction>,
+    pub witness_utxo: Option<TxOut>,
+    pub sighash_type: Option<u32>,
+    pub required_time_lock_time: Option<u32>,
+    pub required_height_lock_time: Option<u32>,
+    pub tap_key_sig: Option<Vec<u8>>,
+    /// Signatures for script-path spends, as (x-only key, leaf hash, signature)
+    pub tap_script_sigs: Vec<([u8; 32], [u8; 32], Vec<u8>)>,
+    pub tap_leaf_scripts: Vec<TapLeafScript>,
+    pub tap_internal_key: Option<[u8; 32]>,
+    pub tap_merkle_root: Option<[u8; 32]>,
+    pub final_script_witness: Option<Vec<Vec<u8>>>,
+}
+
+impl PsbtInput {
+    /// The output this input spends, when the PSBT includes it.
+    pub fn spent_output(&self) -> Option<TxOut> {
+        self.witness_utxo.clone().or_else(|| {
+            self.non_witness_utxo
+                .as_ref()?
+                .outputs
+                .get(usize::try_from(self.output_index).ok()?)
+                .cloned()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsbtOutput {
+    pub amount: u64,
+    pub script_pubkey: Vec<u8>,
+    pub tap_internal_key: Option<[u8; 32]>,
+    pub has_tap_tree: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Psbt {
+    /// PSBT format version, 0 or 2
+    pub version: u32,
+    pub tx_version: i32,
+    /// The transaction's lock time in version 0; the fallback lock time in version 2, used
+    /// when no input requires one
+    pub lock_time: u32,
+    pub inputs: Vec<PsbtInput>,
+    pub outputs: Vec<PsbtOutput>,
+}
+
+struct KeyPair {
+    key_type: u64,
+    key_data: Vec<u8>,
+    value: Vec<u8>,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BitcoinParserError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| {
+                BitcoinParserError::Truncated(format!("needed {len} bytes at offset {}", self.pos))
+            })?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], BitcoinParserError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, BitcoinParserError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, BitcoinParserError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, BitcoinParserError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn compact_size(&mut self) -> Result<u64, BitcoinParserError> {
+        Ok(match self.u8()? {
+            0xfd => u64::from(u16::from_le_bytes(self.array()?)),
+            0xfe => u64::from(self.u32()?),
+            0xff => self.u64()?,
+            small => u64::from(small),
+        })
+    }
+
+    fn var_bytes(&mut self) -> Result<&'a [u8], BitcoinParserError> {
+        let len = self.compact_size()?;
+        let len = usize::try_from(len)
+            .map_err(|_| BitcoinParserError::Truncated(format!("length {len} is too large")))?;
+        self.bytes(len)
+    }
+
+    // Reads one map up to its terminating zero byte, rejecting repeated keys
+    fn map(&mut self, name: &str) -> Result<Vec<KeyPair>, BitcoinParserError> {
+        let mut pairs = Vec::new();
+        let mut seen = HashSet::new();
+        loop {
+            let key = self.var_bytes()?;
+            if key.is_empty() {
+                return Ok(pairs);
+            }
+            if !seen.insert(key.to_vec()) {
+                return Err(invalid(format!(
+                    "Duplicate key {} in {name} map",
+                    hex::encode(key)
+                )));
+            }
+            let mut key_reader = Reader::new(key);
+            let key_type = key_reader.compact_size()?;
+            let key_data = key[key_reader.pos..].to_vec();
+            let value = self.var_bytes()?.to_vec();
+            pairs.push(KeyPair {
+                key_type,
+                key_data,
+                value,
+            });
+        }
+    }
+}
+
+fn invalid(message: String) -> BitcoinParserError {
+    BitcoinParserError::InvalidPsbt(message)
+}
+
+// Reads a whole value with `read`, rejecting bytes left over
+fn parse_value<T>(
+    pair: &KeyPair,
+    name: &str,
+    read: impl FnOnce(&mut Reader) -> Result<T, BitcoinParserError>,
+) -> Result<T, BitcoinParserError> {
+    let mut reader = Reader::new(&pair.value);
+    let value = read(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(invalid(format!("Trailing bytes in {name}")));
+    }
+    Ok(value)
+}
+
+fn no_key_data(pair: &KeyPair, name: &str) -> Result<(), BitcoinParserError> {
+    if pair.key_data.is_empty() {
+        Ok(())
+    } else {
+        Err(invalid(format!("{name} key must not carry key data")))
+    }
+}
+
+fn parse_tx_out(reader: &mut Reader) -> Result<TxOut, BitcoinParserError> {
+    Ok(TxOut {
+        value: reader.u64()?,
+        script_pubkey: reader.var_bytes()?.to_vec(),
+    })
+}
+
+/// Parses a transaction in network serialization, with or without witness data.
+pub fn parse_transaction(bytes: &[u8]) -> Result<BitcoinTransaction, BitcoinParserError> {
+    let mut reader = Reader::new(bytes);
+    let tx = read_transaction(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(invalid("Trailing bytes after transaction".to_string()));
+    }
+    Ok(tx)
+}
+
+fn read_transaction(reader: &mut Reader) -> Result<BitcoinTransaction, BitcoinParserError> {
+    let version = i32::from_le_bytes(reader.array()?);
+    // A zero input count followed by a non-zero flag marks the segwit serialization
+    let segwit = reader.data.get(reader.pos..reader.pos + 2) == Some(&[0x00, 0x01][..]);
+    if segwit {
+        reader.bytes(2)?;
+    }
+
+    let mut inputs = Vec::new();
+    for _ in 0..reader.compact_size()? {
+        let previous_txid = reader.array()?;
+        let output_index = reader.u32()?;
+        reader.var_bytes()?;
+        inputs.push(TxIn {
+            previous_txid,
+            output_index,
+            sequence: reader.u32()?,
+        });
+    }
+    let mut outputs = Vec::new();
+    for _ in 0..reader.compact_size()? {
+        outputs.push(parse_tx_out(reader)?);
+    }
+    if segwit {
+        for _ in 0..inputs.len() {
+            for _ in 0..reader.compact_size()? {
+                reader.var_bytes()?;
+            }
+        }
+    }
+    Ok(BitcoinTransaction {
+        version,
+        inputs,
+        outputs,
+        lock_time: reader.u32()?,
+    })
+}
+
+fn parse_count(pair: &KeyPair, name: &str) -> Result<usize, BitcoinParserError> {
+    let count = parse_value(pair, name, |reader| reader.compact_size())?;
+    usize::try_from(count).map_err(|_| invalid(format!("{name} {count} is too large")))
+}
+
+/// Parses the bytes of a version 0 or version 2 PSBT.
+pub fn parse_psbt(bytes: &[u8]) -> Result<Psbt, BitcoinParserError> {
+    let mut reader = Reader::new(bytes);
+    if reader.bytes(PSBT_MAGIC.len()).ok() != Some(PSBT_MAGIC.as_slice()) {
+        return Err(invalid("Missing PSBT magic".to_string()));
+    }
+
+    let mut version = 0;
+    let mut unsigned_tx = None;
+    let mut tx_version = None;
+    let mut fallback_lock_time = None;
+    let mut input_count = None;
+    let mut output_count = None;
+    let mut v2_fields = Vec::new();
+    for pair in reader.map("global")? {
+        match pair.key_type {
+            PSBT_GLOBAL_UNSIGNED_TX => {
+                no_key_data(&pair, "Unsigned transaction")?;
+                unsigned_tx = Some(parse_transaction(&pair.value)?);
+            }
+            PSBT_GLOBAL_VERSION => {
+                no_key_data(&pair, "Version")?;
+                version = parse_value(&pair, "version", |reader| reader.u32())?;
+            }
+            PSBT_GLOBAL_TX_VERSION => {
+                v2_fields.push("PSBT_GLOBAL_TX_VERSION");
+                tx_version = Some(parse_value(&pair, "transaction version", |reader| {
+                    Ok(i32::from_le_bytes(reader.array()?))
+                })?);
+            }
+            PSBT_GLOBAL_FALLBACK_LOCKTIME => {
+                v2_fields.push("PSBT_GLOBAL_FALLBACK_LOCKTIME");
+                fallback_lock_time = Some(parse_value(&pair, "fallback lock time", |reader| {
+                    reader.u32()
+                })?);
+            }
+            PSBT_GLOBAL_INPUT_COUNT => {
+                v2_fields.push("PSBT_GLOBAL_INPUT_COUNT");
+                input_count = Some(parse_count(&pair, "input count")?);
+            }
+            PSBT_GLOBAL_OUTPUT_COUNT => {
+                v2_fields.push("PSBT_GLOBAL_OUTPUT_COUNT");
+                output_count = Some(parse_count(&pair, "output count")?);
+            }
+            _ => {}
+        }
+    }
+
+    let psbt = match version {
+        0 => {
+            if let Some(field) = v2_fields.first() {
+                return Err(invalid(format!(
+                    "{field} is not allowed in a version 0 PSBT"
+                )));
+            }
+            let tx = unsigned_tx
+                .ok_or_else(|| invalid("Version 0 PSBT has no unsigned transaction".to_string()))?;
+            let mut inputs = Vec::new();
+            for tx_in in &tx.inputs {
+                let mut input = parse_input(reader.map("input")?, version)?;
+                input.previous_txid = tx_in.previous_txid;
+                input.output_index = tx_in.output_index;
+                input.sequence = Some(tx_in.sequence);
+                inputs.push(input);
+            }
+            let mut outputs = Vec::new();
+            for tx_out in &tx.outputs {
+                let mut output = parse_output(reader.map("output")?, version)?;
+                output.amount = tx_out.value;
+                output.script_pubkey = tx_out.script_pubkey.clone();
+                outputs.push(output);
+            }
+            Psbt {
+                version,
+                tx_version: tx.version,
+                lock_time: tx.lock_time,
+                inputs,
+                outputs,
+            }
+        }
+        2 => {
+            if unsigned_tx.is_some() {
+                return Err(invalid(
+                    "PSBT_GLOBAL_UNSIGNED_TX is not allowed in a version 2 PSBT".to_string(),
+                ));
+            }
+            let missing = |name: &str| invalid(format!("Version 2 PSBT has no {name}"));
+            let input_count = input_count.ok_or_else(|| missing("input count"))?;
+            let output_count = output_count.ok_or_else(|| missing("output count"))?;
+            let mut inputs = Vec::new();
+            for _ in 0..input_count {
+                inputs.push(parse_input(reader.map("input")?, version)?);
+            }
+            let mut outputs = Vec::new();
+            for _ in 0..output_count {
+                outputs.push(parse_output(reader.map("output")?, version)?);
+            }
+            Psbt {
+                version,
+                tx_version: tx_version.ok_or_else(|| missing("transaction version"))?,
+                lock_time: fallback_lock_time.unwrap_or(0),
+                inputs,
+                outputs,
+            }
+        }
+        other => {
+            return Err(BitcoinParserError::UnsupportedVersion(format!(
+                "PSBT version {other}"
+            )));
+        }
+    };
+
+    if !reader.is_empty() {
+        return Err(invalid("Trailing bytes after the last output".to_string()));
+    }
+    Ok(psbt)
+}
+
+fn parse_input(pairs: Vec<KeyPair>, version: u32) -> Result<PsbtInput, BitcoinParserError> {
+    let mut input = PsbtInput::default();
+    let mut previous_txid = None;
+    let mut output_index = None;
+    for pair in pairs {
+        match pair.key_type {
+            PSBT_IN_NON_WITNESS_UTXO => {
+                no_key_data(&pair, "Non-witness UTXO")?;
+                input.non_witness_utxo = Some(parse_transaction(&pair.value)?);
+            }
+            PSBT_IN_WITNESS_UTXO => {
+                no_key_data(&pair, "Witness UTXO")?;
+                input.witness_utxo = Some(parse_value(&pair, "witness UTXO", parse_tx_out)?);
+            }
+            PSBT_IN_SIGHASH_TYPE => {
+                no_key_data(&pair, "Sighash type")?;
+                input.sighash_type = Some(parse_value(&pair, "sighash type", |r| r.u32())?);
+            }
+            PSBT_IN_FINAL_SCRIPTWITNESS => {
+                no_key_data(&pair, "Final script witness")?;
+                input.final_script_witness = Some(parse_value(&pair, "witness", |reader| {
+                    let mut items = Vec::new();
+                    for _ in 0..reader.compact_size()? {
+                        items.push(reader.var_bytes()?.to_vec());
+                    }
+                    Ok(items)
+                })?);
+            }
+            PSBT_IN_PREVIOUS_TXID
+            | PSBT_IN_OUTPUT_INDEX
+            | PSBT_IN_SEQUENCE
+            | PSBT_IN_REQUIRED_TIME_LOCKTIME
+            | PSBT_IN_REQUIRED_HEIGHT_LOCKTIME
+                if version == 0 =>
+            {
+                return Err(invalid(format!(
+                    "Input key type 0x{:02x} is not allowed in a version 0 PSBT",
+                    pair.key_type
+                )));
+            }
+            PSBT_IN_PREVIOUS_TXID => {
+                previous_txid = Some(parse_value(&pair, "previous txid", |r| r.array::<32>())?);
+            }
+            PSBT_IN_OUTPUT_INDEX => {
+                output_index = Some(parse_value(&pair, "output index", |r| r.u32())?);
+            }
+            PSBT_IN_SEQUENCE => {
+                input.sequence = Some(parse_value(&pair, "sequence", |r| r.u32())?);
+            }
+            PSBT_IN_REQUIRED_TIME_LOCKTIME => {
+                input.required_time_lock_time =
+                    Some(parse_value(&pair, "required time lock time", |r| r.u32())?);
+            }
+            PSBT_IN_REQUIRED_HEIGHT_LOCKTIME => {
+                input.required_height_lock_time =
+                    Some(parse_value(&pair, "required height lock time", |r| {
+                        r.u32()
+                    })?);
+            }
+            PSBT_IN_TAP_KEY_SIG => {
+                no_key_data(&pair, "Taproot key signature")?;
+                input.tap_key_sig = Some(schnorr_signature(&pair.value)?);
+            }
+            PSBT_IN_TAP_SCRIPT_SIG => {
+                if pair.key_data.len() != 64 {
+                    return Err(invalid(
+                        "Taproot script signature key must be 64 bytes".to_string(),
+                    ));
+                }
+                let mut key = Reader::new(&pair.key_data);
+                let x_only_key = key.array::<32>()?;
+                let leaf_hash = key.array::<32>()?;
+                input.tap_script_sigs.push((
+                    x_only_key,
+                    leaf_hash,
+                    schnorr_signature(&pair.value)?,
+                ));
+            }
+            PSBT_IN_TAP_LEAF_SCRIPT => {
+                let control_block = pair.key_data;
+                if control_block.len() < 33 || (control_block.len() - 33) % 32 != 0 {
+                    return Err(invalid(format!(
+                        "Invalid Taproot control block of {} bytes",
+                        control_block.len()
+                    )));
+                }
+                let (leaf_version, script) = pair
+                    .value
+                    .split_last()
+                    .ok_or_else(|| invalid("Empty Taproot leaf script".to_string()))?;
+                input.tap_leaf_scripts.push(TapLeafScript {
+                    control_block,
+                    script: script.to_vec(),
+                    leaf_version: *leaf_version,
+                });
+            }
+            PSBT_IN_TAP_INTERNAL_KEY => {
+                no_key_data(&pair, "Taproot internal key")?;
+                input.tap_internal_key =
+                    Some(parse_value(&pair, "internal key", |r| r.array::<32>())?);
+            }
+            PSBT_IN_TAP_MERKLE_ROOT => {
+                no_key_data(&pair, "Taproot merkle root")?;
+                input.tap_merkle_root =
+                    Some(parse_value(&pair, "merkle root", |r| r.array::<32>())?);
+            }
+            _ => {}
+        }
+    }
+    if version == 2 {
+        input.previous_txid = previous_txid
+            .ok_or_else(|| invalid("Version 2 PSBT input has no previous txid".to_string()))?;
+        input.output_index = output_index
+            .ok_or_else(|| invalid("Version 2 PSBT input has no output index".to_string()))?;
+    }
+    Ok(input)
+}
+
+fn parse_output(pairs: Vec<KeyPair>, version: u32) -> Result<PsbtOutput, BitcoinParserError> {
+    let mut output = PsbtOutput {
+        amount: 0,
+        script_pubkey: Vec::new(),
+        tap_internal_key: None,
+        has_tap_tree: false,
+    };
+    let mut amount = None;
+    let mut script = None;
+    for pair in pairs {
+        match pair.key_type {
+            PSBT_OUT_AMOUNT | PSBT_OUT_SCRIPT if version == 0 => {
+                return Err(invalid(format!(
+                    "Output key type 0x{:02x} is not allowed in a version 0 PSBT",
+                    pair.key_type
+                )));
+            }
+            PSBT_OUT_AMOUNT => {
+                let value = parse_value(&pair, "amount", |r| r.u64())?;
+                // The amount is a signed 64-bit integer
+                if i64::try_from(value).is_err() {
+                    return Err(invalid(format!("Negative output amount {value}")));
+                }
+                amount = Some(value);
+            }
+            PSBT_OUT_SCRIPT => script = Some(pair.value),
+            PSBT_OUT_TAP_INTERNAL_KEY => {
+                output.tap_internal_key =
+                    Some(parse_value(&pair, "internal key", |r| r.array::<32>())?);
+            }
+            PSBT_OUT_TAP_TREE => output.has_tap_tree = true,
+            _ => {}
+        }
+    }
+    if version == 2 {
+        output.amount =
+            amount.ok_or_else(|| invalid("Version 2 PSBT output has no amount".to_string()))?;
+        output.script_pubkey =
+            script.ok_or_else(|| invalid("Version 2 PSBT output has no script".to_string()))?;
+    }
+    Ok(output)
+}
+
+// A BIP-340 signature, optionally followed by a sighash byte
+fn schnorr_signature(value: &[u8]) -> Result<Vec<u8>, BitcoinParserError> {
+    match value.len() {
+        64 | 65 => Ok(value.to_vec()),
+        len => Err(invalid(format!("Invalid Schnorr signature of {len} bytes"))),
+    }
+}
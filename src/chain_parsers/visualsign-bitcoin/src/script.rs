@@ -0,0 +1,57 @@
+//! Output script templates.
+//!
+//! Addresses depend on the network the PSBT is for, which the PSBT does not record, so
+//! outputs are described by their script template and shown as script hex.
+
+const OP_0: u8 = 0x00;
+const OP_1: u8 = 0x51;
+const OP_RETURN: u8 = 0x6a;
+const OP_DUP: u8 = 0x76;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_HASH160: u8 = 0xa9;
+const OP_CHECKSIG: u8 = 0xac;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptType {
+    P2pkh,
+    P2sh,
+    P2wpkh,
+    P2wsh,
+    P2tr,
+    OpReturn,
+    Nonstandard,
+}
+
+impl ScriptType {
+    pub fn from_script(script: &[u8]) -> Self {
+        match script {
+            [
+                OP_DUP,
+                OP_HASH160,
+                0x14,
+                hash @ ..,
+                OP_EQUALVERIFY,
+                OP_CHECKSIG,
+            ] if hash.len() == 20 => ScriptType::P2pkh,
+            [OP_HASH160, 0x14, hash @ .., OP_EQUAL] if hash.len() == 20 => ScriptType::P2sh,
+            [OP_0, 0x14, hash @ ..] if hash.len() == 20 => ScriptType::P2wpkh,
+            [OP_0, 0x20, hash @ ..] if hash.len() == 32 => ScriptType::P2wsh,
+            [OP_1, 0x20, key @ ..] if key.len() == 32 => ScriptType::P2tr,
+            [OP_RETURN, ..] => ScriptType::OpReturn,
+            _ => ScriptType::Nonstandard,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScriptType::P2pkh => "P2PKH",
+            ScriptType::P2sh => "P2SH",
+            ScriptType::P2wpkh => "P2WPKH",
+            ScriptType::P2wsh => "P2WSH",
+            ScriptType::P2tr => "P2TR (Taproot)",
+            ScriptType::OpReturn => "OP_RETURN (data, unspendable)",
+            ScriptType::Nonstandard => "Nonstandard",
+        }
+    }
+}
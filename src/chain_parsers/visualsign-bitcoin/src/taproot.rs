@@ -0,0 +1,186 @@
+//! How an input is spent and what its signature commits to.
+//!
+//! A Taproot output (BIP-341) is spent either by a single signature for the output key (key
+//! path) or by revealing one leaf script of its script tree with a control block proving the
+//! leaf is in the tree (script path). The spend can also carry an annex, extra data the
+//! signature commits to. The sighash type, per input, decides which inputs and outputs a
+//! signature covers.
+
+use crate::psbt::{PsbtInput, TapLeafScript};
+
+/// First byte of a witness element that is the annex
+pub const ANNEX_TAG: u8 = 0x50;
+
+pub const SIGHASH_DEFAULT: u32 = 0x00;
+pub const SIGHASH_ALL: u32 = 0x01;
+pub const SIGHASH_NONE: u32 = 0x02;
+pub const SIGHASH_SINGLE: u32 = 0x03;
+pub const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpendPath {
+    KeyPath,
+    /// The leaf scripts the input may be spent through; one when the input is finalized
+    ScriptPath(Vec<TapLeafScript>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaprootSpend {
+    pub path: SpendPath,
+    pub annex: Option<Vec<u8>>,
+}
+
+impl TapLeafScript {
+    /// Depth of the leaf in the script tree, from the length of its control block.
+    pub fn merkle_depth(&self) -> usize {
+        self.control_block.len().saturating_sub(33) / 32
+    }
+}
+
+/// Classifies a Taproot input from its final witness, or from the signing fields when the
+/// input is not finalized.
+pub fn classify_spend(input: &PsbtInput) -> TaprootSpend {
+    if let Some(witness) = input
+        .final_script_witness
+        .as_deref()
+        .filter(|w| !w.is_empty())
+    {
+        // With two or more elements, a last element starting with 0x50 is the annex
+        let (stack, annex) = match witness {
+            [stack @ .., last] if !stack.is_empty() && last.first() == Some(&ANNEX_TAG) => {
+                (stack, Some(last.clone()))
+            }
+            _ => (witness, None),
+        };
+        let path = match stack {
+            [] | [_] => SpendPath::KeyPath,
+            [.., script, control_block] => SpendPath::ScriptPath(vec![TapLeafScript {
+                control_block: control_block.clone(),
+                script: script.clone(),
+                leaf_version: control_block.first().map_or(0, |byte| byte & 0xfe),
+            }]),
+        };
+        return TaprootSpend { path, annex };
+    }
+
+    let path = if input.tap_key_sig.is_none()
+        && (!input.tap_leaf_scripts.is_empty() || !input.tap_script_sigs.is_empty())
+    {
+        SpendPath::ScriptPath(input.tap_leaf_scripts.clone())
+    } else {
+        SpendPath::KeyPath
+    };
+    TaprootSpend { path, annex: None }
+}
+
+/// The sighash type the input is signed with: the PSBT's sighash field, else the byte
+/// appended to a Taproot signature, else the default for the input type.
+pub fn effective_sighash(input: &PsbtInput, taproot: bool) -> u32 {
+    if let Some(sighash) = input.sighash_type {
+        return sighash;
+    }
+    let signature = input.tap_key_sig.as_deref().or_else(|| {
+        input
+            .tap_script_sigs
+            .first()
+            .map(|(_, _, signature)| signature.as_slice())
+    });
+    match signature {
+        Some(signature) if signature.len() == 65 => u32::from(signature[64]),
+        _ if taproot => SIGHASH_DEFAULT,
+        _ => SIGHASH_ALL,
+    }
+}
+
+/// Describes what a signature with `sighash` commits to, e.g.
+/// "ALL (signs all inputs and all outputs)".
+pub fn describe_sighash(sighash: u32, taproot: bool) -> String {
+    if sighash == SIGHASH_DEFAULT {
+        return if taproot {
+            "DEFAULT (signs all inputs and all outputs)".to_string()
+        } else {
+            format!("Invalid sighash type 0x{sighash:02x}")
+        };
+    }
+    let base = match sighash & !SIGHASH_ANYONECANPAY {
+        SIGHASH_ALL => ("ALL", "all outputs"),
+        SIGHASH_NONE => ("NONE", "no outputs, so they can be changed"),
+        SIGHASH_SINGLE => ("SINGLE", "only the output at this input's index"),
+        _ => {
+            let consequence = if taproot {
+                "the signature is invalid"
+            } else {
+                "nonstandard, the transaction will not be relayed"
+            };
+            return format!("Invalid sighash type 0x{sighash:02x} ({consequence})");
+        }
+    };
+    let (suffix, inputs) = if sighash & SIGHASH_ANYONECANPAY != 0 {
+        ("|ANYONECANPAY", "only this input, so others can be added,")
+    } else {
+        ("", "all inputs")
+    };
+    format!("{}{suffix} (signs {inputs} and {})", base.0, base.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf_input(witness: Vec<Vec<u8>>) -> PsbtInput {
+        PsbtInput {
+            final_script_witness: Some(witness),
+            ..PsbtInput::default()
+        }
+    }
+
+    #[test]
+    fn test_classify_finalized_spends() {
+        let key_path = classify_spend(&leaf_input(vec![vec![1; 64]]));
+        assert_eq!(key_path.path, SpendPath::KeyPath);
+        assert_eq!(key_path.annex, None);
+
+        let with_annex = classify_spend(&leaf_input(vec![vec![1; 64], vec![ANNEX_TAG, 7]]));
+        assert_eq!(with_annex.path, SpendPath::KeyPath);
+        assert_eq!(with_annex.annex, Some(vec![ANNEX_TAG, 7]));
+
+        let mut control_block = vec![0xc1];
+        control_block.extend([2; 64]);
+        let script_path = classify_spend(&leaf_input(vec![
+            vec![1; 64],
+            vec![0x20, 0xac],
+            control_block.clone(),
+        ]));
+        let SpendPath::ScriptPath(leaves) = script_path.path else {
+            panic!("expected a script-path spend");
+        };
+        assert_eq!(leaves[0].script, vec![0x20, 0xac]);
+        assert_eq!(leaves[0].leaf_version, 0xc0);
+        assert_eq!(leaves[0].merkle_depth(), 1);
+    }
+
+    #[test]
+    fn test_describe_sighash() {
+        assert_eq!(
+            describe_sighash(SIGHASH_DEFAULT, true),
+            "DEFAULT (signs all inputs and all outputs)"
+        );
+        assert_eq!(
+            describe_sighash(SIGHASH_SINGLE | SIGHASH_ANYONECANPAY, false),
+            "SINGLE|ANYONECANPAY (signs only this input, so others can be added, and only the output at this input's index)"
+        );
+        assert!(describe_sighash(SIGHASH_DEFAULT, false).starts_with("Invalid"));
+        assert!(describe_sighash(0x04, true).contains("signature is invalid"));
+
+        let signed = PsbtInput {
+            tap_key_sig: Some([vec![0; 64], vec![0x82]].concat()),
+            ..PsbtInput::default()
+        };
+        assert_eq!(effective_sighash(&signed, true), 0x82);
+        assert_eq!(
+            effective_sighash(&PsbtInput::default(), true),
+            SIGHASH_DEFAULT
+        );
+        assert_eq!(effective_sighash(&PsbtInput::default(), false), SIGHASH_ALL);
+    }
+}
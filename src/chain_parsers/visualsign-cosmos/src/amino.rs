@@ -0,0 +1,176 @@
+//! `SIGN_MODE_LEGACY_AMINO_JSON` sign documents.
+//!
+//! Ledger and older wallets sign the canonical JSON of an amino `StdSignDoc`, where integers
+//! are strings and each message is `{"type": "cosmos-sdk/MsgSend", "value": {...}}`.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::CosmosParserError;
+use crate::sign_doc::{Coin, CosmosMessage, Fee, SignDoc, SignMode};
+
+pub const MSG_SEND: &str = "cosmos-sdk/MsgSend";
+pub const MSG_DELEGATE: &str = "cosmos-sdk/MsgDelegate";
+pub const MSG_UNDELEGATE: &str = "cosmos-sdk/MsgUndelegate";
+pub const MSG_WITHDRAW_DELEGATION_REWARD: &str = "cosmos-sdk/MsgWithdrawDelegationReward";
+
+#[derive(Debug, Deserialize)]
+pub struct StdSignDoc {
+    pub account_number: String,
+    pub chain_id: String,
+    pub fee: StdFee,
+    #[serde(default)]
+    pub memo: String,
+    pub msgs: Vec<AminoMsg>,
+    pub sequence: String,
+    #[serde(default)]
+    pub timeout_height: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StdFee {
+    #[serde(default)]
+    pub amount: Vec<AminoCoin>,
+    pub gas: String,
+    #[serde(default)]
+    pub payer: String,
+    #[serde(default)]
+    pub granter: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AminoCoin {
+    pub denom: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub value: Value,
+}
+
+#[derive(Deserialize)]
+struct AminoSend {
+    from_address: String,
+    to_address: String,
+    #[serde(default)]
+    amount: Vec<AminoCoin>,
+}
+
+#[derive(Deserialize)]
+struct AminoDelegation {
+    delegator_address: String,
+    validator_address: String,
+    #[serde(default)]
+    amount: Option<AminoCoin>,
+}
+
+#[derive(Deserialize)]
+struct AminoWithdrawReward {
+    delegator_address: String,
+    validator_address: String,
+}
+
+impl From<AminoCoin> for Coin {
+    fn from(coin: AminoCoin) -> Self {
+        Coin {
+            denom: coin.denom,
+            amount: coin.amount,
+        }
+    }
+}
+
+/// Decodes an amino JSON `StdSignDoc`.
+pub fn decode_sign_doc(json: &str) -> Result<SignDoc, CosmosParserError> {
+    let doc: StdSignDoc = serde_json::from_str(json).map_err(|e| {
+        CosmosParserError::FailedToDecodeSignDoc(format!("Invalid amino sign doc: {e}"))
+    })?;
+    if doc.msgs.is_empty() {
+        return Err(CosmosParserError::InvalidSignDoc(
+            "Transaction has no messages".to_string(),
+        ));
+    }
+
+    Ok(SignDoc {
+        sign_mode: SignMode::AminoJson,
+        account_number: integer("account_number", &doc.account_number)?,
+        sequence: Some(integer("sequence", &doc.sequence)?),
+        timeout_height: doc
+            .timeout_height
+            .as_deref()
+            .map_or(Ok(0), |height| integer("timeout_height", height))?,
+        chain_id: doc.chain_id,
+        fee: Fee {
+            gas_limit: integer("gas", &doc.fee.gas)?,
+            amount: doc.fee.amount.into_iter().map(Coin::from).collect(),
+            payer: doc.fee.payer,
+            granter: doc.fee.granter,
+        },
+        memo: doc.memo,
+        messages: doc
+            .msgs
+            .into_iter()
+            .map(decode_message)
+            .collect::<Result<_, _>>()?,
+        extension_options: Vec::new(),
+    })
+}
+
+fn decode_message(msg: AminoMsg) -> Result<CosmosMessage, CosmosParserError> {
+    let decoded = match msg.type_name.as_str() {
+        MSG_SEND => {
+            let send: AminoSend = value(MSG_SEND, msg.value)?;
+            CosmosMessage::Send {
+                from_address: send.from_address,
+                to_address: send.to_address,
+                amount: send.amount.into_iter().map(Coin::from).collect(),
+            }
+        }
+        MSG_DELEGATE => {
+            let delegation: AminoDelegation = value(MSG_DELEGATE, msg.value)?;
+            CosmosMessage::Delegate {
+                delegator_address: delegation.delegator_address,
+                validator_address: delegation.validator_address,
+                amount: delegation.amount.map(Coin::from),
+            }
+        }
+        MSG_UNDELEGATE => {
+            let delegation: AminoDelegation = value(MSG_UNDELEGATE, msg.value)?;
+            CosmosMessage::Undelegate {
+                delegator_address: delegation.delegator_address,
+                validator_address: delegation.validator_address,
+                amount: delegation.amount.map(Coin::from),
+            }
+        }
+        MSG_WITHDRAW_DELEGATION_REWARD => {
+            let withdraw: AminoWithdrawReward = value(MSG_WITHDRAW_DELEGATION_REWARD, msg.value)?;
+            CosmosMessage::WithdrawDelegatorReward {
+                delegator_address: withdraw.delegator_address,
+                validator_address: withdraw.validator_address,
+            }
+        }
+        _ => CosmosMessage::Unknown {
+            value: msg.value.to_string(),
+            type_name: msg.type_name,
+        },
+    };
+    Ok(decoded)
+}
+
+fn value<T: serde::de::DeserializeOwned>(
+    type_name: &str,
+    value: Value,
+) -> Result<T, CosmosParserError> {
+    serde_json::from_value(value)
+        .map_err(|e| CosmosParserError::FailedToDecodeSignDoc(format!("Invalid {type_name}: {e}")))
+}
+
+fn integer(name: &str, value: &str) -> Result<u64, CosmosParserError> {
+    value.parse().map_err(|_| {
+        CosmosParserError::FailedToDecodeSignDoc(format!(
+            "{name} must be an integer string, got {value:?}"
+        ))
+    })
+}
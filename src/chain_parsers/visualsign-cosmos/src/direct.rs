@@ -0,0 +1,212 @@
+//! `SIGN_MODE_DIRECT` sign documents.
+//!
+//! The signer signs the protobuf encoding of `cosmos.tx.v1beta1.SignDoc`, which embeds the
+//! encoded `TxBody` and `AuthInfo`. Only the protobuf fields the payload shows are declared
+//! below, with the tags of the Cosmos SDK definitions.
+
+use prost::Message;
+
+use crate::CosmosParserError;
+use crate::sign_doc::{Coin, CosmosMessage, Fee, SignDoc, SignMode};
+
+pub const MSG_SEND: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const MSG_DELEGATE: &str = "/cosmos.staking.v1beta1.MsgDelegate";
+pub const MSG_UNDELEGATE: &str = "/cosmos.staking.v1beta1.MsgUndelegate";
+pub const MSG_WITHDRAW_DELEGATOR_REWARD: &str =
+    "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoSignDoc {
+    #[prost(bytes = "vec", tag = "1")]
+    pub body_bytes: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub auth_info_bytes: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub chain_id: String,
+    #[prost(uint64, tag = "4")]
+    pub account_number: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoAny {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct TxBody {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<ProtoAny>,
+    #[prost(string, tag = "2")]
+    pub memo: String,
+    #[prost(uint64, tag = "3")]
+    pub timeout_height: u64,
+    #[prost(message, repeated, tag = "1023")]
+    pub extension_options: Vec<ProtoAny>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct AuthInfo {
+    #[prost(message, repeated, tag = "1")]
+    pub signer_infos: Vec<SignerInfo>,
+    #[prost(message, optional, tag = "2")]
+    pub fee: Option<ProtoFee>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct SignerInfo {
+    #[prost(message, optional, tag = "1")]
+    pub public_key: Option<ProtoAny>,
+    #[prost(uint64, tag = "3")]
+    pub sequence: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoFee {
+    #[prost(message, repeated, tag = "1")]
+    pub amount: Vec<ProtoCoin>,
+    #[prost(uint64, tag = "2")]
+    pub gas_limit: u64,
+    #[prost(string, tag = "3")]
+    pub payer: String,
+    #[prost(string, tag = "4")]
+    pub granter: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoCoin {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSend {
+    #[prost(string, tag = "1")]
+    pub from_address: String,
+    #[prost(string, tag = "2")]
+    pub to_address: String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: Vec<ProtoCoin>,
+}
+
+/// Shape shared by `MsgDelegate` and `MsgUndelegate`
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgDelegation {
+    #[prost(string, tag = "1")]
+    pub delegator_address: String,
+    #[prost(string, tag = "2")]
+    pub validator_address: String,
+    #[prost(message, optional, tag = "3")]
+    pub amount: Option<ProtoCoin>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgWithdrawDelegatorReward {
+    #[prost(string, tag = "1")]
+    pub delegator_address: String,
+    #[prost(string, tag = "2")]
+    pub validator_address: String,
+}
+
+impl From<ProtoCoin> for Coin {
+    fn from(coin: ProtoCoin) -> Self {
+        Coin {
+            denom: coin.denom,
+            amount: coin.amount,
+        }
+    }
+}
+
+/// Decodes the protobuf encoding of a `SignDoc`.
+pub fn decode_sign_doc(bytes: &[u8]) -> Result<SignDoc, CosmosParserError> {
+    let sign_doc = ProtoSignDoc::decode(bytes).map_err(decode_error("SignDoc"))?;
+    let body = TxBody::decode(sign_doc.body_bytes.as_slice()).map_err(decode_error("TxBody"))?;
+    let auth_info =
+        AuthInfo::decode(sign_doc.auth_info_bytes.as_slice()).map_err(decode_error("AuthInfo"))?;
+    if body.messages.is_empty() {
+        return Err(CosmosParserError::InvalidSignDoc(
+            "Transaction has no messages".to_string(),
+        ));
+    }
+
+    let fee = auth_info.fee.unwrap_or_default();
+    let sequence = match auth_info.signer_infos.as_slice() {
+        [signer] => Some(signer.sequence),
+        _ => None,
+    };
+    Ok(SignDoc {
+        sign_mode: SignMode::Direct,
+        chain_id: sign_doc.chain_id,
+        account_number: sign_doc.account_number,
+        sequence,
+        fee: Fee {
+            amount: fee.amount.into_iter().map(Coin::from).collect(),
+            gas_limit: fee.gas_limit,
+            payer: fee.payer,
+            granter: fee.granter,
+        },
+        memo: body.memo,
+        timeout_height: body.timeout_height,
+        messages: body
+            .messages
+            .iter()
+            .map(decode_message)
+            .collect::<Result<_, _>>()?,
+        extension_options: body
+            .extension_options
+            .into_iter()
+            .map(|option| option.type_url)
+            .collect(),
+    })
+}
+
+fn decode_message(message: &ProtoAny) -> Result<CosmosMessage, CosmosParserError> {
+    let value = message.value.as_slice();
+    let decoded = match message.type_url.as_str() {
+        MSG_SEND => {
+            let msg = MsgSend::decode(value).map_err(decode_error(MSG_SEND))?;
+            CosmosMessage::Send {
+                from_address: msg.from_address,
+                to_address: msg.to_address,
+                amount: msg.amount.into_iter().map(Coin::from).collect(),
+            }
+        }
+        MSG_DELEGATE => {
+            let msg = MsgDelegation::decode(value).map_err(decode_error(MSG_DELEGATE))?;
+            CosmosMessage::Delegate {
+                delegator_address: msg.delegator_address,
+                validator_address: msg.validator_address,
+                amount: msg.amount.map(Coin::from),
+            }
+        }
+        MSG_UNDELEGATE => {
+            let msg = MsgDelegation::decode(value).map_err(decode_error(MSG_UNDELEGATE))?;
+            CosmosMessage::Undelegate {
+                delegator_address: msg.delegator_address,
+                validator_address: msg.validator_address,
+                amount: msg.amount.map(Coin::from),
+            }
+        }
+        MSG_WITHDRAW_DELEGATOR_REWARD => {
+            let msg = MsgWithdrawDelegatorReward::decode(value)
+                .map_err(decode_error(MSG_WITHDRAW_DELEGATOR_REWARD))?;
+            CosmosMessage::WithdrawDelegatorReward {
+                delegator_address: msg.delegator_address,
+                validator_address: msg.validator_address,
+            }
+        }
+        other => CosmosMessage::Unknown {
+            type_name: other.to_string(),
+            value: hex::encode(value),
+        },
+    };
+    Ok(decoded)
+}
+
+fn decode_error(name: &'static str) -> impl Fn(prost::DecodeError) -> CosmosParserError {
+    move |e| CosmosParserError::FailedToDecodeSignDoc(format!("Invalid {name}: {e}"))
+}
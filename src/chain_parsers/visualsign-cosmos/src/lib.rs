@@ -0,0 +1,621 @@
+use visualsign::{
+    SignablePayload, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+    capabilities::ParserCapabilities,
+    chain_id::chain_id_warning,
+    encodings::SupportedEncodings,
+    errors::{ParserError, ParserErrorKind},
+    expiration::{Expiration, create_expiration_field},
+    extensions::{ExpectedChainId, ExpectedNonce},
+    field_builders::{create_address_field, create_raw_data_field, create_text_field},
+    memo::missing_memo_warning,
+    nonce::nonce_warning,
+    parser_info::ParserInfo,
+    registry::Chain,
+    sender::{create_sender_field, resolve_sender},
+    telemetry::record_command_count,
+    vsptrait::{
+        Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
+        VisualSignError, VisualSignOptions,
+    },
+};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as b64};
+
+pub mod amino;
+pub mod direct;
+pub mod sign_doc;
+
+use sign_doc::{CosmosMessage, SignDoc, SignMode, format_coins};
+
+/// Registry name of the Cosmos converter, which has no chain of its own in the proto enum
+pub const CHAIN_NAME: &str = "Cosmos";
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum CosmosParserError {
+    #[error("Failed to decode sign doc: {0}")]
+    FailedToDecodeSignDoc(String),
+    #[error("Invalid sign doc: {0}")]
+    InvalidSignDoc(String),
+}
+
+impl From<CosmosParserError> for TransactionParseError {
+    fn from(error: CosmosParserError) -> Self {
+        let reason = match &error {
+            CosmosParserError::FailedToDecodeSignDoc(_) => "FailedToDecodeSignDoc",
+            CosmosParserError::InvalidSignDoc(_) => "InvalidSignDoc",
+        };
+        TransactionParseError::Parser(ParserError::new(
+            ParserErrorKind::Encoding,
+            CHAIN_NAME,
+            reason,
+            error.to_string(),
+        ))
+    }
+}
+
+/// Wrapper for Cosmos SDK sign documents
+///
+/// A JSON object is read as an amino `StdSignDoc`; anything else as the hex or base64
+/// protobuf encoding of a direct `SignDoc`.
+#[derive(Debug, Clone)]
+pub struct CosmosSignDocWrapper {
+    sign_doc: SignDoc,
+}
+
+impl Transaction for CosmosSignDocWrapper {
+    fn from_string(data: &str) -> Result<Self, TransactionParseError> {
+        if data.trim_start().starts_with('{') {
+            return Ok(Self::new(amino::decode_sign_doc(data)?));
+        }
+        let bytes = if let Some(digits) = data.strip_prefix("0x") {
+            hex::decode(digits).map_err(|e| {
+                CosmosParserError::FailedToDecodeSignDoc(format!("Failed to decode hex: {e}"))
+            })?
+        } else {
+            match SupportedEncodings::detect(data) {
+                SupportedEncodings::Hex => hex::decode(data).map_err(|e| {
+                    CosmosParserError::FailedToDecodeSignDoc(format!("Failed to decode hex: {e}"))
+                })?,
+                SupportedEncodings::Base64 => b64.decode(data).map_err(|e| {
+                    CosmosParserError::FailedToDecodeSignDoc(format!(
+                        "Failed to decode base64: {e}"
+                    ))
+                })?,
+            }
+        };
+        Ok(Self::new(direct::decode_sign_doc(&bytes)?))
+    }
+
+    fn transaction_type(&self) -> String {
+        CHAIN_NAME.to_string()
+    }
+}
+
+impl CosmosSignDocWrapper {
+    pub fn new(sign_doc: SignDoc) -> Self {
+        Self { sign_doc }
+    }
+
+    pub fn inner(&self) -> &SignDoc {
+        &self.sign_doc
+    }
+}
+
+/// Converter for Cosmos SDK transactions in either signing mode
+pub struct CosmosVisualSignConverter;
+
+impl VisualSignConverter<CosmosSignDocWrapper> for CosmosVisualSignConverter {
+    fn to_visual_sign_payload(
+        &self,
+        transaction_wrapper: CosmosSignDocWrapper,
+        options: VisualSignOptions,
+    ) -> Result<SignablePayload, VisualSignError> {
+        convert_to_visual_sign_payload(transaction_wrapper.sign_doc, options)
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        ParserCapabilities::new(
+            &["hex", "base64", "amino-json"],
+            &[SignMode::Direct.name(), SignMode::AminoJson.name()],
+        )
+        .with_decoders([
+            direct::MSG_SEND,
+            direct::MSG_DELEGATE,
+            direct::MSG_UNDELEGATE,
+            direct::MSG_WITHDRAW_DELEGATOR_REWARD,
+            amino::MSG_SEND,
+            amino::MSG_DELEGATE,
+            amino::MSG_UNDELEGATE,
+            amino::MSG_WITHDRAW_DELEGATION_REWARD,
+        ])
+        .with_feature("direct", true)
+        .with_feature("amino_json", true)
+        .with_feature("textual", false)
+    }
+}
+
+impl VisualSignConverterFromString<CosmosSignDocWrapper> for CosmosVisualSignConverter {}
+
+fn convert_to_visual_sign_payload(
+    sign_doc: SignDoc,
+    options: VisualSignOptions,
+) -> Result<SignablePayload, VisualSignError> {
+    let mut fields = vec![text_field("Network", &sign_doc.chain_id)];
+    if let Some(ExpectedChainId(expected)) = options.extensions.get::<ExpectedChainId>() {
+        fields.extend(chain_id_warning(expected, Some(&sign_doc.chain_id)));
+    }
+    fields.push(text_field("Sign Mode", sign_doc.sign_mode.name()));
+
+    // The first message's signer signs first and, without a fee payer, pays the fee
+    let signer = sign_doc.messages.iter().find_map(CosmosMessage::signer);
+    if let Some(sender) = resolve_sender(signer, options.sender.as_deref(), |derived, provided| {
+        derived == provided
+    })? {
+        fields.push(create_sender_field(&sender));
+    }
+
+    fields.push(text_field(
+        "Account Number",
+        &sign_doc.account_number.to_string(),
+    ));
+    if let Some(sequence) = sign_doc.sequence {
+        fields.push(text_field("Sequence", &sequence.to_string()));
+        if let Some(ExpectedNonce(expected)) = options.extensions.get::<ExpectedNonce>() {
+            fields.extend(nonce_warning(*expected, sequence, "sequence"));
+        }
+    }
+    fields.push(text_field("Fee", &format_coins(&sign_doc.fee.amount)));
+    fields.push(text_field("Gas Limit", &sign_doc.fee.gas_limit.to_string()));
+    if !sign_doc.fee.payer.is_empty() {
+        fields.push(text_field("Fee Payer", &sign_doc.fee.payer));
+    }
+    if !sign_doc.fee.granter.is_empty() {
+        fields.push(text_field("Fee Granter", &sign_doc.fee.granter));
+    }
+    let memo = memo_text(&sign_doc.memo);
+    if let Some(memo) = &memo {
+        fields.push(text_field("Memo", memo));
+    }
+    // A zero timeout height leaves the transaction valid until its sequence is used
+    let expiration = match sign_doc.timeout_height {
+        0 => Expiration::Never,
+        height => Expiration::BlockHeight(height),
+    };
+    fields.push(create_expiration_field(&expiration)?.signable_payload_field);
+    if !sign_doc.extension_options.is_empty() {
+        fields.push(text_field(
+            "Extension Options",
+            &sign_doc.extension_options.join(", "),
+        ));
+    }
+
+    record_command_count(sign_doc.messages.len());
+    let chain = Chain::Custom(CHAIN_NAME.to_string());
+    for (index, message) in sign_doc.messages.iter().enumerate() {
+        fields.push(message_section(index + 1, message)?);
+
+        // Warn when an exchange-style destination is missing its memo
+        if let (CosmosMessage::Send { to_address, .. }, Some(requirements)) =
+            (message, &options.memo_requirements)
+        {
+            fields.extend(missing_memo_warning(
+                requirements.as_ref(),
+                &chain,
+                to_address,
+                memo.as_deref(),
+                "memo",
+            ));
+        }
+    }
+
+    let title = options
+        .transaction_name
+        .unwrap_or_else(|| "Cosmos Transaction".to_string());
+    Ok(SignablePayload::new(
+        0,
+        title,
+        None,
+        fields,
+        "CosmosTx".to_string(),
+    ))
+}
+
+// One preview section per message, titled with what the message does
+fn message_section(
+    number: usize,
+    message: &CosmosMessage,
+) -> Result<SignablePayloadField, VisualSignError> {
+    let address =
+        |label: &str, address: &str| create_address_field(label, address, None, None, None, None);
+    let coin_text = |coin: &Option<sign_doc::Coin>| {
+        coin.as_ref()
+            .map_or_else(|| "None".to_string(), ToString::to_string)
+    };
+
+    let (title, subtitle, expanded) = match message {
+        CosmosMessage::Send {
+            from_address,
+            to_address,
+            amount,
+        } => (
+            format!("Send {}", format_coins(amount)),
+            format!("To {to_address}"),
+            vec![
+                address("From", from_address)?,
+                address("To", to_address)?,
+                create_text_field("Amount", &format_coins(amount))?,
+            ],
+        ),
+        CosmosMessage::Delegate {
+            delegator_address,
+            validator_address,
+            amount: coin,
+        } => (
+            format!("Delegate {}", coin_text(coin)),
+            format!("To validator {validator_address}"),
+            vec![
+                address("Delegator", delegator_address)?,
+                address("Validator", validator_address)?,
+                create_text_field("Amount", &coin_text(coin))?,
+            ],
+        ),
+        CosmosMessage::Undelegate {
+            delegator_address,
+            validator_address,
+            amount: coin,
+        } => (
+            format!("Undelegate {}", coin_text(coin)),
+            format!("From validator {validator_address}"),
+            vec![
+                address("Delegator", delegator_address)?,
+                address("Validator", validator_address)?,
+                create_text_field("Amount", &coin_text(coin))?,
+            ],
+        ),
+        CosmosMessage::WithdrawDelegatorReward {
+            delegator_address,
+            validator_address,
+        } => (
+            "Withdraw staking rewards".to_string(),
+            format!("From validator {validator_address}"),
+            vec![
+                address("Delegator", delegator_address)?,
+                address("Validator", validator_address)?,
+            ],
+        ),
+        CosmosMessage::Unknown { type_name, value } => (
+            type_name.clone(),
+            "Not decoded".to_string(),
+            vec![
+                create_text_field("Type", type_name)?,
+                create_raw_data_field(value.as_bytes(), Some(value.clone()))?,
+            ],
+        ),
+    };
+
+    Ok(SignablePayloadField::PreviewLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text: title.clone(),
+            label: format!("Message {number}"),
+        },
+        preview_layout: SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: title.clone(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: subtitle.clone(),
+            }),
+            condensed: Some(SignablePayloadFieldListLayout {
+                fields: vec![create_text_field(
+                    "Summary",
+                    &format!("{title} {subtitle}"),
+                )?],
+            }),
+            expanded: Some(SignablePayloadFieldListLayout { fields: expanded }),
+        },
+    })
+}
+
+fn text_field(label: &str, text: &str) -> SignablePayloadField {
+    SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: text.to_string(),
+            label: label.to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 {
+            text: text.to_string(),
+        },
+    }
+}
+
+// Helper function to render the memo as text, or hex when it is not printable ASCII
+fn memo_text(memo: &str) -> Option<String> {
+    if memo.is_empty() {
+        return None;
+    }
+    if memo.chars().all(|ch| ch.is_ascii_graphic() || ch == ' ') {
+        Some(memo.to_string())
+    } else {
+        Some(format!("0x{}", hex::encode(memo)))
+    }
+}
+
+// Public API functions
+pub fn transaction_to_visual_sign(
+    sign_doc: SignDoc,
+    options: VisualSignOptions,
+) -> Result<SignablePayload, VisualSignError> {
+    CosmosVisualSignConverter.to_visual_sign_payload(CosmosSignDocWrapper::new(sign_doc), options)
+}
+
+pub fn transaction_string_to_visual_sign(
+    transaction_data: &str,
+    options: VisualSignOptions,
+) -> Result<SignablePayload, VisualSignError> {
+    CosmosVisualSignConverter.to_visual_sign_payload_from_string(transaction_data, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use visualsign::memo::MemoRequiredDestinations;
+
+    const FROM: &str = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+    const TO: &str = "cosmos1zg69v7ys40x77y352eufp27daufrg4ncnjqz7q";
+    const VALIDATOR: &str = "cosmosvaloper1sjllsnramtg3ewxqwwrwjxfgc4n4ef9u2lcnj0";
+
+    fn coin(amount: &str) -> direct::ProtoCoin {
+        direct::ProtoCoin {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    fn direct_sign_doc() -> String {
+        let send = direct::MsgSend {
+            from_address: FROM.to_string(),
+            to_address: TO.to_string(),
+            amount: vec![coin("1000000")],
+        };
+        let delegate = direct::MsgDelegation {
+            delegator_address: FROM.to_string(),
+            validator_address: VALIDATOR.to_string(),
+            amount: Some(coin("500")),
+        };
+        let body = direct::TxBody {
+            messages: vec![
+                direct::ProtoAny {
+                    type_url: direct::MSG_SEND.to_string(),
+                    value: send.encode_to_vec(),
+                },
+                direct::ProtoAny {
+                    type_url: direct::MSG_DELEGATE.to_string(),
+                    value: delegate.encode_to_vec(),
+                },
+            ],
+            memo: "Invoice 42".to_string(),
+            timeout_height: 0,
+            extension_options: vec![],
+        };
+        let auth_info = direct::AuthInfo {
+            signer_infos: vec![direct::SignerInfo {
+                public_key: None,
+                sequence: 7,
+            }],
+            fee: Some(direct::ProtoFee {
+                amount: vec![coin("5000")],
+                gas_limit: 200000,
+                payer: String::new(),
+                granter: String::new(),
+            }),
+        };
+        let sign_doc = direct::ProtoSignDoc {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 12,
+        };
+        hex::encode(sign_doc.encode_to_vec())
+    }
+
+    fn amino_sign_doc() -> String {
+        serde_json::json!({
+            "account_number": "12",
+            "chain_id": "cosmoshub-4",
+            "fee": {"amount": [{"amount": "5000", "denom": "uatom"}], "gas": "200000"},
+            "memo": "Invoice 42",
+            "msgs": [
+                {
+                    "type": "cosmos-sdk/MsgSend",
+                    "value": {
+                        "amount": [{"amount": "1000000", "denom": "uatom"}],
+                        "from_address": FROM,
+                        "to_address": TO
+                    }
+                },
+                {
+                    "type": "cosmos-sdk/MsgDelegate",
+                    "value": {
+                        "amount": {"amount": "500", "denom": "uatom"},
+                        "delegator_address": FROM,
+                        "validator_address": VALIDATOR
+                    }
+                }
+            ],
+            "sequence": "7"
+        })
+        .to_string()
+    }
+
+    fn labelled(payload: &SignablePayload) -> Vec<String> {
+        payload
+            .fields
+            .iter()
+            .map(|field| format!("{}: {}", field.label(), field.fallback_text()))
+            .collect()
+    }
+
+    #[test]
+    fn test_direct_sign_doc() {
+        let payload =
+            transaction_string_to_visual_sign(&direct_sign_doc(), VisualSignOptions::default())
+                .unwrap();
+        assert_eq!(payload.payload_type, "CosmosTx");
+        assert_eq!(
+            labelled(&payload),
+            vec![
+                "Network: cosmoshub-4".to_string(),
+                "Sign Mode: Direct (protobuf)".to_string(),
+                format!("From: {FROM}"),
+                "Account Number: 12".to_string(),
+                "Sequence: 7".to_string(),
+                "Fee: 5000 uatom".to_string(),
+                "Gas Limit: 200000".to_string(),
+                "Memo: Invoice 42".to_string(),
+                "Expiration: Never".to_string(),
+                "Message 1: Send 1000000 uatom".to_string(),
+                "Message 2: Delegate 500 uatom".to_string(),
+            ]
+        );
+        assert!(payload.validate_charset().is_ok());
+    }
+
+    #[test]
+    fn test_amino_and_direct_render_the_same() {
+        let direct =
+            transaction_string_to_visual_sign(&direct_sign_doc(), VisualSignOptions::default())
+                .unwrap();
+        let amino =
+            transaction_string_to_visual_sign(&amino_sign_doc(), VisualSignOptions::default())
+                .unwrap();
+        assert_eq!(amino.fields[1].fallback_text(), "Amino JSON (legacy)");
+
+        // Only the sign mode differs
+        let mut amino_fields = amino.fields.clone();
+        amino_fields[1] = direct.fields[1].clone();
+        assert_eq!(amino_fields, direct.fields);
+    }
+
+    #[test]
+    fn test_expected_sequence_warning() {
+        let mut options = VisualSignOptions::default();
+        options.extensions.insert(ExpectedNonce(5));
+        let payload = transaction_string_to_visual_sign(&direct_sign_doc(), options).unwrap();
+        assert_eq!(payload.fields[4].label(), "Sequence");
+        assert_eq!(
+            payload.fields[5].fallback_text(),
+            "Warning: sequence 7 leaves 2 unused sequence(s) before it; the account's next sequence is 5"
+        );
+    }
+
+    #[test]
+    fn test_expected_chain_id_warning() {
+        let options = |expected: &str| {
+            let mut options = VisualSignOptions::default();
+            options
+                .extensions
+                .insert(ExpectedChainId(expected.to_string()));
+            options
+        };
+
+        let payload =
+            transaction_string_to_visual_sign(&direct_sign_doc(), options("cosmoshub-4")).unwrap();
+        assert_eq!(payload.fields[1].label(), "Sign Mode");
+
+        let payload =
+            transaction_string_to_visual_sign(&direct_sign_doc(), options("theta-testnet-001"))
+                .unwrap();
+        assert_eq!(payload.fields[1].label(), "Chain ID Warning");
+        assert_eq!(
+            payload.fields[1].fallback_text(),
+            "Warning: the transaction is for chain ID cosmoshub-4, not the expected theta-testnet-001"
+        );
+    }
+
+    #[test]
+    fn test_unknown_message_and_memo_warning() {
+        let json = serde_json::json!({
+            "account_number": "1",
+            "chain_id": "osmosis-1",
+            "fee": {"amount": [], "gas": "100000", "granter": "osmo1granter"},
+            "msgs": [
+                {"type": "osmosis/gamm/swap-exact-amount-in", "value": {"sender": "osmo1sender"}},
+                {"type": "cosmos-sdk/MsgSend", "value": {"from_address": "osmo1a", "to_address": "osmo1exchange", "amount": []}}
+            ],
+            "sequence": "0"
+        })
+        .to_string();
+        let options = VisualSignOptions {
+            memo_requirements: Some(std::sync::Arc::new(
+                MemoRequiredDestinations::new()
+                    .with_destination(Chain::Custom(CHAIN_NAME.to_string()), "osmo1exchange"),
+            )),
+            ..VisualSignOptions::default()
+        };
+        let payload = transaction_string_to_visual_sign(&json, options).unwrap();
+        let labels: Vec<&str> = payload
+            .fields
+            .iter()
+            .map(|field| field.label().as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Network",
+                "Sign Mode",
+                "From",
+                "Account Number",
+                "Sequence",
+                "Fee",
+                "Gas Limit",
+                "Fee Granter",
+                "Expiration",
+                "Message 1",
+                "Message 2",
+                "Missing memo",
+            ]
+        );
+        assert_eq!(payload.fields[5].fallback_text(), "None");
+        assert_eq!(
+            payload.fields[9].fallback_text(),
+            "osmosis/gamm/swap-exact-amount-in"
+        );
+    }
+
+    #[test]
+    fn test_sender_mismatch_and_invalid_docs() {
+        let options = VisualSignOptions {
+            sender: Some(TO.to_string()),
+            ..VisualSignOptions::default()
+        };
+        assert!(matches!(
+            transaction_string_to_visual_sign(&direct_sign_doc(), options),
+            Err(VisualSignError::ValidationError(_))
+        ));
+
+        let error = |data: &str| {
+            CosmosSignDocWrapper::from_string(data)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(r#"{"chain_id": "cosmoshub-4"}"#).contains("Invalid amino sign doc"));
+        assert!(
+            error(
+                r#"{"account_number": "x", "chain_id": "c", "fee": {"gas": "1"}, "msgs": [{"type": "t", "value": {}}], "sequence": "0"}"#
+            )
+            .contains("account_number")
+        );
+        assert!(
+            error(r#"{"account_number": "1", "chain_id": "c", "fee": {"gas": "1"}, "msgs": [], "sequence": "0"}"#)
+                .contains("no messages")
+        );
+        assert!(error("0xzz").contains("hex"));
+        assert!(error("ffff").contains("Invalid SignDoc"));
+    }
+}
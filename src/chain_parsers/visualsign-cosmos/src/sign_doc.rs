@@ -0,0 +1,121 @@
+//! The sign document both signing modes decode to.
+//!
+//! `SIGN_MODE_DIRECT` signs protobuf bytes and `SIGN_MODE_LEGACY_AMINO_JSON` a canonical JSON
+//! document, but both carry the same content: chain, account, sequence, fee, memo and the
+//! messages. Decoding either into [`SignDoc`] lets the payload be built once, so a transaction
+//! looks the same whichever mode the wallet signs it with.
+
+/// How the sign document was encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignMode {
+    /// `SIGN_MODE_DIRECT`: a protobuf `cosmos.tx.v1beta1.SignDoc`
+    Direct,
+    /// `SIGN_MODE_LEGACY_AMINO_JSON`: an amino `StdSignDoc`
+    AminoJson,
+}
+
+impl SignMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignMode::Direct => "Direct (protobuf)",
+            SignMode::AminoJson => "Amino JSON (legacy)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coin {
+    pub denom: String,
+    /// Integer amount in the denom's base unit, e.g. `uatom`
+    pub amount: String,
+}
+
+impl std::fmt::Display for Coin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.denom)
+    }
+}
+
+/// Renders coins as "1000 uatom, 5 uosmo", or "None" when there are none.
+pub fn format_coins(coins: &[Coin]) -> String {
+    if coins.is_empty() {
+        return "None".to_string();
+    }
+    coins
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fee {
+    pub amount: Vec<Coin>,
+    pub gas_limit: u64,
+    /// Account paying the fee instead of the first signer; empty when unset
+    pub payer: String,
+    /// Account whose fee grant pays the fee; empty when unset
+    pub granter: String,
+}
+
+/// A message of the transaction, decoded where its type is known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CosmosMessage {
+    Send {
+        from_address: String,
+        to_address: String,
+        amount: Vec<Coin>,
+    },
+    Delegate {
+        delegator_address: String,
+        validator_address: String,
+        amount: Option<Coin>,
+    },
+    Undelegate {
+        delegator_address: String,
+        validator_address: String,
+        amount: Option<Coin>,
+    },
+    WithdrawDelegatorReward {
+        delegator_address: String,
+        validator_address: String,
+    },
+    /// A message type this parser does not decode, with its value as hex (direct) or JSON
+    /// (amino)
+    Unknown { type_name: String, value: String },
+}
+
+impl CosmosMessage {
+    /// The account that has to sign this message, when the message type is known.
+    pub fn signer(&self) -> Option<&str> {
+        match self {
+            CosmosMessage::Send { from_address, .. } => Some(from_address),
+            CosmosMessage::Delegate {
+                delegator_address, ..
+            }
+            | CosmosMessage::Undelegate {
+                delegator_address, ..
+            }
+            | CosmosMessage::WithdrawDelegatorReward {
+                delegator_address, ..
+            } => Some(delegator_address),
+            CosmosMessage::Unknown { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignDoc {
+    pub sign_mode: SignMode,
+    pub chain_id: String,
+    pub account_number: u64,
+    /// The signer's sequence; a direct sign document shows it only when it has one signer
+    pub sequence: Option<u64>,
+    pub fee: Fee,
+    pub memo: String,
+    /// Block height after which the transaction is rejected; 0 when unset
+    pub timeout_height: u64,
+    pub messages: Vec<CosmosMessage>,
+    /// Type URLs of extension options, which change how the chain processes the transaction
+    pub extension_options: Vec<String>,
+}
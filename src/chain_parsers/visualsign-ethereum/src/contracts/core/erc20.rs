@@ -1,10 +1,14 @@
+use alloy_primitives::{Address, U256};
 use alloy_sol_types::{SolCall, sol};
 use visualsign::{
     AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldAddressV2,
     SignablePayloadFieldAmountV2, SignablePayloadFieldCommon, SignablePayloadFieldListLayout,
-    SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+    SignablePayloadFieldPreviewLayout, SignablePayloadFieldStaticAnnotation,
+    SignablePayloadFieldTextV2,
 };
 
+use crate::registry::ContractRegistry;
+
 sol! {
     interface IERC20 {
         function name() external view returns (string memory);
@@ -21,10 +25,37 @@ sol! {
         event Approval(address indexed owner, address indexed spender, uint256 value);
     }
 }
+/// Approvals of at least 2^252 exceed any realistic token supply, so contracts treat them like
+/// `type(uint256).max` and they are displayed as unlimited
+const UNLIMITED_APPROVAL_THRESHOLD: U256 = U256::from_limbs([0, 0, 0, 1 << 60]);
+
+/// Whether an approval amount is displayed as unlimited
+pub fn is_unlimited_approval(amount: U256) -> bool {
+    amount >= UNLIMITED_APPROVAL_THRESHOLD
+}
+
+/// The token an ERC20 call is sent to, with the registry that knows its decimals
+#[derive(Clone, Copy)]
+pub struct ERC20Token<'a> {
+    pub chain_id: u64,
+    pub address: Address,
+    pub registry: &'a ContractRegistry,
+}
+
 pub struct ERC20Visualizer {}
 
 impl ERC20Visualizer {
     pub fn visualize_tx_commands(&self, input: &[u8]) -> Option<SignablePayloadField> {
+        self.visualize_token_call(input, None)
+    }
+
+    /// Like [`Self::visualize_tx_commands`], with approvals in whole units of `token` when the
+    /// registry knows its decimals
+    pub fn visualize_token_call(
+        &self,
+        input: &[u8],
+        token: Option<ERC20Token<'_>>,
+    ) -> Option<SignablePayloadField> {
         if input.len() < 4 {
             return None;
         }
@@ -46,6 +77,7 @@ impl ERC20Visualizer {
                             memo: None,
                             asset_label: "".to_string(),
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     static_annotation: None,
@@ -103,6 +135,7 @@ impl ERC20Visualizer {
                             memo: None,
                             asset_label: "".to_string(),
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     static_annotation: None,
@@ -122,6 +155,7 @@ impl ERC20Visualizer {
                             memo: None,
                             asset_label: "".to_string(),
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     static_annotation: None,
@@ -186,34 +220,19 @@ impl ERC20Visualizer {
                             memo: None,
                             asset_label: "".to_string(),
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     static_annotation: None,
                     dynamic_annotation: None,
                 });
 
-                // Amount
-                details.push(AnnotatedPayloadField {
-                    signable_payload_field: SignablePayloadField::AmountV2 {
-                        common: SignablePayloadFieldCommon {
-                            fallback_text: call.amount.to_string(),
-                            label: "Amount".to_string(),
-                        },
-                        amount_v2: SignablePayloadFieldAmountV2 {
-                            amount: call.amount.to_string(),
-                            abbreviation: None,
-                        },
-                    },
-                    static_annotation: None,
-                    dynamic_annotation: None,
-                });
+                let (amount_field, amount) = approval_amount(call.amount, token);
+                details.push(amount_field);
 
                 let preview = SignablePayloadField::PreviewLayout {
                     common: SignablePayloadFieldCommon {
-                        fallback_text: format!(
-                            "Approve {:?} to spend {} tokens",
-                            call.spender, call.amount
-                        ),
+                        fallback_text: format!("Approve {:?} to spend {amount}", call.spender),
                         label: "ERC20 Approve".to_string(),
                     },
                     preview_layout: SignablePayloadFieldPreviewLayout {
@@ -221,10 +240,7 @@ impl ERC20Visualizer {
                             text: "ERC20 Approve".to_string(),
                         }),
                         subtitle: Some(SignablePayloadFieldTextV2 {
-                            text: format!(
-                                "Approve {:?} to spend {} tokens",
-                                call.spender, call.amount
-                            ),
+                            text: format!("Approve {:?} to spend {amount}", call.spender),
                         }),
                         condensed: None,
                         expanded: Some(SignablePayloadFieldListLayout { fields: details }),
@@ -250,6 +266,7 @@ impl ERC20Visualizer {
                             memo: None,
                             asset_label: "".to_string(),
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     static_annotation: None,
@@ -291,6 +308,7 @@ impl ERC20Visualizer {
                             memo: None,
                             asset_label: "".to_string(),
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     static_annotation: None,
@@ -309,6 +327,7 @@ impl ERC20Visualizer {
                             memo: None,
                             asset_label: "".to_string(),
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     static_annotation: None,
@@ -427,6 +446,121 @@ impl ERC20Visualizer {
     }
 }
 
+// The `Amount` field of an approval and the amount as the subtitle words it
+fn approval_amount(amount: U256, token: Option<ERC20Token<'_>>) -> (AnnotatedPayloadField, String) {
+    let symbol = token.and_then(|token| {
+        token
+            .registry
+            .get_token_symbol(token.chain_id, token.address)
+    });
+    if is_unlimited_approval(amount) {
+        let text = "UNLIMITED".to_string();
+        let field = AnnotatedPayloadField {
+            signable_payload_field: SignablePayloadField::TextV2 {
+                common: SignablePayloadFieldCommon {
+                    fallback_text: text.clone(),
+                    label: "Amount".to_string(),
+                },
+                text_v2: SignablePayloadFieldTextV2 { text },
+            },
+            static_annotation: Some(SignablePayloadFieldStaticAnnotation {
+                text: format!(
+                    "The spender may transfer all of your {} now and in the future, until the approval is revoked",
+                    symbol.as_deref().unwrap_or("tokens")
+                ),
+            }),
+            dynamic_annotation: None,
+        };
+        let amount = match symbol {
+            Some(symbol) => format!("UNLIMITED {symbol}"),
+            None => "UNLIMITED tokens".to_string(),
+        };
+        return (field, amount);
+    }
+
+    let formatted = token.and_then(|token| {
+        let raw = u128::try_from(amount).ok()?;
+        token
+            .registry
+            .format_token_amount(token.chain_id, token.address, raw)
+    });
+    // Without known decimals the amount stays in raw units
+    let (value, abbreviation, fallback_text, words) = match formatted {
+        Some((value, symbol)) => {
+            let words = format!("{value} {symbol}");
+            (value, Some(symbol), words.clone(), words)
+        }
+        None => (
+            amount.to_string(),
+            None,
+            amount.to_string(),
+            format!("{amount} tokens"),
+        ),
+    };
+    let field = AnnotatedPayloadField {
+        signable_payload_field: SignablePayloadField::AmountV2 {
+            common: SignablePayloadFieldCommon {
+                fallback_text,
+                label: "Amount".to_string(),
+            },
+            amount_v2: SignablePayloadFieldAmountV2 {
+                amount: value,
+                abbreviation,
+            },
+        },
+        static_annotation: None,
+        dynamic_annotation: None,
+    };
+    (field, words)
+}
+
+/// ERC20 calls that move the caller's tokens or let someone else move them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ERC20AssetFlow {
+    Transfer {
+        to: Address,
+        amount: U256,
+    },
+    TransferFrom {
+        from: Address,
+        to: Address,
+        amount: U256,
+    },
+    Approve {
+        spender: Address,
+        amount: U256,
+    },
+}
+
+impl ERC20AssetFlow {
+    /// Decodes `transfer`, `transferFrom` and `approve` calldata; any other call yields `None`
+    pub fn decode(input: &[u8]) -> Option<Self> {
+        let selector = input.get(..4)?;
+        if selector == IERC20::transferCall::SELECTOR {
+            let call = IERC20::transferCall::abi_decode(input).ok()?;
+            Some(Self::Transfer {
+                to: call.to,
+                amount: call.amount,
+            })
+        } else if selector == IERC20::transferFromCall::SELECTOR {
+            let call = IERC20::transferFromCall::abi_decode(input).ok()?;
+            Some(Self::TransferFrom {
+                from: call.from,
+                to: call.to,
+                amount: call.amount,
+            })
+        } else if selector == IERC20::approveCall::SELECTOR {
+            let call = IERC20::approveCall::abi_decode(input).ok()?;
+            Some(Self::Approve {
+                spender: call.spender,
+                amount: call.amount,
+            })
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -454,6 +588,7 @@ mod tests {
                         memo: None,
                         asset_label: "".to_string(),
                         badge_text: None,
+                        name_resolved: None,
                     },
                 },
                 static_annotation: None,
@@ -519,6 +654,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             static_annotation: None,
@@ -536,6 +672,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             static_annotation: None,
@@ -585,6 +722,95 @@ mod tests {
         assert_eq!(&actual, &expected);
     }
 
+    fn approve_preview(
+        amount: U256,
+        token: Option<ERC20Token<'_>>,
+    ) -> (String, AnnotatedPayloadField) {
+        let input = IERC20::approveCall {
+            spender: [0x44u8; 20].into(),
+            amount,
+        }
+        .abi_encode();
+        let Some(SignablePayloadField::PreviewLayout { preview_layout, .. }) =
+            ERC20Visualizer {}.visualize_token_call(&input, token)
+        else {
+            panic!("Expected PreviewLayout");
+        };
+        (
+            preview_layout.subtitle.unwrap().text,
+            preview_layout.expanded.unwrap().fields[1].clone(),
+        )
+    }
+
+    #[test]
+    fn test_approve_unlimited() {
+        for amount in [U256::MAX, U256::MAX - U256::from(1_000_000u64)] {
+            let (subtitle, field) = approve_preview(amount, None);
+            assert!(subtitle.ends_with("to spend UNLIMITED tokens"));
+            assert_eq!(field.signable_payload_field.fallback_text(), "UNLIMITED");
+            assert!(field.static_annotation.is_some());
+        }
+
+        let (subtitle, field) = approve_preview(UNLIMITED_APPROVAL_THRESHOLD - U256::from(1), None);
+        assert!(!subtitle.contains("UNLIMITED"));
+        assert!(field.static_annotation.is_none());
+    }
+
+    #[test]
+    fn test_approve_with_token_decimals() {
+        let address = Address::repeat_byte(0x11);
+        let mut registry = ContractRegistry::new();
+        registry
+            .register_token(
+                1,
+                crate::token_metadata::TokenMetadata {
+                    symbol: "USDC".to_string(),
+                    name: "USD Coin".to_string(),
+                    erc_standard: crate::token_metadata::ErcStandard::Erc20,
+                    contract_address: address.to_string(),
+                    decimals: 6,
+                },
+            )
+            .unwrap();
+        let token = ERC20Token {
+            chain_id: 1,
+            address,
+            registry: &registry,
+        };
+
+        let (subtitle, field) = approve_preview(U256::from(2_500_000u64), Some(token));
+        assert!(subtitle.ends_with("to spend 2.500000 USDC"));
+        let SignablePayloadField::AmountV2 { amount_v2, .. } = field.signable_payload_field else {
+            panic!("Expected AmountV2");
+        };
+        assert_eq!(amount_v2.amount, "2.500000");
+        assert_eq!(amount_v2.abbreviation.as_deref(), Some("USDC"));
+
+        let (subtitle, _) = approve_preview(U256::MAX, Some(token));
+        assert!(subtitle.ends_with("to spend UNLIMITED USDC"));
+    }
+
+    #[test]
+    fn test_decode_asset_flow() {
+        let approve = IERC20::approveCall::abi_encode(&IERC20::approveCall {
+            spender: [0x44u8; 20].into(),
+            amount: U256::MAX,
+        });
+        assert_eq!(
+            ERC20AssetFlow::decode(&approve),
+            Some(ERC20AssetFlow::Approve {
+                spender: [0x44u8; 20].into(),
+                amount: U256::MAX,
+            })
+        );
+
+        let balance_of = IERC20::balanceOfCall::abi_encode(&IERC20::balanceOfCall {
+            account: [0x55u8; 20].into(),
+        });
+        assert_eq!(ERC20AssetFlow::decode(&balance_of), None);
+        assert_eq!(ERC20AssetFlow::decode(&approve[..10]), None);
+    }
+
     #[test]
     fn test_decode_approve() {
         let call = IERC20::approveCall {
@@ -606,6 +832,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             static_annotation: None,
@@ -672,6 +899,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             static_annotation: None,
@@ -722,6 +950,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             static_annotation: None,
@@ -739,6 +968,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             static_annotation: None,
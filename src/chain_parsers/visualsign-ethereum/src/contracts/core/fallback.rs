@@ -1,10 +1,16 @@
 //! Fallback visualizer for unknown/unhandled contract calls
 //!
 //! This visualizer acts as a catch-all for contract calls that don't have
-//! specific visualizers. It displays the raw calldata as hex.
+//! specific visualizers. It displays the raw calldata as hex, along with the probable
+//! function and its decoded arguments when the selector is in [`crate::selectors`].
 
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use visualsign::field_builders::{BYTES_PREVIEW_EDGE, create_bytes_field};
 use visualsign::{SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2};
 
+use crate::protocols::fields::{annotated, preview_field, text_field};
+use crate::selectors;
+
 /// Fallback visualizer that displays raw hex data for unknown contracts
 pub struct FallbackVisualizer;
 
@@ -14,7 +20,8 @@ impl FallbackVisualizer {
         Self
     }
 
-    /// Visualizes unknown contract calldata as hex
+    /// Visualizes unknown contract calldata as hex, or as a bytes field previewing it when it
+    /// is too long to show whole (more than twice [`BYTES_PREVIEW_EDGE`] bytes)
     ///
     /// # Arguments
     /// * `input` - The raw calldata bytes
@@ -22,6 +29,12 @@ impl FallbackVisualizer {
     /// # Returns
     /// A SignablePayloadField containing the hex-encoded calldata
     pub fn visualize_hex(&self, input: &[u8]) -> SignablePayloadField {
+        // Calldata longer than a preview is shown by one; the field still carries every byte
+        let preview =
+            (input.len() > 2 * BYTES_PREVIEW_EDGE).then(|| create_bytes_field("Input Data", input));
+        if let Some(Ok(field)) = preview {
+            return field.signable_payload_field;
+        }
         let hex_data = if input.is_empty() {
             "0x".to_string()
         } else {
@@ -38,6 +51,65 @@ impl FallbackVisualizer {
     }
 }
 
+impl FallbackVisualizer {
+    /// Visualizes calldata whose selector is in the built-in database as the probable
+    /// function, with each argument decoded by its type, followed by the raw hex
+    ///
+    /// Returns None when the selector is unknown. Arguments that do not decode as the
+    /// signature's types are left out, leaving only the function name and the hex.
+    pub fn visualize_probable_call(&self, input: &[u8]) -> Option<SignablePayloadField> {
+        let signature = selectors::lookup(input)?;
+        let (_, params) = selectors::split_signature(signature)?;
+
+        let mut details = Vec::new();
+        let decoded = DynSolType::parse(params)
+            .ok()
+            .and_then(|ty| Some((ty.clone(), ty.abi_decode_params(&input[4..]).ok()?)));
+        if let Some((DynSolType::Tuple(types), DynSolValue::Tuple(values))) = decoded {
+            for (index, (ty, value)) in types.iter().zip(&values).enumerate() {
+                details.push(text_field(
+                    &format!("Argument {} ({ty})", index + 1),
+                    format_value(value),
+                ));
+            }
+        }
+        details.push(annotated(self.visualize_hex(input)));
+
+        Some(preview_field(
+            "Input Data",
+            "Probable Function",
+            signature.to_string(),
+            details,
+        ))
+    }
+}
+
+// Renders a decoded argument; strings are quoted and escaped since calldata is untrusted
+#[allow(unreachable_patterns)] // `CustomStruct` only exists with alloy's eip712 feature
+fn format_value(value: &DynSolValue) -> String {
+    let list = |values: &[DynSolValue]| {
+        values
+            .iter()
+            .map(format_value)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("0x{}", hex::encode(&word[..*size])),
+        DynSolValue::Address(address) => address.to_string(),
+        DynSolValue::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        DynSolValue::String(value) => format!("{value:?}"),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", list(values))
+        }
+        DynSolValue::Tuple(values) => format!("({})", list(values)),
+        other => format!("0x{}", hex::encode(other.abi_encode())),
+    }
+}
+
 impl Default for FallbackVisualizer {
     fn default() -> Self {
         Self::new()
@@ -76,6 +148,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_visualize_probable_call() {
+        let visualizer = FallbackVisualizer::new();
+        // transferOwnership(address) with 0x1111…1111
+        let mut input = vec![0xf2, 0xfd, 0xe3, 0x8b];
+        input.extend([0u8; 12]);
+        input.extend([0x11u8; 20]);
+
+        let field = visualizer.visualize_probable_call(&input).unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            "Probable Function: transferOwnership(address)"
+        );
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        let details = preview_layout.expanded.unwrap().fields;
+        assert_eq!(
+            details[0].signable_payload_field.label(),
+            "Argument 1 (address)"
+        );
+        assert_eq!(
+            details[0].signable_payload_field.fallback_text(),
+            "0x1111111111111111111111111111111111111111"
+        );
+        assert_eq!(details[1].signable_payload_field.label(), "Input Data");
+
+        // Arguments that do not decode still name the function
+        let field = visualizer.visualize_probable_call(&input[..8]).unwrap();
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        assert_eq!(preview_layout.expanded.unwrap().fields.len(), 1);
+
+        assert_eq!(
+            visualizer.visualize_probable_call(&[0xde, 0xad, 0xbe, 0xef]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_visualize_long_hex_data() {
+        let visualizer = FallbackVisualizer::new();
+        let mut input = vec![0xa9, 0x05, 0x9c, 0xbb];
+        input.extend([0u8; 64]);
+        let field = visualizer.visualize_hex(&input);
+
+        match field {
+            SignablePayloadField::Bytes { bytes, common } => {
+                assert_eq!(common.label, "Input Data");
+                assert_eq!(bytes.hex, format!("0x{}", hex::encode(&input)));
+                assert_eq!(bytes.length, "68");
+                assert_eq!(bytes.preview, "0xa9059cbb00000000...0000000000000000");
+            }
+            _ => panic!("Expected Bytes field"),
+        }
+    }
+
     #[test]
     fn test_visualize_function_selector() {
         let visualizer = FallbackVisualizer::new();
@@ -4,6 +4,6 @@ pub mod erc20;
 pub mod erc721;
 pub mod fallback;
 
-pub use erc20::ERC20Visualizer;
+pub use erc20::{ERC20AssetFlow, ERC20Token, ERC20Visualizer, is_unlimited_approval};
 pub use erc721::ERC721Visualizer;
 pub use fallback::FallbackVisualizer;
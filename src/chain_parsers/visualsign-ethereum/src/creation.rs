@@ -0,0 +1,126 @@
+//! Fields of a contract creation, a transaction without `to`.
+//!
+//! Its input is the init code of the new contract: bytecode run once to return the code stored
+//! at the new address. No ABI describes it, so it is shown as bytes and counts as undecoded.
+//! Where the new contract lands depends on how it is deployed; when the host supplies the
+//! factory and salt of a CREATE2 deployment as the [`Create2Deployment`] extension, the address
+//! is predicted from them and the hash of the init code.
+
+use alloy_primitives::{Address, B256};
+use visualsign::extensions::Create2Deployment;
+use visualsign::field_builders::{create_address_field, create_bytes_field};
+use visualsign::{SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2};
+
+/// Text of the "To" field of a contract creation
+pub const CREATES_NEW_CONTRACT: &str = "Creates New Contract";
+
+pub const INIT_CODE_LABEL: &str = "Init Code";
+
+/// The "To" field of a contract creation, saying that the transaction deploys a contract
+/// instead of leaving the destination out.
+pub fn creates_new_contract_field() -> SignablePayloadField {
+    SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: CREATES_NEW_CONTRACT.to_string(),
+            label: "To".to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 {
+            text: CREATES_NEW_CONTRACT.to_string(),
+        },
+    }
+}
+
+pub fn init_code_size_field(init_code: &[u8]) -> SignablePayloadField {
+    let size = format!("{} bytes", init_code.len());
+    SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: size.clone(),
+            label: "Init Code Size".to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 { text: size },
+    }
+}
+
+/// The init code as a bytes field, previewed when it is long.
+pub fn init_code_field(init_code: &[u8]) -> Option<SignablePayloadField> {
+    create_bytes_field(INIT_CODE_LABEL, init_code)
+        .ok()
+        .map(|field| field.signable_payload_field)
+}
+
+/// Address `deployment` puts `init_code` at, per EIP-1014:
+/// `keccak256(0xff ++ factory ++ salt ++ keccak256(init_code))[12..]`.
+pub fn create2_address(
+    deployment: &Create2Deployment,
+    init_code: &[u8],
+) -> Result<Address, String> {
+    let factory: Address = deployment
+        .factory
+        .parse()
+        .map_err(|e| format!("Invalid CREATE2 factory {}: {e}", deployment.factory))?;
+    let salt: B256 = deployment
+        .salt
+        .parse()
+        .map_err(|e| format!("Invalid CREATE2 salt {}: {e}", deployment.salt))?;
+    Ok(factory.create2_from_code(salt, init_code))
+}
+
+/// The predicted address of the new contract, badged as computed rather than read from the
+/// transaction.
+pub fn predicted_address_field(address: Address) -> Option<SignablePayloadField> {
+    create_address_field(
+        "Predicted Contract Address",
+        &address.to_string(),
+        None,
+        None,
+        None,
+        Some("CREATE2"),
+    )
+    .ok()
+    .map(|field| field.signable_payload_field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(factory: &str, salt: &str) -> Create2Deployment {
+        Create2Deployment {
+            factory: factory.to_string(),
+            salt: salt.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_create2_address() {
+        // Examples 0 and 1 of EIP-1014
+        let zero_salt = format!("0x{}", "00".repeat(32));
+        assert_eq!(
+            create2_address(
+                &deployment("0x0000000000000000000000000000000000000000", &zero_salt),
+                &[0x00],
+            )
+            .unwrap()
+            .to_string(),
+            "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"
+        );
+        assert_eq!(
+            create2_address(
+                &deployment("0xdeadbeef00000000000000000000000000000000", &zero_salt),
+                &[0x00],
+            )
+            .unwrap()
+            .to_string(),
+            "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3"
+        );
+
+        let error = create2_address(&deployment("0xdeadbeef", &zero_salt), &[0x00]).unwrap_err();
+        assert!(error.starts_with("Invalid CREATE2 factory 0xdeadbeef"));
+        let error = create2_address(
+            &deployment("0xdeadbeef00000000000000000000000000000000", "0x01"),
+            &[0x00],
+        )
+        .unwrap_err();
+        assert!(error.starts_with("Invalid CREATE2 salt 0x01"));
+    }
+}
@@ -1,19 +1,17 @@
-use alloy_primitives::utils::{ParseUnits, format_units};
-fn trim_trailing_zeros(s: String) -> String {
-    if s.contains('.') {
-        s.trim_end_matches('0').trim_end_matches('.').to_string()
-    } else {
-        s.to_string()
-    }
-}
+use visualsign::fixed_point::format_fixed_point_digits;
 
 // Helper function to format wei to ether
-pub fn format_ether<T: Into<ParseUnits> + ToString + Copy>(wei: T) -> String {
-    trim_trailing_zeros(format_units(wei, "eth").unwrap_or_else(|_| wei.to_string()))
+pub fn format_ether<T: ToString>(wei: T) -> String {
+    format_wei(&wei.to_string(), 18)
 }
 // Helper function to format wei to gwei
-pub fn format_gwei<T: Into<ParseUnits> + ToString + Copy>(wei: T) -> String {
-    trim_trailing_zeros(format_units(wei, "gwei").unwrap_or_else(|_| wei.to_string()))
+pub fn format_gwei<T: ToString>(wei: T) -> String {
+    format_wei(&wei.to_string(), 9)
+}
+
+// Falls back to the raw wei string for values that are not plain integers
+fn format_wei(wei: &str, decimals: u8) -> String {
+    format_fixed_point_digits(wei, decimals).unwrap_or_else(|_| wei.to_string())
 }
 #[cfg(test)]
 mod tests {
@@ -75,6 +73,14 @@ mod tests {
         assert_eq!("123.456789", format_ether(wei));
     }
 
+    #[test]
+    fn test_format_ether_u256_max() {
+        assert_eq!(
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+            format_ether(alloy_primitives::U256::MAX)
+        );
+    }
+
     #[test]
     fn test_format_gwei_large_value() {
         let wei = 123_456_789_000u128;
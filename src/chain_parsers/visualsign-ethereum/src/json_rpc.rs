@@ -0,0 +1,317 @@
+//! Transactions given as a JSON-RPC transaction object.
+//!
+//! dApps hand wallets the `eth_sendTransaction` parameter, which WalletConnect also forwards
+//! for `eth_sendTransaction` and `eth_signTransaction`, rather than RLP bytes:
+//! `{"from":"0x…","to":"0x…","value":"0x…","data":"0x…","gas":"0x…",…}` with hex quantities.
+//! [`TransactionRequest`] turns that object into the same [`TypedTransaction`] an RLP encoding
+//! decodes to. Its `from` is only what the request claims, since nothing is signed yet.
+//!
+//! Fields a wallet fills in before signing (gas, fees, nonce and, for EIP-1559, the chain id)
+//! are required: the signer has to see the values that will actually be signed.
+
+use alloy_consensus::{TxEip1559, TxLegacy, TypedTransaction};
+use alloy_eips::eip2930::{AccessList, AccessListItem};
+use alloy_primitives::{Address, B256, Bytes, TxKind, U256};
+use serde::Deserialize;
+
+use crate::EthereumParserError;
+
+/// A hex quantity such as `"0x5208"`; some wallets send small values as JSON numbers instead.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Quantity {
+    Hex(String),
+    Number(u64),
+}
+
+impl Quantity {
+    fn to_u256(&self, name: &str) -> Result<U256, EthereumParserError> {
+        match self {
+            Quantity::Number(number) => Ok(U256::from(*number)),
+            Quantity::Hex(hex) => {
+                let digits = hex.strip_prefix("0x").ok_or_else(|| {
+                    invalid(format!(
+                        "{name} must be a 0x-prefixed hex quantity, got {hex:?}"
+                    ))
+                })?;
+                if digits.is_empty() {
+                    return Ok(U256::ZERO);
+                }
+                U256::from_str_radix(digits, 16)
+                    .map_err(|e| invalid(format!("Invalid {name} {hex:?}: {e}")))
+            }
+        }
+    }
+
+    fn to_u64(&self, name: &str) -> Result<u64, EthereumParserError> {
+        u64::try_from(self.to_u256(name)?).map_err(|_| invalid(format!("{name} is too large")))
+    }
+
+    fn to_u128(&self, name: &str) -> Result<u128, EthereumParserError> {
+        u128::try_from(self.to_u256(name)?).map_err(|_| invalid(format!("{name} is too large")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListEntry {
+    pub address: String,
+    #[serde(default)]
+    pub storage_keys: Vec<String>,
+}
+
+/// The transaction object of an `eth_sendTransaction` or `eth_signTransaction` request.
+///
+/// Unknown keys are ignored, as wallets and dApps add their own.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRequest {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub value: Option<Quantity>,
+    pub data: Option<String>,
+    /// Newer name for `data`; either may be sent
+    pub input: Option<String>,
+    pub gas: Option<Quantity>,
+    /// Name some libraries use instead of `gas`
+    pub gas_limit: Option<Quantity>,
+    pub gas_price: Option<Quantity>,
+    pub max_fee_per_gas: Option<Quantity>,
+    pub max_priority_fee_per_gas: Option<Quantity>,
+    pub nonce: Option<Quantity>,
+    pub chain_id: Option<Quantity>,
+    #[serde(rename = "type")]
+    pub transaction_type: Option<Quantity>,
+    pub access_list: Option<Vec<AccessListEntry>>,
+}
+
+impl TransactionRequest {
+    pub fn from_json(json: &str) -> Result<Self, EthereumParserError> {
+        serde_json::from_str(json)
+            .map_err(|e| invalid(format!("Invalid JSON-RPC transaction object: {e}")))
+    }
+
+    /// The sender the request names in `from`, if any.
+    pub fn sender(&self) -> Result<Option<Address>, EthereumParserError> {
+        self.from
+            .as_deref()
+            .map(|from| address("from", from))
+            .transpose()
+    }
+
+    /// Builds the unsigned transaction the request describes.
+    ///
+    /// Without an explicit `type`, EIP-1559 fee fields select an EIP-1559 transaction and a
+    /// `gasPrice` a legacy one.
+    pub fn to_transaction(&self) -> Result<TypedTransaction, EthereumParserError> {
+        let transaction_type = match &self.transaction_type {
+            Some(ty) => ty.to_u64("type")?,
+            None if self.max_fee_per_gas.is_some() || self.max_priority_fee_per_gas.is_some() => 2,
+            None => 0,
+        };
+
+        let to = match self.to.as_deref() {
+            None | Some("") => TxKind::Create,
+            Some(to) => TxKind::Call(address("to", to)?),
+        };
+        let value = self
+            .value
+            .as_ref()
+            .map_or(Ok(U256::ZERO), |value| value.to_u256("value"))?;
+        let input = self.input()?;
+        let gas_limit = self
+            .gas
+            .as_ref()
+            .or(self.gas_limit.as_ref())
+            .ok_or_else(|| missing("gas"))?
+            .to_u64("gas")?;
+        let nonce = required(&self.nonce, "nonce")?.to_u64("nonce")?;
+        let chain_id = self
+            .chain_id
+            .as_ref()
+            .map(|chain_id| chain_id.to_u64("chainId"))
+            .transpose()?;
+
+        match transaction_type {
+            0 => Ok(TypedTransaction::Legacy(TxLegacy {
+                chain_id,
+                nonce,
+                gas_price: required(&self.gas_price, "gasPrice")?.to_u128("gasPrice")?,
+                gas_limit,
+                to,
+                value,
+                input,
+            })),
+            2 => Ok(TypedTransaction::Eip1559(TxEip1559 {
+                chain_id: chain_id.ok_or_else(|| missing("chainId"))?,
+                nonce,
+                gas_limit,
+                max_fee_per_gas: required(&self.max_fee_per_gas, "maxFeePerGas")?
+                    .to_u128("maxFeePerGas")?,
+                max_priority_fee_per_gas: required(
+                    &self.max_priority_fee_per_gas,
+                    "maxPriorityFeePerGas",
+                )?
+                .to_u128("maxPriorityFeePerGas")?,
+                to,
+                value,
+                access_list: self.access_list()?,
+                input,
+            })),
+            other => Err(EthereumParserError::UnsupportedTransactionType(format!(
+                "JSON-RPC transaction type {other}"
+            ))),
+        }
+    }
+
+    fn input(&self) -> Result<Bytes, EthereumParserError> {
+        let data = self
+            .data
+            .as_deref()
+            .map(|data| hex_bytes("data", data))
+            .transpose()?;
+        let input = self
+            .input
+            .as_deref()
+            .map(|input| hex_bytes("input", input))
+            .transpose()?;
+        match (data, input) {
+            (Some(data), Some(input)) if data != input => Err(invalid(
+                "data and input are both set and differ".to_string(),
+            )),
+            (data, input) => Ok(data.or(input).unwrap_or_default()),
+        }
+    }
+
+    fn access_list(&self) -> Result<AccessList, EthereumParserError> {
+        let items = self
+            .access_list
+            .iter()
+            .flatten()
+            .map(|entry| {
+                let storage_keys = entry
+                    .storage_keys
+                    .iter()
+                    .map(|key| {
+                        key.parse::<B256>()
+                            .map_err(|e| invalid(format!("Invalid storage key {key:?}: {e}")))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(AccessListItem {
+                    address: address("accessList address", &entry.address)?,
+                    storage_keys,
+                })
+            })
+            .collect::<Result<Vec<_>, EthereumParserError>>()?;
+        Ok(AccessList(items))
+    }
+}
+
+fn invalid(message: String) -> EthereumParserError {
+    EthereumParserError::FailedToDecodeTransaction(message)
+}
+
+fn missing(name: &str) -> EthereumParserError {
+    invalid(format!("Transaction request has no {name}"))
+}
+
+fn required<'a>(
+    quantity: &'a Option<Quantity>,
+    name: &str,
+) -> Result<&'a Quantity, EthereumParserError> {
+    quantity.as_ref().ok_or_else(|| missing(name))
+}
+
+fn address(name: &str, address: &str) -> Result<Address, EthereumParserError> {
+    address
+        .parse()
+        .map_err(|e| invalid(format!("Invalid {name} address {address:?}: {e}")))
+}
+
+fn hex_bytes(name: &str, data: &str) -> Result<Bytes, EthereumParserError> {
+    let digits = data.strip_prefix("0x").unwrap_or(data);
+    hex::decode(digits)
+        .map(Bytes::from)
+        .map_err(|e| invalid(format!("Invalid {name} {data:?}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Transaction as _;
+
+    #[test]
+    fn test_eip1559_request() {
+        let request = TransactionRequest::from_json(
+            r#"{
+                "from": "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+                "to": "0x3535353535353535353535353535353535353535",
+                "value": "0xde0b6b3a7640000",
+                "data": "0x",
+                "gas": "0x5208",
+                "maxFeePerGas": "0x6fc23ac00",
+                "maxPriorityFeePerGas": "0x77359400",
+                "nonce": "0x1",
+                "chainId": 1,
+                "accessList": [{"address": "0x3535353535353535353535353535353535353535", "storageKeys": []}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request.sender().unwrap(),
+            Some(
+                "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+                    .parse()
+                    .unwrap()
+            )
+        );
+
+        let TypedTransaction::Eip1559(tx) = request.to_transaction().unwrap() else {
+            panic!("Expected an EIP-1559 transaction");
+        };
+        assert_eq!(tx.chain_id, 1);
+        assert_eq!(tx.nonce, 1);
+        assert_eq!(tx.gas_limit, 21000);
+        assert_eq!(tx.max_fee_per_gas, 30_000_000_000);
+        assert_eq!(tx.max_priority_fee_per_gas, 2_000_000_000);
+        assert_eq!(tx.value, U256::from(1_000_000_000_000_000_000u64));
+        assert_eq!(tx.access_list.0.len(), 1);
+        assert!(tx.input.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_request_and_contract_creation() {
+        let request = TransactionRequest::from_json(
+            r#"{"input": "0x6080", "gasLimit": "0x30d40", "gasPrice": "0x4a817c800", "nonce": "0x0"}"#,
+        )
+        .unwrap();
+        let tx = request.to_transaction().unwrap();
+        assert!(matches!(tx, TypedTransaction::Legacy(_)));
+        assert_eq!(tx.kind(), TxKind::Create);
+        assert_eq!(tx.chain_id(), None);
+        assert_eq!(tx.input().as_ref(), [0x60, 0x80]);
+        assert_eq!(request.sender().unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        let error = |json: &str| {
+            TransactionRequest::from_json(json)
+                .and_then(|request| request.to_transaction())
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(r#"{"gasPrice": "0x1", "nonce": "0x0"}"#).contains("no gas"));
+        assert!(
+            error(r#"{"gas": "0x1", "maxFeePerGas": "0x1", "maxPriorityFeePerGas": "0x1", "nonce": "0x0"}"#)
+                .contains("no chainId")
+        );
+        assert!(error(r#"{"gas": "21000", "gasPrice": "0x1", "nonce": "0x0"}"#).contains("hex"));
+        assert!(
+            error(r#"{"gas": "0x1", "gasPrice": "0x1", "nonce": "0x0", "data": "0x01", "input": "0x02"}"#)
+                .contains("differ")
+        );
+        assert!(error(r#"{"gas": "0x1", "nonce": "0x0", "type": "0x3"}"#).contains("type 3"));
+        assert!(error("[]").contains("Invalid JSON-RPC transaction object"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Fee context for OP Stack and Arbitrum rollups.
+//!
+//! On a rollup the gas fields alone do not describe what a transaction costs. OP Stack chains
+//! charge an L1 data fee on top of the L2 execution fee, priced by the L1 base and blob fees
+//! when the batch is posted; Arbitrum folds the L1 cost into the gas used, so the gas limit
+//! covers both. Neither L1 price is known offline, so the fields below show the inputs the fee
+//! is computed from (the size of the transaction data posted to L1) together with how the
+//! sequencer charges, and name the chain's own gas token where it is not ETH.
+
+use alloy_consensus::{SignableTransaction, Transaction as _, TypedTransaction};
+use visualsign::{SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2};
+
+/// Symbol of the native token on Ethereum and on chains without an entry below
+pub const DEFAULT_NATIVE_SYMBOL: &str = "ETH";
+
+// RLP-encoded y-parity (or v), r and s
+const SIGNATURE_SIZE: usize = 1 + 2 * 33;
+
+/// Rollup framework a chain is built on, which decides how its fees are charged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupStack {
+    OpStack,
+    Arbitrum,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L2Chain {
+    pub stack: RollupStack,
+    /// Token gas is paid in, e.g. "MNT" on Mantle
+    pub native_symbol: &'static str,
+}
+
+/// The rollup `chain_id` belongs to, for the OP Stack and Arbitrum chains this parser knows.
+pub fn l2_chain(chain_id: Option<u64>) -> Option<L2Chain> {
+    let (stack, native_symbol) = match chain_id? {
+        // OP Mainnet, Base, Zora, Mode, World Chain, Ink, Unichain, Lisk, Soneium and testnets
+        10 | 8453 | 7777777 | 34443 | 480 | 57073 | 130 | 1135 | 1868 | 11155420 | 84532 => {
+            (RollupStack::OpStack, DEFAULT_NATIVE_SYMBOL)
+        }
+        252 => (RollupStack::OpStack, "frxETH"),
+        5000 => (RollupStack::OpStack, "MNT"),
+        42220 => (RollupStack::OpStack, "CELO"),
+        // Arbitrum One, Arbitrum Nova and Arbitrum Sepolia
+        42161 | 42170 | 421614 => (RollupStack::Arbitrum, DEFAULT_NATIVE_SYMBOL),
+        660279 => (RollupStack::Arbitrum, "XAI"),
+        33139 => (RollupStack::Arbitrum, "APE"),
+        _ => return None,
+    };
+    Some(L2Chain {
+        stack,
+        native_symbol,
+    })
+}
+
+/// Symbol of the token value and gas are paid in on `chain_id`.
+pub fn native_symbol(chain_id: Option<u64>) -> &'static str {
+    l2_chain(chain_id).map_or(DEFAULT_NATIVE_SYMBOL, |chain| chain.native_symbol)
+}
+
+/// Label for the per-gas price: on OP Stack chains it only prices L2 execution.
+pub fn gas_price_label(chain_id: Option<u64>) -> &'static str {
+    match l2_chain(chain_id) {
+        Some(L2Chain {
+            stack: RollupStack::OpStack,
+            ..
+        }) => "L2 Gas Price",
+        _ => "Gas Price",
+    }
+}
+
+/// Fee model, L1 data inputs and sequencer context for a transaction on `chain`.
+pub fn create_l2_fee_fields(
+    chain: &L2Chain,
+    transaction: &TypedTransaction,
+) -> Vec<SignablePayloadField> {
+    let symbol = chain.native_symbol;
+    let (fee_model, sequencer) = match chain.stack {
+        RollupStack::OpStack => (
+            format!(
+                "OP Stack: L2 execution fee plus an L1 data fee in {symbol}, priced on posting"
+            ),
+            "Orders transactions by priority fee; the priority fee is paid to the sequencer",
+        ),
+        RollupStack::Arbitrum => (
+            "Arbitrum: the gas limit also pays for posting the transaction to L1".to_string(),
+            "Orders transactions first come, first served; priority fees are ignored",
+        ),
+    };
+
+    // What the rollup posts to L1 is the signed encoding, i.e. this one plus the signature
+    let mut encoded = Vec::new();
+    transaction.encode_for_signing(&mut encoded);
+    let encoded_size = encoded.len() + SIGNATURE_SIZE;
+    let input = transaction.input();
+    let zero_bytes = input.iter().filter(|byte| **byte == 0).count();
+
+    vec![
+        text_field("Fee Model", &fee_model),
+        text_field(
+            "L1 Data Size",
+            &format!("About {encoded_size} bytes once signed"),
+        ),
+        text_field(
+            "Calldata Bytes",
+            &format!("{} non-zero, {zero_bytes} zero", input.len() - zero_bytes),
+        ),
+        text_field("Sequencer", sequencer),
+    ]
+}
+
+fn text_field(label: &str, text: &str) -> SignablePayloadField {
+    SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: text.to_string(),
+            label: label.to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 {
+            text: text.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::{Address, Bytes, ChainId, TxKind, U256};
+
+    fn labelled(fields: &[SignablePayloadField]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|field| format!("{}: {}", field.label(), field.fallback_text()))
+            .collect()
+    }
+
+    #[test]
+    fn test_l2_chain_lookup() {
+        assert_eq!(l2_chain(Some(1)), None);
+        assert_eq!(l2_chain(None), None);
+        assert_eq!(l2_chain(Some(8453)).unwrap().stack, RollupStack::OpStack);
+        assert_eq!(l2_chain(Some(42161)).unwrap().stack, RollupStack::Arbitrum);
+        assert_eq!(native_symbol(Some(1)), "ETH");
+        assert_eq!(native_symbol(Some(10)), "ETH");
+        assert_eq!(native_symbol(Some(5000)), "MNT");
+        assert_eq!(gas_price_label(Some(10)), "L2 Gas Price");
+        assert_eq!(gas_price_label(Some(42161)), "Gas Price");
+    }
+
+    #[test]
+    fn test_l2_fee_fields() {
+        let tx = TypedTransaction::Eip1559(TxEip1559 {
+            chain_id: ChainId::from(10u64),
+            nonce: 0,
+            gas_limit: 21000,
+            max_fee_per_gas: 1_000_000_000u128,
+            max_priority_fee_per_gas: 1_000_000u128,
+            to: TxKind::Call(Address::ZERO),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: Bytes::from(vec![0xa9, 0x05, 0x00, 0x00]),
+        });
+        let mut encoded = Vec::new();
+        tx.encode_for_signing(&mut encoded);
+
+        let fields = create_l2_fee_fields(&l2_chain(Some(10)).unwrap(), &tx);
+        assert_eq!(
+            labelled(&fields),
+            vec![
+                "Fee Model: OP Stack: L2 execution fee plus an L1 data fee in ETH, priced on posting"
+                    .to_string(),
+                format!("L1 Data Size: About {} bytes once signed", encoded.len() + 67),
+                "Calldata Bytes: 2 non-zero, 2 zero".to_string(),
+                "Sequencer: Orders transactions by priority fee; the priority fee is paid to the sequencer"
+                    .to_string(),
+            ]
+        );
+
+        let fields = create_l2_fee_fields(&l2_chain(Some(42161)).unwrap(), &tx);
+        assert_eq!(
+            fields[0].fallback_text(),
+            "Arbitrum: the gas limit also pays for posting the transaction to L1"
+        );
+    }
+}
@@ -1,39 +1,122 @@
 use std::sync::Arc;
 
 use crate::fmt::{format_ether, format_gwei};
-use alloy_consensus::{Transaction as _, TxType, TypedTransaction};
-use alloy_rlp::{Buf, Decodable};
+use alloy_consensus::{
+    SignableTransaction, Transaction as _, TxType, TypedTransaction,
+    transaction::{RlpEcdsaDecodableTx, RlpEcdsaEncodableTx},
+};
+use alloy_primitives::{Address, Signature};
+use alloy_rlp::{Buf, Decodable, Header};
 use base64::{Engine as _, engine::general_purpose::STANDARD as b64};
 use visualsign::{
     SignablePayload, SignablePayloadField, SignablePayloadFieldAddressV2,
     SignablePayloadFieldAmountV2, SignablePayloadFieldCommon, SignablePayloadFieldTextV2,
+    capabilities::ParserCapabilities,
+    chain_id::chain_id_warning,
+    coverage::Coverage,
     encodings::SupportedEncodings,
+    errors::{ParserError, ParserErrorKind},
+    extensions::{Create2Deployment, ExpectedChainId, ExpectedNonce},
+    field_builders::Section,
+    layout::{PreviewTemplate, package_preview_layout},
+    lossless,
+    nonce::nonce_warning,
+    outcome::{ParseOutcome, ParseWarning, ParseWarningKind},
+    parser_info::ParserInfo,
+    provenance::Provenance,
     registry::LayeredRegistry,
+    replay::{ReplayProtection, create_replay_protection_field},
+    sender::{Sender, SenderSource, create_sender_field, resolve_sender},
     vsptrait::{
         Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
         VisualSignError, VisualSignOptions,
     },
 };
 
+use crate::provenance::RawItem;
+
 pub mod chains;
 pub mod context;
 pub mod contracts;
+pub mod creation;
 pub mod fmt;
+pub mod json_rpc;
+pub mod l2;
+pub mod personal_sign;
 pub mod protocols;
+pub mod provenance;
 pub mod registry;
+pub mod selectors;
+pub mod summary;
 pub mod token_metadata;
 pub mod visualizer;
 
 #[derive(Debug, Eq, PartialEq, thiserror::Error)]
 pub enum EthereumParserError {
-    #[error("Unexpected trailing data: {0}")]
-    UnexpectedTrailingData(String),
+    #[error("Unexpected trailing data: {data}")]
+    UnexpectedTrailingData { offset: usize, data: String },
     #[error("Unexpected transaction type: {0}")]
     UnexpectedTransactionType(String),
     #[error("Unsupported transaction type: {0}")]
     UnsupportedTransactionType(String),
     #[error("Failed to decode transaction: {0}")]
     FailedToDecodeTransaction(String),
+    #[error("Truncated transaction: {0}")]
+    TruncatedTransaction(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+}
+
+impl From<EthereumParserError> for ParserError {
+    fn from(error: EthereumParserError) -> Self {
+        let offset = match &error {
+            EthereumParserError::UnexpectedTrailingData { offset, .. } => Some(*offset),
+            _ => None,
+        };
+        let (kind, reason) = match &error {
+            EthereumParserError::UnexpectedTrailingData { .. } => {
+                (ParserErrorKind::Encoding, "UnexpectedTrailingData")
+            }
+            EthereumParserError::UnexpectedTransactionType(_) => (
+                ParserErrorKind::InternalInvariant,
+                "UnexpectedTransactionType",
+            ),
+            EthereumParserError::UnsupportedTransactionType(_) => (
+                ParserErrorKind::UnsupportedVariant,
+                "UnsupportedTransactionType",
+            ),
+            EthereumParserError::FailedToDecodeTransaction(_) => {
+                (ParserErrorKind::Encoding, "FailedToDecodeTransaction")
+            }
+            EthereumParserError::TruncatedTransaction(_) => {
+                (ParserErrorKind::Truncated, "TruncatedTransaction")
+            }
+            EthereumParserError::InvalidSignature(_) => {
+                (ParserErrorKind::Encoding, "InvalidSignature")
+            }
+        };
+        let detail = ParserError::new(kind, "Ethereum", reason, error.to_string());
+        match offset {
+            Some(offset) => detail.with_offset(offset),
+            None => detail,
+        }
+    }
+}
+
+impl From<EthereumParserError> for TransactionParseError {
+    fn from(error: EthereumParserError) -> Self {
+        TransactionParseError::Parser(error.into())
+    }
+}
+
+// Running out of input is reported as truncation, every other RLP failure as bad encoding
+fn rlp_error(error: alloy_rlp::Error) -> EthereumParserError {
+    match error {
+        alloy_rlp::Error::InputTooShort => {
+            EthereumParserError::TruncatedTransaction(error.to_string())
+        }
+        _ => EthereumParserError::FailedToDecodeTransaction(error.to_string()),
+    }
 }
 
 // Helper function to extract gas price from different transaction types
@@ -84,34 +167,126 @@ fn create_priority_fee_field(max_priority_fee_per_gas: u128) -> SignablePayloadF
 }
 
 /// Wrapper around Alloy's transaction type that implements the Transaction trait
+///
+/// Both unsigned encodings and signed raw transactions are accepted; for a signed one the
+/// signature is kept and the sender recovered from it. A JSON-RPC transaction object (see
+/// [`json_rpc`]) is accepted too, keeping the sender its `from` claims.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct EthereumTransactionWrapper {
     transaction: TypedTransaction,
+    signer: Option<(Signature, Address)>,
+    requested_sender: Option<Address>,
+    raw: Option<Vec<u8>>,
 }
 
 impl Transaction for EthereumTransactionWrapper {
     fn from_string(data: &str) -> Result<Self, TransactionParseError> {
+        if data.trim_start().starts_with('{') {
+            return Ok(Self::from_request(
+                &json_rpc::TransactionRequest::from_json(data)?,
+            )?);
+        }
         let format = if data.starts_with("0x") {
             SupportedEncodings::Hex
         } else {
             visualsign::encodings::SupportedEncodings::detect(data)
         };
-        let transaction = decode_transaction(data, format)
-            .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?;
-        Ok(Self { transaction })
+        let raw = decode_encoded_bytes(data, format)?;
+        let (transaction, signature) = decode_transaction_bytes(&raw)?;
+        let wrapper = match signature {
+            Some(signature) => Self::new_signed(transaction, signature)?,
+            None => Self::new(transaction),
+        };
+        Ok(Self {
+            raw: Some(raw),
+            ..wrapper
+        })
     }
     fn transaction_type(&self) -> String {
         "Ethereum".to_string()
     }
+
+    fn verify_lossless(&self, _data: &str) -> Result<(), TransactionParseError> {
+        // JSON-RPC objects have no byte encoding to compare against
+        let (Some(raw), Some(reencoded)) = (self.raw(), self.reencode()) else {
+            return Ok(());
+        };
+        Ok(lossless::check_reencoding("Ethereum", raw, &reencoded)?)
+    }
 }
 
 impl EthereumTransactionWrapper {
     pub fn new(transaction: TypedTransaction) -> Self {
-        Self { transaction }
+        Self {
+            transaction,
+            signer: None,
+            requested_sender: None,
+            raw: None,
+        }
+    }
+    /// Wraps a transaction that was already signed, recovering its sender from `signature`.
+    pub fn new_signed(
+        transaction: TypedTransaction,
+        signature: Signature,
+    ) -> Result<Self, EthereumParserError> {
+        let sender = signature
+            .recover_address_from_prehash(&transaction.signature_hash())
+            .map_err(|e| EthereumParserError::InvalidSignature(e.to_string()))?;
+        Ok(Self {
+            transaction,
+            signer: Some((signature, sender)),
+            requested_sender: None,
+            raw: None,
+        })
+    }
+    /// Wraps the transaction a JSON-RPC transaction object describes.
+    pub fn from_request(
+        request: &json_rpc::TransactionRequest,
+    ) -> Result<Self, EthereumParserError> {
+        Ok(Self {
+            transaction: request.to_transaction()?,
+            signer: None,
+            requested_sender: request.sender()?,
+            raw: None,
+        })
     }
     pub fn inner(&self) -> &TypedTransaction {
         &self.transaction
     }
+    /// The signature of a signed raw transaction.
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signer.as_ref().map(|(signature, _)| signature)
+    }
+    /// The sender recovered from the signature of a signed raw transaction.
+    pub fn sender(&self) -> Option<Address> {
+        self.signer.as_ref().map(|(_, sender)| *sender)
+    }
+    /// The `from` address of a JSON-RPC transaction object, which nothing has verified yet.
+    pub fn requested_sender(&self) -> Option<Address> {
+        self.requested_sender
+    }
+    /// The decoded bytes of a raw transaction, which field provenance refers to; None for
+    /// JSON-RPC objects and transactions built in code.
+    pub fn raw(&self) -> Option<&[u8]> {
+        self.raw.as_deref()
+    }
+    // The canonical encoding of the transaction, with its signature when it was signed
+    fn reencode(&self) -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        match (&self.transaction, self.signature()) {
+            (TypedTransaction::Legacy(tx), Some(signature)) => {
+                tx.rlp_encode_signed(signature, &mut out)
+            }
+            (TypedTransaction::Eip1559(tx), Some(signature)) => {
+                out.push(u8::from(TxType::Eip1559));
+                tx.rlp_encode_signed(signature, &mut out);
+            }
+            (TypedTransaction::Legacy(tx), None) => tx.encode_for_signing(&mut out),
+            (TypedTransaction::Eip1559(tx), None) => tx.encode_for_signing(&mut out),
+            _ => return None,
+        }
+        Some(out)
+    }
 }
 
 /// Converter that knows how to format Ethereum transactions for VisualSign.
@@ -176,7 +351,43 @@ impl VisualSignConverter<EthereumTransactionWrapper> for EthereumVisualSignConve
         transaction_wrapper: EthereumTransactionWrapper,
         options: VisualSignOptions,
     ) -> Result<SignablePayload, VisualSignError> {
+        self.to_visual_sign_outcome(transaction_wrapper, options)
+            .map(|outcome| outcome.payload)
+    }
+
+    fn to_visual_sign_outcome(
+        &self,
+        transaction_wrapper: EthereumTransactionWrapper,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
         let transaction = transaction_wrapper.inner().clone();
+        let provided = options
+            .sender
+            .as_deref()
+            .map(|sender| {
+                sender.parse::<Address>().map_err(|e| {
+                    VisualSignError::ValidationError(format!("Invalid sender {sender}: {e}"))
+                })
+            })
+            .transpose()?;
+        // A request's `from` stands in for the host's sender but must not contradict it
+        let provided = match (provided, transaction_wrapper.requested_sender()) {
+            (Some(provided), Some(from)) if provided != from => {
+                return Err(VisualSignError::ValidationError(format!(
+                    "Provided sender {provided} does not match the request's from address {from}"
+                )));
+            }
+            (provided, from) => provided.or(from),
+        };
+        // Both sides are checksummed, so equal addresses render identically
+        let sender = resolve_sender(
+            transaction_wrapper
+                .sender()
+                .map(|s| s.to_string())
+                .as_deref(),
+            provided.map(|s| s.to_string()).as_deref(),
+            |derived, provided| derived == provided,
+        )?;
 
         // Create layered registry: global (Arc-shared) + optional request-scoped wallet data.
         // Lookups check request layer first, then fall back to global.
@@ -191,28 +402,91 @@ impl VisualSignConverter<EthereumTransactionWrapper> for EthereumVisualSignConve
             }
         }
 
-        let is_supported = match transaction.tx_type() {
-            TxType::Eip2930 | TxType::Eip4844 | TxType::Eip7702 => false,
-            TxType::Legacy | TxType::Eip1559 => true,
-        };
-        if is_supported {
-            return Ok(convert_to_visual_sign_payload(
+        if is_supported_tx_type(transaction.tx_type()) {
+            return convert_to_visual_sign_payload(
                 transaction,
+                transaction_wrapper.raw(),
+                sender,
                 options,
                 &layered_registry,
-            ));
+            );
         }
-        Err(VisualSignError::DecodeError(format!(
-            "Unsupported transaction type: {}",
-            transaction.tx_type()
-        )))
+        Err(
+            ParserError::from(EthereumParserError::UnsupportedTransactionType(
+                transaction.tx_type().to_string(),
+            ))
+            .into(),
+        )
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        // ERC20 calls are decoded at any address, protocol contracts where they are registered
+        ParserCapabilities::new(&["hex", "base64", "json-rpc"], &["Legacy", "EIP-1559"])
+            .with_decoders(
+                std::iter::once("ERC20".to_string()).chain(self.registry.contract_types()),
+            )
+            .with_feature("legacy", is_supported_tx_type(TxType::Legacy))
+            .with_feature("eip2930", is_supported_tx_type(TxType::Eip2930))
+            .with_feature("eip1559", is_supported_tx_type(TxType::Eip1559))
+            .with_feature("eip4844", is_supported_tx_type(TxType::Eip4844))
+            .with_feature("eip7702", is_supported_tx_type(TxType::Eip7702))
+            .with_feature("json_rpc", true)
+            .with_feature("signed_raw", true)
+            .with_feature("lossless_verification", true)
+    }
+}
+
+// Transaction types rendered into a payload; the others are rejected as unsupported
+fn is_supported_tx_type(tx_type: TxType) -> bool {
+    match tx_type {
+        TxType::Eip2930 | TxType::Eip4844 | TxType::Eip7702 => false,
+        TxType::Legacy | TxType::Eip1559 => true,
     }
 }
 
 impl VisualSignConverterFromString<EthereumTransactionWrapper> for EthereumVisualSignConverter {}
-fn decode_transaction_bytes(mut buf: &[u8]) -> Result<TypedTransaction, EthereumParserError> {
+// Payloads of the items in the RLP list at the start of `buf`, or None when it is not a
+// well-formed list
+fn rlp_list_items(mut buf: &[u8]) -> Option<Vec<&[u8]>> {
+    let header = Header::decode(&mut buf).ok()?;
+    if !header.list {
+        return None;
+    }
+    let mut payload = buf.get(..header.payload_length)?;
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let item = Header::decode(&mut payload).ok()?;
+        let (body, rest) = payload.split_at_checked(item.payload_length)?;
+        items.push(body);
+        payload = rest;
+    }
+    Some(items)
+}
+
+// Signed envelopes append the signature (v or y_parity, r and s) to the unsigned fields. An
+// unsigned EIP-155 legacy transaction has as many items as a signed one, but zero r and s.
+fn is_signed_envelope(ty: TxType, buf: &[u8]) -> bool {
+    let Some(items) = rlp_list_items(buf) else {
+        return false;
+    };
+    match ty {
+        TxType::Legacy => items.len() == 9 && !(items[7].is_empty() && items[8].is_empty()),
+        TxType::Eip1559 => items.len() == 12,
+        TxType::Eip2930 | TxType::Eip4844 | TxType::Eip7702 => false,
+    }
+}
+
+// Decodes an unsigned or signed transaction, returning the signature of a signed one
+fn decode_transaction_bytes(
+    mut buf: &[u8],
+) -> Result<(TypedTransaction, Option<Signature>), EthereumParserError> {
+    let input_len = buf.len();
     let tx = if buf.is_empty() {
-        Err(EthereumParserError::FailedToDecodeTransaction(
+        Err(EthereumParserError::TruncatedTransaction(
             "Input too short".to_string(),
         ))
     } else if buf[0] == 0 || (buf[0] > 0x7f && buf[0] < 0xc0) {
@@ -231,9 +505,17 @@ fn decode_transaction_bytes(mut buf: &[u8]) -> Result<TypedTransaction, Ethereum
         };
         buf.advance(1); // Skip type byte
         match ty {
-            TxType::Eip1559 => Ok(TypedTransaction::Eip1559(
-                alloy_consensus::TxEip1559::decode(&mut buf)
-                    .map_err(|e| EthereumParserError::FailedToDecodeTransaction(e.to_string()))?,
+            TxType::Eip1559 if is_signed_envelope(ty, buf) => {
+                let (tx, signature, _) = alloy_consensus::TxEip1559::rlp_decode_signed(&mut buf)
+                    .map_err(rlp_error)?
+                    .into_parts();
+                Ok((TypedTransaction::Eip1559(tx), Some(signature)))
+            }
+            TxType::Eip1559 => Ok((
+                TypedTransaction::Eip1559(
+                    alloy_consensus::TxEip1559::decode(&mut buf).map_err(rlp_error)?,
+                ),
+                None,
             )),
             TxType::Eip2930 => Err(EthereumParserError::UnsupportedTransactionType(
                 "eip-2930".to_string(),
@@ -248,25 +530,33 @@ fn decode_transaction_bytes(mut buf: &[u8]) -> Result<TypedTransaction, Ethereum
                 "legacy".to_string(), // This shouldn't happen
             )),
         }
+    } else if is_signed_envelope(TxType::Legacy, buf) {
+        let (tx, signature, _) = alloy_consensus::TxLegacy::rlp_decode_signed(&mut buf)
+            .map_err(rlp_error)?
+            .into_parts();
+        Ok((TypedTransaction::Legacy(tx), Some(signature)))
     } else {
-        Ok(TypedTransaction::Legacy(
-            alloy_consensus::TxLegacy::decode(&mut buf)
-                .map_err(|e| EthereumParserError::FailedToDecodeTransaction(e.to_string()))?,
+        Ok((
+            TypedTransaction::Legacy(
+                alloy_consensus::TxLegacy::decode(&mut buf).map_err(rlp_error)?,
+            ),
+            None,
         ))
     };
     if tx.is_ok() && !buf.is_empty() {
-        return Err(EthereumParserError::UnexpectedTrailingData(hex::encode(
-            buf,
-        )));
+        return Err(EthereumParserError::UnexpectedTrailingData {
+            offset: input_len - buf.len(),
+            data: hex::encode(buf),
+        });
     }
     tx
 }
 
-fn decode_transaction(
+fn decode_encoded_bytes(
     raw_transaction: &str,
     encodings: SupportedEncodings,
-) -> Result<TypedTransaction, EthereumParserError> {
-    let bytes = match encodings {
+) -> Result<Vec<u8>, EthereumParserError> {
+    Ok(match encodings {
         SupportedEncodings::Hex => {
             let clean_hex = raw_transaction
                 .strip_prefix("0x")
@@ -278,29 +568,73 @@ fn decode_transaction(
         SupportedEncodings::Base64 => b64.decode(raw_transaction).map_err(|e| {
             EthereumParserError::FailedToDecodeTransaction(format!("Failed to decode base64: {e}"))
         })?,
-    };
-    decode_transaction_bytes(&bytes)
+    })
 }
 
 fn convert_to_visual_sign_payload(
     transaction: TypedTransaction,
+    raw: Option<&[u8]>,
+    sender: Option<Sender>,
     options: VisualSignOptions,
     layered_registry: &LayeredRegistry<registry::ContractRegistry>,
-) -> SignablePayload {
+) -> Result<ParseOutcome, VisualSignError> {
     // Extract chain ID to determine the network
     let chain_id = transaction.chain_id();
 
     let chain_name = chains::get_chain_name(chain_id);
 
-    let mut fields = vec![SignablePayloadField::TextV2 {
+    // Sections of the payload, each with its fields decoded from a single transaction item, by
+    // index within the section
+    let mut network = Section::new("Network");
+    let mut network_sources = vec![(0, RawItem::ChainId)];
+    network.push(SignablePayloadField::TextV2 {
         common: SignablePayloadFieldCommon {
             fallback_text: chain_name.clone(),
             label: "Network".to_string(),
         },
         text_v2: SignablePayloadFieldTextV2 { text: chain_name },
-    }];
+    });
+    if let Some(ExpectedChainId(expected)) = options.extensions.get::<ExpectedChainId>() {
+        let chain_id = chain_id.map(|chain_id| chain_id.to_string());
+        network.extend(chain_id_warning(expected, chain_id.as_deref()));
+    }
+    network.push(summary::create_transaction_summary(
+        &transaction,
+        options.decode_transfers,
+        layered_registry.global(),
+    )?);
+    if let Some(sender) = &sender {
+        network.push(create_sender_field(sender));
+        // A sender recovered from a signature means the transaction is ready to submit
+        if sender.source == SenderSource::Derived {
+            network.push(SignablePayloadField::TextV2 {
+                common: SignablePayloadFieldCommon {
+                    fallback_text: "Already signed".to_string(),
+                    label: "Signature Status".to_string(),
+                },
+                text_v2: SignablePayloadFieldTextV2 {
+                    text: "Already signed".to_string(),
+                },
+            });
+        }
+    }
+    // A legacy transaction signed without a chain ID is valid on every chain
+    let replay_protection = match chain_id {
+        Some(chain_id) => {
+            network_sources.push((network.len(), RawItem::ChainId));
+            ReplayProtection::ChainId(chain_id)
+        }
+        None => ReplayProtection::None,
+    };
+    if let Ok(field) = create_replay_protection_field(&replay_protection) {
+        network.push(field.signable_payload_field);
+    }
+    let mut warnings = Vec::new();
+    let mut destination = Section::new("Destination");
+    let mut destination_sources = Vec::new();
     if let Some(to) = transaction.to() {
-        fields.push(SignablePayloadField::AddressV2 {
+        destination_sources.push((destination.len(), RawItem::To));
+        destination.push(SignablePayloadField::AddressV2 {
             common: SignablePayloadFieldCommon {
                 fallback_text: to.to_string(),
                 label: "To".to_string(),
@@ -311,38 +645,55 @@ fn convert_to_visual_sign_payload(
                 asset_label: "Test Asset".to_string(),
                 memo: None,
                 badge_text: None,
+                name_resolved: None,
             },
         });
+    } else {
+        destination_sources.push((destination.len(), RawItem::To));
+        destination.push(creation::creates_new_contract_field());
+        destination_sources.push((destination.len(), RawItem::Data));
+        destination.push(creation::init_code_size_field(transaction.input()));
+        if let Some(deployment) = options.extensions.get::<Create2Deployment>() {
+            match creation::create2_address(deployment, transaction.input()) {
+                Ok(address) => destination.extend(creation::predicted_address_field(address)),
+                Err(message) => warnings.push(ParseWarning::new(ParseWarningKind::Other, message)),
+            }
+        }
     }
-    fields.extend([
-        SignablePayloadField::AmountV2 {
-            common: SignablePayloadFieldCommon {
-                fallback_text: format!("{} ETH", format_ether(transaction.value())),
-                label: "Value".to_string(),
-            },
-            amount_v2: SignablePayloadFieldAmountV2 {
-                amount: format_ether(transaction.value()),
-                abbreviation: Some("ETH".to_string()),
-            },
+    let symbol = l2::native_symbol(chain_id);
+    let mut amounts = Section::new("Amounts");
+    let amounts_sources = vec![(0, RawItem::Value)];
+    amounts.push(SignablePayloadField::AmountV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: format!("{} {symbol}", format_ether(transaction.value())),
+            label: "Value".to_string(),
         },
-        SignablePayloadField::TextV2 {
-            common: SignablePayloadFieldCommon {
-                fallback_text: format!("{}", transaction.gas_limit()),
-                label: "Gas Limit".to_string(),
-            },
-            text_v2: SignablePayloadFieldTextV2 {
-                text: format!("{}", transaction.gas_limit()),
-            },
+        amount_v2: SignablePayloadFieldAmountV2 {
+            amount: format_ether(transaction.value()),
+            abbreviation: Some(symbol.to_string()),
         },
-    ]);
+    });
+
+    let mut fees = Section::new("Fees");
+    let mut fees_sources = vec![(0, RawItem::GasLimit)];
+    fees.push(SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: format!("{}", transaction.gas_limit()),
+            label: "Gas Limit".to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 {
+            text: format!("{}", transaction.gas_limit()),
+        },
+    });
 
     // Handle gas pricing based on transaction type
     let gas_price_text = format!("{} gwei", format_gwei(extract_gas_price(&transaction)));
 
-    fields.push(SignablePayloadField::TextV2 {
+    fees_sources.push((fees.len(), RawItem::GasPrice));
+    fees.push(SignablePayloadField::TextV2 {
         common: SignablePayloadFieldCommon {
             fallback_text: gas_price_text.clone(),
-            label: "Gas Price".to_string(),
+            label: l2::gas_price_label(chain_id).to_string(),
         },
         text_v2: SignablePayloadFieldTextV2 {
             text: gas_price_text,
@@ -351,10 +702,17 @@ fn convert_to_visual_sign_payload(
 
     // Add priority fee for EIP-1559, EIP-4844, and EIP-7702 transactions
     if let Some(priority_fee) = extract_priority_fee(&transaction) {
-        fields.push(create_priority_fee_field(priority_fee));
+        fees_sources.push((fees.len(), RawItem::PriorityFee));
+        fees.push(create_priority_fee_field(priority_fee));
+    }
+
+    // Rollups charge for posting the transaction to L1, which the gas price alone does not show
+    if let Some(l2_chain) = l2::l2_chain(chain_id) {
+        fees.extend(l2::create_l2_fee_fields(&l2_chain, &transaction));
     }
 
-    fields.push(SignablePayloadField::TextV2 {
+    fees_sources.push((fees.len(), RawItem::Nonce));
+    fees.push(SignablePayloadField::TextV2 {
         common: SignablePayloadFieldCommon {
             fallback_text: format!("{}", transaction.nonce()),
             label: "Nonce".to_string(),
@@ -363,13 +721,39 @@ fn convert_to_visual_sign_payload(
             text: format!("{}", transaction.nonce()),
         },
     });
+    if let Some(ExpectedNonce(expected)) = options.extensions.get::<ExpectedNonce>() {
+        fees.extend(nonce_warning(*expected, transaction.nonce(), "nonce"));
+    }
 
+    let mut data = Section::new("Data");
+    // Bytes of calldata that no decoder explained
+    let mut opaque_bytes = 0;
     // Add contract call data if present
     let input = transaction.input();
-    if !input.is_empty() {
+    if transaction.to().is_none() && !input.is_empty() {
+        // Init code has no ABI to decode it against
+        data.extend(creation::init_code_field(input));
+        warnings.push(ParseWarning::new(
+            ParseWarningKind::Other,
+            format!(
+                "Init code of the new contract is not decoded; showing its {} raw bytes",
+                input.len()
+            ),
+        ));
+        opaque_bytes = input.len();
+    } else if !input.is_empty() {
         let mut input_fields: Vec<SignablePayloadField> = Vec::new();
         if options.decode_transfers {
-            if let Some(field) = (contracts::core::ERC20Visualizer {}).visualize_tx_commands(input)
+            let token = match (transaction.to(), chain_id) {
+                (Some(address), Some(chain_id)) => Some(contracts::core::ERC20Token {
+                    chain_id,
+                    address,
+                    registry: layered_registry.global(),
+                }),
+                _ => None,
+            };
+            if let Some(field) =
+                (contracts::core::ERC20Visualizer {}).visualize_token_call(input, token)
             {
                 input_fields.push(field);
             }
@@ -383,17 +767,154 @@ fn convert_to_visual_sign_payload(
         {
             input_fields.push(field);
         }
+        if let Some(field) =
+            protocols::uniswap::V4PoolManagerVisualizer.visualize_tx_commands(input)
+        {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::curve::CurveVisualizer.visualize_tx_commands(
+            input,
+            chain_id.unwrap_or(1),
+            Some(layered_registry.global()),
+        ) {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::balancer::BalancerVaultVisualizer.visualize_tx_commands(
+            input,
+            chain_id.unwrap_or(1),
+            Some(layered_registry.global()),
+        ) {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::oneinch::AggregationRouterVisualizer.visualize_tx_commands(
+            input,
+            chain_id.unwrap_or(1),
+            Some(layered_registry.global()),
+        ) {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::aave::AaveV3PoolVisualizer.visualize_tx_commands(
+            input,
+            chain_id.unwrap_or(1),
+            Some(layered_registry.global()),
+        ) {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::compound::CometVisualizer.visualize_tx_commands(
+            input,
+            chain_id.unwrap_or(1),
+            Some(layered_registry.global()),
+        ) {
+            input_fields.push(field);
+        }
+        if let Some(field) =
+            protocols::lido::LidoVisualizer.visualize_tx_commands(input, transaction.value())
+        {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::eigenlayer::EigenLayerVisualizer.visualize_tx_commands(
+            input,
+            chain_id.unwrap_or(1),
+            Some(layered_registry.global()),
+        ) {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::wormhole::WormholeTokenBridgeVisualizer
+            .visualize_tx_commands(
+                input,
+                transaction.value(),
+                chain_id.unwrap_or(1),
+                Some(layered_registry.global()),
+            )
+        {
+            input_fields.push(field);
+        }
+        if let Some(field) = transaction.to().and_then(|oft| {
+            protocols::layerzero::OftVisualizer.visualize_tx_commands(
+                input,
+                oft,
+                chain_id.unwrap_or(1),
+                Some(layered_registry.global()),
+            )
+        }) {
+            input_fields.push(field);
+        }
+        if let Some(field) = protocols::cctp::CctpTokenMessengerVisualizer.visualize_tx_commands(
+            input,
+            chain_id.unwrap_or(1),
+            Some(layered_registry.global()),
+        ) {
+            input_fields.push(field);
+        }
         if input_fields.is_empty() {
             // Use fallback visualizer for unknown contract calls
-            input_fields.push(contracts::core::FallbackVisualizer::new().visualize_hex(input));
+            let target = transaction
+                .to()
+                .map_or_else(|| "contract creation".to_string(), |to| to.to_string());
+            let fallback = contracts::core::FallbackVisualizer::new();
+            let (field, shown) = match fallback.visualize_probable_call(input) {
+                Some(field) => (field, "the probable function from its selector"),
+                None => (fallback.visualize_hex(input), "raw input"),
+            };
+            warnings.push(ParseWarning::new(
+                ParseWarningKind::UnknownContract,
+                format!("No decoder for call data sent to {target}; showing {shown}"),
+            ));
+            // A signature guessed from the selector is not a decoder, so the calldata still
+            // counts as undecoded
+            opaque_bytes = input.len();
+            input_fields.push(field);
         }
-        fields.append(&mut input_fields);
+        data.extend(input_fields);
+    }
+    let data_sources = (0..data.len()).map(|i| (i, RawItem::Data)).collect();
+
+    let mut fields = Vec::new();
+    let mut sources = Vec::new();
+    for (section, section_sources) in [
+        (network, network_sources),
+        (destination, destination_sources),
+        (amounts, amounts_sources),
+        (fees, fees_sources),
+        (data, data_sources),
+    ] {
+        let start = section.append_to(&mut fields);
+        sources.extend(
+            section_sources
+                .into_iter()
+                .map(|(index, item)| (start + index, item)),
+        );
     }
 
     let title = options
         .transaction_name
         .unwrap_or_else(|| "Ethereum Transaction".to_string());
-    SignablePayload::new(0, title, None, fields, "EthereumTx".to_string())
+    let mut provenance = match raw {
+        Some(raw) if options.output.include_provenance => {
+            provenance::field_provenance(raw, transaction.tx_type(), &sources)
+        }
+        _ => Provenance::default(),
+    };
+    if options.output.preview_layout {
+        fields = vec![package_preview_layout(&title, fields, &preview_template())];
+        provenance = provenance.within_preview_layout(0);
+    }
+    let total_bytes = raw.map_or(input.len(), <[u8]>::len);
+    Ok(ParseOutcome::with_warnings(
+        SignablePayload::new(0, title, None, fields, "EthereumTx".to_string()),
+        warnings,
+    )
+    .with_provenance(provenance)
+    .with_coverage(Coverage::new(total_bytes, opaque_bytes)))
+}
+
+// The transaction summary already states what is sent and the worst-case cost
+fn preview_template() -> PreviewTemplate {
+    PreviewTemplate::new("{Transaction Summary}")
+        .with_subtitle("{Network}")
+        .with_condensed("Transaction Summary")
+        .with_condensed("To")
+        .with_condensed("Value")
 }
 
 // Public API functions for ease of use
@@ -417,9 +938,11 @@ pub fn transaction_string_to_visual_sign(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_consensus::{SignableTransaction, TxLegacy, TypedTransaction};
+    use alloy_consensus::{SignableTransaction, Transaction as _, TxLegacy, TypedTransaction};
     use alloy_primitives::{Address, Bytes, ChainId, U256};
     use visualsign::SignablePayloadFieldAddressV2;
+    use visualsign::field_builders::create_divider_field;
+    use visualsign::vsptrait::OutputFlags;
 
     fn unsigned_to_hex(tx: &TypedTransaction) -> String {
         let mut encoded = Vec::new();
@@ -461,6 +984,27 @@ mod tests {
                         text: "Ethereum Mainnet".to_string(),
                     },
                 },
+                SignablePayloadField::TextV2 {
+                    common: SignablePayloadFieldCommon {
+                        fallback_text: "Send 1 ETH. Max cost: 1.00042 ETH".to_string(),
+                        label: "Transaction Summary".to_string(),
+                    },
+                    text_v2: SignablePayloadFieldTextV2 {
+                        text: "Send 1 ETH. Max cost: 1.00042 ETH".to_string(),
+                    },
+                },
+                SignablePayloadField::TextV2 {
+                    common: SignablePayloadFieldCommon {
+                        fallback_text: "Chain ID 1".to_string(),
+                        label: "Replay Protection".to_string(),
+                    },
+                    text_v2: SignablePayloadFieldTextV2 {
+                        text: "Chain ID 1".to_string(),
+                    },
+                },
+                create_divider_field("Destination")
+                    .unwrap()
+                    .signable_payload_field,
                 SignablePayloadField::TextV2 {
                     common: SignablePayloadFieldCommon {
                         fallback_text: "0x000000000000000000000000000000000000dEaD".to_string(),
@@ -470,6 +1014,9 @@ mod tests {
                         text: "0x000000000000000000000000000000000000dEaD".to_string(),
                     },
                 },
+                create_divider_field("Amounts")
+                    .unwrap()
+                    .signable_payload_field,
                 SignablePayloadField::TextV2 {
                     common: SignablePayloadFieldCommon {
                         fallback_text: "1 ETH".to_string(),
@@ -479,6 +1026,7 @@ mod tests {
                         text: "1 ETH".to_string(),
                     },
                 },
+                create_divider_field("Fees").unwrap().signable_payload_field,
                 SignablePayloadField::TextV2 {
                     common: SignablePayloadFieldCommon {
                         fallback_text: "21000".to_string(),
@@ -564,6 +1112,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_expected_nonce_warning() {
+        let tx = TypedTransaction::Legacy(TxLegacy {
+            chain_id: Some(ChainId::from(1u64)),
+            nonce: 3,
+            gas_price: 1_000_000_000u128,
+            gas_limit: 21000,
+            to: alloy_primitives::TxKind::Call(Address::ZERO),
+            value: U256::ZERO,
+            input: Bytes::new(),
+        });
+        let options = |expected: u64| {
+            let mut options = VisualSignOptions::default();
+            options.extensions.insert(ExpectedNonce(expected));
+            options
+        };
+
+        let payload = transaction_to_visual_sign(tx.clone(), options(3)).unwrap();
+        assert!(!payload.fields.iter().any(|f| f.label() == "Nonce Warning"));
+
+        let payload = transaction_to_visual_sign(tx, options(5)).unwrap();
+        let nonce = payload
+            .fields
+            .iter()
+            .position(|f| f.label() == "Nonce")
+            .unwrap();
+        assert_eq!(payload.fields[nonce + 1].label(), "Nonce Warning");
+        assert_eq!(
+            payload.fields[nonce + 1].fallback_text(),
+            "Warning: nonce 3 was already used; the account's next nonce is 5"
+        );
+    }
+
+    #[test]
+    fn test_expected_chain_id_warning() {
+        let tx = |chain_id: Option<u64>| {
+            TypedTransaction::Legacy(TxLegacy {
+                chain_id: chain_id.map(ChainId::from),
+                nonce: 0,
+                gas_price: 1_000_000_000u128,
+                gas_limit: 21000,
+                to: alloy_primitives::TxKind::Call(Address::ZERO),
+                value: U256::ZERO,
+                input: Bytes::new(),
+            })
+        };
+        let options = |expected: &str| {
+            let mut options = VisualSignOptions::default();
+            options
+                .extensions
+                .insert(ExpectedChainId(expected.to_string()));
+            options
+        };
+
+        let payload = transaction_to_visual_sign(tx(Some(1)), options("1")).unwrap();
+        assert_eq!(payload.fields[1].label(), "Transaction Summary");
+
+        // A mainnet transaction presented as Sepolia
+        let payload = transaction_to_visual_sign(tx(Some(1)), options("11155111")).unwrap();
+        assert_eq!(payload.fields[1].label(), "Chain ID Warning");
+        assert_eq!(
+            payload.fields[1].fallback_text(),
+            "Warning: the transaction is for chain ID 1, not the expected 11155111"
+        );
+
+        let payload = transaction_to_visual_sign(tx(None), options("1")).unwrap();
+        assert_eq!(
+            payload.fields[1].fallback_text(),
+            "Warning: the transaction names no chain ID; the expected one is 1"
+        );
+    }
+
+    #[test]
+    fn test_unknown_contract_call_warns() {
+        let tx = TypedTransaction::Legacy(TxLegacy {
+            chain_id: Some(ChainId::from(1u64)),
+            nonce: 1,
+            gas_price: 1_000_000_000u128,
+            gas_limit: 50000,
+            to: alloy_primitives::TxKind::Call(Address::ZERO),
+            value: U256::ZERO,
+            input: Bytes::from(vec![0x12, 0x34, 0x56, 0x78]),
+        });
+
+        let outcome = EthereumVisualSignConverter::new()
+            .to_visual_sign_outcome(
+                EthereumTransactionWrapper::new(tx),
+                VisualSignOptions::default(),
+            )
+            .unwrap();
+        assert!(
+            outcome
+                .payload
+                .fields
+                .iter()
+                .any(|f| f.label() == "Input Data")
+        );
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(outcome.warnings[0].kind, ParseWarningKind::UnknownContract);
+        assert!(
+            outcome.warnings[0]
+                .message
+                .contains(&Address::ZERO.to_string())
+        );
+    }
+
     #[test]
     fn test_transaction_with_custom_title() {
         let tx = TypedTransaction::Legacy(TxLegacy {
@@ -577,82 +1231,130 @@ mod tests {
         });
 
         let options = VisualSignOptions {
-            decode_transfers: false,
             transaction_name: Some("Custom Transaction Title".to_string()),
-            metadata: None,
+            ..Default::default()
         };
         let payload = transaction_to_visual_sign(tx, options).unwrap();
 
         assert_eq!(payload.title, "Custom Transaction Title");
     }
 
+    #[test]
+    fn test_transaction_with_preview_layout() {
+        let tx = TypedTransaction::Legacy(TxLegacy {
+            chain_id: Some(ChainId::from(1u64)),
+            nonce: 0,
+            gas_price: 1_000_000_000u128,
+            gas_limit: 21000,
+            to: alloy_primitives::TxKind::Call(Address::ZERO),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            input: Bytes::new(),
+        });
+
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                preview_layout: true,
+                ..Default::default()
+            },
+            ..VisualSignOptions::default()
+        };
+        let payload = transaction_to_visual_sign(tx, options).unwrap();
+
+        assert_eq!(payload.fields.len(), 1);
+        let SignablePayloadField::PreviewLayout {
+            common,
+            preview_layout,
+        } = &payload.fields[0]
+        else {
+            panic!("Expected PreviewLayout");
+        };
+        assert_eq!(common.label, "Ethereum Transaction");
+        assert_eq!(
+            preview_layout.title.as_ref().unwrap().text,
+            "Send 1 ETH. Max cost: 1.000021 ETH"
+        );
+        assert_eq!(
+            preview_layout.subtitle.as_ref().unwrap().text,
+            "Ethereum Mainnet"
+        );
+        let condensed: Vec<&str> = preview_layout
+            .condensed
+            .as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.signable_payload_field.label().as_str())
+            .collect();
+        assert_eq!(condensed, ["Transaction Summary", "To", "Value"]);
+        assert!(preview_layout.expanded.as_ref().unwrap().fields.len() > condensed.len());
+    }
+
     #[test]
     fn test_transaction_wrapper_from_string() {
         // Test with empty string
         assert_eq!(
             EthereumTransactionWrapper::from_string(""),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: Input too short".to_string()
-            )),
+            Err(EthereumParserError::TruncatedTransaction("Input too short".to_string()).into()),
         );
         // Test with invalid hex data
         assert_eq!(
             EthereumTransactionWrapper::from_string("invalid_hex_data"),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: Failed to decode base64: Invalid symbol 95, offset 7.".to_string()
-            )),
+            Err(EthereumParserError::FailedToDecodeTransaction(
+                "Failed to decode base64: Invalid symbol 95, offset 7.".to_string()
+            )
+            .into()),
         );
         // Test with malformed hex (odd length)
         assert_eq!(
             EthereumTransactionWrapper::from_string("0x123"),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: Failed to decode hex: Odd number of digits"
-                    .to_string()
-            )),
+            Err(EthereumParserError::FailedToDecodeTransaction(
+                "Failed to decode hex: Odd number of digits".to_string()
+            )
+            .into()),
         );
         // Test with valid hex prefix but invalid RLP data
         assert_eq!(
             EthereumTransactionWrapper::from_string("0x1234567890abcdef"),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: Unexpected type flag. Got 18.".to_string()
-            )),
+            Err(EthereumParserError::FailedToDecodeTransaction(
+                "Unexpected type flag. Got 18.".to_string()
+            )
+            .into()),
         );
         // Test with valid base64 but invalid RLP data
         assert_eq!(
             EthereumTransactionWrapper::from_string("aGVsbG8gd29ybGQ="),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: Unexpected type flag. Got 104.".to_string()
-            )),
+            Err(EthereumParserError::FailedToDecodeTransaction(
+                "Unexpected type flag. Got 104.".to_string()
+            )
+            .into()),
         );
         // Test with unknown transaction type
         assert_eq!(
             EthereumTransactionWrapper::from_string(
                 "0x05f86401808504a817c800825208940000000000000000000000000000000000000000880de0b6b3a764000080c0"
             ),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: Unexpected type flag. Got 5.".to_string()
-            )),
+            Err(EthereumParserError::FailedToDecodeTransaction(
+                "Unexpected type flag. Got 5.".to_string()
+            )
+            .into()),
         );
         // Test with corrupted typed transaction (invalid RLP after type byte)
         assert_eq!(
             EthereumTransactionWrapper::from_string("0x02ff"),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: input too short".to_string()
-            )),
+            Err(EthereumParserError::TruncatedTransaction("input too short".to_string()).into()),
         );
         // Test with valid transaction type but insufficient data
         assert_eq!(
             EthereumTransactionWrapper::from_string("0x02"),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: input too short".to_string()
-            )),
+            Err(EthereumParserError::TruncatedTransaction("input too short".to_string()).into()),
         );
         // Test with whitespace in input (should fail due to invalid format)
         assert_eq!(
             EthereumTransactionWrapper::from_string(" 0x1234 "),
-            Err(TransactionParseError::DecodeError(
-                "Failed to decode transaction: Failed to decode base64: Invalid symbol 32, offset 0.".to_string()
-            )),
+            Err(EthereumParserError::FailedToDecodeTransaction(
+                "Failed to decode base64: Invalid symbol 32, offset 0.".to_string()
+            )
+            .into()),
         );
         // Test with legacy transaction
         let legacy_tx = TypedTransaction::Legacy(TxLegacy {
@@ -697,9 +1399,7 @@ mod tests {
         });
         assert_eq!(
             EthereumTransactionWrapper::from_string(&unsigned_to_hex(&eip2930_tx)),
-            Err(TransactionParseError::DecodeError(
-                "Unsupported transaction type: eip-2930".to_string()
-            ))
+            Err(EthereumParserError::UnsupportedTransactionType("eip-2930".to_string()).into())
         );
         // Test with EIP-4844 transaction (unsupported)
         let eip4844_tx = TypedTransaction::Eip4844(alloy_consensus::TxEip4844Variant::TxEip4844(
@@ -719,9 +1419,7 @@ mod tests {
         ));
         assert_eq!(
             EthereumTransactionWrapper::from_string(&unsigned_to_hex(&eip4844_tx)),
-            Err(TransactionParseError::DecodeError(
-                "Unsupported transaction type: eip-4844".to_string()
-            ))
+            Err(EthereumParserError::UnsupportedTransactionType("eip-4844".to_string()).into())
         );
         // Test with EIP-7702 transaction (unsupported)
         let eip7702_tx = TypedTransaction::Eip7702(alloy_consensus::TxEip7702 {
@@ -738,12 +1436,445 @@ mod tests {
         });
         assert_eq!(
             EthereumTransactionWrapper::from_string(&unsigned_to_hex(&eip7702_tx)),
-            Err(TransactionParseError::DecodeError(
-                "Unsupported transaction type: eip-7702".to_string()
-            ))
+            Err(EthereumParserError::UnsupportedTransactionType("eip-7702".to_string()).into())
         );
     }
 
+    #[test]
+    fn test_decode_error_kinds() {
+        let kind = |data: &str| {
+            EthereumTransactionWrapper::from_string(data)
+                .unwrap_err()
+                .kind()
+        };
+        assert_eq!(kind("0x02"), ParserErrorKind::Truncated);
+        assert_eq!(kind("0x123"), ParserErrorKind::Encoding);
+
+        let error = EthereumTransactionWrapper::from_string(
+            "0x01c0", // EIP-2930 type byte
+        )
+        .unwrap_err();
+        let TransactionParseError::Parser(detail) = error else {
+            panic!("Expected a chain-specific error");
+        };
+        assert_eq!(detail.chain, "Ethereum");
+        assert_eq!(detail.reason, "UnsupportedTransactionType");
+        assert_eq!(detail.kind, ParserErrorKind::UnsupportedVariant);
+        assert_eq!(detail.offset, None);
+
+        // A complete legacy transaction followed by two extra bytes
+        let legacy_tx = TypedTransaction::Legacy(TxLegacy {
+            chain_id: Some(ChainId::from(1u64)),
+            nonce: 0,
+            gas_price: 20_000_000_000u128,
+            gas_limit: 21000,
+            to: alloy_primitives::TxKind::Call(Address::ZERO),
+            value: U256::ZERO,
+            input: Bytes::new(),
+        });
+        let encoded = unsigned_to_hex(&legacy_tx);
+        let error = EthereumTransactionWrapper::from_string(&format!("{encoded}beef")).unwrap_err();
+        let TransactionParseError::Parser(detail) = error else {
+            panic!("Expected a chain-specific error");
+        };
+        assert_eq!(detail.reason, "UnexpectedTrailingData");
+        assert_eq!(detail.offset, Some((encoded.len() - 2) / 2));
+    }
+
+    #[test]
+    fn test_json_rpc_transaction_object() {
+        let request = r#"{
+            "from": "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+            "to": "0x3535353535353535353535353535353535353535",
+            "value": "0xde0b6b3a7640000",
+            "gas": "0x5208",
+            "maxFeePerGas": "0x6fc23ac00",
+            "maxPriorityFeePerGas": "0x77359400",
+            "nonce": "0x1",
+            "chainId": "0x1"
+        }"#;
+        let wrapper = EthereumTransactionWrapper::from_string(request).unwrap();
+        assert_eq!(wrapper.inner().tx_type(), TxType::Eip1559);
+        assert_eq!(wrapper.sender(), None);
+
+        let payload =
+            transaction_string_to_visual_sign(request, VisualSignOptions::default()).unwrap();
+        assert_eq!(
+            payload.fields[1].fallback_text(),
+            "Send 1 ETH. Max cost: 1.00063 ETH"
+        );
+        let SignablePayloadField::AddressV2 { address_v2, .. } = &payload.fields[2] else {
+            panic!("Expected AddressV2 for From");
+        };
+        assert_eq!(
+            address_v2.address,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        assert_eq!(address_v2.badge_text.as_deref(), Some("Unverified"));
+
+        // The host's sender and the request's `from` must agree
+        let error = transaction_string_to_visual_sign(
+            request,
+            VisualSignOptions {
+                sender: Some(Address::ZERO.to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(error, VisualSignError::ValidationError(_)));
+
+        let error = EthereumTransactionWrapper::from_string(r#"{"to": "0x35"}"#).unwrap_err();
+        assert_eq!(error.kind(), ParserErrorKind::Encoding);
+    }
+
+    #[test]
+    fn test_l2_fee_fields_follow_gas_fields() {
+        let tx = TypedTransaction::Eip1559(alloy_consensus::TxEip1559 {
+            chain_id: ChainId::from(8453u64),
+            nonce: 3,
+            gas_limit: 21000,
+            max_fee_per_gas: 30_000_000u128,
+            max_priority_fee_per_gas: 1_000_000u128,
+            to: alloy_primitives::TxKind::Call(Address::ZERO),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: Bytes::new(),
+        });
+        let payload = transaction_to_visual_sign(tx, VisualSignOptions::default()).unwrap();
+        let labels: Vec<&str> = payload.fields.iter().map(|f| f.label().as_str()).collect();
+        assert_eq!(
+            &labels[9..],
+            [
+                "L2 Gas Price",
+                "Max Priority Fee Per Gas",
+                "Fee Model",
+                "L1 Data Size",
+                "Calldata Bytes",
+                "Sequencer",
+                "Nonce"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coverage_counts_undecoded_calldata() {
+        let tx = TypedTransaction::Eip1559(alloy_consensus::TxEip1559 {
+            chain_id: ChainId::from(1u64),
+            nonce: 7,
+            gas_limit: 50000,
+            max_fee_per_gas: 30_000_000_000u128,
+            max_priority_fee_per_gas: 2_000_000_000u128,
+            to: alloy_primitives::TxKind::Call(Address::repeat_byte(0x11)),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: Bytes::from(vec![0xde; 32]),
+        });
+        let encoded = unsigned_to_hex(&tx);
+        let total_bytes = encoded.len() / 2 - 1;
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                include_coverage: true,
+                ..Default::default()
+            },
+            ..VisualSignOptions::default()
+        };
+        let outcome = EthereumVisualSignConverter::new()
+            .to_visual_sign_outcome_from_string(&encoded, options)
+            .unwrap();
+
+        let expected = Coverage::new(total_bytes, 32);
+        assert_eq!(outcome.coverage, Some(expected));
+        assert_eq!(outcome.payload.coverage, Some(expected));
+        assert!(expected.percent < 100);
+
+        // Without the option the payload leaves it out, but the outcome still reports it
+        let outcome = EthereumVisualSignConverter::new()
+            .to_visual_sign_outcome_from_string(&encoded, VisualSignOptions::default())
+            .unwrap();
+        assert_eq!(outcome.payload.coverage, None);
+        assert_eq!(outcome.coverage, Some(expected));
+    }
+
+    #[test]
+    fn test_contract_creation() {
+        let init_code = vec![0x60, 0x80, 0x60, 0x40, 0x52, 0x00];
+        let tx = TypedTransaction::Eip1559(alloy_consensus::TxEip1559 {
+            chain_id: ChainId::from(1u64),
+            nonce: 0,
+            gas_limit: 500000,
+            max_fee_per_gas: 30_000_000_000u128,
+            max_priority_fee_per_gas: 2_000_000_000u128,
+            to: alloy_primitives::TxKind::Create,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: Bytes::from(init_code.clone()),
+        });
+        let encoded = unsigned_to_hex(&tx);
+        let total_bytes = encoded.len() / 2 - 1;
+        let converter = EthereumVisualSignConverter::new();
+        let outcome = converter
+            .to_visual_sign_outcome_from_string(&encoded, VisualSignOptions::default())
+            .unwrap();
+        let field = |label: &str| {
+            outcome
+                .payload
+                .fields
+                .iter()
+                .find(|field| field.label() == label)
+                .map(|field| field.fallback_text().as_str())
+        };
+        assert_eq!(field("To"), Some("Creates New Contract"));
+        assert_eq!(field("Init Code Size"), Some("6 bytes"));
+        assert_eq!(field("Init Code"), Some("0x608060405200 (6 bytes)"));
+        assert_eq!(field("Predicted Contract Address"), None);
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0].message.starts_with("Init code"));
+        assert_eq!(outcome.coverage, Some(Coverage::new(total_bytes, 6)));
+
+        // A CREATE2 deployment the host describes is predicted
+        let mut options = VisualSignOptions::default();
+        let deployment = Create2Deployment {
+            factory: "0x4e59b44847b379578588920cA78FbF26c0B4956C".to_string(),
+            salt: format!("0x{}", "00".repeat(32)),
+        };
+        let expected = creation::create2_address(&deployment, &init_code).unwrap();
+        options.extensions.insert(deployment);
+        let payload = converter
+            .to_visual_sign_payload_from_string(&encoded, options)
+            .unwrap();
+        let predicted = payload
+            .fields
+            .iter()
+            .find(|field| field.label() == "Predicted Contract Address")
+            .unwrap();
+        assert_eq!(predicted.fallback_text(), &expected.to_string());
+    }
+
+    #[test]
+    fn test_field_provenance() {
+        let tx = TypedTransaction::Eip1559(alloy_consensus::TxEip1559 {
+            chain_id: ChainId::from(1u64),
+            nonce: 7,
+            gas_limit: 21000,
+            max_fee_per_gas: 30_000_000_000u128,
+            max_priority_fee_per_gas: 2_000_000_000u128,
+            to: alloy_primitives::TxKind::Call(Address::repeat_byte(0x11)),
+            value: U256::from(5u64),
+            access_list: Default::default(),
+            input: Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+        });
+        let encoded = unsigned_to_hex(&tx);
+        let raw = hex::decode(&encoded[2..]).unwrap();
+        let converter = EthereumVisualSignConverter::new();
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                include_provenance: true,
+                ..Default::default()
+            },
+            ..VisualSignOptions::default()
+        };
+        let outcome = converter
+            .to_visual_sign_outcome_from_string(&encoded, options.clone())
+            .unwrap();
+        let payload = &outcome.payload;
+        assert!(outcome.provenance.verify(payload, &raw).is_ok());
+
+        let bytes_of = |label: &str| {
+            let index = payload
+                .fields
+                .iter()
+                .position(|field| field.label() == label)
+                .unwrap();
+            outcome
+                .provenance
+                .bytes(&Provenance::field_path(index), &raw)
+        };
+        assert_eq!(bytes_of("Nonce"), Some(&[0x07][..]));
+        assert_eq!(bytes_of("Value"), Some(&[0x05][..]));
+        assert_eq!(bytes_of("Replay Protection"), Some(&[0x01][..]));
+        let mut to = vec![0x94];
+        to.extend([0x11; 20]);
+        assert_eq!(bytes_of("To"), Some(&to[..]));
+        // Raw call data is decoded from the input item
+        assert_eq!(
+            bytes_of("Input Data").map(<[u8]>::to_vec),
+            Some(vec![0x84, 0xde, 0xad, 0xbe, 0xef])
+        );
+        // Computed from several items
+        assert_eq!(bytes_of("Transaction Summary"), None);
+
+        // Wrapped fields keep their sources under the preview layout
+        let wrapped = converter
+            .to_visual_sign_outcome_from_string(
+                &encoded,
+                VisualSignOptions {
+                    output: OutputFlags {
+                        preview_layout: true,
+                        ..options.output
+                    },
+                    ..options
+                },
+            )
+            .unwrap();
+        assert_eq!(wrapped.provenance.len(), outcome.provenance.len());
+        assert!(wrapped.provenance.verify(&wrapped.payload, &raw).is_ok());
+
+        // Off unless requested
+        let outcome = converter
+            .to_visual_sign_outcome_from_string(&encoded, VisualSignOptions::default())
+            .unwrap();
+        assert!(outcome.provenance.is_empty());
+    }
+
+    #[test]
+    fn test_verify_lossless() {
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                verify_lossless: true,
+                ..Default::default()
+            },
+            ..VisualSignOptions::default()
+        };
+        let legacy = TypedTransaction::Legacy(TxLegacy {
+            chain_id: Some(ChainId::from(1u64)),
+            nonce: 3,
+            gas_price: 20_000_000_000u128,
+            gas_limit: 21000,
+            to: alloy_primitives::TxKind::Call(Address::ZERO),
+            value: U256::from(1u64),
+            input: Bytes::new(),
+        });
+        // Unsigned, signed legacy and signed EIP-1559 encodings all re-encode to their input
+        for encoded in [
+            unsigned_to_hex(&legacy),
+            "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83".to_string(),
+            "0x02f873010184773594008506fc23ac00825208943535353535353535353535353535353535353535880de0b6b3a764000080c080a0bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020da0016efaa09b5ffd7af4580187e27b424a5a08f9cc696b5d0ce8900049607c4c61".to_string(),
+        ] {
+            let wrapper = EthereumTransactionWrapper::from_string(&encoded).unwrap();
+            assert!(wrapper.verify_lossless(&encoded).is_ok());
+            assert!(
+                EthereumVisualSignConverter::new()
+                    .to_visual_sign_payload_from_string(&encoded, options.clone())
+                    .is_ok()
+            );
+        }
+
+        // A wrapper whose bytes are not the encoding of its transaction is rejected
+        let mut wrapper =
+            EthereumTransactionWrapper::from_string(&unsigned_to_hex(&legacy)).unwrap();
+        wrapper.raw.as_mut().unwrap().push(0x00);
+        let Err(TransactionParseError::Parser(detail)) = wrapper.verify_lossless("") else {
+            panic!("Expected a re-encoding mismatch");
+        };
+        assert_eq!(detail.reason, "ReencodingMismatch");
+        assert_eq!(detail.offset, Some(wrapper.raw().unwrap().len() - 1));
+    }
+
+    #[test]
+    fn test_signed_raw_transactions() {
+        // Private key 0x4646...46, the EIP-155 example signer
+        let sender: Address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+            .parse()
+            .unwrap();
+
+        // The signed legacy transaction from the EIP-155 specification
+        let legacy = EthereumTransactionWrapper::from_string(
+            "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        )
+        .unwrap();
+        assert_eq!(legacy.sender(), Some(sender));
+        assert_eq!(legacy.inner().chain_id(), Some(1));
+        assert_eq!(legacy.inner().nonce(), 9);
+        assert!(legacy.signature().is_some());
+
+        let eip1559 = EthereumTransactionWrapper::from_string(
+            "0x02f873010184773594008506fc23ac00825208943535353535353535353535353535353535353535880de0b6b3a764000080c080a0bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020da0016efaa09b5ffd7af4580187e27b424a5a08f9cc696b5d0ce8900049607c4c61",
+        )
+        .unwrap();
+        assert_eq!(eip1559.sender(), Some(sender));
+        assert_eq!(eip1559.inner().tx_type(), TxType::Eip1559);
+        assert_eq!(eip1559.inner().max_fee_per_gas(), 30_000_000_000);
+
+        let payload = EthereumVisualSignConverter::new()
+            .to_visual_sign_payload(eip1559, VisualSignOptions::default())
+            .unwrap();
+        let labels: Vec<&str> = payload.fields.iter().map(|f| f.label().as_str()).collect();
+        assert_eq!(
+            &labels[..4],
+            ["Network", "Transaction Summary", "From", "Signature Status"]
+        );
+        let SignablePayloadField::AddressV2 { address_v2, .. } = &payload.fields[2] else {
+            panic!("Expected AddressV2 for From");
+        };
+        assert_eq!(address_v2.address, sender.to_string());
+        assert_eq!(payload.fields[3].fallback_text(), "Already signed");
+
+        // Unsigned encodings carry no signature and show no sender
+        let unsigned = TypedTransaction::Eip1559(alloy_consensus::TxEip1559 {
+            chain_id: ChainId::from(1u64),
+            nonce: 1,
+            gas_limit: 21000,
+            max_fee_per_gas: 30_000_000_000u128,
+            max_priority_fee_per_gas: 2_000_000_000u128,
+            to: alloy_primitives::TxKind::Call(Address::ZERO),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: Bytes::new(),
+        });
+        let wrapper = EthereumTransactionWrapper::from_string(&unsigned_to_hex(&unsigned)).unwrap();
+        assert_eq!(wrapper.sender(), None);
+        let payload = EthereumVisualSignConverter::new()
+            .to_visual_sign_payload(wrapper, VisualSignOptions::default())
+            .unwrap();
+        assert!(payload.fields.iter().all(|f| f.label() != "From"));
+
+        // ...unless the host names the account it will sign with
+        let with_sender = |sender: &str| VisualSignOptions {
+            sender: Some(sender.to_string()),
+            ..Default::default()
+        };
+        let wrapper = EthereumTransactionWrapper::from_string(&unsigned_to_hex(&unsigned)).unwrap();
+        let payload = EthereumVisualSignConverter::new()
+            .to_visual_sign_payload(
+                wrapper,
+                with_sender("0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"),
+            )
+            .unwrap();
+        let labels: Vec<&str> = payload.fields.iter().map(|f| f.label().as_str()).collect();
+        assert_eq!(&labels[..3], ["Network", "Transaction Summary", "From"]);
+        let SignablePayloadField::AddressV2 { address_v2, .. } = &payload.fields[2] else {
+            panic!("Expected AddressV2 for From");
+        };
+        assert_eq!(address_v2.address, sender.to_string());
+        assert_eq!(address_v2.badge_text.as_deref(), Some("Unverified"));
+        assert!(!labels.contains(&"Signature Status"));
+
+        // A provided sender must agree with the recovered one
+        let legacy_hex = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+        let error = EthereumVisualSignConverter::new()
+            .to_visual_sign_payload(
+                EthereumTransactionWrapper::from_string(legacy_hex).unwrap(),
+                with_sender(&Address::ZERO.to_string()),
+            )
+            .unwrap_err();
+        assert!(matches!(error, VisualSignError::ValidationError(_)));
+        assert!(
+            EthereumVisualSignConverter::new()
+                .to_visual_sign_payload(
+                    EthereumTransactionWrapper::from_string(legacy_hex).unwrap(),
+                    with_sender("not an address"),
+                )
+                .is_err()
+        );
+
+        // The EIP-155 example with a zero r cannot be attributed to any sender
+        let error = EthereumTransactionWrapper::from_string(
+            "0xf84c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000802580a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), ParserErrorKind::Encoding);
+    }
+
     #[test]
     fn test_transaction_wrapper_type() {
         let tx = TypedTransaction::Legacy(TxLegacy {
@@ -805,7 +1936,7 @@ mod tests {
                 VisualSignOptions {
                     decode_transfers: true,
                     transaction_name: Some("Test Transaction".to_string()),
-                    metadata: None,
+                    ..Default::default()
                 }
             ),
             Ok(SignablePayload::new(
@@ -822,6 +1953,12 @@ mod tests {
                             text: "Ethereum Mainnet".to_string(),
                         },
                     },
+                    summary::create_transaction_summary(
+                        &tx,
+                        true,
+                        &registry::ContractRegistry::new()
+                    )
+                    .unwrap(),
                     SignablePayloadField::AddressV2 {
                         common: SignablePayloadFieldCommon {
                             fallback_text: "0x0000000000000000000000000000000000000000".to_string(),
@@ -833,6 +1970,7 @@ mod tests {
                             asset_label: "Test Asset".to_string(),
                             memo: None,
                             badge_text: None,
+                            name_resolved: None,
                         },
                     },
                     SignablePayloadField::AmountV2 {
@@ -886,4 +2024,37 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_capabilities_list_registered_protocols() {
+        let capabilities = EthereumVisualSignConverter::new().capabilities();
+        assert_eq!(
+            capabilities.transaction_variants,
+            vec!["Legacy", "EIP-1559"]
+        );
+        assert_eq!(
+            capabilities.decoders,
+            vec![
+                "AaveV3Pool",
+                "BalancerV2Vault",
+                "CctpTokenMessenger",
+                "CompoundV3Comet",
+                "CurveRouterNg",
+                "ERC20",
+                "EigenLayerDelegationManager",
+                "EigenLayerStrategyManager",
+                "LidoStEth",
+                "LidoWithdrawalQueue",
+                "LidoWstEth",
+                "OneInchAggregationRouterV5",
+                "OneInchAggregationRouterV6",
+                "UniswapUniversalRouter",
+                "UniswapV4PoolManager",
+                "UniswapV4PositionManager",
+                "WormholeTokenBridge"
+            ]
+        );
+        assert_eq!(capabilities.features.get("eip1559"), Some(&true));
+        assert_eq!(capabilities.features.get("eip4844"), Some(&false));
+    }
 }
@@ -0,0 +1,226 @@
+//! EIP-191 `personal_sign` messages.
+//!
+//! `personal_sign` takes the message as `0x` hex (how dApps usually send it) or as plain text.
+//! Wallets sign `"\x19Ethereum Signed Message:\n" + len(message) + message`; input that already
+//! carries that prefix has it removed, so the signer sees the message itself. The prefix also
+//! keeps the signature from being valid for any transaction, so none of the transaction fields
+//! apply; the message is shown through [`visualsign::message`].
+
+use alloy_primitives::{Address, eip191_hash_message};
+use visualsign::{
+    SignablePayload, SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2,
+    capabilities::ParserCapabilities,
+    message::{create_message_fields, message_length_cap},
+    parser_info::ParserInfo,
+    sender::{create_sender_field, resolve_sender},
+    vsptrait::{
+        Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
+        VisualSignError, VisualSignOptions,
+    },
+};
+
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// The message of a `personal_sign` request, without the EIP-191 prefix.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PersonalSignMessage {
+    message: Vec<u8>,
+}
+
+impl PersonalSignMessage {
+    /// Wraps `message`, removing an EIP-191 prefix whose length matches the rest.
+    pub fn new(message: Vec<u8>) -> Self {
+        let message = match strip_eip191_prefix(&message) {
+            Some(stripped) => stripped.to_vec(),
+            None => message,
+        };
+        Self { message }
+    }
+
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+}
+
+impl Transaction for PersonalSignMessage {
+    fn from_string(data: &str) -> Result<Self, TransactionParseError> {
+        // Like wallets, read `0x` followed by valid hex as bytes and anything else as text
+        let message = data
+            .strip_prefix("0x")
+            .and_then(|digits| hex::decode(digits).ok())
+            .unwrap_or_else(|| data.as_bytes().to_vec());
+        Ok(Self::new(message))
+    }
+
+    fn transaction_type(&self) -> String {
+        "EthereumPersonalSign".to_string()
+    }
+}
+
+fn strip_eip191_prefix(message: &[u8]) -> Option<&[u8]> {
+    let rest = message.strip_prefix(EIP191_PREFIX)?;
+    let digits = rest.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    // The length is written in decimal, so try each split of the leading digits
+    (1..=digits).find_map(|split| {
+        let length: usize = std::str::from_utf8(&rest[..split]).ok()?.parse().ok()?;
+        let body = &rest[split..];
+        (body.len() == length).then_some(body)
+    })
+}
+
+/// Converter for `personal_sign` messages.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PersonalSignConverter;
+
+impl VisualSignConverter<PersonalSignMessage> for PersonalSignConverter {
+    fn to_visual_sign_payload(
+        &self,
+        message: PersonalSignMessage,
+        options: VisualSignOptions,
+    ) -> Result<SignablePayload, VisualSignError> {
+        // Nothing in a message names its signer, so a provided sender stays unverified
+        let provided = options
+            .sender
+            .as_deref()
+            .map(|sender| {
+                sender.parse::<Address>().map_err(|e| {
+                    VisualSignError::ValidationError(format!("Invalid sender {sender}: {e}"))
+                })
+            })
+            .transpose()?;
+        let sender = resolve_sender(
+            None,
+            provided.map(|s| s.to_string()).as_deref(),
+            |derived, provided| derived == provided,
+        )?;
+
+        let mut fields = create_message_fields(
+            message.message(),
+            options.charset,
+            message_length_cap(&options),
+        );
+        if let Some(sender) = sender {
+            fields.push(create_sender_field(&sender));
+        }
+        let hash = eip191_hash_message(message.message()).to_string();
+        fields.push(SignablePayloadField::TextV2 {
+            common: SignablePayloadFieldCommon {
+                fallback_text: hash.clone(),
+                label: "Message Hash".to_string(),
+            },
+            text_v2: SignablePayloadFieldTextV2 { text: hash },
+        });
+
+        let title = options
+            .transaction_name
+            .unwrap_or_else(|| "Sign Message".to_string());
+        Ok(SignablePayload::new(
+            0,
+            title,
+            None,
+            fields,
+            "EthereumMessage".to_string(),
+        ))
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        ParserCapabilities::new(&["hex", "text"], &["EIP-191 personal_sign"])
+            .with_feature("eip191", true)
+    }
+}
+
+impl VisualSignConverterFromString<PersonalSignMessage> for PersonalSignConverter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visualsign::message::{SIGNING_TYPE_LABEL, SIGNING_TYPE_MESSAGE};
+
+    fn labelled(payload: &SignablePayload) -> Vec<String> {
+        payload
+            .fields
+            .iter()
+            .map(|field| format!("{}: {}", field.label(), field.fallback_text()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hex_message() {
+        let payload = PersonalSignConverter
+            .to_visual_sign_payload_from_string(
+                "0x68656c6c6f20776f726c64",
+                VisualSignOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(payload.title, "Sign Message");
+        assert_eq!(payload.payload_type, "EthereumMessage");
+        assert_eq!(
+            labelled(&payload),
+            vec![
+                format!("{SIGNING_TYPE_LABEL}: {SIGNING_TYPE_MESSAGE}"),
+                "Message: hello world".to_string(),
+                "Message Size: 11 bytes".to_string(),
+                "Message Hash: 0xd9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text_and_prefixed_messages() {
+        let text = PersonalSignMessage::from_string("hello world").unwrap();
+        assert_eq!(text.message(), b"hello world");
+        // Not valid hex, so taken as text
+        assert_eq!(
+            PersonalSignMessage::from_string("0xhello")
+                .unwrap()
+                .message(),
+            b"0xhello"
+        );
+
+        let prefixed =
+            PersonalSignMessage::new(b"\x19Ethereum Signed Message:\n11hello world".to_vec());
+        assert_eq!(prefixed, text);
+        // A length that does not match leaves the message as it is
+        let mismatched = b"\x19Ethereum Signed Message:\n5hello world".to_vec();
+        assert_eq!(
+            PersonalSignMessage::new(mismatched.clone()).message(),
+            mismatched
+        );
+    }
+
+    #[test]
+    fn test_binary_message_and_sender() {
+        let options = VisualSignOptions {
+            sender: Some("0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f".to_string()),
+            ..VisualSignOptions::default()
+        };
+        let payload = PersonalSignConverter
+            .to_validated_visual_sign_payload(
+                PersonalSignMessage::new(vec![0xde, 0xad, 0xbe, 0xef]),
+                options,
+            )
+            .unwrap();
+        assert_eq!(payload.fields[1].label(), "Message (hex)");
+        assert_eq!(payload.fields[1].fallback_text(), "0xdeadbeef");
+        assert_eq!(payload.fields[3].label(), "From");
+        assert_eq!(
+            payload.fields[3].fallback_text(),
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+
+        let options = VisualSignOptions {
+            sender: Some("not an address".to_string()),
+            ..VisualSignOptions::default()
+        };
+        assert!(matches!(
+            PersonalSignConverter
+                .to_visual_sign_payload(PersonalSignMessage::new(vec![1]), options),
+            Err(VisualSignError::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+//! Aave protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for the Aave V3 Pool, the entry point for supplying, borrowing,
+/// repaying and withdrawing
+///
+/// Reference: <https://aave.com/docs/resources/addresses>
+#[derive(Debug, Clone, Copy)]
+pub struct AaveV3Pool;
+
+impl ContractType for AaveV3Pool {}
+
+/// Aave protocol configuration
+pub struct AaveConfig;
+
+impl AaveConfig {
+    /// Returns the chain IDs and addresses of the Aave V3 Pool
+    ///
+    /// Supported chains: Mainnet (1), Optimism (10), Polygon (137), Base (8453) and
+    /// Arbitrum One (42161). Optimism, Polygon and Arbitrum share one address.
+    ///
+    /// Source: <https://github.com/bgd-labs/aave-address-book>
+    pub fn v3_pool_deployments() -> Vec<(u64, Address)> {
+        let shared: Address = "0x794a61358D6845594F94dc1DB02A252b5b4814aD"
+            .parse()
+            .expect("Valid Aave V3 Pool address");
+        vec![
+            (
+                1,
+                "0x87870Bca3F3fD6335C3F4ce8392D69350B4fA4E2"
+                    .parse()
+                    .expect("Valid Aave V3 Pool address"),
+            ),
+            (10, shared),
+            (137, shared),
+            (
+                8453,
+                "0xA238Dd80C259a72e81d7e4664a9801593F98d1c5"
+                    .parse()
+                    .expect("Valid Aave V3 Pool address"),
+            ),
+            (42161, shared),
+        ]
+    }
+}
@@ -0,0 +1,5 @@
+//! Aave protocol contract visualizers
+
+pub mod pool;
+
+pub use pool::AaveV3PoolVisualizer;
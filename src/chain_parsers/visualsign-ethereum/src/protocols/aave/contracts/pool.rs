@@ -0,0 +1,185 @@
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::protocols::fields::{
+    address_field, preview_field, text_field, token_amount, token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/aave/aave-v3-core/blob/master/contracts/interfaces/IPool.sol
+sol! {
+    interface IPool {
+        function supply(address asset, uint256 amount, address onBehalfOf, uint16 referralCode) external;
+
+        function borrow(address asset, uint256 amount, uint256 interestRateMode, uint16 referralCode, address onBehalfOf) external;
+
+        /// @param amount type(uint256).max repays the whole debt
+        function repay(address asset, uint256 amount, uint256 interestRateMode, address onBehalfOf) external returns (uint256);
+
+        /// @param amount type(uint256).max withdraws the whole balance
+        function withdraw(address asset, uint256 amount, address to) external returns (uint256);
+
+        function setUserUseReserveAsCollateral(address asset, bool useAsCollateral) external;
+    }
+}
+
+const LABEL: &str = "Aave V3 Pool";
+
+// From: https://github.com/aave/aave-v3-core/blob/master/contracts/protocol/libraries/types/DataTypes.sol
+fn interest_rate_mode(mode: U256) -> String {
+    match u8::try_from(mode) {
+        Ok(1) => "Stable".to_string(),
+        Ok(2) => "Variable".to_string(),
+        _ => format!("Unknown ({mode})"),
+    }
+}
+
+/// Visualizer for Aave V3 Pool supply, borrow, repay, withdraw and collateral calls
+pub struct AaveV3PoolVisualizer;
+
+impl AaveV3PoolVisualizer {
+    /// Decodes a Pool call, or returns None when `input` is not one
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        // `max` names the whole balance or debt that type(uint256).max stands for
+        let amount = |amount: U256, asset: Address, max: &str| {
+            if amount == U256::MAX {
+                max.replace("{asset}", &token_name(asset, chain_id, registry))
+            } else {
+                token_amount(amount, asset, chain_id, registry)
+            }
+        };
+        let asset_field =
+            |asset: Address| text_field("Asset", token_name(asset, chain_id, registry));
+
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        let (title, subtitle, details): (&str, String, Vec<AnnotatedPayloadField>) = match selector
+        {
+            IPool::supplyCall::SELECTOR => {
+                let call = IPool::supplyCall::abi_decode(input).ok()?;
+                let amount = amount(call.amount, call.asset, "all {asset}");
+                let mut details = vec![
+                    asset_field(call.asset),
+                    text_field("Amount", amount.clone()),
+                    address_field("On Behalf Of", call.onBehalfOf),
+                ];
+                details.extend(referral_field(call.referralCode));
+                ("Aave Supply", format!("Supply {amount}"), details)
+            }
+            IPool::borrowCall::SELECTOR => {
+                let call = IPool::borrowCall::abi_decode(input).ok()?;
+                let amount = amount(call.amount, call.asset, "all {asset}");
+                let mode = interest_rate_mode(call.interestRateMode);
+                let mut details = vec![
+                    asset_field(call.asset),
+                    text_field("Amount", amount.clone()),
+                    text_field("Interest Rate Mode", mode.clone()),
+                    address_field("On Behalf Of", call.onBehalfOf),
+                ];
+                details.extend(referral_field(call.referralCode));
+                (
+                    "Aave Borrow",
+                    format!("Borrow {amount} at a {} rate", mode.to_lowercase()),
+                    details,
+                )
+            }
+            IPool::repayCall::SELECTOR => {
+                let call = IPool::repayCall::abi_decode(input).ok()?;
+                let amount = amount(call.amount, call.asset, "full {asset} debt");
+                let details = vec![
+                    asset_field(call.asset),
+                    text_field("Amount", amount.clone()),
+                    text_field(
+                        "Interest Rate Mode",
+                        interest_rate_mode(call.interestRateMode),
+                    ),
+                    address_field("On Behalf Of", call.onBehalfOf),
+                ];
+                ("Aave Repay", format!("Repay {amount}"), details)
+            }
+            IPool::withdrawCall::SELECTOR => {
+                let call = IPool::withdrawCall::abi_decode(input).ok()?;
+                let amount = amount(call.amount, call.asset, "entire {asset} balance");
+                let details = vec![
+                    asset_field(call.asset),
+                    text_field("Amount", amount.clone()),
+                    address_field("To", call.to),
+                ];
+                ("Aave Withdraw", format!("Withdraw {amount}"), details)
+            }
+            IPool::setUserUseReserveAsCollateralCall::SELECTOR => {
+                let call = IPool::setUserUseReserveAsCollateralCall::abi_decode(input).ok()?;
+                let asset = token_name(call.asset, chain_id, registry);
+                let subtitle = if call.useAsCollateral {
+                    format!("Use {asset} as collateral")
+                } else {
+                    format!("Stop using {asset} as collateral")
+                };
+                let details = vec![
+                    asset_field(call.asset),
+                    text_field(
+                        "Use As Collateral",
+                        if call.useAsCollateral { "Yes" } else { "No" },
+                    ),
+                ];
+                ("Aave Collateral", subtitle, details)
+            }
+            _ => return None,
+        };
+        Some(preview_field(LABEL, title, subtitle, details))
+    }
+}
+
+fn referral_field(referral_code: u16) -> Option<AnnotatedPayloadField> {
+    (referral_code != 0).then(|| text_field("Referral Code", referral_code.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize_borrow() {
+        let asset = Address::repeat_byte(0x11);
+        let input = IPool::borrowCall {
+            asset,
+            amount: U256::from(500),
+            interestRateMode: U256::from(2),
+            referralCode: 0,
+            onBehalfOf: Address::repeat_byte(0x22),
+        }
+        .abi_encode();
+
+        let field = AaveV3PoolVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("Aave Borrow: Borrow 500 {asset} at a variable rate")
+        );
+    }
+
+    #[test]
+    fn test_visualize_withdraw_max() {
+        let input = IPool::withdrawCall {
+            asset: Address::ZERO,
+            amount: U256::MAX,
+            to: Address::repeat_byte(0x22),
+        }
+        .abi_encode();
+
+        let field = AaveV3PoolVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            "Aave Withdraw: Withdraw entire ETH balance"
+        );
+        assert_eq!(interest_rate_mode(U256::from(3)), "Unknown (3)");
+    }
+}
@@ -0,0 +1,27 @@
+//! Aave protocol implementation
+//!
+//! This module contains the visualizer for Aave V3 Pool calls, along with the Pool's
+//! configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::AaveConfig;
+pub use contracts::AaveV3PoolVisualizer;
+
+/// Registers the Aave V3 Pool on all supported chains
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    for (chain_id, address) in AaveConfig::v3_pool_deployments() {
+        contract_reg.register_contract_typed::<config::AaveV3Pool>(chain_id, vec![address]);
+    }
+}
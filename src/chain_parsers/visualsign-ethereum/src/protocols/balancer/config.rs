@@ -0,0 +1,33 @@
+//! Balancer protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for the Balancer V2 Vault, which holds the tokens of every V2 pool and
+/// executes all of their swaps
+///
+/// Reference: <https://docs.balancer.fi/reference/contracts/deployment-addresses/mainnet.html>
+#[derive(Debug, Clone, Copy)]
+pub struct BalancerV2Vault;
+
+impl ContractType for BalancerV2Vault {}
+
+/// Balancer protocol configuration
+pub struct BalancerConfig;
+
+impl BalancerConfig {
+    /// Returns the Balancer V2 Vault address, which is the same on every chain it is deployed to
+    pub fn vault_address() -> Address {
+        "0xBA12222222228d8Ba445958a75a0704d566BF2C8"
+            .parse()
+            .expect("Valid Balancer V2 Vault address")
+    }
+
+    /// Returns the chain IDs where the Vault is registered
+    ///
+    /// Supported chains: Mainnet (1), Optimism (10), Polygon (137), Base (8453) and
+    /// Arbitrum One (42161).
+    pub fn vault_chains() -> &'static [u64] {
+        &[1, 10, 137, 8453, 42161]
+    }
+}
@@ -0,0 +1,5 @@
+//! Balancer protocol contract visualizers
+
+pub mod vault;
+
+pub use vault::BalancerVaultVisualizer;
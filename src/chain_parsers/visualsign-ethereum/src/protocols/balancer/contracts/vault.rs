@@ -0,0 +1,310 @@
+use alloy_primitives::{Address, I256, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::text::route_summary;
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::protocols::fields::{
+    address_field, annotated, deadline_field, preview_field, text_field, token_amount, token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/balancer/balancer-v2-monorepo/blob/master/pkg/interfaces/contracts/vault/IVault.sol
+sol! {
+    /// `kind` is the SwapKind enum: 0 for GIVEN_IN, 1 for GIVEN_OUT
+    struct SingleSwap {
+        bytes32 poolId;
+        uint8 kind;
+        address assetIn;
+        address assetOut;
+        uint256 amount;
+        bytes userData;
+    }
+
+    struct BatchSwapStep {
+        bytes32 poolId;
+        uint256 assetInIndex;
+        uint256 assetOutIndex;
+        uint256 amount;
+        bytes userData;
+    }
+
+    struct FundManagement {
+        address sender;
+        bool fromInternalBalance;
+        address recipient;
+        bool toInternalBalance;
+    }
+
+    interface IVault {
+        function swap(SingleSwap singleSwap, FundManagement funds, uint256 limit, uint256 deadline)
+            external payable returns (uint256);
+
+        /// @param limits Maximum amount of each asset sent when positive, minimum received when negative
+        function batchSwap(
+            uint8 kind,
+            BatchSwapStep[] swaps,
+            address[] assets,
+            FundManagement funds,
+            int256[] limits,
+            uint256 deadline
+        ) external payable returns (int256[]);
+    }
+}
+
+const GIVEN_IN: u8 = 0;
+
+fn swap_kind(kind: u8) -> &'static str {
+    if kind == GIVEN_IN {
+        "Given In"
+    } else {
+        "Given Out"
+    }
+}
+
+/// Visualizer for Balancer V2 Vault `swap` and `batchSwap` calls
+pub struct BalancerVaultVisualizer;
+
+impl BalancerVaultVisualizer {
+    /// Decodes a Vault swap, or returns None when `input` is not one
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        match selector {
+            IVault::swapCall::SELECTOR => {
+                let call = IVault::swapCall::abi_decode(input).ok()?;
+                Some(Self::swap(&call, chain_id, registry))
+            }
+            IVault::batchSwapCall::SELECTOR => {
+                let call = IVault::batchSwapCall::abi_decode(input).ok()?;
+                Some(Self::batch_swap(&call, chain_id, registry))
+            }
+            _ => None,
+        }
+    }
+
+    fn swap(
+        call: &IVault::swapCall,
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> SignablePayloadField {
+        let swap = &call.singleSwap;
+        // The limit bounds whichever side the amount does not fix
+        let (amount_in, amount_out) = if swap.kind == GIVEN_IN {
+            (
+                token_amount(swap.amount, swap.assetIn, chain_id, registry),
+                format!(
+                    "at least {}",
+                    token_amount(call.limit, swap.assetOut, chain_id, registry)
+                ),
+            )
+        } else {
+            (
+                format!(
+                    "at most {}",
+                    token_amount(call.limit, swap.assetIn, chain_id, registry)
+                ),
+                token_amount(swap.amount, swap.assetOut, chain_id, registry),
+            )
+        };
+        let subtitle = format!("Swap {amount_in} for {amount_out}");
+
+        let mut details = vec![
+            text_field("Pool ID", swap.poolId.to_string()),
+            text_field("Kind", swap_kind(swap.kind)),
+            text_field("Amount In", amount_in),
+            text_field("Amount Out", amount_out),
+        ];
+        details.extend(funds_fields(&call.funds));
+        details.extend(deadline_field(call.deadline));
+
+        preview_field("Balancer Vault", "Balancer Swap", subtitle, details)
+    }
+
+    fn batch_swap(
+        call: &IVault::batchSwapCall,
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> SignablePayloadField {
+        let asset = |index: U256| -> Option<Address> {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| call.assets.get(index).copied())
+        };
+        let asset_name = |index: U256| {
+            asset(index).map_or_else(
+                || format!("asset {index}"),
+                |asset| token_name(asset, chain_id, registry),
+            )
+        };
+
+        let route = route_summary(
+            call.swaps
+                .iter()
+                .map(|step| asset_name(step.assetInIndex))
+                .chain(call.swaps.last().map(|step| asset_name(step.assetOutIndex))),
+        );
+        let subtitle = format!("{} steps: {route}", call.swaps.len());
+
+        let mut details = vec![
+            text_field("Kind", swap_kind(call.kind)),
+            text_field("Route", route),
+        ];
+        for (index, step) in call.swaps.iter().enumerate() {
+            // The amount side is the asset in for GIVEN_IN swaps and the asset out otherwise
+            let amount_asset = if call.kind == GIVEN_IN {
+                asset(step.assetInIndex)
+            } else {
+                asset(step.assetOutIndex)
+            };
+            let amount = match amount_asset {
+                // A zero amount takes the amount computed by the previous step
+                _ if step.amount.is_zero() => "Output of the previous step".to_string(),
+                Some(asset) => token_amount(step.amount, asset, chain_id, registry),
+                None => step.amount.to_string(),
+            };
+            let step_details = vec![
+                text_field("Pool ID", step.poolId.to_string()),
+                text_field("Asset In", asset_name(step.assetInIndex)),
+                text_field("Asset Out", asset_name(step.assetOutIndex)),
+                text_field("Amount", amount),
+            ];
+            details.push(annotated(preview_field(
+                &format!("Step {}", index + 1),
+                &format!("Step {}", index + 1),
+                route_summary([
+                    asset_name(step.assetInIndex),
+                    asset_name(step.assetOutIndex),
+                ]),
+                step_details,
+            )));
+        }
+        for (asset, limit) in call.assets.iter().zip(&call.limits) {
+            details.push(text_field(
+                &format!("Limit {}", token_name(*asset, chain_id, registry)),
+                limit_text(*limit, *asset, chain_id, registry),
+            ));
+        }
+        details.extend(funds_fields(&call.funds));
+        details.extend(deadline_field(call.deadline));
+
+        preview_field("Balancer Vault", "Balancer Batch Swap", subtitle, details)
+    }
+}
+
+fn limit_text(
+    limit: I256,
+    asset: Address,
+    chain_id: u64,
+    registry: Option<&ContractRegistry>,
+) -> String {
+    let amount = token_amount(limit.unsigned_abs(), asset, chain_id, registry);
+    if limit.is_negative() {
+        format!("Receive at least {amount}")
+    } else {
+        format!("Send at most {amount}")
+    }
+}
+
+fn funds_fields(funds: &FundManagement) -> Vec<AnnotatedPayloadField> {
+    let mut fields = vec![
+        address_field("Sender", funds.sender),
+        address_field("Recipient", funds.recipient),
+    ];
+    if funds.fromInternalBalance {
+        fields.push(text_field("Pay From", "Vault internal balance"));
+    }
+    if funds.toInternalBalance {
+        fields.push(text_field("Receive To", "Vault internal balance"));
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, Bytes};
+
+    fn funds() -> FundManagement {
+        FundManagement {
+            sender: Address::repeat_byte(0x01),
+            fromInternalBalance: false,
+            recipient: Address::repeat_byte(0x01),
+            toInternalBalance: false,
+        }
+    }
+
+    #[test]
+    fn test_visualize_swap() {
+        let token_out = Address::repeat_byte(0x22);
+        let input = IVault::swapCall {
+            singleSwap: SingleSwap {
+                poolId: B256::repeat_byte(0xaa),
+                kind: GIVEN_IN,
+                assetIn: Address::ZERO,
+                assetOut: token_out,
+                amount: U256::from(10).pow(U256::from(18)),
+                userData: Bytes::new(),
+            },
+            funds: funds(),
+            limit: U256::from(3000),
+            deadline: U256::from(1_700_000_000u64),
+        }
+        .abi_encode();
+
+        let field = BalancerVaultVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("Balancer Swap: Swap 1 ETH for at least 3000 {token_out}")
+        );
+    }
+
+    #[test]
+    fn test_visualize_batch_swap() {
+        let assets = vec![
+            Address::ZERO,
+            Address::repeat_byte(0x22),
+            Address::repeat_byte(0x33),
+        ];
+        let step = |asset_in: u64, asset_out: u64, amount: u64| BatchSwapStep {
+            poolId: B256::repeat_byte(0xaa),
+            assetInIndex: U256::from(asset_in),
+            assetOutIndex: U256::from(asset_out),
+            amount: U256::from(amount),
+            userData: Bytes::new(),
+        };
+        let input = IVault::batchSwapCall {
+            kind: GIVEN_IN,
+            swaps: vec![step(0, 1, 1000), step(1, 2, 0)],
+            assets: assets.clone(),
+            funds: funds(),
+            limits: vec![
+                I256::try_from(1000i64).unwrap(),
+                I256::ZERO,
+                I256::MINUS_ONE,
+            ],
+            deadline: U256::MAX,
+        }
+        .abi_encode();
+
+        let field = BalancerVaultVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!(
+                "Balancer Batch Swap: 2 steps: ETH -> {} -> {}",
+                assets[1], assets[2]
+            )
+        );
+        assert_eq!(
+            limit_text(I256::MINUS_ONE, assets[2], 1, None),
+            format!("Receive at least 1 {}", assets[2])
+        );
+    }
+}
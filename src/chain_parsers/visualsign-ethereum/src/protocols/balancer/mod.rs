@@ -0,0 +1,28 @@
+//! Balancer protocol implementation
+//!
+//! This module contains the visualizer for Balancer V2 Vault swaps, along with the Vault's
+//! configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::BalancerConfig;
+pub use contracts::BalancerVaultVisualizer;
+
+/// Registers the Balancer V2 Vault on all supported chains
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    let address = BalancerConfig::vault_address();
+    for &chain_id in BalancerConfig::vault_chains() {
+        contract_reg.register_contract_typed::<config::BalancerV2Vault>(chain_id, vec![address]);
+    }
+}
@@ -0,0 +1,34 @@
+//! Circle CCTP protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for the CCTP TokenMessenger, which burns USDC for Circle to mint on
+/// the destination domain
+///
+/// Reference: <https://developers.circle.com/stablecoins/evm-smart-contracts>
+#[derive(Debug, Clone, Copy)]
+pub struct CctpTokenMessenger;
+
+impl ContractType for CctpTokenMessenger {}
+
+/// Circle CCTP protocol configuration
+pub struct CctpConfig;
+
+impl CctpConfig {
+    /// Returns the V1 TokenMessenger address on Ethereum Mainnet
+    ///
+    /// Source: <https://developers.circle.com/stablecoins/evm-smart-contracts>
+    pub fn token_messenger_v1_address() -> Address {
+        "0xBd3fa81B58Ba92a82136038B25aDec7066af3155"
+            .parse()
+            .expect("Valid CCTP TokenMessenger address")
+    }
+
+    /// Returns the V2 TokenMessenger address, the same on every supported chain
+    pub fn token_messenger_v2_address() -> Address {
+        "0x28b5a0e9C621a5BadaA536219b3a228C8168cf5d"
+            .parse()
+            .expect("Valid CCTP TokenMessengerV2 address")
+    }
+}
@@ -0,0 +1,5 @@
+//! Circle CCTP protocol contract visualizers
+
+pub mod token_messenger;
+
+pub use token_messenger::CctpTokenMessengerVisualizer;
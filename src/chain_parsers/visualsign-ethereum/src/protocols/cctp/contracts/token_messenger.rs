@@ -0,0 +1,239 @@
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::protocols::fields::{
+    RemoteAddressFormat, preview_field, remote_chain, remote_recipient, text_field, token_amount,
+    token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/circlefin/evm-cctp-contracts/blob/master/src/TokenMessenger.sol
+sol! {
+    interface ITokenMessenger {
+        function depositForBurn(uint256 amount, uint32 destinationDomain, bytes32 mintRecipient, address burnToken) external returns (uint64 nonce);
+
+        /// Only `destinationCaller` can receive the message on the destination domain
+        function depositForBurnWithCaller(uint256 amount, uint32 destinationDomain, bytes32 mintRecipient, address burnToken, bytes32 destinationCaller) external returns (uint64 nonce);
+    }
+}
+
+// From: https://github.com/circlefin/evm-cctp-contracts/blob/master/src/v2/TokenMessengerV2.sol
+sol! {
+    interface ITokenMessengerV2 {
+        /// A zero `destinationCaller` lets anyone receive the message
+        function depositForBurn(uint256 amount, uint32 destinationDomain, bytes32 mintRecipient, address burnToken, bytes32 destinationCaller, uint256 maxFee, uint32 minFinalityThreshold) external;
+
+        function depositForBurnWithHook(uint256 amount, uint32 destinationDomain, bytes32 mintRecipient, address burnToken, bytes32 destinationCaller, uint256 maxFee, uint32 minFinalityThreshold, bytes hookData) external;
+    }
+}
+
+const LABEL: &str = "CCTP TokenMessenger";
+
+// From: https://developers.circle.com/stablecoins/supported-domains
+fn domain(id: u32) -> Option<(&'static str, RemoteAddressFormat)> {
+    let domain = match id {
+        0 => ("Ethereum", RemoteAddressFormat::Evm),
+        1 => ("Avalanche", RemoteAddressFormat::Evm),
+        2 => ("OP Mainnet", RemoteAddressFormat::Evm),
+        3 => ("Arbitrum", RemoteAddressFormat::Evm),
+        4 => ("Noble", RemoteAddressFormat::Hex),
+        5 => ("Solana", RemoteAddressFormat::Solana),
+        6 => ("Base", RemoteAddressFormat::Evm),
+        7 => ("Polygon PoS", RemoteAddressFormat::Evm),
+        8 => ("Sui", RemoteAddressFormat::Hex),
+        9 => ("Aptos", RemoteAddressFormat::Hex),
+        10 => ("Unichain", RemoteAddressFormat::Evm),
+        _ => return None,
+    };
+    Some(domain)
+}
+
+// From: https://developers.circle.com/cctp/technical-guide#finality-thresholds
+fn finality(threshold: u32) -> String {
+    match threshold {
+        ..=1000 => format!("Fast ({threshold})"),
+        2000.. => format!("Standard ({threshold})"),
+        _ => threshold.to_string(),
+    }
+}
+
+/// A burn, whichever TokenMessenger entrypoint it comes from
+struct Burn {
+    amount: U256,
+    destination_domain: u32,
+    mint_recipient: B256,
+    burn_token: Address,
+    destination_caller: B256,
+    /// The V2 fee cap and finality threshold
+    fast_transfer: Option<(U256, u32)>,
+    hook_data_len: Option<usize>,
+}
+
+/// Visualizer for CCTP V1 and V2 TokenMessenger burns
+pub struct CctpTokenMessengerVisualizer;
+
+impl CctpTokenMessengerVisualizer {
+    /// Decodes a TokenMessenger burn, or returns None when `input` is not one
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        let burn = match selector {
+            ITokenMessenger::depositForBurnCall::SELECTOR => {
+                let call = ITokenMessenger::depositForBurnCall::abi_decode(input).ok()?;
+                Burn {
+                    amount: call.amount,
+                    destination_domain: call.destinationDomain,
+                    mint_recipient: call.mintRecipient,
+                    burn_token: call.burnToken,
+                    destination_caller: B256::ZERO,
+                    fast_transfer: None,
+                    hook_data_len: None,
+                }
+            }
+            ITokenMessenger::depositForBurnWithCallerCall::SELECTOR => {
+                let call = ITokenMessenger::depositForBurnWithCallerCall::abi_decode(input).ok()?;
+                Burn {
+                    amount: call.amount,
+                    destination_domain: call.destinationDomain,
+                    mint_recipient: call.mintRecipient,
+                    burn_token: call.burnToken,
+                    destination_caller: call.destinationCaller,
+                    fast_transfer: None,
+                    hook_data_len: None,
+                }
+            }
+            ITokenMessengerV2::depositForBurnCall::SELECTOR => {
+                let call = ITokenMessengerV2::depositForBurnCall::abi_decode(input).ok()?;
+                Burn {
+                    amount: call.amount,
+                    destination_domain: call.destinationDomain,
+                    mint_recipient: call.mintRecipient,
+                    burn_token: call.burnToken,
+                    destination_caller: call.destinationCaller,
+                    fast_transfer: Some((call.maxFee, call.minFinalityThreshold)),
+                    hook_data_len: None,
+                }
+            }
+            ITokenMessengerV2::depositForBurnWithHookCall::SELECTOR => {
+                let call = ITokenMessengerV2::depositForBurnWithHookCall::abi_decode(input).ok()?;
+                Burn {
+                    amount: call.amount,
+                    destination_domain: call.destinationDomain,
+                    mint_recipient: call.mintRecipient,
+                    burn_token: call.burnToken,
+                    destination_caller: call.destinationCaller,
+                    fast_transfer: Some((call.maxFee, call.minFinalityThreshold)),
+                    hook_data_len: Some(call.hookData.len()),
+                }
+            }
+            _ => return None,
+        };
+
+        let amount = token_amount(burn.amount, burn.burn_token, chain_id, registry);
+        let (name, format) = match domain(burn.destination_domain) {
+            Some((name, format)) => (Some(name), format),
+            None => (None, RemoteAddressFormat::Hex),
+        };
+        let destination = remote_chain(name, "domain", burn.destination_domain);
+        let recipient = remote_recipient(burn.mint_recipient, format);
+
+        let mut details: Vec<AnnotatedPayloadField> = vec![
+            text_field("Token", token_name(burn.burn_token, chain_id, registry)),
+            text_field("Amount", amount.clone()),
+            text_field("Destination Domain", destination.clone()),
+            text_field("Mint Recipient", recipient.clone()),
+        ];
+        if burn.destination_caller != B256::ZERO {
+            details.push(text_field(
+                "Destination Caller",
+                remote_recipient(burn.destination_caller, format),
+            ));
+        }
+        if let Some((max_fee, threshold)) = burn.fast_transfer {
+            details.push(text_field(
+                "Max Fee",
+                token_amount(max_fee, burn.burn_token, chain_id, registry),
+            ));
+            details.push(text_field("Finality", finality(threshold)));
+        }
+        if let Some(len) = burn.hook_data_len {
+            details.push(text_field("Hook Data", format!("{len} bytes")));
+        }
+        Some(preview_field(
+            LABEL,
+            "CCTP Transfer",
+            format!("Burn {amount} to mint to {recipient} on {destination}"),
+            details,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize_deposit_for_burn() {
+        let usdc = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        let input = ITokenMessenger::depositForBurnCall {
+            amount: U256::from(250_000_000),
+            destinationDomain: 6,
+            mintRecipient: recipient.into_word(),
+            burnToken: usdc,
+        }
+        .abi_encode();
+
+        let field = CctpTokenMessengerVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!(
+                "CCTP Transfer: Burn 250000000 {usdc} to mint to {recipient} on Base (domain 6)"
+            )
+        );
+    }
+
+    #[test]
+    fn test_visualize_v2_deposit_for_burn() {
+        let usdc = Address::repeat_byte(0x11);
+        let input = ITokenMessengerV2::depositForBurnCall {
+            amount: U256::from(250_000_000),
+            destinationDomain: 5,
+            mintRecipient: B256::ZERO,
+            burnToken: usdc,
+            destinationCaller: B256::ZERO,
+            maxFee: U256::from(25_000),
+            minFinalityThreshold: 1000,
+        }
+        .abi_encode();
+
+        let field = CctpTokenMessengerVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!(
+                "CCTP Transfer: Burn 250000000 {usdc} to mint to 11111111111111111111111111111111 on Solana (domain 5)"
+            )
+        );
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        let details = preview_layout.expanded.unwrap().fields;
+        assert_eq!(
+            details
+                .last()
+                .unwrap()
+                .signable_payload_field
+                .fallback_text(),
+            "Fast (1000)"
+        );
+    }
+}
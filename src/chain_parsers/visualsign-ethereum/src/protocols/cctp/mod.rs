@@ -0,0 +1,33 @@
+//! Circle CCTP protocol implementation
+//!
+//! This module contains the visualizer for Cross-Chain Transfer Protocol burns, along with the
+//! TokenMessenger contracts' configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::CctpConfig;
+pub use contracts::CctpTokenMessengerVisualizer;
+
+/// Registers the CCTP V1 and V2 TokenMessengers on Ethereum Mainnet
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    use config::CctpTokenMessenger;
+
+    contract_reg.register_contract_typed::<CctpTokenMessenger>(
+        1,
+        vec![
+            CctpConfig::token_messenger_v1_address(),
+            CctpConfig::token_messenger_v2_address(),
+        ],
+    );
+}
@@ -0,0 +1,32 @@
+//! Compound protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for Compound V3 (Comet) markets. Each market is its own contract,
+/// lending out one base asset against several collateral assets.
+///
+/// Reference: <https://docs.compound.finance/#networks>
+#[derive(Debug, Clone, Copy)]
+pub struct CompoundV3Comet;
+
+impl ContractType for CompoundV3Comet {}
+
+/// Compound protocol configuration
+pub struct CompoundConfig;
+
+impl CompoundConfig {
+    /// Returns the Comet markets on Ethereum Mainnet: cUSDCv3, cWETHv3 and cUSDTv3
+    ///
+    /// Source: <https://github.com/compound-finance/comet/tree/main/deployments/mainnet>
+    pub fn mainnet_markets() -> Vec<Address> {
+        [
+            "0xc3d688B66703497DAA19211EEdff47f25384cdc3",
+            "0xA17581A9E3356d9A858b789D68B4d866e593aE94",
+            "0x3Afdc9BCA9213A35503b077a6072F3D0d5AB0840",
+        ]
+        .iter()
+        .map(|address| address.parse().expect("Valid Comet address"))
+        .collect()
+    }
+}
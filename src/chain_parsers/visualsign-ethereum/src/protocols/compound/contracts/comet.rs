@@ -0,0 +1,153 @@
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::protocols::fields::{
+    address_field, preview_field, text_field, token_amount, token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/compound-finance/comet/blob/main/contracts/CometMainInterface.sol
+sol! {
+    interface IComet {
+        function supply(address asset, uint256 amount) external;
+        function supplyTo(address dst, address asset, uint256 amount) external;
+        function supplyFrom(address from, address dst, address asset, uint256 amount) external;
+
+        /// Withdrawing more of the base asset than is supplied borrows the difference
+        function withdraw(address asset, uint256 amount) external;
+        function withdrawTo(address to, address asset, uint256 amount) external;
+        function withdrawFrom(address src, address to, address asset, uint256 amount) external;
+
+        /// @param isAllowed Whether `manager` may withdraw and transfer on behalf of the caller
+        function allow(address manager, bool isAllowed) external;
+    }
+}
+
+const LABEL: &str = "Compound V3";
+
+/// Visualizer for Compound V3 (Comet) supply, withdraw and manager calls
+pub struct CometVisualizer;
+
+impl CometVisualizer {
+    /// Decodes a Comet call, or returns None when `input` is not one
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        // type(uint256).max supplies enough to repay the whole borrow, or withdraws everything
+        let amount = |amount: U256, asset: Address, max: &str| {
+            if amount == U256::MAX {
+                max.replace("{asset}", &token_name(asset, chain_id, registry))
+            } else {
+                token_amount(amount, asset, chain_id, registry)
+            }
+        };
+        let asset_field =
+            |asset: Address| text_field("Asset", token_name(asset, chain_id, registry));
+
+        let supply = |from: Option<Address>, dst: Option<Address>, asset: Address, value: U256| {
+            let value = amount(value, asset, "all {asset} owed");
+            let mut details: Vec<AnnotatedPayloadField> =
+                vec![asset_field(asset), text_field("Amount", value.clone())];
+            details.extend(from.map(|from| address_field("From", from)));
+            details.extend(dst.map(|dst| address_field("On Behalf Of", dst)));
+            ("Compound Supply", format!("Supply {value}"), details)
+        };
+        let withdraw = |src: Option<Address>, to: Option<Address>, asset: Address, value: U256| {
+            let value = amount(value, asset, "entire {asset} balance");
+            let mut details: Vec<AnnotatedPayloadField> =
+                vec![asset_field(asset), text_field("Amount", value.clone())];
+            details.extend(src.map(|src| address_field("From Account", src)));
+            details.extend(to.map(|to| address_field("To", to)));
+            ("Compound Withdraw", format!("Withdraw {value}"), details)
+        };
+
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        let (title, subtitle, details) = match selector {
+            IComet::supplyCall::SELECTOR => {
+                let call = IComet::supplyCall::abi_decode(input).ok()?;
+                supply(None, None, call.asset, call.amount)
+            }
+            IComet::supplyToCall::SELECTOR => {
+                let call = IComet::supplyToCall::abi_decode(input).ok()?;
+                supply(None, Some(call.dst), call.asset, call.amount)
+            }
+            IComet::supplyFromCall::SELECTOR => {
+                let call = IComet::supplyFromCall::abi_decode(input).ok()?;
+                supply(Some(call.from), Some(call.dst), call.asset, call.amount)
+            }
+            IComet::withdrawCall::SELECTOR => {
+                let call = IComet::withdrawCall::abi_decode(input).ok()?;
+                withdraw(None, None, call.asset, call.amount)
+            }
+            IComet::withdrawToCall::SELECTOR => {
+                let call = IComet::withdrawToCall::abi_decode(input).ok()?;
+                withdraw(None, Some(call.to), call.asset, call.amount)
+            }
+            IComet::withdrawFromCall::SELECTOR => {
+                let call = IComet::withdrawFromCall::abi_decode(input).ok()?;
+                withdraw(Some(call.src), Some(call.to), call.asset, call.amount)
+            }
+            IComet::allowCall::SELECTOR => {
+                let call = IComet::allowCall::abi_decode(input).ok()?;
+                let subtitle = if call.isAllowed {
+                    format!("Allow {} to manage this account", call.manager)
+                } else {
+                    format!("Revoke {} as manager of this account", call.manager)
+                };
+                let details = vec![
+                    address_field("Manager", call.manager),
+                    text_field("Allowed", if call.isAllowed { "Yes" } else { "No" }),
+                ];
+                ("Compound Allow", subtitle, details)
+            }
+            _ => return None,
+        };
+        Some(preview_field(LABEL, title, subtitle, details))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize_supply_to() {
+        let asset = Address::repeat_byte(0x11);
+        let input = IComet::supplyToCall {
+            dst: Address::repeat_byte(0x22),
+            asset,
+            amount: U256::from(750),
+        }
+        .abi_encode();
+
+        let field = CometVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("Compound Supply: Supply 750 {asset}")
+        );
+    }
+
+    #[test]
+    fn test_visualize_allow() {
+        let manager = Address::repeat_byte(0x33);
+        let input = IComet::allowCall {
+            manager,
+            isAllowed: true,
+        }
+        .abi_encode();
+
+        let field = CometVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("Compound Allow: Allow {manager} to manage this account")
+        );
+    }
+}
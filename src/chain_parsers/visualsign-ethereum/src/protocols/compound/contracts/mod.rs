@@ -0,0 +1,5 @@
+//! Compound protocol contract visualizers
+
+pub mod comet;
+
+pub use comet::CometVisualizer;
@@ -0,0 +1,26 @@
+//! Compound protocol implementation
+//!
+//! This module contains the visualizer for Compound V3 (Comet) market calls, along with the
+//! markets' configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::CompoundConfig;
+pub use contracts::CometVisualizer;
+
+/// Registers the Compound V3 markets on Ethereum Mainnet
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    contract_reg
+        .register_contract_typed::<config::CompoundV3Comet>(1, CompoundConfig::mainnet_markets());
+}
@@ -0,0 +1,29 @@
+//! Curve protocol configuration
+//!
+//! Curve pools are deployed one contract per pool, so only the router that fronts them is
+//! registered by address. Pool `exchange` calls are recognised by their selectors.
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for the Curve Router NG, which routes swaps through up to five pools
+///
+/// Reference: <https://github.com/curvefi/curve-router-ng>
+#[derive(Debug, Clone, Copy)]
+pub struct CurveRouterNg;
+
+impl ContractType for CurveRouterNg {}
+
+/// Curve protocol configuration
+pub struct CurveConfig;
+
+impl CurveConfig {
+    /// Returns the Curve Router NG address on Ethereum Mainnet
+    ///
+    /// Source: <https://docs.curve.fi/references/deployed-contracts/>
+    pub fn router_ng_address() -> Address {
+        "0x16C6521Dff6baB339122a0FE25a9116693265353"
+            .parse()
+            .expect("Valid Curve Router NG address")
+    }
+}
@@ -0,0 +1,224 @@
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::text::route_summary;
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::protocols::fields::{
+    address_field, preview_field, text_field, token_amount, token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/curvefi/curve-contract/blob/master/contracts/pool-templates/base/SwapTemplateBase.vy
+sol! {
+    interface ICurveStablePool {
+        function exchange(int128 i, int128 j, uint256 dx, uint256 min_dy) external payable returns (uint256);
+        function exchange_underlying(int128 i, int128 j, uint256 dx, uint256 min_dy) external payable returns (uint256);
+    }
+}
+
+// From: https://github.com/curvefi/tricrypto-ng/blob/main/contracts/main/CurveTricryptoOptimized.vy
+sol! {
+    interface ICurveCryptoPool {
+        function exchange(uint256 i, uint256 j, uint256 dx, uint256 min_dy) external payable returns (uint256);
+    }
+}
+
+// From: https://github.com/curvefi/curve-router-ng/blob/master/contracts/Router.vy
+sol! {
+    interface ICurveRouterNg {
+        /// @param _route Tokens and pools of the route, alternating and padded with zero addresses
+        /// @param _swap_params [i, j, swap_type, pool_type, n_coins] for each pool of the route
+        function exchange(
+            address[11] _route,
+            uint256[5][5] _swap_params,
+            uint256 _amount,
+            uint256 _expected,
+            address[5] _pools,
+            address _receiver
+        ) external payable returns (uint256);
+    }
+}
+
+/// Visualizer for Curve pool `exchange` calls and Curve Router NG routes
+pub struct CurveVisualizer;
+
+impl CurveVisualizer {
+    /// Decodes a Curve swap, or returns None when `input` is not one
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        match selector {
+            ICurveRouterNg::exchangeCall::SELECTOR => {
+                let call = ICurveRouterNg::exchangeCall::abi_decode(input).ok()?;
+                Some(Self::router_exchange(&call, chain_id, registry))
+            }
+            ICurveStablePool::exchangeCall::SELECTOR => {
+                let call = ICurveStablePool::exchangeCall::abi_decode(input).ok()?;
+                Some(Self::pool_exchange(
+                    "Curve Exchange",
+                    call.i.to_string(),
+                    call.j.to_string(),
+                    call.dx,
+                    call.min_dy,
+                ))
+            }
+            ICurveStablePool::exchange_underlyingCall::SELECTOR => {
+                let call = ICurveStablePool::exchange_underlyingCall::abi_decode(input).ok()?;
+                Some(Self::pool_exchange(
+                    "Curve Exchange Underlying",
+                    call.i.to_string(),
+                    call.j.to_string(),
+                    call.dx,
+                    call.min_dy,
+                ))
+            }
+            ICurveCryptoPool::exchangeCall::SELECTOR => {
+                let call = ICurveCryptoPool::exchangeCall::abi_decode(input).ok()?;
+                Some(Self::pool_exchange(
+                    "Curve Exchange",
+                    call.i.to_string(),
+                    call.j.to_string(),
+                    call.dx,
+                    call.min_dy,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    // A pool only knows its coins by index, so amounts stay in raw units
+    fn pool_exchange(
+        title: &str,
+        coin_in: String,
+        coin_out: String,
+        amount_in: U256,
+        min_out: U256,
+    ) -> SignablePayloadField {
+        let subtitle =
+            format!("Swap {amount_in} of coin {coin_in} for at least {min_out} of coin {coin_out}");
+        let details = vec![
+            text_field("Coin In", format!("Index {coin_in}")),
+            text_field("Amount In", amount_in.to_string()),
+            text_field("Coin Out", format!("Index {coin_out}")),
+            text_field("Minimum Out", min_out.to_string()),
+        ];
+        preview_field(title, title, subtitle, details)
+    }
+
+    fn router_exchange(
+        call: &ICurveRouterNg::exchangeCall,
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> SignablePayloadField {
+        let hops = route_hops(&call._route);
+        let token_in = call._route[0];
+        let token_out = hops.last().map_or(token_in, |hop| hop.2);
+
+        let subtitle = format!(
+            "Swap {} for at least {}",
+            token_amount(call._amount, token_in, chain_id, registry),
+            token_amount(call._expected, token_out, chain_id, registry),
+        );
+        let route = route_summary(
+            std::iter::once(token_in)
+                .chain(hops.iter().map(|hop| hop.2))
+                .map(|token| token_name(token, chain_id, registry)),
+        );
+
+        let mut details: Vec<AnnotatedPayloadField> = vec![
+            text_field("Route", route),
+            text_field(
+                "Amount In",
+                token_amount(call._amount, token_in, chain_id, registry),
+            ),
+            text_field(
+                "Minimum Out",
+                token_amount(call._expected, token_out, chain_id, registry),
+            ),
+        ];
+        for (index, (from, pool, to)) in hops.iter().enumerate() {
+            details.push(text_field(
+                &format!("Hop {}", index + 1),
+                format!(
+                    "{} -> {} via pool {pool}",
+                    token_name(*from, chain_id, registry),
+                    token_name(*to, chain_id, registry),
+                ),
+            ));
+        }
+        details.push(address_field("Receiver", call._receiver));
+
+        preview_field("Curve Router", "Curve Router Swap", subtitle, details)
+    }
+}
+
+// (token in, pool, token out) of each hop, up to the first unused pool slot
+fn route_hops(route: &[Address; 11]) -> Vec<(Address, Address, Address)> {
+    (0..5)
+        .map(|hop| (route[2 * hop], route[2 * hop + 1], route[2 * hop + 2]))
+        .take_while(|(_, pool, _)| *pool != Address::ZERO)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize_pool_exchange() {
+        let input = ICurveStablePool::exchangeCall {
+            i: 0,
+            j: 1,
+            dx: U256::from(1000),
+            min_dy: U256::from(990),
+        }
+        .abi_encode();
+
+        let field = CurveVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            "Curve Exchange: Swap 1000 of coin 0 for at least 990 of coin 1"
+        );
+        assert_eq!(
+            CurveVisualizer.visualize_tx_commands(&input[..4], 1, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_visualize_router_exchange() {
+        let eth: Address = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"
+            .parse()
+            .unwrap();
+        let pool = Address::repeat_byte(0x22);
+        let token_out = Address::repeat_byte(0x33);
+        let mut route = [Address::ZERO; 11];
+        route[0] = eth;
+        route[1] = pool;
+        route[2] = token_out;
+        let input = ICurveRouterNg::exchangeCall {
+            _route: route,
+            _swap_params: [[U256::ZERO; 5]; 5],
+            _amount: U256::from(10).pow(U256::from(18)),
+            _expected: U256::from(2500),
+            _pools: [Address::ZERO; 5],
+            _receiver: Address::repeat_byte(0x44),
+        }
+        .abi_encode();
+
+        let field = CurveVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("Curve Router Swap: Swap 1 ETH for at least 2500 {token_out}")
+        );
+        assert_eq!(route_hops(&route), vec![(eth, pool, token_out)]);
+    }
+}
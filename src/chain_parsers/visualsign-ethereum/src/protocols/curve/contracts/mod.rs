@@ -0,0 +1,5 @@
+//! Curve protocol contract visualizers
+
+pub mod exchange;
+
+pub use exchange::CurveVisualizer;
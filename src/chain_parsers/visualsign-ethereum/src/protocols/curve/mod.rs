@@ -0,0 +1,28 @@
+//! Curve protocol implementation
+//!
+//! This module contains the visualizer for Curve pool and router swaps, along with the
+//! router's configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::CurveConfig;
+pub use contracts::CurveVisualizer;
+
+/// Registers the Curve Router NG in the ContractRegistry
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    contract_reg.register_contract_typed::<config::CurveRouterNg>(
+        1,
+        vec![CurveConfig::router_ng_address()],
+    );
+}
@@ -0,0 +1,41 @@
+//! EigenLayer protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for the EigenLayer DelegationManager, which records the operator each
+/// staker delegates to
+///
+/// Reference: <https://github.com/Layr-Labs/eigenlayer-contracts#deployments>
+#[derive(Debug, Clone, Copy)]
+pub struct EigenLayerDelegationManager;
+
+impl ContractType for EigenLayerDelegationManager {}
+
+/// Contract type marker for the EigenLayer StrategyManager, which takes deposits of liquid
+/// staking tokens into strategies
+#[derive(Debug, Clone, Copy)]
+pub struct EigenLayerStrategyManager;
+
+impl ContractType for EigenLayerStrategyManager {}
+
+/// EigenLayer protocol configuration
+pub struct EigenLayerConfig;
+
+impl EigenLayerConfig {
+    /// Returns the DelegationManager address on Ethereum Mainnet
+    ///
+    /// Source: <https://github.com/Layr-Labs/eigenlayer-contracts#deployments>
+    pub fn delegation_manager_address() -> Address {
+        "0x39053D51B77DC0d36036Fc1fCc8Cb819df8Ef37A"
+            .parse()
+            .expect("Valid EigenLayer DelegationManager address")
+    }
+
+    /// Returns the StrategyManager address on Ethereum Mainnet
+    pub fn strategy_manager_address() -> Address {
+        "0x858646372CC42E1A627fcE94aa7A7033e7CF075A"
+            .parse()
+            .expect("Valid EigenLayer StrategyManager address")
+    }
+}
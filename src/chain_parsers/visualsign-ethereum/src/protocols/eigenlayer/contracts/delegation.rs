@@ -0,0 +1,151 @@
+use alloy_primitives::Address;
+use alloy_sol_types::{SolCall, sol};
+use visualsign::{SignablePayloadField, SignablePayloadFieldStaticAnnotation};
+
+use crate::protocols::fields::{
+    address_field, deadline_field, preview_field, text_field, token_amount, token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/Layr-Labs/eigenlayer-contracts/blob/mainnet/src/contracts/interfaces/IDelegationManager.sol
+// and IStrategyManager.sol
+sol! {
+    struct SignatureWithExpiry {
+        bytes signature;
+        uint256 expiry;
+    }
+
+    interface IDelegationManager {
+        function delegateTo(address operator, SignatureWithExpiry approverSignatureAndExpiry, bytes32 approverSalt) external;
+
+        /// Undelegates `staker` and queues a withdrawal of all of their shares
+        function undelegate(address staker) external returns (bytes32[] withdrawalRoots);
+    }
+
+    interface IStrategyManager {
+        function depositIntoStrategy(address strategy, address token, uint256 amount) external returns (uint256 shares);
+    }
+}
+
+const LABEL: &str = "EigenLayer";
+
+/// Visualizer for EigenLayer delegation and StrategyManager deposits
+pub struct EigenLayerVisualizer;
+
+impl EigenLayerVisualizer {
+    /// Decodes an EigenLayer call, or returns None when `input` is not one
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        let (title, subtitle, details) = match selector {
+            IDelegationManager::delegateToCall::SELECTOR => {
+                let call = IDelegationManager::delegateToCall::abi_decode(input).ok()?;
+                // All of the staker's restaked shares follow the operator, and moving them
+                // means undelegating and waiting out the withdrawal delay
+                let mut operator = address_field("Operator", call.operator);
+                operator.static_annotation = Some(SignablePayloadFieldStaticAnnotation {
+                    text: "All restaked shares are delegated to this operator; changing it requires undelegating and withdrawing".to_string(),
+                });
+                let mut details = vec![operator];
+                if !call.approverSignatureAndExpiry.signature.is_empty() {
+                    details.push(text_field("Approver Signature", "Provided"));
+                    details.extend(deadline_field(call.approverSignatureAndExpiry.expiry));
+                }
+                (
+                    "EigenLayer Delegate",
+                    format!("Delegate all restaked shares to operator {}", call.operator),
+                    details,
+                )
+            }
+            IDelegationManager::undelegateCall::SELECTOR => {
+                let call = IDelegationManager::undelegateCall::abi_decode(input).ok()?;
+                (
+                    "EigenLayer Undelegate",
+                    format!(
+                        "Undelegate {} and queue withdrawal of all shares",
+                        call.staker
+                    ),
+                    vec![address_field("Staker", call.staker)],
+                )
+            }
+            IStrategyManager::depositIntoStrategyCall::SELECTOR => {
+                let call = IStrategyManager::depositIntoStrategyCall::abi_decode(input).ok()?;
+                let amount = token_amount(call.amount, call.token, chain_id, registry);
+                (
+                    "EigenLayer Deposit",
+                    format!("Deposit {amount} into strategy {}", call.strategy),
+                    vec![
+                        address_field("Strategy", call.strategy),
+                        text_field("Token", token_name(call.token, chain_id, registry)),
+                        text_field("Amount", amount),
+                    ],
+                )
+            }
+            _ => return None,
+        };
+        Some(preview_field(LABEL, title, subtitle, details))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, Bytes, U256};
+    use visualsign::SignablePayloadFieldCommon;
+
+    #[test]
+    fn test_visualize_delegate_to_shows_operator() {
+        let operator = Address::repeat_byte(0x42);
+        let input = IDelegationManager::delegateToCall {
+            operator,
+            approverSignatureAndExpiry: SignatureWithExpiry {
+                signature: Bytes::new(),
+                expiry: U256::ZERO,
+            },
+            approverSalt: B256::ZERO,
+        }
+        .abi_encode();
+
+        let field = EigenLayerVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("EigenLayer Delegate: Delegate all restaked shares to operator {operator}")
+        );
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected a PreviewLayout");
+        };
+        let first = &preview_layout.expanded.unwrap().fields[0];
+        let SignablePayloadField::AddressV2 {
+            common: SignablePayloadFieldCommon { label, .. },
+            address_v2,
+        } = &first.signable_payload_field
+        else {
+            panic!("Expected the operator address first");
+        };
+        assert_eq!(label, "Operator");
+        assert_eq!(address_v2.address, operator.to_string());
+        assert!(first.static_annotation.is_some());
+    }
+
+    #[test]
+    fn test_visualize_deposit_into_strategy() {
+        let strategy = Address::repeat_byte(0x55);
+        let input = IStrategyManager::depositIntoStrategyCall {
+            strategy,
+            token: Address::repeat_byte(0x66),
+            amount: U256::from(42),
+        }
+        .abi_encode();
+
+        let field = EigenLayerVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert!(field.fallback_text().contains(&strategy.to_string()));
+    }
+}
@@ -0,0 +1,5 @@
+//! EigenLayer protocol contract visualizers
+
+pub mod delegation;
+
+pub use delegation::EigenLayerVisualizer;
@@ -0,0 +1,34 @@
+//! EigenLayer protocol implementation
+//!
+//! This module contains the visualizer for EigenLayer delegation and strategy deposits, along
+//! with the contracts' configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::EigenLayerConfig;
+pub use contracts::EigenLayerVisualizer;
+
+/// Registers the EigenLayer core contracts on Ethereum Mainnet
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    use config::{EigenLayerDelegationManager, EigenLayerStrategyManager};
+
+    contract_reg.register_contract_typed::<EigenLayerDelegationManager>(
+        1,
+        vec![EigenLayerConfig::delegation_manager_address()],
+    );
+    contract_reg.register_contract_typed::<EigenLayerStrategyManager>(
+        1,
+        vec![EigenLayerConfig::strategy_manager_address()],
+    );
+}
@@ -0,0 +1,218 @@
+//! Field builders shared by the protocol visualizers.
+
+use alloy_primitives::{Address, B256, U256};
+use chrono::{TimeZone, Utc};
+use visualsign::text::sanitize_summary;
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldAddressV2,
+    SignablePayloadFieldCommon, SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout,
+    SignablePayloadFieldTextV2,
+};
+
+use crate::fmt::format_ether;
+use crate::registry::ContractRegistry;
+
+// Placeholder several aggregators use for the native currency
+const NATIVE_PLACEHOLDER: Address = Address::new([0xee; 20]);
+
+pub(crate) fn annotated(field: SignablePayloadField) -> AnnotatedPayloadField {
+    AnnotatedPayloadField {
+        signable_payload_field: field,
+        static_annotation: None,
+        dynamic_annotation: None,
+    }
+}
+
+pub(crate) fn text_field(label: &str, text: impl Into<String>) -> AnnotatedPayloadField {
+    let text = text.into();
+    annotated(SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: text.clone(),
+            label: label.to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 { text },
+    })
+}
+
+pub(crate) fn address_field(label: &str, address: Address) -> AnnotatedPayloadField {
+    annotated(SignablePayloadField::AddressV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: address.to_string(),
+            label: label.to_string(),
+        },
+        address_v2: SignablePayloadFieldAddressV2 {
+            address: address.to_string(),
+            name: "".to_string(),
+            memo: None,
+            asset_label: "".to_string(),
+            badge_text: None,
+            name_resolved: None,
+        },
+    })
+}
+
+/// A `PreviewLayout` titled `title`, with `details` as its expanded view when there are any.
+/// The subtitle is sanitized into a plain-text summary.
+pub(crate) fn preview_field(
+    label: &str,
+    title: &str,
+    subtitle: String,
+    details: Vec<AnnotatedPayloadField>,
+) -> SignablePayloadField {
+    let subtitle = sanitize_summary(&subtitle);
+    SignablePayloadField::PreviewLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text: format!("{title}: {subtitle}"),
+            label: label.to_string(),
+        },
+        preview_layout: SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: title.to_string(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 { text: subtitle }),
+            condensed: None,
+            expanded: (!details.is_empty())
+                .then_some(SignablePayloadFieldListLayout { fields: details }),
+        },
+    }
+}
+
+/// The `Deadline` of a call, or None when it is not a valid timestamp.
+pub(crate) fn deadline_field(deadline: U256) -> Option<AnnotatedPayloadField> {
+    let deadline: i64 = deadline.try_into().ok()?;
+    let deadline = Utc.timestamp_opt(deadline, 0).single()?.to_string();
+    Some(text_field("Deadline", deadline))
+}
+
+/// How a bridge's destination chain writes the 32-byte recipient the bridge carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RemoteAddressFormat {
+    /// A 20-byte address, left-padded with zeros
+    Evm,
+    /// A base58 public key
+    Solana,
+    /// Any other encoding, shown as hex
+    Hex,
+}
+
+/// `recipient` as the destination chain writes it. A recipient that should be an EVM address
+/// but is not left-padded cannot receive anything there, so it is flagged instead.
+pub(crate) fn remote_recipient(recipient: B256, format: RemoteAddressFormat) -> String {
+    match format {
+        RemoteAddressFormat::Evm if recipient[..12].iter().all(|byte| *byte == 0) => {
+            Address::from_slice(&recipient[12..]).to_string()
+        }
+        RemoteAddressFormat::Evm => format!("{recipient} (not an EVM address)"),
+        RemoteAddressFormat::Solana => bs58::encode(recipient).into_string(),
+        RemoteAddressFormat::Hex => recipient.to_string(),
+    }
+}
+
+/// `name (unit id)` for a destination the bridge names, flagged as unknown otherwise, e.g.
+/// `"Base (domain 6)"` or `"unknown domain 42"`.
+pub(crate) fn remote_chain(name: Option<&str>, unit: &str, id: impl std::fmt::Display) -> String {
+    match name {
+        Some(name) => format!("{name} ({unit} {id})"),
+        None => format!("unknown {unit} {id}"),
+    }
+}
+
+fn is_native(token: Address) -> bool {
+    token == Address::ZERO || token == NATIVE_PLACEHOLDER
+}
+
+/// The symbol of `token` when the registry knows it, "ETH" for the native currency, and its
+/// address otherwise.
+pub(crate) fn token_name(
+    token: Address,
+    chain_id: u64,
+    registry: Option<&ContractRegistry>,
+) -> String {
+    if is_native(token) {
+        return "ETH".to_string();
+    }
+    registry
+        .and_then(|registry| registry.get_token_symbol(chain_id, token))
+        .unwrap_or_else(|| token.to_string())
+}
+
+/// `amount` of `token` in whole units when its decimals are known, in raw units otherwise.
+pub(crate) fn token_amount(
+    amount: U256,
+    token: Address,
+    chain_id: u64,
+    registry: Option<&ContractRegistry>,
+) -> String {
+    if is_native(token) {
+        return format!("{} ETH", format_ether(amount));
+    }
+    let formatted = u128::try_from(amount).ok().and_then(|raw| {
+        registry.and_then(|registry| registry.format_token_amount(chain_id, token, raw))
+    });
+    match formatted {
+        Some((amount, symbol)) => format!("{amount} {symbol}"),
+        None => format!("{amount} {token}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_amount() {
+        let token = Address::repeat_byte(0x11);
+        let mut registry = ContractRegistry::new();
+        registry
+            .register_token(
+                1,
+                crate::token_metadata::TokenMetadata {
+                    symbol: "USDC".to_string(),
+                    name: "USD Coin".to_string(),
+                    erc_standard: crate::token_metadata::ErcStandard::Erc20,
+                    contract_address: token.to_string(),
+                    decimals: 6,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            token_amount(U256::from(1_500_000), token, 1, Some(&registry)),
+            "1.500000 USDC"
+        );
+        assert_eq!(
+            token_amount(U256::from(1_500_000), token, 1, None),
+            format!("1500000 {token}")
+        );
+        assert_eq!(
+            token_amount(
+                U256::from(10).pow(U256::from(18)),
+                NATIVE_PLACEHOLDER,
+                1,
+                None
+            ),
+            "1 ETH"
+        );
+        assert_eq!(token_name(token, 1, Some(&registry)), "USDC");
+    }
+
+    #[test]
+    fn test_remote_recipient() {
+        let address = Address::repeat_byte(0x11);
+        assert_eq!(
+            remote_recipient(address.into_word(), RemoteAddressFormat::Evm),
+            address.to_string()
+        );
+        let word = B256::repeat_byte(0x11);
+        assert_eq!(
+            remote_recipient(word, RemoteAddressFormat::Evm),
+            format!("{word} (not an EVM address)")
+        );
+        assert_eq!(
+            remote_recipient(B256::ZERO, RemoteAddressFormat::Solana),
+            "11111111111111111111111111111111"
+        );
+        assert_eq!(remote_chain(Some("Base"), "domain", 6), "Base (domain 6)");
+        assert_eq!(remote_chain(None, "domain", 42), "unknown domain 42");
+    }
+}
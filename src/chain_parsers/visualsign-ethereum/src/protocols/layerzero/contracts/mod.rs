@@ -0,0 +1,5 @@
+//! LayerZero protocol contract visualizers
+
+pub mod oft;
+
+pub use oft::OftVisualizer;
@@ -0,0 +1,161 @@
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::SignablePayloadField;
+
+use crate::fmt::format_ether;
+use crate::protocols::fields::{
+    RemoteAddressFormat, address_field, preview_field, remote_chain, remote_recipient, text_field,
+    token_amount,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/LayerZero-Labs/devtools/blob/main/packages/oft-evm/contracts/interfaces/IOFT.sol
+sol! {
+    struct SendParam {
+        uint32 dstEid;
+        bytes32 to;
+        uint256 amountLD;
+        uint256 minAmountLD;
+        bytes extraOptions;
+        bytes composeMsg;
+        bytes oftCmd;
+    }
+
+    struct MessagingFee {
+        uint256 nativeFee;
+        uint256 lzTokenFee;
+    }
+
+    interface IOFT {
+        /// Debits `amountLD` from the sender and sends it to `to` on the `dstEid` endpoint
+        function send(SendParam _sendParam, MessagingFee _fee, address _refundAddress) external payable;
+    }
+}
+
+const LABEL: &str = "LayerZero OFT";
+
+// From: https://docs.layerzero.network/v2/deployments/deployed-contracts
+fn endpoint(eid: u32) -> Option<(&'static str, RemoteAddressFormat)> {
+    let endpoint = match eid {
+        30101 => ("Ethereum", RemoteAddressFormat::Evm),
+        30102 => ("BNB Smart Chain", RemoteAddressFormat::Evm),
+        30106 => ("Avalanche", RemoteAddressFormat::Evm),
+        30109 => ("Polygon", RemoteAddressFormat::Evm),
+        30110 => ("Arbitrum", RemoteAddressFormat::Evm),
+        30111 => ("Optimism", RemoteAddressFormat::Evm),
+        30168 => ("Solana", RemoteAddressFormat::Solana),
+        30184 => ("Base", RemoteAddressFormat::Evm),
+        _ => return None,
+    };
+    Some(endpoint)
+}
+
+/// Visualizer for LayerZero V2 OFT and OFT adapter sends
+pub struct OftVisualizer;
+
+impl OftVisualizer {
+    /// Decodes an OFT `send`, or returns None when `input` is not one. `oft` is the contract
+    /// called; amounts are in the local decimals of the token it sends, which for an OFT is
+    /// itself.
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        oft: Address,
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        if selector != IOFT::sendCall::SELECTOR {
+            return None;
+        }
+        let call = IOFT::sendCall::abi_decode(input).ok()?;
+        let param = call._sendParam;
+
+        let amount = |amount: U256| token_amount(amount, oft, chain_id, registry);
+        let (name, format) = match endpoint(param.dstEid) {
+            Some((name, format)) => (Some(name), format),
+            None => (None, RemoteAddressFormat::Hex),
+        };
+        let destination = remote_chain(name, "endpoint", param.dstEid);
+        let recipient = remote_recipient(param.to, format);
+        let sent = amount(param.amountLD);
+
+        let mut details = vec![
+            text_field("Amount", sent.clone()),
+            text_field("Minimum Received", amount(param.minAmountLD)),
+            text_field("Destination Chain", destination.clone()),
+            text_field("Recipient", recipient.clone()),
+            text_field(
+                "Messaging Fee",
+                format!("{} ETH", format_ether(call._fee.nativeFee)),
+            ),
+            address_field("Refund Address", call._refundAddress),
+        ];
+        if !param.composeMsg.is_empty() {
+            details.push(text_field(
+                "Compose Message",
+                format!("{} bytes", param.composeMsg.len()),
+            ));
+        }
+        Some(preview_field(
+            LABEL,
+            "LayerZero Send",
+            format!("Bridge {sent} to {recipient} on {destination}"),
+            details,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, Bytes};
+
+    fn send(dst_eid: u32, to: B256) -> Vec<u8> {
+        IOFT::sendCall {
+            _sendParam: SendParam {
+                dstEid: dst_eid,
+                to,
+                amountLD: U256::from(5_000_000),
+                minAmountLD: U256::from(4_950_000),
+                extraOptions: Bytes::new(),
+                composeMsg: Bytes::new(),
+                oftCmd: Bytes::new(),
+            },
+            _fee: MessagingFee {
+                nativeFee: U256::from(10).pow(U256::from(15)),
+                lzTokenFee: U256::ZERO,
+            },
+            _refundAddress: Address::repeat_byte(0x33),
+        }
+        .abi_encode()
+    }
+
+    #[test]
+    fn test_visualize_send() {
+        let oft = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        let field = OftVisualizer
+            .visualize_tx_commands(&send(30184, recipient.into_word()), oft, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!(
+                "LayerZero Send: Bridge 5000000 {oft} to {recipient} on Base (endpoint 30184)"
+            )
+        );
+    }
+
+    #[test]
+    fn test_visualize_send_to_unknown_endpoint() {
+        let oft = Address::repeat_byte(0x11);
+        let to = B256::repeat_byte(0x44);
+        let field = OftVisualizer
+            .visualize_tx_commands(&send(40161, to), oft, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("LayerZero Send: Bridge 5000000 {oft} to {to} on unknown endpoint 40161")
+        );
+    }
+}
@@ -0,0 +1,8 @@
+//! LayerZero protocol implementation
+//!
+//! This module contains the visualizer for LayerZero V2 OFT sends. Every OFT is its own token
+//! contract, so there are no addresses to register: the call is recognized by its selector.
+
+pub mod contracts;
+
+pub use contracts::OftVisualizer;
@@ -0,0 +1,53 @@
+//! Lido protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for stETH, which mints stETH for ETH sent to `submit`
+///
+/// Reference: <https://docs.lido.fi/deployed-contracts/>
+#[derive(Debug, Clone, Copy)]
+pub struct LidoStEth;
+
+impl ContractType for LidoStEth {}
+
+/// Contract type marker for wstETH, the non-rebasing wrapper around stETH
+#[derive(Debug, Clone, Copy)]
+pub struct LidoWstEth;
+
+impl ContractType for LidoWstEth {}
+
+/// Contract type marker for the Lido WithdrawalQueueERC721, which turns stETH into
+/// withdrawal request NFTs that are claimed for ETH once finalized
+#[derive(Debug, Clone, Copy)]
+pub struct LidoWithdrawalQueue;
+
+impl ContractType for LidoWithdrawalQueue {}
+
+/// Lido protocol configuration
+pub struct LidoConfig;
+
+impl LidoConfig {
+    /// Returns the stETH address on Ethereum Mainnet
+    ///
+    /// Source: <https://docs.lido.fi/deployed-contracts/>
+    pub fn steth_address() -> Address {
+        "0xae7ab96520DE3A18E5e111B5EaAb095312D7fE84"
+            .parse()
+            .expect("Valid stETH address")
+    }
+
+    /// Returns the wstETH address on Ethereum Mainnet
+    pub fn wsteth_address() -> Address {
+        "0x7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0"
+            .parse()
+            .expect("Valid wstETH address")
+    }
+
+    /// Returns the WithdrawalQueueERC721 address on Ethereum Mainnet
+    pub fn withdrawal_queue_address() -> Address {
+        "0x889edC2eDab5f40e902b864aD4d7AdE8E412F9B1"
+            .parse()
+            .expect("Valid Lido WithdrawalQueueERC721 address")
+    }
+}
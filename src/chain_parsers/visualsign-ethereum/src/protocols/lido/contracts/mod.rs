@@ -0,0 +1,5 @@
+//! Lido protocol contract visualizers
+
+pub mod staking;
+
+pub use staking::LidoVisualizer;
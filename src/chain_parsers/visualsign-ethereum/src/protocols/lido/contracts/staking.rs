@@ -0,0 +1,183 @@
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::fmt::format_ether;
+use crate::protocols::fields::{address_field, preview_field, text_field};
+
+// From: https://github.com/lidofinance/core/blob/master/contracts/0.4.24/Lido.sol
+sol! {
+    interface ILido {
+        /// Stakes the ETH sent with the call and mints stETH to the sender
+        function submit(address _referral) external payable returns (uint256);
+    }
+}
+
+// From: https://github.com/lidofinance/core/blob/master/contracts/0.6.12/WstETH.sol
+sol! {
+    interface IWstETH {
+        function wrap(uint256 _stETHAmount) external returns (uint256);
+        function unwrap(uint256 _wstETHAmount) external returns (uint256);
+    }
+}
+
+// From: https://github.com/lidofinance/core/blob/master/contracts/0.8.9/WithdrawalQueue.sol
+sol! {
+    interface IWithdrawalQueue {
+        function requestWithdrawals(uint256[] _amounts, address _owner) external returns (uint256[] requestIds);
+        function requestWithdrawalsWstETH(uint256[] _amounts, address _owner) external returns (uint256[] requestIds);
+        function claimWithdrawals(uint256[] _requestIds, uint256[] _hints) external;
+        function claimWithdrawal(uint256 _requestId) external;
+    }
+}
+
+const LABEL: &str = "Lido";
+
+// stETH and wstETH both have 18 decimals, like ETH
+fn steth_amount(amount: U256, symbol: &str) -> String {
+    format!("{} {symbol}", format_ether(amount))
+}
+
+fn request_ids(ids: &[U256]) -> String {
+    ids.iter()
+        .map(|id| format!("#{id}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Visualizer for Lido staking, wstETH wrapping and withdrawal queue calls
+pub struct LidoVisualizer;
+
+impl LidoVisualizer {
+    /// Decodes a Lido call, or returns None when `input` is not one. `value` is the ETH sent
+    /// with the transaction, which is what `submit` stakes.
+    pub fn visualize_tx_commands(&self, input: &[u8], value: U256) -> Option<SignablePayloadField> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        let (title, subtitle, details) = match selector {
+            ILido::submitCall::SELECTOR => {
+                let call = ILido::submitCall::abi_decode(input).ok()?;
+                let amount = steth_amount(value, "ETH");
+                let mut details = vec![text_field("Amount", amount.clone())];
+                if call._referral != Address::ZERO {
+                    details.push(address_field("Referral", call._referral));
+                }
+                ("Lido Stake", format!("Stake {amount} for stETH"), details)
+            }
+            IWstETH::wrapCall::SELECTOR => {
+                let call = IWstETH::wrapCall::abi_decode(input).ok()?;
+                let amount = steth_amount(call._stETHAmount, "stETH");
+                (
+                    "Lido Wrap",
+                    format!("Wrap {amount} into wstETH"),
+                    vec![text_field("Amount", amount)],
+                )
+            }
+            IWstETH::unwrapCall::SELECTOR => {
+                let call = IWstETH::unwrapCall::abi_decode(input).ok()?;
+                let amount = steth_amount(call._wstETHAmount, "wstETH");
+                (
+                    "Lido Unwrap",
+                    format!("Unwrap {amount} into stETH"),
+                    vec![text_field("Amount", amount)],
+                )
+            }
+            IWithdrawalQueue::requestWithdrawalsCall::SELECTOR => {
+                let call = IWithdrawalQueue::requestWithdrawalsCall::abi_decode(input).ok()?;
+                withdrawal_request(&call._amounts, call._owner, "stETH")
+            }
+            IWithdrawalQueue::requestWithdrawalsWstETHCall::SELECTOR => {
+                let call =
+                    IWithdrawalQueue::requestWithdrawalsWstETHCall::abi_decode(input).ok()?;
+                withdrawal_request(&call._amounts, call._owner, "wstETH")
+            }
+            IWithdrawalQueue::claimWithdrawalsCall::SELECTOR => {
+                let call = IWithdrawalQueue::claimWithdrawalsCall::abi_decode(input).ok()?;
+                let ids = request_ids(&call._requestIds);
+                (
+                    "Lido Claim",
+                    format!("Claim ETH for withdrawal requests {ids}"),
+                    vec![text_field("Request IDs", ids)],
+                )
+            }
+            IWithdrawalQueue::claimWithdrawalCall::SELECTOR => {
+                let call = IWithdrawalQueue::claimWithdrawalCall::abi_decode(input).ok()?;
+                let ids = request_ids(&[call._requestId]);
+                (
+                    "Lido Claim",
+                    format!("Claim ETH for withdrawal request {ids}"),
+                    vec![text_field("Request ID", ids)],
+                )
+            }
+            _ => return None,
+        };
+        Some(preview_field(LABEL, title, subtitle, details))
+    }
+}
+
+fn withdrawal_request(
+    amounts: &[U256],
+    owner: Address,
+    symbol: &str,
+) -> (&'static str, String, Vec<AnnotatedPayloadField>) {
+    let total = amounts
+        .iter()
+        .fold(U256::ZERO, |total, amount| total.saturating_add(*amount));
+    let subtitle = format!(
+        "Request withdrawal of {} in {} request(s)",
+        steth_amount(total, symbol),
+        amounts.len()
+    );
+    let mut details: Vec<_> = amounts
+        .iter()
+        .enumerate()
+        .map(|(index, amount)| {
+            text_field(
+                &format!("Request {}", index + 1),
+                steth_amount(*amount, symbol),
+            )
+        })
+        .collect();
+    // A zero owner makes the requests out to the sender
+    if owner == Address::ZERO {
+        details.push(text_field("Owner", "Sender"));
+    } else {
+        details.push(address_field("Owner", owner));
+    }
+    ("Lido Withdrawal Request", subtitle, details)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize_submit() {
+        let input = ILido::submitCall {
+            _referral: Address::ZERO,
+        }
+        .abi_encode();
+
+        let field = LidoVisualizer
+            .visualize_tx_commands(&input, U256::from(10).pow(U256::from(18)))
+            .unwrap();
+        assert_eq!(field.fallback_text(), "Lido Stake: Stake 1 ETH for stETH");
+    }
+
+    #[test]
+    fn test_visualize_request_withdrawals() {
+        let ether = U256::from(10).pow(U256::from(18));
+        let input = IWithdrawalQueue::requestWithdrawalsCall {
+            _amounts: vec![ether, ether],
+            _owner: Address::ZERO,
+        }
+        .abi_encode();
+
+        let field = LidoVisualizer
+            .visualize_tx_commands(&input, U256::ZERO)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            "Lido Withdrawal Request: Request withdrawal of 2 stETH in 2 request(s)"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Lido protocol implementation
+//!
+//! This module contains the visualizer for Lido staking, wrapping and withdrawal requests,
+//! along with the contracts' configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::LidoConfig;
+pub use contracts::LidoVisualizer;
+
+/// Registers the Lido contracts on Ethereum Mainnet
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    use config::{LidoStEth, LidoWithdrawalQueue, LidoWstEth};
+
+    contract_reg.register_contract_typed::<LidoStEth>(1, vec![LidoConfig::steth_address()]);
+    contract_reg.register_contract_typed::<LidoWstEth>(1, vec![LidoConfig::wsteth_address()]);
+    contract_reg.register_contract_typed::<LidoWithdrawalQueue>(
+        1,
+        vec![LidoConfig::withdrawal_queue_address()],
+    );
+}
@@ -1,4 +1,15 @@
+pub mod aave;
+pub mod balancer;
+pub mod cctp;
+pub mod compound;
+pub mod curve;
+pub mod eigenlayer;
+pub(crate) mod fields;
+pub mod layerzero;
+pub mod lido;
+pub mod oneinch;
 pub mod uniswap;
+pub mod wormhole;
 
 use crate::registry::ContractRegistry;
 use crate::visualizer::EthereumVisualizerRegistryBuilder;
@@ -14,4 +25,21 @@ pub fn register_all(
 ) {
     // Register Uniswap protocol
     uniswap::register(contract_reg, visualizer_reg);
+
+    // Register other DEX protocols
+    curve::register(contract_reg, visualizer_reg);
+    balancer::register(contract_reg, visualizer_reg);
+    oneinch::register(contract_reg, visualizer_reg);
+
+    // Register lending protocols
+    aave::register(contract_reg, visualizer_reg);
+    compound::register(contract_reg, visualizer_reg);
+
+    // Register staking protocols
+    lido::register(contract_reg, visualizer_reg);
+    eigenlayer::register(contract_reg, visualizer_reg);
+
+    // Register bridges; LayerZero OFTs have no fixed addresses
+    wormhole::register(contract_reg, visualizer_reg);
+    cctp::register(contract_reg, visualizer_reg);
 }
@@ -0,0 +1,47 @@
+//! 1inch protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for the 1inch Aggregation Router V5
+///
+/// Reference: <https://portal.1inch.dev/documentation/contracts/aggregation-protocol/aggregation-introduction>
+#[derive(Debug, Clone, Copy)]
+pub struct OneInchAggregationRouterV5;
+
+impl ContractType for OneInchAggregationRouterV5 {}
+
+/// Contract type marker for the 1inch Aggregation Router V6
+///
+/// Reference: <https://portal.1inch.dev/documentation/contracts/aggregation-protocol/aggregation-introduction>
+#[derive(Debug, Clone, Copy)]
+pub struct OneInchAggregationRouterV6;
+
+impl ContractType for OneInchAggregationRouterV6 {}
+
+/// 1inch protocol configuration
+pub struct OneInchConfig;
+
+impl OneInchConfig {
+    /// Returns the Aggregation Router V5 address, which is the same on every supported chain
+    pub fn router_v5_address() -> Address {
+        "0x1111111254EEB25477B68fb85Ed929f73A960582"
+            .parse()
+            .expect("Valid 1inch Aggregation Router V5 address")
+    }
+
+    /// Returns the Aggregation Router V6 address, which is the same on every supported chain
+    pub fn router_v6_address() -> Address {
+        "0x111111125421cA6dc452d289314280a0f8842A65"
+            .parse()
+            .expect("Valid 1inch Aggregation Router V6 address")
+    }
+
+    /// Returns the chain IDs where both routers are registered
+    ///
+    /// Supported chains: Mainnet (1), Optimism (10), Polygon (137), Base (8453) and
+    /// Arbitrum One (42161).
+    pub fn router_chains() -> &'static [u64] {
+        &[1, 10, 137, 8453, 42161]
+    }
+}
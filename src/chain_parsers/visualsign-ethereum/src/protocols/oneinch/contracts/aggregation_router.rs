@@ -0,0 +1,289 @@
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::text::route_summary;
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::protocols::fields::{
+    address_field, preview_field, text_field, token_amount, token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/1inch/1inch-v5-contracts (AggregationRouterV5.sol) and
+// https://github.com/1inch/1inch-v6-contracts (AggregationRouterV6.sol)
+sol! {
+    struct SwapDescription {
+        address srcToken;
+        address dstToken;
+        address srcReceiver;
+        address dstReceiver;
+        uint256 amount;
+        uint256 minReturnAmount;
+        uint256 flags;
+    }
+
+    interface IAggregationRouterV5 {
+        function swap(address executor, SwapDescription desc, bytes permit, bytes data)
+            external payable returns (uint256 returnAmount, uint256 spentAmount);
+
+        /// @param pools Pools to swap through, each with its direction and WETH unwrap flags in the top bits
+        function unoswap(address srcToken, uint256 amount, uint256 minReturn, uint256[] pools)
+            external payable returns (uint256 returnAmount);
+
+        function uniswapV3Swap(uint256 amount, uint256 minReturn, uint256[] pools)
+            external payable returns (uint256 returnAmount);
+    }
+
+    interface IAggregationRouterV6 {
+        function swap(address executor, SwapDescription desc, bytes data)
+            external payable returns (uint256 returnAmount, uint256 spentAmount);
+
+        /// @param token Source token in the low 160 bits
+        /// @param dex Pool in the low 160 bits and protocol in the top three
+        function unoswap(uint256 token, uint256 amount, uint256 minReturn, uint256 dex)
+            external returns (uint256 returnAmount);
+    }
+}
+
+// Bits of the SwapDescription flags
+const PARTIAL_FILL_BIT: usize = 0;
+const REQUIRES_EXTRA_ETH_BIT: usize = 1;
+
+// Offset of the protocol in a V6 `dex` word
+const PROTOCOL_OFFSET: usize = 253;
+
+// Addresses are packed into the low 160 bits of a word alongside flags
+fn packed_address(word: U256) -> Address {
+    Address::from_word(B256::from(word))
+}
+
+fn v6_protocol(dex: U256) -> String {
+    match (dex >> PROTOCOL_OFFSET).to::<u8>() {
+        0 => "Uniswap V2".to_string(),
+        1 => "Uniswap V3".to_string(),
+        2 => "Curve".to_string(),
+        other => format!("Protocol {other}"),
+    }
+}
+
+/// Visualizer for 1inch Aggregation Router V5 and V6 swaps
+pub struct AggregationRouterVisualizer;
+
+impl AggregationRouterVisualizer {
+    /// Decodes a router swap, or returns None when `input` is not one
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        const V5: &str = "1inch Aggregation Router V5";
+        const V6: &str = "1inch Aggregation Router V6";
+
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        match selector {
+            IAggregationRouterV5::swapCall::SELECTOR => {
+                let call = IAggregationRouterV5::swapCall::abi_decode(input).ok()?;
+                Some(swap_field(
+                    V5,
+                    call.executor,
+                    &call.desc,
+                    chain_id,
+                    registry,
+                ))
+            }
+            IAggregationRouterV6::swapCall::SELECTOR => {
+                let call = IAggregationRouterV6::swapCall::abi_decode(input).ok()?;
+                Some(swap_field(
+                    V6,
+                    call.executor,
+                    &call.desc,
+                    chain_id,
+                    registry,
+                ))
+            }
+            IAggregationRouterV5::unoswapCall::SELECTOR => {
+                let call = IAggregationRouterV5::unoswapCall::abi_decode(input).ok()?;
+                let pools: Vec<Address> = call
+                    .pools
+                    .iter()
+                    .map(|pool| packed_address(*pool))
+                    .collect();
+                let (subtitle, details) = pool_swap_details(
+                    Some(call.srcToken),
+                    call.amount,
+                    call.minReturn,
+                    &pools,
+                    chain_id,
+                    registry,
+                );
+                Some(preview_field(V5, "1inch Unoswap", subtitle, details))
+            }
+            IAggregationRouterV5::uniswapV3SwapCall::SELECTOR => {
+                let call = IAggregationRouterV5::uniswapV3SwapCall::abi_decode(input).ok()?;
+                let pools: Vec<Address> = call
+                    .pools
+                    .iter()
+                    .map(|pool| packed_address(*pool))
+                    .collect();
+                let (subtitle, details) = pool_swap_details(
+                    None,
+                    call.amount,
+                    call.minReturn,
+                    &pools,
+                    chain_id,
+                    registry,
+                );
+                Some(preview_field(
+                    V5,
+                    "1inch Uniswap V3 Swap",
+                    subtitle,
+                    details,
+                ))
+            }
+            IAggregationRouterV6::unoswapCall::SELECTOR => {
+                let call = IAggregationRouterV6::unoswapCall::abi_decode(input).ok()?;
+                let (subtitle, mut details) = pool_swap_details(
+                    Some(packed_address(call.token)),
+                    call.amount,
+                    call.minReturn,
+                    &[packed_address(call.dex)],
+                    chain_id,
+                    registry,
+                );
+                details.push(text_field("Protocol", v6_protocol(call.dex)));
+                Some(preview_field(V6, "1inch Unoswap", subtitle, details))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn swap_field(
+    label: &str,
+    executor: Address,
+    desc: &SwapDescription,
+    chain_id: u64,
+    registry: Option<&ContractRegistry>,
+) -> SignablePayloadField {
+    let amount_in = token_amount(desc.amount, desc.srcToken, chain_id, registry);
+    let min_out = token_amount(desc.minReturnAmount, desc.dstToken, chain_id, registry);
+    let subtitle = format!("Swap {amount_in} for at least {min_out}");
+
+    let mut details = vec![
+        text_field(
+            "Route",
+            route_summary([
+                token_name(desc.srcToken, chain_id, registry),
+                token_name(desc.dstToken, chain_id, registry),
+            ]),
+        ),
+        text_field("Amount In", amount_in),
+        text_field("Minimum Out", min_out),
+    ];
+    // A zero destination receiver sends the output to the caller
+    if desc.dstReceiver == Address::ZERO {
+        details.push(text_field("Receiver", "Sender"));
+    } else {
+        details.push(address_field("Receiver", desc.dstReceiver));
+    }
+    details.push(address_field("Executor", executor));
+    if desc.flags.bit(PARTIAL_FILL_BIT) {
+        details.push(text_field("Partial Fill", "Allowed"));
+    }
+    if desc.flags.bit(REQUIRES_EXTRA_ETH_BIT) {
+        details.push(text_field("Extra ETH", "Required"));
+    }
+
+    preview_field(label, "1inch Swap", subtitle, details)
+}
+
+// Subtitle and details of swaps that name their pools directly, without an executor. Only the
+// source token is known; the destination follows from the last pool.
+fn pool_swap_details(
+    src_token: Option<Address>,
+    amount: U256,
+    min_return: U256,
+    pools: &[Address],
+    chain_id: u64,
+    registry: Option<&ContractRegistry>,
+) -> (String, Vec<AnnotatedPayloadField>) {
+    let amount_in = match src_token {
+        Some(token) => token_amount(amount, token, chain_id, registry),
+        None => amount.to_string(),
+    };
+    let subtitle = format!(
+        "Swap {amount_in} for at least {min_return} through {} pool(s)",
+        pools.len()
+    );
+
+    let mut details = vec![
+        text_field("Amount In", amount_in),
+        text_field("Minimum Out", min_return.to_string()),
+    ];
+    details.extend(
+        pools
+            .iter()
+            .enumerate()
+            .map(|(index, pool)| address_field(&format!("Pool {}", index + 1), *pool)),
+    );
+    (subtitle, details)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+
+    #[test]
+    fn test_visualize_v6_swap() {
+        let src_token = Address::repeat_byte(0x11);
+        let input = IAggregationRouterV6::swapCall {
+            executor: Address::repeat_byte(0x99),
+            desc: SwapDescription {
+                srcToken: src_token,
+                dstToken: "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"
+                    .parse()
+                    .unwrap(),
+                srcReceiver: Address::repeat_byte(0x99),
+                dstReceiver: Address::ZERO,
+                amount: U256::from(5000),
+                minReturnAmount: U256::from(10).pow(U256::from(17)),
+                flags: U256::from(1) << PARTIAL_FILL_BIT,
+            },
+            data: Bytes::new(),
+        }
+        .abi_encode();
+
+        let field = AggregationRouterVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("1inch Swap: Swap 5000 {src_token} for at least 0.1 ETH")
+        );
+    }
+
+    #[test]
+    fn test_visualize_v6_unoswap() {
+        let pool = Address::repeat_byte(0x22);
+        // Uniswap V3 pool
+        let dex = (U256::from(1) << PROTOCOL_OFFSET) | U256::from_be_slice(pool.as_slice());
+        let input = IAggregationRouterV6::unoswapCall {
+            token: U256::ZERO,
+            amount: U256::from(10).pow(U256::from(18)),
+            minReturn: U256::from(1800),
+            dex,
+        }
+        .abi_encode();
+
+        let field = AggregationRouterVisualizer
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            "1inch Unoswap: Swap 1 ETH for at least 1800 through 1 pool(s)"
+        );
+        assert_eq!(packed_address(dex), pool);
+        assert_eq!(v6_protocol(dex), "Uniswap V3");
+    }
+}
@@ -0,0 +1,5 @@
+//! 1inch protocol contract visualizers
+
+pub mod aggregation_router;
+
+pub use aggregation_router::AggregationRouterVisualizer;
@@ -0,0 +1,36 @@
+//! 1inch protocol implementation
+//!
+//! This module contains the visualizer for 1inch Aggregation Router swaps, along with the
+//! routers' configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::OneInchConfig;
+pub use contracts::AggregationRouterVisualizer;
+
+/// Registers the 1inch Aggregation Routers V5 and V6 on all supported chains
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    use config::{OneInchAggregationRouterV5, OneInchAggregationRouterV6};
+
+    for &chain_id in OneInchConfig::router_chains() {
+        contract_reg.register_contract_typed::<OneInchAggregationRouterV5>(
+            chain_id,
+            vec![OneInchConfig::router_v5_address()],
+        );
+        contract_reg.register_contract_typed::<OneInchAggregationRouterV6>(
+            chain_id,
+            vec![OneInchConfig::router_v6_address()],
+        );
+    }
+}
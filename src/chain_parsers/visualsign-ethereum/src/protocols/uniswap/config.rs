@@ -41,15 +41,22 @@ impl ContractType for UniswapUniversalRouter {}
 // pub struct UniswapUniversalRouterV2;
 // impl ContractType for UniswapUniversalRouterV2 {}
 
-// TODO: Add V4 PoolManager contract type
-//
-// V4 requires the PoolManager contract for liquidity pool management.
-// Deployments: <https://docs.uniswap.org/contracts/v4/deployments>
-//
-// /// Uniswap V4 PoolManager
-// #[derive(Debug, Clone, Copy)]
-// pub struct UniswapV4PoolManager;
-// impl ContractType for UniswapV4PoolManager {}
+/// Contract type marker for the Uniswap V4 PoolManager, the singleton holding every V4 pool
+///
+/// Reference: <https://docs.uniswap.org/contracts/v4/deployments>
+#[derive(Debug, Clone, Copy)]
+pub struct UniswapV4PoolManager;
+
+impl ContractType for UniswapV4PoolManager {}
+
+/// Contract type marker for the Uniswap V4 PositionManager, which mints liquidity positions
+/// as NFTs through `modifyLiquidities`
+///
+/// Reference: <https://docs.uniswap.org/contracts/v4/deployments>
+#[derive(Debug, Clone, Copy)]
+pub struct UniswapV4PositionManager;
+
+impl ContractType for UniswapV4PositionManager {}
 
 /// Uniswap protocol configuration
 pub struct UniswapConfig;
@@ -96,12 +103,26 @@ impl UniswapConfig {
     // }
     // pub fn universal_router_v2_chains() -> &'static [u64] { ... }
 
-    // TODO: Add methods for V4 PoolManager
-    //
-    // Source: https://docs.uniswap.org/contracts/v4/deployments
-    //
-    // pub fn v4_pool_manager_address() -> Address { ... }
-    // pub fn v4_pool_manager_chains() -> &'static [u64] { ... }
+    /// Returns the V4 PoolManager address on Ethereum Mainnet
+    ///
+    /// V4 contracts are deployed at different addresses on each chain; only Mainnet is
+    /// registered so far.
+    ///
+    /// Source: <https://docs.uniswap.org/contracts/v4/deployments>
+    pub fn v4_pool_manager_address() -> Address {
+        "0x000000000004444c5dc75cB358380D2e3dE08A90"
+            .parse()
+            .expect("Valid V4 PoolManager address")
+    }
+
+    /// Returns the V4 PositionManager address on Ethereum Mainnet
+    ///
+    /// Source: <https://docs.uniswap.org/contracts/v4/deployments>
+    pub fn v4_position_manager_address() -> Address {
+        "0xbD216513d74C8cf14cf4747E6AaA6420FF64ee9e"
+            .parse()
+            .expect("Valid V4 PositionManager address")
+    }
 }
 
 #[cfg(test)]
@@ -126,5 +147,9 @@ mod tests {
     fn test_contract_type_id() {
         let type_id = UniswapUniversalRouter::short_type_id();
         assert_eq!(type_id, "UniswapUniversalRouter");
+        assert_eq!(
+            UniswapV4PositionManager::short_type_id(),
+            "UniswapV4PositionManager"
+        );
     }
 }
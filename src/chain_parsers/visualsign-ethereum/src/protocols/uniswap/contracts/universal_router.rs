@@ -3,6 +3,7 @@ use chrono::{TimeZone, Utc};
 use num_enum::TryFromPrimitive;
 use visualsign::{SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2};
 
+use super::V4PoolManagerVisualizer;
 use crate::registry::ContractRegistry;
 
 // From: https://github.com/Uniswap/universal-router/blob/main/contracts/interfaces/IUniversalRouter.sol
@@ -80,16 +81,31 @@ pub enum Command {
     ExecuteSubPlan = 0x21,
 }
 
+// The high bit of a command byte lets it revert without reverting the whole execution, and the
+// two below it are reserved, so the command type is in the low six bits
+const COMMAND_TYPE_MASK: u8 = 0x3f;
+
 fn map_commands(raw: &[u8]) -> Vec<Command> {
     let mut out = Vec::with_capacity(raw.len());
     for &b in raw {
-        if let Ok(cmd) = Command::try_from(b) {
+        if let Ok(cmd) = Command::try_from(b & COMMAND_TYPE_MASK) {
             out.push(cmd);
         }
     }
     out
 }
 
+// Decodes the inputs of the commands Uniswap V4 adds, each into its own layout of actions
+fn decode_v4_command(cmd: Command, label: &str, input: &[u8]) -> Option<SignablePayloadField> {
+    let v4 = V4PoolManagerVisualizer;
+    match cmd {
+        Command::V4Swap => v4.visualize_router_swap(label, input),
+        Command::V4InitializePool => v4.visualize_initialize_pool(label, input),
+        Command::V4PositionManagerCall => v4.visualize_position_manager_call(label, input),
+        _ => None,
+    }
+}
+
 /// Visualizer for Uniswap Universal Router
 ///
 /// Handles the `execute` function from IUniversalRouter interface:
@@ -133,12 +149,17 @@ impl UniversalRouterVisualizer {
                     .map(|b| format!("0x{}", hex::encode(b)))
                     .unwrap_or_else(|| "None".to_string());
 
-                // Decode command-specific parameters (TODO: implement actual decoding)
-                // For now, all commands use the same hex format until decoders are implemented
+                let label = format!("Command {}", i + 1);
+                if let Some(field) = input_bytes.and_then(|b| decode_v4_command(*cmd, &label, b)) {
+                    detail_fields.push(field);
+                    continue;
+                }
+
+                // Commands without a decoder yet show their raw input
                 detail_fields.push(SignablePayloadField::PreviewLayout {
                     common: SignablePayloadFieldCommon {
                         fallback_text: format!("{cmd:?} input: {input_hex}"),
-                        label: format!("Command {}", i + 1),
+                        label,
                     },
                     preview_layout: visualsign::SignablePayloadFieldPreviewLayout {
                         title: Some(visualsign::SignablePayloadFieldTextV2 {
@@ -584,4 +605,41 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_visualize_tx_commands_v4_initialize_pool() {
+        use crate::protocols::uniswap::contracts::v4_pool::{InitializePoolParams, PoolKey};
+        use alloy_sol_types::SolValue;
+
+        let params = InitializePoolParams {
+            key: PoolKey {
+                currency0: alloy_primitives::Address::ZERO,
+                currency1: alloy_primitives::Address::repeat_byte(0x11),
+                fee: alloy_primitives::aliases::U24::from(500),
+                tickSpacing: "10".parse().unwrap(),
+                hooks: alloy_primitives::Address::ZERO,
+            },
+            sqrtPriceX96: alloy_primitives::aliases::U160::from(1u64) << 96,
+        }
+        .abi_encode_params();
+        // Allow-revert flag set on the command byte
+        let commands = vec![Command::V4InitializePool as u8 | 0x80];
+        let input = encode_execute_call(&commands, vec![params], 0);
+
+        let field = UniversalRouterVisualizer {}
+            .visualize_tx_commands(&input, 1, None)
+            .unwrap();
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected a PreviewLayout");
+        };
+        let command = &preview_layout.expanded.unwrap().fields[0].signable_payload_field;
+        assert_eq!(command.label(), "Command 1");
+        assert_eq!(
+            command.fallback_text(),
+            &format!(
+                "Initialize Pool: Create the ETH / {} pool",
+                alloy_primitives::Address::repeat_byte(0x11)
+            )
+        );
+    }
 }
@@ -1,19 +1,27 @@
-//! Uniswap V4 Pool Manager Visualizer
+//! Uniswap V4 Pool Manager and Position Manager Visualizer
 //!
-//! Visualizes interactions with the Uniswap V4 PoolManager contract.
+//! Visualizes interactions with the Uniswap V4 PoolManager contract, `modifyLiquidities` calls
+//! to the PositionManager, and the V4 commands of the Universal Router. Position Manager and
+//! V4 Router calls are a list of action bytes with one ABI-encoded parameter blob each; every
+//! action gets its own `PreviewLayout`.
 //!
 //! Reference: <https://docs.uniswap.org/contracts/v4/overview>
 //! Deployments: <https://docs.uniswap.org/contracts/v4/deployments>
+//! Actions: <https://github.com/Uniswap/v4-periphery/blob/main/src/libraries/Actions.sol>
 
-#![allow(unused_imports)]
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::{SolCall, SolType, sol, sol_data};
+use num_enum::TryFromPrimitive;
+use visualsign::{AnnotatedPayloadField, SignablePayloadField, fixed_point::format_fixed_point};
 
-use alloy_sol_types::{SolCall, sol};
-use visualsign::{SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2};
+use crate::protocols::fields::{
+    address_field, annotated, deadline_field, preview_field, text_field,
+};
 
-// Simplified V4 PoolManager interface
+// From: https://github.com/Uniswap/v4-core/blob/main/src/interfaces/IPoolManager.sol
 sol! {
     interface IPoolManager {
-        function initialize(PoolKey memory key, uint160 sqrtPriceX96, bytes calldata hookData) external returns (int24 tick);
+        function initialize(PoolKey memory key, uint160 sqrtPriceX96) external returns (int24 tick);
         function modifyLiquidity(PoolKey memory key, ModifyLiquidityParams memory params, bytes calldata hookData) external returns (BalanceDelta callerDelta, BalanceDelta feesAccrued);
         function swap(PoolKey memory key, SwapParams memory params, bytes calldata hookData) external returns (BalanceDelta);
         function donate(PoolKey memory key, uint256 amount0, uint256 amount1, bytes calldata hookData) external returns (BalanceDelta);
@@ -44,13 +52,658 @@ sol! {
         int128 amount0;
         int128 amount1;
     }
+
+    // From: https://github.com/Uniswap/v4-periphery/blob/main/src/interfaces/IPositionManager.sol
+    interface IPositionManager {
+        function modifyLiquidities(bytes calldata unlockData, uint256 deadline) external payable;
+        function modifyLiquiditiesWithoutUnlock(bytes calldata actions, bytes[] calldata params) external payable;
+    }
+
+    /// `unlockData` of `modifyLiquidities` and the input of the router's V4_SWAP command
+    struct ActionsData {
+        bytes actions;
+        bytes[] params;
+    }
+
+    /// Input of the router's V4_INITIALIZE_POOL command
+    struct InitializePoolParams {
+        PoolKey key;
+        uint160 sqrtPriceX96;
+    }
+
+    // Action parameters, from v4-periphery's CalldataDecoder and IV4Router. Parameter lists
+    // are encoded as function arguments, the single-struct swap parameters as one tuple
+    struct MintPositionParams {
+        PoolKey poolKey;
+        int24 tickLower;
+        int24 tickUpper;
+        uint256 liquidity;
+        uint128 amount0Max;
+        uint128 amount1Max;
+        address owner;
+        bytes hookData;
+    }
+
+    struct MintPositionFromDeltasParams {
+        PoolKey poolKey;
+        int24 tickLower;
+        int24 tickUpper;
+        uint128 amount0Max;
+        uint128 amount1Max;
+        address owner;
+        bytes hookData;
+    }
+
+    struct IncreaseLiquidityParams {
+        uint256 tokenId;
+        uint256 liquidity;
+        uint128 amount0Max;
+        uint128 amount1Max;
+        bytes hookData;
+    }
+
+    struct DecreaseLiquidityParams {
+        uint256 tokenId;
+        uint256 liquidity;
+        uint128 amount0Min;
+        uint128 amount1Min;
+        bytes hookData;
+    }
+
+    struct BurnPositionParams {
+        uint256 tokenId;
+        uint128 amount0Min;
+        uint128 amount1Min;
+        bytes hookData;
+    }
+
+    struct ExactInputSingleParams {
+        PoolKey poolKey;
+        bool zeroForOne;
+        uint128 amountIn;
+        uint128 amountOutMinimum;
+        bytes hookData;
+    }
+
+    struct ExactOutputSingleParams {
+        PoolKey poolKey;
+        bool zeroForOne;
+        uint128 amountOut;
+        uint128 amountInMaximum;
+        bytes hookData;
+    }
+
+    struct PathKey {
+        address intermediateCurrency;
+        uint24 fee;
+        int24 tickSpacing;
+        address hooks;
+        bytes hookData;
+    }
+
+    struct ExactInputParams {
+        address currencyIn;
+        PathKey[] path;
+        uint128 amountIn;
+        uint128 amountOutMinimum;
+    }
+
+    struct ExactOutputParams {
+        address currencyOut;
+        PathKey[] path;
+        uint128 amountOut;
+        uint128 amountInMaximum;
+    }
+
+    struct SettleParams {
+        address currency;
+        uint256 amount;
+        bool payerIsUser;
+    }
+
+    struct TakeParams {
+        address currency;
+        address recipient;
+        uint256 amount;
+    }
+
+    struct CurrencyAmountParams {
+        address currency;
+        uint256 amount;
+    }
+
+    struct TakePortionParams {
+        address currency;
+        address recipient;
+        uint256 bips;
+    }
+
+    struct CurrencyPairParams {
+        address currency0;
+        address currency1;
+    }
+
+    struct TakePairParams {
+        address currency0;
+        address currency1;
+        address recipient;
+    }
+
+    struct SweepParams {
+        address currency;
+        address to;
+    }
+}
+
+// From: https://github.com/Uniswap/v4-periphery/blob/main/src/libraries/Actions.sol
+#[derive(Copy, Clone, Debug, Eq, PartialEq, TryFromPrimitive)]
+#[repr(u8)]
+pub enum Action {
+    IncreaseLiquidity = 0x00,
+    DecreaseLiquidity = 0x01,
+    MintPosition = 0x02,
+    BurnPosition = 0x03,
+    IncreaseLiquidityFromDeltas = 0x04,
+    MintPositionFromDeltas = 0x05,
+
+    SwapExactInSingle = 0x06,
+    SwapExactIn = 0x07,
+    SwapExactOutSingle = 0x08,
+    SwapExactOut = 0x09,
+
+    Donate = 0x0a,
+
+    Settle = 0x0b,
+    SettleAll = 0x0c,
+    SettlePair = 0x0d,
+
+    Take = 0x0e,
+    TakeAll = 0x0f,
+    TakePortion = 0x10,
+    TakePair = 0x11,
+
+    CloseCurrency = 0x12,
+    ClearOrTake = 0x13,
+    Sweep = 0x14,
+
+    Wrap = 0x15,
+    Unwrap = 0x16,
+
+    Mint6909 = 0x17,
+    Burn6909 = 0x18,
+}
+
+impl Action {
+    /// Title of the action's layout
+    pub fn title(self) -> &'static str {
+        match self {
+            Action::IncreaseLiquidity => "Increase Liquidity",
+            Action::DecreaseLiquidity => "Decrease Liquidity",
+            Action::MintPosition => "Mint Position",
+            Action::BurnPosition => "Burn Position",
+            Action::IncreaseLiquidityFromDeltas => "Increase Liquidity From Deltas",
+            Action::MintPositionFromDeltas => "Mint Position From Deltas",
+            Action::SwapExactInSingle => "Swap Exact In (Single Pool)",
+            Action::SwapExactIn => "Swap Exact In",
+            Action::SwapExactOutSingle => "Swap Exact Out (Single Pool)",
+            Action::SwapExactOut => "Swap Exact Out",
+            Action::Donate => "Donate",
+            Action::Settle => "Settle",
+            Action::SettleAll => "Settle All",
+            Action::SettlePair => "Settle Pair",
+            Action::Take => "Take",
+            Action::TakeAll => "Take All",
+            Action::TakePortion => "Take Portion",
+            Action::TakePair => "Take Pair",
+            Action::CloseCurrency => "Close Currency",
+            Action::ClearOrTake => "Clear Or Take",
+            Action::Sweep => "Sweep",
+            Action::Wrap => "Wrap",
+            Action::Unwrap => "Unwrap",
+            Action::Mint6909 => "Mint ERC-6909",
+            Action::Burn6909 => "Burn ERC-6909",
+        }
+    }
+}
+
+/// Hook callbacks enabled by the low 14 bits of a hook address, highest bit first.
+///
+/// From: <https://github.com/Uniswap/v4-core/blob/main/src/libraries/Hooks.sol>
+pub const HOOK_FLAGS: [(u16, &str); 14] = [
+    (1 << 13, "beforeInitialize"),
+    (1 << 12, "afterInitialize"),
+    (1 << 11, "beforeAddLiquidity"),
+    (1 << 10, "afterAddLiquidity"),
+    (1 << 9, "beforeRemoveLiquidity"),
+    (1 << 8, "afterRemoveLiquidity"),
+    (1 << 7, "beforeSwap"),
+    (1 << 6, "afterSwap"),
+    (1 << 5, "beforeDonate"),
+    (1 << 4, "afterDonate"),
+    (1 << 3, "beforeSwapReturnDelta"),
+    (1 << 2, "afterSwapReturnDelta"),
+    (1 << 1, "afterAddLiquidityReturnDelta"),
+    (1 << 0, "afterRemoveLiquidityReturnDelta"),
+];
+
+/// The hook callbacks a pool with hook contract `hooks` calls.
+pub fn hook_permissions(hooks: Address) -> Vec<&'static str> {
+    let bytes = hooks.as_slice();
+    let bits = u16::from_be_bytes([bytes[18], bytes[19]]);
+    HOOK_FLAGS
+        .iter()
+        .filter(|(flag, _)| bits & flag != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+// Pool fee marking a pool whose hook sets the fee, from v4-core's LPFeeLibrary
+const DYNAMIC_FEE_FLAG: u32 = 0x80_0000;
+
+fn format_fee(fee: u32) -> String {
+    if fee == DYNAMIC_FEE_FLAG {
+        "Dynamic".to_string()
+    } else {
+        // In hundredths of a basis point
+        format!("{}%", format_fixed_point(u128::from(fee), 4))
+    }
+}
+
+// The native currency is the zero address
+fn currency(address: Address) -> String {
+    if address == Address::ZERO {
+        "ETH".to_string()
+    } else {
+        address.to_string()
+    }
 }
 
-/// Visualizer for Uniswap V4 PoolManager contract calls
+// Recipients special-cased by v4-periphery's ActionConstants
+fn recipient(address: Address) -> String {
+    if address == Address::with_last_byte(1) {
+        "Sender".to_string()
+    } else if address == Address::with_last_byte(2) {
+        "Router".to_string()
+    } else {
+        address.to_string()
+    }
+}
+
+// Amounts special-cased by ActionConstants
+fn settle_amount(amount: U256) -> String {
+    if amount.is_zero() {
+        "Open delta".to_string()
+    } else if amount == U256::from(1) << 255 {
+        "Contract balance".to_string()
+    } else {
+        amount.to_string()
+    }
+}
+
+fn hook_data_field(hook_data: &Bytes) -> Option<AnnotatedPayloadField> {
+    (!hook_data.is_empty())
+        .then(|| text_field("Hook Data", format!("0x{}", hex::encode(hook_data))))
+}
+
+fn hooks_fields(hooks: Address) -> Vec<AnnotatedPayloadField> {
+    if hooks == Address::ZERO {
+        return vec![text_field("Hooks", "None")];
+    }
+    let permissions = hook_permissions(hooks);
+    vec![
+        address_field("Hooks", hooks),
+        text_field(
+            "Hook Permissions",
+            if permissions.is_empty() {
+                "None".to_string()
+            } else {
+                permissions.join(", ")
+            },
+        ),
+    ]
+}
+
+fn pool_name(key: &PoolKey) -> String {
+    format!("{} / {}", currency(key.currency0), currency(key.currency1))
+}
+
+fn pool_key_field(key: &PoolKey) -> AnnotatedPayloadField {
+    let fee = format_fee(key.fee.to::<u32>());
+    let mut details = vec![
+        text_field("Currency 0", currency(key.currency0)),
+        text_field("Currency 1", currency(key.currency1)),
+        text_field("Fee", fee.clone()),
+        text_field("Tick Spacing", key.tickSpacing.to_string()),
+    ];
+    details.extend(hooks_fields(key.hooks));
+    annotated(preview_field(
+        "Pool",
+        &pool_name(key),
+        format!("Fee {fee}, tick spacing {}", key.tickSpacing),
+        details,
+    ))
+}
+
+// `(currency in, currency out)` of a single-pool swap
+fn swap_direction(key: &PoolKey, zero_for_one: bool) -> (String, String) {
+    let (input, output) = if zero_for_one {
+        (key.currency0, key.currency1)
+    } else {
+        (key.currency1, key.currency0)
+    };
+    (currency(input), currency(output))
+}
+
+fn path_fields(path: &[PathKey]) -> Vec<AnnotatedPayloadField> {
+    path.iter()
+        .enumerate()
+        .map(|(i, hop)| {
+            let mut details = vec![
+                text_field("Fee", format_fee(hop.fee.to::<u32>())),
+                text_field("Tick Spacing", hop.tickSpacing.to_string()),
+            ];
+            details.extend(hooks_fields(hop.hooks));
+            details.extend(hook_data_field(&hop.hookData));
+            annotated(preview_field(
+                &format!("Hop {}", i + 1),
+                &currency(hop.intermediateCurrency),
+                format!("Fee {}", format_fee(hop.fee.to::<u32>())),
+                details,
+            ))
+        })
+        .collect()
+}
+
+// Subtitle and detail fields of one action, or None when its parameters do not decode
+fn action_details(action: Action, params: &[u8]) -> Option<(String, Vec<AnnotatedPayloadField>)> {
+    let mut details = Vec::new();
+    let subtitle = match action {
+        Action::MintPosition => {
+            let p = MintPositionParams::abi_decode_params(params).ok()?;
+            details.push(pool_key_field(&p.poolKey));
+            details.push(text_field("Tick Lower", p.tickLower.to_string()));
+            details.push(text_field("Tick Upper", p.tickUpper.to_string()));
+            details.push(text_field("Liquidity", p.liquidity.to_string()));
+            details.push(text_field("Max Amount 0", p.amount0Max.to_string()));
+            details.push(text_field("Max Amount 1", p.amount1Max.to_string()));
+            details.push(text_field("Owner", recipient(p.owner)));
+            details.extend(hook_data_field(&p.hookData));
+            format!(
+                "Add {} liquidity to {} between ticks {} and {}",
+                p.liquidity,
+                pool_name(&p.poolKey),
+                p.tickLower,
+                p.tickUpper
+            )
+        }
+        Action::MintPositionFromDeltas => {
+            let p = MintPositionFromDeltasParams::abi_decode_params(params).ok()?;
+            details.push(pool_key_field(&p.poolKey));
+            details.push(text_field("Tick Lower", p.tickLower.to_string()));
+            details.push(text_field("Tick Upper", p.tickUpper.to_string()));
+            details.push(text_field("Max Amount 0", p.amount0Max.to_string()));
+            details.push(text_field("Max Amount 1", p.amount1Max.to_string()));
+            details.push(text_field("Owner", recipient(p.owner)));
+            details.extend(hook_data_field(&p.hookData));
+            format!(
+                "Add the settled balances to {} between ticks {} and {}",
+                pool_name(&p.poolKey),
+                p.tickLower,
+                p.tickUpper
+            )
+        }
+        Action::IncreaseLiquidity => {
+            let p = IncreaseLiquidityParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Position", format!("#{}", p.tokenId)));
+            details.push(text_field("Liquidity", p.liquidity.to_string()));
+            details.push(text_field("Max Amount 0", p.amount0Max.to_string()));
+            details.push(text_field("Max Amount 1", p.amount1Max.to_string()));
+            details.extend(hook_data_field(&p.hookData));
+            format!("Add {} liquidity to position #{}", p.liquidity, p.tokenId)
+        }
+        Action::DecreaseLiquidity => {
+            let p = DecreaseLiquidityParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Position", format!("#{}", p.tokenId)));
+            details.push(text_field("Liquidity", p.liquidity.to_string()));
+            details.push(text_field("Min Amount 0", p.amount0Min.to_string()));
+            details.push(text_field("Min Amount 1", p.amount1Min.to_string()));
+            details.extend(hook_data_field(&p.hookData));
+            format!(
+                "Remove {} liquidity from position #{}",
+                p.liquidity, p.tokenId
+            )
+        }
+        Action::BurnPosition => {
+            let p = BurnPositionParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Position", format!("#{}", p.tokenId)));
+            details.push(text_field("Min Amount 0", p.amount0Min.to_string()));
+            details.push(text_field("Min Amount 1", p.amount1Min.to_string()));
+            details.extend(hook_data_field(&p.hookData));
+            format!("Burn position #{} and withdraw its liquidity", p.tokenId)
+        }
+        Action::SwapExactInSingle => {
+            let p = ExactInputSingleParams::abi_decode(params).ok()?;
+            let (input, output) = swap_direction(&p.poolKey, p.zeroForOne);
+            details.push(pool_key_field(&p.poolKey));
+            details.push(text_field("Amount In", p.amountIn.to_string()));
+            details.push(text_field("Min Amount Out", p.amountOutMinimum.to_string()));
+            details.extend(hook_data_field(&p.hookData));
+            format!(
+                "Swap {} {input} for at least {} {output}",
+                p.amountIn, p.amountOutMinimum
+            )
+        }
+        Action::SwapExactOutSingle => {
+            let p = ExactOutputSingleParams::abi_decode(params).ok()?;
+            let (input, output) = swap_direction(&p.poolKey, p.zeroForOne);
+            details.push(pool_key_field(&p.poolKey));
+            details.push(text_field("Amount Out", p.amountOut.to_string()));
+            details.push(text_field("Max Amount In", p.amountInMaximum.to_string()));
+            details.extend(hook_data_field(&p.hookData));
+            format!(
+                "Swap at most {} {input} for {} {output}",
+                p.amountInMaximum, p.amountOut
+            )
+        }
+        Action::SwapExactIn => {
+            let p = ExactInputParams::abi_decode(params).ok()?;
+            let output = p.path.last()?.intermediateCurrency;
+            details.push(text_field("Currency In", currency(p.currencyIn)));
+            details.extend(path_fields(&p.path));
+            details.push(text_field("Amount In", p.amountIn.to_string()));
+            details.push(text_field("Min Amount Out", p.amountOutMinimum.to_string()));
+            format!(
+                "Swap {} {} for at least {} {} over {} pools",
+                p.amountIn,
+                currency(p.currencyIn),
+                p.amountOutMinimum,
+                currency(output),
+                p.path.len()
+            )
+        }
+        Action::SwapExactOut => {
+            // The path runs from the output back to the input currency
+            let p = ExactOutputParams::abi_decode(params).ok()?;
+            let input = p.path.first()?.intermediateCurrency;
+            details.push(text_field("Currency Out", currency(p.currencyOut)));
+            details.extend(path_fields(&p.path));
+            details.push(text_field("Amount Out", p.amountOut.to_string()));
+            details.push(text_field("Max Amount In", p.amountInMaximum.to_string()));
+            format!(
+                "Swap at most {} {} for {} {} over {} pools",
+                p.amountInMaximum,
+                currency(input),
+                p.amountOut,
+                currency(p.currencyOut),
+                p.path.len()
+            )
+        }
+        Action::Settle => {
+            let p = SettleParams::abi_decode_params(params).ok()?;
+            let payer = if p.payerIsUser { "Sender" } else { "Router" };
+            details.push(text_field("Currency", currency(p.currency)));
+            details.push(text_field("Amount", settle_amount(p.amount)));
+            details.push(text_field("Payer", payer));
+            format!(
+                "Pay {} of {} from {payer}",
+                settle_amount(p.amount),
+                currency(p.currency)
+            )
+        }
+        Action::SettleAll => {
+            let p = CurrencyAmountParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Currency", currency(p.currency)));
+            details.push(text_field("Max Amount", p.amount.to_string()));
+            format!("Pay up to {} {}", p.amount, currency(p.currency))
+        }
+        Action::SettlePair => {
+            let p = CurrencyPairParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Currency 0", currency(p.currency0)));
+            details.push(text_field("Currency 1", currency(p.currency1)));
+            format!(
+                "Pay what is owed in {} and {}",
+                currency(p.currency0),
+                currency(p.currency1)
+            )
+        }
+        Action::Take => {
+            let p = TakeParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Currency", currency(p.currency)));
+            details.push(text_field("Recipient", recipient(p.recipient)));
+            details.push(text_field("Amount", settle_amount(p.amount)));
+            format!(
+                "Send {} of {} to {}",
+                settle_amount(p.amount),
+                currency(p.currency),
+                recipient(p.recipient)
+            )
+        }
+        Action::TakeAll => {
+            let p = CurrencyAmountParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Currency", currency(p.currency)));
+            details.push(text_field("Min Amount", p.amount.to_string()));
+            format!(
+                "Send at least {} {} to the sender",
+                p.amount,
+                currency(p.currency)
+            )
+        }
+        Action::TakePortion => {
+            let p = TakePortionParams::abi_decode_params(params).ok()?;
+            let portion = format!("{}%", format_fixed_point(p.bips.try_into().ok()?, 2));
+            details.push(text_field("Currency", currency(p.currency)));
+            details.push(text_field("Recipient", recipient(p.recipient)));
+            details.push(text_field("Portion", portion.clone()));
+            format!(
+                "Send {portion} of {} to {}",
+                currency(p.currency),
+                recipient(p.recipient)
+            )
+        }
+        Action::TakePair => {
+            let p = TakePairParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Currency 0", currency(p.currency0)));
+            details.push(text_field("Currency 1", currency(p.currency1)));
+            details.push(text_field("Recipient", recipient(p.recipient)));
+            format!(
+                "Send {} and {} to {}",
+                currency(p.currency0),
+                currency(p.currency1),
+                recipient(p.recipient)
+            )
+        }
+        Action::CloseCurrency => {
+            let currency_address = sol_data::Address::abi_decode(params).ok()?;
+            details.push(text_field("Currency", currency(currency_address)));
+            format!(
+                "Settle or take the balance of {}",
+                currency(currency_address)
+            )
+        }
+        Action::ClearOrTake => {
+            let p = CurrencyAmountParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Currency", currency(p.currency)));
+            details.push(text_field("Max Cleared Amount", p.amount.to_string()));
+            format!(
+                "Forfeit up to {} {}, otherwise send it to the sender",
+                p.amount,
+                currency(p.currency)
+            )
+        }
+        Action::Sweep => {
+            let p = SweepParams::abi_decode_params(params).ok()?;
+            details.push(text_field("Currency", currency(p.currency)));
+            details.push(text_field("Recipient", recipient(p.to)));
+            format!(
+                "Send the router's {} balance to {}",
+                currency(p.currency),
+                recipient(p.to)
+            )
+        }
+        Action::Wrap | Action::Unwrap => {
+            let amount = settle_amount(sol_data::Uint::<256>::abi_decode(params).ok()?);
+            details.push(text_field("Amount", amount.clone()));
+            if action == Action::Wrap {
+                format!("Wrap {amount} of ETH into WETH")
+            } else {
+                format!("Unwrap {amount} of WETH into ETH")
+            }
+        }
+        Action::IncreaseLiquidityFromDeltas
+        | Action::Donate
+        | Action::Mint6909
+        | Action::Burn6909 => return None,
+    };
+    Some((subtitle, details))
+}
+
+fn action_field(index: usize, byte: u8, params: Option<&[u8]>) -> AnnotatedPayloadField {
+    let label = format!("Action {}", index + 1);
+    let params_hex = params.map_or_else(|| "None".to_string(), |p| format!("0x{}", hex::encode(p)));
+    let field = match Action::try_from(byte) {
+        Ok(action) => match params.and_then(|params| action_details(action, params)) {
+            Some((subtitle, details)) => preview_field(&label, action.title(), subtitle, details),
+            None => preview_field(
+                &label,
+                action.title(),
+                format!("Params: {params_hex}"),
+                vec![],
+            ),
+        },
+        Err(_) => preview_field(
+            &label,
+            &format!("Unknown Action 0x{byte:02x}"),
+            format!("Params: {params_hex}"),
+            vec![],
+        ),
+    };
+    annotated(field)
+}
+
+/// One field per action byte, each paired with the parameters at the same position.
+fn action_fields(actions: &[u8], params: &[Bytes]) -> Vec<AnnotatedPayloadField> {
+    actions
+        .iter()
+        .enumerate()
+        .map(|(i, &byte)| action_field(i, byte, params.get(i).map(|p| &p.0[..])))
+        .collect()
+}
+
+fn action_titles(actions: &[u8]) -> String {
+    actions
+        .iter()
+        .map(|&byte| {
+            Action::try_from(byte).map_or_else(|_| format!("0x{byte:02x}"), |a| a.title().into())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Visualizer for Uniswap V4 PoolManager and PositionManager contract calls
 pub struct V4PoolManagerVisualizer;
 
 impl V4PoolManagerVisualizer {
-    /// Attempts to decode and visualize V4 PoolManager function calls
+    /// Attempts to decode and visualize V4 PoolManager or PositionManager function calls
     ///
     /// # Arguments
     /// * `input` - The calldata bytes
@@ -62,21 +715,199 @@ impl V4PoolManagerVisualizer {
         if input.len() < 4 {
             return None;
         }
+        let selector = &input[..4];
+        if selector == IPositionManager::modifyLiquiditiesCall::SELECTOR
+            || selector == IPositionManager::modifyLiquiditiesWithoutUnlockCall::SELECTOR
+        {
+            return self.visualize_position_manager_call("Uniswap V4 Position Manager", input);
+        }
+
+        let label = "Uniswap V4 Pool Manager";
+        if selector == IPoolManager::initializeCall::SELECTOR {
+            let call = IPoolManager::initializeCall::abi_decode(input).ok()?;
+            Some(initialize_field(
+                label,
+                &call.key,
+                call.sqrtPriceX96.to_string(),
+            ))
+        } else if selector == IPoolManager::modifyLiquidityCall::SELECTOR {
+            let call = IPoolManager::modifyLiquidityCall::abi_decode(input).ok()?;
+            let mut details = vec![
+                pool_key_field(&call.key),
+                text_field("Tick Lower", call.params.tickLower.to_string()),
+                text_field("Tick Upper", call.params.tickUpper.to_string()),
+                text_field("Liquidity Delta", call.params.liquidityDelta.to_string()),
+                text_field("Salt", call.params.salt.to_string()),
+            ];
+            details.extend(hook_data_field(&call.hookData));
+            Some(preview_field(
+                label,
+                "Modify Liquidity",
+                format!(
+                    "Change liquidity of {} by {} between ticks {} and {}",
+                    pool_name(&call.key),
+                    call.params.liquidityDelta,
+                    call.params.tickLower,
+                    call.params.tickUpper
+                ),
+                details,
+            ))
+        } else if selector == IPoolManager::swapCall::SELECTOR {
+            let call = IPoolManager::swapCall::abi_decode(input).ok()?;
+            let (input_currency, output_currency) =
+                swap_direction(&call.key, call.params.zeroForOne);
+            // A negative amount is exact input, a positive one exact output
+            let amount = call.params.amountSpecified;
+            let subtitle = if amount.is_negative() {
+                format!(
+                    "Swap {} {input_currency} for {output_currency}",
+                    amount.unsigned_abs()
+                )
+            } else {
+                format!("Swap {input_currency} for {amount} {output_currency}")
+            };
+            let mut details = vec![
+                pool_key_field(&call.key),
+                text_field("Amount Specified", amount.to_string()),
+                text_field(
+                    "Price Limit (sqrtPriceX96)",
+                    call.params.sqrtPriceLimitX96.to_string(),
+                ),
+            ];
+            details.extend(hook_data_field(&call.hookData));
+            Some(preview_field(label, "Swap", subtitle, details))
+        } else if selector == IPoolManager::donateCall::SELECTOR {
+            let call = IPoolManager::donateCall::abi_decode(input).ok()?;
+            let mut details = vec![
+                pool_key_field(&call.key),
+                text_field("Amount 0", call.amount0.to_string()),
+                text_field("Amount 1", call.amount1.to_string()),
+            ];
+            details.extend(hook_data_field(&call.hookData));
+            Some(preview_field(
+                label,
+                "Donate",
+                format!(
+                    "Donate {} {} and {} {} to the pool's liquidity providers",
+                    call.amount0,
+                    currency(call.key.currency0),
+                    call.amount1,
+                    currency(call.key.currency1)
+                ),
+                details,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Visualizes PositionManager `modifyLiquidities` calldata, one layout per action.
+    pub fn visualize_position_manager_call(
+        &self,
+        label: &str,
+        input: &[u8],
+    ) -> Option<SignablePayloadField> {
+        let (data, deadline) =
+            if let Ok(call) = IPositionManager::modifyLiquiditiesCall::abi_decode(input) {
+                let data = ActionsData::abi_decode_params(&call.unlockData).ok()?;
+                (data, Some(call.deadline))
+            } else {
+                let call =
+                    IPositionManager::modifyLiquiditiesWithoutUnlockCall::abi_decode(input).ok()?;
+                let data = ActionsData {
+                    actions: call.actions,
+                    params: call.params,
+                };
+                (data, None)
+            };
+        let mut details = action_fields(&data.actions, &data.params);
+        details.extend(deadline.and_then(deadline_field));
+        Some(preview_field(
+            label,
+            "Modify Liquidities",
+            format!(
+                "{} actions: {}",
+                data.actions.len(),
+                action_titles(&data.actions)
+            ),
+            details,
+        ))
+    }
+
+    /// Visualizes the input of the Universal Router's V4_SWAP command, one layout per action.
+    pub fn visualize_router_swap(&self, label: &str, input: &[u8]) -> Option<SignablePayloadField> {
+        let data = ActionsData::abi_decode_params(input).ok()?;
+        Some(preview_field(
+            label,
+            "V4 Swap",
+            format!(
+                "{} actions: {}",
+                data.actions.len(),
+                action_titles(&data.actions)
+            ),
+            action_fields(&data.actions, &data.params),
+        ))
+    }
 
-        // TODO: Implement V4 PoolManager function decoding
-        // - initialize(PoolKey,uint160,bytes)
-        // - modifyLiquidity(PoolKey,ModifyLiquidityParams,bytes)
-        // - swap(PoolKey,SwapParams,bytes)
-        // - donate(PoolKey,uint256,uint256,bytes)
-        //
-        // For now, return None to use fallback visualizer
-        None
+    /// Visualizes the input of the Universal Router's V4_INITIALIZE_POOL command.
+    pub fn visualize_initialize_pool(
+        &self,
+        label: &str,
+        input: &[u8],
+    ) -> Option<SignablePayloadField> {
+        let params = InitializePoolParams::abi_decode_params(input).ok()?;
+        Some(initialize_field(
+            label,
+            &params.key,
+            params.sqrtPriceX96.to_string(),
+        ))
     }
 }
 
+fn initialize_field(label: &str, key: &PoolKey, sqrt_price_x96: String) -> SignablePayloadField {
+    preview_field(
+        label,
+        "Initialize Pool",
+        format!("Create the {} pool", pool_name(key)),
+        vec![
+            pool_key_field(key),
+            text_field("Initial Price (sqrtPriceX96)", sqrt_price_x96),
+        ],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::aliases::U24;
+    use alloy_sol_types::SolValue;
+
+    fn pool_key() -> PoolKey {
+        PoolKey {
+            currency0: Address::ZERO,
+            currency1: Address::repeat_byte(0x11),
+            fee: U24::from(3000),
+            tickSpacing: "60".parse().unwrap(),
+            // Hook address with beforeSwap and afterSwap set
+            hooks: "0x00000000000000000000000000000000000000c0"
+                .parse()
+                .unwrap(),
+        }
+    }
+
+    fn expanded(field: &SignablePayloadField) -> &[AnnotatedPayloadField] {
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected a PreviewLayout, got {field:?}");
+        };
+        &preview_layout.expanded.as_ref().unwrap().fields
+    }
+
+    fn subtitle(field: &SignablePayloadField) -> &str {
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected a PreviewLayout, got {field:?}");
+        };
+        &preview_layout.subtitle.as_ref().unwrap().text
+    }
 
     #[test]
     fn test_visualize_empty_input() {
@@ -90,5 +921,158 @@ mod tests {
         assert_eq!(visualizer.visualize_tx_commands(&[0x01, 0x02]), None);
     }
 
-    // TODO: Add tests for V4 PoolManager functions once implemented
+    #[test]
+    fn test_hook_permissions() {
+        assert_eq!(
+            hook_permissions(pool_key().hooks),
+            vec!["beforeSwap", "afterSwap"]
+        );
+        assert!(hook_permissions(Address::repeat_byte(0x00)).is_empty());
+        let all: Address = "0x0000000000000000000000000000000000003fff"
+            .parse()
+            .unwrap();
+        assert_eq!(hook_permissions(all).len(), 14);
+        assert_eq!(format_fee(3000), "0.3%");
+        assert_eq!(format_fee(DYNAMIC_FEE_FLAG), "Dynamic");
+    }
+
+    #[test]
+    fn test_visualize_pool_manager_swap() {
+        let input = IPoolManager::swapCall {
+            key: pool_key(),
+            params: SwapParams {
+                zeroForOne: true,
+                amountSpecified: "-1000".parse().unwrap(),
+                sqrtPriceLimitX96: Default::default(),
+            },
+            hookData: Bytes::new(),
+        }
+        .abi_encode();
+        let field = V4PoolManagerVisualizer
+            .visualize_tx_commands(&input)
+            .unwrap();
+        assert_eq!(field.label(), "Uniswap V4 Pool Manager");
+        assert_eq!(
+            subtitle(&field),
+            format!("Swap 1000 ETH for {}", Address::repeat_byte(0x11))
+        );
+        let pool = &expanded(&field)[0].signable_payload_field;
+        let labels: Vec<&str> = expanded(pool)
+            .iter()
+            .map(|f| f.signable_payload_field.label().as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Currency 0",
+                "Currency 1",
+                "Fee",
+                "Tick Spacing",
+                "Hooks",
+                "Hook Permissions"
+            ]
+        );
+        assert_eq!(
+            expanded(pool)[5].signable_payload_field.fallback_text(),
+            "beforeSwap, afterSwap"
+        );
+    }
+
+    #[test]
+    fn test_visualize_modify_liquidities() {
+        let mint = MintPositionParams {
+            poolKey: pool_key(),
+            tickLower: "-120".parse().unwrap(),
+            tickUpper: "120".parse().unwrap(),
+            liquidity: U256::from(5000),
+            amount0Max: 100,
+            amount1Max: 200,
+            owner: Address::with_last_byte(1),
+            hookData: Bytes::new(),
+        }
+        .abi_encode_params();
+        let settle_pair = CurrencyPairParams {
+            currency0: Address::ZERO,
+            currency1: Address::repeat_byte(0x11),
+        }
+        .abi_encode_params();
+        let unlock_data = ActionsData {
+            actions: Bytes::from(vec![
+                Action::MintPosition as u8,
+                Action::SettlePair as u8,
+                0x7f,
+            ]),
+            params: vec![
+                Bytes::from(mint),
+                Bytes::from(settle_pair),
+                Bytes::from(vec![0x01]),
+            ],
+        }
+        .abi_encode_params();
+        let input = IPositionManager::modifyLiquiditiesCall {
+            unlockData: Bytes::from(unlock_data),
+            deadline: U256::from(1_700_000_000u64),
+        }
+        .abi_encode();
+
+        let field = V4PoolManagerVisualizer
+            .visualize_tx_commands(&input)
+            .unwrap();
+        assert_eq!(field.label(), "Uniswap V4 Position Manager");
+        assert_eq!(
+            subtitle(&field),
+            "3 actions: Mint Position, Settle Pair, 0x7f"
+        );
+        let actions = expanded(&field);
+        assert_eq!(actions.len(), 4);
+        assert_eq!(
+            subtitle(&actions[0].signable_payload_field),
+            format!(
+                "Add 5000 liquidity to ETH / {} between ticks -120 and 120",
+                Address::repeat_byte(0x11)
+            )
+        );
+        let owner = expanded(&actions[0].signable_payload_field)
+            .iter()
+            .find(|f| f.signable_payload_field.label() == "Owner")
+            .unwrap();
+        assert_eq!(owner.signable_payload_field.fallback_text(), "Sender");
+        assert_eq!(
+            actions[2].signable_payload_field.fallback_text(),
+            "Unknown Action 0x7f: Params: 0x01"
+        );
+        assert_eq!(actions[3].signable_payload_field.label(), "Deadline");
+    }
+
+    #[test]
+    fn test_visualize_router_swap() {
+        let swap = ExactInputSingleParams {
+            poolKey: pool_key(),
+            zeroForOne: false,
+            amountIn: 700,
+            amountOutMinimum: 650,
+            hookData: Bytes::new(),
+        }
+        .abi_encode();
+        let input = ActionsData {
+            actions: Bytes::from(vec![Action::SwapExactInSingle as u8]),
+            params: vec![Bytes::from(swap)],
+        }
+        .abi_encode_params();
+        let field = V4PoolManagerVisualizer
+            .visualize_router_swap("Command 1", &input)
+            .unwrap();
+        assert_eq!(field.label(), "Command 1");
+        assert_eq!(
+            subtitle(&expanded(&field)[0].signable_payload_field),
+            format!(
+                "Swap 700 {} for at least 650 ETH",
+                Address::repeat_byte(0x11)
+            )
+        );
+        assert_eq!(
+            V4PoolManagerVisualizer.visualize_router_swap("Command 1", &[0xde, 0xad]),
+            None
+        );
+    }
 }
@@ -25,7 +25,7 @@ pub fn register(
     contract_reg: &mut ContractRegistry,
     _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
 ) {
-    use config::UniswapUniversalRouter;
+    use config::{UniswapUniversalRouter, UniswapV4PoolManager, UniswapV4PositionManager};
 
     let address = UniswapConfig::universal_router_address();
 
@@ -34,6 +34,16 @@ pub fn register(
         contract_reg.register_contract_typed::<UniswapUniversalRouter>(chain_id, vec![address]);
     }
 
+    // V4 contracts, on Mainnet only
+    contract_reg.register_contract_typed::<UniswapV4PoolManager>(
+        1,
+        vec![UniswapConfig::v4_pool_manager_address()],
+    );
+    contract_reg.register_contract_typed::<UniswapV4PositionManager>(
+        1,
+        vec![UniswapConfig::v4_position_manager_address()],
+    );
+
     // TODO: Register visualizers once we implement ContractVisualizer for UniversalRouterVisualizer
     // For now, we just register the contract addresses
     // Future: visualizer_reg.register(Box::new(UniversalRouterVisualizer::new()));
@@ -66,5 +76,10 @@ mod tests {
                 });
             assert_eq!(contract_type, UniswapUniversalRouter::short_type_id());
         }
+
+        assert_eq!(
+            contract_reg.get_contract_type(1, UniswapConfig::v4_position_manager_address()),
+            Some("UniswapV4PositionManager".to_string())
+        );
     }
 }
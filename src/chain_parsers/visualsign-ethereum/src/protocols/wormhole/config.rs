@@ -0,0 +1,27 @@
+//! Wormhole protocol configuration
+
+use crate::registry::ContractType;
+use alloy_primitives::Address;
+
+/// Contract type marker for the Wormhole Token Bridge, which locks or burns tokens for a
+/// transfer message that other chains redeem
+///
+/// Reference: <https://wormhole.com/docs/products/reference/contract-addresses/>
+#[derive(Debug, Clone, Copy)]
+pub struct WormholeTokenBridge;
+
+impl ContractType for WormholeTokenBridge {}
+
+/// Wormhole protocol configuration
+pub struct WormholeConfig;
+
+impl WormholeConfig {
+    /// Returns the Token Bridge address on Ethereum Mainnet
+    ///
+    /// Source: <https://wormhole.com/docs/products/reference/contract-addresses/>
+    pub fn token_bridge_address() -> Address {
+        "0x3ee18B2214AFF97000D974cf647E7C347E8fa585"
+            .parse()
+            .expect("Valid Wormhole Token Bridge address")
+    }
+}
@@ -0,0 +1,5 @@
+//! Wormhole protocol contract visualizers
+
+pub mod token_bridge;
+
+pub use token_bridge::WormholeTokenBridgeVisualizer;
@@ -0,0 +1,214 @@
+use alloy_primitives::{Address, B256, Bytes, U256};
+use alloy_sol_types::{SolCall, sol};
+use visualsign::{AnnotatedPayloadField, SignablePayloadField};
+
+use crate::protocols::fields::{
+    RemoteAddressFormat, preview_field, remote_chain, remote_recipient, text_field, token_amount,
+    token_name,
+};
+use crate::registry::ContractRegistry;
+
+// From: https://github.com/wormhole-foundation/wormhole/blob/main/ethereum/contracts/bridge/Bridge.sol
+sol! {
+    interface ITokenBridge {
+        function transferTokens(address token, uint256 amount, uint16 recipientChain, bytes32 recipient, uint256 arbiterFee, uint32 nonce) external payable returns (uint64 sequence);
+
+        function transferTokensWithPayload(address token, uint256 amount, uint16 recipientChain, bytes32 recipient, uint32 nonce, bytes payload) external payable returns (uint64 sequence);
+
+        /// Wraps the ETH sent with the call into WETH and transfers it
+        function wrapAndTransferETH(uint16 recipientChain, bytes32 recipient, uint256 arbiterFee, uint32 nonce) external payable returns (uint64 sequence);
+
+        function wrapAndTransferETHWithPayload(uint16 recipientChain, bytes32 recipient, uint32 nonce, bytes payload) external payable returns (uint64 sequence);
+    }
+}
+
+const LABEL: &str = "Wormhole Token Bridge";
+
+// From: https://wormhole.com/docs/products/reference/chain-ids/
+fn chain(id: u16) -> Option<(&'static str, RemoteAddressFormat)> {
+    let chain = match id {
+        1 => ("Solana", RemoteAddressFormat::Solana),
+        2 => ("Ethereum", RemoteAddressFormat::Evm),
+        4 => ("BNB Smart Chain", RemoteAddressFormat::Evm),
+        5 => ("Polygon", RemoteAddressFormat::Evm),
+        6 => ("Avalanche", RemoteAddressFormat::Evm),
+        10 => ("Fantom", RemoteAddressFormat::Evm),
+        14 => ("Celo", RemoteAddressFormat::Evm),
+        16 => ("Moonbeam", RemoteAddressFormat::Evm),
+        21 => ("Sui", RemoteAddressFormat::Hex),
+        22 => ("Aptos", RemoteAddressFormat::Hex),
+        23 => ("Arbitrum", RemoteAddressFormat::Evm),
+        24 => ("Optimism", RemoteAddressFormat::Evm),
+        30 => ("Base", RemoteAddressFormat::Evm),
+        _ => return None,
+    };
+    Some(chain)
+}
+
+/// A Token Bridge transfer, whichever entrypoint it comes from
+struct Transfer {
+    /// The zero address for ETH sent with the call
+    token: Address,
+    amount: U256,
+    recipient_chain: u16,
+    recipient: B256,
+    /// None for transfers with a payload, which carry no relayer fee
+    arbiter_fee: Option<U256>,
+    payload: Option<Bytes>,
+}
+
+/// Visualizer for Wormhole Token Bridge transfers
+pub struct WormholeTokenBridgeVisualizer;
+
+impl WormholeTokenBridgeVisualizer {
+    /// Decodes a Token Bridge transfer, or returns None when `input` is not one. `value` is the
+    /// ETH sent with the transaction, which is what the `wrapAndTransferETH` calls transfer.
+    pub fn visualize_tx_commands(
+        &self,
+        input: &[u8],
+        value: U256,
+        chain_id: u64,
+        registry: Option<&ContractRegistry>,
+    ) -> Option<SignablePayloadField> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        let transfer = match selector {
+            ITokenBridge::transferTokensCall::SELECTOR => {
+                let call = ITokenBridge::transferTokensCall::abi_decode(input).ok()?;
+                Transfer {
+                    token: call.token,
+                    amount: call.amount,
+                    recipient_chain: call.recipientChain,
+                    recipient: call.recipient,
+                    arbiter_fee: Some(call.arbiterFee),
+                    payload: None,
+                }
+            }
+            ITokenBridge::transferTokensWithPayloadCall::SELECTOR => {
+                let call = ITokenBridge::transferTokensWithPayloadCall::abi_decode(input).ok()?;
+                Transfer {
+                    token: call.token,
+                    amount: call.amount,
+                    recipient_chain: call.recipientChain,
+                    recipient: call.recipient,
+                    arbiter_fee: None,
+                    payload: Some(call.payload),
+                }
+            }
+            ITokenBridge::wrapAndTransferETHCall::SELECTOR => {
+                let call = ITokenBridge::wrapAndTransferETHCall::abi_decode(input).ok()?;
+                Transfer {
+                    token: Address::ZERO,
+                    amount: value,
+                    recipient_chain: call.recipientChain,
+                    recipient: call.recipient,
+                    arbiter_fee: Some(call.arbiterFee),
+                    payload: None,
+                }
+            }
+            ITokenBridge::wrapAndTransferETHWithPayloadCall::SELECTOR => {
+                let call =
+                    ITokenBridge::wrapAndTransferETHWithPayloadCall::abi_decode(input).ok()?;
+                Transfer {
+                    token: Address::ZERO,
+                    amount: value,
+                    recipient_chain: call.recipientChain,
+                    recipient: call.recipient,
+                    arbiter_fee: None,
+                    payload: Some(call.payload),
+                }
+            }
+            _ => return None,
+        };
+
+        let amount = token_amount(transfer.amount, transfer.token, chain_id, registry);
+        let (name, format) = match chain(transfer.recipient_chain) {
+            Some((name, format)) => (Some(name), format),
+            None => (None, RemoteAddressFormat::Hex),
+        };
+        let destination = remote_chain(name, "chain", transfer.recipient_chain);
+        let recipient = remote_recipient(transfer.recipient, format);
+
+        let mut details: Vec<AnnotatedPayloadField> = vec![
+            text_field("Token", token_name(transfer.token, chain_id, registry)),
+            text_field("Amount", amount.clone()),
+            text_field("Destination Chain", destination.clone()),
+            text_field("Recipient", recipient.clone()),
+        ];
+        if let Some(fee) = transfer.arbiter_fee {
+            details.push(text_field(
+                "Relayer Fee",
+                token_amount(fee, transfer.token, chain_id, registry),
+            ));
+        }
+        if let Some(payload) = &transfer.payload {
+            details.push(text_field("Payload", format!("{} bytes", payload.len())));
+        }
+        Some(preview_field(
+            LABEL,
+            "Wormhole Transfer",
+            format!("Bridge {amount} to {recipient} on {destination}"),
+            details,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize_wrap_and_transfer_eth() {
+        let recipient = Address::repeat_byte(0x22);
+        let input = ITokenBridge::wrapAndTransferETHCall {
+            recipientChain: 30,
+            recipient: recipient.into_word(),
+            arbiterFee: U256::ZERO,
+            nonce: 0,
+        }
+        .abi_encode();
+
+        let field = WormholeTokenBridgeVisualizer
+            .visualize_tx_commands(&input, U256::from(10).pow(U256::from(18)), 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!("Wormhole Transfer: Bridge 1 ETH to {recipient} on Base (chain 30)")
+        );
+    }
+
+    #[test]
+    fn test_visualize_transfer_tokens_to_solana() {
+        let token = Address::repeat_byte(0x11);
+        let input = ITokenBridge::transferTokensWithPayloadCall {
+            token,
+            amount: U256::from(1_000_000),
+            recipientChain: 1,
+            recipient: B256::ZERO,
+            nonce: 0,
+            payload: Bytes::from(vec![1, 2, 3]),
+        }
+        .abi_encode();
+
+        let field = WormholeTokenBridgeVisualizer
+            .visualize_tx_commands(&input, U256::ZERO, 1, None)
+            .unwrap();
+        assert_eq!(
+            field.fallback_text(),
+            &format!(
+                "Wormhole Transfer: Bridge 1000000 {token} to 11111111111111111111111111111111 on Solana (chain 1)"
+            )
+        );
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        let details = preview_layout.expanded.unwrap().fields;
+        assert_eq!(
+            details
+                .last()
+                .unwrap()
+                .signable_payload_field
+                .fallback_text(),
+            "3 bytes"
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Wormhole protocol implementation
+//!
+//! This module contains the visualizer for Wormhole Token Bridge transfers, along with the
+//! bridge's configuration and registration.
+
+pub mod config;
+pub mod contracts;
+
+use crate::registry::ContractRegistry;
+use crate::visualizer::EthereumVisualizerRegistryBuilder;
+
+pub use config::WormholeConfig;
+pub use contracts::WormholeTokenBridgeVisualizer;
+
+/// Registers the Wormhole Token Bridge on Ethereum Mainnet
+///
+/// # Arguments
+/// * `contract_reg` - The contract registry to register addresses
+/// * `visualizer_reg` - The visualizer registry to register visualizers
+pub fn register(
+    contract_reg: &mut ContractRegistry,
+    _visualizer_reg: &mut EthereumVisualizerRegistryBuilder,
+) {
+    use config::WormholeTokenBridge;
+
+    contract_reg.register_contract_typed::<WormholeTokenBridge>(
+        1,
+        vec![WormholeConfig::token_bridge_address()],
+    );
+}
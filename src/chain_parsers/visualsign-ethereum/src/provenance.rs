@@ -0,0 +1,128 @@
+//! Byte ranges of the RLP items of a raw transaction, recorded as the provenance of the fields
+//! decoded from them.
+
+use std::ops::Range;
+
+use alloy_consensus::TxType;
+use alloy_rlp::Header;
+use visualsign::provenance::{Provenance, ProvenanceSource};
+
+/// Transaction items that a single rendered field is decoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawItem {
+    ChainId,
+    Nonce,
+    /// The gas price of a legacy transaction or the max fee per gas of a dynamic-fee one
+    GasPrice,
+    PriorityFee,
+    GasLimit,
+    To,
+    Value,
+    Data,
+}
+
+impl RawItem {
+    // Position of the item in the RLP list of each transaction type
+    fn position(self, tx_type: TxType) -> Option<usize> {
+        match tx_type {
+            TxType::Legacy => match self {
+                RawItem::Nonce => Some(0),
+                RawItem::GasPrice => Some(1),
+                RawItem::GasLimit => Some(2),
+                RawItem::To => Some(3),
+                RawItem::Value => Some(4),
+                RawItem::Data => Some(5),
+                // The EIP-155 chain ID, or the `v` it is folded into once signed
+                RawItem::ChainId => Some(6),
+                RawItem::PriorityFee => None,
+            },
+            TxType::Eip1559 => match self {
+                RawItem::ChainId => Some(0),
+                RawItem::Nonce => Some(1),
+                RawItem::PriorityFee => Some(2),
+                RawItem::GasPrice => Some(3),
+                RawItem::GasLimit => Some(4),
+                RawItem::To => Some(5),
+                RawItem::Value => Some(6),
+                RawItem::Data => Some(7),
+            },
+            TxType::Eip2930 | TxType::Eip4844 | TxType::Eip7702 => None,
+        }
+    }
+}
+
+/// Ranges of `raw` spanned by the items of its transaction list, each with its RLP header, or
+/// None when `raw` is not a well-formed transaction envelope.
+pub fn item_ranges(raw: &[u8]) -> Option<Vec<Range<usize>>> {
+    // Typed transactions start with their type byte, legacy ones with the list header
+    let start = usize::from(*raw.first()? <= 0x7f);
+    let mut buf = raw.get(start..)?;
+    let header = Header::decode(&mut buf).ok()?;
+    if !header.list {
+        return None;
+    }
+    let mut position = raw.len() - buf.len();
+    let end = position.checked_add(header.payload_length)?;
+    let list = raw.get(..end)?;
+
+    let mut ranges = Vec::new();
+    while position < end {
+        let mut item = &list[position..];
+        let item_header = Header::decode(&mut item).ok()?;
+        let item_end = (end - item.len()).checked_add(item_header.payload_length)?;
+        if item_end > end {
+            return None;
+        }
+        ranges.push(position..item_end);
+        position = item_end;
+    }
+    Some(ranges)
+}
+
+/// Maps the top-level fields at the given indices to the bytes of their items in `raw`,
+/// skipping items the transaction type does not have.
+pub fn field_provenance(raw: &[u8], tx_type: TxType, fields: &[(usize, RawItem)]) -> Provenance {
+    let mut provenance = Provenance::new();
+    let Some(ranges) = item_ranges(raw) else {
+        return provenance;
+    };
+    for (index, item) in fields {
+        if let Some(range) = item
+            .position(tx_type)
+            .and_then(|position| ranges.get(position))
+        {
+            provenance.record(
+                Provenance::field_path(*index),
+                ProvenanceSource::Bytes {
+                    start: range.start,
+                    end: range.end,
+                },
+            );
+        }
+    }
+    provenance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_ranges() {
+        // Type 2, list of chain ID 1, nonce 0 (empty string) and a two-byte value
+        let raw = [0x02, 0xc5, 0x01, 0x80, 0x82, 0x12, 0x34];
+        assert_eq!(item_ranges(&raw), Some(vec![2..3, 3..4, 4..7]));
+
+        let provenance = field_provenance(
+            &raw,
+            TxType::Eip1559,
+            &[(0, RawItem::ChainId), (3, RawItem::Nonce), (4, RawItem::To)],
+        );
+        assert_eq!(provenance.bytes("Fields[3]", &raw), Some(&[0x80][..]));
+        // The list has no `to` item
+        assert_eq!(provenance.len(), 2);
+
+        // A list running past the end of the input
+        assert_eq!(item_ranges(&[0xc5, 0x01]), None);
+    }
+}
@@ -176,6 +176,18 @@ impl ContractRegistry {
         self.address_to_type.get(&(chain_id, address)).cloned()
     }
 
+    /// Lists the registered contract types across all chains, sorted and without duplicates
+    pub fn contract_types(&self) -> Vec<String> {
+        let mut types: Vec<String> = self
+            .type_to_addresses
+            .keys()
+            .map(|(_, contract_type)| contract_type.clone())
+            .collect();
+        types.sort();
+        types.dedup();
+        types
+    }
+
     /// Gets the symbol for a specific token on a chain
     ///
     /// # Arguments
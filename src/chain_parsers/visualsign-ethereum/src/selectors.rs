@@ -0,0 +1,135 @@
+//! Built-in database of well-known function signatures, keyed by 4-byte selector.
+//!
+//! Used as a last resort for calldata no visualizer decodes, so the payload can show the
+//! probable function and its arguments instead of bare hex. The database is embedded and its
+//! selectors are computed from the signatures, so lookups never touch the network and always
+//! give the same answer. A selector only identifies a function probabilistically: unrelated
+//! signatures can share one, so matches are presented as a guess.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use alloy_primitives::keccak256;
+
+/// Signatures in canonical form: name followed by the parenthesised parameter types
+const SIGNATURES: &[&str] = &[
+    // Tokens (ERC20, ERC721, ERC1155, WETH, EIP-2612)
+    "transfer(address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "approve(address,uint256)",
+    "increaseAllowance(address,uint256)",
+    "decreaseAllowance(address,uint256)",
+    "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+    "safeTransferFrom(address,address,uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "setApprovalForAll(address,bool)",
+    "safeTransferFrom(address,address,uint256,uint256,bytes)",
+    "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+    "mint(address,uint256)",
+    "burn(uint256)",
+    "burn(address,uint256)",
+    "deposit()",
+    "withdraw(uint256)",
+    // Ownership, access control and upgrades
+    "transferOwnership(address)",
+    "renounceOwnership()",
+    "acceptOwnership()",
+    "grantRole(bytes32,address)",
+    "revokeRole(bytes32,address)",
+    "renounceRole(bytes32,address)",
+    "pause()",
+    "unpause()",
+    "upgradeTo(address)",
+    "upgradeToAndCall(address,bytes)",
+    // Batching and smart accounts
+    "multicall(bytes[])",
+    "multicall(uint256,bytes[])",
+    "aggregate((address,bytes)[])",
+    "aggregate3((address,bool,bytes)[])",
+    "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)",
+    // Uniswap V2-style routers
+    "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactTokens(uint256,uint256,address[],address,uint256)",
+    "swapExactETHForTokens(uint256,address[],address,uint256)",
+    "swapETHForExactTokens(uint256,address[],address,uint256)",
+    "swapExactTokensForETH(uint256,uint256,address[],address,uint256)",
+    "swapTokensForExactETH(uint256,uint256,address[],address,uint256)",
+    "addLiquidity(address,address,uint256,uint256,uint256,uint256,address,uint256)",
+    "addLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidity(address,address,uint256,uint256,uint256,address,uint256)",
+    "removeLiquidityETH(address,uint256,uint256,uint256,address,uint256)",
+    // Uniswap V3 SwapRouter
+    "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "exactInput((bytes,address,uint256,uint256,uint256))",
+    "exactOutputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+    "exactOutput((bytes,address,uint256,uint256,uint256))",
+    // Staking, rewards and airdrops
+    "stake(uint256)",
+    "unstake(uint256)",
+    "getReward()",
+    "exit()",
+    "claim()",
+    "claim(uint256,address,uint256,bytes32[])",
+    // Governance
+    "delegate(address)",
+    "castVote(uint256,uint8)",
+    "castVoteWithReason(uint256,uint8,string)",
+    // Bridges
+    "depositETH(address,address,uint16)",
+    "bridgeETHTo(address,uint32,bytes)",
+    "depositTransaction(address,uint256,uint64,bool,bytes)",
+];
+
+fn database() -> &'static HashMap<[u8; 4], &'static str> {
+    static DATABASE: OnceLock<HashMap<[u8; 4], &'static str>> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        let mut database = HashMap::with_capacity(SIGNATURES.len());
+        for signature in SIGNATURES {
+            let hash = keccak256(signature.as_bytes());
+            let selector = [hash[0], hash[1], hash[2], hash[3]];
+            // The first signature listed for a selector wins
+            database.entry(selector).or_insert(*signature);
+        }
+        database
+    })
+}
+
+/// The probable signature of the function `input` calls, from its first four bytes.
+pub fn lookup(input: &[u8]) -> Option<&'static str> {
+    let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+    database().get(&selector).copied()
+}
+
+/// Splits a signature into the function name and its parenthesised parameter list.
+pub fn split_signature(signature: &str) -> Option<(&str, &str)> {
+    let open = signature.find('(')?;
+    Some((&signature[..open], &signature[open..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        // transfer(address,uint256)
+        assert_eq!(
+            lookup(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]),
+            Some("transfer(address,uint256)")
+        );
+        // transferOwnership(address)
+        assert_eq!(
+            lookup(&[0xf2, 0xfd, 0xe3, 0x8b]),
+            Some("transferOwnership(address)")
+        );
+        assert_eq!(lookup(&[0xde, 0xad, 0xbe, 0xef]), None);
+        assert_eq!(lookup(&[0xa9, 0x05]), None);
+        assert_eq!(
+            split_signature("approve(address,uint256)"),
+            Some(("approve", "(address,uint256)"))
+        );
+
+        // Every entry is canonical, so no two of them map to the same selector by accident
+        assert_eq!(database().len(), SIGNATURES.len());
+    }
+}
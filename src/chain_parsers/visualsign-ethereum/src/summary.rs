@@ -0,0 +1,396 @@
+//! Top-level "Transaction Summary" combining what a transaction sends, approves and can cost.
+//!
+//! The summary re-reads the transaction rather than the rendered fields so numbers stay exact:
+//! native value, decoded ERC20 `transfer`/`transferFrom`/`approve` calls, and the worst-case
+//! network fee (`gas_limit * max fee per gas`), in the chain's native token. On OP Stack chains
+//! that fee only covers L2 execution; the L1 data fee comes on top (see [`crate::l2`]).
+
+use alloy_consensus::{Transaction as _, TypedTransaction};
+use alloy_primitives::{Address, U256};
+use visualsign::amount::Amount;
+use visualsign::errors::VisualSignError;
+use visualsign::fee::{MAX_TOTAL_COST_LABEL, create_max_total_cost_field};
+use visualsign::field_builders::{create_amount_field, create_text_field};
+use visualsign::text::sanitize_summary;
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+use crate::contracts::core::{ERC20AssetFlow, is_unlimited_approval};
+use crate::extract_gas_price;
+use crate::fmt::format_ether;
+use crate::l2::{self, RollupStack};
+use crate::registry::ContractRegistry;
+
+/// Builds the summary section.
+///
+/// ERC20 calls are only summarized when `decode_transfers` is set, matching whether the
+/// ERC20 visualizer renders them. Token amounts are only scaled when the transaction names its
+/// chain, since the registry is keyed by chain ID.
+pub fn create_transaction_summary(
+    transaction: &TypedTransaction,
+    decode_transfers: bool,
+    registry: &ContractRegistry,
+) -> Result<SignablePayloadField, VisualSignError> {
+    let value = transaction.value();
+    let max_fee = U256::from(transaction.gas_limit())
+        .saturating_mul(U256::from(extract_gas_price(transaction)));
+    let max_cost = value.saturating_add(max_fee);
+    let symbol = l2::native_symbol(transaction.chain_id());
+
+    let mut headline: Vec<String> = Vec::new();
+    let mut fields: Vec<AnnotatedPayloadField> = Vec::new();
+
+    if !value.is_zero() {
+        headline.push(format!("Send {} {symbol}", format_ether(value)));
+        fields.push(create_amount_field(
+            "You Send",
+            &format_ether(value),
+            symbol,
+        )?);
+    }
+
+    let token_flow = if decode_transfers {
+        ERC20AssetFlow::decode(transaction.input())
+    } else {
+        None
+    };
+    if let (Some(flow), Some(token)) = (&token_flow, transaction.to()) {
+        let chain_id = transaction.chain_id();
+        match flow {
+            ERC20AssetFlow::Transfer { to, amount } => {
+                let amount = format_token(registry, chain_id, token, *amount);
+                headline.push(format!("Send {amount}"));
+                fields.push(create_text_field(
+                    "You Send Token",
+                    &format!("{amount} to {to}"),
+                )?);
+            }
+            ERC20AssetFlow::TransferFrom { from, to, amount } => {
+                let amount = format_token(registry, chain_id, token, *amount);
+                headline.push(format!("Move {amount}"));
+                fields.push(create_text_field(
+                    "Token Transfer",
+                    &format!("{amount} from {from} to {to}"),
+                )?);
+            }
+            ERC20AssetFlow::Approve { spender, amount } => {
+                let amount = if is_unlimited_approval(*amount) {
+                    format!("Unlimited {}", token_name(registry, chain_id, token))
+                } else {
+                    format_token(registry, chain_id, token, *amount)
+                };
+                headline.push(format!("Approve {amount}"));
+                fields.push(create_text_field(
+                    "You Approve",
+                    &format!("{amount} for spender {spender}"),
+                )?);
+            }
+        }
+    }
+
+    let max_cost_text = format!("{} {symbol}", format_ether(max_cost));
+    let title_text = if headline.is_empty() {
+        "No assets sent".to_string()
+    } else {
+        sanitize_summary(&headline.join(", "))
+    };
+
+    // OP Stack chains add an L1 data fee that is only priced once the transaction is posted
+    let op_stack = l2::l2_chain(transaction.chain_id())
+        .is_some_and(|chain| chain.stack == RollupStack::OpStack);
+    let breakdown = if op_stack {
+        "gas limit x max fee per gas + value, excluding the L1 data fee"
+    } else {
+        "gas limit x max fee per gas + value"
+    };
+    let max_total_cost = match Amount::from_raw_digits(&max_cost.to_string(), 18, symbol) {
+        Ok(amount) => create_max_total_cost_field(&amount, breakdown),
+        // U256 always displays as plain digits, so this is unreachable in practice
+        Err(_) => create_amount_field(MAX_TOTAL_COST_LABEL, &format_ether(max_cost), symbol)?,
+    };
+
+    let mut condensed_fields = fields.clone();
+    condensed_fields.push(max_total_cost.clone());
+
+    let (fee_label, note) = if op_stack {
+        (
+            "Max L2 Execution Fee",
+            format!(
+                "Max cost is the {symbol} value plus gas limit times max fee per gas, excluding the L1 data fee; tokens are not included"
+            ),
+        )
+    } else {
+        (
+            "Max Network Fee",
+            format!(
+                "Max cost is the {symbol} value plus gas limit times max fee per gas; tokens are not included"
+            ),
+        )
+    };
+    let mut expanded_fields = fields;
+    expanded_fields.push(create_amount_field(
+        fee_label,
+        &format_ether(max_fee),
+        symbol,
+    )?);
+    expanded_fields.push(max_total_cost);
+    expanded_fields.push(create_text_field("Note", &note)?);
+
+    Ok(SignablePayloadField::PreviewLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text: format!("{title_text}. Max cost: {max_cost_text}"),
+            label: "Transaction Summary".to_string(),
+        },
+        preview_layout: SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 { text: title_text }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: format!("Max cost: {max_cost_text}"),
+            }),
+            condensed: Some(SignablePayloadFieldListLayout {
+                fields: condensed_fields,
+            }),
+            expanded: Some(SignablePayloadFieldListLayout {
+                fields: expanded_fields,
+            }),
+        },
+    })
+}
+
+// Formats a raw token amount with the registry's decimals and symbol when the token is known
+fn format_token(
+    registry: &ContractRegistry,
+    chain_id: Option<u64>,
+    token: Address,
+    amount: U256,
+) -> String {
+    chain_id
+        .zip(u128::try_from(amount).ok())
+        .and_then(|(chain_id, raw)| registry.format_token_amount(chain_id, token, raw))
+        .map(|(amount, symbol)| format!("{amount} {symbol}"))
+        .unwrap_or_else(|| format!("{amount} raw units of token {token}"))
+}
+
+fn token_name(registry: &ContractRegistry, chain_id: Option<u64>, token: Address) -> String {
+    chain_id
+        .and_then(|chain_id| registry.get_token_symbol(chain_id, token))
+        .unwrap_or_else(|| format!("token {token}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token_metadata::{ErcStandard, TokenMetadata};
+    use alloy_consensus::{TxEip1559, TxLegacy};
+    use alloy_primitives::{Bytes, ChainId, TxKind};
+    use alloy_sol_types::{SolCall, sol};
+
+    sol! {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+
+    const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const USDC_CHECKSUM: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+    fn layout_fields(field: &SignablePayloadField) -> (Vec<String>, Vec<String>) {
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        let fallbacks = |list: &Option<SignablePayloadFieldListLayout>| {
+            list.as_ref()
+                .unwrap()
+                .fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "{}: {}",
+                        f.signable_payload_field.label(),
+                        f.signable_payload_field.fallback_text()
+                    )
+                })
+                .collect()
+        };
+        (
+            fallbacks(&preview_layout.condensed),
+            fallbacks(&preview_layout.expanded),
+        )
+    }
+
+    fn erc20_call(input: Vec<u8>) -> TypedTransaction {
+        TypedTransaction::Eip1559(TxEip1559 {
+            chain_id: ChainId::from(1u64),
+            nonce: 0,
+            gas_limit: 60_000,
+            max_fee_per_gas: 50_000_000_000u128,
+            max_priority_fee_per_gas: 1_000_000_000u128,
+            to: TxKind::Call(USDC.parse().unwrap()),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: Bytes::from(input),
+        })
+    }
+
+    #[test]
+    fn test_native_transfer_summary() {
+        let tx = TypedTransaction::Legacy(TxLegacy {
+            chain_id: Some(ChainId::from(1u64)),
+            nonce: 0,
+            gas_price: 20_000_000_000u128,
+            gas_limit: 21000,
+            to: TxKind::Call(Address::ZERO),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            input: Bytes::new(),
+        });
+
+        let field = create_transaction_summary(&tx, true, &ContractRegistry::new()).unwrap();
+        assert_eq!(field.label(), "Transaction Summary");
+        assert_eq!(field.fallback_text(), "Send 1 ETH. Max cost: 1.00042 ETH");
+
+        let (condensed, expanded) = layout_fields(&field);
+        assert_eq!(
+            condensed,
+            vec!["You Send: 1 ETH", "Max Total Cost: 1.00042 ETH"]
+        );
+        assert!(expanded.contains(&"Max Network Fee: 0.00042 ETH".to_string()));
+    }
+
+    #[test]
+    fn test_l2_summary_uses_native_symbol_and_execution_fee() {
+        let tx = TypedTransaction::Legacy(TxLegacy {
+            chain_id: Some(ChainId::from(5000u64)),
+            nonce: 0,
+            gas_price: 20_000_000_000u128,
+            gas_limit: 21000,
+            to: TxKind::Call(Address::ZERO),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            input: Bytes::new(),
+        });
+
+        let field = create_transaction_summary(&tx, true, &ContractRegistry::new()).unwrap();
+        assert_eq!(field.fallback_text(), "Send 1 MNT. Max cost: 1.00042 MNT");
+        let (_, expanded) = layout_fields(&field);
+        assert!(expanded.contains(&"Max L2 Execution Fee: 0.00042 MNT".to_string()));
+        assert!(
+            expanded
+                .iter()
+                .any(|f| f.contains("excluding the L1 data fee"))
+        );
+    }
+
+    #[test]
+    fn test_erc20_transfer_summary_uses_token_metadata() {
+        let mut registry = ContractRegistry::new();
+        registry
+            .register_token(
+                1,
+                TokenMetadata {
+                    symbol: "USDC".to_string(),
+                    name: "USD Coin".to_string(),
+                    erc_standard: ErcStandard::Erc20,
+                    contract_address: USDC.to_string(),
+                    decimals: 6,
+                },
+            )
+            .unwrap();
+        let to = Address::repeat_byte(0x11);
+        let tx = erc20_call(
+            transferCall {
+                to,
+                amount: U256::from(1_500_000u64),
+            }
+            .abi_encode(),
+        );
+
+        let field = create_transaction_summary(&tx, true, &registry).unwrap();
+        let (condensed, _) = layout_fields(&field);
+        assert_eq!(
+            condensed,
+            vec![
+                format!("You Send Token: 1.500000 USDC to {to}"),
+                "Max Total Cost: 0.003 ETH".to_string(),
+            ]
+        );
+
+        // Without transfer decoding only the native cost is summarized
+        let field = create_transaction_summary(&tx, false, &registry).unwrap();
+        assert_eq!(field.fallback_text(), "No assets sent. Max cost: 0.003 ETH");
+    }
+
+    #[test]
+    fn test_chainless_transfer_summary_skips_token_metadata() {
+        let mut registry = ContractRegistry::new();
+        registry
+            .register_token(
+                1,
+                TokenMetadata {
+                    symbol: "USDC".to_string(),
+                    name: "USD Coin".to_string(),
+                    erc_standard: ErcStandard::Erc20,
+                    contract_address: USDC.to_string(),
+                    decimals: 6,
+                },
+            )
+            .unwrap();
+        let to = Address::repeat_byte(0x11);
+        // A pre-EIP-155 transaction can be replayed on any chain, so mainnet metadata is not
+        // assumed
+        let tx = TypedTransaction::Legacy(TxLegacy {
+            chain_id: None,
+            nonce: 0,
+            gas_price: 20_000_000_000u128,
+            gas_limit: 60_000,
+            to: TxKind::Call(USDC.parse().unwrap()),
+            value: U256::ZERO,
+            input: Bytes::from(
+                transferCall {
+                    to,
+                    amount: U256::from(1_500_000u64),
+                }
+                .abi_encode(),
+            ),
+        });
+
+        let field = create_transaction_summary(&tx, true, &registry).unwrap();
+        let (condensed, _) = layout_fields(&field);
+        assert_eq!(
+            condensed[0],
+            format!("You Send Token: 1500000 raw units of token {USDC_CHECKSUM} to {to}")
+        );
+    }
+
+    #[test]
+    fn test_unlimited_approve_summary() {
+        let spender = Address::repeat_byte(0x44);
+        let tx = erc20_call(
+            approveCall {
+                spender,
+                amount: U256::MAX,
+            }
+            .abi_encode(),
+        );
+
+        let field = create_transaction_summary(&tx, true, &ContractRegistry::new()).unwrap();
+        let (condensed, _) = layout_fields(&field);
+        assert_eq!(
+            condensed[0],
+            format!("You Approve: Unlimited token {USDC_CHECKSUM} for spender {spender}")
+        );
+
+        // Near-max approvals are unlimited too, matching the ERC20 visualizer
+        let tx = erc20_call(
+            approveCall {
+                spender,
+                amount: U256::MAX - U256::from(1u64 << 32),
+            }
+            .abi_encode(),
+        );
+        let field = create_transaction_summary(&tx, true, &ContractRegistry::new()).unwrap();
+        let (condensed, _) = layout_fields(&field);
+        assert_eq!(
+            condensed[0],
+            format!("You Approve: Unlimited token {USDC_CHECKSUM} for spender {spender}")
+        );
+    }
+}
@@ -32,8 +32,7 @@ fn test_with_fixtures() {
         // Create options for the transaction
         let options = VisualSignOptions {
             decode_transfers: true,
-            transaction_name: None,
-            metadata: None,
+            ..Default::default()
         };
 
         let result = transaction_string_to_visual_sign(transaction_hex, options);
@@ -76,8 +75,7 @@ fn test_ethereum_charset_validation() {
         // Create options for the transaction
         let options = VisualSignOptions {
             decode_transfers: true,
-            transaction_name: None,
-            metadata: None,
+            ..Default::default()
         };
 
         let result = transaction_string_to_visual_sign(transaction_hex, options);
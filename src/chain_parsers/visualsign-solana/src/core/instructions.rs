@@ -1,20 +1,70 @@
 use crate::core::{InstructionVisualizer, VisualizerContext, visualize_with_any};
 use solana_parser::solana::parser::parse_transaction;
 use solana_parser::solana::structs::SolanaAccount;
-use solana_sdk::instruction::Instruction;
+use solana_sdk::instruction::{CompiledInstruction, Instruction};
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::transaction::Transaction as SolanaTransaction;
-use visualsign::AnnotatedPayloadField;
 use visualsign::errors::{TransactionParseError, VisualSignError};
+use visualsign::field_builders::{create_text_field, create_unknown_field};
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout,
+};
 
 // The following include! macro pulls in visualizer implementations generated at build time.
 // The file "generated_visualizers.rs" is created by the build script and contains code for
 // available_visualizers and related items, which are used to decode and visualize instructions.
 include!(concat!(env!("OUT_DIR"), "/generated_visualizers.rs"));
 
+pub const UNDECODED_INSTRUCTIONS_LABEL: &str = "Undecoded Instructions";
+
+/// An instruction whose visualizer failed, shown as an `Unknown` field in partial-parse mode
+#[derive(Debug)]
+pub struct FailedInstruction {
+    pub index: usize,
+    /// Length of the instruction data, which counts as undecoded in the coverage
+    pub data_len: usize,
+    pub error: VisualSignError,
+}
+
+/// The field of an instruction whose visualizer failed: its raw data, explained by the error.
+/// With `partial_parse` unset, the error is returned instead.
+pub(crate) fn degrade_failed_instruction(
+    result: Result<AnnotatedPayloadField, VisualSignError>,
+    index: usize,
+    instruction: &Instruction,
+    partial_parse: bool,
+    failed: &mut Vec<FailedInstruction>,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    match result {
+        Err(error) if partial_parse => {
+            let field = create_unknown_field(
+                &format!("Instruction {}", index + 1),
+                &instruction.data,
+                &format!(
+                    "Could not decode instruction for program {}: {error}",
+                    instruction.program_id
+                ),
+            )?;
+            failed.push(FailedInstruction {
+                index,
+                data_len: instruction.data.len(),
+                error,
+            });
+            Ok(field)
+        }
+        result => result,
+    }
+}
+
 /// Visualizes all the instructions and related fields in a transaction/message
+///
+/// With `partial_parse` set, an instruction whose visualizer fails is shown as an `Unknown`
+/// field and returned among the failed instructions instead of failing the whole decode.
 pub fn decode_instructions(
     transaction: &SolanaTransaction,
-) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
+    partial_parse: bool,
+) -> Result<(Vec<AnnotatedPayloadField>, Vec<FailedInstruction>), VisualSignError> {
     // TODO: add comment that available_visualizers is generated
     let visualizers: Vec<Box<dyn InstructionVisualizer>> = available_visualizers();
     let visualizers_refs: Vec<&dyn InstructionVisualizer> =
@@ -43,6 +93,7 @@ pub fn decode_instructions(
         })
         .collect();
 
+    let mut failed = Vec::new();
     let results: Result<Vec<AnnotatedPayloadField>, VisualSignError> = instructions
         .iter()
         .enumerate()
@@ -57,14 +108,21 @@ pub fn decode_instructions(
             let context = VisualizerContext::new(&sender, instruction_index, &instructions);
 
             // Try to visualize with available visualizers (including unknown_program fallback)
-            visualize_with_any(&visualizers_refs, &context)
+            let result = visualize_with_any(&visualizers_refs, &context)
                 .unwrap_or_else(|| {
                     panic!(
                         "No visualizer available for instruction {} at index {}",
                         instruction.program_id, instruction_index
                     )
                 })
-                .map(|viz_result| viz_result.field)
+                .map(|viz_result| viz_result.field);
+            degrade_failed_instruction(
+                result,
+                instruction_index,
+                instruction,
+                partial_parse,
+                &mut failed,
+            )
         })
         .collect();
 
@@ -79,7 +137,178 @@ pub fn decode_instructions(
         )));
     }
 
-    Ok(fields)
+    Ok((fields, failed))
+}
+
+/// Why an instruction is not decoded by a preset, see [`undecoded_instructions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndecodedReason {
+    /// No visualizer knows the program, so only the unknown program fallback shows it
+    UnknownProgram,
+    /// The program or one of the accounts comes from an address lookup table
+    LookupTable,
+    /// The visualizer of the program failed on it, see [`FailedInstruction`]
+    DecodeFailed,
+}
+
+/// An instruction not decoded by a preset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndecodedInstruction {
+    /// Index of the instruction in the message
+    pub index: usize,
+    /// None when the program comes from an address lookup table
+    pub program_id: Option<Pubkey>,
+    pub reason: UndecodedReason,
+}
+
+impl UndecodedInstruction {
+    /// e.g. "unknown program <program id>"
+    pub fn text(&self) -> String {
+        match (self.reason, self.program_id) {
+            (UndecodedReason::UnknownProgram, Some(program_id)) => {
+                format!("unknown program {program_id}")
+            }
+            (UndecodedReason::DecodeFailed, Some(program_id)) => {
+                format!("program {program_id} could not be decoded")
+            }
+            (_, Some(program_id)) => {
+                format!("program {program_id} with accounts from an address lookup table")
+            }
+            (_, None) => "program from an address lookup table".to_string(),
+        }
+    }
+}
+
+/// The instructions no visualizer decodes, i.e. that only the unknown program fallback shows
+/// raw, in message order. Instructions whose program or accounts come from a lookup table
+/// cannot be matched to a visualizer offline and are undecoded too.
+pub fn undecoded_instructions(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+) -> Vec<UndecodedInstruction> {
+    let visualizers: Vec<Box<dyn InstructionVisualizer>> = available_visualizers();
+    let sender = SolanaAccount {
+        account_key: account_keys
+            .first()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        signer: false,
+        writable: false,
+    };
+
+    let mut undecoded = Vec::new();
+    // Instructions whose program and accounts are all static keys, with their message index
+    let mut resolved = Vec::new();
+    let mut resolved_indexes = Vec::new();
+    for (index, ci) in instructions.iter().enumerate() {
+        let program_id = account_keys.get(ci.program_id_index as usize);
+        let accounts: Option<Vec<_>> = ci
+            .accounts
+            .iter()
+            .map(|&i| {
+                account_keys
+                    .get(i as usize)
+                    .map(|key| solana_sdk::instruction::AccountMeta::new_readonly(*key, false))
+            })
+            .collect();
+        match (program_id, accounts) {
+            (Some(program_id), Some(accounts)) => {
+                resolved.push(Instruction {
+                    program_id: *program_id,
+                    accounts,
+                    data: ci.data.clone(),
+                });
+                resolved_indexes.push(index);
+            }
+            (program_id, _) => undecoded.push(UndecodedInstruction {
+                index,
+                program_id: program_id.copied(),
+                reason: UndecodedReason::LookupTable,
+            }),
+        }
+    }
+
+    for (resolved_index, instruction) in resolved.iter().enumerate() {
+        let context = VisualizerContext::new(&sender, resolved_index, &resolved);
+        let handled_by_decoder = visualizers
+            .iter()
+            .find(|visualizer| visualizer.can_handle(&context))
+            .is_some_and(|visualizer| visualizer.kind().name() != "UnknownProgram");
+        if !handled_by_decoder {
+            undecoded.push(UndecodedInstruction {
+                index: resolved_indexes[resolved_index],
+                program_id: Some(instruction.program_id),
+                reason: UndecodedReason::UnknownProgram,
+            });
+        }
+    }
+    undecoded.sort_by_key(|instruction| instruction.index);
+    undecoded
+}
+
+/// Bytes of instruction data that no visualizer decodes, see [`undecoded_instructions`].
+pub fn undecoded_instruction_bytes(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+) -> usize {
+    undecoded_instructions(account_keys, instructions)
+        .iter()
+        .map(|undecoded| instructions[undecoded.index].data.len())
+        .sum()
+}
+
+/// The undecoded instructions of a message together with those whose visualizer failed,
+/// given by their index in the message, in message order.
+pub fn instruction_coverage_report(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    failed_indexes: &[usize],
+) -> Vec<UndecodedInstruction> {
+    let mut report = undecoded_instructions(account_keys, instructions);
+    // A V0 instruction with accounts from a lookup table is still visualized, and can fail
+    let failed: Vec<UndecodedInstruction> = failed_indexes
+        .iter()
+        .filter(|&&index| report.iter().all(|undecoded| undecoded.index != index))
+        .map(|&index| UndecodedInstruction {
+            index,
+            program_id: instructions
+                .get(index)
+                .and_then(|ci| account_keys.get(ci.program_id_index as usize))
+                .copied(),
+            reason: UndecodedReason::DecodeFailed,
+        })
+        .collect();
+    report.extend(failed);
+    report.sort_by_key(|instruction| instruction.index);
+    report
+}
+
+/// The "Undecoded Instructions" list, one entry per instruction of `report`, or None when
+/// every instruction was decoded. Keeps what the payload shows only as raw data from going
+/// unnoticed among the decoded instructions.
+pub fn create_undecoded_instructions_field(
+    report: &[UndecodedInstruction],
+) -> Result<Option<SignablePayloadField>, VisualSignError> {
+    if report.is_empty() {
+        return Ok(None);
+    }
+
+    let mut fields = Vec::with_capacity(report.len());
+    let mut summaries = Vec::with_capacity(report.len());
+    for instruction in report {
+        let label = format!("Instruction {}", instruction.index + 1);
+        let text = instruction.text();
+        summaries.push(format!("{label}: {text}"));
+        fields.push(create_text_field(&label, &text)?);
+    }
+
+    Ok(Some(SignablePayloadField::ListLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text: summaries.join("\n"),
+            label: UNDECODED_INSTRUCTIONS_LABEL.to_string(),
+        },
+        list_layout: SignablePayloadFieldListLayout { fields },
+    }))
 }
 
 pub fn decode_transfers(
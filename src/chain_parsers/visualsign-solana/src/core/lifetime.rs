@@ -0,0 +1,300 @@
+//! Transaction lifetime fields: recent blockhash versus durable nonce
+
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::CompiledInstruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use visualsign::errors::VisualSignError;
+use visualsign::expiration::{Expiration, create_expiration_field};
+use visualsign::field_builders::create_text_field;
+use visualsign::replay::{ReplayProtection, create_replay_protection_field};
+use visualsign::{
+    SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldListLayout,
+    SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+/// Account positions within an `AdvanceNonceAccount` instruction
+const NONCE_ACCOUNT_INDEX: usize = 0;
+const NONCE_AUTHORITY_INDEX: usize = 2;
+
+const UNRESOLVED_ACCOUNT: &str = "Unresolved (address lookup table)";
+
+/// How long a signed transaction stays valid for submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionLifetime {
+    /// Expires once the blockhash is too old to be accepted by the cluster
+    RecentBlockhash(Hash),
+    /// Stays valid until the nonce account is advanced; the message blockhash is the stored nonce
+    DurableNonce {
+        nonce_account: Option<Pubkey>,
+        nonce_authority: Option<Pubkey>,
+        nonce_value: Hash,
+    },
+}
+
+impl TransactionLifetime {
+    /// Determines the lifetime from the message contents.
+    ///
+    /// The runtime treats a transaction as durable-nonce when its first instruction is a
+    /// System program `AdvanceNonceAccount`; in that case the blockhash field holds the nonce.
+    pub fn from_message(
+        account_keys: &[Pubkey],
+        recent_blockhash: &Hash,
+        instructions: &[CompiledInstruction],
+    ) -> Self {
+        let advance_nonce = instructions.first().filter(|ix| {
+            account_keys.get(ix.program_id_index as usize) == Some(&system_program::id())
+                && matches!(
+                    bincode::deserialize::<SystemInstruction>(&ix.data),
+                    Ok(SystemInstruction::AdvanceNonceAccount)
+                )
+        });
+
+        match advance_nonce {
+            Some(ix) => {
+                // Accounts loaded through lookup tables cannot be resolved offline
+                let account = |index: usize| {
+                    ix.accounts
+                        .get(index)
+                        .and_then(|&key_index| account_keys.get(key_index as usize))
+                        .copied()
+                };
+                Self::DurableNonce {
+                    nonce_account: account(NONCE_ACCOUNT_INDEX),
+                    nonce_authority: account(NONCE_AUTHORITY_INDEX),
+                    nonce_value: *recent_blockhash,
+                }
+            }
+            None => Self::RecentBlockhash(*recent_blockhash),
+        }
+    }
+
+    pub fn is_durable_nonce(&self) -> bool {
+        matches!(self, Self::DurableNonce { .. })
+    }
+
+    /// When the transaction stops being valid; a blockhash cannot be dated offline, so its
+    /// expiry is described relative to the block that produced it
+    pub fn expiration(&self) -> Expiration {
+        match self {
+            Self::RecentBlockhash(_) => Expiration::Described(
+                "About 150 slots (roughly a minute) after the blockhash was produced".to_string(),
+            ),
+            Self::DurableNonce { .. } => Expiration::Described(
+                "Never; valid until the nonce account is advanced".to_string(),
+            ),
+        }
+    }
+
+    /// What keeps the transaction from being submitted twice: the blockhash or nonce value the
+    /// message was signed over
+    pub fn replay_protection(&self) -> ReplayProtection {
+        match self {
+            Self::RecentBlockhash(blockhash) => {
+                ReplayProtection::RecentBlockhash(blockhash.to_string())
+            }
+            Self::DurableNonce { nonce_value, .. } => {
+                ReplayProtection::DurableNonce(nonce_value.to_string())
+            }
+        }
+    }
+}
+
+/// Create the top-level "Transaction Lifetime" field
+/// Uses a preview layout because list layouts are not allowed at the top level
+pub fn create_transaction_lifetime_field(
+    lifetime: &TransactionLifetime,
+) -> Result<SignablePayloadField, VisualSignError> {
+    let expiration = create_expiration_field(&lifetime.expiration())?;
+    let replay_protection = create_replay_protection_field(&lifetime.replay_protection())?;
+    let (title, fallback_text, condensed_fields, expanded_fields) = match lifetime {
+        TransactionLifetime::RecentBlockhash(blockhash) => (
+            "Recent Blockhash",
+            format!("Recent Blockhash: {blockhash}"),
+            vec![create_text_field("Lifetime", "Recent Blockhash")?],
+            vec![
+                create_text_field("Lifetime", "Recent Blockhash")?,
+                create_text_field("Recent Blockhash", &blockhash.to_string())?,
+                expiration,
+                replay_protection,
+            ],
+        ),
+        TransactionLifetime::DurableNonce {
+            nonce_account,
+            nonce_authority,
+            nonce_value,
+        } => {
+            let nonce_account = format_account(nonce_account.as_ref());
+            (
+                "Durable Nonce",
+                format!("Durable Nonce: {nonce_account}"),
+                vec![
+                    create_text_field("Lifetime", "Durable Nonce")?,
+                    create_text_field("Nonce Account", &nonce_account)?,
+                    expiration.clone(),
+                ],
+                vec![
+                    create_text_field("Lifetime", "Durable Nonce")?,
+                    create_text_field("Nonce Account", &nonce_account)?,
+                    create_text_field(
+                        "Nonce Authority",
+                        &format_account(nonce_authority.as_ref()),
+                    )?,
+                    create_text_field("Nonce Value", &nonce_value.to_string())?,
+                    expiration,
+                    replay_protection,
+                ],
+            )
+        }
+    };
+
+    Ok(SignablePayloadField::PreviewLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text,
+            label: "Transaction Lifetime".to_string(),
+        },
+        preview_layout: SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: title.to_string(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: String::new(),
+            }),
+            condensed: Some(SignablePayloadFieldListLayout {
+                fields: condensed_fields,
+            }),
+            expanded: Some(SignablePayloadFieldListLayout {
+                fields: expanded_fields,
+            }),
+        },
+    })
+}
+
+fn format_account(account: Option<&Pubkey>) -> String {
+    account
+        .map(|key| key.to_string())
+        .unwrap_or_else(|| UNRESOLVED_ACCOUNT.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::message::Message;
+    use solana_sdk::system_instruction;
+
+    fn lifetime_of(message: &Message) -> TransactionLifetime {
+        TransactionLifetime::from_message(
+            &message.account_keys,
+            &message.recent_blockhash,
+            &message.instructions,
+        )
+    }
+
+    #[test]
+    fn test_recent_blockhash_lifetime() {
+        let payer = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let message = Message::new_with_blockhash(
+            &[system_instruction::transfer(
+                &payer,
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer),
+            &blockhash,
+        );
+
+        let lifetime = lifetime_of(&message);
+        assert_eq!(lifetime, TransactionLifetime::RecentBlockhash(blockhash));
+        assert!(!lifetime.is_durable_nonce());
+
+        let field = create_transaction_lifetime_field(&lifetime).unwrap();
+        assert_eq!(field.label(), "Transaction Lifetime");
+        assert_eq!(
+            field.fallback_text(),
+            &format!("Recent Blockhash: {blockhash}")
+        );
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        let expanded = preview_layout.expanded.unwrap().fields;
+        assert_eq!(
+            expanded
+                .last()
+                .unwrap()
+                .signable_payload_field
+                .fallback_text(),
+            &format!("Recent blockhash {blockhash}")
+        );
+    }
+
+    #[test]
+    fn test_durable_nonce_lifetime() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let nonce_authority = Pubkey::new_unique();
+        let nonce_value = Hash::new_unique();
+        let message = Message::new_with_nonce(
+            vec![system_instruction::transfer(
+                &payer,
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer),
+            &nonce_account,
+            &nonce_authority,
+        );
+        let message = Message {
+            recent_blockhash: nonce_value,
+            ..message
+        };
+
+        let lifetime = lifetime_of(&message);
+        assert_eq!(
+            lifetime,
+            TransactionLifetime::DurableNonce {
+                nonce_account: Some(nonce_account),
+                nonce_authority: Some(nonce_authority),
+                nonce_value,
+            }
+        );
+
+        let field = create_transaction_lifetime_field(&lifetime).unwrap();
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        assert_eq!(preview_layout.title.unwrap().text, "Durable Nonce");
+        let expanded = preview_layout.expanded.unwrap().fields;
+        let labels: Vec<&str> = expanded
+            .iter()
+            .map(|f| f.signable_payload_field.label().as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Lifetime",
+                "Nonce Account",
+                "Nonce Authority",
+                "Nonce Value",
+                "Expiration",
+                "Replay Protection"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_advance_nonce_must_be_first_instruction() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let message = Message::new(
+            &[
+                system_instruction::transfer(&payer, &Pubkey::new_unique(), 1),
+                system_instruction::advance_nonce_account(&nonce_account, &payer),
+            ],
+            Some(&payer),
+        );
+
+        assert!(!lifetime_of(&message).is_durable_nonce());
+    }
+}
@@ -7,11 +7,17 @@ use solana_sdk::instruction::Instruction;
 
 mod accounts;
 mod instructions;
+mod lifetime;
+mod offchain_message;
+mod token_authorities;
 mod txtypes;
 mod visualsign;
 
 pub use accounts::*;
 pub use instructions::*;
+pub use lifetime::*;
+pub use offchain_message::*;
+pub use token_authorities::*;
 pub use txtypes::*;
 pub use visualsign::*;
 
@@ -24,8 +30,26 @@ pub enum VisualizerKind {
     Lending(&'static str),
     /// Validator or pooled staking without liquid derivative tokens
     StakingPools(&'static str),
+    /// Liquid staking protocols that mint a derivative token for staked SOL
+    LiquidStaking(&'static str),
     /// Payment and simple transfer-related operations
     Payments(&'static str),
+    /// Cross-chain bridges that send tokens to another chain
+    Bridge(&'static str),
+}
+
+impl VisualizerKind {
+    /// The protocol or feature name carried by every kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dex(name)
+            | Self::Lending(name)
+            | Self::StakingPools(name)
+            | Self::LiquidStaking(name)
+            | Self::Payments(name)
+            | Self::Bridge(name) => name,
+        }
+    }
 }
 
 /// Context for visualizing a Solana instruction.
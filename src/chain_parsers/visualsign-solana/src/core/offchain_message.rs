@@ -0,0 +1,281 @@
+//! Solana off-chain messages, as signed by `solana sign-offchain-message` and hardware wallets
+//!
+//! The signed bytes are the signing domain `"\xffsolana offchain"`, a header version (0), the
+//! message format, the body length as a little-endian u16 and the body. No transaction message
+//! starts with `0xff`, so a signature over these bytes cannot be replayed as a transaction.
+
+use base64::{self, Engine};
+use visualsign::{
+    SignablePayload, SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2,
+    capabilities::ParserCapabilities,
+    encodings::SupportedEncodings,
+    message::{create_message_fields, message_length_cap},
+    parser_info::ParserInfo,
+    sender::{create_sender_field, resolve_sender},
+    vsptrait::{
+        Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
+        VisualSignError, VisualSignOptions,
+    },
+};
+
+pub const OFFCHAIN_SIGNING_DOMAIN: &[u8; 16] = b"\xffsolana offchain";
+
+// Signing domain, header version, format and length
+const HEADER_LEN: usize = OFFCHAIN_SIGNING_DOMAIN.len() + 1 + 1 + 2;
+/// Longest body a Limited UTF-8 message may have, so hardware wallets can show it whole
+pub const MAX_LIMITED_MESSAGE_LEN: usize = 1232 - HEADER_LEN;
+pub const MAX_EXTENDED_MESSAGE_LEN: usize = u16::MAX as usize - HEADER_LEN;
+
+/// How the body of an off-chain message is encoded, from the header's format byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffchainMessageFormat {
+    /// Printable ASCII only, up to [`MAX_LIMITED_MESSAGE_LEN`] bytes
+    RestrictedAscii,
+    /// UTF-8 up to [`MAX_LIMITED_MESSAGE_LEN`] bytes
+    LimitedUtf8,
+    /// UTF-8 up to [`MAX_EXTENDED_MESSAGE_LEN`] bytes
+    ExtendedUtf8,
+}
+
+impl OffchainMessageFormat {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::RestrictedAscii),
+            1 => Some(Self::LimitedUtf8),
+            2 => Some(Self::ExtendedUtf8),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RestrictedAscii => "Restricted ASCII",
+            Self::LimitedUtf8 => "Limited UTF-8",
+            Self::ExtendedUtf8 => "Extended UTF-8",
+        }
+    }
+}
+
+/// A decoded version 0 off-chain message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffchainMessage {
+    format: OffchainMessageFormat,
+    body: Vec<u8>,
+}
+
+impl OffchainMessage {
+    /// Decodes the signed bytes of an off-chain message, checking the body against its format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TransactionParseError> {
+        let invalid = TransactionParseError::InvalidFormat;
+        let rest = bytes
+            .strip_prefix(OFFCHAIN_SIGNING_DOMAIN.as_slice())
+            .ok_or_else(|| invalid("Missing the off-chain message signing domain".to_string()))?;
+        let [version, format, len_lo, len_hi, body @ ..] = rest else {
+            return Err(invalid("Truncated off-chain message header".to_string()));
+        };
+        if *version != 0 {
+            return Err(TransactionParseError::UnsupportedVersion(format!(
+                "off-chain message version {version}"
+            )));
+        }
+        let format = OffchainMessageFormat::from_byte(*format)
+            .ok_or_else(|| invalid(format!("Unknown off-chain message format {format}")))?;
+        let length = usize::from(u16::from_le_bytes([*len_lo, *len_hi]));
+        if body.len() != length {
+            return Err(invalid(format!(
+                "Off-chain message declares {length} bytes but has {}",
+                body.len()
+            )));
+        }
+        if body.is_empty() {
+            return Err(invalid("Empty off-chain message".to_string()));
+        }
+
+        let max_len = match format {
+            OffchainMessageFormat::RestrictedAscii | OffchainMessageFormat::LimitedUtf8 => {
+                MAX_LIMITED_MESSAGE_LEN
+            }
+            OffchainMessageFormat::ExtendedUtf8 => MAX_EXTENDED_MESSAGE_LEN,
+        };
+        if length > max_len {
+            return Err(invalid(format!(
+                "{} message is {length} bytes, over the {max_len} byte limit",
+                format.name()
+            )));
+        }
+        let well_formed = match format {
+            OffchainMessageFormat::RestrictedAscii => {
+                body.iter().all(|byte| (0x20..=0x7e).contains(byte))
+            }
+            OffchainMessageFormat::LimitedUtf8 | OffchainMessageFormat::ExtendedUtf8 => {
+                std::str::from_utf8(body).is_ok()
+            }
+        };
+        if !well_formed {
+            return Err(invalid(format!(
+                "Message body is not valid {}",
+                format.name()
+            )));
+        }
+
+        Ok(Self {
+            format,
+            body: body.to_vec(),
+        })
+    }
+
+    pub fn format(&self) -> OffchainMessageFormat {
+        self.format
+    }
+
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+impl Transaction for OffchainMessage {
+    fn from_string(data: &str) -> Result<Self, TransactionParseError> {
+        let bytes = match SupportedEncodings::detect(data) {
+            SupportedEncodings::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?,
+            SupportedEncodings::Hex => {
+                hex::decode(data).map_err(|e| TransactionParseError::DecodeError(e.to_string()))?
+            }
+        };
+        Self::from_bytes(&bytes)
+    }
+
+    fn transaction_type(&self) -> String {
+        "Solana (Off-chain Message)".to_string()
+    }
+}
+
+/// Converter for Solana off-chain messages
+pub struct SolanaOffchainMessageConverter;
+
+impl VisualSignConverter<OffchainMessage> for SolanaOffchainMessageConverter {
+    fn to_visual_sign_payload(
+        &self,
+        message: OffchainMessage,
+        options: VisualSignOptions,
+    ) -> Result<SignablePayload, VisualSignError> {
+        // A version 0 message does not name its signer, so a provided sender stays unverified
+        let sender = resolve_sender(None, options.sender.as_deref(), |derived, provided| {
+            derived == provided
+        })?;
+
+        let mut fields = create_message_fields(
+            message.body(),
+            options.charset,
+            message_length_cap(&options),
+        );
+        let format = message.format().name();
+        fields.push(SignablePayloadField::TextV2 {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format.to_string(),
+                label: "Message Format".to_string(),
+            },
+            text_v2: SignablePayloadFieldTextV2 {
+                text: format.to_string(),
+            },
+        });
+        if let Some(sender) = &sender {
+            fields.push(create_sender_field(sender));
+        }
+
+        let title = options
+            .transaction_name
+            .unwrap_or_else(|| "Sign Message".to_string());
+        Ok(SignablePayload::new(
+            0,
+            title,
+            None,
+            fields,
+            "SolanaMessage".to_string(),
+        ))
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        let formats = [
+            OffchainMessageFormat::RestrictedAscii,
+            OffchainMessageFormat::LimitedUtf8,
+            OffchainMessageFormat::ExtendedUtf8,
+        ]
+        .map(|format| format.name());
+        ParserCapabilities::new(&["hex", "base64"], &formats).with_feature("header_v0", true)
+    }
+}
+
+impl VisualSignConverterFromString<OffchainMessage> for SolanaOffchainMessageConverter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visualsign::message::{SIGNING_TYPE_LABEL, SIGNING_TYPE_MESSAGE};
+
+    fn encode(format: u8, body: &[u8]) -> Vec<u8> {
+        let mut bytes = OFFCHAIN_SIGNING_DOMAIN.to_vec();
+        bytes.push(0);
+        bytes.push(format);
+        bytes.extend_from_slice(&(body.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    #[test]
+    fn test_offchain_message_payload() {
+        let encoded = hex::encode(encode(0, b"Hello, Solana"));
+        let payload = SolanaOffchainMessageConverter
+            .to_visual_sign_payload_from_string(&encoded, VisualSignOptions::default())
+            .unwrap();
+        assert_eq!(payload.payload_type, "SolanaMessage");
+        let labelled: Vec<String> = payload
+            .fields
+            .iter()
+            .map(|field| format!("{}: {}", field.label(), field.fallback_text()))
+            .collect();
+        assert_eq!(
+            labelled,
+            vec![
+                format!("{SIGNING_TYPE_LABEL}: {SIGNING_TYPE_MESSAGE}"),
+                "Message: Hello, Solana".to_string(),
+                "Message Size: 13 bytes".to_string(),
+                "Message Format: Restricted ASCII".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_utf8_message_under_ascii_policy_is_hex() {
+        let message =
+            OffchainMessage::from_bytes(&encode(1, "Gr\u{fc}\u{df}e".as_bytes())).unwrap();
+        assert_eq!(message.format(), OffchainMessageFormat::LimitedUtf8);
+        let payload = SolanaOffchainMessageConverter
+            .to_validated_visual_sign_payload(message, VisualSignOptions::default())
+            .unwrap();
+        assert_eq!(payload.fields[1].label(), "Message (hex)");
+    }
+
+    #[test]
+    fn test_malformed_messages_are_rejected() {
+        let error = |bytes: &[u8]| OffchainMessage::from_bytes(bytes).unwrap_err().to_string();
+        assert!(error(b"solana offchain").contains("signing domain"));
+        assert!(error(&encode(0, b"caf\xc3\xa9")).contains("Restricted ASCII"));
+        assert!(error(&encode(2, b"\xff")).contains("Extended UTF-8"));
+        assert!(error(&encode(3, b"hi")).contains("format 3"));
+        assert!(error(&encode(1, &[b'a'; MAX_LIMITED_MESSAGE_LEN + 1])).contains("limit"));
+        assert!(error(&encode(0, b"")).contains("Empty"));
+
+        let mut truncated = encode(0, b"hello");
+        truncated.pop();
+        assert!(error(&truncated).contains("declares 5 bytes"));
+        let mut version = encode(0, b"hello");
+        version[16] = 1;
+        assert!(error(&version).contains("version 1"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Warnings for tokens whose mint keeps power over holders' accounts after a transfer.
+//!
+//! A mint's freeze authority can freeze any token account of the mint, and the permanent
+//! delegate extension of Token-2022 can transfer or burn tokens from any of them, so tokens
+//! received from such a mint can be frozen or clawed back later. Neither shows in the
+//! transaction: the host passes what it knows about each mint in the [`TokenMetadata`]
+//! extension, and the mints of the token account creations and checked transfers it lists
+//! with either get a warning annotation.
+//!
+//! Only instructions naming their mint are checked: a plain `Transfer` does not, and accounts
+//! loaded through lookup tables cannot be resolved offline.
+
+use solana_sdk::instruction::CompiledInstruction;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::instruction::TokenInstruction;
+use visualsign::errors::VisualSignError;
+use visualsign::extensions::{TokenInfo, TokenMetadata};
+use visualsign::field_builders::create_text_field;
+use visualsign::{
+    SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldListLayout,
+    SignablePayloadFieldStaticAnnotation,
+};
+
+pub const TOKEN_AUTHORITY_WARNING_LABEL: &str = "Token Authority Warnings";
+
+/// Position of the mint among the accounts of an associated token account `Create` or
+/// `CreateIdempotent`: funder, associated account, wallet, mint, ...
+const ATA_MINT_INDEX: usize = 3;
+/// Position of the mint among the accounts of the token `InitializeAccount*` and
+/// `TransferChecked` instructions
+const TOKEN_MINT_INDEX: usize = 1;
+
+/// The mints of the token account creations and checked transfers of a message, in the order
+/// they first appear.
+pub fn token_mints(account_keys: &[Pubkey], instructions: &[CompiledInstruction]) -> Vec<Pubkey> {
+    let mut mints = Vec::new();
+    for ci in instructions {
+        let mint_index = match account_keys.get(ci.program_id_index as usize) {
+            // An empty instruction is a `Create` from before the instruction enum
+            Some(program_id) if *program_id == spl_associated_token_account::id() => {
+                match ci.data.first() {
+                    None | Some(0 | 1) => Some(ATA_MINT_INDEX),
+                    _ => None,
+                }
+            }
+            Some(program_id)
+                if *program_id == spl_token::id() || *program_id == spl_token_2022::id() =>
+            {
+                match TokenInstruction::unpack(&ci.data) {
+                    Ok(
+                        TokenInstruction::InitializeAccount
+                        | TokenInstruction::InitializeAccount2 { .. }
+                        | TokenInstruction::InitializeAccount3 { .. }
+                        | TokenInstruction::TransferChecked { .. },
+                    ) => Some(TOKEN_MINT_INDEX),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let mint = mint_index
+            .and_then(|index| ci.accounts.get(index))
+            .and_then(|&key_index| account_keys.get(key_index as usize));
+        match mint {
+            Some(mint) if !mints.contains(mint) => mints.push(*mint),
+            _ => {}
+        }
+    }
+    mints
+}
+
+/// The warnings for the `mints` whose metadata names a freeze authority or a permanent
+/// delegate, or None when none does.
+///
+/// A list layout, so the annotations are kept at the top level of the payload.
+pub fn create_token_authority_warnings_field(
+    mints: &[Pubkey],
+    metadata: &TokenMetadata,
+) -> Result<Option<SignablePayloadField>, VisualSignError> {
+    let mut fields = Vec::new();
+    let mut summaries = Vec::new();
+    for mint in mints {
+        let Some(token) = find_mint(metadata, mint) else {
+            continue;
+        };
+        let name = format!("{} ({mint})", token.symbol);
+        let warnings = [
+            token.freeze_authority.as_ref().map(|authority| {
+                (
+                    "Freeze Authority",
+                    authority,
+                    format!(
+                        "Warning: {authority} can freeze {} token accounts, the receiving one included",
+                        token.symbol
+                    ),
+                )
+            }),
+            token.permanent_delegate.as_ref().map(|delegate| {
+                (
+                    "Permanent Delegate",
+                    delegate,
+                    format!(
+                        "Warning: {delegate} can transfer or burn {} from any holder, even after this transaction",
+                        token.symbol
+                    ),
+                )
+            }),
+        ];
+        for (label, authority, annotation) in warnings.into_iter().flatten() {
+            let mut field = create_text_field(label, &format!("{name}: {authority}"))?;
+            field.static_annotation = Some(SignablePayloadFieldStaticAnnotation {
+                text: annotation.clone(),
+            });
+            fields.push(field);
+            summaries.push(annotation);
+        }
+    }
+
+    if fields.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(SignablePayloadField::ListLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text: summaries.join("\n"),
+            label: TOKEN_AUTHORITY_WARNING_LABEL.to_string(),
+        },
+        list_layout: SignablePayloadFieldListLayout { fields },
+    }))
+}
+
+// Base58 is case-sensitive, so unlike `TokenMetadata::find` the mint must match exactly
+fn find_mint<'a>(metadata: &'a TokenMetadata, mint: &Pubkey) -> Option<&'a TokenInfo> {
+    let mint = mint.to_string();
+    metadata.0.iter().find(|token| token.address == mint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::message::Message;
+
+    fn token(mint: &Pubkey, freeze_authority: Option<&Pubkey>) -> TokenInfo {
+        TokenInfo {
+            address: mint.to_string(),
+            symbol: "FRZ".to_string(),
+            decimals: 6,
+            name: None,
+            freeze_authority: freeze_authority.map(ToString::to_string),
+            permanent_delegate: None,
+        }
+    }
+
+    #[test]
+    fn test_token_authority_warnings() {
+        let payer = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let create = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            &wallet,
+            &mint,
+            &spl_token::id(),
+        );
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let transfer = spl_token::instruction::transfer_checked(
+            &spl_token::id(),
+            &source,
+            &mint,
+            &destination,
+            &payer,
+            &[],
+            1_000_000,
+            6,
+        )
+        .unwrap();
+        let message = Message::new(&[create, transfer], Some(&payer));
+
+        let mints = token_mints(&message.account_keys, &message.instructions);
+        assert_eq!(mints, vec![mint]);
+
+        let metadata = TokenMetadata(vec![token(&mint, Some(&authority))]);
+        let field = create_token_authority_warnings_field(&mints, &metadata)
+            .unwrap()
+            .expect("the freeze authority is flagged");
+        assert_eq!(field.label(), TOKEN_AUTHORITY_WARNING_LABEL);
+        let SignablePayloadField::ListLayout { list_layout, .. } = &field else {
+            panic!("Expected ListLayout");
+        };
+        assert_eq!(list_layout.fields.len(), 1);
+        assert_eq!(
+            list_layout.fields[0].signable_payload_field.fallback_text(),
+            &format!("FRZ ({mint}): {authority}")
+        );
+        assert_eq!(
+            list_layout.fields[0]
+                .static_annotation
+                .as_ref()
+                .unwrap()
+                .text,
+            format!(
+                "Warning: {authority} can freeze FRZ token accounts, the receiving one included"
+            )
+        );
+
+        // Nothing to flag without authorities, or for mints the host does not know
+        let metadata = TokenMetadata(vec![token(&mint, None)]);
+        assert!(
+            create_token_authority_warnings_field(&mints, &metadata)
+                .unwrap()
+                .is_none()
+        );
+        let metadata = TokenMetadata(vec![token(&Pubkey::new_unique(), Some(&authority))]);
+        assert!(
+            create_token_authority_warnings_field(&mints, &metadata)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::core::{
-    InstructionVisualizer, SolanaAccount, VisualizerContext, available_visualizers,
-    visualize_with_any,
+    FailedInstruction, InstructionVisualizer, SolanaAccount, VisualizerContext,
+    available_visualizers, degrade_failed_instruction, visualize_with_any,
 };
 use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::transaction::VersionedTransaction;
@@ -111,9 +111,13 @@ pub fn decode_v0_transfers(
 
 /// Decode V0 transaction instructions using the visualizer framework
 /// This works for all V0 transactions, including those with lookup tables
+///
+/// `partial_parse` degrades failing instructions as [`crate::core::decode_instructions`] does;
+/// their indexes count only the instructions whose program is a static account key.
 pub fn decode_v0_instructions(
     v0_message: &solana_sdk::message::v0::Message,
-) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
+    partial_parse: bool,
+) -> Result<(Vec<AnnotatedPayloadField>, Vec<FailedInstruction>), VisualSignError> {
     // Get visualizers
     let visualizers: Vec<Box<dyn InstructionVisualizer>> = available_visualizers();
     let visualizers_refs: Vec<&dyn InstructionVisualizer> =
@@ -169,10 +173,11 @@ pub fn decode_v0_instructions(
         ));
     }
 
-    instructions
+    let mut failed = Vec::new();
+    let fields = instructions
         .iter()
         .enumerate()
-        .filter_map(|(instruction_index, _)| {
+        .filter_map(|(instruction_index, instruction)| {
             // Create sender account from first account key (typically the fee payer)
             let sender = SolanaAccount {
                 account_key: account_keys[0].to_string(),
@@ -184,9 +189,18 @@ pub fn decode_v0_instructions(
                 &visualizers_refs,
                 &VisualizerContext::new(&sender, instruction_index, &instructions),
             )
+            .map(|res| {
+                degrade_failed_instruction(
+                    res.map(|viz_result| viz_result.field),
+                    instruction_index,
+                    instruction,
+                    partial_parse,
+                    &mut failed,
+                )
+            })
         })
-        .map(|res| res.map(|viz_result| viz_result.field))
-        .collect()
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((fields, failed))
 }
 
 /// Create a rich address lookup table field with detailed information
@@ -2,7 +2,11 @@ use crate::core::txtypes::{
     create_address_lookup_table_field, decode_v0_instructions, decode_v0_transfers,
 };
 use crate::core::{
-    create_accounts_advanced_preview_layout, decode_accounts, decode_v0_accounts, instructions,
+    FailedInstruction, TransactionLifetime, available_visualizers,
+    create_accounts_advanced_preview_layout, create_token_authority_warnings_field,
+    create_transaction_lifetime_field, create_undecoded_instructions_field, decode_accounts,
+    decode_v0_accounts, instruction_coverage_report, instructions, token_mints,
+    undecoded_instruction_bytes,
 };
 use base64::{self, Engine};
 use solana_sdk::{
@@ -11,7 +15,16 @@ use solana_sdk::{
 };
 use visualsign::{
     SignablePayload, SignablePayloadField, SignablePayloadFieldCommon,
+    capabilities::ParserCapabilities,
+    coverage::Coverage,
     encodings::SupportedEncodings,
+    extensions::TokenMetadata,
+    lossless,
+    outcome::{ParseOutcome, ParseWarning, ParseWarningKind},
+    parser_info::ParserInfo,
+    provenance::{Provenance, ProvenanceSource},
+    sender::{create_sender_field, resolve_sender},
+    telemetry::record_command_count,
     vsptrait::{
         Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
         VisualSignError, VisualSignOptions,
@@ -27,17 +40,7 @@ pub enum SolanaTransactionWrapper {
 
 impl Transaction for SolanaTransactionWrapper {
     fn from_string(data: &str) -> Result<Self, TransactionParseError> {
-        // Detect if format is base64 or hex
-        let format = visualsign::encodings::SupportedEncodings::detect(data);
-
-        let bytes = match format {
-            SupportedEncodings::Base64 => base64::engine::general_purpose::STANDARD
-                .decode(data)
-                .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?,
-            SupportedEncodings::Hex => {
-                hex::decode(data).map_err(|e| TransactionParseError::DecodeError(e.to_string()))?
-            }
-        };
+        let bytes = decode_bytes(data)?;
 
         // First try to decode as a VersionedTransaction
         if let Ok(versioned_tx) = bincode::deserialize::<VersionedTransaction>(&bytes) {
@@ -50,6 +53,20 @@ impl Transaction for SolanaTransactionWrapper {
             .map(Self::Legacy)
     }
 
+    fn verify_lossless(&self, data: &str) -> Result<(), TransactionParseError> {
+        // bincode stops reading at the end of the transaction and ignores any bytes after it
+        let reencoded = match self {
+            Self::Legacy(tx) => bincode::serialize(tx),
+            Self::Versioned(tx) => bincode::serialize(tx),
+        }
+        .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?;
+        Ok(lossless::check_reencoding(
+            "Solana",
+            &decode_bytes(data)?,
+            &reencoded,
+        )?)
+    }
+
     fn transaction_type(&self) -> String {
         match self {
             Self::Legacy(_) => "Solana (Legacy)".to_string(),
@@ -61,6 +78,18 @@ impl Transaction for SolanaTransactionWrapper {
     }
 }
 
+// Detects whether `data` is base64 or hex and decodes it
+fn decode_bytes(data: &str) -> Result<Vec<u8>, TransactionParseError> {
+    match visualsign::encodings::SupportedEncodings::detect(data) {
+        SupportedEncodings::Base64 => base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| TransactionParseError::DecodeError(e.to_string())),
+        SupportedEncodings::Hex => {
+            hex::decode(data).map_err(|e| TransactionParseError::DecodeError(e.to_string()))
+        }
+    }
+}
+
 impl SolanaTransactionWrapper {
     pub fn new_legacy(transaction: SolanaTransaction) -> Self {
         Self::Legacy(transaction)
@@ -88,35 +117,201 @@ impl SolanaTransactionWrapper {
 /// Converter that knows how to format Solana transactions for VisualSign
 pub struct SolanaVisualSignConverter;
 
-impl VisualSignConverter<SolanaTransactionWrapper> for SolanaVisualSignConverter {
-    fn to_visual_sign_payload(
+impl SolanaVisualSignConverter {
+    // The payload, the provenance of its instruction fields, and the instructions shown as
+    // `Unknown` fields in partial-parse mode
+    fn convert(
         &self,
-        transaction_wrapper: SolanaTransactionWrapper,
+        transaction_wrapper: &SolanaTransactionWrapper,
         options: VisualSignOptions,
-    ) -> Result<SignablePayload, VisualSignError> {
-        match transaction_wrapper {
+    ) -> Result<(SignablePayload, Provenance, Vec<FailedInstruction>), VisualSignError> {
+        // The fee payer is the first account key and always signs
+        let fee_payer = match transaction_wrapper {
+            SolanaTransactionWrapper::Legacy(transaction) => {
+                transaction.message.account_keys.first().copied()
+            }
+            SolanaTransactionWrapper::Versioned(transaction) => {
+                transaction.message.static_account_keys().first().copied()
+            }
+        };
+        let sender = resolve_sender(
+            fee_payer.map(|key| key.to_string()).as_deref(),
+            options.sender.as_deref(),
+            |derived, provided| derived == provided,
+        )?;
+        let token_authority_warnings = match options.extensions.get::<TokenMetadata>() {
+            Some(metadata) => {
+                let mints = match transaction_wrapper {
+                    SolanaTransactionWrapper::Legacy(transaction) => token_mints(
+                        &transaction.message.account_keys,
+                        &transaction.message.instructions,
+                    ),
+                    SolanaTransactionWrapper::Versioned(transaction) => token_mints(
+                        transaction.message.static_account_keys(),
+                        transaction.message.instructions(),
+                    ),
+                };
+                create_token_authority_warnings_field(&mints, metadata)?
+            }
+            None => None,
+        };
+
+        let (mut payload, instruction_fields, failed) = match transaction_wrapper {
             SolanaTransactionWrapper::Legacy(transaction) => {
+                record_command_count(transaction.message.instructions.len());
                 // Convert the legacy transaction to a VisualSign payload
                 convert_to_visual_sign_payload(
-                    &transaction,
+                    transaction,
                     options.decode_transfers,
                     options.transaction_name,
+                    options.output.partial_parse,
                 )
             }
             SolanaTransactionWrapper::Versioned(versioned_tx) => {
+                record_command_count(versioned_tx.message.instructions().len());
                 // Handle versioned transactions
                 convert_versioned_to_visual_sign_payload(
-                    &versioned_tx,
+                    versioned_tx,
                     options.decode_transfers,
                     options.transaction_name,
+                    options.output.partial_parse,
                 )
             }
+        }?;
+        // Right after the network, ahead of the instructions
+        let mut shift = 0;
+        if let Some(sender) = &sender {
+            payload.fields.insert(1, create_sender_field(sender));
+            shift += 1;
+        }
+        if let Some(warnings) = token_authority_warnings {
+            payload.fields.insert(1 + shift, warnings);
+            shift += 1;
         }
+        let mut provenance = Provenance::new();
+        for (field, index) in instruction_fields {
+            provenance.record(
+                Provenance::field_path(field + shift),
+                ProvenanceSource::Instruction { index },
+            );
+        }
+        Ok((payload, provenance, failed))
+    }
+}
+
+impl VisualSignConverter<SolanaTransactionWrapper> for SolanaVisualSignConverter {
+    fn to_visual_sign_payload(
+        &self,
+        transaction_wrapper: SolanaTransactionWrapper,
+        options: VisualSignOptions,
+    ) -> Result<SignablePayload, VisualSignError> {
+        self.convert(&transaction_wrapper, options)
+            .map(|(payload, _, _)| payload)
+    }
+
+    fn to_visual_sign_outcome(
+        &self,
+        transaction_wrapper: SolanaTransactionWrapper,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
+        let mut warnings = lookup_table_warnings(&transaction_wrapper);
+        let include_provenance = options.output.include_provenance;
+        let (payload, provenance, failed) = self.convert(&transaction_wrapper, options)?;
+        let coverage = instruction_coverage(&transaction_wrapper, &failed)?;
+        warnings.extend(failed.iter().map(|instruction| {
+            ParseWarning::new(
+                ParseWarningKind::DecodeFailed,
+                format!(
+                    "instruction {} could not be decoded and is shown raw: {}",
+                    instruction.index, instruction.error
+                ),
+            )
+        }));
+        let outcome = ParseOutcome::with_warnings(payload, warnings).with_coverage(coverage);
+        Ok(if include_provenance {
+            outcome.with_provenance(provenance)
+        } else {
+            outcome
+        })
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        ParserCapabilities::new(&["hex", "base64"], &["Legacy", "V0"])
+            .with_decoders(
+                available_visualizers()
+                    .iter()
+                    .map(|visualizer| visualizer.kind().name()),
+            )
+            .with_feature("legacy", true)
+            .with_feature("v0", true)
+            // Lookup table accounts are shown as placeholders, see `lookup_table_warnings`
+            .with_feature("lookup_table_resolution", false)
+            .with_feature("lossless_verification", true)
     }
 }
 
 impl VisualSignConverterFromString<SolanaTransactionWrapper> for SolanaVisualSignConverter {}
 
+// Accounts loaded through lookup tables are shown as placeholders, since resolving them needs
+// on-chain state
+fn lookup_table_warnings(transaction_wrapper: &SolanaTransactionWrapper) -> Vec<ParseWarning> {
+    let Some(VersionedMessage::V0(message)) = transaction_wrapper
+        .inner_versioned()
+        .map(|transaction| &transaction.message)
+    else {
+        return Vec::new();
+    };
+    message
+        .address_table_lookups
+        .iter()
+        .map(|lookup| {
+            ParseWarning::new(
+                ParseWarningKind::UnresolvedLookup,
+                format!(
+                    "{} account(s) from address lookup table {} cannot be resolved offline",
+                    lookup.writable_indexes.len() + lookup.readonly_indexes.len(),
+                    lookup.account_key
+                ),
+            )
+        })
+        .collect()
+}
+
+// Instruction data that only the unknown program fallback shows, or that a failing visualizer
+// left raw in partial-parse mode, is the undecoded part of the serialized transaction
+fn instruction_coverage(
+    transaction_wrapper: &SolanaTransactionWrapper,
+    failed: &[FailedInstruction],
+) -> Result<Coverage, VisualSignError> {
+    let (serialized, undecoded) = match transaction_wrapper {
+        SolanaTransactionWrapper::Legacy(transaction) => (
+            bincode::serialize(transaction),
+            undecoded_instruction_bytes(
+                &transaction.message.account_keys,
+                &transaction.message.instructions,
+            ),
+        ),
+        SolanaTransactionWrapper::Versioned(transaction) => (
+            bincode::serialize(transaction),
+            undecoded_instruction_bytes(
+                transaction.message.static_account_keys(),
+                transaction.message.instructions(),
+            ),
+        ),
+    };
+    let total = serialized
+        .map_err(|e| {
+            VisualSignError::ParseError(TransactionParseError::DecodeError(e.to_string()))
+        })?
+        .len();
+    let failed_bytes: usize = failed.iter().map(|instruction| instruction.data_len).sum();
+    Ok(Coverage::new(total, undecoded + failed_bytes))
+}
+
 /// Public API function for ease of use with legacy transactions
 pub fn transaction_to_visual_sign(
     transaction: SolanaTransaction,
@@ -145,12 +340,17 @@ pub fn transaction_string_to_visual_sign(
     SolanaVisualSignConverter.to_visual_sign_payload_from_string(transaction_data, options)
 }
 
+// The payload with the index of every instruction field and of the instruction it shows, and
+// the instructions shown as `Unknown` fields
+type ConvertedPayload = (SignablePayload, Vec<(usize, usize)>, Vec<FailedInstruction>);
+
 /// Convert Solana transaction to visual sign payload
 fn convert_to_visual_sign_payload(
     transaction: &SolanaTransaction,
     decode_transfers: bool,
     title: Option<String>,
-) -> Result<SignablePayload, VisualSignError> {
+    partial_parse: bool,
+) -> Result<ConvertedPayload, VisualSignError> {
     let message = &transaction.message;
 
     let mut fields = vec![SignablePayloadField::TextV2 {
@@ -172,12 +372,31 @@ fn convert_to_visual_sign_payload(
         );
     }
 
-    // Process instructions with visualizers
-    fields.extend(
-        instructions::decode_instructions(transaction)?
-            .iter()
-            .map(|e| e.signable_payload_field.clone()),
+    // Process instructions with visualizers, one field per instruction
+    let (decoded, failed) = instructions::decode_instructions(transaction, partial_parse)?;
+    let instruction_fields = (0..decoded.len())
+        .map(|index| (fields.len() + index, index))
+        .collect();
+    fields.extend(decoded.iter().map(|e| e.signable_payload_field.clone()));
+
+    // List the instructions no preset decoded, so none is only shown as raw data unnoticed
+    let failed_indexes: Vec<usize> = failed.iter().map(|instruction| instruction.index).collect();
+    let report = instruction_coverage_report(
+        &message.account_keys,
+        &message.instructions,
+        &failed_indexes,
     );
+    if let Some(field) = create_undecoded_instructions_field(&report)? {
+        fields.push(field);
+    }
+
+    // Show whether the transaction expires with its blockhash or relies on a durable nonce
+    let lifetime = TransactionLifetime::from_message(
+        &message.account_keys,
+        &message.recent_blockhash,
+        &message.instructions,
+    );
+    fields.push(create_transaction_lifetime_field(&lifetime)?);
 
     // Decode and sort accounts using the dedicated function
     let accounts = decode_accounts(message)?;
@@ -187,12 +406,16 @@ fn convert_to_visual_sign_payload(
     // Add Accounts field at the bottom using PreviewLayout instead of ListLayout
     fields.push(preview_layout_advanced);
 
-    Ok(SignablePayload::new(
-        0,
-        title.unwrap_or_else(|| "Solana Transaction".to_string()),
-        None,
-        fields,
-        "SolanaTx".to_string(),
+    Ok((
+        SignablePayload::new(
+            0,
+            title.unwrap_or_else(|| "Solana Transaction".to_string()),
+            None,
+            fields,
+            "SolanaTx".to_string(),
+        ),
+        instruction_fields,
+        failed,
     ))
 }
 
@@ -201,7 +424,8 @@ fn convert_versioned_to_visual_sign_payload(
     versioned_tx: &VersionedTransaction,
     decode_transfers: bool,
     title: Option<String>,
-) -> Result<SignablePayload, VisualSignError> {
+    partial_parse: bool,
+) -> Result<ConvertedPayload, VisualSignError> {
     match &versioned_tx.message {
         VersionedMessage::Legacy(legacy_message) => {
             // For legacy messages in versioned transactions, create a legacy transaction
@@ -209,11 +433,17 @@ fn convert_versioned_to_visual_sign_payload(
                 signatures: versioned_tx.signatures.clone(),
                 message: legacy_message.clone(),
             };
-            convert_to_visual_sign_payload(&legacy_tx, decode_transfers, title)
+            convert_to_visual_sign_payload(&legacy_tx, decode_transfers, title, partial_parse)
         }
         VersionedMessage::V0(v0_message) => {
             // Handle V0 transactions - try to use the same instruction processing pipeline
-            convert_v0_to_visual_sign_payload(versioned_tx, v0_message, decode_transfers, title)
+            convert_v0_to_visual_sign_payload(
+                versioned_tx,
+                v0_message,
+                decode_transfers,
+                title,
+                partial_parse,
+            )
         }
     }
 }
@@ -224,7 +454,8 @@ fn convert_v0_to_visual_sign_payload(
     v0_message: &solana_sdk::message::v0::Message,
     decode_transfers: bool,
     title: Option<String>,
-) -> Result<SignablePayload, VisualSignError> {
+    partial_parse: bool,
+) -> Result<ConvertedPayload, VisualSignError> {
     // Decode and sort accounts using the dedicated function
     let accounts = decode_v0_accounts(v0_message)?;
 
@@ -246,9 +477,19 @@ fn convert_v0_to_visual_sign_payload(
 
     // Directly process V0 instructions using the visualizer framework
     // This approach works for all V0 transactions, including those with lookup tables
-    match decode_v0_instructions(v0_message) {
-        Ok(instruction_fields) => {
-            for (index, instruction_field) in instruction_fields.iter().enumerate() {
+    let mut instruction_fields = Vec::new();
+    let mut failed = Vec::new();
+    match decode_v0_instructions(v0_message, partial_parse) {
+        Ok((decoded, failed_instructions)) => {
+            failed = failed_instructions;
+            // Instructions whose program comes from a lookup table are skipped, so fields only
+            // line up with instructions when none was
+            if decoded.len() == v0_message.instructions.len() {
+                instruction_fields = (0..decoded.len())
+                    .map(|index| (fields.len() + index, index))
+                    .collect();
+            }
+            for (index, instruction_field) in decoded.iter().enumerate() {
                 tracing::debug!(
                     "Handling instruction {} with visualizer {:?}",
                     index,
@@ -271,6 +512,28 @@ fn convert_v0_to_visual_sign_payload(
         }
     }
 
+    // List the instructions no preset decoded. Failed instructions are indexed among those
+    // whose program is a static account key, see `decode_v0_instructions`
+    let static_program_indexes: Vec<usize> = v0_message
+        .instructions
+        .iter()
+        .enumerate()
+        .filter(|(_, ci)| (ci.program_id_index as usize) < v0_message.account_keys.len())
+        .map(|(index, _)| index)
+        .collect();
+    let failed_indexes: Vec<usize> = failed
+        .iter()
+        .filter_map(|instruction| static_program_indexes.get(instruction.index).copied())
+        .collect();
+    let report = instruction_coverage_report(
+        &v0_message.account_keys,
+        &v0_message.instructions,
+        &failed_indexes,
+    );
+    if let Some(field) = create_undecoded_instructions_field(&report)? {
+        fields.push(field);
+    }
+
     // Process V0 transfer decoding using solana-parser
     if decode_transfers {
         match decode_v0_transfers(versioned_tx) {
@@ -296,16 +559,28 @@ fn convert_v0_to_visual_sign_payload(
         }
     }
 
+    // Show whether the transaction expires with its blockhash or relies on a durable nonce
+    let lifetime = TransactionLifetime::from_message(
+        &v0_message.account_keys,
+        &v0_message.recent_blockhash,
+        &v0_message.instructions,
+    );
+    fields.push(create_transaction_lifetime_field(&lifetime)?);
+
     // Add Accounts field at the bottom using PreviewLayout instead of ListLayout
     let preview_layout_advanced = create_accounts_advanced_preview_layout("Accounts", &accounts)?;
     fields.push(preview_layout_advanced);
 
-    Ok(SignablePayload::new(
-        0,
-        title.unwrap_or_else(|| "Solana V0 Transaction".to_string()),
-        None,
-        fields,
-        "SolanaTx".to_string(),
+    Ok((
+        SignablePayload::new(
+            0,
+            title.unwrap_or_else(|| "Solana V0 Transaction".to_string()),
+            None,
+            fields,
+            "SolanaTx".to_string(),
+        ),
+        instruction_fields,
+        failed,
     ))
 }
 
@@ -314,6 +589,7 @@ mod tests {
     use super::*;
     use crate::test_utils::payload_from_b64;
     use crate::utils::create_transaction_with_empty_signatures;
+    use visualsign::vsptrait::OutputFlags;
 
     #[test]
     fn test_solana_transaction_to_vsp() {
@@ -340,6 +616,268 @@ mod tests {
         assert!(json_result.is_ok());
     }
 
+    #[test]
+    fn test_capabilities_list_generated_visualizers() {
+        let capabilities = SolanaVisualSignConverter.capabilities();
+        assert_eq!(capabilities.encodings, vec!["hex", "base64"]);
+        for decoder in ["Jupiter", "Marinade", "Stake", "System", "Token2022"] {
+            assert!(
+                capabilities.decoders.iter().any(|name| name == decoder),
+                "{decoder}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fee_payer_is_shown_as_sender() {
+        let solana_transfer_message = "AgABA3Lgs31rdjnEG5FRyrm2uAi4f+erGdyJl0UtJyMMLGzC9wF+t3qhmhpj3vI369n5Ef5xRLms/Vn8J/Lc7bmoIkAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMBafBISARibJ+I25KpHkjLe53ZrqQcLWGy8n97yWD7mAQICAQAMAgAAAADKmjsAAAAA";
+        let transaction = create_transaction_with_empty_signatures(solana_transfer_message);
+        let fee_payer = "8jSCrV9xWkmMRSyf6xH3phL7SretagdqP3LRqkUYUp73";
+
+        let payload = payload_from_b64(&transaction);
+        assert_eq!(payload.fields[1].label(), "From");
+        assert_eq!(payload.fields[1].fallback_text(), fee_payer);
+
+        let options = |sender: &str| VisualSignOptions {
+            sender: Some(sender.to_string()),
+            ..Default::default()
+        };
+        assert!(
+            transaction_string_to_visual_sign(&transaction, options(fee_payer)).is_ok(),
+            "a matching sender is accepted"
+        );
+        let error = transaction_string_to_visual_sign(
+            &transaction,
+            options("HdD2N8HDzNEM6vwAq5mBLiUbgy1P9wyJfbASt93ndDsD"),
+        )
+        .unwrap_err();
+        assert!(matches!(error, VisualSignError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_instruction_provenance() {
+        let solana_transfer_message = "AgABA3Lgs31rdjnEG5FRyrm2uAi4f+erGdyJl0UtJyMMLGzC9wF+t3qhmhpj3vI369n5Ef5xRLms/Vn8J/Lc7bmoIkAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMBafBISARibJ+I25KpHkjLe53ZrqQcLWGy8n97yWD7mAQICAQAMAgAAAADKmjsAAAAA";
+        let transaction = create_transaction_with_empty_signatures(solana_transfer_message);
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                include_provenance: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let outcome = SolanaVisualSignConverter
+            .to_visual_sign_outcome_from_string(&transaction, options)
+            .unwrap();
+
+        // Network and the fee payer come first, then the one transfer instruction
+        assert_eq!(outcome.provenance.len(), 1);
+        assert_eq!(
+            outcome.provenance.get("Fields[2]"),
+            Some(&ProvenanceSource::Instruction { index: 0 })
+        );
+        let raw = base64::engine::general_purpose::STANDARD
+            .decode(&transaction)
+            .unwrap();
+        assert!(outcome.provenance.verify(&outcome.payload, &raw).is_ok());
+    }
+
+    #[test]
+    fn test_instruction_coverage() {
+        use solana_sdk::instruction::Instruction;
+        use solana_sdk::message::Message;
+        use solana_sdk::pubkey::Pubkey;
+
+        let solana_transfer_message = "AgABA3Lgs31rdjnEG5FRyrm2uAi4f+erGdyJl0UtJyMMLGzC9wF+t3qhmhpj3vI369n5Ef5xRLms/Vn8J/Lc7bmoIkAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMBafBISARibJ+I25KpHkjLe53ZrqQcLWGy8n97yWD7mAQICAQAMAgAAAADKmjsAAAAA";
+        let transaction = create_transaction_with_empty_signatures(solana_transfer_message);
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                include_coverage: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let outcome = SolanaVisualSignConverter
+            .to_visual_sign_outcome_from_string(&transaction, options)
+            .unwrap();
+        let raw = base64::engine::general_purpose::STANDARD
+            .decode(&transaction)
+            .unwrap();
+        assert_eq!(outcome.coverage, Some(Coverage::full(raw.len())));
+        assert_eq!(outcome.payload.coverage, outcome.coverage);
+
+        // Data for a program without a visualizer is only shown raw
+        let payer = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3, 4], vec![]);
+        let unknown = SolanaTransaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+        let total = bincode::serialize(&unknown).unwrap().len();
+        assert_eq!(
+            instruction_coverage(&SolanaTransactionWrapper::new_legacy(unknown), &[]).unwrap(),
+            Coverage::new(total, 4)
+        );
+    }
+
+    #[test]
+    fn test_partial_parse_degrades_failed_instructions() {
+        use solana_sdk::instruction::Instruction;
+        use solana_sdk::message::Message;
+        use solana_sdk::pubkey::Pubkey;
+        use solana_sdk::system_instruction;
+
+        // A system program instruction whose data is not a system instruction, next to a
+        // transfer that decodes
+        let payer = Pubkey::new_unique();
+        let invalid = Instruction::new_with_bytes(
+            solana_sdk::system_program::ID,
+            &[0xff, 0xff, 0xff, 0xff],
+            vec![],
+        );
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let transaction =
+            SolanaTransaction::new_unsigned(Message::new(&[invalid, transfer], Some(&payer)));
+        let total = bincode::serialize(&transaction).unwrap().len();
+
+        let strict = SolanaVisualSignConverter.to_visual_sign_outcome(
+            SolanaTransactionWrapper::new_legacy(transaction.clone()),
+            VisualSignOptions::default(),
+        );
+        assert!(strict.is_err());
+
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                partial_parse: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let outcome = SolanaVisualSignConverter
+            .to_visual_sign_outcome(SolanaTransactionWrapper::new_legacy(transaction), options)
+            .unwrap();
+        let unknown = outcome
+            .payload
+            .fields
+            .iter()
+            .find(|field| field.label() == "Instruction 1")
+            .expect("the failed instruction is shown as an Unknown field");
+        match unknown {
+            SignablePayloadField::Unknown { unknown, .. } => {
+                assert_eq!(unknown.data, "0xffffffff");
+                assert!(unknown.explanation.contains("system instruction"));
+            }
+            other => panic!("expected an Unknown field, got {other:?}"),
+        }
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(outcome.warnings[0].kind, ParseWarningKind::DecodeFailed);
+        assert_eq!(outcome.coverage, Some(Coverage::new(total, 4)));
+    }
+
+    #[test]
+    fn test_undecoded_instructions_are_listed() {
+        use crate::core::UNDECODED_INSTRUCTIONS_LABEL;
+        use solana_sdk::instruction::Instruction;
+        use solana_sdk::message::Message;
+        use solana_sdk::pubkey::Pubkey;
+        use solana_sdk::system_instruction;
+
+        // A failing system instruction, an instruction for a program without a visualizer and
+        // a transfer that decodes
+        let payer = Pubkey::new_unique();
+        let unknown_program = Pubkey::new_unique();
+        let invalid = Instruction::new_with_bytes(
+            solana_sdk::system_program::ID,
+            &[0xff, 0xff, 0xff, 0xff],
+            vec![],
+        );
+        let unknown = Instruction::new_with_bytes(unknown_program, &[1, 2, 3, 4], vec![]);
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let transaction = SolanaTransaction::new_unsigned(Message::new(
+            &[invalid, unknown, transfer.clone()],
+            Some(&payer),
+        ));
+
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                partial_parse: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let payload = SolanaVisualSignConverter
+            .to_visual_sign_payload(SolanaTransactionWrapper::new_legacy(transaction), options)
+            .unwrap();
+        let report = payload
+            .fields
+            .iter()
+            .find(|field| field.label() == UNDECODED_INSTRUCTIONS_LABEL)
+            .expect("undecoded instructions are listed");
+        assert_eq!(
+            report.fallback_text(),
+            &format!(
+                "Instruction 1: program {} could not be decoded\nInstruction 2: unknown program {unknown_program}",
+                solana_sdk::system_program::ID
+            )
+        );
+        let SignablePayloadField::ListLayout { list_layout, .. } = report else {
+            panic!("Expected ListLayout");
+        };
+        assert_eq!(list_layout.fields.len(), 2);
+
+        // Nothing to list when every instruction is decoded
+        let decoded = SolanaTransaction::new_unsigned(Message::new(&[transfer], Some(&payer)));
+        let payload = SolanaVisualSignConverter
+            .to_visual_sign_payload(
+                SolanaTransactionWrapper::new_legacy(decoded),
+                VisualSignOptions::default(),
+            )
+            .unwrap();
+        assert!(
+            payload
+                .fields
+                .iter()
+                .all(|field| field.label() != UNDECODED_INSTRUCTIONS_LABEL)
+        );
+    }
+
+    #[test]
+    fn test_verify_lossless_rejects_trailing_bytes() {
+        let solana_transfer_message = "AgABA3Lgs31rdjnEG5FRyrm2uAi4f+erGdyJl0UtJyMMLGzC9wF+t3qhmhpj3vI369n5Ef5xRLms/Vn8J/Lc7bmoIkAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMBafBISARibJ+I25KpHkjLe53ZrqQcLWGy8n97yWD7mAQICAQAMAgAAAADKmjsAAAAA";
+        let transaction = create_transaction_with_empty_signatures(solana_transfer_message);
+        let mut bytes = base64::engine::general_purpose::STANDARD
+            .decode(&transaction)
+            .unwrap();
+        let length = bytes.len();
+        bytes.extend_from_slice(&[0xde, 0xad]);
+        let padded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+
+        // bincode decodes the transaction and ignores what follows it
+        let options = VisualSignOptions::default();
+        assert!(
+            SolanaVisualSignConverter
+                .to_visual_sign_payload_from_string(&padded, options.clone())
+                .is_ok()
+        );
+
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                verify_lossless: true,
+                ..Default::default()
+            },
+            ..options
+        };
+        assert!(
+            SolanaVisualSignConverter
+                .to_visual_sign_payload_from_string(&transaction, options.clone())
+                .is_ok()
+        );
+        let error = SolanaVisualSignConverter
+            .to_visual_sign_payload_from_string(&padded, options)
+            .unwrap_err();
+        let VisualSignError::ParseError(TransactionParseError::Parser(detail)) = error else {
+            panic!("Expected a re-encoding mismatch, got {error:?}");
+        };
+        assert_eq!(detail.reason, "ReencodingMismatch");
+        assert_eq!(detail.offset, Some(length));
+    }
+
     #[test]
     fn test_solana_transaction_trait() {
         let solana_transfer_message = "AgABA3Lgs31rdjnEG5FRyrm2uAi4f+erGdyJl0UtJyMMLGzC9wF+t3qhmhpj3vI369n5Ef5xRLms/Vn8J/Lc7bmoIkAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMBafBISARibJ+I25KpHkjLe53ZrqQcLWGy8n97yWD7mAQICAQAMAgAAAADKmjsAAAAA";
@@ -369,9 +907,9 @@ mod tests {
         let payload_result = SolanaVisualSignConverter.to_visual_sign_payload(
             solana_tx,
             VisualSignOptions {
-                metadata: None,
                 decode_transfers: true,
                 transaction_name: Some("Solana Transaction".to_string()),
+                ..Default::default()
             },
         );
 
@@ -451,9 +989,9 @@ mod tests {
         let payload_result = SolanaVisualSignConverter.to_visual_sign_payload(
             solana_tx,
             VisualSignOptions {
-                metadata: None,
                 decode_transfers: true,
                 transaction_name: Some("V0 Transaction".to_string()),
+                ..Default::default()
             },
         );
 
@@ -565,6 +1103,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_lookup_table_warnings() {
+        use solana_sdk::message::v0::MessageAddressTableLookup;
+        use solana_sdk::pubkey::Pubkey;
+
+        let mut v0_message = solana_sdk::message::v0::Message::default();
+        v0_message.address_table_lookups = vec![MessageAddressTableLookup {
+            account_key: Pubkey::new_unique(),
+            writable_indexes: vec![0, 1],
+            readonly_indexes: vec![2],
+        }];
+        let versioned = SolanaTransactionWrapper::new_versioned(VersionedTransaction {
+            signatures: vec![],
+            message: VersionedMessage::V0(v0_message),
+        });
+
+        let warnings = lookup_table_warnings(&versioned);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ParseWarningKind::UnresolvedLookup);
+        assert!(
+            warnings[0]
+                .message
+                .starts_with("3 account(s) from address lookup table")
+        );
+
+        let legacy = SolanaTransactionWrapper::new_legacy(SolanaTransaction::default());
+        assert!(lookup_table_warnings(&legacy).is_empty());
+    }
+
     #[test]
     fn test_v0_transfer_decoding() {
         // Test the V0 transfer decoding function directly
@@ -617,9 +1184,9 @@ mod tests {
         let legacy_payload_result = SolanaVisualSignConverter.to_visual_sign_payload(
             legacy_tx,
             VisualSignOptions {
-                metadata: None,
                 decode_transfers: true,
                 transaction_name: Some("Legacy Transfer Test".to_string()),
+                ..Default::default()
             },
         );
 
@@ -660,9 +1227,9 @@ mod tests {
         let v0_payload_result = SolanaVisualSignConverter.to_visual_sign_payload(
             v0_tx,
             VisualSignOptions {
-                metadata: None,
                 decode_transfers: true,
                 transaction_name: Some("V0 Transfer Test".to_string()),
+                ..Default::default()
             },
         );
 
@@ -786,9 +1353,9 @@ mod tests {
                 let payload_result = SolanaVisualSignConverter.to_visual_sign_payload(
                     wrapper,
                     VisualSignOptions {
-                        metadata: None,
                         decode_transfers: true,
                         transaction_name: Some("Manual V0 Transfer Test".to_string()),
+                        ..Default::default()
                     },
                 );
 
@@ -936,9 +1503,9 @@ mod tests {
         let payload_result = SolanaVisualSignConverter.to_visual_sign_payload(
             tx,
             VisualSignOptions {
-                metadata: None,
                 decode_transfers: true,
                 transaction_name: Some("TokenKeg Test".to_string()),
+                ..Default::default()
             },
         );
 
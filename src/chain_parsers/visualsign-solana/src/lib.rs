@@ -29,9 +29,9 @@ mod tests {
                 .to_visual_sign_payload(
                     transaction_wrapper,
                     VisualSignOptions {
-                        metadata: None,
                         decode_transfers: true,
                         transaction_name: Some(description.to_string()),
+                        ..Default::default()
                     },
                 )
                 .unwrap_or_else(|e| panic!("Failed to convert {description} to payload: {e:?}"));
@@ -85,9 +85,9 @@ mod tests {
             .to_visual_sign_payload(
                 transaction_wrapper,
                 VisualSignOptions {
-                    metadata: None,
                     decode_transfers: true,
                     transaction_name: Some("Unicode Escape Test".to_string()),
+                    ..Default::default()
                 },
             )
             .expect("Should convert to payload successfully");
@@ -0,0 +1,26 @@
+use crate::core::{SolanaIntegrationConfig, SolanaIntegrationConfigData};
+use std::collections::HashMap;
+
+pub const CCTP_TOKEN_MESSENGER_PROGRAM_ID: &str = "CCTPiPYPc6AsJuwueEnWgSgucamXDZwBd53dQ11YiKX3";
+
+pub struct CctpTokenMessengerConfig;
+
+impl SolanaIntegrationConfig for CctpTokenMessengerConfig {
+    fn new() -> Self {
+        Self
+    }
+
+    fn data(&self) -> &SolanaIntegrationConfigData {
+        static DATA: std::sync::OnceLock<SolanaIntegrationConfigData> = std::sync::OnceLock::new();
+        DATA.get_or_init(|| {
+            let mut programs = HashMap::new();
+            let mut token_messenger_instructions = HashMap::new();
+            token_messenger_instructions.insert("*", vec!["*"]);
+            programs.insert(
+                CCTP_TOKEN_MESSENGER_PROGRAM_ID,
+                token_messenger_instructions,
+            );
+            SolanaIntegrationConfigData { programs }
+        })
+    }
+}
@@ -0,0 +1,309 @@
+//! Circle CCTP TokenMessengerMinter preset
+//!
+//! Burns for a transfer out of Solana are decoded into the token, amount, destination domain and
+//! mint recipient. Other instructions (receiving transfers and administration) are shown by
+//! their Anchor discriminator.
+
+mod config;
+
+use crate::core::{
+    InstructionVisualizer, SolanaIntegrationConfig, VisualizerContext, VisualizerKind,
+};
+use crate::utils::{RemoteAddressFormat, account_at, format_remote_address, get_token_info};
+use borsh::de::BorshDeserialize;
+use config::CctpTokenMessengerConfig;
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::{create_raw_data_field, create_text_field};
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+// Anchor discriminators: sha256("global:<instruction name>")[..8]
+const DEPOSIT_FOR_BURN_DISCRIMINATOR: [u8; 8] = [0xd7, 0x3c, 0x3d, 0x2e, 0x72, 0x37, 0x80, 0xb0];
+const DEPOSIT_FOR_BURN_WITH_CALLER_DISCRIMINATOR: [u8; 8] =
+    [0xa7, 0xde, 0x13, 0x72, 0x55, 0x15, 0x0e, 0x76];
+
+// Account positions in the deposit instructions
+const DEPOSIT_BURN_TOKEN_ACCOUNT_INDEX: usize = 3;
+const DEPOSIT_BURN_TOKEN_MINT_INDEX: usize = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CctpTokenMessengerInstruction {
+    DepositForBurn {
+        amount: u64,
+        destination_domain: u32,
+        mint_recipient: [u8; 32],
+        /// Only this caller can receive the message on the destination domain
+        destination_caller: Option<[u8; 32]>,
+    },
+    Other {
+        discriminator: [u8; 8],
+    },
+}
+
+// Create a static instance that we can reference
+static CCTP_TOKEN_MESSENGER_CONFIG: CctpTokenMessengerConfig = CctpTokenMessengerConfig;
+
+pub struct CctpTokenMessengerVisualizer;
+
+impl InstructionVisualizer for CctpTokenMessengerVisualizer {
+    fn visualize_tx_commands(
+        &self,
+        context: &VisualizerContext,
+    ) -> Result<AnnotatedPayloadField, VisualSignError> {
+        let instruction = context
+            .current_instruction()
+            .ok_or_else(|| VisualSignError::MissingData("No instruction found".into()))?;
+
+        let messenger_instruction = parse_token_messenger_instruction(&instruction.data)
+            .map_err(|e| VisualSignError::DecodeError(e.to_string()))?;
+
+        let mut expanded_fields = vec![create_text_field(
+            "Program ID",
+            &instruction.program_id.to_string(),
+        )?];
+
+        let instruction_text = match &messenger_instruction {
+            CctpTokenMessengerInstruction::DepositForBurn {
+                amount,
+                destination_domain,
+                mint_recipient,
+                destination_caller,
+            } => {
+                let mint = account_at(instruction, DEPOSIT_BURN_TOKEN_MINT_INDEX);
+                let token = get_token_info(&mint, *amount);
+                let (domain_name, format) = match domain(*destination_domain) {
+                    Some((name, format)) => {
+                        (format!("{name} (domain {destination_domain})"), format)
+                    }
+                    None => (
+                        format!("unknown domain {destination_domain}"),
+                        RemoteAddressFormat::Hex,
+                    ),
+                };
+                let recipient = format_remote_address(mint_recipient, format);
+
+                expanded_fields.push(create_text_field("Token", &mint)?);
+                expanded_fields.push(create_text_field(
+                    "Amount",
+                    &format!("{} {}", token.human_readable_amount, token.symbol),
+                )?);
+                expanded_fields.push(create_text_field("Destination Domain", &domain_name)?);
+                expanded_fields.push(create_text_field("Mint Recipient", &recipient)?);
+                expanded_fields.push(create_text_field(
+                    "Burn Token Account",
+                    &account_at(instruction, DEPOSIT_BURN_TOKEN_ACCOUNT_INDEX),
+                )?);
+                if let Some(caller) = destination_caller {
+                    expanded_fields.push(create_text_field(
+                        "Destination Caller",
+                        &format_remote_address(caller, format),
+                    )?);
+                }
+
+                format!(
+                    "CCTP: Burn {} {} to mint to {recipient} on {domain_name}",
+                    token.human_readable_amount, token.symbol
+                )
+            }
+            CctpTokenMessengerInstruction::Other { discriminator } => {
+                expanded_fields.push(create_text_field(
+                    "Discriminator",
+                    &hex::encode(discriminator),
+                )?);
+                "CCTP TokenMessengerMinter: Unknown Instruction".to_string()
+            }
+        };
+
+        let condensed_fields = vec![create_text_field("Instruction", &instruction_text)?];
+        expanded_fields.push(create_raw_data_field(
+            &instruction.data,
+            Some(hex::encode(&instruction.data)),
+        )?);
+
+        let preview_layout = SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: instruction_text.clone(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: String::new(),
+            }),
+            condensed: Some(SignablePayloadFieldListLayout {
+                fields: condensed_fields,
+            }),
+            expanded: Some(SignablePayloadFieldListLayout {
+                fields: expanded_fields,
+            }),
+        };
+
+        Ok(AnnotatedPayloadField {
+            static_annotation: None,
+            dynamic_annotation: None,
+            signable_payload_field: SignablePayloadField::PreviewLayout {
+                common: SignablePayloadFieldCommon {
+                    label: format!("Instruction {}", context.instruction_index() + 1),
+                    fallback_text: format!(
+                        "Program ID: {}\nData: {}",
+                        instruction.program_id,
+                        hex::encode(&instruction.data)
+                    ),
+                },
+                preview_layout,
+            },
+        })
+    }
+
+    fn get_config(&self) -> Option<&dyn SolanaIntegrationConfig> {
+        Some(&CCTP_TOKEN_MESSENGER_CONFIG)
+    }
+
+    fn kind(&self) -> VisualizerKind {
+        VisualizerKind::Bridge("CCTP")
+    }
+}
+
+// From: https://developers.circle.com/stablecoins/supported-domains
+fn domain(id: u32) -> Option<(&'static str, RemoteAddressFormat)> {
+    let domain = match id {
+        0 => ("Ethereum", RemoteAddressFormat::Evm),
+        1 => ("Avalanche", RemoteAddressFormat::Evm),
+        2 => ("OP Mainnet", RemoteAddressFormat::Evm),
+        3 => ("Arbitrum", RemoteAddressFormat::Evm),
+        4 => ("Noble", RemoteAddressFormat::Hex),
+        5 => ("Solana", RemoteAddressFormat::Solana),
+        6 => ("Base", RemoteAddressFormat::Evm),
+        7 => ("Polygon PoS", RemoteAddressFormat::Evm),
+        8 => ("Sui", RemoteAddressFormat::Hex),
+        9 => ("Aptos", RemoteAddressFormat::Hex),
+        10 => ("Unichain", RemoteAddressFormat::Evm),
+        _ => return None,
+    };
+    Some(domain)
+}
+
+fn parse_token_messenger_instruction(
+    data: &[u8],
+) -> Result<CctpTokenMessengerInstruction, &'static str> {
+    let discriminator: [u8; 8] = data
+        .get(0..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Instruction data too short")?;
+    let mut args = &data[8..];
+
+    match discriminator {
+        DEPOSIT_FOR_BURN_DISCRIMINATOR => {
+            let (amount, destination_domain, mint_recipient) =
+                <(u64, u32, [u8; 32])>::deserialize(&mut args)
+                    .map_err(|_| "Invalid deposit data")?;
+            Ok(CctpTokenMessengerInstruction::DepositForBurn {
+                amount,
+                destination_domain,
+                mint_recipient,
+                destination_caller: None,
+            })
+        }
+        DEPOSIT_FOR_BURN_WITH_CALLER_DISCRIMINATOR => {
+            let (amount, destination_domain, mint_recipient, destination_caller) =
+                <(u64, u32, [u8; 32], [u8; 32])>::deserialize(&mut args)
+                    .map_err(|_| "Invalid deposit data")?;
+            Ok(CctpTokenMessengerInstruction::DepositForBurn {
+                amount,
+                destination_domain,
+                mint_recipient,
+                destination_caller: Some(destination_caller),
+            })
+        }
+        discriminator => Ok(CctpTokenMessengerInstruction::Other { discriminator }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_parser::solana::structs::SolanaAccount;
+    use solana_sdk::hash::hash;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn deposit_for_burn_data(destination_domain: u32, mint_recipient: [u8; 32]) -> Vec<u8> {
+        let mut data = DEPOSIT_FOR_BURN_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&100_000_000u64.to_le_bytes());
+        data.extend_from_slice(&destination_domain.to_le_bytes());
+        data.extend_from_slice(&mint_recipient);
+        data
+    }
+
+    #[test]
+    fn test_parse_token_messenger_instruction() {
+        assert_eq!(
+            DEPOSIT_FOR_BURN_DISCRIMINATOR,
+            hash(b"global:deposit_for_burn").to_bytes()[..8]
+        );
+        assert_eq!(
+            DEPOSIT_FOR_BURN_WITH_CALLER_DISCRIMINATOR,
+            hash(b"global:deposit_for_burn_with_caller").to_bytes()[..8]
+        );
+
+        assert_eq!(
+            parse_token_messenger_instruction(&deposit_for_burn_data(6, [0x11; 32])),
+            Ok(CctpTokenMessengerInstruction::DepositForBurn {
+                amount: 100_000_000,
+                destination_domain: 6,
+                mint_recipient: [0x11; 32],
+                destination_caller: None,
+            })
+        );
+        assert_eq!(
+            parse_token_messenger_instruction(&[0; 8]),
+            Ok(CctpTokenMessengerInstruction::Other {
+                discriminator: [0; 8]
+            })
+        );
+        assert!(parse_token_messenger_instruction(&DEPOSIT_FOR_BURN_DISCRIMINATOR).is_err());
+        assert!(parse_token_messenger_instruction(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_deposit_for_burn_fields() {
+        let mut accounts: Vec<AccountMeta> = (0..17)
+            .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+            .collect();
+        accounts[DEPOSIT_BURN_TOKEN_MINT_INDEX].pubkey = Pubkey::from_str(USDC_MINT).unwrap();
+        // Not left-padded, so not an address an EVM chain can mint to
+        let instructions = vec![Instruction {
+            program_id: Pubkey::from_str(config::CCTP_TOKEN_MESSENGER_PROGRAM_ID).unwrap(),
+            accounts,
+            data: deposit_for_burn_data(6, [0x11; 32]),
+        }];
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let context = VisualizerContext::new(&sender, 0, &instructions);
+
+        let field = CctpTokenMessengerVisualizer
+            .visualize_tx_commands(&context)
+            .unwrap();
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } =
+            field.signable_payload_field
+        else {
+            panic!("Expected PreviewLayout");
+        };
+
+        let recipient = format!("0x{} (not an EVM address)", "11".repeat(32));
+        assert_eq!(
+            preview_layout.title.unwrap().text,
+            format!("CCTP: Burn 100 USDC to mint to {recipient} on Base (domain 6)")
+        );
+        let expanded = preview_layout.expanded.unwrap().fields;
+        assert!(expanded.iter().any(|f| {
+            f.signable_payload_field.label() == "Mint Recipient"
+                && f.signable_payload_field.fallback_text() == &recipient
+        }));
+    }
+}
@@ -5,16 +5,27 @@ mod config;
 use crate::core::{
     InstructionVisualizer, SolanaIntegrationConfig, VisualizerContext, VisualizerKind,
 };
+use crate::utils::format_token_amount;
 use borsh::de::BorshDeserialize;
 use config::ComputeBudgetConfig;
-use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::instruction::Instruction;
 use visualsign::errors::VisualSignError;
-use visualsign::field_builders::{create_number_field, create_raw_data_field, create_text_field};
+use visualsign::field_builders::{
+    create_amount_field, create_number_field, create_raw_data_field, create_text_field,
+};
 use visualsign::{
     AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
     SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
 };
 
+/// Maximum number of compute units a transaction may request
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+/// Compute units the runtime allots per instruction when no limit is requested
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+const SOL_DECIMALS: u8 = 9;
+
 // Create a static instance that we can reference
 static COMPUTE_BUDGET_CONFIG: ComputeBudgetConfig = ComputeBudgetConfig;
 
@@ -38,20 +49,33 @@ impl InstructionVisualizer for ComputeBudgetVisualizer {
 
         let instruction_text = format_compute_budget_instruction(&compute_budget_instruction);
 
-        let condensed = SignablePayloadFieldListLayout {
-            fields: vec![AnnotatedPayloadField {
-                static_annotation: None,
-                dynamic_annotation: None,
-                signable_payload_field: SignablePayloadField::TextV2 {
-                    common: SignablePayloadFieldCommon {
-                        fallback_text: instruction_text.clone(),
-                        label: "Instruction".to_string(),
-                    },
-                    text_v2: SignablePayloadFieldTextV2 {
-                        text: instruction_text.clone(),
-                    },
+        // The fee is only attached to the price instruction so it is shown once per transaction
+        let priority_fee = match compute_budget_instruction {
+            ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports) => {
+                Some(compute_priority_fee(micro_lamports, context.instructions()))
+            }
+            _ => None,
+        };
+
+        let mut condensed_fields = vec![AnnotatedPayloadField {
+            static_annotation: None,
+            dynamic_annotation: None,
+            signable_payload_field: SignablePayloadField::TextV2 {
+                common: SignablePayloadFieldCommon {
+                    fallback_text: instruction_text.clone(),
+                    label: "Instruction".to_string(),
+                },
+                text_v2: SignablePayloadFieldTextV2 {
+                    text: instruction_text.clone(),
                 },
-            }],
+            },
+        }];
+        if let Some(priority_fee) = &priority_fee {
+            condensed_fields.push(create_priority_fee_field(priority_fee)?);
+        }
+
+        let condensed = SignablePayloadFieldListLayout {
+            fields: condensed_fields,
         };
 
         let expanded = SignablePayloadFieldListLayout {
@@ -59,7 +83,8 @@ impl InstructionVisualizer for ComputeBudgetVisualizer {
                 &compute_budget_instruction,
                 &instruction.program_id.to_string(),
                 &instruction.data,
-            ),
+                priority_fee.as_ref(),
+            )?,
         };
 
         let preview_layout = SignablePayloadFieldPreviewLayout {
@@ -119,11 +144,85 @@ fn format_compute_budget_instruction(instruction: &ComputeBudgetInstruction) ->
     }
 }
 
+/// Priority fee implied by the compute budget instructions of a whole transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PriorityFee {
+    micro_lamports_per_unit: u64,
+    compute_unit_limit: u32,
+    /// Whether the limit was requested explicitly or falls back to the runtime default
+    explicit_limit: bool,
+    lamports: u64,
+}
+
+/// Computes the priority fee as unit price x compute unit limit, rounded up to whole lamports
+/// the same way the runtime charges it.
+fn compute_priority_fee(micro_lamports_per_unit: u64, instructions: &[Instruction]) -> PriorityFee {
+    let program_id = compute_budget::id();
+    let requested_limit = instructions
+        .iter()
+        .filter(|ix| ix.program_id == program_id)
+        .find_map(
+            |ix| match ComputeBudgetInstruction::try_from_slice(&ix.data) {
+                Ok(ComputeBudgetInstruction::SetComputeUnitLimit(units)) => Some(units),
+                _ => None,
+            },
+        );
+
+    let (compute_unit_limit, explicit_limit) = match requested_limit {
+        Some(units) => (units.min(MAX_COMPUTE_UNIT_LIMIT), true),
+        None => {
+            let instruction_count = instructions
+                .iter()
+                .filter(|ix| ix.program_id != program_id)
+                .count();
+            let default_limit = u32::try_from(instruction_count)
+                .unwrap_or(u32::MAX)
+                .saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+                .min(MAX_COMPUTE_UNIT_LIMIT);
+            (default_limit, false)
+        }
+    };
+
+    let micro_lamports = u128::from(micro_lamports_per_unit) * u128::from(compute_unit_limit);
+    let lamports =
+        u64::try_from(micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT)).unwrap_or(u64::MAX);
+
+    PriorityFee {
+        micro_lamports_per_unit,
+        compute_unit_limit,
+        explicit_limit,
+        lamports,
+    }
+}
+
+fn create_priority_fee_field(
+    priority_fee: &PriorityFee,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    create_amount_field(
+        "Priority Fee",
+        &format_token_amount(priority_fee.lamports, SOL_DECIMALS),
+        "SOL",
+    )
+}
+
+fn format_priority_fee_basis(priority_fee: &PriorityFee) -> String {
+    let limit_source = if priority_fee.explicit_limit {
+        "requested limit"
+    } else {
+        "default limit"
+    };
+    format!(
+        "{} micro-lamports x {} units ({limit_source})",
+        priority_fee.micro_lamports_per_unit, priority_fee.compute_unit_limit
+    )
+}
+
 fn create_compute_budget_expanded_fields(
     instruction: &ComputeBudgetInstruction,
     program_id: &str,
     data: &[u8],
-) -> Vec<AnnotatedPayloadField> {
+    priority_fee: Option<&PriorityFee>,
+) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
     let mut fields = vec![create_text_field("Program ID", program_id).unwrap()];
 
     // Add specific fields based on instruction type
@@ -156,9 +255,120 @@ fn create_compute_budget_expanded_fields(
         }
     }
 
+    if let Some(priority_fee) = priority_fee {
+        fields.push(create_priority_fee_field(priority_fee)?);
+        fields.push(create_text_field(
+            "Priority Fee Basis",
+            &format_priority_fee_basis(priority_fee),
+        )?);
+    }
+
     let hex_fallback_string = hex::encode(data).to_string();
     let raw_data_field = create_raw_data_field(data, Some(hex_fallback_string)).unwrap();
 
     fields.push(raw_data_field);
-    fields
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_parser::solana::structs::SolanaAccount;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::system_instruction;
+
+    fn transfer_instruction() -> Instruction {
+        system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1)
+    }
+
+    fn visualize(instructions: &[Instruction], index: usize) -> SignablePayloadFieldPreviewLayout {
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let instructions = instructions.to_vec();
+        let context = VisualizerContext::new(&sender, index, &instructions);
+        match ComputeBudgetVisualizer
+            .visualize_tx_commands(&context)
+            .unwrap()
+            .signable_payload_field
+        {
+            SignablePayloadField::PreviewLayout { preview_layout, .. } => preview_layout,
+            other => panic!("Expected PreviewLayout, got {other:?}"),
+        }
+    }
+
+    fn priority_fee_amount(fields: &[AnnotatedPayloadField]) -> Option<String> {
+        fields.iter().find_map(|f| match &f.signable_payload_field {
+            SignablePayloadField::AmountV2 { common, amount_v2 }
+                if common.label == "Priority Fee" =>
+            {
+                Some(amount_v2.amount.clone())
+            }
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_priority_fee_uses_requested_limit() {
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+            ComputeBudgetInstruction::set_compute_unit_price(50_000),
+            transfer_instruction(),
+        ];
+
+        let fee = compute_priority_fee(50_000, &instructions);
+        assert_eq!(fee.compute_unit_limit, 300_000);
+        assert!(fee.explicit_limit);
+        assert_eq!(fee.lamports, 15_000);
+
+        let layout = visualize(&instructions, 1);
+        assert_eq!(
+            priority_fee_amount(&layout.condensed.unwrap().fields).as_deref(),
+            Some("0.000015")
+        );
+        assert_eq!(
+            priority_fee_amount(&layout.expanded.unwrap().fields).as_deref(),
+            Some("0.000015")
+        );
+
+        // The limit instruction itself does not repeat the fee
+        let layout = visualize(&instructions, 0);
+        assert!(priority_fee_amount(&layout.expanded.unwrap().fields).is_none());
+    }
+
+    #[test]
+    fn test_priority_fee_default_limit_and_rounding() {
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_price(3),
+            transfer_instruction(),
+            transfer_instruction(),
+        ];
+
+        let fee = compute_priority_fee(3, &instructions);
+        assert_eq!(
+            fee.compute_unit_limit,
+            2 * DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT
+        );
+        assert!(!fee.explicit_limit);
+        // 3 * 400_000 = 1_200_000 micro-lamports, rounded up to 2 lamports
+        assert_eq!(fee.lamports, 2);
+        assert_eq!(
+            format_priority_fee_basis(&fee),
+            "3 micro-lamports x 400000 units (default limit)"
+        );
+    }
+
+    #[test]
+    fn test_priority_fee_limit_is_capped() {
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(u32::MAX),
+            ComputeBudgetInstruction::set_compute_unit_price(u64::MAX),
+        ];
+
+        let fee = compute_priority_fee(u64::MAX, &instructions);
+        assert_eq!(fee.compute_unit_limit, MAX_COMPUTE_UNIT_LIMIT);
+        assert_eq!(fee.lamports, u64::MAX);
+    }
 }
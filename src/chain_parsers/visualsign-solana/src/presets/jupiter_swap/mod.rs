@@ -11,37 +11,55 @@ use visualsign::errors::VisualSignError;
 use visualsign::field_builders::{
     create_amount_field, create_number_field, create_raw_data_field, create_text_field,
 };
+use visualsign::text::sanitize_summary;
 use visualsign::{
     AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
     SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
 };
 
-// Jupiter instruction discriminators (8-byte values)
+// Jupiter instruction discriminators: the first 8 bytes of sha256("global:<instruction_name>")
 const JUPITER_ROUTE_DISCRIMINATOR: [u8; 8] = [0xe5, 0x17, 0xcb, 0x97, 0x7a, 0xe3, 0xad, 0x2a];
 const JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR: [u8; 8] =
-    [0x4b, 0xd7, 0xdf, 0xa8, 0x0c, 0xd0, 0xb6, 0x2a];
+    [0xd0, 0x33, 0xef, 0x97, 0x7b, 0x2b, 0xed, 0x5c];
 const JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR: [u8; 8] =
-    [0x3a, 0xf2, 0xaa, 0xae, 0x2f, 0xb6, 0xd4, 0x2a];
+    [0xc1, 0x20, 0x9b, 0x33, 0x41, 0xd6, 0x9c, 0x81];
+
+// Account positions of the token mints in each Jupiter v6 route instruction. `route` only
+// carries the destination mint; its source mint is hidden in the AMM-specific remaining accounts.
+const ROUTE_DESTINATION_MINT_INDEX: usize = 5;
+const EXACT_OUT_ROUTE_SOURCE_MINT_INDEX: usize = 5;
+const EXACT_OUT_ROUTE_DESTINATION_MINT_INDEX: usize = 6;
+const SHARED_ACCOUNTS_ROUTE_SOURCE_MINT_INDEX: usize = 7;
+const SHARED_ACCOUNTS_ROUTE_DESTINATION_MINT_INDEX: usize = 8;
 
 #[derive(Debug, Clone)]
 pub enum JupiterSwapInstruction {
     Route {
         in_token: Option<SwapTokenInfo>,
         out_token: Option<SwapTokenInfo>,
+        in_amount: u64,
+        out_amount: u64,
         slippage_bps: u16,
         platform_fee_bps: u8,
+        hops: usize,
     },
     ExactOutRoute {
         in_token: Option<SwapTokenInfo>,
         out_token: Option<SwapTokenInfo>,
+        in_amount: u64,
+        out_amount: u64,
         slippage_bps: u16,
         platform_fee_bps: u8,
+        hops: usize,
     },
     SharedAccountsRoute {
         in_token: Option<SwapTokenInfo>,
         out_token: Option<SwapTokenInfo>,
+        in_amount: u64,
+        out_amount: u64,
         slippage_bps: u16,
         platform_fee_bps: u8,
+        hops: usize,
     },
     Unknown,
 }
@@ -89,6 +107,18 @@ impl JupiterSwapInstruction {
 
         Ok((in_amount, out_amount, slippage_bps, platform_fee_bps))
     }
+
+    /// Parse the number of route plan steps (hops) from instruction data
+    ///
+    /// The route plan is a borsh `Vec<RoutePlanStep>` whose u32 length prefix starts at
+    /// `offset`: right after the discriminator, or after the extra `id: u8` argument
+    /// for `sharedAccountsRoute`.
+    fn parse_route_plan_len(data: &[u8], offset: usize) -> Result<usize, &'static str> {
+        let bytes = data
+            .get(offset..offset + 4)
+            .ok_or("Instruction data too short for route plan")?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
 }
 
 // Create a static instance that we can reference
@@ -116,10 +146,11 @@ impl InstructionVisualizer for JupiterSwapVisualizer {
                 .map_err(|e| VisualSignError::DecodeError(e.to_string()))?;
 
         let instruction_text = format_jupiter_swap_instruction(&jupiter_instruction);
+        let summary_text = format_jupiter_swap_summary(&jupiter_instruction);
 
         let condensed = SignablePayloadFieldListLayout {
             fields: vec![
-                create_text_field("Instruction", &instruction_text)
+                create_text_field("Instruction", &summary_text)
                     .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
             ],
         };
@@ -199,16 +230,20 @@ fn parse_route_instruction(
 ) -> Result<JupiterSwapInstruction, &'static str> {
     let (in_amount, out_amount, slippage_bps, platform_fee_bps) =
         JupiterSwapInstruction::parse_amounts_and_slippage_from_data(data)?;
+    let hops = JupiterSwapInstruction::parse_route_plan_len(data, 8)?;
 
-    let in_token = accounts.first().map(|addr| get_token_info(addr, in_amount));
-    // Account index hardcoded to 5 for output token address (destination mint)
-    let out_token = accounts.get(5).map(|addr| get_token_info(addr, out_amount));
+    let out_token = accounts
+        .get(ROUTE_DESTINATION_MINT_INDEX)
+        .map(|addr| get_token_info(addr, out_amount));
 
     Ok(JupiterSwapInstruction::Route {
-        in_token,
+        in_token: None,
         out_token,
+        in_amount,
+        out_amount,
         slippage_bps,
         platform_fee_bps,
+        hops,
     })
 }
 
@@ -216,18 +251,26 @@ fn parse_exact_out_route_instruction(
     data: &[u8],
     accounts: &[String],
 ) -> Result<JupiterSwapInstruction, &'static str> {
-    let (in_amount, out_amount, slippage_bps, platform_fee_bps) =
+    // Exact out routes encode the exact out_amount first, followed by the quoted in_amount
+    let (out_amount, in_amount, slippage_bps, platform_fee_bps) =
         JupiterSwapInstruction::parse_amounts_and_slippage_from_data(data)?;
+    let hops = JupiterSwapInstruction::parse_route_plan_len(data, 8)?;
 
-    let in_token = accounts.first().map(|addr| get_token_info(addr, in_amount));
-    // Account index hardcoded to 5 for output token address (destination mint)
-    let out_token = accounts.get(5).map(|addr| get_token_info(addr, out_amount));
+    let in_token = accounts
+        .get(EXACT_OUT_ROUTE_SOURCE_MINT_INDEX)
+        .map(|addr| get_token_info(addr, in_amount));
+    let out_token = accounts
+        .get(EXACT_OUT_ROUTE_DESTINATION_MINT_INDEX)
+        .map(|addr| get_token_info(addr, out_amount));
 
     Ok(JupiterSwapInstruction::ExactOutRoute {
         in_token,
         out_token,
+        in_amount,
+        out_amount,
         slippage_bps,
         platform_fee_bps,
+        hops,
     })
 }
 
@@ -237,16 +280,23 @@ fn parse_shared_accounts_route_instruction(
 ) -> Result<JupiterSwapInstruction, &'static str> {
     let (in_amount, out_amount, slippage_bps, platform_fee_bps) =
         JupiterSwapInstruction::parse_amounts_and_slippage_from_data(data)?;
+    let hops = JupiterSwapInstruction::parse_route_plan_len(data, 9)?;
 
-    let in_token = accounts.first().map(|addr| get_token_info(addr, in_amount));
-    // Account index hardcoded to 5 for output token address (destination mint)
-    let out_token = accounts.get(5).map(|addr| get_token_info(addr, out_amount));
+    let in_token = accounts
+        .get(SHARED_ACCOUNTS_ROUTE_SOURCE_MINT_INDEX)
+        .map(|addr| get_token_info(addr, in_amount));
+    let out_token = accounts
+        .get(SHARED_ACCOUNTS_ROUTE_DESTINATION_MINT_INDEX)
+        .map(|addr| get_token_info(addr, out_amount));
 
     Ok(JupiterSwapInstruction::SharedAccountsRoute {
         in_token,
         out_token,
+        in_amount,
+        out_amount,
         slippage_bps,
         platform_fee_bps,
+        hops,
     })
 }
 
@@ -255,20 +305,29 @@ fn format_jupiter_swap_instruction(instruction: &JupiterSwapInstruction) -> Stri
         JupiterSwapInstruction::Route {
             in_token,
             out_token,
+            in_amount,
+            out_amount,
             slippage_bps,
             platform_fee_bps,
+            ..
         }
         | JupiterSwapInstruction::ExactOutRoute {
             in_token,
             out_token,
+            in_amount,
+            out_amount,
             slippage_bps,
             platform_fee_bps,
+            ..
         }
         | JupiterSwapInstruction::SharedAccountsRoute {
             in_token,
             out_token,
+            in_amount,
+            out_amount,
             slippage_bps,
             platform_fee_bps,
+            ..
         } => {
             let instruction_type = match instruction {
                 JupiterSwapInstruction::Route { .. } => "Jupiter Swap",
@@ -282,9 +341,9 @@ fn format_jupiter_swap_instruction(instruction: &JupiterSwapInstruction) -> Stri
             let mut result = format!(
                 "{}: From {} {} To {} {} (slippage: {}bps",
                 instruction_type,
-                format_token_amount(in_token),
+                in_amount,
                 format_token_symbol(in_token),
-                format_token_amount(out_token),
+                out_amount,
                 format_token_symbol(out_token),
                 slippage_bps
             );
@@ -300,11 +359,47 @@ fn format_jupiter_swap_instruction(instruction: &JupiterSwapInstruction) -> Stri
     }
 }
 
-fn format_token_amount(token: &Option<SwapTokenInfo>) -> String {
+/// Condensed one-line summary, e.g. "Swap 1.5 SOL -> 210.3 USDC via Jupiter"
+///
+/// Amounts are scaled by the token decimals when the mint is known, and shown raw otherwise.
+fn format_jupiter_swap_summary(instruction: &JupiterSwapInstruction) -> String {
+    match instruction {
+        JupiterSwapInstruction::Route {
+            in_token,
+            out_token,
+            in_amount,
+            out_amount,
+            ..
+        }
+        | JupiterSwapInstruction::ExactOutRoute {
+            in_token,
+            out_token,
+            in_amount,
+            out_amount,
+            ..
+        }
+        | JupiterSwapInstruction::SharedAccountsRoute {
+            in_token,
+            out_token,
+            in_amount,
+            out_amount,
+            ..
+        } => sanitize_summary(&format!(
+            "Swap {} {} -> {} {} via Jupiter",
+            format_token_amount(in_token, *in_amount),
+            format_token_symbol(in_token),
+            format_token_amount(out_token, *out_amount),
+            format_token_symbol(out_token),
+        )),
+        JupiterSwapInstruction::Unknown => "Jupiter: Unknown Instruction".to_string(),
+    }
+}
+
+fn format_token_amount(token: &Option<SwapTokenInfo>, raw_amount: u64) -> String {
     token
         .as_ref()
-        .map(|t| t.amount.to_string())
-        .unwrap_or_else(|| "Unknown".to_string())
+        .map(|t| t.human_readable_amount.clone())
+        .unwrap_or_else(|| raw_amount.to_string())
 }
 
 fn format_token_symbol(token: &Option<SwapTokenInfo>) -> String {
@@ -328,53 +423,83 @@ fn create_jupiter_swap_expanded_fields(
         JupiterSwapInstruction::Route {
             in_token,
             out_token,
+            in_amount,
+            out_amount,
             slippage_bps,
             platform_fee_bps,
+            hops,
         }
         | JupiterSwapInstruction::ExactOutRoute {
             in_token,
             out_token,
+            in_amount,
+            out_amount,
             slippage_bps,
             platform_fee_bps,
+            hops,
         }
         | JupiterSwapInstruction::SharedAccountsRoute {
             in_token,
             out_token,
+            in_amount,
+            out_amount,
             slippage_bps,
             platform_fee_bps,
+            hops,
         } => {
+            // Exact out routes fix the output amount and quote the input amount
+            let (in_amount_label, out_amount_label) = match instruction {
+                JupiterSwapInstruction::ExactOutRoute { .. } => {
+                    ("Quoted Input Amount", "Output Amount")
+                }
+                _ => ("Input Amount", "Quoted Output Amount"),
+            };
+
             // Add input token fields
             if let Some(token) = in_token {
                 fields.extend([
                     create_text_field("Input Token", &token.symbol)
                         .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
-                    create_amount_field("Input Amount", &token.amount.to_string(), &token.symbol)
-                        .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
                     create_text_field("Input Token Name", &token.name)
                         .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
-                    // TODO: Add back Input Token Address
+                    create_text_field("Input Token Address", &token.address)
+                        .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
                 ]);
             }
+            fields.push(
+                create_amount_field(
+                    in_amount_label,
+                    &in_amount.to_string(),
+                    &format_token_symbol(in_token),
+                )
+                .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
+            );
 
             // Add output token fields
             if let Some(token) = out_token {
                 fields.extend([
                     create_text_field("Output Token", &token.symbol)
                         .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
-                    create_amount_field(
-                        "Quoted Output Amount",
-                        &token.amount.to_string(),
-                        &token.symbol,
-                    )
-                    .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
                     create_text_field("Output Token Name", &token.name)
                         .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
                     create_text_field("Output Token Address", &token.address)
                         .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
                 ]);
             }
+            fields.push(
+                create_amount_field(
+                    out_amount_label,
+                    &out_amount.to_string(),
+                    &format_token_symbol(out_token),
+                )
+                .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
+            );
 
-            // Add slippage field
+            // Add route plan and slippage fields
+            fields.push(
+                create_number_field("Hops", &hops.to_string(), "")
+                    .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
+            );
             fields.push(
                 create_number_field("Slippage", &slippage_bps.to_string(), "bps")
                     .map_err(|e| VisualSignError::ConversionError(e.to_string()))?,
@@ -409,6 +534,7 @@ fn create_jupiter_swap_expanded_fields(
 mod tests {
     use super::*;
     use base64::engine::{Engine, general_purpose::STANDARD};
+    use solana_sdk::hash::hash;
     mod fixture_test;
 
     #[test]
@@ -626,6 +752,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_jupiter_discriminators_match_anchor_names() {
+        let discriminator = |name: &str| -> [u8; 8] {
+            hash(format!("global:{name}").as_bytes()).to_bytes()[..8]
+                .try_into()
+                .unwrap()
+        };
+        assert_eq!(discriminator("route"), JUPITER_ROUTE_DISCRIMINATOR);
+        assert_eq!(
+            discriminator("exact_out_route"),
+            JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR
+        );
+        assert_eq!(
+            discriminator("shared_accounts_route"),
+            JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn test_jupiter_shared_accounts_route_wire_bytes() {
+        // sharedAccountsRoute written out byte for byte rather than built from the constants:
+        // discriminator, id, a one-step route plan, in_amount (1 SOL), quoted_out_amount
+        // (150 USDC), slippage_bps (50) and platform_fee_bps (0)
+        let data = hex::decode(concat!(
+            "c1209b3341d69c81",
+            "03",
+            "01000000",
+            "1101640001",
+            "00ca9a3b00000000",
+            "80d1f00800000000",
+            "3200",
+            "00",
+        ))
+        .unwrap();
+
+        let result =
+            parse_jupiter_swap_instruction(&data, &shared_accounts_route_accounts()).unwrap();
+        match &result {
+            JupiterSwapInstruction::SharedAccountsRoute {
+                in_amount,
+                out_amount,
+                slippage_bps,
+                hops,
+                ..
+            } => {
+                assert_eq!(*in_amount, 1_000_000_000);
+                assert_eq!(*out_amount, 150_000_000);
+                assert_eq!(*slippage_bps, 50);
+                assert_eq!(*hops, 1);
+            }
+            _ => panic!("Expected SharedAccountsRoute instruction, got {result:?}"),
+        }
+        assert_eq!(
+            format_jupiter_swap_summary(&result),
+            "Swap 1 SOL -> 150 USDC via Jupiter"
+        );
+    }
+
     #[test]
     fn test_jupiter_discriminator_matching() {
         // Test that our discriminators match correctly
@@ -639,7 +823,7 @@ mod tests {
             0x00, // platform_fee_bps (0 bps)
         ];
         let exact_out_data = [
-            0x4b, 0xd7, 0xdf, 0xa8, 0x0c, 0xd0, 0xb6, 0x2a, // discriminator
+            0xd0, 0x33, 0xef, 0x97, 0x7b, 0x2b, 0xed, 0x5c, // discriminator
             0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, // padding/intermediate data
             0x00, 0xe1, 0xf5, 0x05, 0x00, 0x00, 0x00, 0x00, // in_amount (100000000)
             0x00, 0xc2, 0xeb, 0x0b, 0x00, 0x00, 0x00, 0x00, // out_amount (200000000)
@@ -647,7 +831,7 @@ mod tests {
             0x00, // platform_fee_bps (0 bps)
         ];
         let shared_accounts_data = [
-            0x3a, 0xf2, 0xaa, 0xae, 0x2f, 0xb6, 0xd4, 0x2a, // discriminator
+            0xc1, 0x20, 0x9b, 0x33, 0x41, 0xd6, 0x9c, 0x81, // discriminator
             0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, // padding/intermediate data
             0x00, 0xe1, 0xf5, 0x05, 0x00, 0x00, 0x00, 0x00, // in_amount (100000000)
             0x00, 0xc2, 0xeb, 0x0b, 0x00, 0x00, 0x00, 0x00, // out_amount (200000000)
@@ -762,4 +946,146 @@ mod tests {
         );
         println!("✅ Platform Fee field present in expanded fields");
     }
+
+    fn build_route_data(prefix: &[u8], in_amount: u64, out_amount: u64, slippage: u16) -> Vec<u8> {
+        let mut data = prefix.to_vec();
+        data.extend_from_slice(&in_amount.to_le_bytes());
+        data.extend_from_slice(&out_amount.to_le_bytes());
+        data.extend_from_slice(&slippage.to_le_bytes());
+        data.push(0); // platform_fee_bps
+        data
+    }
+
+    fn shared_accounts_route_accounts() -> Vec<String> {
+        let mut accounts: Vec<String> = (0..13).map(|i| format!("account{i}")).collect();
+        accounts[SHARED_ACCOUNTS_ROUTE_SOURCE_MINT_INDEX] =
+            "So11111111111111111111111111111111111111112".to_string();
+        accounts[SHARED_ACCOUNTS_ROUTE_DESTINATION_MINT_INDEX] =
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string();
+        accounts
+    }
+
+    #[test]
+    fn test_jupiter_shared_accounts_route_mints_and_hops() {
+        let mut prefix = JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR.to_vec();
+        prefix.push(0x00); // id
+        prefix.extend_from_slice(&2u32.to_le_bytes()); // route plan with two steps
+        prefix.extend_from_slice(&[0x00, 0x64, 0x00, 0x01, 0x00, 0x64, 0x01, 0x02]);
+        let data = build_route_data(&prefix, 1_500_000_000, 210_300_000, 50);
+
+        let result =
+            parse_jupiter_swap_instruction(&data, &shared_accounts_route_accounts()).unwrap();
+
+        match &result {
+            JupiterSwapInstruction::SharedAccountsRoute {
+                in_token,
+                out_token,
+                hops,
+                ..
+            } => {
+                assert_eq!(*hops, 2);
+                assert_eq!(in_token.as_ref().unwrap().symbol, "SOL");
+                assert_eq!(out_token.as_ref().unwrap().symbol, "USDC");
+            }
+            _ => panic!("Expected SharedAccountsRoute instruction, got {result:?}"),
+        }
+
+        let summary = format_jupiter_swap_summary(&result);
+        assert_eq!(summary, "Swap 1.5 SOL -> 210.3 USDC via Jupiter");
+        assert!(summary.is_ascii());
+
+        let fields = create_jupiter_swap_expanded_fields(
+            &result,
+            "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+            &data,
+        )
+        .unwrap();
+        let text_of = |label: &str| {
+            fields.iter().find_map(|f| match &f.signable_payload_field {
+                SignablePayloadField::TextV2 { common, text_v2 } if common.label == label => {
+                    Some(text_v2.text.clone())
+                }
+                SignablePayloadField::Number { common, number } if common.label == label => {
+                    Some(number.number.clone())
+                }
+                _ => None,
+            })
+        };
+        assert_eq!(
+            text_of("Input Token Address").as_deref(),
+            Some("So11111111111111111111111111111111111111112")
+        );
+        assert_eq!(
+            text_of("Output Token Address").as_deref(),
+            Some("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")
+        );
+        assert_eq!(text_of("Hops").as_deref(), Some("2"));
+        assert_eq!(text_of("Slippage").as_deref(), Some("50"));
+    }
+
+    #[test]
+    fn test_jupiter_exact_out_route_amount_order() {
+        let mut prefix = JUPITER_EXACT_OUT_ROUTE_DISCRIMINATOR.to_vec();
+        prefix.extend_from_slice(&1u32.to_le_bytes());
+        prefix.extend_from_slice(&[0x00, 0x64, 0x00, 0x01]);
+        // Exact out encodes the exact out_amount before the quoted in_amount
+        let data = build_route_data(&prefix, 5_000_000, 25_000_000, 30);
+
+        let mut accounts: Vec<String> = (0..11).map(|i| format!("account{i}")).collect();
+        accounts[EXACT_OUT_ROUTE_SOURCE_MINT_INDEX] =
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string();
+        accounts[EXACT_OUT_ROUTE_DESTINATION_MINT_INDEX] =
+            "So11111111111111111111111111111111111111112".to_string();
+
+        let result = parse_jupiter_swap_instruction(&data, &accounts).unwrap();
+        match &result {
+            JupiterSwapInstruction::ExactOutRoute {
+                in_amount,
+                out_amount,
+                hops,
+                ..
+            } => {
+                assert_eq!(*in_amount, 25_000_000);
+                assert_eq!(*out_amount, 5_000_000);
+                assert_eq!(*hops, 1);
+            }
+            _ => panic!("Expected ExactOutRoute instruction, got {result:?}"),
+        }
+        assert_eq!(
+            format_jupiter_swap_summary(&result),
+            "Swap 25 USDC -> 0.005 SOL via Jupiter"
+        );
+
+        let fields = create_jupiter_swap_expanded_fields(
+            &result,
+            "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+            &data,
+        )
+        .unwrap();
+        let labels: Vec<&str> = fields
+            .iter()
+            .map(|f| f.signable_payload_field.label().as_str())
+            .collect();
+        assert!(labels.contains(&"Quoted Input Amount"));
+        assert!(labels.contains(&"Output Amount"));
+    }
+
+    #[test]
+    fn test_jupiter_route_summary_without_source_mint() {
+        // `route` does not expose the source mint, so the input side falls back to raw units
+        let mut prefix = JUPITER_ROUTE_DISCRIMINATOR.to_vec();
+        prefix.extend_from_slice(&1u32.to_le_bytes());
+        prefix.extend_from_slice(&[0x00, 0x64, 0x00, 0x01]);
+        let data = build_route_data(&prefix, 2_000_000, 1_000_000, 50);
+
+        let mut accounts: Vec<String> = (0..9).map(|i| format!("account{i}")).collect();
+        accounts[ROUTE_DESTINATION_MINT_INDEX] =
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string();
+
+        let result = parse_jupiter_swap_instruction(&data, &accounts).unwrap();
+        assert_eq!(
+            format_jupiter_swap_summary(&result),
+            "Swap 2000000 Unknown -> 1 USDC via Jupiter"
+        );
+    }
 }
@@ -0,0 +1,23 @@
+use crate::core::{SolanaIntegrationConfig, SolanaIntegrationConfigData};
+use std::collections::HashMap;
+
+pub const LIDO_PROGRAM_ID: &str = "CrX7kMhLC3cSsXJdT7JDgqrRVWGnUpX3gfEfxxU2NVLi";
+
+pub struct LidoConfig;
+
+impl SolanaIntegrationConfig for LidoConfig {
+    fn new() -> Self {
+        Self
+    }
+
+    fn data(&self) -> &SolanaIntegrationConfigData {
+        static DATA: std::sync::OnceLock<SolanaIntegrationConfigData> = std::sync::OnceLock::new();
+        DATA.get_or_init(|| {
+            let mut programs = HashMap::new();
+            let mut lido_instructions = HashMap::new();
+            lido_instructions.insert("*", vec!["*"]);
+            programs.insert(LIDO_PROGRAM_ID, lido_instructions);
+            SolanaIntegrationConfigData { programs }
+        })
+    }
+}
@@ -0,0 +1,323 @@
+//! Lido on Solana liquid staking preset
+//!
+//! Deposits and stSOL withdrawals are decoded into typed fields. Lido on Solana has stopped
+//! accepting new stake, so withdrawals are most of the remaining traffic; maintenance operations
+//! are shown by instruction tag.
+
+mod config;
+
+use crate::core::{
+    InstructionVisualizer, SolanaIntegrationConfig, VisualizerContext, VisualizerKind,
+};
+use crate::utils::{account_at, format_token_amount};
+use config::LidoConfig;
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::{
+    create_amount_field, create_number_field, create_raw_data_field, create_text_field,
+};
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+// Borsh enum tags, from https://github.com/lidofinance/solido/blob/main/program/src/instruction.rs
+const LIDO_DEPOSIT_TAG: u8 = 1;
+const LIDO_WITHDRAW_TAG: u8 = 2;
+
+// Account positions in the Lido deposit instruction
+const DEPOSIT_USER_INDEX: usize = 1;
+const DEPOSIT_RECIPIENT_INDEX: usize = 2;
+
+// Account positions in the Lido withdraw instruction. The burned stSOL is split off the
+// validator's stake account into a new stake account owned by the stSOL owner.
+const WITHDRAW_OWNER_INDEX: usize = 1;
+const WITHDRAW_ST_SOL_ACCOUNT_INDEX: usize = 2;
+const WITHDRAW_VALIDATOR_VOTE_INDEX: usize = 4;
+const WITHDRAW_DESTINATION_STAKE_INDEX: usize = 6;
+
+// Both SOL and stSOL use 9 decimals
+const SOL_DECIMALS: u8 = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LidoInstruction {
+    Deposit { lamports: u64 },
+    Withdraw { st_lamports: u64 },
+    Other { tag: u8 },
+}
+
+// Create a static instance that we can reference
+static LIDO_CONFIG: LidoConfig = LidoConfig;
+
+pub struct LidoVisualizer;
+
+impl InstructionVisualizer for LidoVisualizer {
+    fn visualize_tx_commands(
+        &self,
+        context: &VisualizerContext,
+    ) -> Result<AnnotatedPayloadField, VisualSignError> {
+        let instruction = context
+            .current_instruction()
+            .ok_or_else(|| VisualSignError::MissingData("No instruction found".into()))?;
+
+        let lido_instruction = parse_lido_instruction(&instruction.data)
+            .map_err(|e| VisualSignError::DecodeError(e.to_string()))?;
+
+        let instruction_text = format_lido_instruction(&lido_instruction);
+
+        let mut condensed_fields = vec![create_text_field("Instruction", &instruction_text)?];
+        let mut expanded_fields = vec![create_text_field(
+            "Program ID",
+            &instruction.program_id.to_string(),
+        )?];
+
+        match &lido_instruction {
+            LidoInstruction::Deposit { lamports } => {
+                let exchange =
+                    "stSOL is minted at the current Lido exchange rate (1 stSOL > 1 SOL)";
+                condensed_fields.push(create_text_field("Expected Exchange", exchange)?);
+
+                expanded_fields.push(create_amount_field(
+                    "Deposit Amount",
+                    &format_token_amount(*lamports, SOL_DECIMALS),
+                    "SOL",
+                )?);
+                expanded_fields.push(create_text_field(
+                    "SOL Source",
+                    &account_at(instruction, DEPOSIT_USER_INDEX),
+                )?);
+                expanded_fields.push(create_text_field(
+                    "stSOL Recipient",
+                    &account_at(instruction, DEPOSIT_RECIPIENT_INDEX),
+                )?);
+                expanded_fields.push(create_text_field("Expected Exchange", exchange)?);
+            }
+            LidoInstruction::Withdraw { st_lamports } => {
+                let stake_account = account_at(instruction, WITHDRAW_DESTINATION_STAKE_INDEX);
+                let recipient = account_at(instruction, WITHDRAW_OWNER_INDEX);
+                condensed_fields.push(create_text_field("Stake Account", &stake_account)?);
+
+                expanded_fields.push(create_amount_field(
+                    "Withdraw Amount",
+                    &format_token_amount(*st_lamports, SOL_DECIMALS),
+                    "stSOL",
+                )?);
+                expanded_fields.push(create_text_field(
+                    "stSOL Source",
+                    &account_at(instruction, WITHDRAW_ST_SOL_ACCOUNT_INDEX),
+                )?);
+                expanded_fields.push(create_text_field("Stake Account", &stake_account)?);
+                expanded_fields.push(create_text_field("Stake Account Owner", &recipient)?);
+                expanded_fields.push(create_text_field(
+                    "Validator Vote Account",
+                    &account_at(instruction, WITHDRAW_VALIDATOR_VOTE_INDEX),
+                )?);
+            }
+            LidoInstruction::Other { tag } => {
+                expanded_fields.push(create_number_field(
+                    "Instruction Tag",
+                    &tag.to_string(),
+                    "",
+                )?);
+            }
+        }
+
+        expanded_fields.push(create_raw_data_field(
+            &instruction.data,
+            Some(hex::encode(&instruction.data)),
+        )?);
+
+        let preview_layout = SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: instruction_text.clone(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: String::new(),
+            }),
+            condensed: Some(SignablePayloadFieldListLayout {
+                fields: condensed_fields,
+            }),
+            expanded: Some(SignablePayloadFieldListLayout {
+                fields: expanded_fields,
+            }),
+        };
+
+        Ok(AnnotatedPayloadField {
+            static_annotation: None,
+            dynamic_annotation: None,
+            signable_payload_field: SignablePayloadField::PreviewLayout {
+                common: SignablePayloadFieldCommon {
+                    label: format!("Instruction {}", context.instruction_index() + 1),
+                    fallback_text: format!(
+                        "Program ID: {}\nData: {}",
+                        instruction.program_id,
+                        hex::encode(&instruction.data)
+                    ),
+                },
+                preview_layout,
+            },
+        })
+    }
+
+    fn get_config(&self) -> Option<&dyn SolanaIntegrationConfig> {
+        Some(&LIDO_CONFIG)
+    }
+
+    fn kind(&self) -> VisualizerKind {
+        VisualizerKind::LiquidStaking("Lido")
+    }
+}
+
+fn parse_lido_instruction(data: &[u8]) -> Result<LidoInstruction, &'static str> {
+    let (&tag, args) = data.split_first().ok_or("Empty instruction data")?;
+
+    let amount = || {
+        args.get(0..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or("Instruction data too short")
+    };
+    match tag {
+        LIDO_DEPOSIT_TAG => Ok(LidoInstruction::Deposit {
+            lamports: amount()?,
+        }),
+        LIDO_WITHDRAW_TAG => Ok(LidoInstruction::Withdraw {
+            st_lamports: amount()?,
+        }),
+        tag => Ok(LidoInstruction::Other { tag }),
+    }
+}
+
+fn format_lido_instruction(instruction: &LidoInstruction) -> String {
+    match instruction {
+        LidoInstruction::Deposit { lamports } => format!(
+            "Lido: Stake {} SOL for stSOL",
+            format_token_amount(*lamports, SOL_DECIMALS)
+        ),
+        LidoInstruction::Withdraw { st_lamports } => format!(
+            "Lido: Withdraw {} stSOL as a stake account",
+            format_token_amount(*st_lamports, SOL_DECIMALS)
+        ),
+        LidoInstruction::Other { tag } => format!("Lido: Instruction {tag}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_parser::solana::structs::SolanaAccount;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_lido_instruction() {
+        let mut deposit = vec![LIDO_DEPOSIT_TAG];
+        deposit.extend_from_slice(&3_500_000_000u64.to_le_bytes());
+        assert_eq!(
+            parse_lido_instruction(&deposit),
+            Ok(LidoInstruction::Deposit {
+                lamports: 3_500_000_000
+            })
+        );
+        let mut withdraw = vec![LIDO_WITHDRAW_TAG];
+        withdraw.extend_from_slice(&2_000_000_000u64.to_le_bytes());
+        withdraw.extend_from_slice(&3u32.to_le_bytes()); // validator index
+        assert_eq!(
+            parse_lido_instruction(&withdraw),
+            Ok(LidoInstruction::Withdraw {
+                st_lamports: 2_000_000_000
+            })
+        );
+        assert_eq!(
+            parse_lido_instruction(&[7]),
+            Ok(LidoInstruction::Other { tag: 7 })
+        );
+        assert!(parse_lido_instruction(&[LIDO_DEPOSIT_TAG, 1, 2]).is_err());
+        assert!(parse_lido_instruction(&[]).is_err());
+    }
+
+    #[test]
+    fn test_lido_deposit_fields() {
+        let mut data = vec![LIDO_DEPOSIT_TAG];
+        data.extend_from_slice(&3_500_000_000u64.to_le_bytes());
+        let accounts: Vec<AccountMeta> = (0..8)
+            .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+            .collect();
+        let recipient = accounts[DEPOSIT_RECIPIENT_INDEX].pubkey.to_string();
+        let instructions = vec![Instruction {
+            program_id: Pubkey::from_str(config::LIDO_PROGRAM_ID).unwrap(),
+            accounts,
+            data,
+        }];
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let context = VisualizerContext::new(&sender, 0, &instructions);
+
+        let field = LidoVisualizer.visualize_tx_commands(&context).unwrap();
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } =
+            field.signable_payload_field
+        else {
+            panic!("Expected PreviewLayout");
+        };
+
+        assert_eq!(
+            preview_layout.title.unwrap().text,
+            "Lido: Stake 3.5 SOL for stSOL"
+        );
+        let expanded = preview_layout.expanded.unwrap().fields;
+        assert!(expanded.iter().any(|f| {
+            f.signable_payload_field.label() == "stSOL Recipient"
+                && f.signable_payload_field.fallback_text() == &recipient
+        }));
+    }
+
+    #[test]
+    fn test_lido_withdraw_fields() {
+        let mut data = vec![LIDO_WITHDRAW_TAG];
+        data.extend_from_slice(&2_000_000_000u64.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        let accounts: Vec<AccountMeta> = (0..13)
+            .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+            .collect();
+        let owner = accounts[WITHDRAW_OWNER_INDEX].pubkey.to_string();
+        let stake_account = accounts[WITHDRAW_DESTINATION_STAKE_INDEX]
+            .pubkey
+            .to_string();
+        let instructions = vec![Instruction {
+            program_id: Pubkey::from_str(config::LIDO_PROGRAM_ID).unwrap(),
+            accounts,
+            data,
+        }];
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let context = VisualizerContext::new(&sender, 0, &instructions);
+
+        let field = LidoVisualizer.visualize_tx_commands(&context).unwrap();
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } =
+            field.signable_payload_field
+        else {
+            panic!("Expected PreviewLayout");
+        };
+
+        assert_eq!(
+            preview_layout.title.unwrap().text,
+            "Lido: Withdraw 2 stSOL as a stake account"
+        );
+        let expanded = preview_layout.expanded.unwrap().fields;
+        let text_of = |label: &str| {
+            expanded
+                .iter()
+                .find(|f| f.signable_payload_field.label() == label)
+                .map(|f| f.signable_payload_field.fallback_text().clone())
+        };
+        assert_eq!(text_of("Withdraw Amount").as_deref(), Some("2 stSOL"));
+        assert_eq!(text_of("Stake Account"), Some(stake_account));
+        assert_eq!(text_of("Stake Account Owner"), Some(owner));
+    }
+}
@@ -0,0 +1,23 @@
+use crate::core::{SolanaIntegrationConfig, SolanaIntegrationConfigData};
+use std::collections::HashMap;
+
+pub const MARINADE_PROGRAM_ID: &str = "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD";
+
+pub struct MarinadeConfig;
+
+impl SolanaIntegrationConfig for MarinadeConfig {
+    fn new() -> Self {
+        Self
+    }
+
+    fn data(&self) -> &SolanaIntegrationConfigData {
+        static DATA: std::sync::OnceLock<SolanaIntegrationConfigData> = std::sync::OnceLock::new();
+        DATA.get_or_init(|| {
+            let mut programs = HashMap::new();
+            let mut marinade_instructions = HashMap::new();
+            marinade_instructions.insert("*", vec!["*"]);
+            programs.insert(MARINADE_PROGRAM_ID, marinade_instructions);
+            SolanaIntegrationConfigData { programs }
+        })
+    }
+}
@@ -0,0 +1,422 @@
+//! Marinade liquid staking preset for Solana
+
+mod config;
+
+use crate::core::{
+    InstructionVisualizer, SolanaIntegrationConfig, VisualizerContext, VisualizerKind,
+};
+use crate::utils::{account_at, format_token_amount};
+use config::MarinadeConfig;
+use solana_sdk::instruction::Instruction;
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::{
+    create_amount_field, create_number_field, create_raw_data_field, create_text_field,
+};
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+// Marinade instruction discriminators (Anchor: first 8 bytes of sha256("global:<name>"))
+const MARINADE_DEPOSIT_DISCRIMINATOR: [u8; 8] = [0xf2, 0x23, 0xc6, 0x89, 0x52, 0xe1, 0xf2, 0xb6];
+const MARINADE_DEPOSIT_STAKE_ACCOUNT_DISCRIMINATOR: [u8; 8] =
+    [0x6e, 0x82, 0x73, 0x29, 0xa4, 0x66, 0x02, 0x3b];
+const MARINADE_LIQUID_UNSTAKE_DISCRIMINATOR: [u8; 8] =
+    [0x1e, 0x1e, 0x77, 0xf0, 0xbf, 0xe3, 0x0c, 0x10];
+const MARINADE_ORDER_UNSTAKE_DISCRIMINATOR: [u8; 8] =
+    [0x61, 0xa7, 0x90, 0x6b, 0x75, 0xbe, 0x80, 0x24];
+const MARINADE_CLAIM_DISCRIMINATOR: [u8; 8] = [0x3e, 0xc6, 0xd6, 0xc1, 0xd5, 0x9f, 0x6c, 0xd2];
+const MARINADE_ADD_LIQUIDITY_DISCRIMINATOR: [u8; 8] =
+    [0xb5, 0x9d, 0x59, 0x43, 0x8f, 0xb6, 0x34, 0x48];
+const MARINADE_REMOVE_LIQUIDITY_DISCRIMINATOR: [u8; 8] =
+    [0x50, 0x55, 0xd1, 0x48, 0x18, 0xce, 0xb1, 0x6c];
+
+// Both SOL and mSOL use 9 decimals
+const SOL_DECIMALS: u8 = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarinadeInstruction {
+    Deposit { lamports: u64 },
+    DepositStakeAccount { validator_index: u32 },
+    LiquidUnstake { msol_amount: u64 },
+    OrderUnstake { msol_amount: u64 },
+    Claim,
+    AddLiquidity { lamports: u64 },
+    RemoveLiquidity { tokens: u64 },
+    Unknown,
+}
+
+// Create a static instance that we can reference
+static MARINADE_CONFIG: MarinadeConfig = MarinadeConfig;
+
+pub struct MarinadeVisualizer;
+
+impl InstructionVisualizer for MarinadeVisualizer {
+    fn visualize_tx_commands(
+        &self,
+        context: &VisualizerContext,
+    ) -> Result<AnnotatedPayloadField, VisualSignError> {
+        let instruction = context
+            .current_instruction()
+            .ok_or_else(|| VisualSignError::MissingData("No instruction found".into()))?;
+
+        let marinade_instruction = parse_marinade_instruction(&instruction.data)
+            .map_err(|e| VisualSignError::DecodeError(e.to_string()))?;
+
+        let instruction_text = format_marinade_instruction(&marinade_instruction);
+
+        let mut condensed_fields = vec![create_text_field("Instruction", &instruction_text)?];
+        if let Some(exchange) = expected_exchange(&marinade_instruction) {
+            condensed_fields.push(create_text_field("Expected Exchange", exchange)?);
+        }
+
+        let condensed = SignablePayloadFieldListLayout {
+            fields: condensed_fields,
+        };
+
+        let expanded = SignablePayloadFieldListLayout {
+            fields: create_marinade_expanded_fields(&marinade_instruction, instruction)?,
+        };
+
+        let preview_layout = SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: instruction_text.clone(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: String::new(),
+            }),
+            condensed: Some(condensed),
+            expanded: Some(expanded),
+        };
+
+        Ok(AnnotatedPayloadField {
+            static_annotation: None,
+            dynamic_annotation: None,
+            signable_payload_field: SignablePayloadField::PreviewLayout {
+                common: SignablePayloadFieldCommon {
+                    label: format!("Instruction {}", context.instruction_index() + 1),
+                    fallback_text: format!(
+                        "Program ID: {}\nData: {}",
+                        instruction.program_id,
+                        hex::encode(&instruction.data)
+                    ),
+                },
+                preview_layout,
+            },
+        })
+    }
+
+    fn get_config(&self) -> Option<&dyn SolanaIntegrationConfig> {
+        Some(&MARINADE_CONFIG)
+    }
+
+    fn kind(&self) -> VisualizerKind {
+        VisualizerKind::LiquidStaking("Marinade")
+    }
+}
+
+fn parse_marinade_instruction(data: &[u8]) -> Result<MarinadeInstruction, &'static str> {
+    if data.len() < 8 {
+        return Err("Invalid instruction data length");
+    }
+
+    let discriminator = &data[0..8];
+    let args = &data[8..];
+
+    match discriminator {
+        d if d == MARINADE_DEPOSIT_DISCRIMINATOR => Ok(MarinadeInstruction::Deposit {
+            lamports: read_u64(args)?,
+        }),
+        d if d == MARINADE_DEPOSIT_STAKE_ACCOUNT_DISCRIMINATOR => {
+            Ok(MarinadeInstruction::DepositStakeAccount {
+                validator_index: read_u32(args)?,
+            })
+        }
+        d if d == MARINADE_LIQUID_UNSTAKE_DISCRIMINATOR => Ok(MarinadeInstruction::LiquidUnstake {
+            msol_amount: read_u64(args)?,
+        }),
+        d if d == MARINADE_ORDER_UNSTAKE_DISCRIMINATOR => Ok(MarinadeInstruction::OrderUnstake {
+            msol_amount: read_u64(args)?,
+        }),
+        d if d == MARINADE_CLAIM_DISCRIMINATOR => Ok(MarinadeInstruction::Claim),
+        d if d == MARINADE_ADD_LIQUIDITY_DISCRIMINATOR => Ok(MarinadeInstruction::AddLiquidity {
+            lamports: read_u64(args)?,
+        }),
+        d if d == MARINADE_REMOVE_LIQUIDITY_DISCRIMINATOR => {
+            Ok(MarinadeInstruction::RemoveLiquidity {
+                tokens: read_u64(args)?,
+            })
+        }
+        _ => Ok(MarinadeInstruction::Unknown),
+    }
+}
+
+fn read_u64(args: &[u8]) -> Result<u64, &'static str> {
+    args.get(0..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or("Instruction data too short")
+}
+
+fn read_u32(args: &[u8]) -> Result<u32, &'static str> {
+    args.get(0..4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or("Instruction data too short")
+}
+
+fn format_marinade_instruction(instruction: &MarinadeInstruction) -> String {
+    match instruction {
+        MarinadeInstruction::Deposit { lamports } => format!(
+            "Marinade: Stake {} SOL for mSOL",
+            format_token_amount(*lamports, SOL_DECIMALS)
+        ),
+        MarinadeInstruction::DepositStakeAccount { .. } => {
+            "Marinade: Deposit Stake Account for mSOL".to_string()
+        }
+        MarinadeInstruction::LiquidUnstake { msol_amount } => format!(
+            "Marinade: Liquid Unstake {} mSOL",
+            format_token_amount(*msol_amount, SOL_DECIMALS)
+        ),
+        MarinadeInstruction::OrderUnstake { msol_amount } => format!(
+            "Marinade: Order Unstake {} mSOL",
+            format_token_amount(*msol_amount, SOL_DECIMALS)
+        ),
+        MarinadeInstruction::Claim => "Marinade: Claim Unstake Ticket".to_string(),
+        MarinadeInstruction::AddLiquidity { lamports } => format!(
+            "Marinade: Add {} SOL Liquidity",
+            format_token_amount(*lamports, SOL_DECIMALS)
+        ),
+        MarinadeInstruction::RemoveLiquidity { tokens } => format!(
+            "Marinade: Remove {} LP Tokens of Liquidity",
+            format_token_amount(*tokens, SOL_DECIMALS)
+        ),
+        MarinadeInstruction::Unknown => "Marinade: Unknown Instruction".to_string(),
+    }
+}
+
+/// How the signer's SOL and mSOL are exchanged, since the rate itself is only known on-chain
+fn expected_exchange(instruction: &MarinadeInstruction) -> Option<&'static str> {
+    match instruction {
+        MarinadeInstruction::Deposit { .. } | MarinadeInstruction::DepositStakeAccount { .. } => {
+            Some("mSOL is minted at the current Marinade exchange rate (1 mSOL > 1 SOL)")
+        }
+        MarinadeInstruction::LiquidUnstake { .. } => {
+            Some("mSOL is swapped for SOL immediately through the liquidity pool, minus a fee")
+        }
+        MarinadeInstruction::OrderUnstake { .. } => Some(
+            "mSOL is burned for an unstake ticket; SOL can be claimed after the epoch ends, no fee",
+        ),
+        MarinadeInstruction::Claim => Some("SOL is released from a matured unstake ticket"),
+        MarinadeInstruction::AddLiquidity { .. } => {
+            Some("SOL is added to the mSOL-SOL pool in exchange for LP tokens")
+        }
+        MarinadeInstruction::RemoveLiquidity { .. } => {
+            Some("LP tokens are burned for their share of the pool's SOL and mSOL")
+        }
+        MarinadeInstruction::Unknown => None,
+    }
+}
+
+fn create_marinade_expanded_fields(
+    marinade_instruction: &MarinadeInstruction,
+    instruction: &Instruction,
+) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
+    let account = |index: usize| account_at(instruction, index);
+
+    let mut fields = vec![create_text_field(
+        "Program ID",
+        &instruction.program_id.to_string(),
+    )?];
+
+    // Account positions follow the Marinade IDL for each instruction
+    match marinade_instruction {
+        MarinadeInstruction::Deposit { lamports } => {
+            fields.push(create_amount_field(
+                "Deposit Amount",
+                &format_token_amount(*lamports, SOL_DECIMALS),
+                "SOL",
+            )?);
+            fields.push(create_text_field("SOL Source", &account(6))?);
+            fields.push(create_text_field("mSOL Recipient", &account(7))?);
+        }
+        MarinadeInstruction::DepositStakeAccount { validator_index } => {
+            fields.push(create_text_field("Stake Account", &account(3))?);
+            fields.push(create_text_field("Stake Authority", &account(4))?);
+            fields.push(create_text_field("mSOL Recipient", &account(8))?);
+            fields.push(create_number_field(
+                "Validator Index",
+                &validator_index.to_string(),
+                "",
+            )?);
+        }
+        MarinadeInstruction::LiquidUnstake { msol_amount } => {
+            fields.push(create_amount_field(
+                "Unstake Amount",
+                &format_token_amount(*msol_amount, SOL_DECIMALS),
+                "mSOL",
+            )?);
+            fields.push(create_text_field("mSOL Source", &account(5))?);
+            fields.push(create_text_field("SOL Recipient", &account(7))?);
+        }
+        MarinadeInstruction::OrderUnstake { msol_amount } => {
+            fields.push(create_amount_field(
+                "Unstake Amount",
+                &format_token_amount(*msol_amount, SOL_DECIMALS),
+                "mSOL",
+            )?);
+            fields.push(create_text_field("mSOL Source", &account(2))?);
+            fields.push(create_text_field("Unstake Ticket", &account(4))?);
+        }
+        MarinadeInstruction::Claim => {
+            fields.push(create_text_field("Unstake Ticket", &account(2))?);
+            fields.push(create_text_field("SOL Recipient", &account(3))?);
+        }
+        MarinadeInstruction::AddLiquidity { lamports } => {
+            fields.push(create_amount_field(
+                "Liquidity Amount",
+                &format_token_amount(*lamports, SOL_DECIMALS),
+                "SOL",
+            )?);
+            fields.push(create_text_field("SOL Source", &account(5))?);
+            fields.push(create_text_field("LP Token Recipient", &account(6))?);
+        }
+        MarinadeInstruction::RemoveLiquidity { tokens } => {
+            fields.push(create_amount_field(
+                "LP Token Amount",
+                &format_token_amount(*tokens, SOL_DECIMALS),
+                "LP",
+            )?);
+            fields.push(create_text_field("SOL Recipient", &account(4))?);
+            fields.push(create_text_field("mSOL Recipient", &account(5))?);
+        }
+        MarinadeInstruction::Unknown => {}
+    }
+
+    if let Some(exchange) = expected_exchange(marinade_instruction) {
+        fields.push(create_text_field("Expected Exchange", exchange)?);
+    }
+
+    fields.push(create_raw_data_field(
+        &instruction.data,
+        Some(hex::encode(&instruction.data)),
+    )?);
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_parser::solana::structs::SolanaAccount;
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    fn marinade_instruction(discriminator: [u8; 8], args: &[u8], accounts: usize) -> Instruction {
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(args);
+        Instruction {
+            program_id: Pubkey::from_str(config::MARINADE_PROGRAM_ID).unwrap(),
+            accounts: (0..accounts)
+                .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                .collect(),
+            data,
+        }
+    }
+
+    fn visualize(instruction: Instruction) -> SignablePayloadFieldPreviewLayout {
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let instructions = vec![instruction];
+        let context = VisualizerContext::new(&sender, 0, &instructions);
+        match MarinadeVisualizer
+            .visualize_tx_commands(&context)
+            .unwrap()
+            .signable_payload_field
+        {
+            SignablePayloadField::PreviewLayout { preview_layout, .. } => preview_layout,
+            other => panic!("Expected PreviewLayout, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_marinade_instructions() {
+        let amount = 1_250_000_000u64.to_le_bytes();
+        let mut deposit = MARINADE_DEPOSIT_DISCRIMINATOR.to_vec();
+        deposit.extend_from_slice(&amount);
+        assert_eq!(
+            parse_marinade_instruction(&deposit),
+            Ok(MarinadeInstruction::Deposit {
+                lamports: 1_250_000_000
+            })
+        );
+
+        let mut order_unstake = MARINADE_ORDER_UNSTAKE_DISCRIMINATOR.to_vec();
+        order_unstake.extend_from_slice(&amount);
+        assert_eq!(
+            parse_marinade_instruction(&order_unstake),
+            Ok(MarinadeInstruction::OrderUnstake {
+                msol_amount: 1_250_000_000
+            })
+        );
+
+        assert_eq!(
+            parse_marinade_instruction(&MARINADE_CLAIM_DISCRIMINATOR),
+            Ok(MarinadeInstruction::Claim)
+        );
+        assert_eq!(
+            parse_marinade_instruction(&[0u8; 8]),
+            Ok(MarinadeInstruction::Unknown)
+        );
+        assert!(parse_marinade_instruction(&MARINADE_LIQUID_UNSTAKE_DISCRIMINATOR).is_err());
+        assert!(parse_marinade_instruction(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_deposit_summary() {
+        let instruction = marinade_instruction(
+            MARINADE_DEPOSIT_DISCRIMINATOR,
+            &2_000_000_000u64.to_le_bytes(),
+            11,
+        );
+        let mint_to = instruction.accounts[7].pubkey.to_string();
+        let layout = visualize(instruction);
+
+        assert_eq!(layout.title.unwrap().text, "Marinade: Stake 2 SOL for mSOL");
+        let condensed = layout.condensed.unwrap().fields;
+        assert_eq!(condensed.len(), 2);
+        assert_eq!(
+            condensed[1].signable_payload_field.label(),
+            "Expected Exchange"
+        );
+
+        let expanded = layout.expanded.unwrap().fields;
+        assert!(expanded.iter().any(|f| {
+            f.signable_payload_field.label() == "mSOL Recipient"
+                && f.signable_payload_field.fallback_text() == &mint_to
+        }));
+    }
+
+    #[test]
+    fn test_liquid_unstake_summary() {
+        let layout = visualize(marinade_instruction(
+            MARINADE_LIQUID_UNSTAKE_DISCRIMINATOR,
+            &500_000_000u64.to_le_bytes(),
+            10,
+        ));
+
+        assert_eq!(
+            layout.title.unwrap().text,
+            "Marinade: Liquid Unstake 0.5 mSOL"
+        );
+        let expanded = layout.expanded.unwrap().fields;
+        assert!(expanded.iter().any(|f| {
+            f.signable_payload_field.label() == "Unstake Amount"
+                && f.signable_payload_field.fallback_text() == "0.5 mSOL"
+        }));
+    }
+}
@@ -0,0 +1,28 @@
+//! Configuration for the SPL Memo program integration
+
+use crate::core::{SolanaIntegrationConfig, SolanaIntegrationConfigData};
+
+/// SPL Memo v1 (legacy) and v2 program ids
+pub const MEMO_V1_PROGRAM_ID: &str = "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo";
+pub const MEMO_V2_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+pub struct MemoConfig;
+
+impl SolanaIntegrationConfig for MemoConfig {
+    fn new() -> Self {
+        Self
+    }
+
+    fn data(&self) -> &SolanaIntegrationConfigData {
+        static DATA: std::sync::OnceLock<SolanaIntegrationConfigData> = std::sync::OnceLock::new();
+        DATA.get_or_init(|| {
+            let mut programs = std::collections::HashMap::new();
+            for program_id in [MEMO_V1_PROGRAM_ID, MEMO_V2_PROGRAM_ID] {
+                let mut memo_instructions = std::collections::HashMap::new();
+                memo_instructions.insert("*", vec!["*"]);
+                programs.insert(program_id, memo_instructions);
+            }
+            SolanaIntegrationConfigData { programs }
+        })
+    }
+}
@@ -0,0 +1,231 @@
+//! SPL Memo program preset for Solana
+
+mod config;
+
+use crate::core::{
+    InstructionVisualizer, SolanaIntegrationConfig, VisualizerContext, VisualizerKind,
+};
+use config::MemoConfig;
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::{create_raw_data_field, create_text_field};
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+// Create a static instance that we can reference
+static MEMO_CONFIG: MemoConfig = MemoConfig;
+
+pub struct MemoVisualizer;
+
+impl InstructionVisualizer for MemoVisualizer {
+    fn visualize_tx_commands(
+        &self,
+        context: &VisualizerContext,
+    ) -> Result<AnnotatedPayloadField, VisualSignError> {
+        let instruction = context
+            .current_instruction()
+            .ok_or_else(|| VisualSignError::MissingData("No instruction found".into()))?;
+
+        let memo = MemoContents::decode(&instruction.data);
+
+        let mut condensed_fields = vec![
+            create_text_field("Instruction", "Memo")?,
+            create_text_field("Memo", memo.display_text())?,
+        ];
+        let mut expanded_fields = vec![
+            create_text_field("Program ID", &instruction.program_id.to_string())?,
+            create_text_field("Memo", memo.display_text())?,
+        ];
+
+        if let MemoContents::Hex(_) = memo {
+            let note = "Hex: memo contains characters outside the allowed charset";
+            condensed_fields.push(create_text_field("Memo Encoding", note)?);
+            expanded_fields.push(create_text_field("Memo Encoding", note)?);
+        }
+
+        // Every account passed to the memo program must sign the transaction
+        for (index, signer) in instruction.accounts.iter().enumerate() {
+            expanded_fields.push(create_text_field(
+                &format!("Signer {}", index + 1),
+                &signer.pubkey.to_string(),
+            )?);
+        }
+
+        expanded_fields.push(create_raw_data_field(
+            &instruction.data,
+            Some(hex::encode(&instruction.data)),
+        )?);
+
+        let preview_layout = SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: "Memo".to_string(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: String::new(),
+            }),
+            condensed: Some(SignablePayloadFieldListLayout {
+                fields: condensed_fields,
+            }),
+            expanded: Some(SignablePayloadFieldListLayout {
+                fields: expanded_fields,
+            }),
+        };
+
+        Ok(AnnotatedPayloadField {
+            static_annotation: None,
+            dynamic_annotation: None,
+            signable_payload_field: SignablePayloadField::PreviewLayout {
+                common: SignablePayloadFieldCommon {
+                    label: format!("Instruction {}", context.instruction_index() + 1),
+                    fallback_text: format!(
+                        "Program ID: {}\nData: {}",
+                        instruction.program_id,
+                        hex::encode(&instruction.data)
+                    ),
+                },
+                preview_layout,
+            },
+        })
+    }
+
+    fn get_config(&self) -> Option<&dyn SolanaIntegrationConfig> {
+        Some(&MEMO_CONFIG)
+    }
+
+    fn kind(&self) -> VisualizerKind {
+        VisualizerKind::Payments("Memo")
+    }
+}
+
+/// Memo data as it can be shown to the signer
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MemoContents {
+    /// Printable ASCII that passes the payload charset policy unchanged
+    Text(String),
+    /// Anything else, hex encoded so the payload stays within the charset policy
+    Hex(String),
+}
+
+impl MemoContents {
+    fn decode(data: &[u8]) -> Self {
+        // Only printable ASCII and spaces are allowed through as text; newlines and other
+        // whitespace could be used to make a memo look like separate fields
+        let printable = data
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ');
+        match std::str::from_utf8(data) {
+            Ok(text) if printable && !text.is_empty() => Self::Text(text.to_string()),
+            _ => Self::Hex(format!("0x{}", hex::encode(data))),
+        }
+    }
+
+    fn display_text(&self) -> &str {
+        match self {
+            Self::Text(text) | Self::Hex(text) => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_parser::solana::structs::SolanaAccount;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    fn memo_instruction(data: &[u8], signers: &[Pubkey]) -> Instruction {
+        Instruction {
+            program_id: Pubkey::from_str(config::MEMO_V2_PROGRAM_ID).unwrap(),
+            accounts: signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true))
+                .collect(),
+            data: data.to_vec(),
+        }
+    }
+
+    fn visualize(instruction: Instruction) -> SignablePayloadFieldPreviewLayout {
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let instructions = vec![instruction];
+        let context = VisualizerContext::new(&sender, 0, &instructions);
+        match MemoVisualizer
+            .visualize_tx_commands(&context)
+            .unwrap()
+            .signable_payload_field
+        {
+            SignablePayloadField::PreviewLayout { preview_layout, .. } => preview_layout,
+            other => panic!("Expected PreviewLayout, got {other:?}"),
+        }
+    }
+
+    fn text_of<'a>(fields: &'a [AnnotatedPayloadField], label: &str) -> Option<&'a str> {
+        fields.iter().find_map(|f| match &f.signable_payload_field {
+            SignablePayloadField::TextV2 { common, text_v2 } if common.label == label => {
+                Some(text_v2.text.as_str())
+            }
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_memo_decode() {
+        assert_eq!(
+            MemoContents::decode(b"invoice #42"),
+            MemoContents::Text("invoice #42".to_string())
+        );
+        assert_eq!(
+            MemoContents::decode("caf\u{e9}".as_bytes()),
+            MemoContents::Hex("0x636166c3a9".to_string())
+        );
+        assert_eq!(
+            MemoContents::decode(b"line1\nline2"),
+            MemoContents::Hex("0x6c696e65310a6c696e6532".to_string())
+        );
+        assert_eq!(
+            MemoContents::decode(b""),
+            MemoContents::Hex("0x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_memo_fields() {
+        let signer = Pubkey::new_unique();
+        let layout = visualize(memo_instruction(b"hello world", &[signer]));
+
+        let condensed = layout.condensed.unwrap().fields;
+        assert_eq!(text_of(&condensed, "Memo"), Some("hello world"));
+        assert_eq!(text_of(&condensed, "Memo Encoding"), None);
+
+        let expanded = layout.expanded.unwrap().fields;
+        assert_eq!(
+            text_of(&expanded, "Signer 1"),
+            Some(signer.to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_memo_passes_charset_policy() {
+        let layout = visualize(memo_instruction("gm \u{1f31e}".as_bytes(), &[]));
+        let condensed = layout.condensed.unwrap().fields;
+        assert_eq!(text_of(&condensed, "Memo"), Some("0x676d20f09f8c9e"));
+        assert!(text_of(&condensed, "Memo Encoding").is_some());
+
+        let payload = visualsign::SignablePayload::new(
+            0,
+            "Memo Test".to_string(),
+            None,
+            condensed
+                .into_iter()
+                .map(|f| f.signable_payload_field)
+                .collect(),
+            "SolanaTx".to_string(),
+        );
+        assert!(payload.validate_charset().is_ok());
+    }
+}
@@ -1,7 +1,13 @@
 pub mod associated_token_account;
+pub mod cctp_token_messenger;
 pub mod compute_budget;
 pub mod jupiter_swap;
+pub mod lido;
+pub mod marinade;
+pub mod memo;
+pub mod stake;
 pub mod stakepool;
 pub mod system;
 pub mod token_2022;
 pub mod unknown_program;
+pub mod wormhole_token_bridge;
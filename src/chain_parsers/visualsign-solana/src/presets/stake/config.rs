@@ -0,0 +1,26 @@
+//! Configuration for the native Stake program integration
+
+use crate::core::{SolanaIntegrationConfig, SolanaIntegrationConfigData};
+use std::collections::HashMap;
+
+pub struct StakeConfig;
+
+impl SolanaIntegrationConfig for StakeConfig {
+    fn new() -> Self {
+        Self
+    }
+
+    fn data(&self) -> &SolanaIntegrationConfigData {
+        static DATA: std::sync::OnceLock<SolanaIntegrationConfigData> = std::sync::OnceLock::new();
+        DATA.get_or_init(|| {
+            let mut programs = HashMap::new();
+            let mut stake_instructions = HashMap::new();
+            stake_instructions.insert("*", vec!["*"]);
+            programs.insert(
+                "Stake11111111111111111111111111111111111111",
+                stake_instructions,
+            );
+            SolanaIntegrationConfigData { programs }
+        })
+    }
+}
@@ -5,6 +5,7 @@ mod config;
 use crate::core::{
     InstructionVisualizer, SolanaIntegrationConfig, VisualizerContext, VisualizerKind,
 };
+use crate::utils::{account_at, format_token_amount};
 use config::SystemConfig;
 use solana_program::system_instruction::SystemInstruction;
 use visualsign::errors::VisualSignError;
@@ -13,6 +14,8 @@ use visualsign::{
     SignablePayloadFieldCommon,
 };
 
+const SOL_DECIMALS: u8 = 9;
+
 // Create a static instance that we can reference
 static SYSTEM_CONFIG: SystemConfig = SystemConfig;
 
@@ -78,7 +81,10 @@ fn create_system_preview_layout(
                     dynamic_annotation: None,
                     signable_payload_field: SignablePayloadField::AmountV2 {
                         common: SignablePayloadFieldCommon {
-                            fallback_text: format!("{} SOL", (*lamports as f64) / 1_000_000_000.0),
+                            fallback_text: format!(
+                                "{} SOL",
+                                format_token_amount(*lamports, SOL_DECIMALS)
+                            ),
                             label: "Transfer Amount".to_string(),
                         },
                         amount_v2: SignablePayloadFieldAmountV2 {
@@ -145,7 +151,7 @@ fn create_system_preview_layout(
                 create_text_field("Space", &format!("{space} bytes"))?,
                 create_text_field(
                     "Rent",
-                    &format!("{} SOL", (*lamports as f64) / 1_000_000_000.0),
+                    &format!("{} SOL", format_token_amount(*lamports, SOL_DECIMALS)),
                 )?,
             ];
 
@@ -155,10 +161,7 @@ fn create_system_preview_layout(
                 create_text_field("Payer", &payer)?,
                 create_number_field("Space (bytes)", &space.to_string(), "")?,
                 create_number_field("Rent (lamports)", &lamports.to_string(), "")?,
-                create_text_field(
-                    "Rent (SOL)",
-                    &format!("{}", (*lamports as f64) / 1_000_000_000.0),
-                )?,
+                create_text_field("Rent (SOL)", &format_token_amount(*lamports, SOL_DECIMALS))?,
                 create_text_field("Owner Program", &owner.to_string())?,
                 create_text_field("Program", "System Program")?,
             ];
@@ -197,54 +200,485 @@ fn create_system_preview_layout(
                 },
             })
         }
-        _ => {
-            // Handle other system instructions with basic layout
-            let instruction_name = account_labels::system_instruction_label(instruction);
+        SystemInstruction::Assign { owner } => {
+            let account = account_at(solana_instruction, 0);
 
             let condensed_fields = vec![
-                create_text_field("Action", &instruction_name)?,
+                create_text_field("Action", "Assign")?,
+                create_text_field("New Owner Program", &owner.to_string())?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Assign")?,
+                create_text_field("Account", &account)?,
+                create_text_field("New Owner Program", &owner.to_string())?,
                 create_text_field("Program", "System Program")?,
             ];
 
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::AssignWithSeed { base, seed, owner } => {
+            let account = account_at(solana_instruction, 0);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Assign With Seed")?,
+                create_text_field("New Owner Program", &owner.to_string())?,
+            ];
+
             let expanded_fields = vec![
-                create_text_field("Action", &instruction_name)?,
+                create_text_field("Action", "Assign With Seed")?,
+                create_text_field("Account", &account)?,
+                create_text_field("Base", &base.to_string())?,
+                create_text_field("Seed", seed)?,
+                create_text_field("New Owner Program", &owner.to_string())?,
                 create_text_field("Program", "System Program")?,
-                create_text_field("Instruction Data", &format!("{instruction:?}"))?,
             ];
 
-            let condensed = visualsign::SignablePayloadFieldListLayout {
-                fields: condensed_fields,
-            };
-            let expanded = visualsign::SignablePayloadFieldListLayout {
-                fields: expanded_fields,
-            };
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::Allocate { space } => {
+            let account = account_at(solana_instruction, 0);
 
-            let preview_layout = visualsign::SignablePayloadFieldPreviewLayout {
-                title: Some(visualsign::SignablePayloadFieldTextV2 {
-                    text: instruction_name.to_string(),
-                }),
-                subtitle: Some(visualsign::SignablePayloadFieldTextV2 {
-                    text: String::new(),
-                }),
-                condensed: Some(condensed),
-                expanded: Some(expanded),
-            };
+            let condensed_fields = vec![
+                create_text_field("Action", "Allocate")?,
+                create_text_field("Space", &format!("{space} bytes"))?,
+            ];
 
-            Ok(AnnotatedPayloadField {
-                static_annotation: None,
-                dynamic_annotation: None,
-                signable_payload_field: SignablePayloadField::PreviewLayout {
-                    common: SignablePayloadFieldCommon {
-                        label: format!("Instruction {}", context.instruction_index() + 1),
-                        fallback_text: format!(
-                            "Program ID: {}\nData: {}",
-                            solana_instruction.program_id,
-                            hex::encode(&solana_instruction.data)
-                        ),
-                    },
-                    preview_layout,
-                },
-            })
+            let expanded_fields = vec![
+                create_text_field("Action", "Allocate")?,
+                create_text_field("Account", &account)?,
+                create_number_field("Space (bytes)", &space.to_string(), "")?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::AllocateWithSeed {
+            base,
+            seed,
+            space,
+            owner,
+        } => {
+            let account = account_at(solana_instruction, 0);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Allocate With Seed")?,
+                create_text_field("Space", &format!("{space} bytes"))?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Allocate With Seed")?,
+                create_text_field("Account", &account)?,
+                create_text_field("Base", &base.to_string())?,
+                create_text_field("Seed", seed)?,
+                create_number_field("Space (bytes)", &space.to_string(), "")?,
+                create_text_field("Owner Program", &owner.to_string())?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::CreateAccountWithSeed {
+            base,
+            seed,
+            lamports,
+            space,
+            owner,
+        } => {
+            let payer = account_at(solana_instruction, 0);
+            let new_account = account_at(solana_instruction, 1);
+            let rent_sol = format_token_amount(*lamports, SOL_DECIMALS);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Create Account With Seed")?,
+                create_text_field("Space", &format!("{space} bytes"))?,
+                create_text_field("Rent", &format!("{rent_sol} SOL"))?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Create Account With Seed")?,
+                create_text_field("New Account", &new_account)?,
+                create_text_field("Payer", &payer)?,
+                create_text_field("Base", &base.to_string())?,
+                create_text_field("Seed", seed)?,
+                create_number_field("Space (bytes)", &space.to_string(), "")?,
+                create_number_field("Rent (lamports)", &lamports.to_string(), "")?,
+                create_text_field("Rent (SOL)", &rent_sol)?,
+                create_text_field("Owner Program", &owner.to_string())?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::TransferWithSeed {
+            lamports,
+            from_seed,
+            from_owner,
+        } => {
+            let from = account_at(solana_instruction, 0);
+            let base = account_at(solana_instruction, 1);
+            let to = account_at(solana_instruction, 2);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Transfer With Seed")?,
+                create_amount_field("Transfer Amount", &lamports.to_string(), "lamports")?,
+                create_text_field("To", &to)?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Transfer With Seed")?,
+                create_text_field("From", &from)?,
+                create_text_field("Base", &base)?,
+                create_text_field("Seed", from_seed)?,
+                create_text_field("From Owner Program", &from_owner.to_string())?,
+                create_text_field("To", &to)?,
+                create_amount_field("Transfer Amount", &lamports.to_string(), "lamports")?,
+                create_text_field(
+                    "Transfer Amount (SOL)",
+                    &format_token_amount(*lamports, SOL_DECIMALS),
+                )?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::AdvanceNonceAccount => {
+            let nonce_account = account_at(solana_instruction, 0);
+            let authority = account_at(solana_instruction, 2);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Advance Nonce Account")?,
+                create_text_field("Nonce Account", &nonce_account)?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Advance Nonce Account")?,
+                create_text_field("Nonce Account", &nonce_account)?,
+                create_text_field("Nonce Authority", &authority)?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::WithdrawNonceAccount(lamports) => {
+            let nonce_account = account_at(solana_instruction, 0);
+            let recipient = account_at(solana_instruction, 1);
+            let authority = account_at(solana_instruction, 4);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Withdraw Nonce Account")?,
+                create_amount_field("Withdraw Amount", &lamports.to_string(), "lamports")?,
+                create_text_field("Recipient", &recipient)?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Withdraw Nonce Account")?,
+                create_text_field("Nonce Account", &nonce_account)?,
+                create_text_field("Recipient", &recipient)?,
+                create_text_field("Nonce Authority", &authority)?,
+                create_amount_field("Withdraw Amount", &lamports.to_string(), "lamports")?,
+                create_text_field(
+                    "Withdraw Amount (SOL)",
+                    &format_token_amount(*lamports, SOL_DECIMALS),
+                )?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::InitializeNonceAccount(authority) => {
+            let nonce_account = account_at(solana_instruction, 0);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Initialize Nonce Account")?,
+                create_text_field("Nonce Authority", &authority.to_string())?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Initialize Nonce Account")?,
+                create_text_field("Nonce Account", &nonce_account)?,
+                create_text_field("Nonce Authority", &authority.to_string())?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::AuthorizeNonceAccount(new_authority) => {
+            let nonce_account = account_at(solana_instruction, 0);
+            let current_authority = account_at(solana_instruction, 1);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Authorize Nonce Account")?,
+                create_text_field("New Nonce Authority", &new_authority.to_string())?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Authorize Nonce Account")?,
+                create_text_field("Nonce Account", &nonce_account)?,
+                create_text_field("Current Nonce Authority", &current_authority)?,
+                create_text_field("New Nonce Authority", &new_authority.to_string())?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
+        }
+        SystemInstruction::UpgradeNonceAccount => {
+            let nonce_account = account_at(solana_instruction, 0);
+
+            let condensed_fields = vec![
+                create_text_field("Action", "Upgrade Nonce Account")?,
+                create_text_field("Nonce Account", &nonce_account)?,
+            ];
+
+            let expanded_fields = vec![
+                create_text_field("Action", "Upgrade Nonce Account")?,
+                create_text_field("Nonce Account", &nonce_account)?,
+                create_text_field("Program", "System Program")?,
+            ];
+
+            create_system_instruction_layout(
+                instruction,
+                condensed_fields,
+                expanded_fields,
+                solana_instruction,
+                context,
+            )
         }
     }
 }
+
+/// Wraps the condensed and expanded fields of a system instruction in the standard preview layout
+fn create_system_instruction_layout(
+    instruction: &SystemInstruction,
+    condensed_fields: Vec<AnnotatedPayloadField>,
+    expanded_fields: Vec<AnnotatedPayloadField>,
+    solana_instruction: &solana_sdk::instruction::Instruction,
+    context: &VisualizerContext,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    let preview_layout = visualsign::SignablePayloadFieldPreviewLayout {
+        title: Some(visualsign::SignablePayloadFieldTextV2 {
+            text: account_labels::system_instruction_label(instruction),
+        }),
+        subtitle: Some(visualsign::SignablePayloadFieldTextV2 {
+            text: String::new(),
+        }),
+        condensed: Some(visualsign::SignablePayloadFieldListLayout {
+            fields: condensed_fields,
+        }),
+        expanded: Some(visualsign::SignablePayloadFieldListLayout {
+            fields: expanded_fields,
+        }),
+    };
+
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                label: format!("Instruction {}", context.instruction_index() + 1),
+                fallback_text: format!(
+                    "Program ID: {}\nData: {}",
+                    solana_instruction.program_id,
+                    hex::encode(&solana_instruction.data)
+                ),
+            },
+            preview_layout,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_parser::solana::structs::SolanaAccount;
+    use solana_program::system_instruction;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn visualize(
+        instruction: solana_sdk::instruction::Instruction,
+    ) -> visualsign::SignablePayloadFieldPreviewLayout {
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let instructions = vec![instruction];
+        let context = VisualizerContext::new(&sender, 0, &instructions);
+        match SystemVisualizer
+            .visualize_tx_commands(&context)
+            .unwrap()
+            .signable_payload_field
+        {
+            SignablePayloadField::PreviewLayout { preview_layout, .. } => preview_layout,
+            other => panic!("Expected PreviewLayout, got {other:?}"),
+        }
+    }
+
+    fn expanded_labels(layout: &visualsign::SignablePayloadFieldPreviewLayout) -> Vec<String> {
+        layout
+            .expanded
+            .as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.signable_payload_field.label().clone())
+            .collect()
+    }
+
+    fn expanded_text(
+        layout: &visualsign::SignablePayloadFieldPreviewLayout,
+        label: &str,
+    ) -> Option<String> {
+        layout
+            .expanded
+            .as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .find(|f| f.signable_payload_field.label() == label)
+            .map(|f| f.signable_payload_field.fallback_text().clone())
+    }
+
+    #[test]
+    fn test_assign_and_allocate() {
+        let account = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let layout = visualize(system_instruction::assign(&account, &owner));
+        assert_eq!(expanded_text(&layout, "Account"), Some(account.to_string()));
+        assert_eq!(
+            expanded_text(&layout, "New Owner Program"),
+            Some(owner.to_string())
+        );
+
+        let layout = visualize(system_instruction::allocate(&account, 165));
+        assert_eq!(layout.title.unwrap().text, "Allocate (space: 165)");
+    }
+
+    #[test]
+    fn test_create_account_with_seed() {
+        let payer = Pubkey::new_unique();
+        let base = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let new_account = Pubkey::create_with_seed(&base, "vault", &owner).unwrap();
+
+        let layout = visualize(system_instruction::create_account_with_seed(
+            &payer,
+            &new_account,
+            &base,
+            "vault",
+            2_039_280,
+            165,
+            &owner,
+        ));
+
+        assert_eq!(
+            expanded_labels(&layout),
+            vec![
+                "Action",
+                "New Account",
+                "Payer",
+                "Base",
+                "Seed",
+                "Space (bytes)",
+                "Rent (lamports)",
+                "Rent (SOL)",
+                "Owner Program",
+                "Program",
+            ]
+        );
+        assert_eq!(expanded_text(&layout, "Seed"), Some("vault".to_string()));
+        assert_eq!(
+            expanded_text(&layout, "Rent (SOL)"),
+            Some("0.00203928".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transfer_with_seed() {
+        let base = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let from = Pubkey::create_with_seed(&base, "savings", &owner).unwrap();
+        let to = Pubkey::new_unique();
+
+        let layout = visualize(system_instruction::transfer_with_seed(
+            &from,
+            &base,
+            "savings".to_string(),
+            &owner,
+            &to,
+            1_000_000,
+        ));
+
+        assert_eq!(expanded_text(&layout, "From"), Some(from.to_string()));
+        assert_eq!(expanded_text(&layout, "To"), Some(to.to_string()));
+        assert_eq!(
+            expanded_text(&layout, "Transfer Amount"),
+            Some("1000000 lamports".to_string())
+        );
+        assert_eq!(
+            expanded_text(&layout, "Transfer Amount (SOL)"),
+            Some("0.001".to_string())
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use crate::core::{SolanaIntegrationConfig, SolanaIntegrationConfigData};
+use std::collections::HashMap;
+
+pub const WORMHOLE_TOKEN_BRIDGE_PROGRAM_ID: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+
+pub struct WormholeTokenBridgeConfig;
+
+impl SolanaIntegrationConfig for WormholeTokenBridgeConfig {
+    fn new() -> Self {
+        Self
+    }
+
+    fn data(&self) -> &SolanaIntegrationConfigData {
+        static DATA: std::sync::OnceLock<SolanaIntegrationConfigData> = std::sync::OnceLock::new();
+        DATA.get_or_init(|| {
+            let mut programs = HashMap::new();
+            let mut token_bridge_instructions = HashMap::new();
+            token_bridge_instructions.insert("*", vec!["*"]);
+            programs.insert(WORMHOLE_TOKEN_BRIDGE_PROGRAM_ID, token_bridge_instructions);
+            SolanaIntegrationConfigData { programs }
+        })
+    }
+}
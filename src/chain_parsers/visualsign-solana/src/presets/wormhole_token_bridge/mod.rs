@@ -0,0 +1,337 @@
+//! Wormhole Token Bridge preset
+//!
+//! Transfers out of Solana are decoded into the token, amount, destination chain and recipient.
+//! Other instructions (redeeming transfers, attestations and governance) are shown by name.
+
+mod config;
+
+use crate::core::{
+    InstructionVisualizer, SolanaIntegrationConfig, VisualizerContext, VisualizerKind,
+};
+use crate::utils::{RemoteAddressFormat, account_at, format_remote_address, get_token_info};
+use borsh::de::BorshDeserialize;
+use config::WormholeTokenBridgeConfig;
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::{create_raw_data_field, create_text_field};
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+// Borsh enum tags of the transfer instructions
+const TRANSFER_WRAPPED_TAG: u8 = 4;
+const TRANSFER_NATIVE_TAG: u8 = 5;
+const TRANSFER_WRAPPED_WITH_PAYLOAD_TAG: u8 = 11;
+const TRANSFER_NATIVE_WITH_PAYLOAD_TAG: u8 = 12;
+
+// Account positions in the transfer instructions; wrapped transfers name the owner of the
+// source account before the mint
+const TRANSFER_FROM_INDEX: usize = 2;
+const TRANSFER_NATIVE_MINT_INDEX: usize = 3;
+const TRANSFER_WRAPPED_MINT_INDEX: usize = 4;
+
+// From: https://github.com/wormhole-foundation/wormhole/blob/main/solana/modules/token_bridge/program/src/lib.rs
+const INSTRUCTION_NAMES: [&str; 13] = [
+    "Initialize",
+    "Attest Token",
+    "Complete Native",
+    "Complete Wrapped",
+    "Transfer Wrapped",
+    "Transfer Native",
+    "Register Chain",
+    "Create Wrapped",
+    "Upgrade Contract",
+    "Complete Native With Payload",
+    "Complete Wrapped With Payload",
+    "Transfer Wrapped With Payload",
+    "Transfer Native With Payload",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WormholeTokenBridgeInstruction {
+    Transfer {
+        /// Whether the token is a Wormhole-wrapped token burned on Solana rather than locked
+        wrapped: bool,
+        amount: u64,
+        /// None for transfers with a payload, which carry no relayer fee
+        fee: Option<u64>,
+        target_address: [u8; 32],
+        target_chain: u16,
+        payload_len: Option<usize>,
+    },
+    Other {
+        tag: u8,
+    },
+}
+
+// Create a static instance that we can reference
+static WORMHOLE_TOKEN_BRIDGE_CONFIG: WormholeTokenBridgeConfig = WormholeTokenBridgeConfig;
+
+pub struct WormholeTokenBridgeVisualizer;
+
+impl InstructionVisualizer for WormholeTokenBridgeVisualizer {
+    fn visualize_tx_commands(
+        &self,
+        context: &VisualizerContext,
+    ) -> Result<AnnotatedPayloadField, VisualSignError> {
+        let instruction = context
+            .current_instruction()
+            .ok_or_else(|| VisualSignError::MissingData("No instruction found".into()))?;
+
+        let bridge_instruction = parse_token_bridge_instruction(&instruction.data)
+            .map_err(|e| VisualSignError::DecodeError(e.to_string()))?;
+
+        let mut expanded_fields = vec![create_text_field(
+            "Program ID",
+            &instruction.program_id.to_string(),
+        )?];
+
+        let instruction_text = match &bridge_instruction {
+            WormholeTokenBridgeInstruction::Transfer {
+                wrapped,
+                amount,
+                fee,
+                target_address,
+                target_chain,
+                payload_len,
+            } => {
+                let mint_index = if *wrapped {
+                    TRANSFER_WRAPPED_MINT_INDEX
+                } else {
+                    TRANSFER_NATIVE_MINT_INDEX
+                };
+                let mint = account_at(instruction, mint_index);
+                let token = get_token_info(&mint, *amount);
+                let (chain_name, format) = match chain(*target_chain) {
+                    Some((name, format)) => (format!("{name} (chain {target_chain})"), format),
+                    None => (
+                        format!("unknown chain {target_chain}"),
+                        RemoteAddressFormat::Hex,
+                    ),
+                };
+                let recipient = format_remote_address(target_address, format);
+
+                expanded_fields.push(create_text_field("Token", &mint)?);
+                expanded_fields.push(create_text_field(
+                    "Amount",
+                    &format!("{} {}", token.human_readable_amount, token.symbol),
+                )?);
+                expanded_fields.push(create_text_field("Destination Chain", &chain_name)?);
+                expanded_fields.push(create_text_field("Recipient", &recipient)?);
+                expanded_fields.push(create_text_field(
+                    "From",
+                    &account_at(instruction, TRANSFER_FROM_INDEX),
+                )?);
+                if let Some(fee) = fee {
+                    let fee = get_token_info(&mint, *fee);
+                    expanded_fields.push(create_text_field(
+                        "Relayer Fee",
+                        &format!("{} {}", fee.human_readable_amount, fee.symbol),
+                    )?);
+                }
+                if let Some(len) = payload_len {
+                    expanded_fields.push(create_text_field("Payload", &format!("{len} bytes"))?);
+                }
+
+                format!(
+                    "Wormhole: Bridge {} {} to {recipient} on {chain_name}",
+                    token.human_readable_amount, token.symbol
+                )
+            }
+            WormholeTokenBridgeInstruction::Other { tag } => {
+                let name = INSTRUCTION_NAMES
+                    .get(usize::from(*tag))
+                    .map_or_else(|| format!("Instruction {tag}"), |name| name.to_string());
+                format!("Wormhole Token Bridge: {name}")
+            }
+        };
+
+        let condensed_fields = vec![create_text_field("Instruction", &instruction_text)?];
+        expanded_fields.push(create_raw_data_field(
+            &instruction.data,
+            Some(hex::encode(&instruction.data)),
+        )?);
+
+        let preview_layout = SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 {
+                text: instruction_text.clone(),
+            }),
+            subtitle: Some(SignablePayloadFieldTextV2 {
+                text: String::new(),
+            }),
+            condensed: Some(SignablePayloadFieldListLayout {
+                fields: condensed_fields,
+            }),
+            expanded: Some(SignablePayloadFieldListLayout {
+                fields: expanded_fields,
+            }),
+        };
+
+        Ok(AnnotatedPayloadField {
+            static_annotation: None,
+            dynamic_annotation: None,
+            signable_payload_field: SignablePayloadField::PreviewLayout {
+                common: SignablePayloadFieldCommon {
+                    label: format!("Instruction {}", context.instruction_index() + 1),
+                    fallback_text: format!(
+                        "Program ID: {}\nData: {}",
+                        instruction.program_id,
+                        hex::encode(&instruction.data)
+                    ),
+                },
+                preview_layout,
+            },
+        })
+    }
+
+    fn get_config(&self) -> Option<&dyn SolanaIntegrationConfig> {
+        Some(&WORMHOLE_TOKEN_BRIDGE_CONFIG)
+    }
+
+    fn kind(&self) -> VisualizerKind {
+        VisualizerKind::Bridge("Wormhole")
+    }
+}
+
+// From: https://wormhole.com/docs/products/reference/chain-ids/
+fn chain(id: u16) -> Option<(&'static str, RemoteAddressFormat)> {
+    let chain = match id {
+        1 => ("Solana", RemoteAddressFormat::Solana),
+        2 => ("Ethereum", RemoteAddressFormat::Evm),
+        4 => ("BNB Smart Chain", RemoteAddressFormat::Evm),
+        5 => ("Polygon", RemoteAddressFormat::Evm),
+        6 => ("Avalanche", RemoteAddressFormat::Evm),
+        10 => ("Fantom", RemoteAddressFormat::Evm),
+        14 => ("Celo", RemoteAddressFormat::Evm),
+        16 => ("Moonbeam", RemoteAddressFormat::Evm),
+        21 => ("Sui", RemoteAddressFormat::Hex),
+        22 => ("Aptos", RemoteAddressFormat::Hex),
+        23 => ("Arbitrum", RemoteAddressFormat::Evm),
+        24 => ("Optimism", RemoteAddressFormat::Evm),
+        30 => ("Base", RemoteAddressFormat::Evm),
+        _ => return None,
+    };
+    Some(chain)
+}
+
+fn parse_token_bridge_instruction(
+    data: &[u8],
+) -> Result<WormholeTokenBridgeInstruction, &'static str> {
+    let (&tag, mut args) = data.split_first().ok_or("Empty instruction data")?;
+
+    match tag {
+        TRANSFER_WRAPPED_TAG | TRANSFER_NATIVE_TAG => {
+            let (_nonce, amount, fee, target_address, target_chain) =
+                <(u32, u64, u64, [u8; 32], u16)>::deserialize(&mut args)
+                    .map_err(|_| "Invalid transfer data")?;
+            Ok(WormholeTokenBridgeInstruction::Transfer {
+                wrapped: tag == TRANSFER_WRAPPED_TAG,
+                amount,
+                fee: Some(fee),
+                target_address,
+                target_chain,
+                payload_len: None,
+            })
+        }
+        TRANSFER_WRAPPED_WITH_PAYLOAD_TAG | TRANSFER_NATIVE_WITH_PAYLOAD_TAG => {
+            // The CPI program id after the payload is not shown
+            let (_nonce, amount, target_address, target_chain, payload) =
+                <(u32, u64, [u8; 32], u16, Vec<u8>)>::deserialize(&mut args)
+                    .map_err(|_| "Invalid transfer data")?;
+            Ok(WormholeTokenBridgeInstruction::Transfer {
+                wrapped: tag == TRANSFER_WRAPPED_WITH_PAYLOAD_TAG,
+                amount,
+                fee: None,
+                target_address,
+                target_chain,
+                payload_len: Some(payload.len()),
+            })
+        }
+        tag => Ok(WormholeTokenBridgeInstruction::Other { tag }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_parser::solana::structs::SolanaAccount;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+
+    const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn transfer_native_data(target_address: [u8; 32], target_chain: u16) -> Vec<u8> {
+        let mut data = vec![TRANSFER_NATIVE_TAG];
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&2_500_000u64.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&target_address);
+        data.extend_from_slice(&target_chain.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_parse_token_bridge_instruction() {
+        let mut target_address = [0u8; 32];
+        target_address[12..].copy_from_slice(&[0x22; 20]);
+        assert_eq!(
+            parse_token_bridge_instruction(&transfer_native_data(target_address, 2)),
+            Ok(WormholeTokenBridgeInstruction::Transfer {
+                wrapped: false,
+                amount: 2_500_000,
+                fee: Some(0),
+                target_address,
+                target_chain: 2,
+                payload_len: None,
+            })
+        );
+        assert_eq!(
+            parse_token_bridge_instruction(&[2]),
+            Ok(WormholeTokenBridgeInstruction::Other { tag: 2 })
+        );
+        assert!(parse_token_bridge_instruction(&[TRANSFER_NATIVE_TAG, 1, 2]).is_err());
+        assert!(parse_token_bridge_instruction(&[]).is_err());
+    }
+
+    #[test]
+    fn test_transfer_native_fields() {
+        let mut target_address = [0u8; 32];
+        target_address[12..].copy_from_slice(&[0x22; 20]);
+        let mut accounts: Vec<AccountMeta> = (0..12)
+            .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+            .collect();
+        accounts[TRANSFER_NATIVE_MINT_INDEX].pubkey = Pubkey::from_str(USDC_MINT).unwrap();
+        let instructions = vec![Instruction {
+            program_id: Pubkey::from_str(config::WORMHOLE_TOKEN_BRIDGE_PROGRAM_ID).unwrap(),
+            accounts,
+            data: transfer_native_data(target_address, 2),
+        }];
+        let sender = SolanaAccount {
+            account_key: Pubkey::new_unique().to_string(),
+            signer: true,
+            writable: true,
+        };
+        let context = VisualizerContext::new(&sender, 0, &instructions);
+
+        let field = WormholeTokenBridgeVisualizer
+            .visualize_tx_commands(&context)
+            .unwrap();
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } =
+            field.signable_payload_field
+        else {
+            panic!("Expected PreviewLayout");
+        };
+
+        let recipient = format!("0x{}", "22".repeat(20));
+        assert_eq!(
+            preview_layout.title.unwrap().text,
+            format!("Wormhole: Bridge 2.5 USDC to {recipient} on Ethereum (chain 2)")
+        );
+        let expanded = preview_layout.expanded.unwrap().fields;
+        assert!(expanded.iter().any(|f| {
+            f.signable_payload_field.label() == "Recipient"
+                && f.signable_payload_field.fallback_text() == &recipient
+        }));
+    }
+}
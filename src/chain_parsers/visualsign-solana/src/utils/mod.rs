@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use base64::{self, Engine};
+use visualsign::fixed_point::format_fixed_point;
 
 // Constants
 const ADDRESS_TRUNCATION_LENGTH: usize = 8;
@@ -45,6 +46,16 @@ pub fn get_token_lookup_table() -> HashMap<&'static str, TokenInfo> {
         },
     );
 
+    // Wrapped SOL (the mint Jupiter and other DEXes route through)
+    tokens.insert(
+        "So11111111111111111111111111111111111111112",
+        TokenInfo {
+            symbol: "SOL",
+            name: "Wrapped SOL",
+            decimals: 9,
+        },
+    );
+
     // USDC
     tokens.insert(
         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
@@ -65,26 +76,32 @@ pub fn get_token_lookup_table() -> HashMap<&'static str, TokenInfo> {
         },
     );
 
+    // Marinade staked SOL
+    tokens.insert(
+        "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So",
+        TokenInfo {
+            symbol: "mSOL",
+            name: "Marinade staked SOL",
+            decimals: 9,
+        },
+    );
+
+    // Lido staked SOL
+    tokens.insert(
+        "7dHbWXmci3dT8UFYWYZweBLXgycu7Y3iL6trKn1Y7ARj",
+        TokenInfo {
+            symbol: "stSOL",
+            name: "Lido Staked SOL",
+            decimals: 9,
+        },
+    );
+
     tokens
 }
 
 /// Helper function to format token amounts
 pub fn format_token_amount(amount: u64, decimals: u8) -> String {
-    let divisor = 10_u64.pow(decimals as u32);
-    let whole = amount / divisor;
-    let fractional = amount % divisor;
-
-    if fractional == 0 {
-        format!("{whole}")
-    } else {
-        let fractional_str = format!("{:0width$}", fractional, width = decimals as usize);
-        let trimmed = fractional_str.trim_end_matches('0');
-        if trimmed.is_empty() {
-            format!("{whole}")
-        } else {
-            format!("{whole}.{trimmed}")
-        }
-    }
+    format_fixed_point(u128::from(amount), decimals)
 }
 
 /// Enhanced swap instruction with token information
@@ -130,6 +147,44 @@ pub fn get_token_info(address: &str, amount: u64) -> SwapTokenInfo {
     }
 }
 
+/// How a bridge's destination chain writes the 32-byte recipient the bridge carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteAddressFormat {
+    /// A 20-byte address, left-padded with zeros
+    Evm,
+    /// A base58 public key
+    Solana,
+    /// Any other encoding, shown as hex
+    Hex,
+}
+
+/// `address` as the destination chain writes it. A recipient that should be an EVM address but
+/// is not left-padded cannot receive anything there, so it is flagged instead.
+pub fn format_remote_address(address: &[u8; 32], format: RemoteAddressFormat) -> String {
+    match format {
+        RemoteAddressFormat::Evm if address[..12].iter().all(|byte| *byte == 0) => {
+            format!("0x{}", hex::encode(&address[12..]))
+        }
+        RemoteAddressFormat::Evm => format!("0x{} (not an EVM address)", hex::encode(address)),
+        RemoteAddressFormat::Solana => {
+            solana_sdk::pubkey::Pubkey::new_from_array(*address).to_string()
+        }
+        RemoteAddressFormat::Hex => format!("0x{}", hex::encode(address)),
+    }
+}
+
+/// The pubkey of the instruction's `index`-th account, or "Unknown" when the instruction has fewer
+pub(crate) fn account_at(
+    instruction: &solana_sdk::instruction::Instruction,
+    index: usize,
+) -> String {
+    instruction
+        .accounts
+        .get(index)
+        .map(|meta| meta.pubkey.to_string())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
 #[cfg(test)]
 pub mod test_utils {
     use crate::transaction_string_to_visual_sign;
@@ -140,9 +195,8 @@ pub mod test_utils {
         transaction_string_to_visual_sign(
             data,
             VisualSignOptions {
-                metadata: None,
                 decode_transfers: true,
-                transaction_name: None,
+                ..Default::default()
             },
         )
         .expect("Failed to visualize tx commands")
@@ -10,30 +10,48 @@
 //! - The order of visualizers is the filesystem iteration order; conflicts are resolved
 //!   by the first visualizer that reports it can handle a command.
 //! - If a visualizer returns an error for the selected command, the entire decode flow
-//!   for that command fails and the error is propagated.
+//!   for that command fails and the error is propagated, unless partial parsing is requested:
+//!   the command is then shown as an `Unknown` field holding its BCS bytes.
 
-use crate::core::{CommandVisualizer, VisualizerContext, visualize_with_any};
+use crate::core::{
+    CommandVisualizer, PtbDataflow, VisualizeResult, VisualizerContext, VisualizerKind,
+    visualize_with_any,
+};
 
 use sui_json_rpc_types::{
     SuiTransactionBlockData, SuiTransactionBlockDataAPI, SuiTransactionBlockKind,
 };
+use sui_types::transaction::{TransactionData, TransactionDataAPI, TransactionKind};
 
 use visualsign::AnnotatedPayloadField;
 use visualsign::errors::VisualSignError;
+use visualsign::field_builders::create_unknown_field;
 
 // The list of available visualizers is generated by `build.rs` into OUT_DIR.
 include!(concat!(env!("OUT_DIR"), "/generated_visualizers.rs"));
 
-/// Visualizes all commands in a transaction block, returning their signable fields.
+/// A command whose visualizer failed, shown as an `Unknown` field in partial-parse mode.
+#[derive(Debug)]
+pub struct FailedCommand {
+    pub index: usize,
+    pub error: VisualSignError,
+}
+
+/// Visualizes all commands in a transaction block, returning one result per handled command.
 ///
 /// - Returns an empty vector for non-programmable transactions.
-/// - Errors if any chosen visualizer fails while rendering a command.
+/// - Errors if any chosen visualizer fails while rendering a command, unless `partial_parse`
+///   is set: the command's result then holds an `Unknown` field with the raw command from
+///   `transaction`, and the command is returned among the failed ones.
 pub fn decode_commands(
+    transaction: &TransactionData,
     block_data: &SuiTransactionBlockData,
-) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
+    dataflow: &PtbDataflow,
+    partial_parse: bool,
+) -> Result<(Vec<VisualizeResult>, Vec<FailedCommand>), VisualSignError> {
     let (tx_commands, tx_inputs) = match block_data.transaction() {
         SuiTransactionBlockKind::ProgrammableTransaction(tx) => (&tx.commands, &tx.inputs),
-        _ => return Ok(vec![]),
+        _ => return Ok((vec![], vec![])),
     };
 
     // `available_visualizers()` is generated at build time by `build.rs`.
@@ -44,23 +62,73 @@ pub fn decode_commands(
         .map(std::convert::AsRef::as_ref)
         .collect::<Vec<_>>();
 
-    tx_commands
+    let mut failed = Vec::new();
+    let results = tx_commands
         .iter()
         .enumerate()
         .filter_map(|(command_index, _)| {
-            visualize_with_any(
-                &visualizers_refs,
-                &VisualizerContext::new(block_data.sender(), command_index, tx_commands, tx_inputs),
-            )
+            let context = VisualizerContext::new(
+                block_data.sender(),
+                command_index,
+                tx_commands,
+                tx_inputs,
+                dataflow,
+            );
+            match visualize_with_any(&visualizers_refs, &context)? {
+                Err(error) if partial_parse => {
+                    // `visualize_with_any` picked the first visualizer that can handle it
+                    let Some(visualizer) = visualizers_refs
+                        .iter()
+                        .find(|visualizer| visualizer.can_handle(&context))
+                    else {
+                        return Some(Err(error));
+                    };
+                    let kind = visualizer.kind();
+                    let result = failed_command_field(transaction, command_index, &kind, &error)
+                        .map(|field| VisualizeResult {
+                            field: vec![field],
+                            kind,
+                        });
+                    failed.push(FailedCommand {
+                        index: command_index,
+                        error,
+                    });
+                    Some(result)
+                }
+                result => Some(result),
+            }
         })
-        .map(|res| res.map(|viz_result| viz_result.field))
-        .collect::<Result<Vec<Vec<AnnotatedPayloadField>>, _>>()
-        .map(|nested| nested.into_iter().flatten().collect())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((results, failed))
+}
+
+// The `Unknown` field of a command whose visualizer failed, holding the command's BCS bytes
+fn failed_command_field(
+    transaction: &TransactionData,
+    command_index: usize,
+    kind: &VisualizerKind,
+    error: &VisualSignError,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    let raw_command = match transaction.kind() {
+        TransactionKind::ProgrammableTransaction(ptb) => ptb
+            .commands
+            .get(command_index)
+            .map(bcs::to_bytes)
+            .transpose()
+            .map_err(|e| VisualSignError::SerializationError(e.to_string()))?,
+        _ => None,
+    };
+    create_unknown_field(
+        &format!("Command {}", command_index + 1),
+        &raw_command.unwrap_or_default(),
+        &format!("Could not decode {} command: {error}", kind.name()),
+    )
 }
 
 pub fn decode_transfers(
     block_data: &SuiTransactionBlockData,
-) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
+    dataflow: &PtbDataflow,
+) -> Result<Vec<VisualizeResult>, VisualSignError> {
     let (tx_commands, tx_inputs) = match block_data.transaction() {
         SuiTransactionBlockKind::ProgrammableTransaction(tx) => (&tx.commands, &tx.inputs),
         _ => return Ok(vec![]),
@@ -74,12 +142,16 @@ pub fn decode_transfers(
         .filter_map(|(command_index, _)| {
             visualize_with_any(
                 &[&visualizer],
-                &VisualizerContext::new(block_data.sender(), command_index, tx_commands, tx_inputs),
+                &VisualizerContext::new(
+                    block_data.sender(),
+                    command_index,
+                    tx_commands,
+                    tx_inputs,
+                    dataflow,
+                ),
             )
         })
-        .map(|res| res.map(|viz_result| viz_result.field))
-        .collect::<Result<Vec<Vec<AnnotatedPayloadField>>, _>>()
-        .map(|nested| nested.into_iter().flatten().collect())
+        .collect()
 }
 
 #[cfg(test)]
@@ -114,6 +186,7 @@ mod tests {
             _ => panic!("expected programmable transaction"),
         };
 
+        let dataflow = PtbDataflow::analyze(tx_commands, tx_inputs);
         let visualizer = crate::presets::coin_transfer::CoinTransferVisualizer;
 
         let results: Vec<_> = tx_commands
@@ -127,6 +200,7 @@ mod tests {
                         command_index,
                         tx_commands,
                         tx_inputs,
+                        &dataflow,
                     ),
                 )
             })
@@ -141,4 +215,32 @@ mod tests {
             |r| matches!(r.kind, VisualizerKind::Payments(name) if name == "Native Transfer")
         ));
     }
+
+    #[test]
+    fn test_failed_command_field_holds_raw_command() {
+        // https://suivision.xyz/txblock/CE46w3GYgWnZU8HF4P149m6ANGebD22xuNqA64v7JykJ
+        let test_data = "AQAAAAAABQEAm9cmP35lHGKppWJLgoYU7aexd43oTT2ci4QzxDXFNv92CAsjAAAAACANp0teIzSyzZ4Pj5dL3YaYBdeVmiWScWL/9RCV4mUINwEAARQFJheK7qwbpqmQudEhsSyQ6AjVawfLpN4XRBhe12FH6TIiAAAAACDXzuT2xanZ36QNQSYtDhZn31zfzIlhRk5H6pTsqGdRDAEAXpykdGz3KJdaAVjyAMZQxufRYJfqzNXfOu8jVCAjEjIzfYIhAAAAACA5hk9rACYb1i5fqrUBJIgXhdUFOqOaouNWmQINCW4/WQAIAPLhNQAAAAAAIEutPmqkZpN81fwdos/haXZAQJoZsX8SvKilyMRxrv/pAwMBAAACAQEAAQIAAgEAAAEBAwABAQIBAAEEAA4x8k3bZAV+p192pmk9h7U2nGDwuTmW8EY6c95JyFHCAaCnde0j6aiVXUd/1gCf3q5Uuj1mPVIuuEpJn1teueghdggLIwAAAAAgNhuP2zGpc0qF3gRzxQC5B0lpAZR7xyssXC3gKbH8uxwOMfJN22QFfqdfdqZpPYe1Npxg8Lk5lvBGOnPeSchRwugDAAAAAAAAoIVIAAAAAAAAAWEAFrlPuI8JOSzIoIBc0xwfWia7T5uPf1PS+aSSphoTTq0lRpNuTOg8eOggpBxpLsQDrbAx3jDoWg1R8hZKR62LBex1R808U6AgiY8V7LxOVsChXFf8nSAEGaeSLQc7mJbx";
+        let wrapper =
+            <SuiTransactionWrapper as Transaction>::from_string(test_data).expect("parse tx");
+        let transaction = wrapper.inner();
+        let TransactionKind::ProgrammableTransaction(ptb) = transaction.kind() else {
+            panic!("expected programmable transaction");
+        };
+        let raw_command = bcs::to_bytes(&ptb.commands[0]).unwrap();
+
+        let kind = VisualizerKind::Payments("Native Transfer");
+        let error = VisualSignError::DecodeError("unexpected argument".to_string());
+        let field = failed_command_field(transaction, 0, &kind, &error)
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(field.label(), "Command 1");
+        let visualsign::SignablePayloadField::Unknown { unknown, .. } = field else {
+            panic!("expected an Unknown field");
+        };
+        assert_eq!(unknown.data, format!("0x{}", hex::encode(raw_command)));
+        assert_eq!(
+            unknown.explanation,
+            "Could not decode Native Transfer command: Failed to decode instruction: unexpected argument"
+        );
+    }
 }
@@ -0,0 +1,320 @@
+//! Cross-command dataflow analysis for programmable transaction blocks.
+//!
+//! A PTB passes values between commands by reference (`Result`/`NestedResult`), e.g.
+//! `SplitCoins` → `MoveCall` → `TransferObjects`. This pass walks the commands once, before any
+//! visualizer runs, so presets can resolve coin amounts and follow values generically instead of
+//! re-walking the block themselves.
+//!
+//! Constraints and behavior:
+//! - Amounts are only known for coins split off with a `Pure` amount input. Gas and input coin
+//!   balances are not part of the transaction, and `MoveCall` results are opaque.
+//! - Amounts are tracked per command index: splitting from or merging into a coin only changes
+//!   the amount seen by later commands.
+//! - The analysis never fails; values it cannot follow simply resolve to `None`.
+
+use std::collections::HashMap;
+
+use sui_json_rpc_types::{SuiArgument, SuiCallArg, SuiCommand};
+use sui_types::base_types::SuiAddress;
+
+use crate::utils::decode_number;
+
+/// A value that can be passed between commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PtbValue {
+    /// The coin paying for gas (always SUI)
+    GasCoin,
+    /// A transaction input
+    Input(u16),
+    /// Result `index` of command `command`; a plain `Result(command)` is result 0
+    Result { command: u16, index: u16 },
+}
+
+impl From<SuiArgument> for PtbValue {
+    fn from(argument: SuiArgument) -> Self {
+        match argument {
+            SuiArgument::GasCoin => Self::GasCoin,
+            SuiArgument::Input(index) => Self::Input(index),
+            SuiArgument::Result(command) => Self::Result { command, index: 0 },
+            SuiArgument::NestedResult(command, index) => Self::Result { command, index },
+        }
+    }
+}
+
+/// An object moved to a recipient by a `TransferObjects` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectTransfer {
+    pub command_index: usize,
+    pub object: PtbValue,
+    /// `None` when the recipient is not a `Pure` address input
+    pub recipient: Option<SuiAddress>,
+}
+
+/// Where values flow within a PTB.
+#[derive(Debug, Clone, Default)]
+pub struct PtbDataflow {
+    /// Coin amounts as `(first command index the amount applies to, amount)`, in command order
+    amounts: HashMap<PtbValue, Vec<(usize, Option<u64>)>>,
+    /// The coin each split result was ultimately carved from
+    origins: HashMap<PtbValue, PtbValue>,
+    /// Commands consuming each value, as `(command index, argument position)`
+    consumers: HashMap<PtbValue, Vec<(usize, usize)>>,
+    transfers: Vec<ObjectTransfer>,
+}
+
+impl PtbDataflow {
+    /// Walks all commands once and records how values move between them.
+    pub fn analyze(commands: &[SuiCommand], inputs: &[SuiCallArg]) -> Self {
+        let mut dataflow = Self::default();
+
+        for (command_index, command) in commands.iter().enumerate() {
+            match command {
+                SuiCommand::SplitCoins(coin, amounts) => {
+                    dataflow.record_split(command_index, *coin, amounts, inputs);
+                }
+                SuiCommand::MergeCoins(destination, sources) => {
+                    dataflow.record_merge(command_index, *destination, sources);
+                }
+                SuiCommand::TransferObjects(objects, recipient) => {
+                    let recipient = pure_address(inputs, *recipient);
+                    for (position, object) in objects.iter().enumerate() {
+                        dataflow.record_consumer(*object, command_index, position);
+                        dataflow.transfers.push(ObjectTransfer {
+                            command_index,
+                            object: (*object).into(),
+                            recipient,
+                        });
+                    }
+                    dataflow.record_consumer(*recipient, command_index, objects.len());
+                }
+                SuiCommand::MoveCall(pwc) => {
+                    for (position, argument) in pwc.arguments.iter().enumerate() {
+                        dataflow.record_consumer(*argument, command_index, position);
+                    }
+                }
+                SuiCommand::MakeMoveVec(_, elements) => {
+                    for (position, element) in elements.iter().enumerate() {
+                        dataflow.record_consumer(*element, command_index, position);
+                    }
+                }
+                SuiCommand::Upgrade(_, _, ticket) => {
+                    dataflow.record_consumer(*ticket, command_index, 0);
+                }
+                SuiCommand::Publish(_) => {}
+            }
+        }
+
+        dataflow
+    }
+
+    /// Amount of the coin referenced by `value`, as seen by the command at `command_index`.
+    pub fn amount_at(&self, value: impl Into<PtbValue>, command_index: usize) -> Option<u64> {
+        self.amounts
+            .get(&value.into())?
+            .iter()
+            .rev()
+            .find(|(from, _)| *from <= command_index)
+            .and_then(|(_, amount)| *amount)
+    }
+
+    /// The coin a value was split from, following chained splits; the value itself otherwise.
+    pub fn origin(&self, value: impl Into<PtbValue>) -> PtbValue {
+        let value = value.into();
+        self.origins.get(&value).copied().unwrap_or(value)
+    }
+
+    /// Whether the value is SUI split from the gas coin (or the gas coin itself).
+    pub fn is_from_gas_coin(&self, value: impl Into<PtbValue>) -> bool {
+        self.origin(value) == PtbValue::GasCoin
+    }
+
+    /// Commands consuming the value, as `(command index, argument position)`.
+    pub fn consumers(&self, argument: SuiArgument) -> &[(usize, usize)] {
+        self.consumers
+            .get(&argument.into())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// All objects moved by `TransferObjects` commands, in command order.
+    pub fn transfers(&self) -> &[ObjectTransfer] {
+        &self.transfers
+    }
+
+    fn record_split(
+        &mut self,
+        command_index: usize,
+        coin: SuiArgument,
+        amounts: &[SuiArgument],
+        inputs: &[SuiCallArg],
+    ) {
+        self.record_consumer(coin, command_index, 0);
+        let Ok(command) = u16::try_from(command_index) else {
+            return;
+        };
+        let origin = self.origin(coin);
+        let mut total = Some(0u64);
+
+        for (index, amount_argument) in (0u16..).zip(amounts) {
+            self.record_consumer(*amount_argument, command_index, usize::from(index) + 1);
+
+            let amount = match amount_argument {
+                SuiArgument::Input(input) => inputs
+                    .get(*input as usize)
+                    .and_then(|arg| decode_number::<u64>(arg).ok()),
+                _ => None,
+            };
+            total = total.zip(amount).and_then(|(a, b)| a.checked_add(b));
+
+            let result = PtbValue::Result { command, index };
+            self.amounts
+                .insert(result, vec![(command_index + 1, amount)]);
+            self.origins.insert(result, origin);
+        }
+
+        // The source coin shrinks by the split total for every later command
+        if let Some(history) = self.amounts.get_mut(&coin.into()) {
+            let before = history.last().and_then(|(_, amount)| *amount);
+            let after = before
+                .zip(total)
+                .and_then(|(before, total)| before.checked_sub(total));
+            history.push((command_index + 1, after));
+        }
+    }
+
+    fn record_merge(
+        &mut self,
+        command_index: usize,
+        destination: SuiArgument,
+        sources: &[SuiArgument],
+    ) {
+        self.record_consumer(destination, command_index, 0);
+        for (position, source) in sources.iter().enumerate() {
+            self.record_consumer(*source, command_index, position + 1);
+        }
+
+        // Only coins with a known amount are tracked; an untracked destination stays unknown
+        if !self.amounts.contains_key(&destination.into()) {
+            return;
+        }
+
+        let merged = sources.iter().fold(
+            self.amount_at(destination, command_index),
+            |total, source| {
+                total
+                    .zip(self.amount_at(*source, command_index))
+                    .and_then(|(total, amount)| total.checked_add(amount))
+            },
+        );
+        if let Some(history) = self.amounts.get_mut(&destination.into()) {
+            history.push((command_index + 1, merged));
+        }
+    }
+
+    fn record_consumer(&mut self, argument: SuiArgument, command_index: usize, position: usize) {
+        self.consumers
+            .entry(argument.into())
+            .or_default()
+            .push((command_index, position));
+    }
+}
+
+fn pure_address(inputs: &[SuiCallArg], argument: SuiArgument) -> Option<SuiAddress> {
+    let SuiArgument::Input(index) = argument else {
+        return None;
+    };
+
+    inputs.get(index as usize)?.pure()?.to_sui_address().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SuiTransactionWrapper;
+    use crate::core::SuiModuleResolver;
+
+    use move_bytecode_utils::module_cache::SyncModuleCache;
+    use sui_json_rpc_types::{
+        SuiTransactionBlockData, SuiTransactionBlockDataAPI, SuiTransactionBlockKind,
+    };
+    use visualsign::vsptrait::Transaction;
+
+    // Synthetic transaction: split 2.5 SUI from gas, kiosk::purchase with it, then
+    // transfer_policy::confirm_request with the returned transfer request
+    const PURCHASE_TX: &str = "AQAAAAAABAEBXzwPfxwqO01eb3CBkqO0xdbn+AkaKzxNXm9wgZKjtMUqAAAAAAAAAAEAIJ6NfGtaSTgnFgX049LBsKmYh3ZlVEMyIRAA/+7dzLuqAAgA+QKVAAAAAAEBKzxNXm9wgZKjtMXW5/gJGis8TV5vcIGSo7TF1uf4CRArAAAAAAAAAAADAgABAQIAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACBWtpb3NrCHB1cmNoYXNlAQfuSWoMwE0Go0WYK6ZpfJDGGQIN6eJ0QIx4GfeH/2bhoQhzdWlmcmVucwdTdWlGcmVuAAMBAAABAQADAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAg90cmFuc2Zlcl9wb2xpY3kPY29uZmlybV9yZXF1ZXN0AQfuSWoMwE0Go0WYK6ZpfJDGGQIN6eJ0QIx4GfeH/2bhoQhzdWlmcmVucwdTdWlGcmVuAAIBAwADAQABAD5mBnBjcVYuaY/E5NbnxxAekmLOkxY0GI/iRu+ttwZ1AjfHHKcFZnGnwzTl75xAKOpM5bY3Z4qGwOeraONnx3VIqPcHIwAAAAAg0FQuHzIbJl+jpR6QIcj0fC2kc4LuNwY/Ma4ZUWOmChv2L+ptp3B6T4W6xJDq0osodSA90+BQERPq2jFKHwrED6j3ByMAAAAAIOaqfo46J4lFzzRuDdP/VwxUqaFLdIrGxFDIl4zbRorwPmYGcGNxVi5pj8Tk1ufHEB6SYs6TFjQYj+JG7623BnUhAgAAAAAAAAwKnAAAAAAAAAFhAJI9BDedQZGyFBP+pTIveuzBxrATJdovE7Z7VJxYZQtqyvAFtnxkMXPJoNYE21ftnh7NQrGfZuu3FOAlswPvTwIP/xAlK5s1M87INMWEYMA0A8vZQBfHum6QGvP+WHnOrA==";
+
+    /// Input 2 of `PURCHASE_TX` is a `Pure` u64 holding this amount
+    const AMOUNT: u64 = 2_500_000_000;
+    const AMOUNT_INPUT: SuiArgument = SuiArgument::Input(2);
+
+    fn commands_and_inputs(data: &str) -> (Vec<SuiCommand>, Vec<SuiCallArg>) {
+        let wrapper = <SuiTransactionWrapper as Transaction>::from_string(data).expect("parse tx");
+        let block_data = SuiTransactionBlockData::try_from_with_module_cache(
+            wrapper.inner().clone(),
+            &SyncModuleCache::new(SuiModuleResolver),
+        )
+        .expect("block data");
+
+        match block_data.transaction() {
+            SuiTransactionBlockKind::ProgrammableTransaction(tx) => {
+                (tx.commands.clone(), tx.inputs.clone())
+            }
+            _ => panic!("expected programmable transaction"),
+        }
+    }
+
+    #[test]
+    fn test_split_result_flows_into_move_call() {
+        let (commands, inputs) = commands_and_inputs(PURCHASE_TX);
+        let dataflow = PtbDataflow::analyze(&commands, &inputs);
+        let payment = SuiArgument::NestedResult(0, 0);
+
+        // The split coin only exists after the `SplitCoins` command
+        assert_eq!(dataflow.amount_at(payment, 0), None);
+        assert_eq!(dataflow.amount_at(payment, 1), Some(AMOUNT));
+        assert_eq!(dataflow.amount_at(SuiArgument::Result(0), 1), Some(AMOUNT));
+        assert!(dataflow.is_from_gas_coin(payment));
+        assert_eq!(dataflow.consumers(payment), &[(1, 2)]);
+
+        // The transfer request returned by `purchase` is consumed by `confirm_request`
+        assert_eq!(
+            dataflow.consumers(SuiArgument::NestedResult(1, 1)),
+            &[(2, 1)]
+        );
+        assert_eq!(dataflow.amount_at(SuiArgument::GasCoin, 2), None);
+    }
+
+    #[test]
+    fn test_merge_and_split_update_later_amounts() {
+        let (_, inputs) = commands_and_inputs(PURCHASE_TX);
+        let commands = vec![
+            SuiCommand::SplitCoins(SuiArgument::GasCoin, vec![AMOUNT_INPUT, AMOUNT_INPUT]),
+            SuiCommand::MergeCoins(
+                SuiArgument::NestedResult(0, 0),
+                vec![SuiArgument::NestedResult(0, 1)],
+            ),
+            SuiCommand::SplitCoins(SuiArgument::NestedResult(0, 0), vec![AMOUNT_INPUT]),
+            SuiCommand::TransferObjects(vec![SuiArgument::Result(2)], SuiArgument::Input(0)),
+        ];
+        let dataflow = PtbDataflow::analyze(&commands, &inputs);
+        let merged = SuiArgument::NestedResult(0, 0);
+
+        assert_eq!(dataflow.amount_at(merged, 1), Some(AMOUNT));
+        assert_eq!(dataflow.amount_at(merged, 2), Some(2 * AMOUNT));
+        assert_eq!(dataflow.amount_at(merged, 3), Some(AMOUNT));
+        assert_eq!(dataflow.amount_at(SuiArgument::Result(2), 3), Some(AMOUNT));
+        assert!(dataflow.is_from_gas_coin(SuiArgument::Result(2)));
+
+        // Input 0 is the kiosk object, so the recipient is not a known address
+        assert_eq!(
+            dataflow.transfers(),
+            &[ObjectTransfer {
+                command_index: 3,
+                object: PtbValue::Result {
+                    command: 2,
+                    index: 0
+                },
+                recipient: None,
+            }]
+        );
+    }
+}
@@ -2,23 +2,32 @@
 //!
 //! - `chain_config`: declarative macros for package/module/function layouts and typed getters.
 //! - `commands`: walks transaction commands and dispatches to available visualizers.
+//! - `dataflow`: tracks values flowing between commands so amounts resolve across a PTB.
 //! - `helper`: chain resolution utilities (module cache adapters, etc.).
+//! - `shared_objects`: warnings for shared objects the transaction can modify.
+//! - `system`: payloads of system transactions (epoch changes, consensus commits, ...).
+//! - `summary`: synthesized net-effect section aggregated over the whole transaction.
 //! - `transaction`: raw decoding and helpers for titles/network/details.
 //! - `visualsign`: public API surface for converting to `VisualSign` payloads.
 
 mod chain_config;
 mod commands;
+mod dataflow;
 mod helper;
+mod shared_objects;
+mod summary;
+mod system;
 mod transaction;
 mod visualsign;
 
 use std::collections::HashMap;
 
-use sui_json_rpc_types::{SuiCallArg, SuiCommand};
+use sui_json_rpc_types::{SuiArgument, SuiCallArg, SuiCommand};
 use sui_types::base_types::SuiAddress;
 
 use ::visualsign::AnnotatedPayloadField;
 use ::visualsign::errors::VisualSignError;
+pub use dataflow::{ObjectTransfer, PtbDataflow, PtbValue};
 pub use helper::SuiModuleResolver;
 pub use visualsign::{
     SuiTransactionWrapper, SuiVisualSignConverter, transaction_string_to_visual_sign,
@@ -36,6 +45,21 @@ pub enum VisualizerKind {
     StakingPools(&'static str),
     /// Payment and simple transfer-related operations
     Payments(&'static str),
+    /// NFT trading through kiosks and marketplaces
+    Marketplace(&'static str),
+}
+
+impl VisualizerKind {
+    /// The protocol or feature name carried by every kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dex(name)
+            | Self::Lending(name)
+            | Self::StakingPools(name)
+            | Self::Payments(name)
+            | Self::Marketplace(name) => name,
+        }
+    }
 }
 
 pub struct SuiIntegrationConfigData {
@@ -74,6 +98,8 @@ pub struct VisualizerContext<'a> {
     commands: &'a [SuiCommand],
     /// All input arguments for the transaction.
     inputs: &'a [SuiCallArg],
+    /// Dataflow analysis of the whole transaction, computed once by the dispatcher.
+    dataflow: &'a PtbDataflow,
 }
 
 impl<'a> VisualizerContext<'a> {
@@ -83,12 +109,14 @@ impl<'a> VisualizerContext<'a> {
         command_index: usize,
         commands: &'a [SuiCommand],
         inputs: &'a [SuiCallArg],
+        dataflow: &'a PtbDataflow,
     ) -> Self {
         Self {
             sender,
             command_index,
             commands,
             inputs,
+            dataflow,
         }
     }
 
@@ -111,6 +139,19 @@ impl<'a> VisualizerContext<'a> {
     pub fn inputs(&self) -> &[SuiCallArg] {
         self.inputs
     }
+
+    /// Returns the dataflow analysis of the transaction.
+    pub fn dataflow(&self) -> &PtbDataflow {
+        self.dataflow
+    }
+
+    /// Resolves the coin amount behind `argument` as seen by the current command.
+    ///
+    /// Returns `None` when the amount is not known from the transaction itself
+    /// (e.g. gas or input coins, or `MoveCall` results).
+    pub fn resolve_amount(&self, argument: SuiArgument) -> Option<u64> {
+        self.dataflow.amount_at(argument, self.command_index)
+    }
 }
 
 /// Trait for visualizing Sui transaction commands.
@@ -0,0 +1,201 @@
+//! Warnings for shared objects a PTB takes by mutable reference.
+//!
+//! A shared object belongs to no one: any transaction can pass it to a `MoveCall`, and a call
+//! taking it by `&mut` can change state other users rely on, such as a pool's reserves, a
+//! kiosk's listings or a protocol's configuration. Signers should know which of them a
+//! transaction touches, so every mutable shared input gets a warning naming the commands it is
+//! passed to.
+//!
+//! Constraints and behavior:
+//! - Only inputs declared mutable are reported; read-only shared inputs cannot be changed.
+//! - System objects (the Sui system state, clock, randomness, ...) are shared by every user of
+//!   the chain and changed only as the framework allows, so they are left out.
+//! - Whether a call actually writes to the object depends on its Move code; the declaration
+//!   only says that it can.
+
+use sui_json_rpc_types::{SuiArgument, SuiCallArg, SuiCommand, SuiObjectArg};
+use sui_types::base_types::ObjectID;
+use sui_types::{
+    SUI_AUTHENTICATOR_STATE_OBJECT_ID, SUI_BRIDGE_OBJECT_ID, SUI_CLOCK_OBJECT_ID,
+    SUI_DENY_LIST_OBJECT_ID, SUI_RANDOMNESS_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
+};
+
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::create_text_field;
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldStaticAnnotation,
+};
+
+use crate::core::PtbDataflow;
+use crate::utils::truncate_address;
+
+pub const SHARED_OBJECTS_LABEL: &str = "Mutable Shared Objects";
+
+const SYSTEM_OBJECTS: [ObjectID; 6] = [
+    SUI_SYSTEM_STATE_OBJECT_ID,
+    SUI_CLOCK_OBJECT_ID,
+    SUI_AUTHENTICATOR_STATE_OBJECT_ID,
+    SUI_RANDOMNESS_STATE_OBJECT_ID,
+    SUI_BRIDGE_OBJECT_ID,
+    SUI_DENY_LIST_OBJECT_ID,
+];
+
+/// A shared input taken by mutable reference, with the commands it is passed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutableSharedObject {
+    pub object_id: ObjectID,
+    /// e.g. `"kiosk::purchase (command 2)"`, in command order
+    pub commands: Vec<String>,
+}
+
+/// The mutable shared inputs of a PTB that are not system objects, in input order.
+pub fn mutable_shared_objects(
+    commands: &[SuiCommand],
+    inputs: &[SuiCallArg],
+    dataflow: &PtbDataflow,
+) -> Vec<MutableSharedObject> {
+    (0u16..)
+        .zip(inputs)
+        .filter_map(|(index, input)| match input {
+            SuiCallArg::Object(SuiObjectArg::SharedObject {
+                object_id,
+                mutable: true,
+                ..
+            }) if !SYSTEM_OBJECTS.contains(object_id) => Some(MutableSharedObject {
+                object_id: *object_id,
+                commands: dataflow
+                    .consumers(SuiArgument::Input(index))
+                    .iter()
+                    .map(|(command_index, _)| describe_command(commands, *command_index))
+                    .collect(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn describe_command(commands: &[SuiCommand], command_index: usize) -> String {
+    match commands.get(command_index) {
+        Some(SuiCommand::MoveCall(pwc)) => format!(
+            "{}::{} (command {})",
+            pwc.module,
+            pwc.function,
+            command_index + 1
+        ),
+        _ => format!("command {}", command_index + 1),
+    }
+}
+
+/// The warning field listing `objects`, each annotated with the risk, or None when there are
+/// none.
+///
+/// A list layout, so the annotations are kept at the top level of the payload.
+pub fn create_shared_objects_field(
+    objects: &[MutableSharedObject],
+) -> Result<Option<AnnotatedPayloadField>, VisualSignError> {
+    if objects.is_empty() {
+        return Ok(None);
+    }
+
+    let mut fields = Vec::with_capacity(objects.len());
+    for object in objects {
+        let object_id = object.object_id.to_hex_literal();
+        let text = if object.commands.is_empty() {
+            object_id.clone()
+        } else {
+            format!("{object_id} via {}", object.commands.join(", "))
+        };
+        let mut field = create_text_field("Shared Object", &text)?;
+        field.static_annotation = Some(SignablePayloadFieldStaticAnnotation {
+            text: format!("This transaction can modify shared object {object_id}"),
+        });
+        fields.push(field);
+    }
+
+    let ids: Vec<String> = objects
+        .iter()
+        .map(|object| truncate_address(&object.object_id.to_hex_literal()))
+        .collect();
+    Ok(Some(AnnotatedPayloadField {
+        signable_payload_field: SignablePayloadField::ListLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format!(
+                    "Warning: this transaction can modify {} shared object(s): {}",
+                    objects.len(),
+                    ids.join(", ")
+                ),
+                label: SHARED_OBJECTS_LABEL.to_string(),
+            },
+            list_layout: SignablePayloadFieldListLayout { fields },
+        },
+        static_annotation: None,
+        dynamic_annotation: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SuiTransactionWrapper;
+    use crate::core::SuiModuleResolver;
+    use crate::utils::payload_from_b64;
+
+    use move_bytecode_utils::module_cache::SyncModuleCache;
+    use sui_json_rpc_types::{
+        SuiTransactionBlockData, SuiTransactionBlockDataAPI, SuiTransactionBlockKind,
+    };
+    use visualsign::vsptrait::Transaction;
+
+    // The purchase of the summary tests: kiosk::purchase takes the kiosk (a mutable shared
+    // input) and transfer_policy::confirm_request the policy (a read-only shared input)
+    const PURCHASE_AND_PAY_TX: &str = "AQAAAAAABwEBXzwPfxwqO01eb3CBkqO0xdbn+AkaKzxNXm9wgZKjtMUqAAAAAAAAAAEAIJ6NfGtaSTgnFgX049LBsKmYh3ZlVEMyIRAA/+7dzLuqAAgA+QKVAAAAAAEBKzxNXm9wgZKjtMXW5/gJGis8TV5vcIGSo7TF1uf4CRArAAAAAAAAAAAAID5mBnBjcVYuaY/E5NbnxxAekmLOkxY0GI/iRu+ttwZ1AAgAypo7AAAAAAAgq83vEjRWeJCrze8SNFZ4kKvN7xI0VniQq83vEjRWeJAFAgACAQIAAQUAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACBWtpb3NrCHB1cmNoYXNlAQfuSWoMwE0Go0WYK6ZpfJDGGQIN6eJ0QIx4GfeH/2bhoQhzdWlmcmVucwdTdWlGcmVuAAMBAAABAQADAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAg90cmFuc2Zlcl9wb2xpY3kPY29uZmlybV9yZXF1ZXN0AQfuSWoMwE0Go0WYK6ZpfJDGGQIN6eJ0QIx4GfeH/2bhoQhzdWlmcmVucwdTdWlGcmVuAAIBAwADAQABAAEBAwEAAAABBAABAQMAAAEAAQYAPmYGcGNxVi5pj8Tk1ufHEB6SYs6TFjQYj+JG7623BnUCN8ccpwVmcafDNOXvnEAo6kzltjdniobA56to42fHdUio9wcjAAAAACDQVC4fMhsmX6OlHpAhyPR8LaRzgu43Bj8xrhlRY6YKG/Yv6m2ncHpPhbrEkOrSiyh1ID3T4FARE+raMUofCsQPqPcHIwAAAAAg5qp+jjoniUXPNG4N0/9XDFSpoUt0isbEUMiXjNtGivA+ZgZwY3FWLmmPxOTW58cQHpJizpMWNBiP4kbvrbcGdSECAAAAAAAADAqcAAAAAAAAAWEAkj0EN51BkbIUE/6lMi967MHGsBMl2i8TtntUnFhlC2rK8AW2fGQxc8mg1gTbV+2eHs1CsZ9m67cU4CWzA+9PAg//ECUrmzUzzsg0xYRgwDQDy9lAF8e6bpAa8/5Yec6s";
+
+    const KIOSK: &str = "0x5f3c0f7f1c2a3b4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5";
+
+    #[test]
+    fn test_mutable_shared_objects() {
+        let wrapper = <SuiTransactionWrapper as Transaction>::from_string(PURCHASE_AND_PAY_TX)
+            .expect("parse tx");
+        let block_data = SuiTransactionBlockData::try_from_with_module_cache(
+            wrapper.inner().clone(),
+            &SyncModuleCache::new(SuiModuleResolver),
+        )
+        .expect("block data");
+        let SuiTransactionBlockKind::ProgrammableTransaction(tx) = block_data.transaction() else {
+            panic!("expected programmable transaction");
+        };
+
+        let dataflow = PtbDataflow::analyze(&tx.commands, &tx.inputs);
+        let objects = mutable_shared_objects(&tx.commands, &tx.inputs, &dataflow);
+        assert_eq!(
+            objects,
+            vec![MutableSharedObject {
+                object_id: ObjectID::from_hex_literal(KIOSK).unwrap(),
+                commands: vec!["kiosk::purchase (command 2)".to_string()],
+            }]
+        );
+
+        let field = create_shared_objects_field(&objects)
+            .unwrap()
+            .unwrap()
+            .signable_payload_field;
+        let SignablePayloadField::ListLayout { list_layout, .. } = &field else {
+            panic!("Expected ListLayout");
+        };
+        assert_eq!(
+            list_layout.fields[0]
+                .static_annotation
+                .as_ref()
+                .unwrap()
+                .text,
+            format!("This transaction can modify shared object {KIOSK}")
+        );
+        assert!(create_shared_objects_field(&[]).unwrap().is_none());
+
+        // Shown right after the sender
+        let payload = payload_from_b64(PURCHASE_AND_PAY_TX);
+        assert_eq!(payload.fields[2].label(), "From");
+        assert_eq!(payload.fields[3], field);
+    }
+}
@@ -0,0 +1,324 @@
+//! Synthesized "Transaction Summary" section with the net effect of a whole PTB.
+//!
+//! Per-command layouts describe each step on its own; this section aggregates what leaves and
+//! reaches the sender across all of them, following values between commands with the dataflow
+//! analysis and naming the protocols that handled them.
+//!
+//! Constraints and behavior:
+//! - SUI out only counts coins split from the gas coin that are passed to a `MoveCall` or
+//!   transferred to another address. Gas fees are shown in `Transaction Details` instead.
+//! - Coins whose amount is not part of the transaction (e.g. the whole gas coin) are reported
+//!   as such rather than guessed.
+//! - Items received are `MoveCall` results transferred back to the sender; their types are not
+//!   known without on-chain data.
+
+use std::collections::HashSet;
+
+use sui_json_rpc_types::{SuiArgument, SuiCallArg, SuiCommand};
+use sui_types::base_types::SuiAddress;
+
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::{
+    create_amount_field_from, create_number_field, create_text_field,
+};
+use visualsign::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+use crate::core::{PtbDataflow, PtbValue, VisualizeResult};
+use crate::utils::{format_mist_as_sui, mist_amount, sui_amount, truncate_address};
+
+/// Net asset movements of a transaction, from the sender's point of view.
+#[derive(Debug, Default)]
+struct NetEffect {
+    /// SUI leaving the sender with a known amount, in MIST
+    sui_out: u64,
+    /// Whether SUI also left in an amount that is not known from the transaction
+    sui_out_unknown: bool,
+    /// `"{object} to {recipient}"` for every object sent to another address
+    transfers_out: Vec<String>,
+    /// Every `MoveCall` result sent back to the sender
+    received: Vec<String>,
+}
+
+impl NetEffect {
+    fn compute(
+        sender: &SuiAddress,
+        commands: &[SuiCommand],
+        inputs: &[SuiCallArg],
+        dataflow: &PtbDataflow,
+    ) -> Self {
+        let mut effect = Self::default();
+        // A coin is spent once even if several commands consume it
+        let mut spent = HashSet::new();
+
+        for (command_index, command) in commands.iter().enumerate() {
+            let SuiCommand::MoveCall(pwc) = command else {
+                continue;
+            };
+            // The gas coin itself can only be borrowed by a `MoveCall`, so only split coins count
+            for argument in &pwc.arguments {
+                if *argument != SuiArgument::GasCoin && dataflow.is_from_gas_coin(*argument) {
+                    effect.spend_sui(dataflow, &mut spent, (*argument).into(), command_index);
+                }
+            }
+        }
+
+        for transfer in dataflow.transfers() {
+            let object = describe_value(
+                commands,
+                inputs,
+                dataflow,
+                transfer.object,
+                transfer.command_index,
+            );
+
+            if transfer.recipient.as_ref() == Some(sender) {
+                if let PtbValue::Result { command, .. } = transfer.object
+                    && matches!(
+                        commands.get(command as usize),
+                        Some(SuiCommand::MoveCall(_))
+                    )
+                {
+                    effect.received.push(object);
+                }
+                continue;
+            }
+
+            if dataflow.is_from_gas_coin(transfer.object) {
+                effect.spend_sui(
+                    dataflow,
+                    &mut spent,
+                    transfer.object,
+                    transfer.command_index,
+                );
+            }
+            let recipient = transfer.recipient.map_or_else(
+                || "an unresolved recipient".to_string(),
+                std::string::ToString::to_string,
+            );
+            effect
+                .transfers_out
+                .push(format!("{object} to {recipient}"));
+        }
+
+        effect
+    }
+
+    fn spend_sui(
+        &mut self,
+        dataflow: &PtbDataflow,
+        spent: &mut HashSet<PtbValue>,
+        coin: PtbValue,
+        command_index: usize,
+    ) {
+        if !spent.insert(coin) {
+            return;
+        }
+
+        match dataflow.amount_at(coin, command_index) {
+            Some(amount) => self.sui_out = self.sui_out.saturating_add(amount),
+            None => self.sui_out_unknown = true,
+        }
+    }
+}
+
+/// Builds the "Transaction Summary" layout for a programmable transaction.
+///
+/// `results` are the per-command visualizations; they name the protocols involved.
+pub fn create_transaction_summary(
+    sender: &SuiAddress,
+    commands: &[SuiCommand],
+    inputs: &[SuiCallArg],
+    dataflow: &PtbDataflow,
+    results: &[VisualizeResult],
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    let effect = NetEffect::compute(sender, commands, inputs, dataflow);
+
+    let mut protocols: Vec<&str> = Vec::new();
+    for result in results {
+        if !protocols.contains(&result.kind.name()) {
+            protocols.push(result.kind.name());
+        }
+    }
+
+    let sui_out = format_mist_as_sui(effect.sui_out);
+    let title_text = if effect.sui_out_unknown {
+        format!("Transaction Summary: {sui_out} SUI out plus an unknown amount")
+    } else {
+        format!("Transaction Summary: {sui_out} SUI out")
+    };
+    let subtitle_text = format!(
+        "{} object(s) transferred, {} item(s) received",
+        effect.transfers_out.len(),
+        effect.received.len()
+    );
+
+    let mut condensed_fields = vec![
+        create_amount_field_from("SUI Out", &sui_amount(effect.sui_out))?,
+        create_number_field(
+            "Objects Transferred",
+            &effect.transfers_out.len().to_string(),
+            "",
+        )?,
+    ];
+    if !effect.received.is_empty() {
+        condensed_fields.push(create_number_field(
+            "Items Received",
+            &effect.received.len().to_string(),
+            "",
+        )?);
+    }
+
+    let mut expanded_fields = vec![
+        create_amount_field_from("SUI Out", &sui_amount(effect.sui_out))?,
+        create_amount_field_from("SUI Out (MIST)", &mist_amount(effect.sui_out))?,
+    ];
+    if effect.sui_out_unknown {
+        expanded_fields.push(create_text_field(
+            "Unknown SUI Out",
+            "Some SUI is spent in an amount not stated in the transaction (e.g. the whole gas coin)",
+        )?);
+    }
+    for (index, transfer) in effect.transfers_out.iter().enumerate() {
+        expanded_fields.push(create_text_field(
+            &format!("Transfer {}", index + 1),
+            transfer,
+        )?);
+    }
+    for (index, item) in effect.received.iter().enumerate() {
+        expanded_fields.push(create_text_field(&format!("Received {}", index + 1), item)?);
+    }
+    if !protocols.is_empty() {
+        expanded_fields.push(create_text_field("Protocols", &protocols.join(", "))?);
+    }
+    expanded_fields.push(create_text_field(
+        "Note",
+        "Gas fees are not included; see Transaction Details",
+    )?);
+
+    let preview_layout = SignablePayloadFieldPreviewLayout {
+        title: Some(SignablePayloadFieldTextV2 {
+            text: title_text.clone(),
+        }),
+        subtitle: Some(SignablePayloadFieldTextV2 {
+            text: subtitle_text,
+        }),
+        condensed: Some(SignablePayloadFieldListLayout {
+            fields: condensed_fields,
+        }),
+        expanded: Some(SignablePayloadFieldListLayout {
+            fields: expanded_fields,
+        }),
+    };
+
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: title_text,
+                label: "Transaction Summary".to_string(),
+            },
+            preview_layout,
+        },
+    })
+}
+
+/// Short description of a transferred value for the summary lines.
+fn describe_value(
+    commands: &[SuiCommand],
+    inputs: &[SuiCallArg],
+    dataflow: &PtbDataflow,
+    value: PtbValue,
+    command_index: usize,
+) -> String {
+    match value {
+        PtbValue::GasCoin => "Gas coin (entire SUI balance)".to_string(),
+        _ if dataflow.is_from_gas_coin(value) => {
+            dataflow.amount_at(value, command_index).map_or_else(
+                || "SUI coin (amount unknown)".to_string(),
+                |amount| format!("{} SUI", format_mist_as_sui(amount)),
+            )
+        }
+        PtbValue::Input(index) => inputs
+            .get(index as usize)
+            .and_then(SuiCallArg::object)
+            .map_or_else(
+                || format!("Input {index}"),
+                |id| format!("Object {}", truncate_address(&id.to_string())),
+            ),
+        PtbValue::Result { command, .. } => match commands.get(command as usize) {
+            Some(SuiCommand::MoveCall(pwc)) => format!(
+                "Result of {}::{} (command {})",
+                pwc.module,
+                pwc.function,
+                command + 1
+            ),
+            _ => format!("Result of command {}", command + 1),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SuiTransactionWrapper;
+    use crate::core::SuiModuleResolver;
+    use crate::utils::payload_from_b64;
+
+    use move_bytecode_utils::module_cache::SyncModuleCache;
+    use sui_json_rpc_types::{
+        SuiTransactionBlockData, SuiTransactionBlockDataAPI, SuiTransactionBlockKind,
+    };
+    use visualsign::test_utils::{assert_has_field, assert_has_field_with_value};
+    use visualsign::vsptrait::Transaction;
+
+    // Synthetic transaction: split 2.5 and 1 SUI from gas, kiosk::purchase with the first coin,
+    // transfer_policy::confirm_request, transfer the purchased item to the sender and the second
+    // coin to 0xabcdef...7890
+    const PURCHASE_AND_PAY_TX: &str = "AQAAAAAABwEBXzwPfxwqO01eb3CBkqO0xdbn+AkaKzxNXm9wgZKjtMUqAAAAAAAAAAEAIJ6NfGtaSTgnFgX049LBsKmYh3ZlVEMyIRAA/+7dzLuqAAgA+QKVAAAAAAEBKzxNXm9wgZKjtMXW5/gJGis8TV5vcIGSo7TF1uf4CRArAAAAAAAAAAAAID5mBnBjcVYuaY/E5NbnxxAekmLOkxY0GI/iRu+ttwZ1AAgAypo7AAAAAAAgq83vEjRWeJCrze8SNFZ4kKvN7xI0VniQq83vEjRWeJAFAgACAQIAAQUAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACBWtpb3NrCHB1cmNoYXNlAQfuSWoMwE0Go0WYK6ZpfJDGGQIN6eJ0QIx4GfeH/2bhoQhzdWlmcmVucwdTdWlGcmVuAAMBAAABAQADAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAg90cmFuc2Zlcl9wb2xpY3kPY29uZmlybV9yZXF1ZXN0AQfuSWoMwE0Go0WYK6ZpfJDGGQIN6eJ0QIx4GfeH/2bhoQhzdWlmcmVucwdTdWlGcmVuAAIBAwADAQABAAEBAwEAAAABBAABAQMAAAEAAQYAPmYGcGNxVi5pj8Tk1ufHEB6SYs6TFjQYj+JG7623BnUCN8ccpwVmcafDNOXvnEAo6kzltjdniobA56to42fHdUio9wcjAAAAACDQVC4fMhsmX6OlHpAhyPR8LaRzgu43Bj8xrhlRY6YKG/Yv6m2ncHpPhbrEkOrSiyh1ID3T4FARE+raMUofCsQPqPcHIwAAAAAg5qp+jjoniUXPNG4N0/9XDFSpoUt0isbEUMiXjNtGivA+ZgZwY3FWLmmPxOTW58cQHpJizpMWNBiP4kbvrbcGdSECAAAAAAAADAqcAAAAAAAAAWEAkj0EN51BkbIUE/6lMi967MHGsBMl2i8TtntUnFhlC2rK8AW2fGQxc8mg1gTbV+2eHs1CsZ9m67cU4CWzA+9PAg//ECUrmzUzzsg0xYRgwDQDy9lAF8e6bpAa8/5Yec6s";
+
+    const RECIPIENT: &str = "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890";
+
+    #[test]
+    fn test_net_effect() {
+        let wrapper = <SuiTransactionWrapper as Transaction>::from_string(PURCHASE_AND_PAY_TX)
+            .expect("parse tx");
+        let block_data = SuiTransactionBlockData::try_from_with_module_cache(
+            wrapper.inner().clone(),
+            &SyncModuleCache::new(SuiModuleResolver),
+        )
+        .expect("block data");
+        let SuiTransactionBlockKind::ProgrammableTransaction(tx) = block_data.transaction() else {
+            panic!("expected programmable transaction");
+        };
+
+        let dataflow = PtbDataflow::analyze(&tx.commands, &tx.inputs);
+        let effect = NetEffect::compute(block_data.sender(), &tx.commands, &tx.inputs, &dataflow);
+
+        assert_eq!(effect.sui_out, 3_500_000_000);
+        assert!(!effect.sui_out_unknown);
+        assert_eq!(effect.transfers_out, vec![format!("1 SUI to {RECIPIENT}")]);
+        assert_eq!(
+            effect.received,
+            vec!["Result of kiosk::purchase (command 2)".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_transaction_summary_is_first_after_network() {
+        let payload = payload_from_b64(PURCHASE_AND_PAY_TX);
+        assert_eq!(payload.fields[0].label(), "Network");
+        assert_eq!(payload.fields[1].label(), "Transaction Summary");
+        assert_eq!(payload.fields[2].label(), "From");
+
+        assert_has_field_with_value(&payload, "SUI Out", "3.5");
+        assert_has_field_with_value(&payload, "SUI Out (MIST)", "3500000000");
+        assert_has_field_with_value(&payload, "Objects Transferred", "1");
+        assert_has_field_with_value(&payload, "Items Received", "1");
+        assert_has_field_with_value(&payload, "Protocols", "Native Transfer, Kiosk");
+        assert_has_field(&payload, "Received 1");
+    }
+}
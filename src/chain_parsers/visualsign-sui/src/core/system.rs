@@ -0,0 +1,242 @@
+//! Payloads of the transaction kinds validators execute on their own: epoch changes, consensus
+//! commit prologues, state updates and the like.
+//!
+//! No user signs them, but monitoring tools run every on-chain transaction through the parser.
+//! They are shown under their own [`SYSTEM_PAYLOAD_TYPE`], so they cannot pass for a user
+//! transaction, with the few values each kind carries.
+//!
+//! Constraints and behavior:
+//! - Built from the BCS transaction alone; the RPC form programmable transactions are converted
+//!   to resolves Move modules, which system transactions do not need to be shown.
+//! - The sender is the zero address and the gas is not paid by anyone, so neither is shown.
+//! - Programmable system transactions are listed with their command count; their commands are
+//!   framework calls the visualizers do not cover.
+
+use sui_types::transaction::{TransactionData, TransactionDataAPI, TransactionKind};
+
+use visualsign::errors::VisualSignError;
+use visualsign::field_builders::{
+    create_amount_field, create_number_field, create_raw_data_field, create_text_field,
+    create_timestamp_field_from_millis,
+};
+use visualsign::vsptrait::VisualSignOptions;
+use visualsign::{AnnotatedPayloadField, SignablePayload, SignablePayloadField};
+
+use crate::core::transaction::get_tx_network;
+
+/// Payload type of system transactions; user transactions are `"Sui"`
+pub const SYSTEM_PAYLOAD_TYPE: &str = "SuiSystemTransaction";
+
+/// The kinds shown as system transactions, as listed in the parser capabilities.
+pub const SYSTEM_TRANSACTION_TYPES: [&str; 10] = [
+    "Change Epoch",
+    "Genesis",
+    "Consensus Commit",
+    "Authenticator State Update",
+    "Randomness State Update",
+    "End of Epoch Transaction",
+    "Consensus Commit Prologue V2",
+    "Consensus Commit Prologue V3",
+    "Consensus Commit Prologue V4",
+    "Programmable System Transaction",
+];
+
+/// The payload of `transaction` when it is a system transaction, or None for a programmable
+/// transaction.
+pub fn system_transaction_payload(
+    transaction: &TransactionData,
+    options: &VisualSignOptions,
+) -> Result<Option<SignablePayload>, VisualSignError> {
+    let (transaction_type, kind_fields) = match transaction.kind() {
+        TransactionKind::ProgrammableTransaction(_) => return Ok(None),
+        TransactionKind::ChangeEpoch(change) => (
+            SYSTEM_TRANSACTION_TYPES[0],
+            vec![
+                create_number_field("New Epoch", &change.epoch.to_string(), "")?,
+                create_number_field(
+                    "Protocol Version",
+                    &change.protocol_version.as_u64().to_string(),
+                    "",
+                )?,
+                create_amount_field("Storage Charge", &change.storage_charge.to_string(), "MIST")?,
+                create_amount_field(
+                    "Computation Charge",
+                    &change.computation_charge.to_string(),
+                    "MIST",
+                )?,
+                create_amount_field("Storage Rebate", &change.storage_rebate.to_string(), "MIST")?,
+                timestamp_field("Epoch Start", change.epoch_start_timestamp_ms)?,
+            ],
+        ),
+        TransactionKind::Genesis(genesis) => (
+            SYSTEM_TRANSACTION_TYPES[1],
+            vec![create_number_field(
+                "Objects",
+                &genesis.objects.len().to_string(),
+                "",
+            )?],
+        ),
+        TransactionKind::ConsensusCommitPrologue(prologue) => (
+            SYSTEM_TRANSACTION_TYPES[2],
+            commit_fields(prologue.epoch, prologue.round, prologue.commit_timestamp_ms)?,
+        ),
+        TransactionKind::AuthenticatorStateUpdate(update) => (
+            SYSTEM_TRANSACTION_TYPES[3],
+            vec![
+                create_number_field("Epoch", &update.epoch.to_string(), "")?,
+                create_number_field("Round", &update.round.to_string(), "")?,
+                create_number_field("Active JWKs", &update.new_active_jwks.len().to_string(), "")?,
+            ],
+        ),
+        TransactionKind::RandomnessStateUpdate(update) => (
+            SYSTEM_TRANSACTION_TYPES[4],
+            vec![
+                create_number_field("Epoch", &update.epoch.to_string(), "")?,
+                create_number_field(
+                    "Randomness Round",
+                    &update.randomness_round.0.to_string(),
+                    "",
+                )?,
+            ],
+        ),
+        TransactionKind::EndOfEpochTransaction(transactions) => (
+            SYSTEM_TRANSACTION_TYPES[5],
+            vec![create_number_field(
+                "Operations",
+                &transactions.len().to_string(),
+                "",
+            )?],
+        ),
+        TransactionKind::ConsensusCommitPrologueV2(prologue) => (
+            SYSTEM_TRANSACTION_TYPES[6],
+            commit_fields(prologue.epoch, prologue.round, prologue.commit_timestamp_ms)?,
+        ),
+        TransactionKind::ConsensusCommitPrologueV3(prologue) => (
+            SYSTEM_TRANSACTION_TYPES[7],
+            commit_fields(prologue.epoch, prologue.round, prologue.commit_timestamp_ms)?,
+        ),
+        TransactionKind::ConsensusCommitPrologueV4(prologue) => (
+            SYSTEM_TRANSACTION_TYPES[8],
+            commit_fields(prologue.epoch, prologue.round, prologue.commit_timestamp_ms)?,
+        ),
+        TransactionKind::ProgrammableSystemTransaction(ptb) => (
+            SYSTEM_TRANSACTION_TYPES[9],
+            vec![create_number_field(
+                "Commands",
+                &ptb.commands.len().to_string(),
+                "",
+            )?],
+        ),
+    };
+
+    let mut fields: Vec<SignablePayloadField> = vec![
+        get_tx_network()?.signable_payload_field,
+        create_text_field("Transaction Type", transaction_type)?.signable_payload_field,
+        create_text_field("Executed By", "Validators; no user signs this transaction")?
+            .signable_payload_field,
+    ];
+    fields.extend(
+        kind_fields
+            .into_iter()
+            .map(|field| field.signable_payload_field),
+    );
+    if let Ok(encoded) = bcs::to_bytes(transaction) {
+        fields.push(create_raw_data_field(&encoded, None)?.signable_payload_field);
+    }
+
+    let title = options
+        .transaction_name
+        .clone()
+        .unwrap_or_else(|| transaction_type.to_string());
+    Ok(Some(SignablePayload::new(
+        0,
+        title,
+        None,
+        fields,
+        SYSTEM_PAYLOAD_TYPE.to_string(),
+    )))
+}
+
+fn commit_fields(
+    epoch: u64,
+    round: u64,
+    commit_timestamp_ms: u64,
+) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
+    Ok(vec![
+        create_number_field("Epoch", &epoch.to_string(), "")?,
+        create_number_field("Round", &round.to_string(), "")?,
+        timestamp_field("Commit Time", commit_timestamp_ms)?,
+    ])
+}
+
+// Keep timestamps outside the representable range as raw text
+fn timestamp_field(
+    label: &str,
+    timestamp_ms: u64,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    match i64::try_from(timestamp_ms)
+        .map(|millis| create_timestamp_field_from_millis(label, millis))
+    {
+        Ok(Ok(field)) => Ok(field),
+        _ => create_text_field(label, &format!("{timestamp_ms} ms")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{SuiTransactionWrapper, SuiVisualSignConverter};
+
+    use sui_types::base_types::SuiAddress;
+    use sui_types::messages_consensus::ConsensusCommitPrologue;
+    use sui_types::transaction::GasData;
+    use visualsign::vsptrait::VisualSignConverter;
+
+    fn consensus_commit_prologue() -> TransactionData {
+        TransactionData::new_with_gas_data(
+            TransactionKind::ConsensusCommitPrologue(ConsensusCommitPrologue {
+                epoch: 812,
+                round: 4_215,
+                commit_timestamp_ms: 1_750_000_000_000,
+            }),
+            SuiAddress::ZERO,
+            GasData {
+                payment: vec![],
+                owner: SuiAddress::ZERO,
+                price: 1,
+                budget: 0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_system_transaction_payload() {
+        let outcome = SuiVisualSignConverter
+            .to_visual_sign_outcome(
+                SuiTransactionWrapper::new(consensus_commit_prologue()),
+                VisualSignOptions::default(),
+            )
+            .expect("system transactions convert");
+        assert!(outcome.warnings.is_empty());
+
+        let payload = outcome.payload;
+        assert_eq!(payload.payload_type, SYSTEM_PAYLOAD_TYPE);
+        assert_eq!(payload.title, "Consensus Commit");
+        let labels: Vec<&str> = payload.fields.iter().map(|f| f.label().as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "Network",
+                "Transaction Type",
+                "Executed By",
+                "Epoch",
+                "Round",
+                "Commit Time",
+                "Raw Data",
+            ]
+        );
+        assert_eq!(payload.fields[3].fallback_text(), "812");
+        assert_eq!(payload.fields[4].fallback_text(), "4215");
+        assert!(payload.fields.iter().all(|f| f.label() != "From"));
+    }
+}
@@ -1,15 +1,19 @@
 use super::determine_transaction_type_string;
+use crate::utils::sui_amount;
 
 use sui_json_rpc_types::{SuiTransactionBlockData, SuiTransactionBlockDataAPI};
-use sui_types::transaction::TransactionData;
+use sui_types::transaction::{TransactionData, TransactionDataAPI, TransactionExpiration};
 
 use visualsign::{
     AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
     SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
     errors::VisualSignError,
+    expiration::{Expiration, create_expiration_field},
+    fee::create_max_total_cost_field,
     field_builders::{
         create_address_field, create_amount_field, create_raw_data_field, create_text_field,
     },
+    replay::{ReplayProtection, create_replay_protection_field},
 };
 
 pub fn get_tx_network() -> Result<AnnotatedPayloadField, VisualSignError> {
@@ -23,6 +27,10 @@ pub fn get_tx_details(
     let payload_fields: Vec<AnnotatedPayloadField> = vec![create_tx_type_fields(block_data)?]
         .into_iter()
         .chain(create_tx_gas_fields(block_data)?)
+        .chain([
+            create_tx_expiration_field(tx_data)?,
+            create_tx_replay_protection_field(tx_data)?,
+        ])
         .chain(create_tx_data_fields(tx_data)?)
         .collect();
 
@@ -101,9 +109,40 @@ fn create_tx_gas_fields(
             &block_data.gas_data().price.to_string(),
             "MIST",
         )?,
+        // The budget is already the total the gas payment can be charged, price included
+        create_max_total_cost_field(
+            &sui_amount(block_data.gas_data().budget),
+            "gas budget; SUI moved by the commands is not included",
+        ),
     ])
 }
 
+fn create_tx_expiration_field(
+    tx_data: &TransactionData,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    let expiration = match tx_data.expiration() {
+        TransactionExpiration::None => Expiration::Never,
+        TransactionExpiration::Epoch(epoch) => Expiration::Epoch(*epoch),
+        #[allow(unreachable_patterns)]
+        other => Expiration::Described(format!("{other:?}")),
+    };
+    create_expiration_field(&expiration)
+}
+
+// Sui signs over the exact versions of the objects it spends, the gas coins included, so a
+// transaction cannot execute twice even without an expiration epoch
+fn create_tx_replay_protection_field(
+    tx_data: &TransactionData,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    let protection = match tx_data.expiration() {
+        TransactionExpiration::Epoch(epoch) => ReplayProtection::Epoch(*epoch),
+        _ => ReplayProtection::Described(
+            "Gas coin versions; each can be spent only once".to_string(),
+        ),
+    };
+    create_replay_protection_field(&protection)
+}
+
 fn create_tx_data_fields(
     tx_data: &TransactionData,
 ) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
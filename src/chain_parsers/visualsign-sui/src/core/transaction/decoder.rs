@@ -1,6 +1,7 @@
 use base64::Engine;
 
 use visualsign::encodings::SupportedEncodings;
+use visualsign::lossless;
 use visualsign::vsptrait::TransactionParseError;
 
 use sui_json_rpc_types::{
@@ -8,24 +9,54 @@ use sui_json_rpc_types::{
 };
 use sui_types::transaction::{SenderSignedData, TransactionData};
 
-/// Decode a transaction from string format
-pub fn decode_transaction(
+fn decode_bytes(
     raw_transaction: &str,
     encodings: SupportedEncodings,
-) -> Result<TransactionData, TransactionParseError> {
+) -> Result<Vec<u8>, TransactionParseError> {
     if raw_transaction.is_empty() {
         return Err(TransactionParseError::DecodeError(
             "Transaction is empty".into(),
         ));
     }
 
-    let bytes = match encodings {
+    match encodings {
         SupportedEncodings::Base64 => base64::engine::general_purpose::STANDARD
             .decode(raw_transaction)
-            .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?,
+            .map_err(|e| TransactionParseError::DecodeError(e.to_string())),
         SupportedEncodings::Hex => hex::decode(raw_transaction)
-            .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?,
-    };
+            .map_err(|e| TransactionParseError::DecodeError(e.to_string())),
+    }
+}
+
+/// Checks that the transaction in `raw_transaction` is the BCS encoding of what
+/// [`decode_transaction`] decodes from it, signatures included.
+///
+/// # Errors
+///
+/// Returns an error if the input does not decode, or re-encodes to different bytes.
+pub fn verify_transaction_encoding(
+    raw_transaction: &str,
+    encodings: SupportedEncodings,
+) -> Result<(), TransactionParseError> {
+    let bytes = decode_bytes(raw_transaction, encodings)?;
+
+    let reencoded = if let Ok(sender_signed_data) = bcs::from_bytes::<SenderSignedData>(&bytes) {
+        bcs::to_bytes(&sender_signed_data)
+    } else {
+        bcs::from_bytes::<TransactionData>(&bytes)
+            .and_then(|transaction_data| bcs::to_bytes(&transaction_data))
+    }
+    .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?;
+
+    Ok(lossless::check_reencoding("Sui", &bytes, &reencoded)?)
+}
+
+/// Decode a transaction from string format
+pub fn decode_transaction(
+    raw_transaction: &str,
+    encodings: SupportedEncodings,
+) -> Result<TransactionData, TransactionParseError> {
+    let bytes = decode_bytes(raw_transaction, encodings)?;
 
     if let Ok(sender_signed_data) = bcs::from_bytes::<SenderSignedData>(&bytes) {
         return Ok(sender_signed_data.transaction_data().clone());
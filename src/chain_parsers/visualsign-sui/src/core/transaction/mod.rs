@@ -2,4 +2,6 @@ mod common;
 mod decoder;
 
 pub use common::{get_tx_details, get_tx_network};
-pub use decoder::{decode_transaction, determine_transaction_type_string};
+pub use decoder::{
+    decode_transaction, determine_transaction_type_string, verify_transaction_encoding,
+};
@@ -1,20 +1,35 @@
 //! Public conversion entry points and wrapper types for `VisualSign` on Sui.
 
-use crate::core::commands::decode_commands;
+use std::str::FromStr;
+
+use crate::core::PtbDataflow;
+use crate::core::commands::{FailedCommand, decode_commands};
 use crate::core::helper::SuiModuleResolver;
+use crate::core::shared_objects::{create_shared_objects_field, mutable_shared_objects};
+use crate::core::summary::create_transaction_summary;
+use crate::core::system::{SYSTEM_TRANSACTION_TYPES, system_transaction_payload};
 use crate::core::transaction::{
     decode_transaction, determine_transaction_type_string, get_tx_details, get_tx_network,
+    verify_transaction_encoding,
 };
 
 use move_bytecode_utils::module_cache::SyncModuleCache;
 
-use sui_json_rpc_types::SuiTransactionBlockData;
+use sui_json_rpc_types::{
+    SuiTransactionBlockData, SuiTransactionBlockDataAPI, SuiTransactionBlockKind,
+};
+use sui_types::base_types::SuiAddress;
 use sui_types::transaction::TransactionData;
 
 use crate::core::commands;
 use visualsign::{
     SignablePayload, SignablePayloadField,
+    capabilities::ParserCapabilities,
     encodings::SupportedEncodings,
+    outcome::{ParseOutcome, ParseWarning, ParseWarningKind},
+    parser_info::ParserInfo,
+    sender::{create_sender_field, resolve_sender},
+    telemetry::record_command_count,
     vsptrait::{
         Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
         VisualSignError, VisualSignOptions,
@@ -51,6 +66,10 @@ impl Transaction for SuiTransactionWrapper {
         Ok(Self { transaction })
     }
 
+    fn verify_lossless(&self, data: &str) -> Result<(), TransactionParseError> {
+        verify_transaction_encoding(data, SupportedEncodings::detect(data))
+    }
+
     fn transaction_type(&self) -> String {
         "Sui".to_string()
     }
@@ -67,53 +86,145 @@ impl VisualSignConverter<SuiTransactionWrapper> for SuiVisualSignConverter {
         transaction_wrapper: SuiTransactionWrapper,
         options: VisualSignOptions,
     ) -> Result<SignablePayload, VisualSignError> {
-        let transaction = transaction_wrapper.inner();
+        convert_to_visual_sign_payload(transaction_wrapper.inner(), &options)
+            .map(|(payload, _)| payload)
+    }
 
-        convert_to_visual_sign_payload(
-            transaction,
-            options.decode_transfers,
-            options.transaction_name,
-        )
+    fn to_visual_sign_outcome(
+        &self,
+        transaction_wrapper: SuiTransactionWrapper,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
+        let (payload, failed) =
+            convert_to_visual_sign_payload(transaction_wrapper.inner(), &options)?;
+        let warnings = failed
+            .iter()
+            .map(|command| {
+                ParseWarning::new(
+                    ParseWarningKind::DecodeFailed,
+                    format!(
+                        "command {} could not be decoded and is shown raw: {}",
+                        command.index, command.error
+                    ),
+                )
+            })
+            .collect();
+        Ok(ParseOutcome::with_warnings(payload, warnings))
+    }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        // Visualizers decode the commands of programmable transactions; system kinds get the
+        // values they carry
+        let transaction_types: Vec<&str> = std::iter::once("Programmable Transaction")
+            .chain(SYSTEM_TRANSACTION_TYPES)
+            .collect();
+        ParserCapabilities::new(&["hex", "base64"], &transaction_types)
+            .with_decoders(
+                commands::available_visualizers()
+                    .iter()
+                    .map(|visualizer| visualizer.kind().name()),
+            )
+            .with_feature("programmable_transactions", true)
+            .with_feature("system_transactions", true)
+            .with_feature("lossless_verification", true)
     }
 }
 
-/// Convert Sui transaction to a `VisualSign` payload.
+/// Convert Sui transaction to a `VisualSign` payload, with the commands shown as `Unknown`
+/// fields in partial-parse mode.
+///
+/// System transactions get their own payload type and skip the conversion to the RPC form.
 fn convert_to_visual_sign_payload(
     transaction: &TransactionData,
-    decode_transfers: bool,
-    title: Option<String>,
-) -> Result<SignablePayload, VisualSignError> {
+    options: &VisualSignOptions,
+) -> Result<(SignablePayload, Vec<FailedCommand>), VisualSignError> {
+    if let Some(payload) = system_transaction_payload(transaction, options)? {
+        return Ok((payload, Vec::new()));
+    }
+
     let block_data: SuiTransactionBlockData = SuiTransactionBlockData::try_from_with_module_cache(
         transaction.clone(),
         &SyncModuleCache::new(SuiModuleResolver),
     )
     .map_err(|e| VisualSignError::ParseError(TransactionParseError::DecodeError(e.to_string())))?;
 
+    let ptb = match block_data.transaction() {
+        SuiTransactionBlockKind::ProgrammableTransaction(tx) => Some(tx),
+        _ => None,
+    };
+    if let Some(tx) = ptb {
+        record_command_count(tx.commands.len());
+    }
+    let dataflow = ptb.map_or_else(PtbDataflow::default, |tx| {
+        PtbDataflow::analyze(&tx.commands, &tx.inputs)
+    });
+
+    let mut results = Vec::new();
+    if options.decode_transfers {
+        results.extend(commands::decode_transfers(&block_data, &dataflow)?);
+    }
+    let (decoded, failed) = decode_commands(
+        transaction,
+        &block_data,
+        &dataflow,
+        options.output.partial_parse,
+    )?;
+    results.extend(decoded);
+
     let mut fields: Vec<SignablePayloadField> = vec![get_tx_network()?.signable_payload_field];
 
-    if decode_transfers {
-        fields.extend(
-            commands::decode_transfers(&block_data)?
-                .iter()
-                .map(|e| e.signable_payload_field.clone()),
+    if let Some(tx) = ptb {
+        fields.push(
+            create_transaction_summary(
+                block_data.sender(),
+                &tx.commands,
+                &tx.inputs,
+                &dataflow,
+                &results,
+            )?
+            .signable_payload_field,
         );
     }
 
+    // Compare parsed addresses so a provided sender in another hex case still matches
+    let sender = resolve_sender(
+        Some(&block_data.sender().to_string()),
+        options.sender.as_deref(),
+        |derived, provided| {
+            SuiAddress::from_str(provided).is_ok_and(|provided| provided.to_string() == derived)
+        },
+    )?;
+    if let Some(sender) = &sender {
+        fields.push(create_sender_field(sender));
+    }
+
+    if let Some(tx) = ptb {
+        let objects = mutable_shared_objects(&tx.commands, &tx.inputs, &dataflow);
+        if let Some(field) = create_shared_objects_field(&objects)? {
+            fields.push(field.signable_payload_field);
+        }
+    }
+
     fields.extend(
-        decode_commands(&block_data)?
-            .iter()
-            .map(|e| e.signable_payload_field.clone()),
+        results
+            .into_iter()
+            .flat_map(|result| result.field)
+            .map(|field| field.signable_payload_field),
     );
 
     fields.push(get_tx_details(transaction, &block_data)?.signable_payload_field);
 
-    let title = title.unwrap_or_else(|| determine_transaction_type_string(&block_data).to_string());
-    Ok(SignablePayload::new(
-        0,
-        title,
-        None,
-        fields,
-        "Sui".to_string(),
+    let title = options
+        .transaction_name
+        .clone()
+        .unwrap_or_else(|| determine_transaction_type_string(&block_data).to_string());
+    Ok((
+        SignablePayload::new(0, title, None, fields, "Sui".to_string()),
+        failed,
     ))
 }
 
@@ -165,6 +276,30 @@ mod tests {
         assert!(json_result.is_ok());
     }
 
+    #[test]
+    fn test_sender_is_checked_against_provided_sender() {
+        let test_data = "AQAAAAAAAgAI6AMAAAAAAAAAIKHjrlUcKr48a86iLT8ZNWpkcIbWvVasDQnk7u0GKQt2AgIAAQEAAAEBAgAAAQEA1ukuAC4mw6+yCIABwbWCC2TyvDUb/aWiNCrL+fXBysIBy0he+AoLr5B5piHELIsMtlzpmG4cgf0W7ogDjwBKWu3zD9AUAAAAACB0zCGEALsfD5u98y58qbKGIiXkCtDxxN2Pu+r/HyOy1tbpLgAuJsOvsgiAAcG1ggtk8rw1G/2lojQqy/n1wcrC6AMAAAAAAABAS0wAAAAAAAABYQBMegviWYFsLskcYMnTIhZRxiZkET3j2RqtgG1g7f1/EuPjfCHfTvgDqVys+AA6jLWojR35eW4HoOh8qURdshkADNDs6YjOg+HDmdMLe0zMuMDJKqzwIYg08CT6mXiLc2Y=";
+        let payload = payload_from_b64(test_data);
+        let sender = payload
+            .fields
+            .iter()
+            .find(|f| f.label() == "From")
+            .expect("Should show the sender")
+            .fallback_text()
+            .clone();
+
+        let options = |sender: String| VisualSignOptions {
+            sender: Some(sender),
+            ..Default::default()
+        };
+        let upper = format!("0x{}", sender.trim_start_matches("0x").to_uppercase());
+        assert!(transaction_string_to_visual_sign(test_data, options(upper)).is_ok());
+        let error =
+            transaction_string_to_visual_sign(test_data, options(SuiAddress::ZERO.to_string()))
+                .unwrap_err();
+        assert!(matches!(error, VisualSignError::ValidationError(_)));
+    }
+
     #[test]
     fn test_sui_transaction_trait() {
         let test_data = "AQAAAAAAAgAI6AMAAAAAAAAAIKHjrlUcKr48a86iLT8ZNWpkcIbWvVasDQnk7u0GKQt2AgIAAQEAAAEBAgAAAQEA1ukuAC4mw6+yCIABwbWCC2TyvDUb/aWiNCrL+fXBysIBy0he+AoLr5B5piHELIsMtlzpmG4cgf0W7ogDjwBKWu3zD9AUAAAAACB0zCGEALsfD5u98y58qbKGIiXkCtDxxN2Pu+r/HyOy1tbpLgAuJsOvsgiAAcG1ggtk8rw1G/2lojQqy/n1wcrC6AMAAAAAAABAS0wAAAAAAAABYQBMegviWYFsLskcYMnTIhZRxiZkET3j2RqtgG1g7f1/EuPjfCHfTvgDqVys+AA6jLWojR35eW4HoOh8qURdshkADNDs6YjOg+HDmdMLe0zMuMDJKqzwIYg08CT6mXiLc2Y=";
@@ -193,5 +328,22 @@ mod tests {
             transaction_preview.is_some(),
             "Should have Transaction Details layout"
         );
+
+        let Some(SignablePayloadField::PreviewLayout { preview_layout, .. }) = transaction_preview
+        else {
+            panic!("Expected PreviewLayout");
+        };
+        let replay_protection = preview_layout
+            .expanded
+            .as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .find(|f| f.signable_payload_field.label() == "Replay Protection")
+            .unwrap();
+        assert_eq!(
+            replay_protection.signable_payload_field.fallback_text(),
+            "Gas coin versions; each can be spent only once"
+        );
     }
 }
@@ -1,11 +1,10 @@
 use crate::core::{CommandVisualizer, SuiIntegrationConfig, VisualizerContext, VisualizerKind};
 use crate::truncate_address;
-use crate::utils::{CoinObject, decode_number, parse_numeric_argument};
+use crate::utils::{CoinObject, format_mist_as_sui, parse_numeric_argument};
 
 use sui_json_rpc_types::{SuiArgument, SuiCallArg, SuiCommand, SuiObjectArg};
 use sui_types::base_types::SuiAddress;
 
-use sui_types::gas_coin::MIST_PER_SUI;
 use visualsign::errors::{TransactionParseError, VisualSignError};
 use visualsign::field_builders::{create_address_field, create_amount_field, create_text_field};
 use visualsign::{
@@ -126,46 +125,22 @@ fn resolve_object(
     }
 }
 
-fn resolve_amount(
-    commands: &[SuiCommand],
-    inputs: &[SuiCallArg],
-    object_argument: SuiArgument,
-) -> Result<Option<u64>, VisualSignError> {
-    let SuiArgument::Result(_) = object_argument else {
-        return Ok(None);
-    };
-
-    let command = commands
-        .get(parse_numeric_argument(object_argument)? as usize)
-        .ok_or(VisualSignError::MissingData("Command not found".into()))?;
-
-    match command {
-        SuiCommand::SplitCoins(_, input_coin_args) if input_coin_args.len() == 1 => {
-            let amount_arg = inputs
-                .get(parse_numeric_argument(input_coin_args[0])? as usize)
-                .ok_or(VisualSignError::MissingData(
-                    "Amount argument not found".into(),
-                ))?;
-
-            Ok(Some(decode_number::<u64>(amount_arg)?))
-        }
-        _ => Ok(None),
-    }
-}
-
 fn visualize_transfer_command(
     context: &VisualizerContext,
     receiver: SuiAddress,
     object_sent_to_receiver: &CoinObject,
     object_argument: SuiArgument,
 ) -> Result<AnnotatedPayloadField, VisualSignError> {
-    let amount = resolve_amount(context.commands(), context.inputs(), object_argument)?;
+    let amount = context.resolve_amount(object_argument);
 
     let (amount_str, title_text, amount_field) = match amount {
         Some(amount) => {
             let title_text = match object_sent_to_receiver {
                 CoinObject::Sui => {
-                    format!("Transfer: {} MIST ({} SUI)", amount, amount / MIST_PER_SUI)
+                    format!(
+                        "Transfer: {amount} MIST ({} SUI)",
+                        format_mist_as_sui(amount)
+                    )
                 }
                 CoinObject::UnknownObject(id) => format!("Transfer: {amount} {id}"),
             };
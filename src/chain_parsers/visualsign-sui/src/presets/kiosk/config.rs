@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+crate::chain_config! {
+  config KIOSK_CONFIG as Config;
+
+  sui_framework => {
+      package_id => 0x2,
+      modules as KioskModules: {
+        kiosk as Kiosk => KioskFunctions: {
+          place as Place => PlaceIndexes(),
+          take as Take => TakeIndexes(),
+          list as List => ListIndexes(
+            price as Price: u64 => 3 => get_price,
+          ),
+          place_and_list as PlaceAndList => PlaceAndListIndexes(
+            price as Price: u64 => 3 => get_price,
+          ),
+          delist as Delist => DelistIndexes(),
+          purchase as Purchase => PurchaseIndexes(),
+        },
+        transfer_policy as TransferPolicy => TransferPolicyFunctions: {
+          confirm_request as ConfirmRequest => ConfirmRequestIndexes(),
+        },
+      }
+  },
+}
@@ -1,5 +1,6 @@
 pub mod cetus;
 pub mod coin_transfer;
+pub mod kiosk;
 pub mod momentum;
 pub mod sui_native_staking;
 pub mod suilend;
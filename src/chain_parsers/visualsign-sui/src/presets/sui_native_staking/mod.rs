@@ -3,7 +3,9 @@ mod config;
 use config::{Config, NATIVE_STAKING_CONFIG, SuiSystemFunctions};
 
 use crate::core::{CommandVisualizer, SuiIntegrationConfig, VisualizerContext, VisualizerKind};
-use crate::utils::{decode_number, get_index, parse_numeric_argument, truncate_address};
+use crate::utils::{
+    format_mist_as_sui, get_index, get_object_value, mist_amount, sui_amount, truncate_address,
+};
 
 use sui_json_rpc_types::{SuiArgument, SuiCallArg, SuiCommand, SuiProgrammableMoveCall};
 use sui_types::base_types::SuiAddress;
@@ -12,7 +14,7 @@ use visualsign::{
     AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
     SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
     errors::VisualSignError,
-    field_builders::{create_address_field, create_amount_field, create_text_field},
+    field_builders::{create_address_field, create_amount_field_from, create_text_field},
 };
 
 pub struct SuiNativeStakingVisualizer;
@@ -49,102 +51,113 @@ impl SuiNativeStakingVisualizer {
         context: &VisualizerContext,
         pwc: &SuiProgrammableMoveCall,
     ) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
-        let amount = get_stake_amount(context.commands(), context.inputs(), &pwc.arguments)
-            .unwrap_or_default();
+        let amount = pwc
+            .arguments
+            .get(1)
+            .and_then(|stake| context.resolve_amount(*stake));
         let receiver = get_stake_receiver(context.inputs(), &pwc.arguments).unwrap_or_default();
 
-        {
-            let (title_text, amount_field) = match amount {
-                Some(amount) => (
-                    format!("Stake: {amount} MIST"),
-                    create_amount_field("Amount", &amount.to_string(), "MIST")?,
-                ),
-                None => (
-                    "Stake Command".to_string(),
-                    create_text_field("Amount", "N/A MIST")?,
-                ),
-            };
-
-            let subtitle_text = format!(
-                "From {} to validator {}",
-                truncate_address(&context.sender().to_string()),
-                truncate_address(&receiver.to_string())
-            );
-
-            let condensed = SignablePayloadFieldListLayout {
-                fields: vec![amount_field.clone()],
-            };
-
-            let expanded = SignablePayloadFieldListLayout {
-                fields: vec![
-                    create_address_field(
-                        "From",
-                        &context.sender().to_string(),
-                        None,
-                        None,
-                        None,
-                        None,
-                    )?,
-                    create_address_field(
-                        "Validator",
-                        &receiver.to_string(),
-                        None,
-                        None,
-                        None,
-                        None,
-                    )?,
-                    amount_field,
+        let validator_field =
+            create_address_field("Validator", &receiver.to_string(), None, None, None, None)?;
+
+        let (title_text, amount_fields) = match amount {
+            Some(amount) => (
+                format!("Stake: {} SUI", format_mist_as_sui(amount)),
+                vec![
+                    create_amount_field_from("Amount", &sui_amount(amount))?,
+                    create_amount_field_from("Amount (MIST)", &mist_amount(amount))?,
                 ],
-            };
-
-            Ok(vec![AnnotatedPayloadField {
-                static_annotation: None,
-                dynamic_annotation: None,
-                signable_payload_field: SignablePayloadField::PreviewLayout {
-                    common: SignablePayloadFieldCommon {
-                        fallback_text: title_text.clone(),
-                        label: "Stake Command".to_string(),
-                    },
-                    preview_layout: SignablePayloadFieldPreviewLayout {
-                        title: Some(SignablePayloadFieldTextV2 { text: title_text }),
-                        subtitle: Some(SignablePayloadFieldTextV2 {
-                            text: subtitle_text,
-                        }),
-                        condensed: Some(condensed),
-                        expanded: Some(expanded),
-                    },
-                },
-            }])
-        }
-    }
+            ),
+            None => (
+                "Stake Command".to_string(),
+                vec![create_text_field("Amount", "N/A SUI")?],
+            ),
+        };
 
-    fn handle_withdraw_stake(
-        context: &VisualizerContext,
-        _pwc: &SuiProgrammableMoveCall,
-    ) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
-        let title_text = "Withdraw Stake".to_string();
-        let subtitle_text = format!("From {}", truncate_address(&context.sender().to_string()));
+        let subtitle_text = format!(
+            "From {} to validator {}",
+            truncate_address(&context.sender().to_string()),
+            truncate_address(&receiver.to_string())
+        );
 
         let condensed = SignablePayloadFieldListLayout {
-            fields: vec![create_address_field(
+            fields: vec![amount_fields[0].clone(), validator_field.clone()],
+        };
+
+        let mut expanded_fields = vec![
+            create_address_field(
                 "From",
                 &context.sender().to_string(),
                 None,
                 None,
                 None,
                 None,
-            )?],
+            )?,
+            validator_field,
+        ];
+        expanded_fields.extend(amount_fields);
+
+        Ok(vec![AnnotatedPayloadField {
+            static_annotation: None,
+            dynamic_annotation: None,
+            signable_payload_field: SignablePayloadField::PreviewLayout {
+                common: SignablePayloadFieldCommon {
+                    fallback_text: title_text.clone(),
+                    label: "Stake Command".to_string(),
+                },
+                preview_layout: SignablePayloadFieldPreviewLayout {
+                    title: Some(SignablePayloadFieldTextV2 { text: title_text }),
+                    subtitle: Some(SignablePayloadFieldTextV2 {
+                        text: subtitle_text,
+                    }),
+                    condensed: Some(condensed),
+                    expanded: Some(SignablePayloadFieldListLayout {
+                        fields: expanded_fields,
+                    }),
+                },
+            },
+        }])
+    }
+
+    fn handle_withdraw_stake(
+        context: &VisualizerContext,
+        pwc: &SuiProgrammableMoveCall,
+    ) -> Result<Vec<AnnotatedPayloadField>, VisualSignError> {
+        // `request_withdraw_stake(state, staked_sui)`: the `StakedSui` object is the second argument.
+        // It is only resolvable when passed directly as an input, not as the result of another command.
+        let staked_object = get_object_value(&pwc.arguments, context.inputs(), 1)
+            .map(|id| id.to_string())
+            .ok();
+
+        let title_text = match &staked_object {
+            Some(id) => format!("Withdraw Stake: {}", truncate_address(id)),
+            None => "Withdraw Stake".to_string(),
+        };
+        let subtitle_text = format!("From {}", truncate_address(&context.sender().to_string()));
+
+        let from_field = create_address_field(
+            "From",
+            &context.sender().to_string(),
+            None,
+            None,
+            None,
+            None,
+        )?;
+        let staked_object_field = match &staked_object {
+            Some(id) => create_address_field("Staked Object", id, None, None, None, None)?,
+            None => create_text_field("Staked Object", "Result of a previous command")?,
+        };
+        let withdrawal_note = create_text_field(
+            "Withdrawal",
+            "Principal and accrued rewards of the staked object are returned to the sender",
+        )?;
+
+        let condensed = SignablePayloadFieldListLayout {
+            fields: vec![staked_object_field.clone(), from_field.clone()],
         };
 
         let expanded = SignablePayloadFieldListLayout {
-            fields: vec![create_address_field(
-                "From",
-                &context.sender().to_string(),
-                None,
-                None,
-                None,
-                None,
-            )?],
+            fields: vec![from_field, staked_object_field, withdrawal_note],
         };
 
         Ok(vec![AnnotatedPayloadField {
@@ -188,34 +201,11 @@ fn get_stake_receiver(
     }
 }
 
-fn get_stake_amount(
-    commands: &[SuiCommand],
-    inputs: &[SuiCallArg],
-    args: &[SuiArgument],
-) -> Result<Option<u64>, VisualSignError> {
-    let command = commands
-        .get(get_index(args, Some(1))? as usize)
-        .ok_or(VisualSignError::MissingData("Command not found".into()))?;
-
-    match command {
-        SuiCommand::SplitCoins(_, input_coin_args) if input_coin_args.len() == 1 => {
-            let amount_arg = inputs
-                .get(parse_numeric_argument(input_coin_args[0])? as usize)
-                .ok_or(VisualSignError::MissingData(
-                    "Amount argument not found".into(),
-                ))?;
-
-            Ok(Some(decode_number::<u64>(amount_arg)?))
-        }
-        _ => Ok(None),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::utils::payload_from_b64;
 
-    use visualsign::test_utils::assert_has_field;
+    use visualsign::test_utils::{assert_has_field, assert_has_field_with_value};
 
     #[test]
     fn test_stake_commands() {
@@ -224,6 +214,13 @@ mod tests {
 
         let payload = payload_from_b64(test_data);
         assert_has_field(&payload, "Stake Command");
+        assert_has_field_with_value(&payload, "Amount", "3805");
+        assert_has_field_with_value(&payload, "Amount (MIST)", "3805000000000");
+        assert_has_field_with_value(
+            &payload,
+            "Validator",
+            "0x00ae78d3e5ba5d6b8de32455474f52811b95617cbad39ebf4f9e2daf67187407",
+        );
     }
 
     #[test]
@@ -233,5 +230,10 @@ mod tests {
 
         let payload = payload_from_b64(test_data);
         assert_has_field(&payload, "Withdraw Command");
+        assert_has_field_with_value(
+            &payload,
+            "Staked Object",
+            "0x3b6e65145971769b627d9e2e938f1aade89f2b2cff42b2bafdc1a01dcf40f888",
+        );
     }
 }
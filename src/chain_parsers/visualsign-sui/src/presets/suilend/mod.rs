@@ -11,12 +11,9 @@ use config::{
 };
 
 use crate::core::{CommandVisualizer, SuiIntegrationConfig, VisualizerContext, VisualizerKind};
-use crate::utils::{
-    SuiCoin, SuiPackage, decode_number, get_index, get_nested_result_value, get_object_value,
-    get_tx_type_arg, truncate_address,
-};
+use crate::utils::{SuiCoin, SuiPackage, get_object_value, get_tx_type_arg, truncate_address};
 
-use sui_json_rpc_types::{SuiArgument, SuiCallArg, SuiCommand, SuiProgrammableMoveCall};
+use sui_json_rpc_types::{SuiCommand, SuiProgrammableMoveCall};
 
 use visualsign::{
     AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
@@ -81,29 +78,6 @@ impl CommandVisualizer for SuilendVisualizer {
     }
 }
 
-fn get_repay_amount(
-    commands: &[SuiCommand],
-    inputs: &[SuiCallArg],
-    transfer_args: &[SuiArgument],
-) -> Result<Option<u64>, VisualSignError> {
-    let command_index_with_input_amount = get_nested_result_value(transfer_args, 4, 0);
-    let command_with_input_amount = commands
-        .get(command_index_with_input_amount? as usize)
-        .ok_or(VisualSignError::MissingData("Command not found".into()))?;
-
-    match command_with_input_amount {
-        SuiCommand::SplitCoins(_, args_with_input_index) => {
-            let amount_arg = inputs
-                .get(get_index(args_with_input_index, Some(0))? as usize)
-                .ok_or(VisualSignError::MissingData(
-                    "Amount argument not found".into(),
-                ))?;
-            Ok(Some(decode_number::<u64>(amount_arg)?))
-        }
-        _ => Ok(None),
-    }
-}
-
 impl SuilendVisualizer {
     fn handle_borrow_request(
         context: &VisualizerContext,
@@ -149,14 +123,6 @@ impl SuilendVisualizer {
                     None,
                     None,
                 )?,
-                create_address_field(
-                    "Lending Market",
-                    &get_object_value(&pwc.arguments, context.inputs(), 0)?.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )?,
                 create_text_field("Pool Address", &package.to_string())?,
                 create_text_field("Borrowed Coin", &coin.to_string())?,
                 create_text_field("Borrowed Reserve Index", &reserve_index.to_string())?,
@@ -234,14 +200,6 @@ impl SuilendVisualizer {
                     None,
                     None,
                 )?,
-                create_address_field(
-                    "Lending Market",
-                    &get_object_value(&pwc.arguments, context.inputs(), 0)?.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )?,
                 create_text_field("Pool Address", &package.to_string())?,
                 create_text_field("Reward Coin", &reward_coin.to_string())?,
                 create_text_field("Claim Rewards Reserve Index", &reserve_id.to_string())?,
@@ -322,14 +280,6 @@ impl SuilendVisualizer {
                     None,
                     None,
                 )?,
-                create_address_field(
-                    "Lending Market",
-                    &get_object_value(&pwc.arguments, context.inputs(), 0)?.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )?,
                 create_text_field("Pool Address", &package.to_string())?,
                 create_text_field("Claim and Deposit Coin", &coin.to_string())?,
                 create_text_field("Reward Side", reward_side)?,
@@ -395,14 +345,6 @@ impl SuilendVisualizer {
                     None,
                     None,
                 )?,
-                create_address_field(
-                    "Lending Market",
-                    &get_object_value(&pwc.arguments, context.inputs(), 0)?.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )?,
                 create_text_field("Pool Address", &package.to_string())?,
             ],
         };
@@ -472,14 +414,6 @@ impl SuilendVisualizer {
                     None,
                     None,
                 )?,
-                create_address_field(
-                    "Lending Market",
-                    &get_object_value(&pwc.arguments, context.inputs(), 0)?.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )?,
                 create_text_field("Pool Address", &package.to_string())?,
                 create_text_field("CToken (underlying)", &coin.to_string())?,
                 create_text_field("Deposit cTokens Reserve Index", &reserve_index.to_string())?,
@@ -553,14 +487,6 @@ impl SuilendVisualizer {
                     None,
                     None,
                 )?,
-                create_address_field(
-                    "Lending Market",
-                    &get_object_value(&pwc.arguments, context.inputs(), 0)?.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )?,
                 create_text_field("Pool Address", &package.to_string())?,
                 create_text_field("Deposit Liquidity and Mint cTokens Coin", &coin.to_string())?,
                 create_text_field(
@@ -628,14 +554,6 @@ impl SuilendVisualizer {
                     None,
                     None,
                 )?,
-                create_address_field(
-                    "Lending Market",
-                    &get_object_value(&pwc.arguments, context.inputs(), 0)?.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                )?,
                 create_text_field("Pool Address", &package.to_string())?,
                 create_text_field("Fulfill Liquidity Coin", &coin.to_string())?,
                 create_text_field(
@@ -877,8 +795,11 @@ impl SuilendVisualizer {
         let package: SuiPackage = get_tx_type_arg(&pwc.type_arguments, 0).unwrap_or_default();
         let reserve_index =
             RefreshReservePriceIndexes::get_reserve_array_index(context.inputs(), &pwc.arguments)?;
-        let amount = get_repay_amount(context.commands(), context.inputs(), &pwc.arguments)
-            .unwrap_or_default();
+        // The repaid coin (argument 4) is usually split off just before the call
+        let amount = pwc
+            .arguments
+            .get(4)
+            .and_then(|coin| context.resolve_amount(*coin));
 
         let (title_text, amount_str, amount_field) = match amount {
             Some(amount) => (
@@ -1,3 +1,8 @@
+use visualsign::amount::Amount;
+
+/// Decimal places between MIST and SUI (`MIST_PER_SUI` is `10^9`).
+const SUI_DECIMALS: u8 = 9;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SuiCoin {
     pub address: String,
@@ -91,3 +96,33 @@ impl Default for CoinObject {
         CoinObject::UnknownObject(String::default())
     }
 }
+
+/// Formats an amount in MIST as a decimal SUI string without going through floating point
+/// (e.g. `1_500_000_000` → `"1.5"`).
+pub fn format_mist_as_sui(mist: u64) -> String {
+    sui_amount(mist).to_decimal_string()
+}
+
+/// A MIST value as an [`Amount`] in SUI.
+pub fn sui_amount(mist: u64) -> Amount {
+    Amount::new(u128::from(mist), SUI_DECIMALS, "SUI")
+}
+
+/// A MIST value as an [`Amount`] in MIST, for showing the exact on-chain figure.
+pub fn mist_amount(mist: u64) -> Amount {
+    Amount::new(u128::from(mist), 0, "MIST")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_mist_as_sui() {
+        assert_eq!(format_mist_as_sui(0), "0");
+        assert_eq!(format_mist_as_sui(1), "0.000000001");
+        assert_eq!(format_mist_as_sui(1_500_000_000), "1.5");
+        assert_eq!(format_mist_as_sui(3_805_000_000_000), "3805");
+        assert_eq!(format_mist_as_sui(u64::MAX), "18446744073.709551615");
+    }
+}
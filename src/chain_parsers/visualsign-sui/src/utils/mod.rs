@@ -7,12 +7,10 @@ mod test_helpers;
 mod tx_args;
 
 pub use address::truncate_address;
-pub use coin::{CoinObject, SuiCoin};
+pub use coin::{CoinObject, SuiCoin, format_mist_as_sui, mist_amount, sui_amount};
 pub use numeric::decode_number;
 pub use package::SuiPackage;
-pub use tx_args::{
-    get_index, get_nested_result_value, get_object_value, get_tx_type_arg, parse_numeric_argument,
-};
+pub use tx_args::{get_index, get_object_value, get_tx_type_arg, parse_numeric_argument};
 
 #[cfg(test)]
 pub use test_helpers::*;
@@ -50,7 +50,7 @@
 //!
 //! As shown in the `cetus` and other presets, create a JSON file that matches this format and run the `run_aggregated_fixture` test.
 
-use crate::core::{CommandVisualizer, SuiModuleResolver, VisualizerContext};
+use crate::core::{CommandVisualizer, PtbDataflow, SuiModuleResolver, VisualizerContext};
 use crate::{SuiTransactionWrapper, transaction_string_to_visual_sign};
 
 use std::collections::HashMap;
@@ -70,8 +70,7 @@ pub fn payload_from_b64(data: &str) -> SignablePayload {
         data,
         VisualSignOptions {
             decode_transfers: true,
-            transaction_name: None,
-            metadata: None,
+            ..Default::default()
         },
     )
     .expect("Failed to visualize tx commands")
@@ -83,8 +82,7 @@ pub fn payload_from_b64_with_context(data: &str, context: &str) -> SignablePaylo
         data,
         VisualSignOptions {
             decode_transfers: true,
-            transaction_name: None,
-            metadata: None,
+            ..Default::default()
         },
     ) {
         Ok(payload) => payload,
@@ -163,11 +161,13 @@ pub fn run_aggregated_fixture(json_str: &str, protocol: Box<dyn CommandVisualize
                     "Command index is out of bounds. {test_info_context}"
                 );
 
+                let dataflow = PtbDataflow::analyze(tx_commands, tx_inputs);
                 let context = VisualizerContext::new(
                     block_data.sender(),
                     op.command_index,
                     tx_commands,
                     tx_inputs,
+                    &dataflow,
                 );
 
                 assert!(
@@ -17,29 +17,6 @@ pub fn get_index(sui_args: &[SuiArgument], index: Option<usize>) -> Result<u16,
     parse_numeric_argument(*arg)
 }
 
-/// Gets a specific value from `NestedResult` by argument index and nested index
-pub fn get_nested_result_value(
-    sui_args: &[SuiArgument],
-    arg_index: usize,
-    nested_index: usize,
-) -> Result<u16, VisualSignError> {
-    let arg = sui_args.get(arg_index).ok_or(VisualSignError::MissingData(
-        "Index out of bounds for nested result".into(),
-    ))?;
-
-    match arg {
-        SuiArgument::NestedResult(first, second) => [*first, *second]
-            .get(nested_index)
-            .copied()
-            .ok_or(VisualSignError::MissingData(
-                "Nested index out of bounds".into(),
-            )),
-        _ => Err(VisualSignError::DecodeError(
-            "Expected `NestedResult`".into(),
-        )),
-    }
-}
-
 /// Parses a numeric argument from a Sui argument (`Input` or `Result`)
 pub fn parse_numeric_argument(arg: SuiArgument) -> Result<u16, VisualSignError> {
     match arg {
@@ -1,6 +1,21 @@
 use visualsign::{
     SignablePayload, SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2,
+    amount::Amount,
+    capabilities::ParserCapabilities,
     encodings::SupportedEncodings,
+    errors::{ParserError, ParserErrorKind},
+    expiration::{Expiration, create_expiration_field},
+    fee::{MAX_TOTAL_COST_LABEL, create_max_total_cost_field},
+    field_builders::{create_text_field, create_timestamp_field_from_millis},
+    fixed_point::format_fixed_point,
+    layout::{PreviewTemplate, package_preview_layout},
+    lossless,
+    memo::{MEMO_WARNING_LABEL, missing_memo_warning},
+    parser_info::ParserInfo,
+    registry::Chain,
+    replay::{ReplayProtection, create_replay_protection_field},
+    sender::{create_sender_field, resolve_sender},
+    telemetry::record_command_count,
     vsptrait::{
         Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
         VisualSignError, VisualSignOptions,
@@ -13,17 +28,45 @@ use base64::{Engine as _, engine::general_purpose::STANDARD as b64};
 use protobuf::Message;
 use sha2::{Digest, Sha256};
 
+const TRX_DECIMALS: u8 = 6;
+
 #[derive(Debug, Eq, PartialEq, thiserror::Error)]
 pub enum TronParserError {
     #[error("Failed to decode transaction: {0}")]
     FailedToDecodeTransaction(String),
 }
 
+impl From<TronParserError> for TransactionParseError {
+    fn from(error: TronParserError) -> Self {
+        let reason = match &error {
+            TronParserError::FailedToDecodeTransaction(_) => "FailedToDecodeTransaction",
+        };
+        TransactionParseError::Parser(ParserError::new(
+            ParserErrorKind::Encoding,
+            "Tron",
+            reason,
+            error.to_string(),
+        ))
+    }
+}
+
 fn decode_transaction(
     raw_transaction: &str,
     encodings: SupportedEncodings,
 ) -> Result<transaction::Raw, TronParserError> {
-    let bytes = match encodings {
+    let bytes = decode_bytes(raw_transaction, encodings)?;
+
+    // Parse and return the Tron transaction
+    transaction::Raw::parse_from_bytes(&bytes).map_err(|e| {
+        TronParserError::FailedToDecodeTransaction(format!("Failed to parse Tron transaction: {e}"))
+    })
+}
+
+fn decode_bytes(
+    raw_transaction: &str,
+    encodings: SupportedEncodings,
+) -> Result<Vec<u8>, TronParserError> {
+    Ok(match encodings {
         SupportedEncodings::Hex => {
             let clean_hex = raw_transaction
                 .strip_prefix("0x")
@@ -35,14 +78,17 @@ fn decode_transaction(
         SupportedEncodings::Base64 => b64.decode(raw_transaction).map_err(|e| {
             TronParserError::FailedToDecodeTransaction(format!("Failed to decode base64: {e}"))
         })?,
-    };
-
-    // Parse and return the Tron transaction
-    transaction::Raw::parse_from_bytes(&bytes).map_err(|e| {
-        TronParserError::FailedToDecodeTransaction(format!("Failed to parse Tron transaction: {e}"))
     })
 }
 
+fn detect_encoding(data: &str) -> SupportedEncodings {
+    if data.starts_with("0x") {
+        SupportedEncodings::Hex
+    } else {
+        visualsign::encodings::SupportedEncodings::detect(data)
+    }
+}
+
 // This module provides a parser and wrapper for Tron blockchain transactions,
 // enabling their decoding and integration with the VisualSign framework.
 /// Wrapper for Tron transactions
@@ -53,16 +99,25 @@ pub struct TronTransactionWrapper {
 
 impl Transaction for TronTransactionWrapper {
     fn from_string(data: &str) -> Result<Self, TransactionParseError> {
-        let format = if data.starts_with("0x") {
-            SupportedEncodings::Hex
-        } else {
-            visualsign::encodings::SupportedEncodings::detect(data)
-        };
-        let transaction = decode_transaction(data, format)
-            .map_err(|e| TransactionParseError::DecodeError(e.to_string()))?;
+        let transaction = decode_transaction(data, detect_encoding(data))?;
         Ok(Self { transaction })
     }
 
+    fn verify_lossless(&self, data: &str) -> Result<(), TransactionParseError> {
+        // Protobuf readers accept fields in any order, repeated and unknown ones; only the
+        // canonical encoding of the decoded fields is accepted here
+        let reencoded = self.transaction.write_to_bytes().map_err(|e| {
+            TronParserError::FailedToDecodeTransaction(format!(
+                "Failed to re-encode Tron transaction: {e}"
+            ))
+        })?;
+        Ok(lossless::check_reencoding(
+            "Tron",
+            &decode_bytes(data, detect_encoding(data))?,
+            &reencoded,
+        )?)
+    }
+
     fn transaction_type(&self) -> String {
         "Tron".to_string()
     }
@@ -89,6 +144,18 @@ impl VisualSignConverter<TronTransactionWrapper> for TronVisualSignConverter {
     ) -> Result<SignablePayload, VisualSignError> {
         convert_to_visual_sign_payload(transaction_wrapper.inner().clone(), options)
     }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        // Other contract types are shown by their type URL
+        ParserCapabilities::new(&["hex", "base64"], &["TransferContract"])
+            .with_feature("trx_transfer", true)
+            .with_feature("trigger_smart_contract", false)
+            .with_feature("lossless_verification", true)
+    }
 }
 
 fn convert_to_visual_sign_payload(
@@ -105,32 +172,15 @@ fn convert_to_visual_sign_payload(
         text_v2: SignablePayloadFieldTextV2 { text: chain_name },
     }];
 
-    // Add timestamp field
-    let timestamp_formatted = format_timestamp(raw_data.timestamp);
-    fields.push(SignablePayloadField::TextV2 {
-        common: SignablePayloadFieldCommon {
-            fallback_text: format!("{} ({} ms)", timestamp_formatted, raw_data.timestamp),
-            label: "Timestamp".to_string(),
-        },
-        text_v2: SignablePayloadFieldTextV2 {
-            text: format!("{} ({} ms)", timestamp_formatted, raw_data.timestamp),
-        },
-    });
-
-    // Add expiration field
-    let expiration_formatted = format_timestamp(raw_data.expiration);
-    fields.push(SignablePayloadField::TextV2 {
-        common: SignablePayloadFieldCommon {
-            fallback_text: format!("{} ({} ms)", expiration_formatted, raw_data.expiration),
-            label: "Expiration".to_string(),
-        },
-        text_v2: SignablePayloadFieldTextV2 {
-            text: format!("{} ({} ms)", expiration_formatted, raw_data.expiration),
-        },
-    });
+    // Add timestamp and expiration fields (Tron uses Unix milliseconds)
+    fields.push(timestamp_field("Timestamp", raw_data.timestamp)?);
+    fields.push(
+        create_expiration_field(&Expiration::Timestamp(raw_data.expiration))?
+            .signable_payload_field,
+    );
 
     // Add fee limit field
-    let fee_limit_trx = raw_data.fee_limit as f64 / 1_000_000.0;
+    let fee_limit_trx = format_sun_as_trx(raw_data.fee_limit);
     fields.push(SignablePayloadField::TextV2 {
         common: SignablePayloadFieldCommon {
             fallback_text: format!("{} SUN ({} TRX)", raw_data.fee_limit, fee_limit_trx),
@@ -162,8 +212,29 @@ fn convert_to_visual_sign_payload(
             text: hex::encode(&raw_data.ref_block_hash),
         },
     });
+    fields.push(
+        create_replay_protection_field(&ReplayProtection::RefBlock {
+            bytes: hex::encode(&raw_data.ref_block_bytes),
+            hash: hex::encode(&raw_data.ref_block_hash),
+        })?
+        .signable_payload_field,
+    );
+
+    // Add memo field when the transaction carries one
+    let memo = memo_text(&raw_data.data);
+    if let Some(memo) = &memo {
+        fields.push(SignablePayloadField::TextV2 {
+            common: SignablePayloadFieldCommon {
+                fallback_text: memo.clone(),
+                label: "Memo".to_string(),
+            },
+            text_v2: SignablePayloadFieldTextV2 { text: memo.clone() },
+        });
+    }
 
     // Parse contracts
+    record_command_count(raw_data.contract.len());
+    let mut trx_sent: u128 = 0;
     for contract in raw_data.contract.iter() {
         if let Some(parameter) = contract.parameter.as_ref() {
             // Decode specific contract types
@@ -181,15 +252,15 @@ fn convert_to_visual_sign_payload(
                             },
                         });
 
-                        // Add from address field
+                        // Add from address field, checked against the host's sender if any
                         let from_address = address_to_base58(&transfer.owner_address);
-                        fields.push(SignablePayloadField::TextV2 {
-                            common: SignablePayloadFieldCommon {
-                                fallback_text: from_address.clone(),
-                                label: "From".to_string(),
-                            },
-                            text_v2: SignablePayloadFieldTextV2 { text: from_address },
-                        });
+                        if let Some(sender) = resolve_sender(
+                            Some(&from_address),
+                            options.sender.as_deref(),
+                            |derived, provided| derived == provided,
+                        )? {
+                            fields.push(create_sender_field(&sender));
+                        }
 
                         // Add to address field
                         let to_address = address_to_base58(&transfer.to_address);
@@ -198,11 +269,25 @@ fn convert_to_visual_sign_payload(
                                 fallback_text: to_address.clone(),
                                 label: "To".to_string(),
                             },
-                            text_v2: SignablePayloadFieldTextV2 { text: to_address },
+                            text_v2: SignablePayloadFieldTextV2 {
+                                text: to_address.clone(),
+                            },
                         });
 
+                        // Warn when an exchange-style destination is missing its memo
+                        if let Some(requirements) = &options.memo_requirements {
+                            fields.extend(missing_memo_warning(
+                                requirements.as_ref(),
+                                &Chain::Tron,
+                                &to_address,
+                                memo.as_deref(),
+                                "memo",
+                            ));
+                        }
+
                         // Add amount field
-                        let amount_trx = transfer.amount as f64 / 1_000_000.0;
+                        trx_sent = trx_sent.saturating_add(sun_to_u128(transfer.amount));
+                        let amount_trx = format_sun_as_trx(transfer.amount);
                         fields.push(SignablePayloadField::TextV2 {
                             common: SignablePayloadFieldCommon {
                                 fallback_text: format!(
@@ -233,9 +318,22 @@ fn convert_to_visual_sign_payload(
         }
     }
 
+    // The fee limit caps what the transaction can burn; TRX sent by transfers comes on top
+    let max_total_cost = sun_to_u128(raw_data.fee_limit).saturating_add(trx_sent);
+    fields.push(
+        create_max_total_cost_field(
+            &Amount::new(max_total_cost, TRX_DECIMALS, "TRX"),
+            "fee limit + TRX sent",
+        )
+        .signable_payload_field,
+    );
+
     let title = options
         .transaction_name
         .unwrap_or_else(|| "Tron Transaction".to_string());
+    if options.output.preview_layout {
+        fields = vec![package_preview_layout(&title, fields, &preview_template())];
+    }
 
     Ok(SignablePayload::new(
         0,
@@ -246,6 +344,19 @@ fn convert_to_visual_sign_payload(
     ))
 }
 
+// Non-transfer contracts have no amount, so their title falls back to the payload title
+fn preview_template() -> PreviewTemplate {
+    PreviewTemplate::new("Send {Amount}")
+        .with_subtitle("To {To} on {Network}")
+        .with_condensed("Contract Type")
+        .with_condensed("From")
+        .with_condensed("To")
+        .with_condensed("Amount")
+        .with_condensed(MAX_TOTAL_COST_LABEL)
+        .with_condensed("Memo")
+        .with_condensed(MEMO_WARNING_LABEL)
+}
+
 impl VisualSignConverterFromString<TronTransactionWrapper> for TronVisualSignConverter {}
 
 // Public API functions
@@ -283,10 +394,37 @@ fn address_to_base58(address_bytes: &[u8]) -> String {
     base58::ToBase58::to_base58(&with_checksum[..])
 }
 
-// Helper function to format Unix timestamp (milliseconds) to human-readable format
-fn format_timestamp(timestamp_ms: i64) -> String {
-    use chrono::{TimeZone, Utc};
+// Helper function to format SUN (1 TRX = 1_000_000 SUN) as TRX without floating point
+fn format_sun_as_trx(sun: i64) -> String {
+    let trx = format_fixed_point(u128::from(sun.unsigned_abs()), TRX_DECIMALS);
+    if sun < 0 { format!("-{trx}") } else { trx }
+}
 
-    let datetime = Utc.timestamp_millis_opt(timestamp_ms).unwrap();
-    datetime.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+// Helper function to read a SUN amount for summing, treating invalid negative values as zero
+fn sun_to_u128(sun: i64) -> u128 {
+    u128::try_from(sun).unwrap_or(0)
+}
+
+// Helper function to render the raw_data memo as text, or hex when it is not printable ASCII
+fn memo_text(data: &[u8]) -> Option<String> {
+    if data.is_empty() {
+        return None;
+    }
+    if data.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        Some(String::from_utf8_lossy(data).into_owned())
+    } else {
+        Some(format!("0x{}", hex::encode(data)))
+    }
+}
+
+// Timestamps are set by the sender, so keep values outside the representable range as raw text
+fn timestamp_field(
+    label: &str,
+    timestamp_ms: i64,
+) -> Result<SignablePayloadField, VisualSignError> {
+    let field = match create_timestamp_field_from_millis(label, timestamp_ms) {
+        Ok(field) => field,
+        Err(_) => create_text_field(label, &format!("{timestamp_ms} ms"))?,
+    };
+    Ok(field.signable_payload_field)
 }
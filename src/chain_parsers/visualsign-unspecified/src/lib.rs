@@ -1,5 +1,7 @@
 use visualsign::{
     SignablePayload, SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2,
+    capabilities::ParserCapabilities,
+    parser_info::ParserInfo,
     vsptrait::{
         Transaction, TransactionParseError, VisualSignConverter, VisualSignConverterFromString,
         VisualSignError, VisualSignOptions,
@@ -74,6 +76,15 @@ impl VisualSignConverter<UnspecifiedTransactionWrapper> for UnspecifiedVisualSig
             "fill in parsed signable payload".to_string(), // This is what the test expects
         ))
     }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        Some(visualsign::parser_info!())
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        // Any input is shown as is
+        ParserCapabilities::new(&["text"], &[])
+    }
 }
 
 impl VisualSignConverterFromString<UnspecifiedTransactionWrapper>
@@ -27,6 +27,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // BORSH - Used for QOS sha256 checks
         .type_attribute(".parser.ParsedTransactionPayload", BORSH_DERIVE)
         .enum_attribute(".parser.ParsedTransactionPayload", BORSH_ENUM_DISC_ATTR)
+        .type_attribute(".parser.ParsedBundlePayload", BORSH_DERIVE)
+        .enum_attribute(".parser.ParsedBundlePayload", BORSH_ENUM_DISC_ATTR)
         .type_attribute(".parser.Metadata", BORSH_DERIVE)
         .enum_attribute(".parser.Metadata", BORSH_ENUM_DISC_ATTR)
         .client_mod_attribute(".", TONIC_FEATURE_GATE)
@@ -41,6 +43,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "../proto/grpc/health/v1/health.proto",
                 "../proto/vendor/google/rpc/status.proto",
                 "../proto/vendor/google/rpc/code.proto",
+                "../proto/vendor/google/rpc/error_details.proto",
             ],
             &[PROTO_INCLUDE_PATH],
         )?;
@@ -9,8 +9,12 @@
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QosParserRequest {
-    #[prost(oneof = "qos_parser_request::Input", tags = "1, 2")]
+    #[prost(oneof = "qos_parser_request::Input", tags = "1, 2, 3, 4, 5, 7")]
     pub input: ::core::option::Option<qos_parser_request::Input>,
+    /// Caller-supplied id the app logs the request under and echoes in its response, so logs on
+    /// both sides of the enclave boundary can be correlated
+    #[prost(string, tag = "6")]
+    pub request_id: ::prost::alloc::string::String,
 }
 /// Nested message and enum types in `QOSParserRequest`.
 pub mod qos_parser_request {
@@ -27,6 +31,14 @@ pub mod qos_parser_request {
         ParseRequest(super::ParseRequest),
         #[prost(message, tag = "2")]
         HealthRequest(super::super::health::AppHealthRequest),
+        #[prost(message, tag = "3")]
+        ParserInfoRequest(super::ParserInfoRequest),
+        #[prost(message, tag = "4")]
+        ListChainsRequest(super::ListChainsRequest),
+        #[prost(message, tag = "5")]
+        GetCapabilitiesRequest(super::GetCapabilitiesRequest),
+        #[prost(message, tag = "7")]
+        ParseBundleRequest(super::ParseBundleRequest),
     }
 }
 #[cfg_attr(
@@ -37,8 +49,11 @@ pub mod qos_parser_request {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QosParserResponse {
-    #[prost(oneof = "qos_parser_response::Output", tags = "1, 3, 4")]
+    #[prost(oneof = "qos_parser_response::Output", tags = "1, 3, 4, 5, 6, 7, 9")]
     pub output: ::core::option::Option<qos_parser_response::Output>,
+    /// The request_id of the request this answers
+    #[prost(string, tag = "8")]
+    pub request_id: ::prost::alloc::string::String,
 }
 /// Nested message and enum types in `QOSParserResponse`.
 pub mod qos_parser_response {
@@ -57,6 +72,14 @@ pub mod qos_parser_response {
         HealthResponse(super::super::health::AppHealthResponse),
         #[prost(message, tag = "4")]
         Status(super::super::google::rpc::Status),
+        #[prost(message, tag = "5")]
+        ParserInfoResponse(super::ParserInfoResponse),
+        #[prost(message, tag = "6")]
+        ListChainsResponse(super::ListChainsResponse),
+        #[prost(message, tag = "7")]
+        GetCapabilitiesResponse(super::GetCapabilitiesResponse),
+        #[prost(message, tag = "9")]
+        ParseBundleResponse(super::ParseBundleResponse),
     }
 }
 #[cfg_attr(
@@ -73,6 +96,17 @@ pub struct ParseRequest {
     pub chain: i32,
     #[prost(message, optional, tag = "3")]
     pub chain_metadata: ::core::option::Option<ChainMetadata>,
+    /// JSON-encoded `VisualSignOptions` overrides, e.g. the host's charset policy and render
+    /// budget; empty keeps the parser defaults
+    #[prost(string, tag = "4")]
+    pub options: ::prost::alloc::string::String,
+    /// Registry name of the chain when `chain` is CHAIN_CUSTOM
+    #[prost(string, tag = "5")]
+    pub custom_chain: ::prost::alloc::string::String,
+    /// Compression the caller can read the signable payload in, one of
+    /// GetCapabilitiesResponse.payload_compressions; NONE returns the plain payload
+    #[prost(enumeration = "PayloadCompression", tag = "6")]
+    pub accept_compression: i32,
 }
 #[cfg_attr(
     feature = "serde_derive",
@@ -112,6 +146,220 @@ pub mod chain_metadata {
 pub struct ParseResponse {
     #[prost(message, optional, tag = "1")]
     pub parsed_transaction: ::core::option::Option<ParsedTransaction>,
+    /// JSON map from field path (e.g. "Fields\[3\]") to the bytes or instruction of the unsigned
+    /// payload the field is decoded from; set when the options ask for `IncludeProvenance`. It is
+    /// not covered by the signature.
+    #[prost(string, tag = "2")]
+    pub provenance: ::prost::alloc::string::String,
+    /// Share of the unsigned payload decoded into structured fields rather than shown as raw
+    /// data, for chains that measure it. Policy engines can refuse low-coverage (effectively
+    /// blind) transactions. It is not covered by the signature; set `IncludeCoverage` in the
+    /// options to also embed it in the signed payload.
+    #[prost(message, optional, tag = "3")]
+    pub coverage: ::core::option::Option<Coverage>,
+    /// The signable payload compressed with `compression`, set instead of
+    /// `parsed_transaction.payload.signable_payload` when the request accepted a compression. The
+    /// signature covers the uncompressed payload.
+    #[prost(bytes = "vec", tag = "4")]
+    pub compressed_signable_payload: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration = "PayloadCompression", tag = "5")]
+    pub compression: i32,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Coverage {
+    #[prost(uint64, tag = "1")]
+    pub decoded_bytes: u64,
+    #[prost(uint64, tag = "2")]
+    pub total_bytes: u64,
+    /// decoded_bytes as a percentage of total_bytes, rounded down
+    #[prost(uint32, tag = "3")]
+    pub percent: u32,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParseBundleRequest {
+    /// Shown above the payloads, e.g. "Swap USDC for ETH"
+    #[prost(string, tag = "1")]
+    pub title: ::prost::alloc::string::String,
+    /// In signing order, at most 16; their `accept_compression` is ignored
+    #[prost(message, repeated, tag = "2")]
+    pub transactions: ::prost::alloc::vec::Vec<ParseRequest>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParseBundleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub parsed_bundle: ::core::option::Option<ParsedBundle>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAttestationRequest {}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAttestationResponse {
+    /// NSM attestation document of the enclave (CBOR-encoded COSE Sign1)
+    #[prost(bytes = "vec", tag = "1")]
+    pub attestation_document: ::prost::alloc::vec::Vec<u8>,
+    /// Hex SHA-256 of the borsh-encoded QOS manifest the enclave booted with; empty without one
+    #[prost(string, tag = "2")]
+    pub manifest_digest: ::prost::alloc::string::String,
+    /// Versions of the parser crates running in the enclave
+    #[prost(message, repeated, tag = "3")]
+    pub parser_versions: ::prost::alloc::vec::Vec<CrateVersion>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParserInfoRequest {}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParserInfoResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub crate_versions: ::prost::alloc::vec::Vec<CrateVersion>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CrateVersion {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub version: ::prost::alloc::string::String,
+    /// Commit the crate was built from; empty when the build did not record one
+    #[prost(string, tag = "3")]
+    pub git_commit: ::prost::alloc::string::String,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListChainsRequest {}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListChainsResponse {
+    /// Sorted by chain name
+    #[prost(message, repeated, tag = "1")]
+    pub chains: ::prost::alloc::vec::Vec<ChainCapabilities>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainCapabilities {
+    /// CHAIN_CUSTOM for chains registered by name only
+    #[prost(enumeration = "Chain", tag = "1")]
+    pub chain: i32,
+    /// Registry name of the chain, which `custom_chain` of a ParseRequest takes for CHAIN_CUSTOM
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// Parser crate serving the chain; unset when the parser does not report one
+    #[prost(message, optional, tag = "3")]
+    pub parser: ::core::option::Option<CrateVersion>,
+    /// Input encodings, e.g. "hex" or "base64"
+    #[prost(string, repeated, tag = "4")]
+    pub encodings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "5")]
+    pub transaction_variants: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Protocols, programs or message types decoded into dedicated fields
+    #[prost(string, repeated, tag = "6")]
+    pub decoders: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCapabilitiesRequest {}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCapabilitiesResponse {
+    /// `version` values of the payloads the parsers produce
+    #[prost(string, repeated, tag = "1")]
+    pub payload_versions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Charset policies payloads can be validated against, e.g. "Ascii"
+    #[prost(string, repeated, tag = "2")]
+    pub charset_policies: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Sorted by chain name
+    #[prost(message, repeated, tag = "3")]
+    pub chains: ::prost::alloc::vec::Vec<ChainFeatures>,
+    /// Compressions ParseRequest.accept_compression can ask for
+    #[prost(enumeration = "PayloadCompression", repeated, tag = "4")]
+    pub payload_compressions: ::prost::alloc::vec::Vec<i32>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainFeatures {
+    /// CHAIN_CUSTOM for chains registered by name only
+    #[prost(enumeration = "Chain", tag = "1")]
+    pub chain: i32,
+    /// Registry name of the chain
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// Feature flags by name, e.g. "eip4844"; false for known features not supported yet
+    #[prost(map = "string, bool", tag = "3")]
+    pub features: ::std::collections::HashMap<::prost::alloc::string::String, bool>,
 }
 #[cfg_attr(
     feature = "serde_derive",
@@ -164,6 +412,36 @@ pub struct ParsedTransactionPayload {
 pub struct ParsedTransaction {
     #[prost(message, optional, tag = "1")]
     pub payload: ::core::option::Option<ParsedTransactionPayload>,
+    /// Signature of the enclave over `payload`, so relying parties can check which attested parser
+    /// produced it without trusting the transport
+    #[prost(message, optional, tag = "2")]
+    pub signature: ::core::option::Option<Signature>,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(borsh::BorshSerialize, borsh::BorshDeserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParsedBundlePayload {
+    /// Canonical JSON of a `SignablePayloadBundle`: the title, the payloads of the transactions in
+    /// request order and a digest over both
+    #[prost(string, tag = "1")]
+    pub signable_bundle: ::prost::alloc::string::String,
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParsedBundle {
+    #[prost(message, optional, tag = "1")]
+    pub payload: ::core::option::Option<ParsedBundlePayload>,
+    /// Signature of the enclave over `payload`, made as for a single parsed transaction
     #[prost(message, optional, tag = "2")]
     pub signature: ::core::option::Option<Signature>,
 }
@@ -177,10 +455,15 @@ pub struct ParsedTransaction {
 pub struct Signature {
     #[prost(enumeration = "SignatureScheme", tag = "1")]
     pub scheme: i32,
+    /// Hex encoded public key of the enclave's ephemeral key, bound to the enclave by the public
+    /// key of its attestation document
     #[prost(string, tag = "2")]
     pub public_key: ::prost::alloc::string::String,
+    /// Hex SHA-256 of the borsh-encoded ParsedTransactionPayload, or ParsedBundlePayload, that was
+    /// signed
     #[prost(string, tag = "3")]
     pub message: ::prost::alloc::string::String,
+    /// Hex encoded P256 signature of `message` by the ephemeral key
     #[prost(string, tag = "4")]
     pub signature: ::prost::alloc::string::String,
 }
@@ -299,6 +582,40 @@ impl Chain {
 #[cfg_attr(feature = "serde_derive", serde(untagged))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum PayloadCompression {
+    None = 0,
+    /// A single zstd frame at level 3 recording the content size, without a checksum or
+    /// dictionary, so a payload always compresses to the same bytes
+    Zstd = 1,
+}
+impl PayloadCompression {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PayloadCompression::None => "PAYLOAD_COMPRESSION_NONE",
+            PayloadCompression::Zstd => "PAYLOAD_COMPRESSION_ZSTD",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PAYLOAD_COMPRESSION_NONE" => Some(Self::None),
+            "PAYLOAD_COMPRESSION_ZSTD" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+#[cfg_attr(
+    feature = "serde_derive",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde_derive", serde(untagged))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum SignatureScheme {
     Unspecified = 0,
     /// Scheme used for Turnkey app proofs
@@ -468,6 +785,114 @@ pub mod parser_service_client {
                 .insert(GrpcMethod::new("parser.ParserService", "Parse"));
             self.inner.unary(req, path, codec).await
         }
+        /// Attestation of the enclave behind this host, with the parser versions it runs, so clients
+        /// can check which parser code signs their payloads
+        pub async fn get_attestation(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetAttestationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAttestationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/parser.ParserService/GetAttestation",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("parser.ParserService", "GetAttestation"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Chain parsers registered in the enclave, with the encodings, transaction variants and
+        /// protocol decoders each supports
+        pub async fn list_chains(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListChainsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListChainsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/parser.ParserService/ListChains",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("parser.ParserService", "ListChains"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Payload versions, charset policies and per-chain feature flags the enclave supports, so
+        /// clients can gate features on what is actually deployed
+        pub async fn get_capabilities(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCapabilitiesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCapabilitiesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/parser.ParserService/GetCapabilities",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("parser.ParserService", "GetCapabilities"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Parses transactions the user approves together, e.g. an approval and the swap spending it,
+        /// into one signed bundle of payloads
+        pub async fn parse_bundle(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ParseBundleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ParseBundleResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/parser.ParserService/ParseBundle",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("parser.ParserService", "ParseBundle"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -482,6 +907,42 @@ pub mod parser_service_server {
             &self,
             request: tonic::Request<super::ParseRequest>,
         ) -> std::result::Result<tonic::Response<super::ParseResponse>, tonic::Status>;
+        /// Attestation of the enclave behind this host, with the parser versions it runs, so clients
+        /// can check which parser code signs their payloads
+        async fn get_attestation(
+            &self,
+            request: tonic::Request<super::GetAttestationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAttestationResponse>,
+            tonic::Status,
+        >;
+        /// Chain parsers registered in the enclave, with the encodings, transaction variants and
+        /// protocol decoders each supports
+        async fn list_chains(
+            &self,
+            request: tonic::Request<super::ListChainsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListChainsResponse>,
+            tonic::Status,
+        >;
+        /// Payload versions, charset policies and per-chain feature flags the enclave supports, so
+        /// clients can gate features on what is actually deployed
+        async fn get_capabilities(
+            &self,
+            request: tonic::Request<super::GetCapabilitiesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCapabilitiesResponse>,
+            tonic::Status,
+        >;
+        /// Parses transactions the user approves together, e.g. an approval and the swap spending it,
+        /// into one signed bundle of payloads
+        async fn parse_bundle(
+            &self,
+            request: tonic::Request<super::ParseBundleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ParseBundleResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct ParserServiceServer<T: ParserService> {
@@ -605,6 +1066,190 @@ pub mod parser_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/parser.ParserService/GetAttestation" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAttestationSvc<T: ParserService>(pub Arc<T>);
+                    impl<
+                        T: ParserService,
+                    > tonic::server::UnaryService<super::GetAttestationRequest>
+                    for GetAttestationSvc<T> {
+                        type Response = super::GetAttestationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetAttestationRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_attestation(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetAttestationSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/parser.ParserService/ListChains" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListChainsSvc<T: ParserService>(pub Arc<T>);
+                    impl<
+                        T: ParserService,
+                    > tonic::server::UnaryService<super::ListChainsRequest>
+                    for ListChainsSvc<T> {
+                        type Response = super::ListChainsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListChainsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).list_chains(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListChainsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/parser.ParserService/GetCapabilities" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetCapabilitiesSvc<T: ParserService>(pub Arc<T>);
+                    impl<
+                        T: ParserService,
+                    > tonic::server::UnaryService<super::GetCapabilitiesRequest>
+                    for GetCapabilitiesSvc<T> {
+                        type Response = super::GetCapabilitiesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCapabilitiesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_capabilities(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetCapabilitiesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/parser.ParserService/ParseBundle" => {
+                    #[allow(non_camel_case_types)]
+                    struct ParseBundleSvc<T: ParserService>(pub Arc<T>);
+                    impl<
+                        T: ParserService,
+                    > tonic::server::UnaryService<super::ParseBundleRequest>
+                    for ParseBundleSvc<T> {
+                        type Response = super::ParseBundleResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ParseBundleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).parse_bundle(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ParseBundleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -36,3 +36,31 @@ impl From<Status> for tonic::Status {
         )
     }
 }
+
+/// A proxied message carrying a caller-supplied request id across the enclave boundary.
+pub trait RequestId {
+    /// The request id, empty when none was set
+    fn request_id(&self) -> &str;
+    /// Replaces the request id
+    fn set_request_id(&mut self, request_id: String);
+}
+
+impl RequestId for parser::QosParserRequest {
+    fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    fn set_request_id(&mut self, request_id: String) {
+        self.request_id = request_id;
+    }
+}
+
+impl RequestId for parser::QosParserResponse {
+    fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    fn set_request_id(&mut self, request_id: String) {
+        self.request_id = request_id;
+    }
+}
@@ -24,7 +24,14 @@ use qos_core::{
     client::SocketClient,
     protocol::{ProtocolPhase, msg::ProtocolMsg},
 };
-use std::{pin::Pin, time::Duration};
+use std::{
+    pin::Pin,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc;
 use tokio_stream::Stream;
 
@@ -35,6 +42,96 @@ pub const LIVENESS: &str = "liveness";
 /// k8s terminology to check if a service is ready to serve traffic.
 pub const READINESS: &str = "readiness";
 
+/// Set once the host starts shutting down, after which [`K8Health`] reports
+/// [`READINESS`] as `NOT_SERVING` so no new traffic is routed to it. Liveness
+/// stays `SERVING` so the host is not restarted while it drains.
+///
+/// Clones share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct Draining(Arc<AtomicBool>);
+
+impl Draining {
+    /// Start draining; there is no way back.
+    pub fn start(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether draining has started.
+    #[must_use]
+    pub fn is_draining(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Probes the enclave with a QOS status request, which the enclave answers
+/// without involving the app, so [`K8Health`] can report not ready when the
+/// enclave is down or wedged.
+///
+/// The outcome is cached for a while so frequent health checks do not load the
+/// enclave. Clones share the same cache.
+#[derive(Clone, Debug)]
+pub struct EnclaveProbe {
+    client: SocketClient,
+    timeout: Duration,
+    ttl: Duration,
+    last: Arc<Mutex<Option<(Instant, bool)>>>,
+}
+
+impl EnclaveProbe {
+    /// Create a new instance of [`Self`], giving up on a probe after `timeout`
+    /// and reusing its outcome for `ttl`.
+    #[must_use]
+    pub fn new(client: SocketClient, timeout: Duration, ttl: Duration) -> Self {
+        Self {
+            client,
+            timeout,
+            ttl,
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Whether the enclave answered its last probe in time with its quorum key
+    /// provisioned, probing again once the cached outcome expires.
+    pub async fn is_ready(&self) -> bool {
+        let cached = *self.last.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((probed_at, ready)) = cached
+            && probed_at.elapsed() < self.ttl
+        {
+            return ready;
+        }
+
+        let ready = self.probe().await;
+        *self.last.lock().unwrap_or_else(PoisonError::into_inner) = Some((Instant::now(), ready));
+        ready
+    }
+
+    async fn probe(&self) -> bool {
+        let encoded_request = borsh::to_vec(&ProtocolMsg::StatusRequest)
+            .expect("ProtocolMsg can always serialize. qed.");
+        let Ok(Ok(encoded_response)) =
+            tokio::time::timeout(self.timeout, self.client.call(&encoded_request)).await
+        else {
+            return false;
+        };
+
+        matches!(
+            ProtocolMsg::try_from_slice(&encoded_response),
+            Ok(ProtocolMsg::StatusResponse(
+                ProtocolPhase::QuorumKeyProvisioned
+            ))
+        )
+    }
+}
+
+/// Optional behaviors of [`K8Health`].
+#[derive(Clone, Debug, Default)]
+pub struct K8HealthOptions {
+    /// Report not ready once draining starts
+    pub draining: Draining,
+    /// Probe the enclave itself before the app when checking readiness
+    pub enclave_probe: Option<EnclaveProbe>,
+}
+
 /// Turnkeys health check service for performing primitive health checks via an
 /// app host.
 pub struct TkHealthCheck<T> {
@@ -130,6 +227,7 @@ where
 #[derive(Clone)]
 pub struct K8Health<T> {
     app_check: T,
+    options: K8HealthOptions,
 }
 
 impl<T> K8Health<T>
@@ -140,10 +238,31 @@ where
     /// (`enclave_addr`).
     #[must_use]
     pub fn build_service(app_check: T) -> K8HealthServer<K8Health<T>> {
-        let inner = Self { app_check };
+        Self::build_service_with_options(app_check, K8HealthOptions::default())
+    }
+
+    /// Like [`Self::build_service`], with `options`.
+    #[must_use]
+    pub fn build_service_with_options(
+        app_check: T,
+        options: K8HealthOptions,
+    ) -> K8HealthServer<K8Health<T>> {
+        let inner = Self { app_check, options };
         K8HealthServer::new(inner)
     }
 
+    async fn readiness(&self) -> K8ServingStatus {
+        if self.options.draining.is_draining() {
+            return K8ServingStatus::NotServing;
+        }
+        if let Some(probe) = &self.options.enclave_probe
+            && !probe.is_ready().await
+        {
+            return K8ServingStatus::NotServing;
+        }
+        self.app_status().await
+    }
+
     async fn app_status(&self) -> K8ServingStatus {
         match self
             .app_check
@@ -165,7 +284,7 @@ where
     ) -> K8HealthCheckResponse {
         let status = match request.get_ref().service.as_str() {
             LIVENESS => K8ServingStatus::Serving,
-            READINESS => self.app_status().await,
+            READINESS => self.readiness().await,
             _ => K8ServingStatus::ServiceUnknown,
         };
 
@@ -202,6 +321,11 @@ where
             loop {
                 let status = self2.k8_request(&request).await;
                 match tx.send(Ok(status)).await {
+                    Ok(()) if self2.options.draining.is_draining() => {
+                        // End the stream so it does not hold the connection
+                        // open while the host drains
+                        break;
+                    }
                     Ok(()) => {
                         // `status` was queued to be sent to the gRPC client
                     }
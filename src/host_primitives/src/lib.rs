@@ -5,10 +5,15 @@
 use std::time::Duration;
 
 use borsh::BorshDeserialize;
+use generated::RequestId;
 use prost::Message;
 use qos_core::protocol::{ProtocolError, msg::ProtocolMsg};
+use qos_nsm::types::NsmResponse;
 use tonic::Status;
 
+mod pool;
+pub use pool::{EnclaveClientPool, EnclavePoolConfig};
+
 /// Buffer size for socket message queue.
 pub static ENCLAVE_QUEUE_CAPACITY: usize = 12;
 /// Maximum gRPC message size. Set to 25MB (25*1024*1024)
@@ -34,7 +39,7 @@ where
     let encoded_qos_response = client
         .call(&encoded_qos_request)
         .await
-        .map_err(|e| Status::internal(format!("Failed to query enclave: {e:?}")))?;
+        .map_err(|e| Status::unavailable(format!("Failed to query enclave: {e:?}")))?;
     let qos_response = ProtocolMsg::try_from_slice(&encoded_qos_response)
         .map_err(|e| Status::internal(format!("Failed to deserialized enclave response: {e:?}")))?;
 
@@ -56,6 +61,80 @@ where
         .map_err(|e| Status::internal(format!("Failed to deserialize enclave response: {e:?}")))
 }
 
+/// Send a message to a secure app via QOS proxy using the `Client`, tagged with `request_id`.
+///
+/// The app logs the request under the id and echoes it in its response. A response echoing
+/// another id is rejected, so a reply is never handed to the wrong request; one without an id,
+/// from an app that does not echo it, is accepted.
+pub async fn send_traced_proxy_request<Req, Resp>(
+    mut request: Req,
+    request_id: &str,
+    client: &qos_core::client::SocketClient,
+) -> Result<Resp, tonic::Status>
+where
+    Resp: Message + Default + RequestId,
+    Req: Message + RequestId,
+{
+    request.set_request_id(request_id.to_string());
+    let response: Resp = send_proxy_request(request, client).await?;
+    check_request_id(response, request_id)
+}
+
+fn check_request_id<Resp: RequestId>(response: Resp, request_id: &str) -> Result<Resp, Status> {
+    let echoed = response.request_id();
+    if echoed.is_empty() || echoed == request_id {
+        Ok(response)
+    } else {
+        Err(Status::internal(format!(
+            "enclave answered request {request_id:?} with the response to {echoed:?}"
+        )))
+    }
+}
+
+/// An enclave's live attestation, as returned by the QOS protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LiveAttestation {
+    /// NSM attestation document (CBOR-encoded COSE Sign1)
+    pub document: Vec<u8>,
+    /// SHA-256 of the borsh-encoded manifest the enclave booted with, if it has one
+    pub manifest_digest: Option<[u8; 32]>,
+}
+
+/// Request a fresh attestation document from the enclave's QOS layer.
+pub async fn request_live_attestation(
+    client: &qos_core::client::SocketClient,
+) -> Result<LiveAttestation, tonic::Status> {
+    let encoded_qos_request = borsh::to_vec(&ProtocolMsg::LiveAttestationDocRequest)
+        .map_err(|e| Status::internal(format!("Failed to serialize qos request: {e:?}")))?;
+
+    let encoded_qos_response = client
+        .call(&encoded_qos_request)
+        .await
+        .map_err(|e| Status::unavailable(format!("Failed to query enclave: {e:?}")))?;
+    let qos_response = ProtocolMsg::try_from_slice(&encoded_qos_response)
+        .map_err(|e| Status::internal(format!("Failed to deserialized enclave response: {e:?}")))?;
+
+    match qos_response {
+        ProtocolMsg::LiveAttestationDocResponse {
+            nsm_response: NsmResponse::Attestation { document },
+            manifest_envelope,
+        } => {
+            let manifest_digest = manifest_envelope
+                .map(|envelope| borsh::to_vec(&envelope.manifest))
+                .transpose()
+                .map_err(|e| Status::internal(format!("Failed to serialize manifest: {e:?}")))?
+                .map(|manifest| qos_crypto::sha_256(&manifest));
+            Ok(LiveAttestation {
+                document,
+                manifest_digest,
+            })
+        }
+        other => Err(Status::internal(format!(
+            "Expected a ProtocolMsg::LiveAttestationDocResponse but got {other:?}"
+        ))),
+    }
+}
+
 /// A default timeout for hosts to configure their qos protocol socket client with.
 pub const fn enclave_client_timeout() -> Duration {
     qos_core::protocol::INITIAL_CLIENT_TIMEOUT
@@ -0,0 +1,120 @@
+//! Concurrent access to an enclave socket.
+//!
+//! [`EnclaveClientPool`] spreads requests over several socket connections and bounds how many
+//! are in flight at once, so a burst of requests queues for a free slot instead of serializing
+//! behind one connection, and every request fails with `DEADLINE_EXCEEDED` once its deadline
+//! passes, whether it was still queued or already sent.
+
+use std::{sync::Arc, time::Duration};
+
+use generated::RequestId;
+use prost::Message;
+use qos_core::{
+    client::SocketClient,
+    io::{IOError, SocketAddress, StreamPool},
+};
+use tokio::sync::Semaphore;
+use tonic::Status;
+
+use crate::{
+    ENCLAVE_QUEUE_CAPACITY, enclave_client_timeout, send_proxy_request, send_traced_proxy_request,
+};
+
+/// Sizing and deadlines for an [`EnclaveClientPool`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnclavePoolConfig {
+    /// Socket connections opened to the enclave. The enclave must accept at least as many.
+    pub connections: u8,
+    /// Requests in flight at once; further requests wait for a free slot.
+    pub max_in_flight: usize,
+    /// Deadline for one request, including the time spent waiting for a slot.
+    pub request_timeout: Duration,
+}
+
+impl Default for EnclavePoolConfig {
+    fn default() -> Self {
+        Self {
+            connections: 1,
+            max_in_flight: ENCLAVE_QUEUE_CAPACITY,
+            request_timeout: enclave_client_timeout(),
+        }
+    }
+}
+
+/// A pooled, concurrency-limited client for one enclave.
+///
+/// Clones share the same connections and in-flight limit.
+#[derive(Clone, Debug)]
+pub struct EnclaveClientPool {
+    client: SocketClient,
+    permits: Arc<Semaphore>,
+    request_timeout: Duration,
+}
+
+impl EnclaveClientPool {
+    /// Open `config.connections` connections to the enclave at `addr`.
+    pub fn new(addr: SocketAddress, config: &EnclavePoolConfig) -> Result<Self, IOError> {
+        let pool = StreamPool::new(addr, config.connections.max(1))?;
+        Ok(Self {
+            client: SocketClient::new(pool.shared(), enclave_client_timeout()),
+            permits: Arc::new(Semaphore::new(config.max_in_flight.max(1))),
+            request_timeout: config.request_timeout,
+        })
+    }
+
+    /// The underlying client, for callers that manage their own concurrency such as health
+    /// checks.
+    pub fn client(&self) -> &SocketClient {
+        &self.client
+    }
+
+    /// Send a message to the secure app once a slot is free, failing when the request deadline
+    /// passes first.
+    pub async fn send<Req, Resp>(&self, request: Req) -> Result<Resp, Status>
+    where
+        Resp: Message + Default,
+        Req: Message,
+    {
+        self.with_slot(send_proxy_request(request, &self.client))
+            .await
+    }
+
+    /// Like [`Self::send`], tagging the message with `request_id`; see
+    /// [`send_traced_proxy_request`].
+    pub async fn send_traced<Req, Resp>(
+        &self,
+        request: Req,
+        request_id: &str,
+    ) -> Result<Resp, Status>
+    where
+        Resp: Message + Default + RequestId,
+        Req: Message + RequestId,
+    {
+        self.with_slot(send_traced_proxy_request(request, request_id, &self.client))
+            .await
+    }
+
+    // Runs `send` once a slot is free, failing when the request deadline passes first
+    async fn with_slot<Resp>(
+        &self,
+        send: impl Future<Output = Result<Resp, Status>>,
+    ) -> Result<Resp, Status> {
+        let call = async {
+            let _permit = self
+                .permits
+                .acquire()
+                .await
+                .map_err(|_| Status::unavailable("enclave client pool is closed"))?;
+            send.await
+        };
+
+        tokio::time::timeout(self.request_timeout, call)
+            .await
+            .map_err(|_| {
+                Status::deadline_exceeded(format!(
+                    "enclave request timed out after {:?}",
+                    self.request_timeout
+                ))
+            })?
+    }
+}
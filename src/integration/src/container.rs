@@ -0,0 +1,126 @@
+//! Container backend for the [`crate::Builder`].
+//!
+//! Each binary runs in its own container through the `docker` CLI. The test's
+//! working directory is mounted at [`WORK_DIR`] in every container, so they
+//! share the unix socket files, fixtures and manifest envelope with the
+//! harness; the hosts publish their gRPC port on the loopback interface.
+
+use std::process::Command;
+
+use crate::{Binary, ChildWrapper};
+
+/// Where the test's working directory is mounted in the containers.
+pub const WORK_DIR: &str = "/work";
+
+const DOCKER: &str = "docker";
+
+/// The image a binary runs from and the program to run in it.
+#[derive(Debug, Clone)]
+pub struct ContainerImage {
+    /// Image reference, e.g. `anchorageoss-visualsign-parser/parser_host`
+    pub image: String,
+    /// Path of the binary inside the image
+    pub program: String,
+}
+
+impl ContainerImage {
+    /// Create a new instance of [`Self`].
+    #[must_use]
+    pub fn new(image: impl Into<String>, program: impl Into<String>) -> Self {
+        Self {
+            image: image.into(),
+            program: program.into(),
+        }
+    }
+}
+
+/// The images the [`crate::Builder`] runs each binary from.
+///
+/// Defaults to the images `make non-oci-docker-images` builds.
+#[derive(Debug, Clone)]
+pub struct ContainerImages {
+    /// Image of the simulated enclave
+    pub enclave: ContainerImage,
+    /// Image of the parser secure app
+    pub app: ContainerImage,
+    /// Image of the parser host
+    pub host: ContainerImage,
+}
+
+impl Default for ContainerImages {
+    fn default() -> Self {
+        Self {
+            enclave: ContainerImage::new(
+                "anchorageoss-visualsign-parser/simulator_enclave",
+                "/simulator_enclave",
+            ),
+            app: ContainerImage::new("anchorageoss-visualsign-parser/parser_app", "/parser_app"),
+            host: ContainerImage::new(
+                "anchorageoss-visualsign-parser/parser_host",
+                "/usr/local/bin/parser_host",
+            ),
+        }
+    }
+}
+
+impl ContainerImages {
+    pub(crate) fn get(&self, binary: Binary) -> &ContainerImage {
+        match binary {
+            Binary::Enclave => &self.enclave,
+            Binary::App => &self.app,
+            Binary::Host => &self.host,
+        }
+    }
+}
+
+/// A running container, removed on drop.
+#[derive(Debug)]
+pub struct ContainerWrapper {
+    name: String,
+    // The attached `docker run` client, which streams the container's output
+    client: ChildWrapper,
+}
+
+impl ContainerWrapper {
+    pub(crate) fn new(name: String, client: ChildWrapper) -> Self {
+        Self { name, client }
+    }
+}
+
+impl Drop for ContainerWrapper {
+    fn drop(&mut self) {
+        // Remove the container and explicitly ignore the results; the client
+        // exits with it, having forwarded all of its output
+        drop(Command::new(DOCKER).args(["rm", "-f", &self.name]).output());
+        drop(self.client.0.wait());
+    }
+}
+
+/// The `docker run` command starting `image` as container `name`, with `envs`
+/// set and `port` published on the loopback interface. The program's
+/// arguments are appended by the caller.
+pub(crate) fn run_command(
+    name: &str,
+    image: &ContainerImage,
+    envs: &[(String, String)],
+    port: Option<u16>,
+) -> Command {
+    let work_dir = std::env::current_dir().expect("failed to read the working directory");
+
+    let mut command = Command::new(DOCKER);
+    command
+        .args(["run", "--rm", "--name", name])
+        .arg("--volume")
+        .arg(format!("{}:{WORK_DIR}", work_dir.display()))
+        .args(["--workdir", WORK_DIR]);
+    for (key, value) in envs {
+        command.arg("--env").arg(format!("{key}={value}"));
+    }
+    if let Some(port) = port {
+        command
+            .arg("--publish")
+            .arg(format!("127.0.0.1:{port}:{port}"));
+    }
+    command.arg(&image.image).arg(&image.program);
+    command
+}
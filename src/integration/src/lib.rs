@@ -11,9 +11,11 @@
 
 use std::net::TcpListener;
 use std::ops::Range;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
-use std::{fs, future::Future, panic::AssertUnwindSafe, process::Command};
+use std::time::{Duration, Instant};
+use std::{fs, future::Future, panic::AssertUnwindSafe};
 
 use futures::future::FutureExt;
 use generated::grpc::health::v1::{HealthCheckRequest, HealthCheckResponse};
@@ -24,14 +26,23 @@ use generated::health::health_check_service_client::HealthCheckServiceClient;
 use generated::parser::parser_service_client::ParserServiceClient;
 
 use host_primitives::GRPC_MAX_RECV_MSG_SIZE;
-use qos_core::protocol::services::boot::{Manifest, ManifestEnvelope, MemberPubKey, PatchSet};
+use qos_core::handles::EphemeralKeyHandle;
+use qos_core::io::{SocketAddress, StreamPool};
+use qos_core::protocol::services::boot::{ManifestEnvelope, MemberPubKey, PatchSet};
+use qos_core::server::SocketServer;
 use qos_p256::P256Pair;
 use qos_test_primitives::PathWrapper;
 use tonic::transport::Channel;
 
-const MAX_PORT_BIND_WAIT_TIME: Duration = Duration::from_secs(90);
-const PORT_BIND_WAIT_TIME_INCREMENT: Duration = Duration::from_millis(500);
-const POST_BIND_SLEEP: Duration = Duration::from_millis(500);
+pub mod container;
+pub mod simulator;
+
+use container::{ContainerImages, ContainerWrapper};
+use simulator::Faults;
+
+const MAX_SERVING_WAIT_TIME: Duration = Duration::from_secs(90);
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
 const SERVER_PORT_RANGE: Range<u16> = 10000..60000;
 const MAX_PORT_SEARCH_ATTEMPTS: u16 = 50;
 
@@ -49,6 +60,24 @@ impl Drop for ChildWrapper {
     fn drop(&mut self) {
         // Kill the process and explicitly ignore the result
         drop(self.0.kill());
+        // Reap it so everything it wrote has reached its log before a dump
+        drop(self.0.wait());
+    }
+}
+
+/// Wrapper type for [`tokio::task::JoinHandle`] that aborts the task on drop.
+#[derive(Debug)]
+pub struct TaskWrapper(tokio::task::JoinHandle<()>);
+
+impl From<tokio::task::JoinHandle<()>> for TaskWrapper {
+    fn from(handle: tokio::task::JoinHandle<()>) -> Self {
+        Self(handle)
+    }
+}
+
+impl Drop for TaskWrapper {
+    fn drop(&mut self) {
+        self.0.abort();
     }
 }
 
@@ -65,29 +94,42 @@ pub fn find_free_port() -> Option<u16> {
     None
 }
 
-/// Wait until the given `port` is bound. Helpful for telling if something is
-/// listening on the given port.
+/// Wait until the canonical gRPC health service at `addr` reports `service` as
+/// `SERVING`, polling every `HEALTH_POLL_INTERVAL`.
+///
+/// Connection failures and non-serving statuses are retried, so this can be
+/// called right after spawning the server.
 ///
 /// # Panics
 ///
-/// Panics if the the port is not bound to within `MAX_PORT_BIND_WAIT_TIME`.
-pub fn wait_until_port_is_bound(port: u16) {
-    let mut wait_time = PORT_BIND_WAIT_TIME_INCREMENT;
-
-    while wait_time < MAX_PORT_BIND_WAIT_TIME {
-        thread::sleep(wait_time);
-        if port_is_available(port) {
-            wait_time += PORT_BIND_WAIT_TIME_INCREMENT;
-        } else {
-            thread::sleep(POST_BIND_SLEEP);
+/// Panics if `service` is not serving within `MAX_SERVING_WAIT_TIME`.
+pub async fn wait_until_serving(addr: &str, service: &str) {
+    let deadline = Instant::now() + MAX_SERVING_WAIT_TIME;
+
+    loop {
+        let check = async {
+            let mut client = HealthClient::connect(addr.to_string()).await.ok()?;
+            let request = tonic::Request::new(HealthCheckRequest {
+                service: service.to_string(),
+            });
+            client.check(request).await.ok()
+        };
+        let status = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check)
+            .await
+            .ok()
+            .flatten()
+            .map(|response| response.into_inner().status);
+        if status == Some(ServingStatus::Serving as i32) {
             return;
         }
+
+        assert!(
+            Instant::now() < deadline,
+            "Server has not come up: {service} at {addr} is not serving after {}s",
+            MAX_SERVING_WAIT_TIME.as_secs()
+        );
+        tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
     }
-    panic!(
-        "Server has not come up: port {} is still available after {}s",
-        port,
-        MAX_PORT_BIND_WAIT_TIME.as_secs()
-    )
 }
 
 /// Return wether or not the port can be bind-ed too.
@@ -97,6 +139,9 @@ fn port_is_available(port: u16) -> bool {
 
 const HOST_IP: &str = "127.0.0.1";
 const SIMULATOR_ENCLAVE_PATH: &str = "../target/debug/simulator_enclave";
+const PARSER_APP_PATH: &str = "../target/debug/parser_app";
+const PARSER_HOST_PATH: &str = "../target/debug/parser_host";
+const EPHEMERAL_FILE: &str = "fixtures/ephemeral.secret";
 
 /// Arguments passed to the `test` function in [`Builder::execute`].
 #[derive(Default)]
@@ -108,107 +153,614 @@ pub struct TestArgs {
     /// A client for canonical gRPC health check service
     /// See <https://github.com/grpc/grpc/blob/master/doc/health-checking.md>
     pub k8_health_client: Option<HealthClient<Channel>>,
+    /// Address of the (first) host, to connect clients of the test's own
+    pub host_addr: Option<String>,
+    /// A parser client for each host started, see [`Builder::hosts`]
+    pub parser_clients: Vec<ParserServiceClient<Channel>>,
+    /// Kills and restarts the binaries mid-test; not set with [`Builder::in_process`]
+    pub chaos: Option<Chaos>,
+}
+
+/// Kills and restarts the binaries the [`Builder`] spawned while the test runs.
+///
+/// Killing the app, for instance, has the enclave answer the host with
+/// `AppClientRecvTimeout` until the app is restarted.
+#[derive(Clone)]
+pub struct Chaos {
+    builder: Arc<Builder>,
+    running: Arc<Mutex<Running>>,
+}
+
+impl Chaos {
+    /// Kill every running instance of `binary`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `binary` was not started.
+    pub fn kill(&self, binary: Binary) {
+        let mut running = self.running.lock().unwrap();
+        let mut instances = running
+            .spawned
+            .iter_mut()
+            .filter(|spawned| spawned.binary == binary)
+            .peekable();
+        assert!(instances.peek().is_some(), "{binary:?} was not started");
+        instances.for_each(Spawned::kill);
+    }
+
+    /// Start every killed instance of `binary` again with the arguments it was
+    /// first started with, waiting for restarted hosts to be live.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `binary` was not started or a restarted host does not come up.
+    pub async fn restart(&self, binary: Binary) {
+        let mut host_ports = vec![];
+        {
+            let mut running = self.running.lock().unwrap();
+            let instances: Vec<_> = (0..running.spawned.len())
+                .filter(|&index| running.spawned[index].binary == binary)
+                .collect();
+            assert!(!instances.is_empty(), "{binary:?} was not started");
+            for index in instances {
+                if running.spawned[index].is_running() {
+                    continue;
+                }
+                self.builder.start(&mut running, index);
+                host_ports.extend(running.spawned[index].port);
+            }
+        }
+
+        for port in host_ports {
+            wait_until_serving(&format!("http://{HOST_IP}:{port}"), health_check::LIVENESS).await;
+        }
+    }
+}
+
+/// A binary started by the [`Builder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binary {
+    /// The simulated enclave relaying between the host and the secure app
+    Enclave,
+    /// The parser secure app
+    App,
+    /// The parser host serving gRPC
+    Host,
+}
+
+impl Binary {
+    fn name(self) -> &'static str {
+        match self {
+            Binary::Enclave => "enclave",
+            Binary::App => "app",
+            Binary::Host => "host",
+        }
+    }
+}
+
+// Per-test log files the binaries' stdout and stderr are redirected to.
+//
+// On drop the logs are printed to stderr and kept if the thread is panicking,
+// i.e. the test or the startup failed, and removed otherwise.
+struct ProcessLogs {
+    test_id: String,
+    logs: Vec<(String, String)>,
+}
+
+impl ProcessLogs {
+    // Opens the log file for `name`, appending when it is restarted, and
+    // returns its stdout and stderr
+    fn capture(&mut self, name: &str) -> (Stdio, Stdio) {
+        let path = format!("./{}.{name}.log", self.test_id);
+        let stdout = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .expect("failed to create process log");
+        let stderr = stdout.try_clone().expect("failed to clone process log");
+        if !self.logs.iter().any(|(logged, _)| logged == name) {
+            self.logs.push((name.to_string(), path));
+        }
+        (stdout.into(), stderr.into())
+    }
+}
+
+impl Drop for ProcessLogs {
+    fn drop(&mut self) {
+        let failed = thread::panicking();
+        for (name, path) in &self.logs {
+            if failed {
+                let output = fs::read_to_string(path).unwrap_or_default();
+                eprintln!("---- {name} output ({path}) ----\n{output}");
+            } else {
+                drop(fs::remove_file(path));
+            }
+        }
+    }
+}
+
+// A binary the builder spawned, with what it takes to spawn it again
+struct Spawned {
+    binary: Binary,
+    name: String,
+    args: Vec<String>,
+    port: Option<u16>,
+    // One of them is set while it runs, depending on the backend
+    process: Option<ChildWrapper>,
+    container: Option<ContainerWrapper>,
+}
+
+impl Spawned {
+    fn is_running(&self) -> bool {
+        self.process.is_some() || self.container.is_some()
+    }
+
+    fn kill(&mut self) {
+        drop(self.process.take());
+        drop(self.container.take());
+    }
+}
+
+// Everything the builder started, stopped on drop. Fields drop in order, so
+// the binaries are stopped before their logs are dumped.
+struct Running {
+    spawned: Vec<Spawned>,
+    tasks: Vec<TaskWrapper>,
+    logs: ProcessLogs,
+}
+
+impl Running {
+    fn new(test_id: &str) -> Self {
+        Self {
+            spawned: vec![],
+            tasks: vec![],
+            logs: ProcessLogs {
+                test_id: test_id.to_string(),
+                logs: vec![],
+            },
+        }
+    }
+}
+
+// How the builder starts one binary, on top of the arguments the harness wires up itself
+#[derive(Debug, Clone)]
+struct BinaryConfig {
+    path: String,
+    args: Vec<String>,
+    envs: Vec<(String, String)>,
+}
+
+impl BinaryConfig {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            args: vec![],
+            envs: vec![],
+        }
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new(&self.path);
+        command.envs(self.envs.iter().map(|(key, value)| (key, value)));
+        command
+    }
+}
+
+// Where the builder runs the binaries
+#[derive(Debug, Clone)]
+enum Backend {
+    Processes,
+    InProcess,
+    Containers(ContainerImages),
 }
 
 /// Test harness builder.
-#[derive(Default)]
-pub struct Builder {}
+///
+/// By default it starts the simulated enclave, the parser app and the parser
+/// host from the workspace target directory; downstream repos can point it at
+/// their own binaries and tune how each one is started.
+pub struct Builder {
+    binaries: Vec<Binary>,
+    enclave: BinaryConfig,
+    app: BinaryConfig,
+    host: BinaryConfig,
+    host_port: Option<u16>,
+    host_count: usize,
+    manifest_envelope: Option<ManifestEnvelope>,
+    faults: Faults,
+    backend: Backend,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            binaries: vec![Binary::Enclave, Binary::App, Binary::Host],
+            enclave: BinaryConfig::new(SIMULATOR_ENCLAVE_PATH),
+            app: BinaryConfig::new(PARSER_APP_PATH),
+            host: BinaryConfig::new(PARSER_HOST_PATH),
+            host_port: None,
+            host_count: 1,
+            manifest_envelope: None,
+            faults: Faults::default(),
+            backend: Backend::Processes,
+        }
+    }
+}
 
 impl Builder {
-    /// Create a new instance of [`Self`].
+    /// Create a new instance of [`Self`] that starts every binary.
     #[must_use]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Start only `binaries`.
+    ///
+    /// Clients in [`TestArgs`] are only set when [`Binary::Host`] is started.
+    /// Startup waits for the host's readiness when the enclave and app are
+    /// both started and no fault is injected, and only for its liveness
+    /// otherwise.
+    #[must_use]
+    pub fn only(mut self, binaries: &[Binary]) -> Self {
+        self.binaries = binaries.to_vec();
+        self
+    }
+
+    /// Start `binary` from `path` instead of the workspace target directory.
+    ///
+    /// The replacement is passed the same arguments as the original.
+    #[must_use]
+    pub fn binary_path(mut self, binary: Binary, path: impl Into<String>) -> Self {
+        self.config_mut(binary).path = path.into();
+        self
+    }
+
+    /// Pass `arg` to `binary` after the arguments the harness sets.
+    #[must_use]
+    pub fn arg(mut self, binary: Binary, arg: impl Into<String>) -> Self {
+        self.config_mut(binary).args.push(arg.into());
+        self
+    }
+
+    /// Set the environment variable `key` to `value` for `binary`.
+    #[must_use]
+    pub fn env(mut self, binary: Binary, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.config_mut(binary)
+            .envs
+            .push((key.into(), value.into()));
+        self
+    }
+
+    /// Serve the (first) host on `port` instead of a random free one.
+    #[must_use]
+    pub fn host_port(mut self, port: u16) -> Self {
+        self.host_port = Some(port);
+        self
+    }
+
+    /// Start `count` hosts, all against the one enclave.
+    ///
+    /// [`TestArgs::parser_clients`] has a client for each; the other clients
+    /// in [`TestArgs`] talk to the first host.
+    #[must_use]
+    pub fn hosts(mut self, count: usize) -> Self {
+        self.host_count = count;
+        self
+    }
+
+    /// Have the simulated enclave attest with `manifest_envelope`, as if it
+    /// had booted with it. Without one the enclave attests without a manifest.
+    #[must_use]
+    pub fn manifest_envelope(mut self, manifest_envelope: ManifestEnvelope) -> Self {
+        self.manifest_envelope = Some(manifest_envelope);
+        self
+    }
+
+    /// Have the simulated enclave wait `delay` before answering each request,
+    /// e.g. to run into the host's enclave timeout.
+    ///
+    /// The host's readiness goes through the enclave, so startup then only
+    /// waits for its liveness.
+    #[must_use]
+    pub fn delay_enclave_responses(mut self, delay: Duration) -> Self {
+        self.faults.response_delay = Some(delay);
+        let millis = delay.as_millis().to_string();
+        self.env(Binary::Enclave, simulator::RESPONSE_DELAY_MS_ENV, millis)
+    }
+
+    /// Have the simulated enclave cut each app response it relays to at most
+    /// `len` bytes, so the host receives malformed messages.
+    ///
+    /// The host's readiness goes through the app too, so startup then only
+    /// waits for its liveness.
+    #[must_use]
+    pub fn truncate_proxy_responses(mut self, len: usize) -> Self {
+        self.faults.truncate_responses_to = Some(len);
+        self.env(
+            Binary::Enclave,
+            simulator::TRUNCATE_RESPONSES_ENV,
+            len.to_string(),
+        )
+    }
+
+    /// Run the simulated enclave, the parser app and the parser host as tasks
+    /// of the test's runtime instead of spawning binaries, so the tests do not
+    /// need the workspace binaries to be built first.
+    ///
+    /// They still talk over unix sockets, which is the only transport of the
+    /// qos socket layer. Binary paths, environment and the app's and
+    /// enclave's extra arguments only apply to spawned binaries; the host's
+    /// extra arguments are honoured. Output goes to the test's own stdout and
+    /// stderr rather than log files.
+    #[must_use]
+    pub fn in_process(mut self) -> Self {
+        self.backend = Backend::InProcess;
+        self
+    }
+
+    /// Run each binary in a container from `images` instead of spawning it
+    /// from the target directory; see [`container`].
+    ///
+    /// Binary paths are ignored in favour of the images' programs; extra
+    /// arguments and environment still apply.
+    #[must_use]
+    pub fn containers(mut self, images: ContainerImages) -> Self {
+        self.backend = Backend::Containers(images);
+        self
+    }
+
+    fn config(&self, binary: Binary) -> &BinaryConfig {
+        match binary {
+            Binary::Enclave => &self.enclave,
+            Binary::App => &self.app,
+            Binary::Host => &self.host,
+        }
+    }
+
+    fn config_mut(&mut self, binary: Binary) -> &mut BinaryConfig {
+        match binary {
+            Binary::Enclave => &mut self.enclave,
+            Binary::App => &mut self.app,
+            Binary::Host => &mut self.host,
+        }
+    }
+
+    // Spawns `binary` as process or container `name` with `args`, publishing `port`
+    fn spawn(
+        &self,
+        binary: Binary,
+        name: &str,
+        args: &[String],
+        port: Option<u16>,
+        running: &mut Running,
+    ) {
+        running.spawned.push(Spawned {
+            binary,
+            name: name.to_string(),
+            args: args.to_vec(),
+            port,
+            process: None,
+            container: None,
+        });
+        self.start(running, running.spawned.len() - 1);
+    }
+
+    // (Re)starts the `index`th spawned binary
+    fn start(&self, running: &mut Running, index: usize) {
+        let Running { spawned, logs, .. } = running;
+        let spawned = &mut spawned[index];
+        let config = self.config(spawned.binary);
+        let container_name = format!("{}-{}", logs.test_id, spawned.name);
+        let mut command = match &self.backend {
+            Backend::Containers(images) => container::run_command(
+                &container_name,
+                images.get(spawned.binary),
+                &config.envs,
+                spawned.port,
+            ),
+            Backend::Processes | Backend::InProcess => config.command(),
+        };
+
+        let (stdout, stderr) = logs.capture(&spawned.name);
+        let child: ChildWrapper = command
+            .args(&spawned.args)
+            .args(&config.args)
+            .stdout(stdout)
+            .stderr(stderr)
+            .spawn()
+            .unwrap()
+            .into();
+
+        if matches!(self.backend, Backend::Containers(_)) {
+            spawned.container = Some(ContainerWrapper::new(container_name, child));
+        } else {
+            spawned.process = Some(child);
+        }
+    }
+
     /// Execute `test`.
     ///
+    /// Spawned binaries can be killed and restarted from the test through
+    /// [`TestArgs::chaos`].
+    ///
     /// Note this test env builder relies on binaries from other crates already
     /// being built and existing in the target directory. Thus any test that
     /// uses this should only be called after the whole workspace is compiled.
     /// This can be accomplished by either running `cargo build` or `cargo test`
     /// in the workspace root. However if you just test this crate (`cargo test
     /// -p integration`), this might fail because we don't build the
-    /// binaries from the other crates.
+    /// binaries from the other crates, unless [`Builder::in_process`] is set.
     ///
     /// # Panics
     ///
     /// Panics if `test` panics or any of the binaries started panics.
+    ///
+    /// The binaries' stdout and stderr go to `<test id>.<binary>.log` files,
+    /// which are printed and kept when the test fails and removed otherwise.
+    #[allow(clippy::too_many_lines)]
     pub async fn execute<F, T>(self, test: F)
     where
         F: Fn(TestArgs) -> T,
         T: Future<Output = ()>,
     {
         let test_id = format!("{:?}", rand::random::<u64>());
-        let mut process_handles = vec![];
+        let mut running = Running::new(&test_id);
         let mut file_handles = vec![];
 
-        // TODO: is this needed?
-        // We pass manifest_path as an argument in some apps for testing
-        // but I suspect we can do without it here.
-        let _ = setup_manifest(&test_id);
+        let in_process = matches!(self.backend, Backend::InProcess);
+        let containers = matches!(self.backend, Backend::Containers(_));
+        // Containers see the working directory at their mount point
+        let dir = if containers { container::WORK_DIR } else { "." };
+
+        // Kept until the end of the test, removing the file on drop
+        let manifest_path = self
+            .manifest_envelope
+            .as_ref()
+            .filter(|_| !in_process)
+            .map(|envelope| write_manifest_envelope(&test_id, envelope));
 
         let mut test_args = TestArgs {
             ..Default::default()
         };
 
-        let app_sock_path = format!("./{test_id}.parser.app.sock");
-        file_handles.push(app_sock_path.clone());
-        let enclave_sock_path = format!("./{test_id}.parser.enclave.sock");
-        file_handles.push(enclave_sock_path.clone());
+        file_handles.push(format!("./{test_id}.parser.app.sock"));
+        file_handles.push(format!("./{test_id}.parser.enclave.sock"));
+        let app_sock_path = format!("{dir}/{test_id}.parser.app.sock");
+        let enclave_sock_path = format!("{dir}/{test_id}.parser.enclave.sock");
 
         // Start parser enclave (simulator)
-        let enclave_process: ChildWrapper = Command::new(SIMULATOR_ENCLAVE_PATH)
-            .arg(&enclave_sock_path)
-            .arg(&app_sock_path)
-            .spawn()
-            .unwrap()
-            .into();
-        process_handles.push(enclave_process);
+        if self.binaries.contains(&Binary::Enclave) && in_process {
+            let (enclave_sock_path, app_sock_path) =
+                (enclave_sock_path.clone(), app_sock_path.clone());
+            let manifest_envelope = self.manifest_envelope.clone();
+            let faults = self.faults;
+            let enclave_task = tokio::spawn(async move {
+                simulator::run(
+                    &enclave_sock_path,
+                    &app_sock_path,
+                    1,
+                    manifest_envelope,
+                    faults,
+                )
+                .await;
+            });
+            running.tasks.push(enclave_task.into());
+        } else if self.binaries.contains(&Binary::Enclave) {
+            let mut args = vec![enclave_sock_path.clone(), app_sock_path.clone()];
+            if manifest_path.is_some() {
+                // The manifest envelope follows the pool size
+                args.push("1".to_string());
+                args.push(format!("{dir}/{test_id}.manifest_envelope"));
+            }
+            let name = Binary::Enclave.name();
+            self.spawn(Binary::Enclave, name, &args, None, &mut running);
+        }
 
         // Start parser secure app
-        let parser_process: ChildWrapper = Command::new("../target/debug/parser_app")
-            .arg("--usock")
-            .arg(&app_sock_path)
-            .arg("--ephemeral-file")
-            .arg("./fixtures/ephemeral.secret")
-            .spawn()
-            .unwrap()
-            .into();
-        process_handles.push(parser_process);
-
-        // Start parser host
-        let host_port = find_free_port().unwrap();
-        let host_process: ChildWrapper = Command::new("../target/debug/parser_host")
-            .arg("--host-ip")
-            .arg(HOST_IP)
-            .arg("--host-port")
-            .arg(host_port.to_string())
-            .arg("--usock")
-            .arg(&enclave_sock_path)
-            .spawn()
-            .unwrap()
-            .into();
-        process_handles.push(host_process);
-        wait_until_port_is_bound(host_port);
-
-        let host_addr = format!("http://{HOST_IP}:{host_port}");
+        if self.binaries.contains(&Binary::App) && in_process {
+            let app_task = tokio::spawn(run_app(app_sock_path.clone()));
+            running.tasks.push(app_task.into());
+        } else if self.binaries.contains(&Binary::App) {
+            let args = [
+                "--usock".to_string(),
+                app_sock_path.clone(),
+                "--ephemeral-file".to_string(),
+                format!("{dir}/{EPHEMERAL_FILE}"),
+            ];
+            let name = Binary::App.name();
+            self.spawn(Binary::App, name, &args, None, &mut running);
+        }
 
-        let health_check_client = HealthCheckServiceClient::connect(host_addr.clone())
-            .await
+        // Start parser hosts
+        let host_count = if self.binaries.contains(&Binary::Host) {
+            self.host_count
+        } else {
+            0
+        };
+        for index in 0..host_count {
+            let host_port = if index == 0 {
+                self.host_port.or_else(find_free_port)
+            } else {
+                find_free_port()
+            }
             .unwrap();
+            // A container's host must listen on its own interface to be published
+            let listen_ip = if containers { "0.0.0.0" } else { HOST_IP };
+            let mut host_args = vec![
+                "--host-ip".to_string(),
+                listen_ip.to_string(),
+                "--host-port".to_string(),
+                host_port.to_string(),
+                "--usock".to_string(),
+                enclave_sock_path.clone(),
+            ];
+
+            if in_process {
+                host_args.insert(0, "parser_host".to_string());
+                host_args.extend(self.host.args.iter().cloned());
+                let host_task = tokio::spawn(parser_host::cli::CLI::execute_embedded(host_args));
+                running.tasks.push(host_task.into());
+            } else {
+                let name = if index == 0 {
+                    Binary::Host.name().to_string()
+                } else {
+                    format!("{}-{index}", Binary::Host.name())
+                };
+                self.spawn(
+                    Binary::Host,
+                    &name,
+                    &host_args,
+                    Some(host_port),
+                    &mut running,
+                );
+            }
+
+            let host_addr = format!("http://{HOST_IP}:{host_port}");
+
+            // Readiness goes through the enclave to the app, so it can only
+            // be reached when both are running and answering in time
+            let service = if self.binaries.contains(&Binary::Enclave)
+                && self.binaries.contains(&Binary::App)
+                && self.faults.is_none()
+            {
+                health_check::READINESS
+            } else {
+                health_check::LIVENESS
+            };
+            wait_until_serving(&host_addr, service).await;
+
+            let parser_client = ParserServiceClient::connect(host_addr.clone())
+                .await
+                .unwrap()
+                .max_decoding_message_size(GRPC_MAX_RECV_MSG_SIZE);
+            test_args.parser_clients.push(parser_client.clone());
+
+            if index > 0 {
+                continue;
+            }
+
+            let health_check_client = HealthCheckServiceClient::connect(host_addr.clone())
+                .await
+                .unwrap();
+
+            test_args.health_check_client = Some(health_check_client);
+
+            let k8_health_client = HealthClient::connect(host_addr.clone()).await.unwrap();
+            test_args.k8_health_client = Some(k8_health_client);
+
+            test_args.host_addr = Some(host_addr);
+
+            test_args.parser_client = Some(parser_client);
+        }
 
-        test_args.health_check_client = Some(health_check_client);
-
-        let k8_health_client = HealthClient::connect(host_addr.clone()).await.unwrap();
-        test_args.k8_health_client = Some(k8_health_client);
-
-        let parser_client = ParserServiceClient::connect(host_addr)
-            .await
-            .unwrap()
-            .max_decoding_message_size(GRPC_MAX_RECV_MSG_SIZE);
-
-        test_args.parser_client = Some(parser_client);
+        // Shared with the test so it can kill and restart the spawned binaries
+        let running = Arc::new(Mutex::new(running));
+        if !in_process {
+            test_args.chaos = Some(Chaos {
+                builder: Arc::new(self),
+                running: Arc::clone(&running),
+            });
+        }
 
         // Note: this isn't actually unwind safe. However, since we don't
         // attempt to access any memory from `test` that may get corrupted
@@ -223,19 +775,22 @@ impl Builder {
     }
 }
 
-fn setup_manifest(test_id: &str) -> PathWrapper {
+// Serves the parser app on `app_sock_path` until dropped, like the `parser_app` binary
+async fn run_app(app_sock_path: String) {
+    let processor =
+        parser_app::service::Processor::new(EphemeralKeyHandle::new(format!("./{EPHEMERAL_FILE}")));
+    let pool = StreamPool::new(SocketAddress::new_unix(&app_sock_path), 1)
+        .expect("unable to create app pool");
+    let server = SocketServer::listen_all(pool, &processor).expect("unable to start Parser server");
+
+    // The server stops when dropped, so keep it for as long as we are polled
+    std::future::pending::<()>().await;
+    drop(server);
+}
+
+fn write_manifest_envelope(test_id: &str, envelope: &ManifestEnvelope) -> PathWrapper {
     let path: PathWrapper = format!("./{test_id}.manifest_envelope").into();
-    let (patch_set, _) = make_patch_set(3, 2);
-    let manifest = Manifest {
-        patch_set,
-        ..Default::default()
-    };
-
-    let envelope = borsh::to_vec(&ManifestEnvelope {
-        manifest,
-        ..Default::default()
-    })
-    .unwrap();
+    let envelope = borsh::to_vec(envelope).unwrap();
     fs::write(&*path, envelope).expect("failed to write manifest envelope to disk");
 
     path
@@ -0,0 +1,185 @@
+//! Enclave to simulate communication patterns with a secure app.
+//!
+//! It relays proxy requests from the host to the app, answers live
+//! attestation requests with a mock document and status requests as a
+//! provisioned enclave. The `simulator_enclave` binary
+//! wraps [`run`]; the [`crate::Builder`] also runs it in-process. Both can
+//! inject [`Faults`] into the traffic to exercise the host's error handling.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use borsh::BorshDeserialize;
+use host_primitives::enclave_client_timeout;
+use qos_core::{
+    client::{ClientError, SocketClient},
+    io::{SocketAddress, StreamPool},
+    protocol::{ProtocolError, ProtocolPhase, msg::ProtocolMsg, services::boot::ManifestEnvelope},
+    server::{RequestProcessor, SharedProcessor, SocketServer},
+};
+use qos_nsm::types::NsmResponse;
+use tokio::sync::RwLock;
+
+/// Environment variable the binary reads [`Faults::response_delay`] from, in milliseconds.
+pub const RESPONSE_DELAY_MS_ENV: &str = "SIMULATOR_RESPONSE_DELAY_MS";
+/// Environment variable the binary reads [`Faults::truncate_responses_to`] from.
+pub const TRUNCATE_RESPONSES_ENV: &str = "SIMULATOR_TRUNCATE_RESPONSES";
+
+/// Faults the simulated enclave injects into the traffic it relays.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Faults {
+    /// Wait this long before answering each request
+    pub response_delay: Option<Duration>,
+    /// Cut each proxied app response to at most this many bytes
+    pub truncate_responses_to: Option<usize>,
+}
+
+impl Faults {
+    /// Reads the faults from [`RESPONSE_DELAY_MS_ENV`] and [`TRUNCATE_RESPONSES_ENV`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable is set but not a number.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.parse::<u64>().expect("invalid simulator fault value"))
+        };
+        Self {
+            response_delay: var(RESPONSE_DELAY_MS_ENV).map(Duration::from_millis),
+            truncate_responses_to: var(TRUNCATE_RESPONSES_ENV)
+                .map(|len| usize::try_from(len).expect("invalid simulator fault value")),
+        }
+    }
+
+    /// Whether no fault is injected.
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Clone)]
+struct Processor {
+    app_client: SocketClient,
+    manifest_envelope: Option<ManifestEnvelope>,
+    faults: Faults,
+}
+
+impl Processor {
+    pub fn new(
+        app_client: SocketClient,
+        manifest_envelope: Option<ManifestEnvelope>,
+        faults: Faults,
+    ) -> SharedProcessor<Self> {
+        Arc::new(RwLock::new(Self {
+            app_client,
+            manifest_envelope,
+            faults,
+        }))
+    }
+
+    /// Expands the app pool to given pool size
+    pub async fn expand_to(&mut self, pool_size: u8) -> Result<(), ClientError> {
+        self.app_client.expand_to(pool_size).await
+    }
+}
+
+impl RequestProcessor for Processor {
+    async fn process(&self, request: &[u8]) -> Vec<u8> {
+        let msg_req = ProtocolMsg::try_from_slice(request)
+            .expect("enclave_stub: Failed to deserialize request");
+
+        if let Some(delay) = self.faults.response_delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        match msg_req {
+            ProtocolMsg::ProxyRequest { data } => {
+                let mut resp_data = match self.app_client.call(&data).await {
+                    Ok(d) => d,
+                    // Answer like QOS does when the app is gone, e.g. killed by a test
+                    Err(err) => {
+                        eprintln!("enclave_stub: Error from app: {err:?}");
+                        return borsh::to_vec(&ProtocolMsg::ProtocolErrorResponse(
+                            ProtocolError::AppClientRecvTimeout,
+                        ))
+                        .expect("enclave_stub: Failed to serialize response");
+                    }
+                };
+                if let Some(len) = self.faults.truncate_responses_to {
+                    resp_data.truncate(len);
+                }
+
+                borsh::to_vec(&ProtocolMsg::ProxyResponse { data: resp_data })
+                    .expect("enclave_stub: Failed to serialize response")
+            }
+            ProtocolMsg::LiveAttestationDocRequest => {
+                let data_string = borsh::to_vec(&"MOCK_DOCUMENT".to_string())
+                    .expect("unable to serialize mock document");
+                let nsm_response = NsmResponse::Attestation {
+                    document: data_string,
+                };
+
+                borsh::to_vec(&ProtocolMsg::LiveAttestationDocResponse {
+                    nsm_response,
+                    manifest_envelope: self.manifest_envelope.clone().map(Box::new),
+                })
+                .expect("enclave stub: Failed to serialize response")
+            }
+            ProtocolMsg::StatusRequest => borsh::to_vec(&ProtocolMsg::StatusResponse(
+                ProtocolPhase::QuorumKeyProvisioned,
+            ))
+            .expect("enclave stub: Failed to serialize response"),
+            other => panic!("enclave_stub: Unexpected request {other:?}"),
+        }
+    }
+}
+
+/// Listen on `enclave_sock_path` and relay to the app on `app_sock_path` with
+/// `pool_size` connections each, until the returned future is dropped.
+///
+/// Live attestations carry `manifest_envelope`, as if the enclave had booted
+/// with it, and `faults` are injected into every response.
+///
+/// # Panics
+///
+/// Panics if the sockets cannot be set up.
+pub async fn run(
+    enclave_sock_path: &str,
+    app_sock_path: &str,
+    pool_size: u8,
+    manifest_envelope: Option<ManifestEnvelope>,
+    faults: Faults,
+) {
+    let enclave_sock_pool = StreamPool::new(SocketAddress::new_unix(enclave_sock_path), pool_size)
+        .expect("unable to create enclave pool");
+
+    let app_sock_pool = StreamPool::new(SocketAddress::new_unix(app_sock_path), pool_size)
+        .expect("unable to create app pool");
+    let processor = Processor::new(
+        SocketClient::new(app_sock_pool.shared(), enclave_client_timeout()),
+        manifest_envelope,
+        faults,
+    );
+
+    let mut server = SocketServer::listen_all(enclave_sock_pool, &processor)
+        .expect("unable to start enclave socket server");
+
+    server
+        .listen_to(pool_size, &processor)
+        .expect("unable to listen_to on the running server");
+    // expand app connections to pool_size
+    processor
+        .write()
+        .await
+        .expand_to(pool_size)
+        .await
+        .expect("unable to expand_to on the processor app pool");
+
+    // The server stops when dropped, so keep it for as long as we are polled
+    std::future::pending::<()>().await;
+    drop(server);
+}
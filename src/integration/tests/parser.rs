@@ -1,6 +1,13 @@
+use generated::google::rpc::{ErrorInfo, Status};
 use generated::health::{AppHealthRequest, AppHealthResponse};
-use generated::parser::{Chain, ParseRequest};
-use integration::TestArgs;
+use generated::parser::{
+    Chain, GetAttestationRequest, GetCapabilitiesRequest, ListChainsRequest, ParseBundleRequest,
+    ParseRequest, PayloadCompression,
+};
+use integration::{Binary, TestArgs};
+use parser_client::{ClientConfig, ParserClient, RetryPolicy};
+use prost::Message;
+use std::time::Duration;
 use tonic::Code;
 
 /// Recursively validates that all fields in expected are present in actual
@@ -93,6 +100,7 @@ async fn parser_e2e() {
             unsigned_payload: "unsignedpayload".to_string(),
             chain: Chain::Unspecified as i32,
             chain_metadata: None,
+            ..Default::default()
         };
 
         let parse_response = test_args
@@ -120,6 +128,7 @@ async fn propagates_grpc_errors() {
             unsigned_payload: "no-no-that-is-not-valid-base64".to_string(),
             chain: Chain::Ethereum as i32,
             chain_metadata: None,
+            ..Default::default()
         };
 
         let parse_error = test_args
@@ -132,13 +141,140 @@ async fn propagates_grpc_errors() {
         assert_eq!(parse_error.code(), Code::InvalidArgument);
         assert_eq!(
             parse_error.message(),
-            "Failed to parse transaction: Decode error: Failed to decode transaction: Failed to decode base64: Invalid symbol 45, offset 2."
+            "Failed to parse transaction: Failed to decode transaction: Failed to decode base64: Invalid symbol 45, offset 2."
         );
+
+        // The parser's classification travels as a google.rpc.ErrorInfo detail
+        let status = Status::decode(parse_error.details()).unwrap();
+        assert_eq!(
+            status.details[0].type_url,
+            "type.googleapis.com/google.rpc.ErrorInfo"
+        );
+        let info = ErrorInfo::decode(status.details[0].value.as_slice()).unwrap();
+        assert_eq!(info.reason, "ENCODING");
+        assert_eq!(info.domain, "visualsign.parser");
+        assert_eq!(info.metadata["chain"], "Ethereum");
+        assert_eq!(info.metadata["detail"], "FailedToDecodeTransaction");
     }
 
     integration::Builder::new().execute(test).await
 }
 
+#[tokio::test]
+async fn rejects_invalid_requests_before_the_enclave() {
+    async fn test(test_args: TestArgs) {
+        let client = test_args.parser_client.unwrap();
+        // Rejected by the host with an ErrorInfo detail naming the broken rule
+        let reject = |parse_request: ParseRequest| {
+            let mut client = client.clone();
+            async move {
+                let parse_error = client
+                    .parse(tonic::Request::new(parse_request))
+                    .await
+                    .unwrap_err();
+                assert_eq!(parse_error.code(), Code::InvalidArgument);
+                let status = Status::decode(parse_error.details()).unwrap();
+                let info = ErrorInfo::decode(status.details[0].value.as_slice()).unwrap();
+                assert_eq!(info.domain, "visualsign.host");
+                info
+            }
+        };
+
+        let info = reject(ParseRequest {
+            unsigned_payload: "00".repeat(32),
+            chain: Chain::Solana as i32,
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(info.reason, "PAYLOAD_TOO_LARGE");
+        assert_eq!(info.metadata["max_size"], "32");
+
+        let info = reject(ParseRequest {
+            chain: Chain::Solana as i32,
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(info.reason, "EMPTY_PAYLOAD");
+
+        let info = reject(ParseRequest {
+            unsigned_payload: "not base64!".to_string(),
+            chain: Chain::Solana as i32,
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(info.reason, "UNSUPPORTED_ENCODING");
+        assert_eq!(info.metadata["chain"], "CHAIN_SOLANA");
+
+        let info = reject(ParseRequest {
+            unsigned_payload: "00".to_string(),
+            chain: Chain::Custom as i32,
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(info.reason, "MISSING_CUSTOM_CHAIN");
+    }
+
+    integration::Builder::new()
+        .arg(Binary::Host, "--max-payload-size")
+        .arg(Binary::Host, "32")
+        .execute(test)
+        .await
+}
+
+#[tokio::test]
+async fn routes_parse_requests_to_healthy_backends() {
+    async fn test(test_args: TestArgs) {
+        let mut client = test_args.parser_client.unwrap();
+
+        // `blue` has no enclave, so its route fails over to the default backend
+        let parse_response = client
+            .parse(tonic::Request::new(ParseRequest {
+                unsigned_payload: "unsignedpayload".to_string(),
+                chain: Chain::Unspecified as i32,
+                ..Default::default()
+            }))
+            .await;
+        assert!(
+            parse_response
+                .unwrap()
+                .into_inner()
+                .parsed_transaction
+                .is_some()
+        );
+
+        // ... and a route without another backend is unavailable
+        let parse_error = client
+            .parse(tonic::Request::new(ParseRequest {
+                unsigned_payload: "00".to_string(),
+                chain: Chain::Ethereum as i32,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(parse_error.code(), Code::Unavailable);
+        assert!(parse_error.message().contains("no healthy enclave backend"));
+    }
+
+    let dir = std::env::temp_dir();
+    let config = dir.join(format!("parser_routes_{}.json", std::process::id()));
+    std::fs::write(
+        &config,
+        r#"{"Routes":{"CHAIN_UNSPECIFIED":["blue","default"],"CHAIN_ETHEREUM":["blue"]}}"#,
+    )
+    .unwrap();
+    let blue = dir.join(format!("parser_routes_{}_blue.sock", std::process::id()));
+
+    integration::Builder::new()
+        .arg(Binary::Host, "--config")
+        .arg(Binary::Host, config.to_str().unwrap())
+        .arg(Binary::Host, "--enclave-backends")
+        .arg(Binary::Host, format!("blue={}", blue.display()))
+        .execute(test)
+        .await;
+
+    let _ = std::fs::remove_file(config);
+}
+
 #[tokio::test]
 async fn parser_health_check() {
     async fn test(test_args: TestArgs) {
@@ -157,6 +293,303 @@ async fn parser_health_check() {
     integration::Builder::new().execute(test).await
 }
 
+#[tokio::test]
+async fn parser_get_attestation() {
+    async fn test(test_args: TestArgs) {
+        let response = test_args
+            .parser_client
+            .unwrap()
+            .get_attestation(tonic::Request::new(GetAttestationRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // The simulated enclave attests with a mock document and boots without a manifest
+        assert_eq!(
+            response.attestation_document,
+            borsh::to_vec(&"MOCK_DOCUMENT".to_string()).unwrap()
+        );
+        assert!(response.manifest_digest.is_empty());
+        let names: Vec<&str> = response
+            .parser_versions
+            .iter()
+            .map(|version| version.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "parser_app",
+                "visualsign",
+                "visualsign-bitcoin",
+                "visualsign-cosmos",
+                "visualsign-ethereum",
+                "visualsign-solana",
+                "visualsign-sui",
+                "visualsign-tron",
+                "visualsign-unspecified",
+            ]
+        );
+        assert!(
+            response
+                .parser_versions
+                .iter()
+                .all(|version| !version.version.is_empty())
+        );
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_get_attestation_with_manifest() {
+    use qos_core::protocol::services::boot::{Manifest, ManifestEnvelope};
+
+    let (patch_set, _) = integration::make_patch_set(3, 2);
+    let manifest = Manifest {
+        patch_set,
+        ..Default::default()
+    };
+    let digest = qos_hex::encode(&qos_crypto::sha_256(&borsh::to_vec(&manifest).unwrap()));
+
+    let test = move |test_args: TestArgs| {
+        let digest = digest.clone();
+        async move {
+            let response = test_args
+                .parser_client
+                .unwrap()
+                .get_attestation(tonic::Request::new(GetAttestationRequest {}))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(response.manifest_digest, digest);
+        }
+    };
+
+    integration::Builder::new()
+        .manifest_envelope(ManifestEnvelope {
+            manifest,
+            ..Default::default()
+        })
+        .execute(test)
+        .await
+}
+
+#[tokio::test]
+async fn parser_list_chains() {
+    async fn test(test_args: TestArgs) {
+        let response = test_args
+            .parser_client
+            .unwrap()
+            .list_chains(tonic::Request::new(ListChainsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let names: Vec<&str> = response
+            .chains
+            .iter()
+            .map(|chain| chain.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Bitcoin",
+                "Cosmos",
+                "Ethereum",
+                "EthereumPersonalSign",
+                "Lightning",
+                "Solana",
+                "SolanaOffchainMessage",
+                "Sui",
+                "Tron",
+                "Unspecified",
+            ]
+        );
+
+        let ethereum = &response.chains[2];
+        assert_eq!(ethereum.chain, Chain::Ethereum as i32);
+        assert_eq!(
+            ethereum.parser.as_ref().map(|parser| parser.name.as_str()),
+            Some("visualsign-ethereum")
+        );
+        assert!(
+            ethereum
+                .decoders
+                .iter()
+                .any(|decoder| decoder == "UniswapUniversalRouter")
+        );
+        // Chains registered by name only are custom chains
+        assert_eq!(response.chains[1].chain, Chain::Custom as i32);
+        assert!(!response.chains[5].decoders.is_empty());
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_echoes_request_ids() {
+    async fn test(test_args: TestArgs) {
+        let mut client = test_args.parser_client.unwrap();
+
+        let mut request = tonic::Request::new(ParseRequest {
+            unsigned_payload: "unsignedpayload".to_string(),
+            chain: Chain::Unspecified as i32,
+            ..Default::default()
+        });
+        request
+            .metadata_mut()
+            .insert("x-request-id", "trace-1234".parse().unwrap());
+        let response = client.parse(request).await.unwrap();
+        assert_eq!(
+            response.metadata().get("x-request-id").unwrap(),
+            "trace-1234"
+        );
+
+        // Without one the host makes up an id, and returns it on errors too
+        let error = client
+            .parse(tonic::Request::new(ParseRequest::default()))
+            .await
+            .unwrap_err();
+        assert!(!error.metadata().get("x-request-id").unwrap().is_empty());
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_get_capabilities() {
+    async fn test(test_args: TestArgs) {
+        let response = test_args
+            .parser_client
+            .unwrap()
+            .get_capabilities(tonic::Request::new(GetCapabilitiesRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.payload_versions, ["0"]);
+        assert_eq!(response.charset_policies, ["Ascii", "Unicode"]);
+        assert_eq!(
+            response.payload_compressions,
+            [PayloadCompression::Zstd as i32]
+        );
+
+        let ethereum = response
+            .chains
+            .iter()
+            .find(|chain| chain.name == "Ethereum")
+            .unwrap();
+        assert_eq!(ethereum.chain, Chain::Ethereum as i32);
+        assert_eq!(ethereum.features.get("eip1559"), Some(&true));
+        assert_eq!(ethereum.features.get("eip4844"), Some(&false));
+        // Every chain but the catch-all reports its features
+        assert!(
+            response
+                .chains
+                .iter()
+                .filter(|chain| chain.name != "Unspecified")
+                .all(|chain| !chain.features.is_empty())
+        );
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_compresses_payloads_when_accepted() {
+    async fn test(test_args: TestArgs) {
+        let mut client = test_args.parser_client.unwrap();
+        let parse_request = ParseRequest {
+            unsigned_payload: "unsignedpayload".to_string(),
+            chain: Chain::Unspecified as i32,
+            ..Default::default()
+        };
+
+        let plain = client
+            .parse(tonic::Request::new(parse_request.clone()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(plain.compression, PayloadCompression::None as i32);
+
+        let compressed = client
+            .parse(tonic::Request::new(ParseRequest {
+                accept_compression: PayloadCompression::Zstd as i32,
+                ..parse_request
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(compressed.compression, PayloadCompression::Zstd as i32);
+        let parsed_transaction = compressed.parsed_transaction.as_ref().unwrap();
+        assert!(
+            parsed_transaction
+                .payload
+                .as_ref()
+                .unwrap()
+                .signable_payload
+                .is_empty()
+        );
+
+        // Compression is deterministic and leaves the signed payload as it was
+        let mut restored = compressed.clone();
+        parser_client::decompress_payload(&mut restored).unwrap();
+        assert_eq!(restored.parsed_transaction, plain.parsed_transaction);
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_parse_bundle() {
+    async fn test(test_args: TestArgs) {
+        let mut client = test_args.parser_client.unwrap();
+        let transaction = |unsigned_payload: &str| ParseRequest {
+            unsigned_payload: unsigned_payload.to_string(),
+            chain: Chain::Unspecified as i32,
+            ..Default::default()
+        };
+
+        let response = client
+            .parse_bundle(tonic::Request::new(ParseBundleRequest {
+                title: "Approve and swap".to_string(),
+                transactions: vec![transaction("approve"), transaction("swap")],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let parsed_bundle = response.parsed_bundle.unwrap();
+        assert!(parsed_bundle.signature.is_some());
+        let signable_bundle = parsed_bundle.payload.unwrap().signable_bundle;
+        validate_safe_charset(&signable_bundle);
+
+        // The bundle decodes only if its digest matches, and keeps the request order
+        let bundle =
+            visualsign::bundle::SignablePayloadBundle::from_json(&signable_bundle).unwrap();
+        assert_eq!(bundle.title, "Approve and swap");
+        assert_eq!(bundle.payloads.len(), 2);
+        assert_eq!(bundle.to_json().unwrap(), signable_bundle);
+
+        let status = client
+            .parse_bundle(tonic::Request::new(ParseBundleRequest {
+                title: "Approve and swap".to_string(),
+                transactions: vec![transaction("approve"), transaction("")],
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().starts_with("transactions[1]: "));
+
+        let status = client
+            .parse_bundle(tonic::Request::new(ParseBundleRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
 #[tokio::test]
 async fn parser_k8_health() {
     async fn test(test_args: TestArgs) {
@@ -166,6 +599,191 @@ async fn parser_k8_health() {
     integration::Builder::new().execute(test).await
 }
 
+#[tokio::test]
+async fn parser_k8_health_in_process() {
+    async fn test(test_args: TestArgs) {
+        integration::k8_health(test_args).await;
+    }
+
+    integration::Builder::new().in_process().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_two_hosts_share_one_enclave() {
+    async fn test(test_args: TestArgs) {
+        assert_eq!(test_args.parser_clients.len(), 2);
+        for mut client in test_args.parser_clients {
+            let response = client
+                .list_chains(tonic::Request::new(ListChainsRequest {}))
+                .await
+                .unwrap()
+                .into_inner();
+            assert!(!response.chains.is_empty());
+        }
+    }
+
+    integration::Builder::new().hosts(2).execute(test).await
+}
+
+#[tokio::test]
+#[ignore = "needs docker and the images built by `make non-oci-docker-images`"]
+async fn parser_k8_health_in_containers() {
+    async fn test(test_args: TestArgs) {
+        integration::k8_health(test_args).await;
+    }
+
+    integration::Builder::new()
+        .containers(integration::container::ContainerImages::default())
+        .execute(test)
+        .await
+}
+
+#[tokio::test]
+async fn parser_host_not_ready_without_enclave() {
+    async fn test(test_args: TestArgs) {
+        use generated::grpc::health::v1::{
+            HealthCheckRequest, health_check_response::ServingStatus,
+        };
+        let mut client = test_args.k8_health_client.unwrap();
+
+        let request = tonic::Request::new(HealthCheckRequest {
+            service: health_check::READINESS.to_string(),
+        });
+        let response = client.check(request).await.unwrap().into_inner();
+        assert_eq!(response.status, ServingStatus::NotServing as i32);
+    }
+
+    integration::Builder::new()
+        .only(&[Binary::Host])
+        .execute(test)
+        .await
+}
+
+#[tokio::test]
+async fn parser_recovers_from_app_restart() {
+    async fn test(test_args: TestArgs) {
+        let chaos = test_args.chaos.unwrap();
+        let mut client = test_args.health_check_client.unwrap();
+
+        chaos.kill(Binary::App);
+        let error = client
+            .app_health(tonic::Request::new(AppHealthRequest {}))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::Internal);
+        assert!(error.message().contains("AppClientRecvTimeout"));
+
+        chaos.restart(Binary::App).await;
+        let response = client
+            .app_health(tonic::Request::new(AppHealthRequest {}))
+            .await;
+        assert_eq!(
+            response.unwrap().into_inner(),
+            AppHealthResponse { code: 200 }
+        );
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_client_retries_while_host_restarts() {
+    async fn test(test_args: TestArgs) {
+        let chaos = test_args.chaos.unwrap();
+        let addr = test_args.host_addr.unwrap();
+        let config = ClientConfig {
+            retry: RetryPolicy {
+                max_attempts: 100,
+                initial_backoff: Duration::from_millis(50),
+                max_backoff: Duration::from_millis(200),
+            },
+            ..ClientConfig::default()
+        };
+        let client = ParserClient::connect(addr, config).await.unwrap();
+
+        chaos.kill(Binary::Host);
+        let (response, ()) = tokio::join!(
+            client.list_chains(ListChainsRequest {}),
+            chaos.restart(Binary::Host)
+        );
+        assert!(!response.unwrap().chains.is_empty());
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_times_out_on_slow_enclave() {
+    async fn test(test_args: TestArgs) {
+        let response = test_args
+            .health_check_client
+            .unwrap()
+            .app_health(tonic::Request::new(AppHealthRequest {}))
+            .await;
+        assert!(response.is_err());
+    }
+
+    integration::Builder::new()
+        .delay_enclave_responses(host_primitives::enclave_client_timeout() + Duration::from_secs(1))
+        .execute(test)
+        .await
+}
+
+#[tokio::test]
+async fn parser_host_not_ready_with_wedged_enclave() {
+    async fn test(test_args: TestArgs) {
+        use generated::grpc::health::v1::{
+            HealthCheckRequest, health_check_response::ServingStatus,
+        };
+        let mut k8_client = test_args.k8_health_client.unwrap();
+
+        // The app still answers, only slower than the readiness probe allows
+        let response = test_args
+            .health_check_client
+            .unwrap()
+            .app_health(tonic::Request::new(AppHealthRequest {}))
+            .await;
+        assert_eq!(
+            response.unwrap().into_inner(),
+            AppHealthResponse { code: 200 }
+        );
+
+        let request = tonic::Request::new(HealthCheckRequest {
+            service: health_check::READINESS.to_string(),
+        });
+        let response = k8_client.check(request).await.unwrap().into_inner();
+        assert_eq!(response.status, ServingStatus::NotServing as i32);
+    }
+
+    integration::Builder::new()
+        .delay_enclave_responses(Duration::from_secs(2))
+        .execute(test)
+        .await
+}
+
+#[tokio::test]
+async fn parser_rejects_truncated_app_responses() {
+    async fn test(test_args: TestArgs) {
+        let error = test_args
+            .health_check_client
+            .unwrap()
+            .app_health(tonic::Request::new(AppHealthRequest {}))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), Code::Internal);
+        assert!(
+            error
+                .message()
+                .contains("Failed to deserialize enclave response")
+        );
+    }
+
+    integration::Builder::new()
+        .truncate_proxy_responses(1)
+        .execute(test)
+        .await
+}
+
 // This is deliberately using a more "high level test" that only handles the native transfer - any chain specific logic is handled by the tests in chain_parsers
 // This allows us to focus on the parser's ability to handle different chain types without getting bogged down in chain-specific libraries
 #[tokio::test]
@@ -186,6 +804,7 @@ async fn parser_solana_native_transfer_e2e() {
             unsigned_payload: solana_tx,
             chain: Chain::Solana as i32,
             chain_metadata: None,
+            ..Default::default()
         };
 
         let parse_response = test_args
@@ -209,6 +828,14 @@ async fn parser_solana_native_transfer_e2e() {
                     },
                     "Type": "text_v2"
                 },
+                {
+                    "AddressV2": {
+                        "Address": "8jSCrV9xWkmMRSyf6xH3phL7SretagdqP3LRqkUYUp73"
+                    },
+                    "FallbackText": "8jSCrV9xWkmMRSyf6xH3phL7SretagdqP3LRqkUYUp73",
+                    "Label": "From",
+                    "Type": "address_v2"
+                },
                 {
                     "FallbackText": "Transfer 1: From HdD2N8HDzNEM6vwAq5mBLiUbgy1P9wyJfbASt93ndDsD To 8jSCrV9xWkmMRSyf6xH3phL7SretagdqP3LRqkUYUp73 For 1000000000",
                     "Label": "Transfer 1",
@@ -271,6 +898,67 @@ async fn parser_solana_native_transfer_e2e() {
                     },
                     "Type": "preview_layout"
                 },
+                {
+                    "FallbackText": "Recent Blockhash: DwsJaUU1f2nC3rWb1osFWpqU4VpwmzdSTpQ1vAiGYicD",
+                    "Label": "Transaction Lifetime",
+                    "PreviewLayout": {
+                        "Condensed": {
+                            "Fields": [
+                                {
+                                    "FallbackText": "Recent Blockhash",
+                                    "Label": "Lifetime",
+                                    "TextV2": {
+                                        "Text": "Recent Blockhash"
+                                    },
+                                    "Type": "text_v2"
+                                }
+                            ]
+                        },
+                        "Expanded": {
+                            "Fields": [
+                                {
+                                    "FallbackText": "Recent Blockhash",
+                                    "Label": "Lifetime",
+                                    "TextV2": {
+                                        "Text": "Recent Blockhash"
+                                    },
+                                    "Type": "text_v2"
+                                },
+                                {
+                                    "FallbackText": "DwsJaUU1f2nC3rWb1osFWpqU4VpwmzdSTpQ1vAiGYicD",
+                                    "Label": "Recent Blockhash",
+                                    "TextV2": {
+                                        "Text": "DwsJaUU1f2nC3rWb1osFWpqU4VpwmzdSTpQ1vAiGYicD"
+                                    },
+                                    "Type": "text_v2"
+                                },
+                                {
+                                    "FallbackText": "About 150 slots (roughly a minute) after the blockhash was produced",
+                                    "Label": "Expiration",
+                                    "TextV2": {
+                                        "Text": "About 150 slots (roughly a minute) after the blockhash was produced"
+                                    },
+                                    "Type": "text_v2"
+                                },
+                                {
+                                    "FallbackText": "Recent blockhash DwsJaUU1f2nC3rWb1osFWpqU4VpwmzdSTpQ1vAiGYicD",
+                                    "Label": "Replay Protection",
+                                    "TextV2": {
+                                        "Text": "Recent blockhash DwsJaUU1f2nC3rWb1osFWpqU4VpwmzdSTpQ1vAiGYicD"
+                                    },
+                                    "Type": "text_v2"
+                                }
+                            ]
+                        },
+                        "Subtitle": {
+                            "Text": ""
+                        },
+                        "Title": {
+                            "Text": "Recent Blockhash"
+                        }
+                    },
+                    "Type": "preview_layout"
+                },
                 {
                     "FallbackText": "8jSCrV9xWkmMRSyf6xH3phL7SretagdqP3LRqkUYUp73[SW], HdD2N8HDzNEM6vwAq5mBLiUbgy1P9wyJfbASt93ndDsD[SW], 11111111111111111111111111111111[R]",
                     "Label": "Accounts",
@@ -367,6 +1055,7 @@ async fn parser_ethereum_native_transfer_e2e() {
             unsigned_payload: ethereum_tx_hex.to_string(),
             chain: Chain::Ethereum as i32,
             chain_metadata: None,
+            ..Default::default()
         };
 
         let parse_response = test_args
@@ -390,6 +1079,96 @@ async fn parser_ethereum_native_transfer_e2e() {
             },
             "Type": "text_v2"
           },
+          {
+            "FallbackText": "Send 1 ETH. Max cost: 1.00042 ETH",
+            "Label": "Transaction Summary",
+            "PreviewLayout": {
+              "Title": {
+                "Text": "Send 1 ETH"
+              },
+              "Subtitle": {
+                "Text": "Max cost: 1.00042 ETH"
+              },
+              "Condensed": {
+                "Fields": [
+                {
+                  "FallbackText": "1 ETH",
+                  "Label": "You Send",
+                  "AmountV2": {
+                    "Amount": "1",
+                    "Abbreviation": "ETH"
+                  },
+                  "Type": "amount_v2"
+                },
+                {
+                  "FallbackText": "1.00042 ETH",
+                  "Label": "Max Total Cost",
+                  "AmountV2": {
+                    "Amount": "1.00042",
+                    "Abbreviation": "ETH"
+                  },
+                  "Type": "amount_v2"
+                }
+                ]
+              },
+              "Expanded": {
+                "Fields": [
+                {
+                  "FallbackText": "1 ETH",
+                  "Label": "You Send",
+                  "AmountV2": {
+                    "Amount": "1",
+                    "Abbreviation": "ETH"
+                  },
+                  "Type": "amount_v2"
+                },
+                {
+                  "FallbackText": "0.00042 ETH",
+                  "Label": "Max Network Fee",
+                  "AmountV2": {
+                    "Amount": "0.00042",
+                    "Abbreviation": "ETH"
+                  },
+                  "Type": "amount_v2"
+                },
+                {
+                  "FallbackText": "1.00042 ETH",
+                  "Label": "Max Total Cost",
+                  "AmountV2": {
+                    "Amount": "1.00042",
+                    "Abbreviation": "ETH"
+                  },
+                  "Type": "amount_v2"
+                },
+                {
+                  "FallbackText": "Max cost is the ETH value plus gas limit times max fee per gas; tokens are not included",
+                  "Label": "Note",
+                  "TextV2": {
+                    "Text": "Max cost is the ETH value plus gas limit times max fee per gas; tokens are not included"
+                  },
+                  "Type": "text_v2"
+                }
+                ]
+              }
+            },
+            "Type": "preview_layout"
+          },
+          {
+            "FallbackText": "Chain ID 37",
+            "Label": "Replay Protection",
+            "TextV2": {
+            "Text": "Chain ID 37"
+            },
+            "Type": "text_v2"
+          },
+          {
+            "FallbackText": "Destination",
+            "Label": "Destination",
+            "Divider": {
+              "Style": ""
+            },
+            "Type": "divider"
+          },
           {
             "FallbackText": "0x3535353535353535353535353535353535353535",
             "Label": "To",
@@ -400,6 +1179,14 @@ async fn parser_ethereum_native_transfer_e2e() {
             },
             "Type": "address_v2"
           },
+          {
+            "FallbackText": "Amounts",
+            "Label": "Amounts",
+            "Divider": {
+              "Style": ""
+            },
+            "Type": "divider"
+          },
           {
             "FallbackText": "1 ETH",
             "Label": "Value",
@@ -409,6 +1196,14 @@ async fn parser_ethereum_native_transfer_e2e() {
             },
             "Type": "amount_v2"
           },
+          {
+            "FallbackText": "Fees",
+            "Label": "Fees",
+            "Divider": {
+              "Style": ""
+            },
+            "Type": "divider"
+          },
           {
             "FallbackText": "21000",
             "Label": "Gas Limit",
@@ -450,6 +1245,93 @@ async fn parser_ethereum_native_transfer_e2e() {
     integration::Builder::new().execute(test).await
 }
 
+#[tokio::test]
+async fn parser_ethereum_field_provenance() {
+    async fn test(test_args: TestArgs) {
+        let ethereum_tx_hex = "0xf86c808504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+        let parse_request = ParseRequest {
+            unsigned_payload: ethereum_tx_hex.to_string(),
+            chain: Chain::Ethereum as i32,
+            options: r#"{"IncludeProvenance":true}"#.to_string(),
+            ..Default::default()
+        };
+
+        let parse_response = test_args
+            .parser_client
+            .unwrap()
+            .parse(tonic::Request::new(parse_request))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let signable_payload: serde_json::Value = serde_json::from_str(
+            &parse_response
+                .parsed_transaction
+                .unwrap()
+                .payload
+                .unwrap()
+                .signable_payload,
+        )
+        .unwrap();
+        let nonce_index = signable_payload["Fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .position(|field| field["Label"] == "Nonce")
+            .unwrap();
+
+        // The nonce is the first item of the list, after its two-byte header
+        let provenance: serde_json::Value =
+            serde_json::from_str(&parse_response.provenance).unwrap();
+        assert_eq!(
+            provenance[format!("Fields[{nonce_index}]")],
+            serde_json::json!({"Bytes": {"Start": 2, "End": 3}})
+        );
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
+#[tokio::test]
+async fn parser_verify_lossless_rejects_trailing_bytes() {
+    async fn test(test_args: TestArgs) {
+        use base64::Engine as _;
+
+        let solana_transfer_message = "AgABA3Lgs31rdjnEG5FRyrm2uAi4f+erGdyJl0UtJyMMLGzC9wF+t3qhmhpj3vI369n5Ef5xRLms/Vn8J/Lc7bmoIkAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMBafBISARibJ+I25KpHkjLe53ZrqQcLWGy8n97yWD7mAQICAQAMAgAAAADKmjsAAAAA";
+        let solana_tx = visualsign_solana::utils::create_transaction_with_empty_signatures(
+            solana_transfer_message,
+        );
+        let mut bytes = base64::engine::general_purpose::STANDARD
+            .decode(&solana_tx)
+            .unwrap();
+        bytes.extend_from_slice(&[0xde, 0xad]);
+
+        let parse_request = ParseRequest {
+            unsigned_payload: base64::engine::general_purpose::STANDARD.encode(&bytes),
+            chain: Chain::Solana as i32,
+            options: r#"{"VerifyLossless":true}"#.to_string(),
+            ..Default::default()
+        };
+
+        let parse_error = test_args
+            .parser_client
+            .unwrap()
+            .parse(tonic::Request::new(parse_request))
+            .await
+            .unwrap_err();
+
+        assert_eq!(parse_error.code(), Code::InvalidArgument);
+        let status = Status::decode(parse_error.details()).unwrap();
+        let info = ErrorInfo::decode(status.details[0].value.as_slice()).unwrap();
+        assert_eq!(info.reason, "ENCODING");
+        assert_eq!(info.metadata["chain"], "Solana");
+        assert_eq!(info.metadata["detail"], "ReencodingMismatch");
+    }
+
+    integration::Builder::new().execute(test).await
+}
+
 #[tokio::test]
 async fn parser_charset_validation_all_chains() {
     async fn test(test_args: TestArgs) {
@@ -478,6 +1360,7 @@ async fn parser_charset_validation_all_chains() {
                 unsigned_payload: transaction.to_string(),
                 chain: chain as i32,
                 chain_metadata: None,
+                ..Default::default()
             };
 
             let parse_response = test_args
@@ -535,6 +1418,7 @@ async fn parser_sui_native_transfer_e2e() {
             unsigned_payload: sui_tx_b64.to_string(),
             chain: Chain::Sui as i32,
             chain_metadata: None,
+            ..Default::default()
         };
 
         let parse_response = test_args
@@ -557,6 +1441,94 @@ async fn parser_sui_native_transfer_e2e() {
                 "Text": "Sui Network"
               }
             },
+            {
+              "Type": "preview_layout",
+              "FallbackText": "Transaction Summary: 1 SUI out",
+              "Label": "Transaction Summary",
+              "PreviewLayout": {
+                "Title": {
+                  "Text": "Transaction Summary: 1 SUI out"
+                },
+                "Subtitle": {
+                  "Text": "1 object(s) transferred, 0 item(s) received"
+                },
+                "Condensed": {
+                  "Fields": [
+                    {
+                      "Type": "amount_v2",
+                      "FallbackText": "1 SUI",
+                      "Label": "SUI Out",
+                      "AmountV2": {
+                        "Amount": "1",
+                        "Abbreviation": "SUI"
+                      }
+                    },
+                    {
+                      "Type": "number",
+                      "FallbackText": "1",
+                      "Label": "Objects Transferred",
+                      "Number": {
+                        "Number": "1"
+                      }
+                    }
+                  ]
+                },
+                "Expanded": {
+                  "Fields": [
+                    {
+                      "Type": "amount_v2",
+                      "FallbackText": "1 SUI",
+                      "Label": "SUI Out",
+                      "AmountV2": {
+                        "Amount": "1",
+                        "Abbreviation": "SUI"
+                      }
+                    },
+                    {
+                      "Type": "amount_v2",
+                      "FallbackText": "1000000000 MIST",
+                      "Label": "SUI Out (MIST)",
+                      "AmountV2": {
+                        "Amount": "1000000000",
+                        "Abbreviation": "MIST"
+                      }
+                    },
+                    {
+                      "Type": "text_v2",
+                      "FallbackText": "1 SUI to 0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890",
+                      "Label": "Transfer 1",
+                      "TextV2": {
+                        "Text": "1 SUI to 0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890"
+                      }
+                    },
+                    {
+                      "Type": "text_v2",
+                      "FallbackText": "Native Transfer",
+                      "Label": "Protocols",
+                      "TextV2": {
+                        "Text": "Native Transfer"
+                      }
+                    },
+                    {
+                      "Type": "text_v2",
+                      "FallbackText": "Gas fees are not included; see Transaction Details",
+                      "Label": "Note",
+                      "TextV2": {
+                        "Text": "Gas fees are not included; see Transaction Details"
+                      }
+                    }
+                  ]
+                }
+              }
+            },
+            {
+              "Type": "address_v2",
+              "FallbackText": "0xd6e92e002e26c3afb2088001c1b5820b64f2bc351bfda5a2342acbf9f5c1cac2",
+              "Label": "From",
+              "AddressV2": {
+                "Address": "0xd6e92e002e26c3afb2088001c1b5820b64f2bc351bfda5a2342acbf9f5c1cac2"
+              }
+            },
             {
               "Type": "preview_layout",
               "FallbackText": "Transfer: 1000000000 MIST (1 SUI)",
@@ -687,6 +1659,31 @@ async fn parser_sui_native_transfer_e2e() {
                         "Abbreviation": "MIST"
                       }
                     },
+                    {
+                      "Type": "amount_v2",
+                      "FallbackText": "0.005 SUI",
+                      "Label": "Max Total Cost",
+                      "AmountV2": {
+                        "Amount": "0.005",
+                        "Abbreviation": "SUI"
+                      }
+                    },
+                    {
+                      "Type": "text_v2",
+                      "FallbackText": "Never",
+                      "Label": "Expiration",
+                      "TextV2": {
+                        "Text": "Never"
+                      }
+                    },
+                    {
+                      "Type": "text_v2",
+                      "FallbackText": "Gas coin versions; each can be spent only once",
+                      "Label": "Replay Protection",
+                      "TextV2": {
+                        "Text": "Gas coin versions; each can be spent only once"
+                      }
+                    },
                     {
                       "Type": "text_v2",
                       "FallbackText": "0000020020abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890000800ca9a3b00000000020200010101000101020000010000d6e92e002e26c3afb2088001c1b5820b64f2bc351bfda5a2342acbf9f5c1cac201cb485ef80a0baf9079a621c42c8b0cb65ce9986e1c81fd16ee88038f004a5aed1b9ad417000000002063805f82e957f1589a7a62346b37cb5aec0f0c1ff4ed74c7949d2bbbd73fef50d6e92e002e26c3afb2088001c1b5820b64f2bc351bfda5a2342acbf9f5c1cac2e803000000000000404b4c000000000000",
@@ -195,6 +195,7 @@ fn test_ethereum_abi_with_secp256k1_signature() {
         chain_metadata: Some(ChainMetadata {
             metadata: Some(chain_metadata::Metadata::Ethereum(ethereum_metadata)),
         }),
+        ..Default::default()
     };
 
     // Verify the request was created correctly
@@ -272,6 +273,7 @@ fn test_solana_idl_with_ed25519_signature() {
         chain_metadata: Some(ChainMetadata {
             metadata: Some(chain_metadata::Metadata::Solana(solana_metadata)),
         }),
+        ..Default::default()
     };
 
     // Verify the request was created correctly
@@ -346,6 +348,7 @@ fn test_signature_tampering_detection() {
         chain_metadata: Some(ChainMetadata {
             metadata: Some(chain_metadata::Metadata::Ethereum(ethereum_metadata)),
         }),
+        ..Default::default()
     };
 
     // Now verify with tampered ABI
@@ -5,6 +5,7 @@ use generated::parser::Chain as ProtoChain;
 
 pub(crate) fn proto_to_registry(proto: ProtoChain) -> RegistryChain {
     match proto {
+        ProtoChain::Bitcoin => RegistryChain::Bitcoin,
         ProtoChain::Solana => RegistryChain::Solana,
         ProtoChain::Ethereum => RegistryChain::Ethereum,
         ProtoChain::Sui => RegistryChain::Sui,
@@ -14,35 +15,42 @@ pub(crate) fn proto_to_registry(proto: ProtoChain) -> RegistryChain {
     }
 }
 
+/// Chains without a value of their own in the proto enum map to `Custom`, to be named by
+/// `custom_chain`
+pub(crate) fn registry_to_proto(registry: &RegistryChain) -> ProtoChain {
+    match registry {
+        RegistryChain::Unspecified => ProtoChain::Unspecified,
+        RegistryChain::Bitcoin => ProtoChain::Bitcoin,
+        RegistryChain::Solana => ProtoChain::Solana,
+        RegistryChain::Ethereum => ProtoChain::Ethereum,
+        RegistryChain::Sui => ProtoChain::Sui,
+        RegistryChain::Tron => ProtoChain::Tron,
+        _ => ProtoChain::Custom,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    pub(crate) fn registry_to_proto(registry: &RegistryChain) -> ProtoChain {
-        match registry {
-            RegistryChain::Unspecified => ProtoChain::Unspecified,
-            RegistryChain::Solana => ProtoChain::Solana,
-            RegistryChain::Ethereum => ProtoChain::Ethereum,
-            RegistryChain::Sui => ProtoChain::Sui,
-            _ => ProtoChain::Custom,
-        }
-    }
-
     #[test]
     fn test_conversions() {
         // Test supported chains round-trip
         for (proto, registry) in [
+            (ProtoChain::Bitcoin, RegistryChain::Bitcoin),
             (ProtoChain::Solana, RegistryChain::Solana),
             (ProtoChain::Ethereum, RegistryChain::Ethereum),
             (ProtoChain::Sui, RegistryChain::Sui),
+            (ProtoChain::Tron, RegistryChain::Tron),
         ] {
             assert_eq!(proto_to_registry(proto), registry);
             assert_eq!(registry_to_proto(&registry), proto);
         }
 
-        // Test unsupported map to unspecified
+        // Test chains without a proto value map to custom
+        assert_eq!(registry_to_proto(&RegistryChain::Aptos), ProtoChain::Custom);
         assert_eq!(
-            registry_to_proto(&RegistryChain::Bitcoin),
+            registry_to_proto(&RegistryChain::Custom("Cosmos".into())),
             ProtoChain::Custom
         );
         assert_eq!(
@@ -7,6 +7,7 @@ use qos_core::{
     parser::{GetParserForOptions, OptionsParser, Parser, Token},
     server::SocketServer,
 };
+use tracing_subscriber::{EnvFilter, fmt::format::FmtSpan};
 
 /// CLI options for starting up the app server.
 #[derive(Default, Clone, Debug, PartialEq)]
@@ -80,6 +81,7 @@ impl Cli {
         } else if opts.parsed.help() {
             println!("{}", opts.parsed.info());
         } else {
+            init_tracing();
             let processor =
                 crate::service::Processor::new(EphemeralKeyHandle::new(opts.ephemeral_file()));
 
@@ -98,3 +100,14 @@ impl Cli {
         }
     }
 }
+
+// Logs parse spans when they close, filtered by `RUST_LOG` (errors only by default)
+fn init_tracing() {
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_span_events(FmtSpan::CLOSE)
+        .finish();
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        eprintln!("a global tracing subscriber is already set");
+    }
+}
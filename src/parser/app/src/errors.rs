@@ -1,13 +1,25 @@
 //! standardized errors for enclaves. We use gRPC in and out of enclaves, so we model a gRPC error here.
-use generated::google::rpc::Code;
+use std::collections::HashMap;
+
+use generated::google::rpc::{Code, ErrorInfo, Status};
+use generated::prost::Message;
+use generated::prost_types::Any;
+use visualsign::errors::{ParserErrorKind, VisualSignError};
+
+/// `google.rpc.ErrorInfo` domain for errors raised while parsing a transaction
+pub const PARSER_ERROR_DOMAIN: &str = "visualsign.parser";
+
+const ERROR_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.ErrorInfo";
 
 /// GRPC error type to use in enclave applications
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct GrpcError {
     /// the gRPC code
     pub code: Code,
     /// the gRPC message
     pub message: String,
+    /// machine-readable details, returned as `google.rpc.Status` details
+    pub details: Vec<Any>,
 }
 
 impl GrpcError {
@@ -17,6 +29,7 @@ impl GrpcError {
         GrpcError {
             code,
             message: message.to_string(),
+            details: Vec::new(),
         }
     }
 
@@ -25,4 +38,56 @@ impl GrpcError {
     pub fn internal(message: &str) -> Self {
         Self::new(Code::Internal, message)
     }
+
+    /// attaches a `google.rpc.ErrorInfo` detail
+    #[must_use]
+    pub fn with_error_info(mut self, info: &ErrorInfo) -> Self {
+        self.details.push(Any {
+            type_url: ERROR_INFO_TYPE_URL.to_string(),
+            value: info.encode_to_vec(),
+        });
+        self
+    }
+
+    /// classifies a parser failure for `chain`
+    ///
+    /// The error kind becomes the `ErrorInfo` reason (e.g. `TRUNCATED`); its metadata names the
+    /// chain and, when the parser reported them, its own error variant and the byte offset where
+    /// decoding failed.
+    #[must_use]
+    pub fn from_visualsign_error(error: &VisualSignError, chain: &str) -> Self {
+        let kind = error.kind();
+        let code = match kind {
+            ParserErrorKind::Encoding
+            | ParserErrorKind::Truncated
+            | ParserErrorKind::UnsupportedVariant => Code::InvalidArgument,
+            ParserErrorKind::UnsupportedProtocol => Code::Unimplemented,
+            ParserErrorKind::InternalInvariant => Code::Internal,
+        };
+
+        let mut metadata = HashMap::from([("chain".to_string(), chain.to_string())]);
+        if let Some(detail) = error.parser_error() {
+            metadata.insert("chain".to_string(), detail.chain.clone());
+            metadata.insert("detail".to_string(), detail.reason.clone());
+            if let Some(offset) = detail.offset {
+                metadata.insert("offset".to_string(), offset.to_string());
+            }
+        }
+        let info = ErrorInfo {
+            reason: kind.code().to_string(),
+            domain: PARSER_ERROR_DOMAIN.to_string(),
+            metadata,
+        };
+        Self::new(code, &error.to_string()).with_error_info(&info)
+    }
+}
+
+impl From<GrpcError> for Status {
+    fn from(error: GrpcError) -> Self {
+        Status {
+            code: error.code as i32,
+            message: error.message,
+            details: error.details,
+        }
+    }
 }
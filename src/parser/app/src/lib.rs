@@ -15,5 +15,9 @@ pub mod chain_conversion;
 pub mod registry;
 
 mod routes {
+    pub(crate) mod capabilities;
+    pub(crate) mod list_chains;
     pub(crate) mod parse;
+    pub(crate) mod parse_bundle;
+    pub(crate) mod parser_info;
 }
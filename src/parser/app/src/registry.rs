@@ -1,22 +1,50 @@
 //! Registry module for managing type definitions and lookups
 
+use visualsign::errors::VisualSignError;
+use visualsign::outcome::ParseOutcome;
+use visualsign::registry::Chain;
+use visualsign::vsptrait::VisualSignOptions;
+
 // TODO(pg): this may not be the right place for this
 /// Creates and configures a new transaction converter registry with all supported chains.
 ///
-/// Returns a registry with converters for Solana and Unspecified transaction types.
+/// Returns a registry with converters for each chain's transactions, Cosmos and Lightning
+/// payment requests as custom chains, and the custom chains `EthereumPersonalSign` and
+/// `SolanaOffchainMessage` for off-chain messages.
 #[must_use]
 pub fn create_registry() -> visualsign::registry::TransactionConverterRegistry {
     let mut registry = visualsign::registry::TransactionConverterRegistry::new();
     // TODO: Create a ChainRegistry trait that all chains can implement for token metadata,
     // contract types, etc. Currently only Ethereum has a ContractRegistry.
+    registry.register::<visualsign_bitcoin::BitcoinPsbtWrapper, _>(
+        visualsign::registry::Chain::Bitcoin,
+        visualsign_bitcoin::BitcoinVisualSignConverter,
+    );
+    registry.register::<visualsign_bitcoin::lightning::LightningInvoice, _>(
+        visualsign::registry::Chain::Custom(visualsign_bitcoin::lightning::CHAIN_NAME.to_string()),
+        visualsign_bitcoin::lightning::LightningInvoiceConverter,
+    );
+    registry.register::<visualsign_cosmos::CosmosSignDocWrapper, _>(
+        visualsign::registry::Chain::Custom(visualsign_cosmos::CHAIN_NAME.to_string()),
+        visualsign_cosmos::CosmosVisualSignConverter,
+    );
     registry.register::<visualsign_ethereum::EthereumTransactionWrapper, _>(
         visualsign::registry::Chain::Ethereum,
         visualsign_ethereum::EthereumVisualSignConverter::new(),
     );
+    // Message signing has no chain of its own, so each format is a custom chain
+    registry.register::<visualsign_ethereum::personal_sign::PersonalSignMessage, _>(
+        visualsign::registry::Chain::Custom("EthereumPersonalSign".to_string()),
+        visualsign_ethereum::personal_sign::PersonalSignConverter,
+    );
     registry.register::<visualsign_solana::SolanaTransactionWrapper, _>(
         visualsign::registry::Chain::Solana,
         visualsign_solana::SolanaVisualSignConverter,
     );
+    registry.register::<visualsign_solana::OffchainMessage, _>(
+        visualsign::registry::Chain::Custom("SolanaOffchainMessage".to_string()),
+        visualsign_solana::SolanaOffchainMessageConverter,
+    );
     registry.register::<visualsign_sui::SuiTransactionWrapper, _>(
         visualsign::registry::Chain::Sui,
         visualsign_sui::SuiVisualSignConverter,
@@ -31,3 +59,17 @@ pub fn create_registry() -> visualsign::registry::TransactionConverterRegistry {
     );
     registry
 }
+
+/// Converts `transaction_data` with the converters of [`create_registry`], trying the `chain`
+/// hint first and detecting the chain when there is none or its converter cannot parse the
+/// data. Returns the chain that converted it.
+///
+/// The one dispatch path of the service and the CLI; see
+/// [`visualsign::registry::TransactionConverterRegistry::parse_any`].
+pub fn parse_any(
+    chain: Option<&Chain>,
+    transaction_data: &str,
+    options: VisualSignOptions,
+) -> Result<(Chain, ParseOutcome), VisualSignError> {
+    create_registry().parse_any(chain, transaction_data, options)
+}
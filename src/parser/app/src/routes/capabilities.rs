@@ -0,0 +1,30 @@
+//! What the parsers in this enclave app support, for clients gating features on the deployment
+
+use generated::parser::{ChainFeatures, GetCapabilitiesResponse, PayloadCompression};
+use visualsign::{CharsetPolicy, PAYLOAD_VERSIONS};
+
+use crate::chain_conversion::registry_to_proto;
+use crate::registry::create_registry;
+
+/// Reports the payload versions and charset policies of the `visualsign` crate, the feature
+/// flags of every registered chain, read from the registry the parse route uses, and the payload
+/// compressions the parse route supports.
+pub fn get_capabilities() -> GetCapabilitiesResponse {
+    GetCapabilitiesResponse {
+        payload_versions: PAYLOAD_VERSIONS.iter().map(ToString::to_string).collect(),
+        charset_policies: CharsetPolicy::ALL
+            .iter()
+            .map(|policy| policy.as_str().to_string())
+            .collect(),
+        chains: create_registry()
+            .chain_capabilities()
+            .into_iter()
+            .map(|chain| ChainFeatures {
+                chain: registry_to_proto(&chain.chain) as i32,
+                name: chain.chain.as_str().to_string(),
+                features: chain.capabilities.features.into_iter().collect(),
+            })
+            .collect(),
+        payload_compressions: vec![PayloadCompression::Zstd as i32],
+    }
+}
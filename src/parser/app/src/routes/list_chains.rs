@@ -0,0 +1,29 @@
+//! Chain parsers registered in this enclave app and what each supports
+
+use generated::parser::{ChainCapabilities, CrateVersion, ListChainsResponse};
+
+use crate::chain_conversion::registry_to_proto;
+use crate::registry::create_registry;
+
+/// Lists every registered chain with its parser crate, encodings, transaction variants and
+/// decoders, read from the registry the parse route uses.
+pub fn list_chains() -> ListChainsResponse {
+    ListChainsResponse {
+        chains: create_registry()
+            .chain_capabilities()
+            .into_iter()
+            .map(|chain| ChainCapabilities {
+                chain: registry_to_proto(&chain.chain) as i32,
+                name: chain.chain.as_str().to_string(),
+                parser: chain.parser_info.map(|info| CrateVersion {
+                    name: info.crate_name,
+                    version: info.version,
+                    git_commit: info.git_commit.unwrap_or_default(),
+                }),
+                encodings: chain.capabilities.encodings,
+                transaction_variants: chain.capabilities.transaction_variants,
+                decoders: chain.capabilities.decoders,
+            })
+            .collect(),
+    }
+}
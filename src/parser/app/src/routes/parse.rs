@@ -1,69 +1,176 @@
 //! Parsing endpoint for `VisualSign`
 
-use crate::{chain_conversion, errors::GrpcError, registry::create_registry};
+use crate::{chain_conversion, errors::GrpcError, registry::parse_any};
 use generated::parser::Chain as ProtoChain;
 use generated::{
     google::rpc::Code,
     parser::{
-        ParseRequest, ParseResponse, ParsedTransaction, ParsedTransactionPayload, Signature,
-        SignatureScheme,
+        Coverage, ParseRequest, ParseResponse, ParsedTransaction, ParsedTransactionPayload,
+        PayloadCompression, Signature, SignatureScheme,
     },
 };
 use qos_crypto::sha_256;
 use qos_p256::P256Pair;
 
+use visualsign::extensions::{Extensions, InterfaceDefinitions};
+use visualsign::outcome::ParseOutcome;
 use visualsign::registry::Chain as VisualSignRegistryChain;
 use visualsign::vsptrait::VisualSignOptions;
 
+/// zstd level of `PAYLOAD_COMPRESSION_ZSTD`; changing it changes the compressed bytes.
+const ZSTD_LEVEL: i32 = 3;
+
 pub fn parse(
     parse_request: ParseRequest,
     ephemeral_key: &P256Pair,
 ) -> Result<ParseResponse, GrpcError> {
-    let request_payload = parse_request.unsigned_payload;
-    if request_payload.is_empty() {
+    let span = tracing::info_span!(
+        "enclave_parse",
+        chain = ProtoChain::from_i32(parse_request.chain).map_or("INVALID", |c| c.as_str_name()),
+        payload_size = parse_request.unsigned_payload.len(),
+        field_count = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    let _entered = span.enter();
+
+    let result = parse_and_sign(parse_request, ephemeral_key);
+    if let Err(error) = &result {
+        span.record("error", error.code.as_str_name());
+    }
+    result
+}
+
+/// Converts the transaction of `parse_request` with the enclave's default options, overridden
+/// by the request's.
+pub(crate) fn convert(parse_request: &ParseRequest) -> Result<ParseOutcome, GrpcError> {
+    if parse_request.unsigned_payload.is_empty() {
         return Err(GrpcError::new(
             Code::InvalidArgument,
             "unsigned transaction is empty",
         ));
     }
 
-    let options = VisualSignOptions {
+    let mut options = VisualSignOptions {
         decode_transfers: true,
-        transaction_name: None,
         metadata: parse_request.chain_metadata.clone(),
+        ..Default::default()
     };
-    let registry = create_registry();
+    if !parse_request.options.is_empty() {
+        options = options
+            .with_overrides(&parse_request.options)
+            .map_err(|e| GrpcError::new(Code::InvalidArgument, &format!("invalid options: {e}")))?;
+    }
+    // ABI or IDL from the chain metadata, unless the options already carry definitions
+    if options.extensions.get::<InterfaceDefinitions>().is_none() {
+        let from_metadata = parse_request
+            .chain_metadata
+            .as_ref()
+            .map(Extensions::from_chain_metadata)
+            .unwrap_or_default();
+        if let Some(definitions) = from_metadata.get::<InterfaceDefinitions>() {
+            options.extensions.insert(definitions.clone());
+        }
+    }
+
     let proto_chain = ProtoChain::from_i32(parse_request.chain)
         .ok_or_else(|| GrpcError::new(Code::InvalidArgument, "invalid chain"))?;
-    let registry_chain: VisualSignRegistryChain = chain_conversion::proto_to_registry(proto_chain);
-
-    let signable_payload_str = registry
-        .convert_transaction(&registry_chain, request_payload.as_str(), options)
-        .map_err(|e| GrpcError::new(Code::InvalidArgument, &format!("{e}")))?;
+    let registry_chain: VisualSignRegistryChain =
+        if proto_chain == ProtoChain::Custom && !parse_request.custom_chain.is_empty() {
+            VisualSignRegistryChain::Custom(parse_request.custom_chain.clone())
+        } else {
+            chain_conversion::proto_to_registry(proto_chain)
+        };
 
-    // Convert SignablePayload to String (assuming you want JSON)
-    let signable_payload = serde_json::to_string(&signable_payload_str).map_err(|e| {
-        GrpcError::new(Code::Internal, &format!("Failed to serialize payload: {e}"))
-    })?;
-
-    let payload = ParsedTransactionPayload { signable_payload };
+    parse_any(
+        Some(&registry_chain),
+        parse_request.unsigned_payload.as_str(),
+        options,
+    )
+    .map(|(_, outcome)| outcome)
+    .map_err(|e| GrpcError::from_visualsign_error(&e, registry_chain.as_str()))
+}
 
-    let digest = sha_256(&borsh::to_vec(&payload).expect("payload implements borsh::Serialize"));
+/// Signs the SHA-256 of the borsh encoding of `payload` with the enclave's ephemeral key.
+pub(crate) fn sign<T: borsh::BorshSerialize>(
+    payload: &T,
+    ephemeral_key: &P256Pair,
+) -> Result<Signature, GrpcError> {
+    let digest = sha_256(&borsh::to_vec(payload).expect("payload implements borsh::Serialize"));
     let sig = ephemeral_key
         .sign(&digest)
         .map_err(|e| GrpcError::new(Code::Internal, &format!("{e:?}")))?;
 
-    let signature = Signature {
+    Ok(Signature {
         public_key: qos_hex::encode(&ephemeral_key.public_key().to_bytes()),
         signature: qos_hex::encode(&sig),
         message: qos_hex::encode(&digest),
         scheme: SignatureScheme::TurnkeyP256EphemeralKey as i32,
+    })
+}
+
+fn parse_and_sign(
+    parse_request: ParseRequest,
+    ephemeral_key: &P256Pair,
+) -> Result<ParseResponse, GrpcError> {
+    let outcome = convert(&parse_request)?;
+    let signable_payload_str = outcome.payload;
+
+    tracing::Span::current().record("field_count", signable_payload_str.fields.len());
+
+    // Convert SignablePayload to String (assuming you want JSON)
+    let signable_payload = serde_json::to_string(&signable_payload_str).map_err(|e| {
+        GrpcError::new(Code::Internal, &format!("Failed to serialize payload: {e}"))
+    })?;
+
+    let payload = ParsedTransactionPayload { signable_payload };
+    let provenance = if outcome.provenance.is_empty() {
+        String::new()
+    } else {
+        serde_json::to_string(&outcome.provenance).map_err(|e| {
+            GrpcError::new(
+                Code::Internal,
+                &format!("Failed to serialize provenance: {e}"),
+            )
+        })?
     };
 
+    let coverage = outcome.coverage.map(|coverage| Coverage {
+        decoded_bytes: u64::try_from(coverage.decoded_bytes).unwrap_or(u64::MAX),
+        total_bytes: u64::try_from(coverage.total_bytes).unwrap_or(u64::MAX),
+        percent: u32::from(coverage.percent),
+    });
+
+    let signature = sign(&payload, ephemeral_key)?;
+
+    // Only the transport form changes, the signature covers the plain payload. Compressions
+    // this app does not know fall back to the plain payload.
+    let (payload, compressed_signable_payload, compression) =
+        match PayloadCompression::from_i32(parse_request.accept_compression) {
+            Some(PayloadCompression::Zstd) => {
+                let compressed = zstd::bulk::compress(
+                    payload.signable_payload.as_bytes(),
+                    ZSTD_LEVEL,
+                )
+                .map_err(|e| {
+                    GrpcError::new(Code::Internal, &format!("Failed to compress payload: {e}"))
+                })?;
+                (
+                    ParsedTransactionPayload::default(),
+                    compressed,
+                    PayloadCompression::Zstd,
+                )
+            }
+            _ => (payload, Vec::new(), PayloadCompression::None),
+        };
+
     Ok(ParseResponse {
         parsed_transaction: Some(ParsedTransaction {
             payload: Some(payload),
             signature: Some(signature),
         }),
+        provenance,
+        coverage,
+        compressed_signable_payload,
+        compression: compression as i32,
     })
 }
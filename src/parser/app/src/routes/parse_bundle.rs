@@ -0,0 +1,79 @@
+//! Parsing endpoint for transactions approved together
+
+use generated::google::rpc::Code;
+use generated::parser::{
+    ParseBundleRequest, ParseBundleResponse, ParsedBundle, ParsedBundlePayload,
+};
+use qos_p256::P256Pair;
+use visualsign::bundle::{MAX_BUNDLE_PAYLOADS, SignablePayloadBundle};
+
+use crate::errors::GrpcError;
+use crate::routes::parse::{convert, sign};
+
+/// Converts every transaction of the bundle as the parse route does, in request order, and signs
+/// the canonical JSON of the resulting [`SignablePayloadBundle`].
+///
+/// The first transaction that fails to parse fails the whole bundle; its index prefixes the
+/// error message.
+pub fn parse_bundle(
+    request: ParseBundleRequest,
+    ephemeral_key: &P256Pair,
+) -> Result<ParseBundleResponse, GrpcError> {
+    let span = tracing::info_span!(
+        "enclave_parse_bundle",
+        transactions = request.transactions.len(),
+        error = tracing::field::Empty,
+    );
+    let _entered = span.enter();
+
+    let result = parse_and_sign(request, ephemeral_key);
+    if let Err(error) = &result {
+        span.record("error", error.code.as_str_name());
+    }
+    result
+}
+
+fn parse_and_sign(
+    request: ParseBundleRequest,
+    ephemeral_key: &P256Pair,
+) -> Result<ParseBundleResponse, GrpcError> {
+    if request.transactions.is_empty() || request.transactions.len() > MAX_BUNDLE_PAYLOADS {
+        return Err(GrpcError::new(
+            Code::InvalidArgument,
+            &format!(
+                "a bundle holds 1 to {MAX_BUNDLE_PAYLOADS} transactions, got {}",
+                request.transactions.len()
+            ),
+        ));
+    }
+
+    let payloads = request
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(index, transaction)| {
+            convert(transaction)
+                .map(|outcome| outcome.payload)
+                .map_err(|mut error| {
+                    error.message = format!("transactions[{index}]: {}", error.message);
+                    error
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let bundle = SignablePayloadBundle::new(request.title, payloads)
+        .map_err(|e| GrpcError::new(Code::InvalidArgument, &e.to_string()))?;
+    let signable_bundle = bundle
+        .to_json()
+        .map_err(|e| GrpcError::new(Code::Internal, &format!("Failed to serialize bundle: {e}")))?;
+
+    let payload = ParsedBundlePayload { signable_bundle };
+    let signature = sign(&payload, ephemeral_key)?;
+
+    Ok(ParseBundleResponse {
+        parsed_bundle: Some(ParsedBundle {
+            payload: Some(payload),
+            signature: Some(signature),
+        }),
+    })
+}
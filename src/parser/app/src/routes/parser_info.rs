@@ -0,0 +1,36 @@
+//! Versions of the parser crates built into this enclave app
+
+use std::collections::HashSet;
+
+use generated::parser::{CrateVersion, ParserInfoResponse};
+use visualsign::parser_info::ParserInfo;
+
+use crate::registry::create_registry;
+
+/// Reports the app, `visualsign` and chain parser crate versions, returned to clients as part
+/// of the host's `GetAttestation` response.
+pub fn parser_info() -> ParserInfoResponse {
+    let builtin = [
+        visualsign::parser_info!(),
+        ParserInfo::new("visualsign", visualsign::VERSION, None),
+    ];
+    // A crate serving several chains is listed once
+    let mut seen = HashSet::new();
+    let chains = create_registry()
+        .parser_infos()
+        .into_iter()
+        .map(|(_, info)| info);
+
+    ParserInfoResponse {
+        crate_versions: builtin
+            .into_iter()
+            .chain(chains)
+            .filter(|info| seen.insert(info.crate_name.clone()))
+            .map(|info| CrateVersion {
+                name: info.crate_name,
+                version: info.version,
+                git_commit: info.git_commit.unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
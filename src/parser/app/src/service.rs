@@ -36,12 +36,19 @@ impl RequestProcessor for Processor {
                         details: vec![],
                     })
                 })
-                .map_err(|o| QosParserResponse { output: Some(o) })
-            {
+                .map_err(|o| QosParserResponse {
+                    output: Some(o),
+                    ..Default::default()
+                }) {
                 Ok(request) => request,
                 Err(err_resp) => return err_resp.encode_to_vec(),
             };
 
+            // Logged under the host's id, which the response echoes
+            let request_id = request.request_id;
+            let span = tracing::info_span!("enclave_request", request_id = %request_id);
+            let _entered = span.enter();
+
             let ephemeral_key = match self
                 .handle
                 .get_ephemeral_key()
@@ -54,6 +61,7 @@ impl RequestProcessor for Processor {
                 })
                 .map_err(|output| QosParserResponse {
                     output: Some(output),
+                    request_id: request_id.clone(),
                 }) {
                 Ok(input) => input,
                 Err(err_resp) => return err_resp.encode_to_vec(),
@@ -68,8 +76,10 @@ impl RequestProcessor for Processor {
                         details: vec![],
                     })
                 })
-                .map_err(|o| QosParserResponse { output: Some(o) })
-            {
+                .map_err(|o| QosParserResponse {
+                    output: Some(o),
+                    request_id: request_id.clone(),
+                }) {
                 Ok(input) => input,
                 Err(err_resp) => return err_resp.encode_to_vec(),
             };
@@ -78,23 +88,45 @@ impl RequestProcessor for Processor {
                 qos_parser_request::Input::ParseRequest(parse_request) => {
                     match crate::routes::parse::parse(parse_request, &ephemeral_key)
                         .map(qos_parser_response::Output::ParseResponse)
-                        .map_err(|e| {
-                            qos_parser_response::Output::Status(Status {
-                                code: e.code as i32,
-                                message: e.message,
-                                details: vec![],
-                            })
-                        }) {
+                        .map_err(|e| qos_parser_response::Output::Status(Status::from(e)))
+                    {
                         Ok(o) | Err(o) => o,
                     }
                 }
                 qos_parser_request::Input::HealthRequest(_) => {
                     qos_parser_response::Output::HealthResponse(AppHealthResponse { code: 200 })
                 }
+                qos_parser_request::Input::ParserInfoRequest(_) => {
+                    qos_parser_response::Output::ParserInfoResponse(
+                        crate::routes::parser_info::parser_info(),
+                    )
+                }
+                qos_parser_request::Input::ListChainsRequest(_) => {
+                    qos_parser_response::Output::ListChainsResponse(
+                        crate::routes::list_chains::list_chains(),
+                    )
+                }
+                qos_parser_request::Input::GetCapabilitiesRequest(_) => {
+                    qos_parser_response::Output::GetCapabilitiesResponse(
+                        crate::routes::capabilities::get_capabilities(),
+                    )
+                }
+                qos_parser_request::Input::ParseBundleRequest(parse_bundle_request) => {
+                    match crate::routes::parse_bundle::parse_bundle(
+                        parse_bundle_request,
+                        &ephemeral_key,
+                    )
+                    .map(qos_parser_response::Output::ParseBundleResponse)
+                    .map_err(|e| qos_parser_response::Output::Status(Status::from(e)))
+                    {
+                        Ok(o) | Err(o) => o,
+                    }
+                }
             };
 
             QosParserResponse {
                 output: Some(output),
+                request_id,
             }
             .encode_to_vec()
         })
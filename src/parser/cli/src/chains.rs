@@ -22,6 +22,16 @@ pub fn parse_chain(chain_str: &str) -> Chain {
         .unwrap_or(Chain::Unspecified)
 }
 
+/// Chain name that has the parser detect the chain of a transaction
+pub const AUTO_CHAIN: &str = "auto";
+
+/// The chain to try first for a chain string, or None for [`AUTO_CHAIN`], which leaves the
+/// chain to detection.
+#[must_use]
+pub fn chain_hint(chain_str: &str) -> Option<Chain> {
+    (chain_str != AUTO_CHAIN).then(|| parse_chain(chain_str))
+}
+
 /// Returns a vector of all available chain names as string slices.
 #[must_use]
 pub fn available_chains() -> Vec<&'static str> {
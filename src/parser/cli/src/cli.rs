@@ -1,27 +1,49 @@
 use crate::chains;
-use chains::parse_chain;
-use clap::Parser;
-use parser_app::registry::create_registry;
-use visualsign::vsptrait::VisualSignOptions;
+use crate::compare::{PayloadChange, diff_payloads};
+use chains::chain_hint;
+use clap::{CommandFactory, Parser, Subcommand};
+use parser_app::registry::{create_registry, parse_any};
+use serde::Serialize;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use visualsign::capabilities::{ChainCapabilities, ParserCapabilities};
+use visualsign::errors::VisualSignError;
+use visualsign::parser_info::ParserInfo;
+use visualsign::vsptrait::{OutputFlags, VisualSignOptions};
 use visualsign::{SignablePayload, SignablePayloadField};
 
 #[derive(Parser, Debug)]
 #[command(name = "visualsign-parser")]
 #[command(version = "1.0")]
 #[command(about = "Converts raw transactions to visual signing properties")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(short, long, help = "Chain type")]
-    chain: String,
+    #[command(subcommand)]
+    command: Option<Command>,
 
     #[arg(
         short,
         long,
+        required = true,
+        help = "Chain type, or `auto` to detect it"
+    )]
+    chain: Option<String>,
+
+    #[arg(
+        short,
+        long,
+        required = true,
         value_name = "RAW_TX",
         help = "Raw transaction hex string"
     )]
-    transaction: String,
+    transaction: Option<String>,
 
-    #[arg(short, long, default_value = "text", help = "Output format")]
+    #[arg(
+        short,
+        long,
+        default_value = "text",
+        help = "Output format: text, json, yaml or pretty (human is an alias of pretty)"
+    )]
     output: OutputFormat,
 
     #[arg(
@@ -29,12 +51,158 @@ struct Args {
         help = "Show only condensed view (what hardware wallets display)"
     )]
     condensed_only: bool,
+
+    #[command(flatten)]
+    parse_options: ParseOptions,
+}
+
+/// Flags that change how a transaction is parsed, shared by every parsing mode
+#[derive(clap::Args, Debug)]
+struct ParseOptions {
+    #[arg(
+        long,
+        help = "Group flat field lists into a condensed/expanded preview layout"
+    )]
+    preview_layout: bool,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Account that will sign the transaction, shown as its sender"
+    )]
+    sender: Option<String>,
+
+    #[arg(
+        long,
+        help = "Reject transactions that do not re-encode to exactly the input bytes"
+    )]
+    verify_lossless: bool,
+
+    #[arg(
+        long,
+        help = "Report how much of the transaction was decoded rather than shown raw"
+    )]
+    include_coverage: bool,
+
+    #[arg(
+        long,
+        help = "Show instructions or commands that fail to decode as unknown fields instead of failing"
+    )]
+    partial_parse: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a completion script for `shell` to stdout
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// List the registered chain parsers with their encodings, transaction variants and decoders
+    ListChains {
+        #[arg(
+            short,
+            long,
+            default_value = "text",
+            help = "Output format: text, json, yaml or pretty (human is an alias of pretty)"
+        )]
+        output: OutputFormat,
+    },
+    /// Read newline-delimited raw transactions from stdin and write one canonical JSON payload
+    /// per line to stdout, flushed as soon as it is ready
+    Pipe {
+        #[arg(short, long, help = "Chain type, or `auto` to detect it")]
+        chain: String,
+
+        #[command(flatten)]
+        parse_options: ParseOptions,
+    },
+    /// Parse a transaction with this binary and with another parser binary, or against a saved
+    /// JSON payload, and print the differences; exits with status 1 when the payloads differ
+    Compare {
+        #[arg(short, long, help = "Chain type, or `auto` to detect it")]
+        chain: String,
+
+        #[arg(
+            short,
+            long,
+            value_name = "RAW_TX",
+            help = "Raw transaction hex string"
+        )]
+        transaction: String,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            required_unless_present = "fixture",
+            conflicts_with = "fixture",
+            help = "Parser binary producing the baseline payload"
+        )]
+        binary: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Saved JSON payload to use as the baseline"
+        )]
+        fixture: Option<PathBuf>,
+
+        #[arg(
+            short,
+            long,
+            default_value = "text",
+            help = "Output format: text, json, yaml or pretty (human is an alias of pretty)"
+        )]
+        output: OutputFormat,
+
+        #[command(flatten)]
+        parse_options: ParseOptions,
+    },
+}
+
+impl ParseOptions {
+    /// The command-line flags that reproduce these options, for running another parser binary
+    fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.preview_layout {
+            args.push("--preview-layout".to_string());
+        }
+        if let Some(sender) = &self.sender {
+            args.extend(["--sender".to_string(), sender.clone()]);
+        }
+        if self.verify_lossless {
+            args.push("--verify-lossless".to_string());
+        }
+        if self.include_coverage {
+            args.push("--include-coverage".to_string());
+        }
+        if self.partial_parse {
+            args.push("--partial-parse".to_string());
+        }
+        args
+    }
+
+    fn to_visual_sign_options(&self) -> VisualSignOptions {
+        VisualSignOptions {
+            decode_transfers: true,
+            sender: self.sender.clone(),
+            output: OutputFlags {
+                preview_layout: self.preview_layout,
+                verify_lossless: self.verify_lossless,
+                include_coverage: self.include_coverage,
+                partial_parse: self.partial_parse,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 enum OutputFormat {
     Text,
     Json,
+    Yaml,
     Human,
 }
 
@@ -45,12 +213,68 @@ impl std::str::FromStr for OutputFormat {
         match s {
             "text" => Ok(OutputFormat::Text),
             "json" => Ok(OutputFormat::Json),
-            "human" => Ok(OutputFormat::Human),
+            "yaml" => Ok(OutputFormat::Yaml),
+            "pretty" | "human" => Ok(OutputFormat::Human),
             _ => Err(format!("Invalid output format: {s}")),
         }
     }
 }
 
+/// Process exit statuses, so scripts can tell failures apart without reading stderr. Clap exits
+/// with 2 on usage errors.
+mod exit_code {
+    use visualsign::errors::ParserErrorKind;
+
+    /// `compare` found differences between the payloads
+    pub const DIFFERENCES: i32 = 1;
+    /// Reading input, a baseline or another binary's output failed
+    pub const IO: i32 = 3;
+
+    /// The status for a transaction that failed to parse with an error of `kind`
+    pub fn for_kind(kind: ParserErrorKind) -> i32 {
+        match kind {
+            ParserErrorKind::Encoding => 10,
+            ParserErrorKind::Truncated => 11,
+            ParserErrorKind::UnsupportedVariant => 12,
+            ParserErrorKind::UnsupportedProtocol => 13,
+            ParserErrorKind::InternalInvariant => 14,
+        }
+    }
+}
+
+/// A failure of a CLI command, mapped to its exit status
+enum CliError {
+    Parse(VisualSignError),
+    Io(String),
+}
+
+impl CliError {
+    fn exit(&self) -> ! {
+        match self {
+            CliError::Parse(err) => {
+                eprintln!("Error ({}): {err}", err.kind().code());
+                std::process::exit(exit_code::for_kind(err.kind()))
+            }
+            CliError::Io(message) => {
+                eprintln!("Error: {message}");
+                std::process::exit(exit_code::IO)
+            }
+        }
+    }
+}
+
+/// Prints `value` as pretty JSON, or as YAML for `--output yaml`
+fn print_serialized<T: Serialize + ?Sized>(value: &T, output_format: OutputFormat) {
+    let serialized = match output_format {
+        OutputFormat::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        _ => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+    };
+    match serialized {
+        Ok(output) => println!("{}", output.trim_end()),
+        Err(err) => eprintln!("Error: Failed to serialize output: {err}"),
+    }
+}
+
 struct HumanReadableFormatter<'a> {
     payload: &'a SignablePayload,
     condensed_only: bool,
@@ -152,6 +376,18 @@ impl<'a> HumanReadableFormatter<'a> {
                     amount_v2.abbreviation.as_deref().unwrap_or("")
                 )?;
             }
+            SignablePayloadField::AmountV3 { common, .. }
+            | SignablePayloadField::Timestamp { common, .. }
+            | SignablePayloadField::Duration { common, .. }
+            | SignablePayloadField::Percentage { common, .. }
+            | SignablePayloadField::ImageRef { common, .. }
+            | SignablePayloadField::Bytes { common, .. } => {
+                writeln!(
+                    writer,
+                    "{} {}: {}",
+                    prefix, common.label, common.fallback_text
+                )?;
+            }
             SignablePayloadField::AddressV2 { common, address_v2 } => {
                 writeln!(
                     writer,
@@ -200,6 +436,12 @@ fn common_label(field: &SignablePayloadField) -> String {
         SignablePayloadField::TextV2 { common, .. }
         | SignablePayloadField::PreviewLayout { common, .. }
         | SignablePayloadField::AmountV2 { common, .. }
+        | SignablePayloadField::AmountV3 { common, .. }
+        | SignablePayloadField::Timestamp { common, .. }
+        | SignablePayloadField::Duration { common, .. }
+        | SignablePayloadField::Percentage { common, .. }
+        | SignablePayloadField::ImageRef { common, .. }
+        | SignablePayloadField::Bytes { common, .. }
         | SignablePayloadField::AddressV2 { common, .. } => common.label.clone(),
         _ => "Unknown".to_string(),
     }
@@ -212,34 +454,201 @@ fn parse_and_display(
     output_format: OutputFormat,
     condensed_only: bool,
 ) {
-    let registry_chain = parse_chain(chain);
+    let hint = chain_hint(chain);
+
+    match parse_any(hint.as_ref(), raw_tx, options) {
+        Ok((detected, outcome)) => {
+            if hint.is_none() {
+                eprintln!("Detected chain: {}", detected.as_str());
+            }
+            for warning in &outcome.warnings {
+                eprintln!("Warning ({:?}): {}", warning.kind, warning.message);
+            }
+            display_payload(&outcome.payload, output_format, condensed_only);
+        }
+        Err(err) => CliError::Parse(err).exit(),
+    }
+}
+
+fn display_payload(payload: &SignablePayload, output_format: OutputFormat, condensed_only: bool) {
+    match output_format {
+        OutputFormat::Json | OutputFormat::Yaml => print_serialized(payload, output_format),
+        OutputFormat::Text => {
+            println!("{payload:#?}");
+        }
+        OutputFormat::Human => {
+            let formatter = HumanReadableFormatter::new(payload, condensed_only);
+            println!("{formatter}");
+            if !condensed_only {
+                eprintln!(
+                    "\nRun with `--condensed-only` to see what users see on hardware wallets"
+                );
+            }
+        }
+    }
+}
 
+/// Converts each non-blank line of `input` and writes the result to `output` as one line of
+/// JSON: the canonical payload JSON on success, `{"Error": "...", "Kind": "..."}` with the code
+/// of the error's [`visualsign::errors::ParserErrorKind`] otherwise, so results line up with the
+/// transactions that produced them. Output is flushed after every line so a consumer reading
+/// from a pipe sees each result without waiting for the input to end.
+fn run_pipe(
+    chain: &str,
+    options: &VisualSignOptions,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> std::io::Result<()> {
+    let hint = chain_hint(chain);
     let registry = create_registry();
-    let signable_payload_str = registry.convert_transaction(&registry_chain, raw_tx, options);
-    match signable_payload_str {
-        Ok(payload) => match output_format {
-            OutputFormat::Json => {
-                if let Ok(json_output) = serde_json::to_string_pretty(&payload) {
-                    println!("{json_output}");
-                } else {
-                    eprintln!("Error: Failed to serialize output as JSON");
+
+    for line in input.lines() {
+        let line = line?;
+        let raw_tx = line.trim();
+        if raw_tx.is_empty() {
+            continue;
+        }
+
+        let result = registry
+            .parse_any(hint.as_ref(), raw_tx, options.clone())
+            .and_then(|(_, outcome)| {
+                for warning in &outcome.warnings {
+                    eprintln!("Warning ({:?}): {}", warning.kind, warning.message);
                 }
+                outcome.payload.to_validated_json()
+            });
+        let json = match result {
+            Ok(json) => json,
+            Err(err) => serde_json::json!({ "Error": err.to_string(), "Kind": err.kind().code() })
+                .to_string(),
+        };
+        writeln!(output, "{json}")?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// The payload another parser binary produces for `raw_tx`, read from its `--output json`.
+fn baseline_from_binary(
+    binary: &Path,
+    chain: &str,
+    raw_tx: &str,
+    parse_options: &ParseOptions,
+) -> Result<serde_json::Value, CliError> {
+    let output = std::process::Command::new(binary)
+        .args([
+            "--chain",
+            chain,
+            "--output",
+            "json",
+            "--transaction",
+            raw_tx,
+        ])
+        .args(parse_options.to_args())
+        .output()
+        .map_err(|e| CliError::Io(format!("Failed to run {}: {e}", binary.display())))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    serde_json::from_str(&stdout).map_err(|e| {
+        CliError::Io(format!(
+            "{} did not print a JSON payload ({e}): {}",
+            binary.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    })
+}
+
+fn baseline_from_fixture(fixture: &Path) -> Result<serde_json::Value, CliError> {
+    let contents = std::fs::read_to_string(fixture)
+        .map_err(|e| CliError::Io(format!("Failed to read {}: {e}", fixture.display())))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| CliError::Io(format!("{} is not a JSON payload: {e}", fixture.display())))
+}
+
+/// Parses `raw_tx` with this binary and returns the changes from `baseline` to its payload.
+fn compare_with_baseline(
+    chain: &str,
+    raw_tx: &str,
+    options: VisualSignOptions,
+    baseline: &serde_json::Value,
+) -> Result<Vec<PayloadChange>, CliError> {
+    let (_, outcome) =
+        parse_any(chain_hint(chain).as_ref(), raw_tx, options).map_err(CliError::Parse)?;
+    let payload = outcome.payload;
+    let current = serde_json::to_value(&payload)
+        .map_err(|e| CliError::Parse(VisualSignError::SerializationError(e.to_string())))?;
+    Ok(diff_payloads(baseline, &current))
+}
+
+fn display_changes(changes: &[PayloadChange], output_format: OutputFormat) {
+    match output_format {
+        OutputFormat::Json | OutputFormat::Yaml => print_serialized(changes, output_format),
+        OutputFormat::Text | OutputFormat::Human => {
+            if changes.is_empty() {
+                println!("Payloads are identical");
             }
-            OutputFormat::Text => {
-                println!("{payload:#?}");
+            for change in changes {
+                println!("{change}");
             }
-            OutputFormat::Human => {
-                let formatter = HumanReadableFormatter::new(&payload, condensed_only);
-                println!("{formatter}");
-                if !condensed_only {
-                    eprintln!(
-                        "\nRun with `--condensed-only` to see what users see on hardware wallets"
-                    );
+        }
+    }
+}
+
+/// One registered chain as listed by `list-chains --output json`
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct ChainListing<'a> {
+    chain: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parser_info: Option<&'a ParserInfo>,
+    #[serde(flatten)]
+    capabilities: &'a ParserCapabilities,
+}
+
+fn display_chains(chains: &[ChainCapabilities], output_format: OutputFormat) {
+    match output_format {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let listings: Vec<ChainListing> = chains
+                .iter()
+                .map(|chain| ChainListing {
+                    chain: chain.chain.as_str(),
+                    parser_info: chain.parser_info.as_ref(),
+                    capabilities: &chain.capabilities,
+                })
+                .collect();
+            print_serialized(&listings, output_format);
+        }
+        OutputFormat::Text | OutputFormat::Human => {
+            for chain in chains {
+                match &chain.parser_info {
+                    Some(info) => {
+                        println!(
+                            "{} ({} {})",
+                            chain.chain.as_str(),
+                            info.crate_name,
+                            info.version
+                        );
+                    }
+                    None => println!("{}", chain.chain.as_str()),
+                }
+                let capabilities = &chain.capabilities;
+                for (label, values) in [
+                    ("Encodings", &capabilities.encodings),
+                    ("Transaction variants", &capabilities.transaction_variants),
+                    ("Decoders", &capabilities.decoders),
+                ] {
+                    if !values.is_empty() {
+                        println!("  {label}: {}", values.join(", "));
+                    }
+                }
+                if !capabilities.features.is_empty() {
+                    let features: Vec<String> = capabilities
+                        .features
+                        .iter()
+                        .map(|(name, enabled)| format!("{name}={enabled}"))
+                        .collect();
+                    println!("  Features: {}", features.join(", "));
                 }
             }
-        },
-        Err(err) => {
-            eprintln!("Error: {err:?}");
         }
     }
 }
@@ -255,15 +664,84 @@ impl Cli {
     pub fn execute() {
         let args = Args::parse();
 
-        let options = VisualSignOptions {
-            decode_transfers: true,
-            transaction_name: None,
-            metadata: None,
+        match args.command {
+            Some(Command::Completions { shell }) => {
+                let mut command = Args::command();
+                let name = command.get_name().to_string();
+                clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+                return;
+            }
+            Some(Command::ListChains { output }) => {
+                display_chains(&create_registry().chain_capabilities(), output);
+                return;
+            }
+            Some(Command::Pipe {
+                chain,
+                parse_options,
+            }) => {
+                let options = parse_options.to_visual_sign_options();
+                let result = run_pipe(
+                    &chain,
+                    &options,
+                    &mut std::io::stdin().lock(),
+                    &mut std::io::stdout().lock(),
+                );
+                match result {
+                    // A consumer closing the pipe early is a normal way to stop
+                    Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => {
+                        CliError::Io(err.to_string()).exit();
+                    }
+                    _ => {}
+                }
+                return;
+            }
+            Some(Command::Compare {
+                chain,
+                transaction,
+                binary,
+                fixture,
+                output,
+                parse_options,
+            }) => {
+                let baseline = match (&binary, &fixture) {
+                    (Some(binary), _) => {
+                        baseline_from_binary(binary, &chain, &transaction, &parse_options)
+                    }
+                    (None, Some(fixture)) => baseline_from_fixture(fixture),
+                    // clap requires one of them
+                    (None, None) => return,
+                };
+                let changes = baseline.and_then(|baseline| {
+                    compare_with_baseline(
+                        &chain,
+                        &transaction,
+                        parse_options.to_visual_sign_options(),
+                        &baseline,
+                    )
+                });
+                match changes {
+                    Ok(changes) => {
+                        display_changes(&changes, output);
+                        if !changes.is_empty() {
+                            std::process::exit(exit_code::DIFFERENCES);
+                        }
+                    }
+                    Err(err) => err.exit(),
+                }
+                return;
+            }
+            None => {}
+        }
+        // clap requires both unless a subcommand is given
+        let (Some(chain), Some(transaction)) = (args.chain, args.transaction) else {
+            return;
         };
 
+        let options = args.parse_options.to_visual_sign_options();
+
         parse_and_display(
-            &args.chain,
-            &args.transaction,
+            &chain,
+            &transaction,
             options,
             args.output,
             args.condensed_only,
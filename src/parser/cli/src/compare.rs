@@ -0,0 +1,110 @@
+//! Structured differences between two payloads, used by the `compare` subcommand.
+//!
+//! Payloads are compared as JSON values so that key order and formatting do not matter, while
+//! field order does: arrays are compared element by element. Paths use the pointer notation of
+//! `visualsign::field_path`, e.g. `/Fields/1/TextV2/Text`.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// One difference between a baseline payload and the current one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "Kind", rename_all = "PascalCase")]
+pub enum PayloadChange {
+    /// Present only in the current payload
+    Added {
+        #[serde(rename = "Path")]
+        path: String,
+        #[serde(rename = "Value")]
+        value: Value,
+    },
+    /// Present only in the baseline payload
+    Removed {
+        #[serde(rename = "Path")]
+        path: String,
+        #[serde(rename = "Value")]
+        value: Value,
+    },
+    /// Present in both with different values
+    Changed {
+        #[serde(rename = "Path")]
+        path: String,
+        #[serde(rename = "Before")]
+        before: Value,
+        #[serde(rename = "After")]
+        after: Value,
+    },
+}
+
+impl fmt::Display for PayloadChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadChange::Added { path, value } => write!(f, "+ {path}: {value}"),
+            PayloadChange::Removed { path, value } => write!(f, "- {path}: {value}"),
+            PayloadChange::Changed {
+                path,
+                before,
+                after,
+            } => write!(f, "~ {path}: {before} -> {after}"),
+        }
+    }
+}
+
+/// The changes that turn `baseline` into `current`, in document order.
+#[must_use]
+pub fn diff_payloads(baseline: &Value, current: &Value) -> Vec<PayloadChange> {
+    let mut changes = Vec::new();
+    diff_values("", baseline, current, &mut changes);
+    changes
+}
+
+fn diff_values(path: &str, baseline: &Value, current: &Value, changes: &mut Vec<PayloadChange>) {
+    match (baseline, current) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, value) in before {
+                let path = format!("{path}/{key}");
+                match after.get(key) {
+                    Some(after_value) => diff_values(&path, value, after_value, changes),
+                    None => changes.push(PayloadChange::Removed {
+                        path,
+                        value: value.clone(),
+                    }),
+                }
+            }
+            for (key, value) in after {
+                if !before.contains_key(key) {
+                    changes.push(PayloadChange::Added {
+                        path: format!("{path}/{key}"),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            for index in 0..before.len().max(after.len()) {
+                let path = format!("{path}/{index}");
+                match (before.get(index), after.get(index)) {
+                    (Some(before_value), Some(after_value)) => {
+                        diff_values(&path, before_value, after_value, changes);
+                    }
+                    (Some(value), None) => changes.push(PayloadChange::Removed {
+                        path,
+                        value: value.clone(),
+                    }),
+                    (None, Some(value)) => changes.push(PayloadChange::Added {
+                        path,
+                        value: value.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if baseline != current => changes.push(PayloadChange::Changed {
+            path: if path.is_empty() { "/" } else { path }.to_string(),
+            before: baseline.clone(),
+            after: current.clone(),
+        }),
+        _ => {}
+    }
+}
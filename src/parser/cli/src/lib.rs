@@ -8,3 +8,5 @@
 pub mod chains;
 /// Command-line interface functionality and types.
 pub mod cli;
+/// Structured differences between payloads.
+pub mod compare;
@@ -1,7 +1,8 @@
 use similar::{ChangeTag, TextDiff};
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 #[test]
 fn test_cli_with_fixtures() {
@@ -78,3 +79,136 @@ fn test_cli_with_fixtures() {
         }
     }
 }
+
+#[test]
+fn test_cli_pipe_mode() {
+    let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("solana-json.input");
+    let input_contents = fs::read_to_string(&fixture).unwrap();
+    let transaction = input_contents.lines().last().unwrap().trim();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_parser_cli"))
+        .args(["pipe", "--chain", "solana"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("Failed to execute CLI: {e}"));
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(format!("{transaction}\n\nnot a transaction\n{transaction}\n").as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3, "{stdout}");
+    assert!(lines[0].get("Fields").is_some());
+    assert!(lines[1].get("Error").is_some());
+    assert_eq!(lines[0], lines[2]);
+}
+
+#[test]
+fn test_cli_detects_chain() {
+    let fixtures_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures");
+    let input_contents = fs::read_to_string(fixtures_dir.join("solana-json.input")).unwrap();
+    let transaction = input_contents.lines().last().unwrap().trim();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_parser_cli"))
+        .args(["--chain", "auto", "-o", "json", "-t", transaction])
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to execute CLI: {e}"));
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("Detected chain: Solana")
+    );
+    let expected: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(fixtures_dir.join("solana-json.expected")).unwrap(),
+    )
+    .unwrap();
+    let actual: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_cli_compare_against_fixture() {
+    let fixtures_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures");
+    let input_contents = fs::read_to_string(fixtures_dir.join("solana-json.input")).unwrap();
+    let transaction = input_contents.lines().last().unwrap().trim();
+    let compare = |fixture: &PathBuf| {
+        Command::new(env!("CARGO_BIN_EXE_parser_cli"))
+            .args([
+                "compare",
+                "--chain",
+                "solana",
+                "-t",
+                transaction,
+                "--fixture",
+            ])
+            .arg(fixture)
+            .output()
+            .unwrap_or_else(|e| panic!("Failed to execute CLI: {e}"))
+    };
+
+    let expected_path = fixtures_dir.join("solana-json.expected");
+    let output = compare(&expected_path);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        "Payloads are identical"
+    );
+
+    let mut baseline: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&expected_path).unwrap()).unwrap();
+    baseline["Title"] = serde_json::Value::from("Old Title");
+    let baseline_path = std::env::temp_dir().join("parser_cli_compare_baseline.json");
+    fs::write(&baseline_path, baseline.to_string()).unwrap();
+    let output = compare(&baseline_path);
+    fs::remove_file(&baseline_path).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("~ /Title: \"Old Title\" -> "),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_cli_exit_codes_and_completions() {
+    let output = Command::new(env!("CARGO_BIN_EXE_parser_cli"))
+        .args(["--chain", "solana", "-t", "not a transaction", "-o", "yaml"])
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to execute CLI: {e}"));
+    let code = output.status.code().unwrap();
+    assert!((10..=14).contains(&code), "unexpected exit status {code}");
+    assert!(output.stdout.is_empty());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("Error (")
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_parser_cli"))
+        .args(["completions", "bash"])
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to execute CLI: {e}"));
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .contains("visualsign-parser")
+    );
+}
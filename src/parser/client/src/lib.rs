@@ -0,0 +1,315 @@
+//! Client helpers for the parser gRPC service.
+//!
+//! [`ParserClient`] wraps the generated [`ParserServiceClient`] with the resilience every
+//! embedder needs: calls failing with `UNAVAILABLE` are retried with exponential backoff, every
+//! call has a deadline covering its retries, and HTTP/2 keep-alive pings let an idle client
+//! notice a dead connection before its next call. Parse responses in a compression the request
+//! accepted are handed back uncompressed, see [`decompress_payload`].
+#![forbid(unsafe_code)]
+#![deny(clippy::all, clippy::unwrap_used)]
+#![warn(missing_docs)]
+
+use std::future::Future;
+use std::time::Duration;
+
+use generated::parser::parser_service_client::ParserServiceClient;
+use generated::parser::{
+    GetAttestationRequest, GetAttestationResponse, GetCapabilitiesRequest, GetCapabilitiesResponse,
+    ListChainsRequest, ListChainsResponse, ParseBundleRequest, ParseBundleResponse, ParseRequest,
+    ParseResponse, ParsedTransactionPayload, PayloadCompression,
+};
+use generated::tonic::transport::{Channel, Endpoint, Error};
+use generated::tonic::{self, Code, Status};
+use tokio::time::Instant;
+
+/// Largest response the client decodes, the same as the host's own gRPC message limit (25MB).
+pub const MAX_DECODING_MESSAGE_SIZE: usize = 26_214_400;
+
+/// How a [`ParserClient`] retries calls failing with `UNAVAILABLE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per call, including the first one; `1` disables retries.
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled before each further one.
+    pub initial_backoff: Duration,
+    /// Upper bound on the wait between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Attempt each call once.
+    pub const NONE: Self = Self {
+        max_attempts: 1,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    };
+
+    // Wait before the `retry`th retry, counting from 0
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Deadlines, retries and keep-alive of a [`ParserClient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// Deadline for establishing a connection to the host.
+    pub connect_timeout: Duration,
+    /// Deadline for one call, including its retries and the backoff between them.
+    pub call_timeout: Duration,
+    /// Interval between HTTP/2 keep-alive pings, also sent while no call is in flight; `None`
+    /// disables them.
+    pub keep_alive_interval: Option<Duration>,
+    /// How long to wait for a ping to be acknowledged before closing the connection.
+    pub keep_alive_timeout: Duration,
+    /// Retries of calls failing with `UNAVAILABLE`.
+    pub retry: RetryPolicy,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(5),
+            call_timeout: Duration::from_secs(30),
+            keep_alive_interval: Some(Duration::from_secs(30)),
+            keep_alive_timeout: Duration::from_secs(10),
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// A [`ParserServiceClient`] applying a [`ClientConfig`] to every call.
+///
+/// Clones share the same connection.
+#[derive(Clone, Debug)]
+pub struct ParserClient {
+    client: ParserServiceClient<Channel>,
+    config: ClientConfig,
+}
+
+impl ParserClient {
+    /// Connect to the parser host at `uri`, e.g. `http://127.0.0.1:44020`.
+    pub async fn connect(uri: impl Into<String>, config: ClientConfig) -> Result<Self, Error> {
+        let channel = endpoint(uri.into(), &config)?.connect().await?;
+        Ok(Self::new(channel, config))
+    }
+
+    /// Create a client for the parser host at `uri` that connects on its first call, so it can
+    /// be created before the host is up.
+    pub fn connect_lazy(uri: impl Into<String>, config: ClientConfig) -> Result<Self, Error> {
+        let channel = endpoint(uri.into(), &config)?.connect_lazy();
+        Ok(Self::new(channel, config))
+    }
+
+    /// Create a client over an existing `channel`. Only the deadline and retries of `config`
+    /// apply; connection settings are the channel's own.
+    pub fn new(channel: Channel, config: ClientConfig) -> Self {
+        Self {
+            client: ParserServiceClient::new(channel)
+                .max_decoding_message_size(MAX_DECODING_MESSAGE_SIZE),
+            config,
+        }
+    }
+
+    /// The generated client, for calls that manage their own deadline and retries.
+    pub fn inner(&self) -> &ParserServiceClient<Channel> {
+        &self.client
+    }
+
+    /// Parse a transaction, see [`ParserServiceClient::parse`].
+    ///
+    /// Setting `accept_compression` shrinks the response on the wire; the payload is returned
+    /// uncompressed either way.
+    pub async fn parse(&self, request: ParseRequest) -> Result<ParseResponse, Status> {
+        let mut response = self
+            .call(request, |mut client, request| async move {
+                client.parse(request).await
+            })
+            .await?;
+        decompress_payload(&mut response)?;
+        Ok(response)
+    }
+
+    /// Parse transactions approved together into one signed bundle, see
+    /// [`ParserServiceClient::parse_bundle`].
+    pub async fn parse_bundle(
+        &self,
+        request: ParseBundleRequest,
+    ) -> Result<ParseBundleResponse, Status> {
+        self.call(request, |mut client, request| async move {
+            client.parse_bundle(request).await
+        })
+        .await
+    }
+
+    /// Fetch the enclave's attestation, see [`ParserServiceClient::get_attestation`].
+    pub async fn get_attestation(
+        &self,
+        request: GetAttestationRequest,
+    ) -> Result<GetAttestationResponse, Status> {
+        self.call(request, |mut client, request| async move {
+            client.get_attestation(request).await
+        })
+        .await
+    }
+
+    /// List the registered chain parsers, see [`ParserServiceClient::list_chains`].
+    pub async fn list_chains(
+        &self,
+        request: ListChainsRequest,
+    ) -> Result<ListChainsResponse, Status> {
+        self.call(request, |mut client, request| async move {
+            client.list_chains(request).await
+        })
+        .await
+    }
+
+    /// Fetch the enclave's capabilities, see [`ParserServiceClient::get_capabilities`].
+    pub async fn get_capabilities(
+        &self,
+        request: GetCapabilitiesRequest,
+    ) -> Result<GetCapabilitiesResponse, Status> {
+        self.call(request, |mut client, request| async move {
+            client.get_capabilities(request).await
+        })
+        .await
+    }
+
+    // Calls `rpc` with `request` until it succeeds, fails with anything but `UNAVAILABLE`, runs
+    // out of attempts or the call deadline passes. Each attempt tells the host how much of the
+    // deadline is left, so it can give up at the same time.
+    async fn call<Req, Resp, F, Fut>(&self, request: Req, rpc: F) -> Result<Resp, Status>
+    where
+        Req: Clone,
+        F: Fn(ParserServiceClient<Channel>, tonic::Request<Req>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<Resp>, Status>>,
+    {
+        let deadline = Instant::now() + self.config.call_timeout;
+        let mut attempt = 1;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let mut attempt_request = tonic::Request::new(request.clone());
+            attempt_request.set_timeout(remaining);
+
+            let status =
+                match tokio::time::timeout(remaining, rpc(self.client.clone(), attempt_request))
+                    .await
+                {
+                    Ok(Ok(response)) => return Ok(response.into_inner()),
+                    Ok(Err(status)) => status,
+                    Err(_) => {
+                        return Err(Status::deadline_exceeded(format!(
+                            "parser call timed out after {:?}",
+                            self.config.call_timeout
+                        )));
+                    }
+                };
+
+            if status.code() != Code::Unavailable || attempt >= self.config.retry.max_attempts {
+                return Err(status);
+            }
+            let backoff = self.config.retry.backoff(attempt - 1);
+            if Instant::now() + backoff >= deadline {
+                return Err(status);
+            }
+
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Restores the signable payload of a `response` the host sent compressed, so it can be read
+/// and its signature checked like an uncompressed one.
+pub fn decompress_payload(response: &mut ParseResponse) -> Result<(), Status> {
+    let signable_payload = match PayloadCompression::from_i32(response.compression) {
+        Some(PayloadCompression::None) => return Ok(()),
+        Some(PayloadCompression::Zstd) => {
+            let bytes =
+                zstd::stream::decode_all(response.compressed_signable_payload.as_slice())
+                    .map_err(|e| Status::internal(format!("failed to decompress payload: {e}")))?;
+            String::from_utf8(bytes)
+                .map_err(|e| Status::internal(format!("decompressed payload is not UTF-8: {e}")))?
+        }
+        None => {
+            return Err(Status::internal(format!(
+                "unknown payload compression {}",
+                response.compression
+            )));
+        }
+    };
+
+    let parsed_transaction = response
+        .parsed_transaction
+        .get_or_insert_with(Default::default);
+    parsed_transaction.payload = Some(ParsedTransactionPayload { signable_payload });
+    response.compressed_signable_payload = Vec::new();
+    response.compression = PayloadCompression::None as i32;
+    Ok(())
+}
+
+fn endpoint(uri: String, config: &ClientConfig) -> Result<Endpoint, Error> {
+    let mut endpoint = Endpoint::from_shared(uri)?
+        .connect_timeout(config.connect_timeout)
+        .keep_alive_timeout(config.keep_alive_timeout);
+    if let Some(interval) = config.keep_alive_interval {
+        endpoint = endpoint
+            .http2_keep_alive_interval(interval)
+            .keep_alive_while_idle(true);
+    }
+    Ok(endpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_max() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+
+        let backoffs: Vec<_> = (0..5).map(|retry| policy.backoff(retry)).collect();
+        assert_eq!(
+            backoffs,
+            [100, 200, 400, 500, 500].map(Duration::from_millis)
+        );
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn decompress_payload_restores_zstd_payloads() {
+        let signable_payload = r#"{"Fields":[],"Title":"Transfer","Version":"0"}"#;
+        let mut response = ParseResponse {
+            compressed_signable_payload: zstd::bulk::compress(signable_payload.as_bytes(), 3)
+                .expect("payload compresses"),
+            compression: PayloadCompression::Zstd as i32,
+            ..Default::default()
+        };
+
+        decompress_payload(&mut response).expect("payload decompresses");
+        let payload = response.parsed_transaction.and_then(|tx| tx.payload);
+        assert_eq!(
+            payload.map(|payload| payload.signable_payload).as_deref(),
+            Some(signable_payload)
+        );
+        assert!(response.compressed_signable_payload.is_empty());
+        assert_eq!(response.compression, PayloadCompression::None as i32);
+    }
+}
@@ -0,0 +1,128 @@
+//! Admission control in front of the enclave.
+//!
+//! The enclave only buffers [`host_primitives::ENCLAVE_QUEUE_CAPACITY`] messages, so the host
+//! decides up front which parse requests it will serve. A request is admitted while fewer than
+//! `max_in_flight + queue_depth` requests are pending and its client is within its rate limit;
+//! otherwise it is shed immediately with `RESOURCE_EXHAUSTED` rather than waiting in an
+//! unbounded queue. Health checks bypass admission so a saturated host still reports as live.
+
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use generated::tonic::Status;
+use host_primitives::ENCLAVE_QUEUE_CAPACITY;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Sustained rate and burst allowed for each client address.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    /// Requests per second a client may sustain.
+    pub requests_per_second: f64,
+    /// Requests a client may send at once after being idle.
+    pub burst: u32,
+}
+
+/// Sizing of the admission queue.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdmissionConfig {
+    /// Requests allowed to wait for an enclave slot; more are shed.
+    pub queue_depth: usize,
+    /// Per-client rate limit; unset admits every client.
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            queue_depth: ENCLAVE_QUEUE_CAPACITY,
+            rate_limit: None,
+        }
+    }
+}
+
+/// Admits or sheds parse requests. Clones share the same queue and limits.
+#[derive(Clone, Debug)]
+pub struct Admission {
+    pending: Arc<Semaphore>,
+    rate_limit: Option<RateLimit>,
+    buckets: Arc<Mutex<HashMap<IpAddr, TokenBucket>>>,
+}
+
+impl Admission {
+    /// Admission for a pool running up to `max_in_flight` enclave requests at once.
+    pub fn new(config: &AdmissionConfig, max_in_flight: usize) -> Self {
+        Self {
+            pending: Arc::new(Semaphore::new(max_in_flight + config.queue_depth)),
+            rate_limit: config.rate_limit,
+            buckets: Arc::default(),
+        }
+    }
+
+    /// Admits a request from `client`, returning a permit to hold until the request completes.
+    pub fn admit(&self, client: Option<IpAddr>) -> Result<OwnedSemaphorePermit, Status> {
+        if let (Some(limit), Some(client)) = (self.rate_limit, client) {
+            let mut buckets = self
+                .buckets
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let now = Instant::now();
+            // Forget clients whose bucket has refilled, so the map only holds active clients
+            buckets.retain(|_, bucket| !bucket.is_full(&limit, now));
+            if !buckets
+                .entry(client)
+                .or_insert_with(|| TokenBucket::full(&limit, now))
+                .take(&limit, now)
+            {
+                return Err(Status::resource_exhausted(format!(
+                    "rate limit of {} requests per second exceeded for {client}",
+                    limit.requests_per_second
+                )));
+            }
+        }
+
+        Arc::clone(&self.pending)
+            .try_acquire_owned()
+            .map_err(|_| Status::resource_exhausted("parse queue is full, retry later"))
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn full(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(limit.burst),
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * limit.requests_per_second)
+            .min(f64::from(limit.burst));
+        self.updated = now;
+    }
+
+    fn take(&mut self, limit: &RateLimit, now: Instant) -> bool {
+        self.refill(limit, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_full(&self, limit: &RateLimit, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens + elapsed.as_secs_f64() * limit.requests_per_second >= f64::from(limit.burst)
+    }
+}
@@ -0,0 +1,62 @@
+//! Cache of enclave parse responses.
+//!
+//! Approval flows often display the same transaction several times. Responses are deterministic
+//! for a given request, so the host keeps the most recent ones keyed by chain and a SHA-256 of
+//! the encoded request (payload and chain metadata) and answers repeats without an enclave round
+//! trip. Only successful responses are cached.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use generated::parser::{ParseRequest, ParseResponse};
+use generated::prost::Message;
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+/// Identifies a parse request by its chain and a digest of its encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    chain: i32,
+    digest: [u8; 32],
+}
+
+impl CacheKey {
+    /// The cache key of `request`.
+    pub fn of(request: &ParseRequest) -> Self {
+        Self {
+            chain: request.chain,
+            digest: Sha256::digest(request.encode_to_vec()).into(),
+        }
+    }
+}
+
+/// A bounded, least-recently-used response cache. Clones share the same entries.
+#[derive(Clone, Debug)]
+pub struct ResponseCache {
+    entries: Arc<Mutex<LruCache<CacheKey, ParseResponse>>>,
+}
+
+impl ResponseCache {
+    /// A cache holding up to `capacity` responses.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// The cached response for `key`, marking it as recently used.
+    pub fn get(&self, key: &CacheKey) -> Option<ParseResponse> {
+        self.entries().get(key).cloned()
+    }
+
+    /// Stores `response`, evicting the least recently used entry when full.
+    pub fn insert(&self, key: CacheKey, response: ParseResponse) {
+        self.entries().put(key, response);
+    }
+
+    fn entries(&self) -> MutexGuard<'_, LruCache<CacheKey, ParseResponse>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -4,9 +4,18 @@ use futures::future::join_all;
 use std::{
     env,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::NonZeroUsize,
+    path::PathBuf,
     str::FromStr,
+    time::Duration,
 };
 
+use host_primitives::EnclavePoolConfig;
+
+use crate::admission::{AdmissionConfig, RateLimit};
+use crate::config::ConfigHandle;
+use crate::validation::ValidationConfig;
+
 use qos_core::{
     cli::{CID, PORT, USOCK},
     io::SocketAddress,
@@ -18,6 +27,17 @@ const HOST_PORT: &str = "host-port";
 const METRICS: &str = "metrics";
 const METRICS_PORT: &str = "metrics-port";
 const VSOCK_TO_HOST: &str = "vsock-to-host";
+const ENCLAVE_CONNECTIONS: &str = "enclave-connections";
+const MAX_IN_FLIGHT: &str = "max-in-flight";
+const REQUEST_TIMEOUT_MS: &str = "request-timeout-ms";
+const QUEUE_DEPTH: &str = "queue-depth";
+const RATE_LIMIT: &str = "rate-limit";
+const RATE_LIMIT_BURST: &str = "rate-limit-burst";
+const CACHE_SIZE: &str = "cache-size";
+const CONFIG: &str = "config";
+const MAX_PAYLOAD_SIZE: &str = "max-payload-size";
+const DRAIN_TIMEOUT_MS: &str = "drain-timeout-ms";
+const ENCLAVE_BACKENDS: &str = "enclave-backends";
 
 struct HostParser;
 impl GetParserForOptions for HostParser {
@@ -61,6 +81,84 @@ impl GetParserForOptions for HostParser {
 					.takes_value(true)
 					.forbids(vec![USOCK])
 			)
+            .token(
+                Token::new(
+                    ENCLAVE_CONNECTIONS,
+                    "socket connections to open to the enclave (default 1)",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    MAX_IN_FLIGHT,
+                    "parse requests sent to the enclave concurrently; others wait (default 12)",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    REQUEST_TIMEOUT_MS,
+                    "deadline in milliseconds for one enclave request, including queueing",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    QUEUE_DEPTH,
+                    "parse requests allowed to wait for the enclave; more are rejected with RESOURCE_EXHAUSTED (default 12)",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    RATE_LIMIT,
+                    "parse requests per second allowed from each client address (default unlimited)",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    RATE_LIMIT_BURST,
+                    "parse requests a client may send at once under `--rate-limit` (default 1)",
+                )
+                .takes_value(true)
+                .requires(RATE_LIMIT),
+            )
+            .token(
+                Token::new(
+                    CACHE_SIZE,
+                    "parse responses to cache by request hash; 0 or unset disables the cache",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    CONFIG,
+                    "path of a JSON host configuration file, reloaded on SIGHUP",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    MAX_PAYLOAD_SIZE,
+                    "largest unsigned payload in bytes; larger parse requests are rejected with INVALID_ARGUMENT (default 4194304)",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    DRAIN_TIMEOUT_MS,
+                    "milliseconds to wait for in-flight requests after SIGTERM before exiting (default 25000)",
+                )
+                .takes_value(true),
+            )
+            .token(
+                Token::new(
+                    ENCLAVE_BACKENDS,
+                    "comma separated `name=socket` enclaves parse requests can be routed to by `Routes` in `--config`; the socket is a unix socket path, or `cid:port` for VSOCK",
+                )
+                .takes_value(true),
+            )
     }
 }
 
@@ -120,6 +218,148 @@ impl HostOptions {
         }
     }
 
+    /// Additional enclave backends by name, see [`crate::routing`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a backend is not of the form `name=socket`.
+    fn enclave_backends(&self) -> Vec<(String, SocketAddress)> {
+        let Some(backends) = self.parsed.single(ENCLAVE_BACKENDS) else {
+            return vec![];
+        };
+        backends
+            .split(',')
+            .map(|backend| {
+                let (name, socket) = backend
+                    .split_once('=')
+                    .expect("`--enclave-backends` entries must be of the form `name=socket`");
+                (name.trim().to_string(), self.backend_addr(socket.trim()))
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "vsock")]
+    fn backend_addr(&self, socket: &str) -> SocketAddress {
+        match socket.split_once(':') {
+            Some((c, p)) => SocketAddress::new_vsock(
+                c.parse::<u32>()
+                    .expect("could not parse `--enclave-backends` cid to u32"),
+                p.parse::<u32>()
+                    .expect("could not parse `--enclave-backends` port to u32"),
+                self.vsock_to_host_flag(),
+            ),
+            None => SocketAddress::new_unix(socket),
+        }
+    }
+
+    #[cfg(not(feature = "vsock"))]
+    #[allow(clippy::unused_self)]
+    fn backend_addr(&self, socket: &str) -> SocketAddress {
+        SocketAddress::new_unix(socket)
+    }
+
+    /// Connection pool settings for the enclave client, defaulting any unset option.
+    fn pool_config(&self) -> EnclavePoolConfig {
+        let defaults = EnclavePoolConfig::default();
+        EnclavePoolConfig {
+            connections: self.parsed.single(ENCLAVE_CONNECTIONS).map_or(
+                defaults.connections,
+                |c| {
+                    c.parse()
+                        .expect("could not parse `--enclave-connections` to u8")
+                },
+            ),
+            max_in_flight: self
+                .parsed
+                .single(MAX_IN_FLIGHT)
+                .map_or(defaults.max_in_flight, |m| {
+                    m.parse()
+                        .expect("could not parse `--max-in-flight` to usize")
+                }),
+            request_timeout: self.parsed.single(REQUEST_TIMEOUT_MS).map_or(
+                defaults.request_timeout,
+                |t| {
+                    Duration::from_millis(
+                        t.parse()
+                            .expect("could not parse `--request-timeout-ms` to u64"),
+                    )
+                },
+            ),
+        }
+    }
+
+    /// Admission queue settings, defaulting any unset option.
+    fn admission_config(&self) -> AdmissionConfig {
+        let defaults = AdmissionConfig::default();
+        let rate_limit = self.parsed.single(RATE_LIMIT).map(|rate| {
+            let requests_per_second: f64 = rate
+                .parse()
+                .expect("could not parse `--rate-limit` to a number");
+            assert!(
+                requests_per_second > 0.0,
+                "`--rate-limit` must be greater than zero"
+            );
+            RateLimit {
+                requests_per_second,
+                burst: self.parsed.single(RATE_LIMIT_BURST).map_or(1, |b| {
+                    b.parse()
+                        .expect("could not parse `--rate-limit-burst` to u32")
+                }),
+            }
+        });
+        AdmissionConfig {
+            queue_depth: self
+                .parsed
+                .single(QUEUE_DEPTH)
+                .map_or(defaults.queue_depth, |d| {
+                    d.parse().expect("could not parse `--queue-depth` to usize")
+                }),
+            rate_limit,
+        }
+    }
+
+    /// Capacity of the parse response cache, `None` when caching is disabled.
+    fn cache_size(&self) -> Option<NonZeroUsize> {
+        self.parsed.single(CACHE_SIZE).and_then(|size| {
+            NonZeroUsize::new(
+                size.parse()
+                    .expect("could not parse `--cache-size` to usize"),
+            )
+        })
+    }
+
+    /// Parse request validation limits, defaulting any unset option.
+    fn validation_config(&self) -> ValidationConfig {
+        let defaults = ValidationConfig::default();
+        ValidationConfig {
+            max_payload_size: self.parsed.single(MAX_PAYLOAD_SIZE).map_or(
+                defaults.max_payload_size,
+                |s| {
+                    s.parse()
+                        .expect("could not parse `--max-payload-size` to usize")
+                },
+            ),
+        }
+    }
+
+    /// How long to drain in-flight requests on shutdown.
+    fn drain_timeout(&self) -> Duration {
+        self.parsed
+            .single(DRAIN_TIMEOUT_MS)
+            .map_or(crate::host::DEFAULT_DRAIN_TIMEOUT, |t| {
+                Duration::from_millis(
+                    t.parse()
+                        .expect("could not parse `--drain-timeout-ms` to u64"),
+                )
+            })
+    }
+
+    /// Host configuration, loaded from `--config` when given.
+    fn config(&self) -> ConfigHandle {
+        ConfigHandle::load(self.parsed.single(CONFIG).map(PathBuf::from))
+            .expect("could not load `--config`")
+    }
+
     fn ip(&self) -> String {
         self.parsed
             .single(HOST_IP)
@@ -183,15 +423,48 @@ impl CLI {
             return;
         }
 
+        crate::telemetry::init();
+
+        Self::run(&opts).await;
+    }
+
+    /// Run the host with `args`, given as on the command line including the
+    /// program name, without installing the global tracing subscriber.
+    ///
+    /// For embedding the host in another process, e.g. a test harness that
+    /// sets up tracing itself.
+    pub async fn execute_embedded(mut args: Vec<String>) {
+        let opts = HostOptions::new(&mut args);
+        Self::run(&opts).await;
+    }
+
+    async fn run(opts: &HostOptions) {
         let mut handles = vec![];
 
         // host
         let host_addr = opts.host_addr();
         let enclave_addr = opts.enclave_addr();
+        let backends = opts.enclave_backends();
+        let pool_config = opts.pool_config();
+        let admission_config = opts.admission_config();
+        let cache_size = opts.cache_size();
+        let config = opts.config();
+        let validation_config = opts.validation_config();
+        let drain_timeout = opts.drain_timeout();
         handles.push(tokio::spawn(async move {
-            crate::host::Host::listen(host_addr, enclave_addr)
-                .await
-                .expect("`Host::listen` error");
+            crate::host::Host::listen(
+                host_addr,
+                enclave_addr,
+                backends,
+                pool_config,
+                admission_config,
+                cache_size,
+                config,
+                validation_config,
+                drain_timeout,
+            )
+            .await
+            .expect("`Host::listen` error");
         }));
 
         // metrics
@@ -0,0 +1,231 @@
+//! Operator configuration for the host, reloaded on `SIGHUP`.
+//!
+//! The configuration is a JSON file, e.g.
+//! `{"EnabledChains":["CHAIN_ETHEREUM","CHAIN_CUSTOM"],"CustomChains":["Cosmos"],"Charset":"Ascii","RenderBudget":{"MaxFields":40}}`.
+//! Every key is optional; an absent file or key keeps the built-in behavior. Requests for
+//! disabled chains are rejected before they reach the enclave, and the charset policy, render
+//! budget and `IncludeParserInfo` flag are forwarded to the parser through
+//! `ParseRequest.options`, overriding any value the caller sent, so operators can tighten or
+//! relax them without rebuilding the enclave image. The charset policy is always forwarded,
+//! ASCII unless configured, so only the operator can allow Unicode in signed payloads. `Routes` picks the enclave backends serving
+//! each chain, see [`crate::routing`]. A reload that fails to read or validate keeps the
+//! previous configuration.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use generated::parser::{Chain as ProtoChain, ParseRequest};
+use generated::tonic::Status;
+use serde::{Deserialize, Serialize};
+use tokio::signal::unix::{SignalKind, signal};
+use visualsign::CharsetPolicy;
+use visualsign::extensions::{Extensions, RenderBudget};
+
+/// Host behavior an operator can change at runtime.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HostConfig {
+    /// Proto names of the chains served, e.g. `CHAIN_SUI`; unset serves every chain
+    #[serde(rename = "EnabledChains", skip_serializing_if = "Option::is_none")]
+    pub enabled_chains: Option<Vec<String>>,
+    /// Registry names accepted as `ParseRequest.custom_chain` for `CHAIN_CUSTOM` requests
+    #[serde(rename = "CustomChains")]
+    pub custom_chains: Vec<String>,
+    /// Charset policy the parser validates payloads against; unset means ASCII, whatever the
+    /// caller asks for
+    #[serde(rename = "Charset", skip_serializing_if = "Option::is_none")]
+    pub charset: Option<CharsetPolicy>,
+    /// Display limits passed to the parser as a `RenderBudget` extension
+    #[serde(rename = "RenderBudget", skip_serializing_if = "Option::is_none")]
+    pub render_budget: Option<RenderBudget>,
+    /// Whether payloads embed the release of the parser that rendered them
+    #[serde(rename = "IncludeParserInfo", skip_serializing_if = "Option::is_none")]
+    pub include_parser_info: Option<bool>,
+    /// Enclave backends serving each chain, by proto name, in order of preference
+    #[serde(rename = "Routes", skip_serializing_if = "BTreeMap::is_empty")]
+    pub routes: BTreeMap<String, Vec<String>>,
+}
+
+impl HostConfig {
+    /// Reads and validates the configuration at `path`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let config: Self = serde_json::from_str(&contents)
+            .map_err(|e| format!("invalid configuration in {}: {e}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for chain in self.enabled_chains.iter().flatten() {
+            if ProtoChain::from_str_name(chain).is_none() {
+                return Err(format!("unknown chain {chain:?} in EnabledChains"));
+            }
+        }
+        for (chain, backends) in &self.routes {
+            if ProtoChain::from_str_name(chain).is_none() {
+                return Err(format!("unknown chain {chain:?} in Routes"));
+            }
+            if backends.is_empty() {
+                return Err(format!("no enclave backend in Routes for {chain}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects requests for chains this host does not serve and applies the configured parser
+    /// options to `request`.
+    pub fn apply(&self, request: &mut ParseRequest) -> Result<(), Status> {
+        let chain = ProtoChain::from_i32(request.chain)
+            .ok_or_else(|| Status::invalid_argument("invalid chain"))?;
+        let disabled = self
+            .enabled_chains
+            .as_ref()
+            .is_some_and(|enabled| !enabled.iter().any(|name| name == chain.as_str_name()));
+        if disabled {
+            return Err(Status::failed_precondition(format!(
+                "{} is disabled on this host",
+                chain.as_str_name()
+            )));
+        }
+        if chain == ProtoChain::Custom && !self.custom_chains.contains(&request.custom_chain) {
+            return Err(Status::failed_precondition(format!(
+                "custom chain {:?} is not registered on this host",
+                request.custom_chain
+            )));
+        }
+
+        request.options = self.override_options(&request.options)?;
+        Ok(())
+    }
+
+    fn override_options(&self, options: &str) -> Result<String, Status> {
+        let invalid =
+            |e: serde_json::Error| Status::invalid_argument(format!("invalid options: {e}"));
+        let mut options = if options.is_empty() {
+            serde_json::Map::new()
+        } else {
+            serde_json::from_str(options).map_err(invalid)?
+        };
+
+        // Callers never pick the charset, so a request cannot relax it to Unicode
+        let charset = self.charset.unwrap_or(CharsetPolicy::Ascii);
+        options.insert(
+            "Charset".to_string(),
+            serde_json::to_value(charset).map_err(invalid)?,
+        );
+        if let Some(include) = self.include_parser_info {
+            options.insert(
+                "IncludeParserInfo".to_string(),
+                serde_json::Value::Bool(include),
+            );
+        }
+        if let Some(budget) = self.render_budget {
+            let mut extensions: Extensions = match options.remove("Extensions") {
+                Some(extensions) => serde_json::from_value(extensions).map_err(invalid)?,
+                None => Extensions::default(),
+            };
+            extensions.insert(budget);
+            options.insert(
+                "Extensions".to_string(),
+                serde_json::to_value(extensions).map_err(invalid)?,
+            );
+        }
+        serde_json::to_string(&options).map_err(invalid)
+    }
+}
+
+/// The current configuration, shared by every request and swapped on reload.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigHandle {
+    path: Option<PathBuf>,
+    current: Arc<RwLock<Arc<HostConfig>>>,
+}
+
+impl ConfigHandle {
+    /// Loads the configuration at `path`, or the defaults when no path is given.
+    pub fn load(path: Option<PathBuf>) -> Result<Self, String> {
+        let config = match &path {
+            Some(path) => HostConfig::load(path)?,
+            None => HostConfig::default(),
+        };
+        Ok(Self {
+            path,
+            current: Arc::new(RwLock::new(Arc::new(config))),
+        })
+    }
+
+    /// A snapshot of the configuration; later reloads do not affect it.
+    pub fn current(&self) -> Arc<HostConfig> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Re-reads the configuration file, keeping the current configuration on failure.
+    pub fn reload(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let config = HostConfig::load(path)?;
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+        Ok(())
+    }
+
+    /// Reloads the configuration every time the process receives `SIGHUP`.
+    pub async fn reload_on_sighup(self) {
+        let mut hangups = signal(SignalKind::hangup()).expect("failed to create SIGHUP handler");
+        while hangups.recv().await.is_some() {
+            match self.reload() {
+                Ok(()) => tracing::info!(config = ?self.current(), "configuration reloaded"),
+                Err(error) => tracing::error!(%error, "configuration reload failed"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(options: &str) -> ParseRequest {
+        ParseRequest {
+            chain: ProtoChain::Ethereum as i32,
+            options: options.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn charset_of(request: &ParseRequest) -> serde_json::Value {
+        let options: serde_json::Value = serde_json::from_str(&request.options).unwrap();
+        options["Charset"].clone()
+    }
+
+    #[test]
+    fn test_client_cannot_request_unicode() {
+        let mut escalating = request(r#"{"Charset":"Unicode","DecodeTransfers":true}"#);
+        HostConfig::default().apply(&mut escalating).unwrap();
+        assert_eq!(charset_of(&escalating), "Ascii");
+        // Other caller options are kept
+        let options: serde_json::Value = serde_json::from_str(&escalating.options).unwrap();
+        assert_eq!(options["DecodeTransfers"], true);
+
+        let mut empty = request("");
+        HostConfig::default().apply(&mut empty).unwrap();
+        assert_eq!(charset_of(&empty), "Ascii");
+    }
+
+    #[test]
+    fn test_operator_sets_charset() {
+        let config = HostConfig {
+            charset: Some(CharsetPolicy::Unicode),
+            ..Default::default()
+        };
+        let mut unicode = request(r#"{"Charset":"Ascii"}"#);
+        config.apply(&mut unicode).unwrap();
+        assert_eq!(charset_of(&unicode), "Unicode");
+    }
+}
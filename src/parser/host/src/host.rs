@@ -11,57 +11,123 @@
 
 use generated::health::{AppHealthRequest, AppHealthResponse};
 use generated::parser::{
-    ParseRequest, ParseResponse, QosParserRequest, QosParserResponse, parser_service_server,
-    qos_parser_request, qos_parser_response,
+    Chain as ProtoChain, GetAttestationRequest, GetAttestationResponse, GetCapabilitiesRequest,
+    GetCapabilitiesResponse, ListChainsRequest, ListChainsResponse, ParseBundleRequest,
+    ParseBundleResponse, ParseRequest, ParseResponse, ParserInfoRequest, QosParserRequest,
+    QosParserResponse, parser_service_server, qos_parser_request, qos_parser_response,
 };
-use generated::tonic;
-use generated::tonic::{Request, Response, Status};
+use generated::tonic::{Request, Response, Status, metadata::MetadataValue};
+use generated::{qos_hex, tonic};
 use health_check::AppHealthCheckable;
-use host_primitives::{GRPC_MAX_RECV_MSG_SIZE, enclave_client_timeout};
+
+use crate::admission::{Admission, AdmissionConfig};
+use crate::cache::{CacheKey, ResponseCache};
+use crate::config::ConfigHandle;
+use crate::routing::Router;
+use crate::validation::ValidationConfig;
+use futures::FutureExt;
+use health_check::{Draining, K8HealthOptions};
+use host_primitives::{EnclaveClientPool, EnclavePoolConfig, GRPC_MAX_RECV_MSG_SIZE};
 use metrics::request;
 use qos_core::{client::SocketClient, io::SocketAddress};
-use std::time::Instant;
+use std::{
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
+use tracing::Instrument;
 
+use std::sync::{
+    OnceLock,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::oneshot::{self, Sender};
 use tokio::{
     signal::unix::{SignalKind, signal},
     spawn,
 };
 
+/// How long the host waits for in-flight requests after `SIGTERM` by default, short of the
+/// 30 second grace period Kubernetes gives a pod before killing it.
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(25);
+
+/// Metadata key of the caller-supplied id a request is traced under, up to the enclave and back.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAX_REQUEST_ID_LEN: usize = 128;
+
 /// Host `gRPC` server.
 #[derive(Debug)]
 pub struct Host {
-    client: SocketClient,
+    router: Router,
+    admission: Admission,
+    cache: Option<ResponseCache>,
+    config: ConfigHandle,
+    validation: ValidationConfig,
 }
 
 impl Host {
     /// Start the host server.
+    ///
+    /// Parse requests are routed across the enclave at `enclave_addr` and the named `backends`
+    /// by the `Routes` of `config`, see [`crate::routing`].
+    ///
+    /// On `SIGTERM` it reports not ready, stops accepting connections and waits up to
+    /// `drain_timeout` for in-flight requests to finish before returning.
+    #[allow(clippy::too_many_arguments)]
     pub async fn listen(
         listen_addr: std::net::SocketAddr,
         enclave_addr: SocketAddress,
+        backends: Vec<(String, SocketAddress)>,
+        pool_config: EnclavePoolConfig,
+        admission_config: AdmissionConfig,
+        cache_size: Option<NonZeroUsize>,
+        config: ConfigHandle,
+        validation: ValidationConfig,
+        drain_timeout: Duration,
     ) -> Result<(), tonic::transport::Error> {
         let reflection_service = generated::tonic_reflection::server::Builder::configure()
             .register_encoded_file_descriptor_set(generated::FILE_DESCRIPTOR_SET)
             .build()
             .expect("failed to start reflection service");
 
-        let client = SocketClient::single(enclave_addr.clone(), enclave_client_timeout())
-            .expect("unable to create socket client");
+        let router =
+            Router::new(enclave_addr, backends, &pool_config).expect("invalid enclave backends");
+        router
+            .check(&config.current().routes)
+            .expect("invalid `Routes` in `--config`");
+        let default_backend = router.default_backend();
+        let client = default_backend.pool().client().clone();
         let app_checker = ParserHealth {
             client: client.clone(),
         };
         let health_check_service =
             health_check::TkHealthCheck::build_service(client.clone(), app_checker.clone());
-        let k8_health_service = health_check::K8Health::build_service(app_checker);
+        let draining = Draining::default();
+        let k8_health_service = health_check::K8Health::build_service_with_options(
+            app_checker,
+            K8HealthOptions {
+                draining: draining.clone(),
+                enclave_probe: Some(default_backend.probe().clone()),
+            },
+        );
 
-        let host = Host { client };
+        let admission = Admission::new(&admission_config, pool_config.max_in_flight);
+        let host = Host {
+            router,
+            admission,
+            cache: cache_size.map(ResponseCache::new),
+            config: config.clone(),
+            validation,
+        };
 
         println!("HostServer listening on {listen_addr}");
 
         let (sigterm_sender, sigterm_receiver) = oneshot::channel();
         spawn(Self::wait_for_sigterm(sigterm_sender));
+        spawn(config.reload_on_sighup());
+        let sigterm = sigterm_receiver.map(drop).shared();
 
-        tonic::transport::Server::builder()
+        let serve = tonic::transport::Server::builder()
             .add_service(reflection_service)
             .add_service(
                 parser_service_server::ParserServiceServer::new(host)
@@ -69,11 +135,32 @@ impl Host {
             )
             .add_service(health_check_service)
             .add_service(k8_health_service)
-            .serve_with_shutdown(listen_addr, async {
-                sigterm_receiver.await.ok();
-                println!("SIGTERM received");
-            })
-            .await
+            .serve_with_shutdown(listen_addr, {
+                let sigterm = sigterm.clone();
+                async move {
+                    sigterm.await;
+                    draining.start();
+                    println!(
+                        "SIGTERM received, draining in-flight requests for up to {drain_timeout:?}"
+                    );
+                }
+            });
+        let drain_expired = async {
+            sigterm.await;
+            tokio::time::sleep(drain_timeout).await;
+        };
+
+        // Serving ends once every connection has closed, unless the drain timeout passes first
+        let served = tokio::select! {
+            served = serve => served,
+            () = drain_expired => {
+                println!("drain timeout passed, dropping the remaining requests");
+                Ok(())
+            }
+        };
+
+        crate::telemetry::shutdown();
+        served
     }
 
     async fn wait_for_sigterm(sender: Sender<()>) {
@@ -84,34 +171,27 @@ impl Host {
         println!("SIGTERM signal handled, forwarding to host server");
         let _ = sender.send(());
     }
-}
 
-#[tonic::async_trait]
-impl parser_service_server::ParserService for Host {
-    async fn parse(
-        &self,
-        request: Request<ParseRequest>,
+    async fn forward_parse(
+        pool: &EnclaveClientPool,
+        request: ParseRequest,
+        request_id: &str,
+        now: Instant,
     ) -> Result<Response<ParseResponse>, Status> {
-        let now = Instant::now();
-
         let request = QosParserRequest {
-            input: Some(qos_parser_request::Input::ParseRequest(
-                request.into_inner(),
-            )),
+            input: Some(qos_parser_request::Input::ParseRequest(request)),
+            ..Default::default()
         };
 
         let request_decode_elapsed = now.elapsed();
 
         let now_step = Instant::now();
 
-        let raw_output =
-            host_primitives::send_proxy_request::<QosParserRequest, QosParserResponse>(
-                request,
-                &self.client,
-            )
+        let raw_output = pool
+            .send_traced::<QosParserRequest, QosParserResponse>(request, request_id)
             .await;
         let output = raw_output
-            .map_err(|e| Status::internal(format!("Parse: unexpected socket failure: {e:?}")))?
+            .map_err(|e| with_context(&e, "Parse"))?
             .output
             .ok_or_else(|| Status::internal("QosParserResponse::output was None"))?;
 
@@ -154,6 +234,284 @@ impl parser_service_server::ParserService for Host {
     }
 }
 
+#[tonic::async_trait]
+impl parser_service_server::ParserService for Host {
+    async fn parse(
+        &self,
+        request: Request<ParseRequest>,
+    ) -> Result<Response<ParseResponse>, Status> {
+        let now = Instant::now();
+        let client = request.remote_addr().map(|addr| addr.ip());
+        let request_id = request_id(&request);
+        let mut request = request.into_inner();
+
+        let span = tracing::info_span!(
+            "parse",
+            request_id = %request_id,
+            chain = ProtoChain::from_i32(request.chain).map_or("INVALID", |c| c.as_str_name()),
+            payload_size = request.unsigned_payload.len(),
+            duration_ms = tracing::field::Empty,
+            cache_hit = tracing::field::Empty,
+            backend = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+
+        // Malformed requests are rejected before they reach the enclave
+        let config = self.config.current();
+        let checked = self
+            .validation
+            .validate(&request)
+            .and_then(|()| config.apply(&mut request));
+        if let Err(status) = checked {
+            span.record("error", tracing::field::debug(status.code()));
+            return tag_request_id(Err(status), &request_id);
+        }
+
+        let cache_key = self.cache.as_ref().map(|_| CacheKey::of(&request));
+        if let (Some(cache), Some(key)) = (&self.cache, &cache_key) {
+            let cached = cache.get(key);
+            span.record("cache_hit", cached.is_some());
+            if let Some(response) = cached {
+                span.record(
+                    "duration_ms",
+                    u64::try_from(now.elapsed().as_millis()).unwrap_or(u64::MAX),
+                );
+                return tag_request_id(Ok(Response::new(response)), &request_id);
+            }
+        }
+
+        let chain = ProtoChain::from_i32(request.chain).unwrap_or(ProtoChain::Unspecified);
+        let response = match self.admission.admit(client) {
+            // Hold the admission permit until the enclave has answered
+            Ok(_permit) => match self.router.route(chain, &config.routes).await {
+                Ok(backend) => {
+                    span.record("backend", backend.name());
+                    Self::forward_parse(backend.pool(), request, &request_id, now)
+                        .instrument(span.clone())
+                        .await
+                }
+                Err(status) => Err(status),
+            },
+            Err(status) => Err(status),
+        };
+
+        span.record(
+            "duration_ms",
+            u64::try_from(now.elapsed().as_millis()).unwrap_or(u64::MAX),
+        );
+        match &response {
+            Ok(response) => {
+                if let (Some(cache), Some(key)) = (&self.cache, cache_key) {
+                    cache.insert(key, response.get_ref().clone());
+                }
+            }
+            Err(status) => {
+                span.record("error", tracing::field::debug(status.code()));
+            }
+        }
+        tag_request_id(response, &request_id)
+    }
+
+    async fn get_attestation(
+        &self,
+        request: Request<GetAttestationRequest>,
+    ) -> Result<Response<GetAttestationResponse>, Status> {
+        let now = Instant::now();
+        let request_id = request_id(&request);
+
+        let attestation = host_primitives::request_live_attestation(
+            self.router.default_backend().pool().client(),
+        )
+        .await
+        .map_err(|e| with_context(&e, "Attestation"))?;
+
+        let request = QosParserRequest {
+            input: Some(qos_parser_request::Input::ParserInfoRequest(
+                ParserInfoRequest {},
+            )),
+            ..Default::default()
+        };
+        let output = self
+            .router
+            .default_backend()
+            .pool()
+            .send_traced::<QosParserRequest, QosParserResponse>(request, &request_id)
+            .await
+            .map_err(|e| with_context(&e, "Parser Info"))?
+            .output
+            .ok_or_else(|| Status::internal("QosParserResponse::output was None"))?;
+
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        let response = match output {
+            qos_parser_response::Output::ParserInfoResponse(info) => {
+                Ok(Response::new(GetAttestationResponse {
+                    attestation_document: attestation.document,
+                    manifest_digest: attestation
+                        .manifest_digest
+                        .map(|digest| qos_hex::encode(&digest))
+                        .unwrap_or_default(),
+                    parser_versions: info.crate_versions,
+                }))
+            }
+            qos_parser_response::Output::Status(status) => Err(Status::from(status)),
+            _ => Err(Status::internal(format!(
+                "unexpected parser info response: {output:?}"
+            ))),
+        };
+
+        request::track_enclave_request("attestation", response.is_ok(), now.elapsed());
+
+        tag_request_id(response, &request_id)
+    }
+
+    async fn list_chains(
+        &self,
+        request: Request<ListChainsRequest>,
+    ) -> Result<Response<ListChainsResponse>, Status> {
+        let now = Instant::now();
+        let request_id = request_id(&request);
+
+        // The enclave answers from its own registry, so the list matches what it can parse
+        let request = QosParserRequest {
+            input: Some(qos_parser_request::Input::ListChainsRequest(
+                request.into_inner(),
+            )),
+            ..Default::default()
+        };
+        let output = self
+            .router
+            .default_backend()
+            .pool()
+            .send_traced::<QosParserRequest, QosParserResponse>(request, &request_id)
+            .await
+            .map_err(|e| with_context(&e, "List Chains"))?
+            .output
+            .ok_or_else(|| Status::internal("QosParserResponse::output was None"))?;
+
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        let response = match output {
+            qos_parser_response::Output::ListChainsResponse(chains) => Ok(Response::new(chains)),
+            qos_parser_response::Output::Status(status) => Err(Status::from(status)),
+            _ => Err(Status::internal(format!(
+                "unexpected list chains response: {output:?}"
+            ))),
+        };
+
+        request::track_enclave_request("list_chains", response.is_ok(), now.elapsed());
+
+        tag_request_id(response, &request_id)
+    }
+
+    async fn get_capabilities(
+        &self,
+        request: Request<GetCapabilitiesRequest>,
+    ) -> Result<Response<GetCapabilitiesResponse>, Status> {
+        let now = Instant::now();
+        let request_id = request_id(&request);
+
+        // Answered by the enclave, so the flags describe the deployed parsers rather than the host
+        let request = QosParserRequest {
+            input: Some(qos_parser_request::Input::GetCapabilitiesRequest(
+                request.into_inner(),
+            )),
+            ..Default::default()
+        };
+        let output = self
+            .router
+            .default_backend()
+            .pool()
+            .send_traced::<QosParserRequest, QosParserResponse>(request, &request_id)
+            .await
+            .map_err(|e| with_context(&e, "Get Capabilities"))?
+            .output
+            .ok_or_else(|| Status::internal("QosParserResponse::output was None"))?;
+
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        let response = match output {
+            qos_parser_response::Output::GetCapabilitiesResponse(capabilities) => {
+                Ok(Response::new(capabilities))
+            }
+            qos_parser_response::Output::Status(status) => Err(Status::from(status)),
+            _ => Err(Status::internal(format!(
+                "unexpected get capabilities response: {output:?}"
+            ))),
+        };
+
+        request::track_enclave_request("get_capabilities", response.is_ok(), now.elapsed());
+
+        tag_request_id(response, &request_id)
+    }
+
+    async fn parse_bundle(
+        &self,
+        request: Request<ParseBundleRequest>,
+    ) -> Result<Response<ParseBundleResponse>, Status> {
+        let now = Instant::now();
+        let client = request.remote_addr().map(|addr| addr.ip());
+        let request_id = request_id(&request);
+        let mut request = request.into_inner();
+
+        // Every transaction is checked and configured as a single parse request would be
+        let config = self.config.current();
+        for (index, transaction) in request.transactions.iter_mut().enumerate() {
+            let checked = self
+                .validation
+                .validate(transaction)
+                .and_then(|()| config.apply(transaction));
+            if let Err(status) = checked {
+                let status = with_context(&status, &format!("transactions[{index}]"));
+                return tag_request_id(Err(status), &request_id);
+            }
+        }
+
+        // Bundles of one chain follow its route; mixed bundles go to the default backend
+        let chains: Vec<i32> = request.transactions.iter().map(|tx| tx.chain).collect();
+        let chain = match chains.first() {
+            Some(&first) if chains.iter().all(|&chain| chain == first) => {
+                ProtoChain::from_i32(first).unwrap_or(ProtoChain::Unspecified)
+            }
+            _ => ProtoChain::Unspecified,
+        };
+
+        let request = QosParserRequest {
+            input: Some(qos_parser_request::Input::ParseBundleRequest(request)),
+            ..Default::default()
+        };
+        let response = match self.admission.admit(client) {
+            // Hold the admission permit until the enclave has answered
+            Ok(_permit) => match self.router.route(chain, &config.routes).await {
+                Ok(backend) => backend
+                    .pool()
+                    .send_traced::<QosParserRequest, QosParserResponse>(request, &request_id)
+                    .await
+                    .map_err(|e| with_context(&e, "Parse Bundle"))
+                    .and_then(|response| {
+                        response
+                            .output
+                            .ok_or_else(|| Status::internal("QosParserResponse::output was None"))
+                    }),
+                Err(status) => Err(status),
+            },
+            Err(status) => Err(status),
+        };
+
+        let response = match response {
+            Ok(qos_parser_response::Output::ParseBundleResponse(bundle)) => {
+                Ok(Response::new(bundle))
+            }
+            Ok(qos_parser_response::Output::Status(status)) => Err(Status::from(status)),
+            Ok(output) => Err(Status::internal(format!(
+                "unexpected parse bundle response: {output:?}"
+            ))),
+            Err(status) => Err(status),
+        };
+
+        request::track_enclave_request("parse_bundle", response.is_ok(), now.elapsed());
+
+        tag_request_id(response, &request_id)
+    }
+}
+
 #[derive(Clone)]
 struct ParserHealth {
     client: SocketClient,
@@ -167,6 +525,7 @@ impl AppHealthCheckable for ParserHealth {
             input: Some(qos_parser_request::Input::HealthRequest(
                 AppHealthRequest {},
             )),
+            ..Default::default()
         };
 
         let raw_output =
@@ -177,7 +536,7 @@ impl AppHealthCheckable for ParserHealth {
             .await;
 
         let output = raw_output
-            .map_err(|e| Status::internal(format!("App Health: unexpected socket failure: {e:?}")))?
+            .map_err(|e| with_context(&e, "App Health"))?
             .output
             .ok_or_else(|| Status::internal("QosParserResponse::output was None"))?;
 
@@ -197,3 +556,56 @@ impl AppHealthCheckable for ParserHealth {
         response
     }
 }
+
+// The caller's request id from the `x-request-id` header, or a fresh one when it sent none or
+// one too long to log
+fn request_id<T>(request: &Request<T>) -> String {
+    static STARTED: OnceLock<u64> = OnceLock::new();
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    let supplied = request
+        .metadata()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|id| id.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN);
+    if let Some(id) = supplied {
+        return id.to_string();
+    }
+
+    // The start time keeps ids generated before and after a restart apart
+    let started = STARTED.get_or_init(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs())
+    });
+    format!("{started:x}-{}", NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
+// Returns the request id to the caller in the `x-request-id` header, on errors too
+fn tag_request_id<T>(
+    response: Result<Response<T>, Status>,
+    request_id: &str,
+) -> Result<Response<T>, Status> {
+    let Ok(value) = MetadataValue::try_from(request_id) else {
+        return response;
+    };
+    match response {
+        Ok(mut response) => {
+            response.metadata_mut().insert(REQUEST_ID_HEADER, value);
+            Ok(response)
+        }
+        Err(mut status) => {
+            status.metadata_mut().insert(REQUEST_ID_HEADER, value);
+            Err(status)
+        }
+    }
+}
+
+// Prefixes the message of an enclave transport failure while keeping its code and details
+fn with_context(status: &Status, context: &str) -> Status {
+    Status::with_details(
+        status.code(),
+        format!("{context}: {}", status.message()),
+        status.details().to_vec().into(),
+    )
+}
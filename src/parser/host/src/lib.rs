@@ -9,5 +9,11 @@
     clippy::missing_panics_doc
 )]
 
+mod admission;
+mod cache;
 pub mod cli;
+mod config;
 mod host;
+mod routing;
+mod telemetry;
+mod validation;
@@ -0,0 +1,139 @@
+//! Routing of parse requests across several enclaves.
+//!
+//! Besides the enclave given with `--usock` or `--cid`/`--port`, named [`DEFAULT_BACKEND`], the
+//! host can connect to further enclaves, e.g. one per chain family or a blue and a green parser
+//! version. The `Routes` of the host configuration map a chain to the backends serving it in
+//! order of preference, e.g. `{"Routes":{"CHAIN_SUI":["green","default"]}}`; chains without a
+//! route go to the default backend. A request goes to the first backend of its route whose
+//! enclave answers status requests, so a route fails over while its preferred enclave is down,
+//! and is rejected with `UNAVAILABLE` when none does. All other calls go to the default backend.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use generated::parser::Chain as ProtoChain;
+use generated::tonic::Status;
+use health_check::EnclaveProbe;
+use host_primitives::{EnclaveClientPool, EnclavePoolConfig};
+use qos_core::io::SocketAddress;
+
+/// Name of the backend for the enclave given with `--usock` or `--cid`/`--port`.
+pub const DEFAULT_BACKEND: &str = "default";
+
+/// How long a backend health check waits for the enclave to answer a status request.
+const ENCLAVE_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+/// How long a backend health check reuses the enclave's last answer.
+const ENCLAVE_PROBE_TTL: Duration = Duration::from_secs(2);
+
+/// An enclave the host sends requests to, and the health of it.
+#[derive(Clone, Debug)]
+pub struct Backend {
+    name: String,
+    pool: EnclaveClientPool,
+    probe: EnclaveProbe,
+}
+
+impl Backend {
+    fn new(
+        name: String,
+        addr: SocketAddress,
+        pool_config: &EnclavePoolConfig,
+    ) -> Result<Self, String> {
+        let pool = EnclaveClientPool::new(addr, pool_config).map_err(|e| {
+            format!("unable to create enclave client pool for backend {name:?}: {e:?}")
+        })?;
+        let probe = EnclaveProbe::new(
+            pool.client().clone(),
+            ENCLAVE_PROBE_TIMEOUT,
+            ENCLAVE_PROBE_TTL,
+        );
+        Ok(Self { name, pool, probe })
+    }
+
+    /// Name the backend is referred to by in `Routes`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Client pool for the backend's enclave.
+    pub fn pool(&self) -> &EnclaveClientPool {
+        &self.pool
+    }
+
+    /// Cached status probe of the backend's enclave.
+    pub fn probe(&self) -> &EnclaveProbe {
+        &self.probe
+    }
+}
+
+/// Every backend of the host, by name.
+#[derive(Clone, Debug)]
+pub struct Router {
+    backends: HashMap<String, Backend>,
+}
+
+impl Router {
+    /// Connect to the default enclave at `default_addr` and to the named `backends`, each with
+    /// its own client pool configured with `pool_config`.
+    pub fn new(
+        default_addr: SocketAddress,
+        backends: Vec<(String, SocketAddress)>,
+        pool_config: &EnclavePoolConfig,
+    ) -> Result<Self, String> {
+        let mut router = HashMap::new();
+        for (name, addr) in
+            std::iter::once((DEFAULT_BACKEND.to_string(), default_addr)).chain(backends)
+        {
+            if router.contains_key(&name) {
+                return Err(format!("enclave backend {name:?} is defined twice"));
+            }
+            router.insert(name.clone(), Backend::new(name, addr, pool_config)?);
+        }
+        Ok(Self { backends: router })
+    }
+
+    /// The backend for the enclave given with `--usock` or `--cid`/`--port`.
+    pub fn default_backend(&self) -> &Backend {
+        &self.backends[DEFAULT_BACKEND]
+    }
+
+    /// Rejects `routes` naming a backend the host is not connected to.
+    pub fn check(&self, routes: &BTreeMap<String, Vec<String>>) -> Result<(), String> {
+        for (chain, names) in routes {
+            if let Some(name) = names.iter().find(|name| !self.backends.contains_key(*name)) {
+                return Err(format!(
+                    "unknown enclave backend {name:?} in Routes for {chain}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The first healthy backend serving `chain` under `routes`.
+    ///
+    /// Backends unknown to the host, e.g. named by a configuration reloaded since startup, are
+    /// skipped.
+    pub async fn route(
+        &self,
+        chain: ProtoChain,
+        routes: &BTreeMap<String, Vec<String>>,
+    ) -> Result<&Backend, Status> {
+        let Some(names) = routes.get(chain.as_str_name()) else {
+            return Ok(self.default_backend());
+        };
+
+        for name in names {
+            let Some(backend) = self.backends.get(name) else {
+                tracing::warn!(backend = %name, "route names an unknown enclave backend");
+                continue;
+            };
+            if backend.probe.is_ready().await {
+                return Ok(backend);
+            }
+        }
+        Err(Status::unavailable(format!(
+            "no healthy enclave backend for {}, tried {names:?}",
+            chain.as_str_name()
+        )))
+    }
+}
@@ -0,0 +1,68 @@
+//! Tracing setup for the host binary.
+//!
+//! Spans are logged when they close, filtered by `RUST_LOG` (`info` by default). With the `otel`
+//! feature they are also exported over OTLP so operators can chart per-chain latency and error
+//! rates from the `parse` span's `chain` and `error` fields.
+
+use tracing_subscriber::{
+    EnvFilter, fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt,
+};
+
+/// Installs the global tracing subscriber.
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_span_events(FmtSpan::CLOSE));
+
+    #[cfg(feature = "otel")]
+    let subscriber = subscriber.with(otel::layer());
+
+    if subscriber.try_init().is_err() {
+        eprintln!("a global tracing subscriber is already set");
+    }
+}
+
+/// Flushes spans still buffered for export.
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use opentelemetry::{KeyValue, trace::TracerProvider as _};
+    use opentelemetry_sdk::{Resource, runtime, trace::Config};
+    use tracing::Subscriber;
+    use tracing_opentelemetry::OpenTelemetryLayer;
+    use tracing_subscriber::registry::LookupSpan;
+
+    const SERVICE_NAME: &str = "visualsign-parser-host";
+
+    /// The OTLP export layer, or `None` when the exporter cannot be built.
+    pub(super) fn layer<S>() -> Option<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let provider = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(opentelemetry_otlp::new_exporter().tonic())
+            .with_trace_config(
+                Config::default()
+                    .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)])),
+            )
+            .install_batch(runtime::Tokio);
+
+        match provider {
+            Ok(provider) => {
+                let tracer = provider.tracer(SERVICE_NAME);
+                opentelemetry::global::set_tracer_provider(provider);
+                Some(tracing_opentelemetry::layer().with_tracer(tracer))
+            }
+            Err(e) => {
+                eprintln!("OpenTelemetry export disabled: {e}");
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Validation of parse requests before they reach the enclave.
+//!
+//! Requests with an empty or oversized payload, an unknown chain, a `CHAIN_CUSTOM` chain
+//! without a name, or a payload in an encoding the chain's parsers cannot read are rejected
+//! with `INVALID_ARGUMENT` and a `google.rpc.ErrorInfo` detail naming the violated rule, so a
+//! malformed request never crosses the enclave boundary.
+
+use std::collections::HashMap;
+
+use generated::google::rpc::{Code, ErrorInfo, Status as RpcStatus};
+use generated::parser::{Chain as ProtoChain, ParseRequest};
+use generated::prost::Message;
+use generated::prost_types::Any;
+use generated::tonic::Status;
+
+/// `google.rpc.ErrorInfo` domain for requests the host rejects before parsing
+pub const VALIDATION_ERROR_DOMAIN: &str = "visualsign.host";
+
+/// Largest `unsigned_payload` accepted by default, in bytes of its encoded form.
+pub const DEFAULT_MAX_PAYLOAD_SIZE: usize = 4 * 1024 * 1024;
+
+const ERROR_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.ErrorInfo";
+
+/// Limits parse requests are validated against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationConfig {
+    /// Largest `unsigned_payload` accepted, in bytes of its encoded form.
+    pub max_payload_size: usize,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Rejects `request` if it breaks any rule, see the module documentation.
+    pub fn validate(&self, request: &ParseRequest) -> Result<(), Status> {
+        let Some(chain) = ProtoChain::from_i32(request.chain) else {
+            return Err(invalid(
+                "INVALID_CHAIN",
+                format!("invalid chain {}", request.chain),
+                [("chain", request.chain.to_string())],
+            ));
+        };
+        let chain_name = chain.as_str_name().to_string();
+
+        if chain == ProtoChain::Custom && request.custom_chain.is_empty() {
+            return Err(invalid(
+                "MISSING_CUSTOM_CHAIN",
+                "custom_chain is required for CHAIN_CUSTOM".to_string(),
+                [("chain", chain_name)],
+            ));
+        }
+
+        let size = request.unsigned_payload.len();
+        if size == 0 {
+            return Err(invalid(
+                "EMPTY_PAYLOAD",
+                "unsigned_payload is empty".to_string(),
+                [("chain", chain_name)],
+            ));
+        }
+        if size > self.max_payload_size {
+            return Err(invalid(
+                "PAYLOAD_TOO_LARGE",
+                format!(
+                    "unsigned_payload is {size} bytes, more than the {} allowed",
+                    self.max_payload_size
+                ),
+                [
+                    ("chain", chain_name),
+                    ("size", size.to_string()),
+                    ("max_size", self.max_payload_size.to_string()),
+                ],
+            ));
+        }
+
+        if binary_only(chain) && !is_hex_or_base64(&request.unsigned_payload) {
+            return Err(invalid(
+                "UNSUPPORTED_ENCODING",
+                format!("{chain_name} payloads must be hex or base64 encoded"),
+                [("chain", chain_name)],
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// Whether every parser of `chain` only reads hex or base64 encoded bytes. Other chains also
+// accept text forms, e.g. JSON-RPC transactions for Ethereum.
+fn binary_only(chain: ProtoChain) -> bool {
+    matches!(
+        chain,
+        ProtoChain::Bitcoin | ProtoChain::Solana | ProtoChain::Sui | ProtoChain::Tron
+    )
+}
+
+// Whether `payload` only uses the hex (optionally `0x` prefixed) or the standard or URL-safe
+// base64 alphabet. The parsers still reject payloads that do not decode.
+fn is_hex_or_base64(payload: &str) -> bool {
+    let payload = payload.trim();
+    let digits = payload
+        .strip_prefix("0x")
+        .or_else(|| payload.strip_prefix("0X"))
+        .unwrap_or(payload);
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return true;
+    }
+
+    let unpadded = payload.trim_end_matches('=');
+    !unpadded.is_empty()
+        && payload.len() - unpadded.len() <= 2
+        && unpadded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_'))
+}
+
+fn invalid<const N: usize>(reason: &str, message: String, metadata: [(&str, String); N]) -> Status {
+    let info = ErrorInfo {
+        reason: reason.to_string(),
+        domain: VALIDATION_ERROR_DOMAIN.to_string(),
+        metadata: metadata
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect::<HashMap<_, _>>(),
+    };
+    RpcStatus {
+        code: Code::InvalidArgument as i32,
+        message,
+        details: vec![Any {
+            type_url: ERROR_INFO_TYPE_URL.to_string(),
+            value: info.encode_to_vec(),
+        }],
+    }
+    .into()
+}
@@ -0,0 +1,40 @@
+/*
+ * C interface of the visualsign_ffi library.
+ *
+ * Every function returning char * returns a NUL-terminated JSON object owned by the library,
+ * which the caller releases with visualsign_string_free:
+ *
+ *   {"Payload": "<payload JSON>"}                on success
+ *   {"Error": "<message>", "Kind": "<code>"}     on failure, Kind being one of ENCODING,
+ *                                                TRUNCATED, UNSUPPORTED_VARIANT,
+ *                                                UNSUPPORTED_PROTOCOL or INTERNAL_INVARIANT
+ */
+
+#ifndef VISUALSIGN_H
+#define VISUALSIGN_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Parses an encoded transaction (hex, base64, ... as the chain's parser expects) into the
+ * payload JSON the enclave would sign.
+ *
+ * chain:        chain name, e.g. "ethereum", "solana" or a custom chain such as
+ *               "EthereumPersonalSign"
+ * transaction:  the encoded transaction
+ * options_json: NULL, or option overrides as in the gRPC ParseRequest.options field,
+ *               e.g. {"PreviewLayout": true}
+ */
+char *visualsign_parse_transaction(const char *chain, const char *transaction,
+                                   const char *options_json);
+
+/* Releases a string returned by this library. NULL is ignored. */
+void visualsign_string_free(char *ptr);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VISUALSIGN_H */
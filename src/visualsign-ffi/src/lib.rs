@@ -0,0 +1,205 @@
+//! C ABI over the `VisualSign` parsers.
+//!
+//! Mobile and desktop signers embed this library to build the same payloads the enclave does:
+//! [`parse_transaction`] goes through the enclave's converter registry with the enclave's default
+//! options, and returns the payload JSON the enclave signs. Swift, Kotlin and other callers use
+//! the C functions declared in `include/visualsign.h`.
+//!
+//! Every C function returns a NUL-terminated JSON object owned by the library, released with
+//! [`visualsign_string_free`]: `{"Payload": "<payload JSON>"}` on success, or
+//! `{"Error": "<message>", "Kind": "<code>"}` with the code of the error's
+//! [`ParserErrorKind`], e.g. `"TRUNCATED"`. The payload is kept as a string so that callers
+//! can hash or display exactly the bytes the enclave would produce.
+
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::str::FromStr;
+
+use parser_app::registry::create_registry;
+use visualsign::errors::{ParserErrorKind, VisualSignError};
+use visualsign::registry::Chain;
+use visualsign::vsptrait::VisualSignOptions;
+
+/// Parses `transaction`, encoded as the chain's parser expects (hex, base64, ...), into the
+/// payload JSON the enclave would sign.
+///
+/// `chain` is a chain name such as `"ethereum"` or a custom chain such as
+/// `"EthereumPersonalSign"`. `options_json` holds option overrides in the format of the gRPC
+/// `ParseRequest.options` field, e.g. `{"PreviewLayout": true}`; an empty string keeps the
+/// enclave's defaults.
+pub fn parse_transaction(
+    chain: &str,
+    transaction: &str,
+    options_json: &str,
+) -> Result<String, VisualSignError> {
+    let mut options = VisualSignOptions {
+        decode_transfers: true,
+        ..VisualSignOptions::default()
+    };
+    if !options_json.trim().is_empty() {
+        options = options
+            .with_overrides(options_json)
+            .map_err(|e| VisualSignError::ValidationError(format!("invalid options: {e}")))?;
+    }
+    // Unknown names become custom chains, which the registry rejects as unsupported
+    let chain = Chain::from_str(chain).unwrap_or_else(|()| Chain::Custom(chain.to_string()));
+    let outcome =
+        create_registry().convert_transaction_with_warnings(&chain, transaction, options)?;
+    serde_json::to_string(&outcome.payload)
+        .map_err(|e| VisualSignError::SerializationError(e.to_string()))
+}
+
+fn result_json(result: Result<String, VisualSignError>) -> String {
+    match result {
+        Ok(payload) => serde_json::json!({ "Payload": payload }),
+        Err(error) => error_json(&error.to_string(), error.kind()),
+    }
+    .to_string()
+}
+
+fn error_json(message: &str, kind: ParserErrorKind) -> serde_json::Value {
+    serde_json::json!({ "Error": message, "Kind": kind.code() })
+}
+
+/// Reads a C string argument, `name` naming it in the error
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string valid for the duration of the call.
+unsafe fn read_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, VisualSignError> {
+    if ptr.is_null() {
+        return Err(VisualSignError::MissingData(format!("{name} is null")));
+    }
+    // SAFETY: the caller guarantees `ptr` points to a valid NUL-terminated string
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|e| VisualSignError::DecodeError(format!("{name} is not UTF-8: {e}")))
+}
+
+fn into_c_string(json: String) -> *mut c_char {
+    // JSON escapes control characters, so serialized output never contains a NUL byte
+    CString::new(json).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// C entry point of [`parse_transaction`]. `options_json` may be null for the defaults.
+///
+/// Returns a JSON object to release with [`visualsign_string_free`], see the crate
+/// documentation.
+///
+/// # Safety
+///
+/// `chain` and `transaction` must point to NUL-terminated strings, and `options_json` must be
+/// null or point to one, each valid for the duration of the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn visualsign_parse_transaction(
+    chain: *const c_char,
+    transaction: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    // SAFETY: the caller guarantees the pointers are valid C strings or null
+    let args = unsafe {
+        read_arg(chain, "chain").and_then(|chain| {
+            let transaction = read_arg(transaction, "transaction")?;
+            let options = if options_json.is_null() {
+                ""
+            } else {
+                read_arg(options_json, "options")?
+            };
+            Ok((chain, transaction, options))
+        })
+    };
+    let json = match args {
+        Ok((chain, transaction, options)) => {
+            // A panic must not unwind into the caller's frames
+            catch_unwind(AssertUnwindSafe(|| {
+                result_json(parse_transaction(chain, transaction, options))
+            }))
+            .unwrap_or_else(|_| {
+                error_json("parser panicked", ParserErrorKind::InternalInvariant).to_string()
+            })
+        }
+        Err(error) => result_json(Err(error)),
+    };
+    into_c_string(json)
+}
+
+/// Releases a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `ptr` must be null or a string returned by this library that has not been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn visualsign_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        // SAFETY: the caller guarantees `ptr` came from `CString::into_raw` in this library
+        drop(unsafe { CString::from_raw(ptr) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Legacy transfer of 1 ETH, the README's example transaction
+    const ETH_TRANSFER: &str = "0xf86c808504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    fn call(chain: &CStr, transaction: &CStr, options: Option<&CStr>) -> serde_json::Value {
+        let options = options.map_or(std::ptr::null(), CStr::as_ptr);
+        // SAFETY: all pointers are valid C strings or null, and the result is freed once
+        unsafe {
+            let result =
+                visualsign_parse_transaction(chain.as_ptr(), transaction.as_ptr(), options);
+            let json = CStr::from_ptr(result).to_str().unwrap().to_string();
+            visualsign_string_free(result);
+            serde_json::from_str(&json).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_parse_transaction_matches_the_registry() {
+        let transaction = CString::new(ETH_TRANSFER).unwrap();
+        let result = call(c"ethereum", &transaction, None);
+        let payload = result["Payload"].as_str().unwrap();
+        assert_eq!(
+            payload,
+            parse_transaction("ethereum", ETH_TRANSFER, "").unwrap()
+        );
+
+        let payload: serde_json::Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(payload["Fields"][0]["Label"], "Network");
+
+        let result = call(
+            c"ethereum",
+            &transaction,
+            Some(c"{\"PreviewLayout\": true}"),
+        );
+        assert!(result["Payload"].is_string());
+    }
+
+    #[test]
+    fn test_parse_transaction_errors() {
+        let result = call(c"ethereum", c"0x123", None);
+        assert!(result["Error"].is_string());
+        assert_eq!(result["Kind"], "ENCODING");
+
+        let result = call(c"ethereum", c"0x123", Some(c"[]"));
+        assert_eq!(result["Kind"], "INTERNAL_INVARIANT");
+
+        // SAFETY: null arguments are reported as errors
+        let result = unsafe {
+            visualsign_parse_transaction(std::ptr::null(), std::ptr::null(), std::ptr::null())
+        };
+        // SAFETY: `result` is a string returned by the library, freed once
+        let json = unsafe {
+            let json = CStr::from_ptr(result).to_str().unwrap().to_string();
+            visualsign_string_free(result);
+            json
+        };
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap()["Error"],
+            "Missing required data: chain is null"
+        );
+        // SAFETY: null is ignored
+        unsafe { visualsign_string_free(std::ptr::null_mut()) };
+    }
+}
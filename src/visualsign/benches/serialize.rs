@@ -0,0 +1,87 @@
+//! Cost of the `strict-verify` field check when serializing payloads.
+//!
+//! Run it once with the check and once without, then compare the reports:
+//!
+//! ```text
+//! cargo bench -p visualsign --bench serialize
+//! cargo bench -p visualsign --bench serialize --no-default-features
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use visualsign::field_builders::{create_address_field, create_amount_field, create_text_field};
+use visualsign::{
+    SignablePayload, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+/// A payload shaped like a decoded contract call: a few top-level fields followed by one
+/// preview layout per call, each expanding into its own fields
+fn payload(calls: usize) -> SignablePayload {
+    let mut fields = vec![
+        create_text_field("Network", "Ethereum Mainnet")
+            .unwrap()
+            .signable_payload_field,
+        create_amount_field("Value", "0.5", "ETH")
+            .unwrap()
+            .signable_payload_field,
+    ];
+    for i in 0..calls {
+        let expanded = vec![
+            create_address_field(
+                "Recipient",
+                "0x1111111111111111111111111111111111111111",
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap(),
+            create_amount_field("Amount", &format!("{i}.25"), "USDC").unwrap(),
+            create_text_field("Memo", "bench").unwrap(),
+        ];
+        fields.push(SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format!("Transfer {i}"),
+                label: format!("Call {i}"),
+            },
+            preview_layout: SignablePayloadFieldPreviewLayout {
+                title: Some(SignablePayloadFieldTextV2 {
+                    text: format!("Transfer {i}"),
+                }),
+                subtitle: None,
+                condensed: None,
+                expanded: Some(SignablePayloadFieldListLayout { fields: expanded }),
+            },
+        });
+    }
+    SignablePayload::new(
+        0,
+        "Bench Transaction".to_string(),
+        None,
+        fields,
+        "EthereumTx".to_string(),
+    )
+}
+
+fn bench_serialize(c: &mut Criterion) {
+    let name = if cfg!(feature = "strict-verify") {
+        "serialize/strict-verify"
+    } else {
+        "serialize/unverified"
+    };
+    let mut group = c.benchmark_group(name);
+    for calls in [1, 10, 100] {
+        let payload = payload(calls);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(calls),
+            &payload,
+            |b, payload| b.iter(|| serde_json::to_string(black_box(payload)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_serialize);
+criterion_main!(benches);
@@ -23,8 +23,8 @@ struct BadType {
 
 // If you uncomment the following line, it will fail at compile time:
 // impl DeterministicOrdering for BadType {}
-// This would fail because BadType doesn't have a custom Serialize implementation
-// that ensures deterministic ordering
+// DeterministicOrdering is sealed, so only types audited inside visualsign can implement it
+// (see tests/ui/impl_outside_crate.rs)
 
 fn main() {
     // These will compile because they implement DeterministicOrdering
@@ -0,0 +1,149 @@
+//! A scaled token amount with its unit, formatted the same way by every parser.
+//!
+//! [`Amount`] keeps the raw integer in the chain's smallest unit (as decimal digits, so values
+//! wider than `u128` such as `U256` fit), the number of decimals and the display symbol.
+//! Formatting is locale-independent: `.` is the decimal separator, trailing fractional zeros
+//! are removed, and thousands grouping (`,`) is only used by [`Amount::to_grouped_string`] for
+//! prose since `AmountV2` values must stay plain numbers.
+
+use std::fmt;
+
+use crate::errors::VisualSignError;
+use crate::fixed_point::{format_fixed_point, format_fixed_point_digits};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Amount {
+    // Canonical decimal value, already scaled by `decimals`
+    value: String,
+    raw: String,
+    decimals: u8,
+    symbol: String,
+}
+
+impl Amount {
+    pub fn new(raw: u128, decimals: u8, symbol: &str) -> Self {
+        Amount {
+            value: format_fixed_point(raw, decimals),
+            raw: raw.to_string(),
+            decimals,
+            symbol: symbol.to_string(),
+        }
+    }
+
+    /// Builds an amount from a base-10 integer string of any width.
+    pub fn from_raw_digits(raw: &str, decimals: u8, symbol: &str) -> Result<Self, VisualSignError> {
+        let value = format_fixed_point_digits(raw, decimals)?;
+        let raw = match raw.trim_start_matches('0') {
+            "" => "0",
+            digits => digits,
+        };
+        Ok(Amount {
+            value,
+            raw: raw.to_string(),
+            decimals,
+            symbol: symbol.to_string(),
+        })
+    }
+
+    /// The integer amount in the smallest unit.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// The scaled value without grouping, e.g. `"1234.5"`; this is what `AmountV2` carries.
+    pub fn to_decimal_string(&self) -> String {
+        self.value.clone()
+    }
+
+    /// The scaled value with `,` thousands separators, e.g. `"1,234.5"`.
+    pub fn to_grouped_string(&self) -> String {
+        group_thousands(&self.value)
+    }
+}
+
+/// A host-supplied conversion of an amount into a fiat currency, e.g. from a price feed.
+///
+/// The rate source and timestamp travel with the value so the signer can judge how much to
+/// trust it; the fiat value is informational and never part of the signed transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FiatQuote {
+    /// Plain non-negative decimal, e.g. `"1230.5"`
+    pub value: String,
+    /// ISO 4217 code, e.g. `"USD"`
+    pub currency: String,
+    /// Where the exchange rate came from, e.g. `"Chainlink ETH/USD"`
+    pub rate_source: String,
+    /// When the rate was observed, as an RFC 3339 UTC timestamp
+    pub rate_timestamp: String,
+}
+
+// Inserts `,` every three digits of the whole part of a plain decimal string
+pub(crate) fn group_thousands(value: &str) -> String {
+    let (whole, fraction) = match value.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (value, None),
+    };
+
+    let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    match fraction {
+        Some(fraction) => format!("{grouped}.{fraction}"),
+        None => grouped,
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_formatting() {
+        let amount = Amount::new(1_234_500_000, 6, "USDC");
+        assert_eq!(amount.to_decimal_string(), "1234.5");
+        assert_eq!(amount.to_grouped_string(), "1,234.5");
+        assert_eq!(amount.to_string(), "1234.5 USDC");
+        assert_eq!(amount.raw(), "1234500000");
+        assert_eq!(amount.decimals(), 6);
+        assert_eq!(amount.symbol(), "USDC");
+
+        assert_eq!(Amount::new(999, 0, "X").to_grouped_string(), "999");
+        assert_eq!(
+            Amount::new(1_000_000, 0, "X").to_grouped_string(),
+            "1,000,000"
+        );
+        assert_eq!(Amount::new(5, 9, "SUI").to_grouped_string(), "0.000000005");
+    }
+
+    #[test]
+    fn test_amount_from_raw_digits() {
+        let amount = Amount::from_raw_digits("0001500000000000000000", 18, "ETH").unwrap();
+        assert_eq!(amount.raw(), "1500000000000000000");
+        assert_eq!(amount.to_string(), "1.5 ETH");
+
+        let zero = Amount::from_raw_digits("000", 18, "ETH").unwrap();
+        assert_eq!(zero.raw(), "0");
+        assert_eq!(zero.to_decimal_string(), "0");
+
+        assert!(Amount::from_raw_digits("1e18", 18, "ETH").is_err());
+    }
+}
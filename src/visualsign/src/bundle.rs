@@ -0,0 +1,202 @@
+//! Several payloads approved at once.
+//!
+//! Some flows ask the user to sign more than one transaction together: an ERC-20 approval and
+//! the swap that spends it, or a batch of Solana transactions. A [`SignablePayloadBundle`] keeps
+//! their payloads in signing order under one title, with a digest committing to both, so a
+//! wallet can show the whole flow on one approval screen and a signer can check that the set
+//! the user approved is the set it signs.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::VisualSignError;
+use crate::{CharsetPolicy, SignablePayload};
+
+/// Most payloads a bundle may hold
+pub const MAX_BUNDLE_PAYLOADS: usize = 16;
+
+/// Payloads of transactions approved together, in signing order.
+///
+/// Fields are declared in alphabetical order of their serialized names, so the derived
+/// serialization is already deterministic. Build it with [`SignablePayloadBundle::new`], which
+/// computes the digest.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignablePayloadBundle {
+    /// `sha256:` followed by the lowercase hex digest of the canonical JSON of the bundle
+    /// without `Digest`, i.e. `{"Payloads":[...],"Title":"..."}`
+    #[serde(rename = "Digest")]
+    pub digest: String,
+    #[serde(rename = "Payloads")]
+    pub payloads: Vec<SignablePayload>,
+    /// Shown above the payloads, e.g. "Swap USDC for ETH"
+    #[serde(rename = "Title")]
+    pub title: String,
+}
+
+crate::impl_deterministic_serialize!(SignablePayloadBundle);
+
+// The part of a bundle its digest covers
+#[derive(Serialize)]
+struct DigestInput<'a> {
+    #[serde(rename = "Payloads")]
+    payloads: &'a [SignablePayload],
+    #[serde(rename = "Title")]
+    title: &'a str,
+}
+
+fn canonical_json<T: Serialize>(value: &T) -> Result<String, VisualSignError> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| VisualSignError::SerializationError(e.to_string()))?;
+    serde_json::to_string(&crate::sort_json_alphabetically(value))
+        .map_err(|e| VisualSignError::SerializationError(e.to_string()))
+}
+
+fn bundle_digest(title: &str, payloads: &[SignablePayload]) -> Result<String, VisualSignError> {
+    let json = canonical_json(&DigestInput { payloads, title })?;
+    let hash = Sha256::digest(json.as_bytes());
+    let hex: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+    Ok(format!("sha256:{hex}"))
+}
+
+impl SignablePayloadBundle {
+    /// Bundles `payloads`, in signing order, under `title`. A bundle holds between one and
+    /// [`MAX_BUNDLE_PAYLOADS`] payloads.
+    pub fn new(title: String, payloads: Vec<SignablePayload>) -> Result<Self, VisualSignError> {
+        if payloads.is_empty() {
+            return Err(VisualSignError::ValidationError(
+                "a bundle needs at least one payload".to_string(),
+            ));
+        }
+        if payloads.len() > MAX_BUNDLE_PAYLOADS {
+            return Err(VisualSignError::ValidationError(format!(
+                "a bundle holds at most {MAX_BUNDLE_PAYLOADS} payloads, got {}",
+                payloads.len()
+            )));
+        }
+        let digest = bundle_digest(&title, &payloads)?;
+        Ok(SignablePayloadBundle {
+            digest,
+            payloads,
+            title,
+        })
+    }
+
+    /// Checks that `digest` matches the title and payloads.
+    pub fn verify_digest(&self) -> Result<(), VisualSignError> {
+        let expected = bundle_digest(&self.title, &self.payloads)?;
+        if self.digest != expected {
+            return Err(VisualSignError::ValidationError(format!(
+                "bundle digest {} does not match its payloads, expected {expected}",
+                self.digest
+            )));
+        }
+        Ok(())
+    }
+
+    /// The canonical JSON of the bundle: keys sorted at every level, no whitespace, and each
+    /// payload encoded as [`SignablePayload::to_json`] encodes it.
+    pub fn to_json(&self) -> Result<String, VisualSignError> {
+        canonical_json(self)
+    }
+
+    /// Like [`SignablePayload::to_validated_json`]: the title and every payload must pass the
+    /// ASCII charset policy.
+    pub fn to_validated_json(&self) -> Result<String, VisualSignError> {
+        let title = canonical_json(&self.title)?;
+        crate::check_charset(&title, CharsetPolicy::Ascii)
+            .map_err(|message| VisualSignError::ValidationError(format!("{message} in Title")))?;
+        for (index, payload) in self.payloads.iter().enumerate() {
+            payload
+                .validate_charset()
+                .map_err(|e| VisualSignError::ValidationError(format!("Payloads[{index}]: {e}")))?;
+        }
+        self.to_json()
+    }
+
+    /// Decodes a bundle from untrusted JSON, rejecting it when the digest does not match.
+    pub fn from_json(json: &str) -> Result<Self, VisualSignError> {
+        let bundle: SignablePayloadBundle = serde_json::from_str(json)
+            .map_err(|e| VisualSignError::SerializationError(e.to_string()))?;
+        bundle.verify_digest()?;
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::create_text_field;
+    use crate::DeterministicOrdering;
+
+    fn payload(title: &str) -> SignablePayload {
+        SignablePayload::new(
+            0,
+            title.to_string(),
+            None,
+            vec![
+                create_text_field("Network", "Ethereum Mainnet")
+                    .unwrap()
+                    .signable_payload_field,
+            ],
+            "EthereumTx".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_bundle_digest_commits_to_order_and_title() {
+        let bundle = SignablePayloadBundle::new(
+            "Swap USDC for ETH".to_string(),
+            vec![payload("Approve USDC"), payload("Swap")],
+        )
+        .unwrap();
+        assert!(bundle.digest.starts_with("sha256:"));
+        assert_eq!(bundle.digest.len(), "sha256:".len() + 64);
+        assert!(bundle.verify_digest().is_ok());
+
+        let reordered = SignablePayloadBundle::new(
+            "Swap USDC for ETH".to_string(),
+            vec![payload("Swap"), payload("Approve USDC")],
+        )
+        .unwrap();
+        assert_ne!(reordered.digest, bundle.digest);
+        let retitled =
+            SignablePayloadBundle::new("Swap".to_string(), bundle.payloads.clone()).unwrap();
+        assert_ne!(retitled.digest, bundle.digest);
+
+        assert!(SignablePayloadBundle::new("Empty".to_string(), Vec::new()).is_err());
+        let too_many = vec![payload("Swap"); MAX_BUNDLE_PAYLOADS + 1];
+        assert!(SignablePayloadBundle::new("Batch".to_string(), too_many).is_err());
+    }
+
+    #[test]
+    fn test_bundle_canonical_json_round_trip() {
+        let bundle = SignablePayloadBundle::new(
+            "Swap USDC for ETH".to_string(),
+            vec![payload("Approve USDC"), payload("Swap")],
+        )
+        .unwrap();
+        let json = bundle.to_validated_json().unwrap();
+        assert!(json.starts_with(&format!(
+            "{{\"Digest\":\"{}\",\"Payloads\":[{},",
+            bundle.digest,
+            bundle.payloads[0].to_json().unwrap()
+        )));
+        assert!(json.ends_with(",\"Title\":\"Swap USDC for ETH\"}"));
+        assert!(bundle.verify_deterministic_ordering().is_ok());
+
+        let decoded = SignablePayloadBundle::from_json(&json).unwrap();
+        assert_eq!(decoded, bundle);
+        assert_eq!(decoded.to_json().unwrap(), json);
+
+        let tampered = json.replace("Approve USDC", "Approve DAI");
+        assert!(SignablePayloadBundle::from_json(&tampered)
+            .unwrap_err()
+            .to_string()
+            .contains("does not match"));
+
+        let unicode =
+            SignablePayloadBundle::new("Swap \u{2192} ETH".to_string(), bundle.payloads).unwrap();
+        assert!(unicode.to_validated_json().is_err());
+        assert!(unicode.to_json().is_ok());
+    }
+}
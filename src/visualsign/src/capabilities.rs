@@ -0,0 +1,96 @@
+//! What each registered parser accepts and decodes.
+//!
+//! Feature flags name optional behaviour a client may want to gate UI on, such as whether
+//! EIP-4844 transactions render, with `false` for known features that are not supported yet.
+//!
+//! Converters describe themselves through
+//! [`crate::vsptrait::VisualSignConverter::capabilities`], usually from the same tables they
+//! decode with, and [`crate::registry::TransactionConverterRegistry::chain_capabilities`]
+//! collects them, so a listing of supported chains never drifts from the code behind it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::parser_info::ParserInfo;
+use crate::registry::Chain;
+
+/// Input encodings, transaction variants and protocol decoders of one converter.
+///
+/// Fields are declared in alphabetical order of their serialized names, so the derived
+/// serialization is already deterministic.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParserCapabilities {
+    /// Protocols, programs or message types decoded into dedicated fields, beyond the generic
+    /// rendering every transaction gets
+    #[serde(rename = "Decoders", default)]
+    pub decoders: Vec<String>,
+    /// Input encodings accepted by the converter, e.g. "hex" or "base64"
+    #[serde(rename = "Encodings", default)]
+    pub encodings: Vec<String>,
+    /// Feature flags by name, e.g. `eip4844`
+    #[serde(rename = "Features", default)]
+    pub features: BTreeMap<String, bool>,
+    /// Transaction or message variants the converter renders
+    #[serde(rename = "TransactionVariants", default)]
+    pub transaction_variants: Vec<String>,
+}
+
+crate::impl_deterministic_serialize!(ParserCapabilities);
+
+impl ParserCapabilities {
+    pub fn new(encodings: &[&str], transaction_variants: &[&str]) -> Self {
+        ParserCapabilities {
+            decoders: Vec::new(),
+            encodings: encodings.iter().map(|s| s.to_string()).collect(),
+            features: BTreeMap::new(),
+            transaction_variants: transaction_variants.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Adds `decoders`, keeping the list sorted and free of duplicates.
+    pub fn with_decoders<I, S>(mut self, decoders: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.decoders.extend(decoders.into_iter().map(Into::into));
+        self.decoders.sort();
+        self.decoders.dedup();
+        self
+    }
+
+    /// Sets the feature flag `name`.
+    pub fn with_feature(mut self, name: &str, enabled: bool) -> Self {
+        self.features.insert(name.to_string(), enabled);
+        self
+    }
+}
+
+/// The capabilities of the converter registered for one chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainCapabilities {
+    pub chain: Chain,
+    pub parser_info: Option<ParserInfo>,
+    pub capabilities: ParserCapabilities,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeterministicOrdering;
+
+    #[test]
+    fn test_parser_capabilities_serialization() {
+        let capabilities = ParserCapabilities::new(&["hex", "base64"], &["Legacy"])
+            .with_decoders(["Uniswap", "ERC20", "Uniswap"])
+            .with_feature("legacy", true)
+            .with_feature("eip4844", false);
+        assert_eq!(capabilities.decoders, vec!["ERC20", "Uniswap"]);
+        assert!(capabilities.verify_deterministic_ordering().is_ok());
+        assert_eq!(
+            serde_json::to_string(&capabilities).unwrap(),
+            r#"{"Decoders":["ERC20","Uniswap"],"Encodings":["hex","base64"],"Features":{"eip4844":false,"legacy":true},"TransactionVariants":["Legacy"]}"#
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! Warnings for transactions bound to another chain than the one the host presents.
+//!
+//! A wallet showing "Sepolia" while asking to sign a mainnet transaction gets the signer to
+//! move real funds believing they are test funds. The transaction alone cannot tell what the
+//! signer was shown, so the host passes the chain it means as the
+//! [`crate::extensions::ExpectedChainId`] extension and parsers call [`chain_id_warning`] with
+//! the transaction's own chain id.
+
+use crate::field_builders::create_warning_field;
+use crate::SignablePayloadField;
+
+pub const CHAIN_ID_WARNING_LABEL: &str = "Chain ID Warning";
+
+/// Returns a warning field when `chain_id` differs from the `expected` one, or when the
+/// transaction carries none, e.g. a pre-EIP-155 legacy transaction.
+pub fn chain_id_warning(expected: &str, chain_id: Option<&str>) -> Option<SignablePayloadField> {
+    let (label, text, annotation) = match chain_id {
+        Some(chain_id) if chain_id == expected => return None,
+        Some(chain_id) => (
+            "Chain ID Mismatch",
+            format!("the transaction is for chain ID {chain_id}, not the expected {expected}"),
+            "Warning: this transaction executes on another chain than the one shown",
+        ),
+        None => (
+            "Chain ID Missing",
+            format!("the transaction names no chain ID; the expected one is {expected}"),
+            "Warning: this transaction can execute on any chain, the expected one included",
+        ),
+    };
+
+    Some(
+        create_warning_field(
+            CHAIN_ID_WARNING_LABEL,
+            label,
+            &text,
+            annotation,
+            &format!("Warning: {text}"),
+        )
+        .signable_payload_field,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::warning_message;
+
+    #[test]
+    fn test_chain_id_warning() {
+        assert!(chain_id_warning("1", Some("1")).is_none());
+
+        let field = chain_id_warning("11155111", Some("1")).expect("warning expected");
+        assert_eq!(field.label(), CHAIN_ID_WARNING_LABEL);
+        assert_eq!(
+            field.fallback_text(),
+            "Warning: the transaction is for chain ID 1, not the expected 11155111"
+        );
+        assert_eq!(
+            warning_message(&field).signable_payload_field.label(),
+            "Chain ID Mismatch"
+        );
+
+        let field = chain_id_warning("1", None).expect("warning expected");
+        assert_eq!(
+            field.fallback_text(),
+            "Warning: the transaction names no chain ID; the expected one is 1"
+        );
+        assert_eq!(
+            warning_message(&field).signable_payload_field.label(),
+            "Chain ID Missing"
+        );
+    }
+}
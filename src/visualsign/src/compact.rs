@@ -0,0 +1,413 @@
+//! Compact, QR-friendly encoding of a [`SignablePayload`] for air-gapped signers.
+//!
+//! The canonical JSON value (keys sorted, as produced by [`SignablePayload::to_json`]) is
+//! written as deterministic CBOR (RFC 8949 core deterministic encoding with shortest-form
+//! lengths) and then as base45 (RFC 9285), whose alphabet is exactly the QR alphanumeric set.
+//! Payloads too large for one code are split into frames of the form `VS1:<index>/<total>:<data>`
+//! that can be scanned in any order.
+
+use serde_json::{Map, Value};
+
+use crate::errors::VisualSignError;
+use crate::{sort_json_alphabetically, SignablePayload};
+
+/// Alphanumeric capacity of a version 40 QR code at error correction level L.
+pub const QR_ALPHANUMERIC_CAPACITY: usize = 4296;
+
+const BASE45_ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+const FRAME_PREFIX: &str = "VS1";
+// Bounds nesting when decoding untrusted input
+const MAX_DEPTH: usize = 64;
+
+impl SignablePayload {
+    /// Encodes the canonical payload as base45 CBOR that fits in a single QR code.
+    ///
+    /// Fails when the encoding exceeds [`QR_ALPHANUMERIC_CAPACITY`]; use
+    /// [`SignablePayload::to_compact_chunks`] for larger payloads.
+    pub fn to_compact_string(&self) -> Result<String, VisualSignError> {
+        let encoded = base45_encode(&self.to_canonical_cbor()?);
+        if encoded.len() > QR_ALPHANUMERIC_CAPACITY {
+            return Err(VisualSignError::ValidationError(format!(
+                "Compact payload is {} characters, more than the QR capacity of {QR_ALPHANUMERIC_CAPACITY}",
+                encoded.len()
+            )));
+        }
+        Ok(encoded)
+    }
+
+    /// Encodes the canonical payload as frames of at most `max_frame_len` characters each.
+    pub fn to_compact_chunks(&self, max_frame_len: usize) -> Result<Vec<String>, VisualSignError> {
+        let encoded = base45_encode(&self.to_canonical_cbor()?);
+
+        // The header grows with the frame count, so size it for the worst case first
+        let header_len = |total: usize| format!("{FRAME_PREFIX}:{total}/{total}:").len();
+        let mut total = 1;
+        loop {
+            let capacity = max_frame_len
+                .checked_sub(header_len(total))
+                .filter(|capacity| *capacity > 0)
+                .ok_or_else(|| {
+                    VisualSignError::ValidationError(format!(
+                        "Frame length {max_frame_len} is too small for compact payload frames"
+                    ))
+                })?;
+            let needed = encoded.len().div_ceil(capacity).max(1);
+            if needed <= total {
+                let frames = encoded
+                    .as_bytes()
+                    .chunks(capacity)
+                    .enumerate()
+                    .map(|(index, chunk)| {
+                        // base45 output is ASCII, so any byte boundary is a char boundary
+                        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+                        format!("{FRAME_PREFIX}:{}/{needed}:{chunk}", index + 1)
+                    })
+                    .collect();
+                return Ok(frames);
+            }
+            total = needed;
+        }
+    }
+
+    /// Decodes a payload from a single compact string or from all of its frames, in any order.
+    pub fn from_compact_chunks<S: AsRef<str>>(chunks: &[S]) -> Result<Self, VisualSignError> {
+        let encoded = match chunks {
+            [single] if !single.as_ref().starts_with(&format!("{FRAME_PREFIX}:")) => {
+                single.as_ref().to_string()
+            }
+            _ => reassemble(chunks)?,
+        };
+
+        let bytes = base45_decode(&encoded)?;
+        let mut decoder = CborDecoder {
+            bytes: &bytes,
+            position: 0,
+        };
+        let value = decoder.decode(0)?;
+        if decoder.position != bytes.len() {
+            return Err(invalid("trailing bytes after CBOR value"));
+        }
+        serde_json::from_value(value)
+            .map_err(|e| VisualSignError::SerializationError(e.to_string()))
+    }
+
+    fn to_canonical_cbor(&self) -> Result<Vec<u8>, VisualSignError> {
+        let value = serde_json::to_value(self)
+            .map_err(|e| VisualSignError::SerializationError(e.to_string()))?;
+        let mut out = Vec::new();
+        encode_cbor(&sort_json_alphabetically(value), &mut out)?;
+        Ok(out)
+    }
+}
+
+fn invalid(detail: &str) -> VisualSignError {
+    VisualSignError::DecodeError(format!("Invalid compact payload: {detail}"))
+}
+
+fn reassemble<S: AsRef<str>>(chunks: &[S]) -> Result<String, VisualSignError> {
+    let mut parts: Vec<Option<&str>> = Vec::new();
+    for chunk in chunks {
+        let parse = || -> Option<(usize, usize, &str)> {
+            let rest = chunk
+                .as_ref()
+                .strip_prefix(FRAME_PREFIX)?
+                .strip_prefix(':')?;
+            let (position, data) = rest.split_once(':')?;
+            let (index, total) = position.split_once('/')?;
+            Some((index.parse().ok()?, total.parse().ok()?, data))
+        };
+        let (index, total, data) = parse().ok_or_else(|| invalid("malformed frame header"))?;
+
+        if parts.is_empty() {
+            if total == 0 || total > chunks.len() {
+                return Err(invalid("missing frames"));
+            }
+            parts = vec![None; total];
+        }
+        if total != parts.len() || index == 0 || index > total {
+            return Err(invalid("inconsistent frame numbering"));
+        }
+        if parts[index - 1].replace(data).is_some() {
+            return Err(invalid("duplicate frame"));
+        }
+    }
+
+    parts
+        .into_iter()
+        .collect::<Option<String>>()
+        .ok_or_else(|| invalid("missing frames"))
+}
+
+fn base45_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(2) * 3);
+    for pair in bytes.chunks(2) {
+        let (mut value, digits) = match pair {
+            [a, b] => (usize::from(*a) * 256 + usize::from(*b), 3),
+            [a] => (usize::from(*a), 2),
+            _ => unreachable!("chunks(2) yields one or two bytes"),
+        };
+        for _ in 0..digits {
+            out.push(char::from(BASE45_ALPHABET[value % 45]));
+            value /= 45;
+        }
+    }
+    out
+}
+
+fn base45_decode(encoded: &str) -> Result<Vec<u8>, VisualSignError> {
+    let digits = encoded
+        .bytes()
+        .map(|c| BASE45_ALPHABET.iter().position(|&a| a == c))
+        .collect::<Option<Vec<usize>>>()
+        .ok_or_else(|| invalid("character outside the base45 alphabet"))?;
+
+    let mut out = Vec::with_capacity(digits.len() / 3 * 2 + 1);
+    for group in digits.chunks(3) {
+        match group {
+            [c, d, e] => {
+                let value = c + d * 45 + e * 45 * 45;
+                let value = u16::try_from(value).map_err(|_| invalid("base45 group overflow"))?;
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            [c, d] => {
+                let value =
+                    u8::try_from(c + d * 45).map_err(|_| invalid("base45 group overflow"))?;
+                out.push(value);
+            }
+            _ => return Err(invalid("truncated base45 data")),
+        }
+    }
+    Ok(out)
+}
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const SIMPLE_FALSE: u8 = 0xf4;
+const SIMPLE_TRUE: u8 = 0xf5;
+const SIMPLE_NULL: u8 = 0xf6;
+
+fn encode_head(major: u8, argument: u64, out: &mut Vec<u8>) {
+    let major = major << 5;
+    match argument {
+        0..=23 => out.push(major | argument as u8),
+        24..=0xff => out.extend([major | 24, argument as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend((argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend((argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend(argument.to_be_bytes());
+        }
+    }
+}
+
+fn encode_cbor(value: &Value, out: &mut Vec<u8>) -> Result<(), VisualSignError> {
+    match value {
+        Value::Null => out.push(SIMPLE_NULL),
+        Value::Bool(false) => out.push(SIMPLE_FALSE),
+        Value::Bool(true) => out.push(SIMPLE_TRUE),
+        Value::Number(number) => {
+            if let Some(unsigned) = number.as_u64() {
+                encode_head(MAJOR_UNSIGNED, unsigned, out);
+            } else if let Some(negative) = number.as_i64() {
+                // CBOR stores -1 - n for negative integers
+                encode_head(MAJOR_NEGATIVE, !(negative as u64), out);
+            } else {
+                return Err(VisualSignError::SerializationError(format!(
+                    "Non-integer number {number} has no canonical compact encoding"
+                )));
+            }
+        }
+        Value::String(text) => {
+            encode_head(MAJOR_TEXT, text.len() as u64, out);
+            out.extend_from_slice(text.as_bytes());
+        }
+        Value::Array(items) => {
+            encode_head(MAJOR_ARRAY, items.len() as u64, out);
+            for item in items {
+                encode_cbor(item, out)?;
+            }
+        }
+        Value::Object(map) => {
+            // Deterministic CBOR orders keys by their encoded bytes: shorter keys first, then
+            // bytewise, which differs from the alphabetical JSON order
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+            encode_head(MAJOR_MAP, entries.len() as u64, out);
+            for (key, item) in entries {
+                encode_cbor(&Value::String(key.clone()), out)?;
+                encode_cbor(item, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+struct CborDecoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl CborDecoder<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], VisualSignError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| invalid("unexpected end of CBOR data"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn argument(&mut self, additional: u8) -> Result<u64, VisualSignError> {
+        let width = match additional {
+            0..=23 => return Ok(u64::from(additional)),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(invalid("unsupported CBOR length encoding")),
+        };
+        let bytes = self.take(width)?;
+        Ok(bytes.iter().fold(0, |acc, b| (acc << 8) | u64::from(*b)))
+    }
+
+    fn length(&mut self, additional: u8) -> Result<usize, VisualSignError> {
+        let length = usize::try_from(self.argument(additional)?)
+            .map_err(|_| invalid("CBOR length too large"))?;
+        // Every item takes at least one byte, which bounds allocations on hostile input
+        if length > self.bytes.len() - self.position {
+            return Err(invalid("CBOR length exceeds remaining data"));
+        }
+        Ok(length)
+    }
+
+    fn decode(&mut self, depth: usize) -> Result<Value, VisualSignError> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("CBOR nesting too deep"));
+        }
+        let initial = self.take(1)?[0];
+        let (major, additional) = (initial >> 5, initial & 0x1f);
+        match major {
+            MAJOR_UNSIGNED => Ok(Value::from(self.argument(additional)?)),
+            MAJOR_NEGATIVE => {
+                let encoded = i64::try_from(self.argument(additional)?)
+                    .map_err(|_| invalid("negative integer out of range"))?;
+                Ok(Value::from(-1 - encoded))
+            }
+            MAJOR_TEXT => {
+                let length = self.length(additional)?;
+                let text = std::str::from_utf8(self.take(length)?)
+                    .map_err(|_| invalid("text is not UTF-8"))?;
+                Ok(Value::String(text.to_string()))
+            }
+            MAJOR_ARRAY => {
+                let length = self.length(additional)?;
+                let items = (0..length)
+                    .map(|_| self.decode(depth + 1))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Array(items))
+            }
+            MAJOR_MAP => {
+                let length = self.length(additional)?;
+                let mut map = Map::new();
+                for _ in 0..length {
+                    let Value::String(key) = self.decode(depth + 1)? else {
+                        return Err(invalid("map key is not text"));
+                    };
+                    let item = self.decode(depth + 1)?;
+                    if map.insert(key, item).is_some() {
+                        return Err(invalid("duplicate map key"));
+                    }
+                }
+                Ok(Value::Object(map))
+            }
+            _ => match initial {
+                SIMPLE_FALSE => Ok(Value::Bool(false)),
+                SIMPLE_TRUE => Ok(Value::Bool(true)),
+                SIMPLE_NULL => Ok(Value::Null),
+                _ => Err(invalid("unsupported CBOR item")),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::{create_amount_field, create_text_field};
+
+    fn payload(memo: &str) -> SignablePayload {
+        SignablePayload::new(
+            0,
+            "Transfer".to_string(),
+            Some("Send 1 ETH".to_string()),
+            vec![
+                create_amount_field("Amount", "1", "ETH")
+                    .unwrap()
+                    .signable_payload_field,
+                create_text_field("Memo", memo)
+                    .unwrap()
+                    .signable_payload_field,
+            ],
+            "EthereumTx".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_base45_rfc9285_vectors() {
+        assert_eq!(base45_encode(b"AB"), "BB8");
+        assert_eq!(base45_encode(b"Hello!!"), "%69 VD92EX0");
+        assert_eq!(base45_encode(b"base-45"), "UJCLQE7W581");
+        assert_eq!(base45_decode("QED8WEX0").unwrap(), b"ietf!");
+        assert!(base45_decode("GGW").is_err());
+        assert!(base45_decode("a").is_err());
+    }
+
+    #[test]
+    fn test_compact_string_round_trip() {
+        let payload = payload("gm");
+        let compact = payload.to_compact_string().unwrap();
+        assert!(compact.bytes().all(|c| BASE45_ALPHABET.contains(&c)));
+        // Deterministic across calls
+        assert_eq!(compact, payload.to_compact_string().unwrap());
+
+        let decoded = SignablePayload::from_compact_chunks(&[compact]).unwrap();
+        assert_eq!(decoded, payload);
+        assert_eq!(decoded.to_json().unwrap(), payload.to_json().unwrap());
+    }
+
+    #[test]
+    fn test_compact_chunks_round_trip_in_any_order() {
+        let payload = payload(&"long memo ".repeat(600));
+        assert!(payload.to_compact_string().is_err());
+
+        let mut chunks = payload.to_compact_chunks(1000).unwrap();
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 1000));
+        assert!(chunks[0].starts_with(&format!("VS1:1/{}:", chunks.len())));
+
+        chunks.reverse();
+        let decoded = SignablePayload::from_compact_chunks(&chunks).unwrap();
+        assert_eq!(decoded, payload);
+
+        chunks.pop();
+        assert!(SignablePayload::from_compact_chunks(&chunks).is_err());
+        let duplicated = vec![chunks[0].clone(), chunks[0].clone()];
+        assert!(SignablePayload::from_compact_chunks(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_compact_chunks_reject_tiny_frames() {
+        assert!(payload("gm").to_compact_chunks(8).is_err());
+    }
+}
@@ -0,0 +1,67 @@
+//! How much of a transaction a payload actually explains.
+//!
+//! Parsers fall back to raw hex for calldata or instructions they cannot decode, and a payload
+//! made mostly of such fields is effectively blind signing. [`Coverage`] reports the share of
+//! the raw input bytes that were decoded into structured fields, so a policy engine can refuse
+//! low-coverage transactions. It is carried on [`crate::outcome::ParseOutcome`] and embedded
+//! in the validated payload as `Coverage` when
+//! [`crate::vsptrait::OutputFlags::include_coverage`] is set.
+
+use serde::{Deserialize, Serialize};
+
+/// Decoded versus total raw input bytes.
+///
+/// Fields are declared in alphabetical order of their serialized names, so the derived
+/// serialization is already deterministic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coverage {
+    #[serde(rename = "DecodedBytes")]
+    pub decoded_bytes: usize,
+    /// Share of the input that was decoded, rounded down; 100 for an empty input
+    #[serde(rename = "Percent")]
+    pub percent: u8,
+    #[serde(rename = "TotalBytes")]
+    pub total_bytes: usize,
+}
+
+crate::impl_deterministic_serialize!(Coverage);
+
+impl Coverage {
+    /// Coverage of a `total_bytes` input of which `opaque_bytes` were only shown raw.
+    pub fn new(total_bytes: usize, opaque_bytes: usize) -> Self {
+        let decoded_bytes = total_bytes.saturating_sub(opaque_bytes);
+        let percent = if total_bytes == 0 {
+            100
+        } else {
+            // decoded_bytes <= total_bytes, so this is at most 100
+            (decoded_bytes as u128 * 100 / total_bytes as u128) as u8
+        };
+        Coverage {
+            decoded_bytes,
+            percent,
+            total_bytes,
+        }
+    }
+
+    /// An input that was decoded in full.
+    pub fn full(total_bytes: usize) -> Self {
+        Coverage::new(total_bytes, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_percent() {
+        assert_eq!(Coverage::new(200, 50).percent, 75);
+        assert_eq!(Coverage::new(3, 1).percent, 66);
+        assert_eq!(Coverage::new(10, 20).decoded_bytes, 0);
+        assert_eq!(Coverage::full(0).percent, 100);
+        assert_eq!(
+            serde_json::to_string(&Coverage::new(4, 1)).unwrap(),
+            r#"{"DecodedBytes":3,"Percent":75,"TotalBytes":4}"#
+        );
+    }
+}
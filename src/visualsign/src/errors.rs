@@ -1,5 +1,72 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Chain-independent classification of parser failures, suitable as a machine-readable error
+/// code for callers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ParserErrorKind {
+    /// The input is not valid in its encoding (hex, base64, RLP, BCS, protobuf, ...)
+    Encoding,
+    /// The input ends before a complete transaction was read
+    Truncated,
+    /// The transaction type or version is recognized but not supported
+    UnsupportedVariant,
+    /// The chain, program or protocol has no converter
+    UnsupportedProtocol,
+    /// The parser could not build a valid payload from an accepted transaction
+    InternalInvariant,
+}
+
+impl ParserErrorKind {
+    /// Stable upper-case code, e.g. `"UNSUPPORTED_VARIANT"`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParserErrorKind::Encoding => "ENCODING",
+            ParserErrorKind::Truncated => "TRUNCATED",
+            ParserErrorKind::UnsupportedVariant => "UNSUPPORTED_VARIANT",
+            ParserErrorKind::UnsupportedProtocol => "UNSUPPORTED_PROTOCOL",
+            ParserErrorKind::InternalInvariant => "INTERNAL_INVARIANT",
+        }
+    }
+}
+
+/// A chain parser's own error, classified by [`ParserErrorKind`].
+///
+/// `reason` names the parser's error variant (e.g. `"UnexpectedTrailingData"`) so callers can
+/// tell chain-specific cases apart without matching on `message`; `offset` is the byte offset in
+/// the decoded input where decoding failed, when the parser knows it.
+#[derive(Debug, Clone, Eq, PartialEq, Error, Serialize, Deserialize)]
+#[error("{message}")]
+pub struct ParserError {
+    #[serde(rename = "Kind")]
+    pub kind: ParserErrorKind,
+    #[serde(rename = "Chain")]
+    pub chain: String,
+    #[serde(rename = "Reason")]
+    pub reason: String,
+    #[serde(rename = "Message")]
+    pub message: String,
+    #[serde(rename = "Offset", default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+impl ParserError {
+    pub fn new(kind: ParserErrorKind, chain: &str, reason: &str, message: String) -> Self {
+        ParserError {
+            kind,
+            chain: chain.to_string(),
+            reason: reason.to_string(),
+            message,
+            offset: None,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
 /// Errors that can occur during transaction parsing
 #[derive(Debug, Eq, PartialEq, Error)]
 pub enum TransactionParseError {
@@ -11,6 +78,20 @@ pub enum TransactionParseError {
     UnsupportedVersion(String),
     #[error("Unsupported encoding format: {0}")]
     UnsupportedEncoding(String),
+    #[error("{0}")]
+    Parser(#[from] ParserError),
+}
+
+impl TransactionParseError {
+    pub fn kind(&self) -> ParserErrorKind {
+        match self {
+            TransactionParseError::InvalidFormat(_)
+            | TransactionParseError::DecodeError(_)
+            | TransactionParseError::UnsupportedEncoding(_) => ParserErrorKind::Encoding,
+            TransactionParseError::UnsupportedVersion(_) => ParserErrorKind::UnsupportedVariant,
+            TransactionParseError::Parser(error) => error.kind,
+        }
+    }
 }
 
 // Our library's custom, top-level error type.
@@ -37,4 +118,73 @@ pub enum VisualSignError {
     InvariantViolation(String),
     #[error("Serialization failed: {0}")]
     SerializationError(String),
+    #[error("{0}")]
+    Parser(#[from] ParserError),
+}
+
+impl VisualSignError {
+    /// Classifies the error; errors raised while building fields count as internal invariants.
+    pub fn kind(&self) -> ParserErrorKind {
+        match self {
+            VisualSignError::ParseError(error) => error.kind(),
+            VisualSignError::DecodeError(_) | VisualSignError::InvalidNumberField(_) => {
+                ParserErrorKind::Encoding
+            }
+            VisualSignError::MissingData(_) => ParserErrorKind::Truncated,
+            VisualSignError::ConversionError(_) => ParserErrorKind::UnsupportedProtocol,
+            VisualSignError::MissingField(_)
+            | VisualSignError::EmptyField(_)
+            | VisualSignError::ValidationError(_)
+            | VisualSignError::InvariantViolation(_)
+            | VisualSignError::SerializationError(_) => ParserErrorKind::InternalInvariant,
+            VisualSignError::Parser(error) => error.kind,
+        }
+    }
+
+    /// The chain-specific detail, when a parser reported one.
+    pub fn parser_error(&self) -> Option<&ParserError> {
+        match self {
+            VisualSignError::Parser(error)
+            | VisualSignError::ParseError(TransactionParseError::Parser(error)) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kinds() {
+        let detail = ParserError::new(
+            ParserErrorKind::Truncated,
+            "Ethereum",
+            "TruncatedTransaction",
+            "Truncated transaction: input too short".to_string(),
+        );
+        let error = VisualSignError::ParseError(detail.clone().into());
+        assert_eq!(error.kind(), ParserErrorKind::Truncated);
+        assert_eq!(error.kind().code(), "TRUNCATED");
+        assert_eq!(error.parser_error(), Some(&detail));
+        assert_eq!(
+            error.to_string(),
+            "Failed to parse transaction: Truncated transaction: input too short"
+        );
+        assert_eq!(
+            serde_json::to_string(&detail).unwrap(),
+            r#"{"Kind":"Truncated","Chain":"Ethereum","Reason":"TruncatedTransaction","Message":"Truncated transaction: input too short"}"#
+        );
+
+        let error = VisualSignError::ConversionError("No converter".to_string());
+        assert_eq!(error.kind(), ParserErrorKind::UnsupportedProtocol);
+        assert_eq!(error.parser_error(), None);
+        assert_eq!(
+            VisualSignError::ParseError(TransactionParseError::UnsupportedVersion(
+                "v2".to_string()
+            ))
+            .kind(),
+            ParserErrorKind::UnsupportedVariant
+        );
+    }
 }
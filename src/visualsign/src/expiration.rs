@@ -0,0 +1,89 @@
+//! The "Expiration" field telling the signer whether and when a transaction stops being valid.
+//!
+//! Chains bound a transaction's lifetime in different terms: Tron by wall-clock time, Sui by
+//! epoch, Cosmos by block height, Solana by the age of its recent blockhash, and some
+//! transactions never expire at all. Parsers describe theirs as an [`Expiration`] and render it
+//! with [`create_expiration_field`], so every chain shows it under the same label and a
+//! transaction without a bound says so instead of leaving the field out.
+
+use crate::errors::VisualSignError;
+use crate::field_builders::{create_text_field, create_timestamp_field_from_millis};
+use crate::AnnotatedPayloadField;
+
+pub const EXPIRATION_LABEL: &str = "Expiration";
+
+/// When a transaction stops being accepted by the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expiration {
+    /// Valid until it executes or is replaced
+    Never,
+    /// Rejected after this Unix time in milliseconds
+    Timestamp(i64),
+    /// Rejected once this epoch has ended
+    Epoch(u64),
+    /// Rejected in blocks above this height
+    BlockHeight(u64),
+    /// A bound the parser can only describe, e.g. one relative to a block it cannot date
+    Described(String),
+}
+
+impl Expiration {
+    /// The expiration in words, as shown for every kind but a timestamp.
+    pub fn text(&self) -> String {
+        match self {
+            Expiration::Never => "Never".to_string(),
+            Expiration::Timestamp(unix_millis) => format!("{unix_millis} ms"),
+            Expiration::Epoch(epoch) => format!("End of epoch {epoch}"),
+            Expiration::BlockHeight(height) => format!("Block height {height}"),
+            Expiration::Described(text) => text.clone(),
+        }
+    }
+}
+
+/// The "Expiration" field for `expiration`; a timestamp becomes a timestamp field, falling
+/// back to the raw milliseconds when it is out of range.
+pub fn create_expiration_field(
+    expiration: &Expiration,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    if let Expiration::Timestamp(unix_millis) = expiration {
+        if let Ok(field) = create_timestamp_field_from_millis(EXPIRATION_LABEL, *unix_millis) {
+            return Ok(field);
+        }
+    }
+    create_text_field(EXPIRATION_LABEL, &expiration.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SignablePayloadField;
+
+    #[test]
+    fn test_expiration_field() {
+        let field = create_expiration_field(&Expiration::Timestamp(1_700_000_000_000)).unwrap();
+        assert!(matches!(
+            field.signable_payload_field,
+            SignablePayloadField::Timestamp { .. }
+        ));
+        assert_eq!(field.signable_payload_field.label(), EXPIRATION_LABEL);
+        assert_eq!(
+            field.signable_payload_field.fallback_text(),
+            "2023-11-14 22:13:20 UTC"
+        );
+
+        let text = |expiration: Expiration| {
+            create_expiration_field(&expiration)
+                .unwrap()
+                .signable_payload_field
+                .fallback_text()
+                .clone()
+        };
+        assert_eq!(text(Expiration::Never), "Never");
+        assert_eq!(text(Expiration::Epoch(42)), "End of epoch 42");
+        assert_eq!(text(Expiration::BlockHeight(100)), "Block height 100");
+        assert_eq!(
+            text(Expiration::Timestamp(i64::MAX)),
+            format!("{} ms", i64::MAX)
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Typed, serializable extensions carried in [`crate::vsptrait::VisualSignOptions`].
+//!
+//! Hosts attach context a parser cannot derive from the transaction itself: token metadata,
+//! address labels, the signer's locale, ABI/IDL blobs, a render budget, the account's
+//! expected nonce, the chain the transaction is expected on and how a new contract is
+//! deployed. Each kind has its own Rust type and appears at most once, so parsers look
+//! values up by type instead of digging through untyped metadata:
+//!
+//! ```
+//! use visualsign::extensions::{Extensions, Locale};
+//!
+//! let mut extensions = Extensions::default();
+//! extensions.insert(Locale("en-US".to_string()));
+//! assert_eq!(extensions.get::<Locale>(), Some(&Locale("en-US".to_string())));
+//! ```
+//!
+//! On the wire the map is a list of `{"Kind": ..., "Value": ...}` objects sorted by kind, which
+//! lets the options cross the enclave boundary as JSON.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use generated::parser::{chain_metadata, ChainMetadata};
+
+/// The well-known extension kinds, in wire order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ExtensionKind {
+    TokenMetadata,
+    AddressLabels,
+    Locale,
+    InterfaceDefinitions,
+    RenderBudget,
+    ExpectedNonce,
+    ExpectedChainId,
+    Create2Deployment,
+}
+
+/// Display metadata for one token, keyed by its contract address or mint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    #[serde(rename = "Address")]
+    pub address: String,
+    #[serde(rename = "Symbol")]
+    pub symbol: String,
+    #[serde(rename = "Decimals")]
+    pub decimals: u8,
+    #[serde(rename = "Name", default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Solana: the account that can freeze token accounts of the mint
+    #[serde(
+        rename = "FreezeAuthority",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub freeze_authority: Option<String>,
+    /// Solana Token-2022: the permanent delegate, which can transfer or burn from any holder
+    #[serde(
+        rename = "PermanentDelegate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub permanent_delegate: Option<String>,
+}
+
+/// Token metadata known to the host.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata(pub Vec<TokenInfo>);
+
+impl TokenMetadata {
+    /// Finds a token by address, ignoring ASCII case so hex addresses match in any checksum.
+    pub fn find(&self, address: &str) -> Option<&TokenInfo> {
+        self.0
+            .iter()
+            .find(|token| token.address.eq_ignore_ascii_case(address))
+    }
+}
+
+/// Host-supplied names for addresses, e.g. from the signer's address book.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressLabels(pub BTreeMap<String, String>);
+
+/// The signer's BCP 47 locale, e.g. `"en-US"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Locale(pub String);
+
+/// What an [`InterfaceDefinition`] blob contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InterfaceFormat {
+    /// Ethereum JSON ABI
+    Abi,
+    /// Solana JSON IDL
+    Idl,
+}
+
+/// A contract ABI or program IDL the parser may use to decode calls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceDefinition {
+    /// Contract or program the definition applies to; unset applies it to any target
+    #[serde(rename = "Address", default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(rename = "Format")]
+    pub format: InterfaceFormat,
+    /// The JSON definition as supplied by the host
+    #[serde(rename = "Definition")]
+    pub definition: String,
+}
+
+/// ABI and IDL blobs supplied by the host.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceDefinitions(pub Vec<InterfaceDefinition>);
+
+/// Limits on how much the signer's device can display; unset limits are unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderBudget {
+    #[serde(rename = "MaxFields", default, skip_serializing_if = "Option::is_none")]
+    pub max_fields: Option<u32>,
+    #[serde(
+        rename = "MaxTextLength",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_text_length: Option<u32>,
+}
+
+/// The account's next nonce or sequence number as the host knows it, checked against the
+/// transaction's own (see [`crate::nonce`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedNonce(pub u64);
+
+/// The chain the host presents the transaction for, in the chain's own notation: the decimal
+/// EIP-155 id such as `"1"` for Ethereum, the chain-id string such as `"cosmoshub-4"` for Cosmos.
+/// Checked against the transaction's own (see [`crate::chain_id`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedChainId(pub String);
+
+/// The CREATE2 factory and salt a contract creation is deployed with, both hex, from which the
+/// address of the new contract is predicted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Create2Deployment {
+    #[serde(rename = "Factory")]
+    pub factory: String,
+    #[serde(rename = "Salt")]
+    pub salt: String,
+}
+
+/// One extension value, tagged with its kind on the wire.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "Kind", content = "Value")]
+pub enum Extension {
+    TokenMetadata(TokenMetadata),
+    AddressLabels(AddressLabels),
+    Locale(Locale),
+    InterfaceDefinitions(InterfaceDefinitions),
+    RenderBudget(RenderBudget),
+    ExpectedNonce(ExpectedNonce),
+    ExpectedChainId(ExpectedChainId),
+    Create2Deployment(Create2Deployment),
+}
+
+impl Extension {
+    pub fn kind(&self) -> ExtensionKind {
+        match self {
+            Extension::TokenMetadata(_) => ExtensionKind::TokenMetadata,
+            Extension::AddressLabels(_) => ExtensionKind::AddressLabels,
+            Extension::Locale(_) => ExtensionKind::Locale,
+            Extension::InterfaceDefinitions(_) => ExtensionKind::InterfaceDefinitions,
+            Extension::RenderBudget(_) => ExtensionKind::RenderBudget,
+            Extension::ExpectedNonce(_) => ExtensionKind::ExpectedNonce,
+            Extension::ExpectedChainId(_) => ExtensionKind::ExpectedChainId,
+            Extension::Create2Deployment(_) => ExtensionKind::Create2Deployment,
+        }
+    }
+}
+
+/// Implemented by each well-known extension type so [`Extensions`] can be indexed by type.
+pub trait WellKnownExtension: Sized {
+    const KIND: ExtensionKind;
+
+    fn into_extension(self) -> Extension;
+
+    fn from_extension(extension: &Extension) -> Option<&Self>;
+}
+
+macro_rules! well_known_extension {
+    ($($variant:ident),* $(,)?) => {
+        $(
+            impl WellKnownExtension for $variant {
+                const KIND: ExtensionKind = ExtensionKind::$variant;
+
+                fn into_extension(self) -> Extension {
+                    Extension::$variant(self)
+                }
+
+                fn from_extension(extension: &Extension) -> Option<&Self> {
+                    match extension {
+                        Extension::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+well_known_extension!(
+    TokenMetadata,
+    AddressLabels,
+    Locale,
+    InterfaceDefinitions,
+    RenderBudget,
+    ExpectedNonce,
+    ExpectedChainId,
+    Create2Deployment,
+);
+
+/// At most one value per [`ExtensionKind`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<Extension>", into = "Vec<Extension>")]
+pub struct Extensions(BTreeMap<ExtensionKind, Extension>);
+
+impl Extensions {
+    /// Stores `value`, returning the extension of the same kind it replaced.
+    pub fn insert<E: WellKnownExtension>(&mut self, value: E) -> Option<Extension> {
+        self.0.insert(E::KIND, value.into_extension())
+    }
+
+    pub fn get<E: WellKnownExtension>(&self) -> Option<&E> {
+        self.0.get(&E::KIND).and_then(E::from_extension)
+    }
+
+    pub fn remove(&mut self, kind: ExtensionKind) -> Option<Extension> {
+        self.0.remove(&kind)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The extensions in kind order.
+    pub fn iter(&self) -> impl Iterator<Item = &Extension> {
+        self.0.values()
+    }
+
+    /// Carries the ABI or IDL of a gRPC `ChainMetadata` as an
+    /// [`InterfaceDefinitions`] extension.
+    pub fn from_chain_metadata(metadata: &ChainMetadata) -> Self {
+        let definition = match &metadata.metadata {
+            Some(chain_metadata::Metadata::Ethereum(ethereum)) => {
+                ethereum.abi.as_ref().map(|abi| InterfaceDefinition {
+                    address: None,
+                    format: InterfaceFormat::Abi,
+                    definition: abi.value.clone(),
+                })
+            }
+            Some(chain_metadata::Metadata::Solana(solana)) => {
+                solana.idl.as_ref().map(|idl| InterfaceDefinition {
+                    address: None,
+                    format: InterfaceFormat::Idl,
+                    definition: idl.value.clone(),
+                })
+            }
+            None => None,
+        };
+
+        let mut extensions = Extensions::default();
+        if let Some(definition) = definition {
+            extensions.insert(InterfaceDefinitions(vec![definition]));
+        }
+        extensions
+    }
+}
+
+impl TryFrom<Vec<Extension>> for Extensions {
+    type Error = String;
+
+    fn try_from(list: Vec<Extension>) -> Result<Self, Self::Error> {
+        let mut extensions = BTreeMap::new();
+        for extension in list {
+            let kind = extension.kind();
+            if extensions.insert(kind, extension).is_some() {
+                return Err(format!("duplicate extension kind {kind:?}"));
+            }
+        }
+        Ok(Extensions(extensions))
+    }
+}
+
+impl From<Extensions> for Vec<Extension> {
+    fn from(extensions: Extensions) -> Self {
+        extensions.0.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generated::parser::{Abi, EthereumMetadata};
+
+    #[test]
+    fn test_typed_lookup() {
+        let mut extensions = Extensions::default();
+        assert!(extensions.get::<Locale>().is_none());
+
+        extensions.insert(TokenMetadata(vec![TokenInfo {
+            address: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            name: None,
+            freeze_authority: None,
+            permanent_delegate: None,
+        }]));
+        extensions.insert(Locale("en-US".to_string()));
+        let replaced = extensions.insert(Locale("de-DE".to_string()));
+
+        assert_eq!(
+            replaced,
+            Some(Extension::Locale(Locale("en-US".to_string())))
+        );
+        assert_eq!(extensions.get::<Locale>().unwrap().0, "de-DE");
+        let usdc = extensions
+            .get::<TokenMetadata>()
+            .and_then(|tokens| tokens.find("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"));
+        assert_eq!(usdc.map(|token| token.decimals), Some(6));
+    }
+
+    #[test]
+    fn test_wire_format_round_trip() {
+        let mut extensions = Extensions::default();
+        extensions.insert(RenderBudget {
+            max_fields: Some(12),
+            max_text_length: None,
+        });
+        extensions.insert(AddressLabels(BTreeMap::from([(
+            "0xabc".to_string(),
+            "Treasury".to_string(),
+        )])));
+
+        let json = serde_json::to_string(&extensions).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"Kind":"AddressLabels","Value":{"0xabc":"Treasury"}},{"Kind":"RenderBudget","Value":{"MaxFields":12}}]"#
+        );
+        let decoded: Extensions = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, extensions);
+
+        let duplicate = r#"[{"Kind":"Locale","Value":"en-US"},{"Kind":"Locale","Value":"fr"}]"#;
+        let err = serde_json::from_str::<Extensions>(duplicate).unwrap_err();
+        assert!(err.to_string().contains("duplicate extension kind Locale"));
+    }
+
+    #[test]
+    fn test_from_chain_metadata() {
+        let metadata = ChainMetadata {
+            metadata: Some(chain_metadata::Metadata::Ethereum(EthereumMetadata {
+                abi: Some(Abi {
+                    value: "[]".to_string(),
+                    signature: None,
+                }),
+            })),
+        };
+        let extensions = Extensions::from_chain_metadata(&metadata);
+        assert_eq!(
+            extensions.get::<InterfaceDefinitions>(),
+            Some(&InterfaceDefinitions(vec![InterfaceDefinition {
+                address: None,
+                format: InterfaceFormat::Abi,
+                definition: "[]".to_string(),
+            }]))
+        );
+
+        assert!(Extensions::from_chain_metadata(&ChainMetadata { metadata: None }).is_empty());
+    }
+}
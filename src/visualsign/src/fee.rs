@@ -0,0 +1,61 @@
+//! The "Max Total Cost" field: the most a transaction can take from the signer in the native
+//! token.
+//!
+//! Fees are bounded differently on every chain (Ethereum's gas limit times max fee per gas,
+//! Tron's fee limit, Sui's gas budget) and the value sent comes on top, so a signer reading the
+//! separate fields has to add them up. Parsers compute the bound in the chain's smallest unit
+//! and render it with [`create_max_total_cost_field`], which states what the sum covers next to
+//! the number.
+
+use crate::amount::Amount;
+use crate::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldAmountV2,
+    SignablePayloadFieldCommon, SignablePayloadFieldStaticAnnotation,
+};
+
+pub const MAX_TOTAL_COST_LABEL: &str = "Max Total Cost";
+
+/// The "Max Total Cost" field for `max_total_cost`, annotated with `breakdown`, the chain's
+/// terms for what it adds up, e.g. "Gas limit x max fee per gas + value".
+pub fn create_max_total_cost_field(
+    max_total_cost: &Amount,
+    breakdown: &str,
+) -> AnnotatedPayloadField {
+    AnnotatedPayloadField {
+        signable_payload_field: SignablePayloadField::AmountV2 {
+            common: SignablePayloadFieldCommon {
+                fallback_text: max_total_cost.to_string(),
+                label: MAX_TOTAL_COST_LABEL.to_string(),
+            },
+            amount_v2: SignablePayloadFieldAmountV2 {
+                amount: max_total_cost.to_decimal_string(),
+                abbreviation: Some(max_total_cost.symbol().to_string()),
+            },
+        },
+        static_annotation: Some(SignablePayloadFieldStaticAnnotation {
+            text: format!("Worst case: {breakdown}"),
+        }),
+        dynamic_annotation: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_total_cost_field() {
+        let field =
+            create_max_total_cost_field(&Amount::new(1_500_000, 6, "TRX"), "Fee limit + amount");
+        assert_eq!(field.signable_payload_field.label(), MAX_TOTAL_COST_LABEL);
+        assert_eq!(field.signable_payload_field.fallback_text(), "1.5 TRX");
+        let SignablePayloadField::AmountV2 { amount_v2, .. } = &field.signable_payload_field else {
+            panic!("Expected AmountV2");
+        };
+        assert_eq!(amount_v2.amount, "1.5");
+        assert_eq!(
+            field.static_annotation.unwrap().text,
+            "Worst case: Fee limit + amount"
+        );
+    }
+}
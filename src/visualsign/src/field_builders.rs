@@ -1,8 +1,14 @@
+use crate::amount::{group_thousands, Amount, FiatQuote};
 use crate::errors;
+use crate::fixed_point::format_fixed_point;
+use crate::number::Decimal;
 use crate::{
-    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldAddressV2,
-    SignablePayloadFieldAmountV2, SignablePayloadFieldCommon, SignablePayloadFieldNumber,
-    SignablePayloadFieldTextV2,
+    AnnotatedPayloadField, DividerStyle, SignablePayloadField, SignablePayloadFieldAddressV2,
+    SignablePayloadFieldAmountV2, SignablePayloadFieldAmountV3, SignablePayloadFieldCommon,
+    SignablePayloadFieldDivider, SignablePayloadFieldDuration, SignablePayloadFieldImageRef,
+    SignablePayloadFieldListLayout, SignablePayloadFieldNumber, SignablePayloadFieldPercentage,
+    SignablePayloadFieldStaticAnnotation, SignablePayloadFieldTextV2,
+    SignablePayloadFieldTimestamp, SignablePayloadFieldUnknown,
 };
 
 use regex::Regex;
@@ -87,6 +93,15 @@ pub fn create_number_field(
     })
 }
 
+/// Helper function to create a number field from a [`Decimal`], in its canonical rendering
+pub fn create_number_field_from(
+    label: &str,
+    number: &Decimal,
+    unit: &str,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    create_number_field(label, &number.to_string(), unit)
+}
+
 pub fn create_amount_field(
     label: &str,
     amount: &str,
@@ -116,6 +131,273 @@ pub fn create_amount_field(
     })
 }
 
+/// Helper function to create an amount field from a scaled [`Amount`]
+pub fn create_amount_field_from(
+    label: &str,
+    amount: &Amount,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    create_amount_field(label, &amount.to_decimal_string(), amount.symbol())
+}
+
+/// Helper function to create an `AmountV3` field, optionally with a host-supplied fiat value
+///
+/// With a USD quote the fallback text reads like `"0.5 ETH (~$1,230)"`; other currencies use
+/// their code, e.g. `"0.5 ETH (~1,150 EUR)"`.
+pub fn create_amount_v3_field(
+    label: &str,
+    amount: &Amount,
+    fiat: Option<&FiatQuote>,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    let value = amount.to_decimal_string();
+    if amount.symbol().is_empty() {
+        return Err(errors::VisualSignError::EmptyField(
+            amount.symbol().to_string(),
+        ));
+    }
+
+    let mut fallback_text = amount.to_string();
+    let mut amount_v3 = SignablePayloadFieldAmountV3 {
+        amount: value,
+        abbreviation: Some(amount.symbol().to_string()),
+        fiat_value: None,
+        fiat_currency: None,
+        rate_source: None,
+        rate_timestamp: None,
+    };
+
+    if let Some(fiat) = fiat {
+        validate_number_string(&fiat.value)?;
+        if fiat.value.starts_with(['-', '+']) {
+            return Err(errors::VisualSignError::InvalidNumberField(
+                fiat.value.clone(),
+            ));
+        }
+        if fiat.currency.len() != 3 || !fiat.currency.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(errors::VisualSignError::ValidationError(format!(
+                "Invalid fiat currency code: '{}'",
+                fiat.currency
+            )));
+        }
+        for (name, value) in [
+            ("rate source", &fiat.rate_source),
+            ("rate timestamp", &fiat.rate_timestamp),
+        ] {
+            if value.trim().is_empty() {
+                return Err(errors::VisualSignError::MissingField(name.to_string()));
+            }
+        }
+
+        let grouped = group_thousands(&fiat.value);
+        let approximate = match fiat.currency.as_str() {
+            "USD" => format!("~${grouped}"),
+            currency => format!("~{grouped} {currency}"),
+        };
+        fallback_text = format!("{fallback_text} ({approximate})");
+
+        amount_v3.fiat_value = Some(fiat.value.clone());
+        amount_v3.fiat_currency = Some(fiat.currency.clone());
+        amount_v3.rate_source = Some(fiat.rate_source.clone());
+        amount_v3.rate_timestamp = Some(fiat.rate_timestamp.clone());
+    }
+
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::AmountV3 {
+            common: SignablePayloadFieldCommon {
+                fallback_text,
+                label: label.to_string(),
+            },
+            amount_v3,
+        },
+    })
+}
+
+/// Helper function to create a timestamp field from Unix seconds
+pub fn create_timestamp_field(
+    label: &str,
+    unix_seconds: i64,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    let unix_millis = unix_seconds.checked_mul(1000).ok_or_else(|| {
+        errors::VisualSignError::ValidationError(format!("Timestamp out of range: {unix_seconds}"))
+    })?;
+    create_timestamp_field_from_millis(label, unix_millis)
+}
+
+/// Helper function to create a timestamp field from Unix milliseconds
+///
+/// The field carries RFC 3339 UTC (`"2024-06-01T12:00:00Z"`) and the fallback text reads
+/// `"2024-06-01 12:00:00 UTC"`. Years outside 0000-9999 are rejected.
+pub fn create_timestamp_field_from_millis(
+    label: &str,
+    unix_millis: i64,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    let (year, month, day) = civil_from_days(unix_millis.div_euclid(MILLIS_PER_DAY));
+    if !(0..=9999).contains(&year) {
+        return Err(errors::VisualSignError::ValidationError(format!(
+            "Timestamp out of range: {unix_millis} ms"
+        )));
+    }
+
+    let millis_of_day = unix_millis.rem_euclid(MILLIS_PER_DAY);
+    let seconds_of_day = millis_of_day / 1000;
+    let (hour, minute, second) = (
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+    );
+    let fraction = match millis_of_day % 1000 {
+        0 => String::new(),
+        millis => format!(".{millis:03}"),
+    };
+
+    let date = format!("{year:04}-{month:02}-{day:02}");
+    let time = format!("{hour:02}:{minute:02}:{second:02}{fraction}");
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::Timestamp {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format!("{date} {time} UTC"),
+                label: label.to_string(),
+            },
+            timestamp: SignablePayloadFieldTimestamp {
+                timestamp: format!("{date}T{time}Z"),
+            },
+        },
+    })
+}
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+// Days since 1970-01-01 to a proleptic Gregorian (year, month, day), after Howard Hinnant's
+// `civil_from_days`
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Helper function to create a duration field
+///
+/// The fallback text uses the largest units first, e.g. `"1d 2h 30m"`.
+pub fn create_duration_field(
+    label: &str,
+    seconds: u64,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    let units = [
+        (seconds / 86_400, "d"),
+        (seconds % 86_400 / 3600, "h"),
+        (seconds % 3600 / 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let parts: Vec<String> = units
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| format!("{count}{unit}"))
+        .collect();
+    let fallback_text = if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    };
+
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::Duration {
+            common: SignablePayloadFieldCommon {
+                fallback_text,
+                label: label.to_string(),
+            },
+            duration: SignablePayloadFieldDuration {
+                seconds: seconds.to_string(),
+            },
+        },
+    })
+}
+
+/// Helper function to create a percentage field, where `percentage` is a decimal such as "0.5"
+pub fn create_percentage_field(
+    label: &str,
+    percentage: &str,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    validate_number_string(percentage)?;
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::Percentage {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format!("{percentage}%"),
+                label: label.to_string(),
+            },
+            percentage: SignablePayloadFieldPercentage {
+                percentage: percentage.to_string(),
+            },
+        },
+    })
+}
+
+/// Helper function to create a percentage field from basis points (1 bps = 0.01%)
+pub fn create_percentage_field_from_bps(
+    label: &str,
+    bps: u64,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    create_percentage_field(label, &format_fixed_point(u128::from(bps), 2))
+}
+
+/// Helper function to create an image reference field for an asset icon
+///
+/// `content_hash` must be `sha256:` followed by 64 lowercase hex digits; the identifier doubles
+/// as the fallback text for clients that cannot show images.
+pub fn create_image_ref_field(
+    label: &str,
+    identifier: &str,
+    content_hash: &str,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    if identifier.trim().is_empty() {
+        return Err(errors::VisualSignError::EmptyField(identifier.to_string()));
+    }
+    let is_valid_hash = content_hash.strip_prefix("sha256:").is_some_and(|digest| {
+        digest.len() == 64
+            && digest
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    });
+    if !is_valid_hash {
+        return Err(errors::VisualSignError::ValidationError(format!(
+            "Invalid image content hash: '{content_hash}'"
+        )));
+    }
+
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::ImageRef {
+            common: SignablePayloadFieldCommon {
+                fallback_text: identifier.to_string(),
+                label: label.to_string(),
+            },
+            image_ref: SignablePayloadFieldImageRef {
+                content_hash: content_hash.to_string(),
+                identifier: identifier.to_string(),
+            },
+        },
+    })
+}
+
 /// Helper function to create an address field
 pub fn create_address_field(
     label: &str,
@@ -140,6 +422,7 @@ pub fn create_address_field(
                 memo: memo.map(|s| s.to_string()),
                 asset_label: asset_label.unwrap_or("").to_string(),
                 badge_text: badge_text.map(|s| s.to_string()),
+                name_resolved: None,
             },
         },
     })
@@ -152,6 +435,50 @@ fn default_hex_representation(data: &[u8]) -> String {
         .join("")
 }
 
+/// Bytes shown at each end of the preview of a [`create_bytes_field`] field
+pub const BYTES_PREVIEW_EDGE: usize = 8;
+
+/// The canonical preview of `data`: its first and last [`BYTES_PREVIEW_EDGE`] bytes in hex
+/// joined by `...`, e.g. `0xa9059cbb00000000...0000000000000064`, or the full hex when `data`
+/// is no longer than that.
+pub fn bytes_preview(data: &[u8]) -> String {
+    if data.len() <= 2 * BYTES_PREVIEW_EDGE {
+        return format!("0x{}", default_hex_representation(data));
+    }
+    format!(
+        "0x{}...{}",
+        default_hex_representation(&data[..BYTES_PREVIEW_EDGE]),
+        default_hex_representation(&data[data.len() - BYTES_PREVIEW_EDGE..])
+    )
+}
+
+/// Helper function to create a bytes field holding all of `data` with its canonical preview
+///
+/// The fallback text is the preview and the length, e.g. `"0xa9059cbb...00000064 (68 bytes)"`,
+/// so clients that do not know the field type stay short too.
+pub fn create_bytes_field(
+    label: &str,
+    data: &[u8],
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    let preview = bytes_preview(data);
+    let unit = if data.len() == 1 { "byte" } else { "bytes" };
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::Bytes {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format!("{preview} ({} {unit})", data.len()),
+                label: label.to_string(),
+            },
+            bytes: SignablePayloadFieldBytes {
+                hex: format!("0x{}", default_hex_representation(data)),
+                length: data.len().to_string(),
+                preview,
+            },
+        },
+    })
+}
+
 /// Create a standard Raw Data field for expanded views
 pub fn create_raw_data_field(
     data: &[u8],
@@ -175,6 +502,173 @@ pub fn create_raw_data_field(
     })
 }
 
+/// Helper function to create an `Unknown` field holding `data` in hex for a part of the
+/// transaction that could not be decoded, with `explanation` saying why
+///
+/// The fallback text is the explanation followed by the canonical preview, e.g.
+/// `"Unsupported instruction: 0x0102 (2 bytes)"`.
+pub fn create_unknown_field(
+    label: &str,
+    data: &[u8],
+    explanation: &str,
+) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    if explanation.trim().is_empty() {
+        return Err(errors::VisualSignError::EmptyField(label.to_string()));
+    }
+    let unit = if data.len() == 1 { "byte" } else { "bytes" };
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::Unknown {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format!(
+                    "{explanation}: {} ({} {unit})",
+                    bytes_preview(data),
+                    data.len()
+                ),
+                label: label.to_string(),
+            },
+            unknown: SignablePayloadFieldUnknown {
+                data: format!("0x{}", default_hex_representation(data)),
+                explanation: explanation.to_string(),
+            },
+        },
+    })
+}
+
+/// Helper function to create a divider headed by `label`, e.g. "Fees"
+pub fn create_divider_field(label: &str) -> Result<AnnotatedPayloadField, errors::VisualSignError> {
+    if label.trim().is_empty() {
+        return Err(errors::VisualSignError::EmptyField(label.to_string()));
+    }
+    Ok(AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: divider_field(label),
+    })
+}
+
+fn divider_field(label: &str) -> SignablePayloadField {
+    SignablePayloadField::Divider {
+        common: SignablePayloadFieldCommon {
+            fallback_text: label.to_string(),
+            label: label.to_string(),
+        },
+        divider: SignablePayloadFieldDivider {
+            style: DividerStyle::THIN,
+        },
+    }
+}
+
+/// Helper function to create a warning: a list layout labeled `label` holding the text field
+/// `message_label`: `message`, annotated with `annotation`
+///
+/// The annotation sits on the inner field, so the warning is kept when the list layout is
+/// placed at the top level of a payload. `fallback_text` is what clients that do not show list
+/// layouts display, e.g. `"Warning: nonce 5 was already used; ..."`.
+pub fn create_warning_field(
+    label: &str,
+    message_label: &str,
+    message: &str,
+    annotation: &str,
+    fallback_text: &str,
+) -> AnnotatedPayloadField {
+    let warning = AnnotatedPayloadField {
+        static_annotation: Some(SignablePayloadFieldStaticAnnotation {
+            text: annotation.to_string(),
+        }),
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::TextV2 {
+            common: SignablePayloadFieldCommon {
+                fallback_text: message.to_string(),
+                label: message_label.to_string(),
+            },
+            text_v2: SignablePayloadFieldTextV2 {
+                text: message.to_string(),
+            },
+        },
+    };
+    AnnotatedPayloadField {
+        static_annotation: None,
+        dynamic_annotation: None,
+        signable_payload_field: SignablePayloadField::ListLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: fallback_text.to_string(),
+                label: label.to_string(),
+            },
+            list_layout: SignablePayloadFieldListLayout {
+                fields: vec![warning],
+            },
+        },
+    }
+}
+
+/// The annotated message of a field built by [`create_warning_field`]
+#[cfg(test)]
+pub(crate) fn warning_message(field: &SignablePayloadField) -> &AnnotatedPayloadField {
+    let SignablePayloadField::ListLayout { list_layout, .. } = field else {
+        panic!("Expected ListLayout");
+    };
+    assert_eq!(list_layout.fields.len(), 1);
+    &list_layout.fields[0]
+}
+
+/// Related fields shown together under a labeled divider, e.g. the fees of a transaction
+///
+/// Converters fill one section per group and append them in order with
+/// [`Section::append_to`], which separates each from the fields before it by a divider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    title: String,
+    fields: Vec<SignablePayloadField>,
+}
+
+impl Section {
+    pub fn new(title: &str) -> Self {
+        Section {
+            title: title.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn fields(&self) -> &[SignablePayloadField] {
+        &self.fields
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn push(&mut self, field: SignablePayloadField) {
+        self.fields.push(field);
+    }
+
+    pub fn extend(&mut self, fields: impl IntoIterator<Item = SignablePayloadField>) {
+        self.fields.extend(fields);
+    }
+
+    /// Appends the section to `fields` and returns the index its first field lands at.
+    ///
+    /// The divider goes before the section's fields unless `fields` is empty: a section opening
+    /// the list has nothing to be set apart from. An empty section appends nothing.
+    pub fn append_to(self, fields: &mut Vec<SignablePayloadField>) -> usize {
+        if !self.fields.is_empty() && !fields.is_empty() {
+            fields.push(divider_field(&self.title));
+        }
+        let start = fields.len();
+        fields.extend(self.fields);
+        start
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,6 +726,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_number_field_from_decimal() {
+        let number: Decimal = "+0012.500".parse().unwrap();
+        let field = create_number_field_from("Ratio", &number, "x").unwrap();
+        match field.signable_payload_field {
+            SignablePayloadField::Number { common, number } => {
+                assert_eq!(common.fallback_text, "12.5 x");
+                assert_eq!(number.number, "12.5");
+            }
+            _ => panic!("Expected Number field"),
+        }
+    }
+
     #[test]
     fn test_create_number_field_invalid_number() {
         // let invalid_numbers = ["abc", "12.3.4", "NaN", "--1"];
@@ -566,4 +1073,307 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_create_amount_field_from() {
+        let amount = Amount::new(2_500_000_000, 9, "SUI");
+        let field = create_amount_field_from("Amount", &amount).expect("should succeed");
+        match field.signable_payload_field {
+            SignablePayloadField::AmountV2 { common, amount_v2 } => {
+                assert_eq!(common.label, "Amount");
+                assert_eq!(common.fallback_text, "2.5 SUI");
+                assert_eq!(amount_v2.amount, "2.5");
+                assert_eq!(amount_v2.abbreviation.as_deref(), Some("SUI"));
+            }
+            _ => panic!("Expected AmountV2 field"),
+        }
+    }
+
+    fn eth_usd_quote() -> FiatQuote {
+        FiatQuote {
+            value: "1230".to_string(),
+            currency: "USD".to_string(),
+            rate_source: "Chainlink ETH/USD".to_string(),
+            rate_timestamp: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_create_amount_v3_field_with_fiat() {
+        let amount = Amount::new(500_000_000_000_000_000, 18, "ETH");
+        let field = create_amount_v3_field("Value", &amount, Some(&eth_usd_quote()))
+            .expect("should succeed")
+            .signable_payload_field;
+        assert_eq!(field.fallback_text(), "0.5 ETH (~$1,230)");
+
+        let json = serde_json::to_string(&field).unwrap();
+        assert_eq!(
+            json,
+            r#"{"AmountV3":{"Abbreviation":"ETH","Amount":"0.5","FiatCurrency":"USD","FiatValue":"1230","RateSource":"Chainlink ETH/USD","RateTimestamp":"2026-01-01T00:00:00Z"},"FallbackText":"0.5 ETH (~$1,230)","Label":"Value","Type":"amount_v3"}"#
+        );
+        let round_trip: SignablePayloadField = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip, field);
+
+        let euro = FiatQuote {
+            value: "1150.25".to_string(),
+            currency: "EUR".to_string(),
+            ..eth_usd_quote()
+        };
+        let field = create_amount_v3_field("Value", &amount, Some(&euro)).unwrap();
+        assert_eq!(
+            field.signable_payload_field.fallback_text(),
+            "0.5 ETH (~1,150.25 EUR)"
+        );
+    }
+
+    #[test]
+    fn test_create_amount_v3_field_without_fiat() {
+        let amount = Amount::new(5, 0, "USDC");
+        let field = create_amount_v3_field("Amount", &amount, None)
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(field.fallback_text(), "5 USDC");
+        assert_eq!(
+            serde_json::to_string(&field).unwrap(),
+            r#"{"AmountV3":{"Abbreviation":"USDC","Amount":"5"},"FallbackText":"5 USDC","Label":"Amount","Type":"amount_v3"}"#
+        );
+    }
+
+    #[test]
+    fn test_create_amount_v3_field_rejects_bad_quotes() {
+        let amount = Amount::new(1, 0, "ETH");
+        let cases = [
+            FiatQuote {
+                value: "-1".to_string(),
+                ..eth_usd_quote()
+            },
+            FiatQuote {
+                value: "1,230".to_string(),
+                ..eth_usd_quote()
+            },
+            FiatQuote {
+                currency: "usd".to_string(),
+                ..eth_usd_quote()
+            },
+            FiatQuote {
+                rate_source: " ".to_string(),
+                ..eth_usd_quote()
+            },
+            FiatQuote {
+                rate_timestamp: String::new(),
+                ..eth_usd_quote()
+            },
+        ];
+        for quote in cases {
+            assert!(
+                create_amount_v3_field("Value", &amount, Some(&quote)).is_err(),
+                "{quote:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_timestamp_field() {
+        let test_cases = [
+            // (unix_millis, expected_timestamp, expected_fallback)
+            (0, "1970-01-01T00:00:00Z", "1970-01-01 00:00:00 UTC"),
+            (
+                1_717_243_200_000,
+                "2024-06-01T12:00:00Z",
+                "2024-06-01 12:00:00 UTC",
+            ),
+            (
+                951_825_600_123,
+                "2000-02-29T12:00:00.123Z",
+                "2000-02-29 12:00:00.123 UTC",
+            ),
+            (-1000, "1969-12-31T23:59:59Z", "1969-12-31 23:59:59 UTC"),
+        ];
+        for (unix_millis, expected_timestamp, expected_fallback) in test_cases {
+            let field = create_timestamp_field_from_millis("Expiration", unix_millis).unwrap();
+            match field.signable_payload_field {
+                SignablePayloadField::Timestamp { common, timestamp } => {
+                    assert_eq!(timestamp.timestamp, expected_timestamp);
+                    assert_eq!(common.fallback_text, expected_fallback);
+                }
+                _ => panic!("Expected Timestamp field"),
+            }
+        }
+
+        let field = create_timestamp_field("Deadline", 1_717_243_200)
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(
+            serde_json::to_string(&field).unwrap(),
+            r#"{"FallbackText":"2024-06-01 12:00:00 UTC","Label":"Deadline","Timestamp":{"Timestamp":"2024-06-01T12:00:00Z"},"Type":"timestamp"}"#
+        );
+
+        assert!(create_timestamp_field("Deadline", 253_402_300_800).is_err());
+        assert!(create_timestamp_field("Deadline", i64::MAX).is_err());
+        assert!(create_timestamp_field_from_millis("Deadline", i64::MIN).is_err());
+    }
+
+    #[test]
+    fn test_create_duration_field() {
+        let test_cases = [
+            (0, "0s"),
+            (59, "59s"),
+            (3600, "1h"),
+            (95_400, "1d 2h 30m"),
+            (u64::MAX, "213503982334601d 7h 15s"),
+        ];
+        for (seconds, expected_fallback) in test_cases {
+            let field = create_duration_field("Lock Period", seconds).unwrap();
+            match field.signable_payload_field {
+                SignablePayloadField::Duration { common, duration } => {
+                    assert_eq!(duration.seconds, seconds.to_string());
+                    assert_eq!(common.fallback_text, expected_fallback);
+                }
+                _ => panic!("Expected Duration field"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_percentage_field() {
+        let field = create_percentage_field_from_bps("Slippage", 50)
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(
+            serde_json::to_string(&field).unwrap(),
+            r#"{"FallbackText":"0.5%","Label":"Slippage","Percentage":{"Percentage":"0.5"},"Type":"percentage"}"#
+        );
+
+        let field = create_percentage_field_from_bps("Fee", 10_000).unwrap();
+        assert_eq!(field.signable_payload_field.fallback_text(), "100%");
+
+        assert!(matches!(
+            create_percentage_field("Fee", "5%"),
+            Err(VisualSignError::InvalidNumberField(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_image_ref_field() {
+        let hash = format!("sha256:{}", "ab".repeat(32));
+        let field = create_image_ref_field("Token Icon", "erc20:1:0xa0b8", &hash)
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(field.fallback_text(), "erc20:1:0xa0b8");
+        assert_eq!(
+            serde_json::to_string(&field).unwrap(),
+            format!(
+                r#"{{"FallbackText":"erc20:1:0xa0b8","ImageRef":{{"ContentHash":"{hash}","Identifier":"erc20:1:0xa0b8"}},"Label":"Token Icon","Type":"image_ref"}}"#
+            )
+        );
+
+        let invalid_hashes = [
+            "ab".repeat(32),
+            format!("sha256:{}", "AB".repeat(32)),
+            format!("sha256:{}", "ab".repeat(31)),
+            format!("md5:{}", "ab".repeat(32)),
+            "https://example.com/icon.png".to_string(),
+        ];
+        for invalid in invalid_hashes {
+            assert!(
+                create_image_ref_field("Token Icon", "erc20:1:0xa0b8", &invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+        assert!(matches!(
+            create_image_ref_field("Token Icon", " ", &hash),
+            Err(VisualSignError::EmptyField(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_bytes_field() {
+        let data: Vec<u8> = (0..20).collect();
+        let field = create_bytes_field("Call Data", &data)
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(
+            serde_json::to_string(&field).unwrap(),
+            r#"{"Bytes":{"Hex":"0x000102030405060708090a0b0c0d0e0f10111213","Length":"20","Preview":"0x0001020304050607...0c0d0e0f10111213"},"FallbackText":"0x0001020304050607...0c0d0e0f10111213 (20 bytes)","Label":"Call Data","Type":"bytes"}"#
+        );
+
+        // Data no longer than the preview is shown whole
+        assert_eq!(
+            bytes_preview(&data[..16]),
+            format!("0x{}", hex::encode(&data[..16]))
+        );
+        assert_eq!(bytes_preview(&[]), "0x");
+        let field = create_bytes_field("Selector", &[0xa9]).unwrap();
+        assert_eq!(
+            field.signable_payload_field.fallback_text(),
+            "0xa9 (1 byte)"
+        );
+    }
+
+    #[test]
+    fn test_create_unknown_field() {
+        let field = create_unknown_field("Instruction 2", &[0x01, 0x02], "Unsupported instruction")
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(
+            serde_json::to_string(&field).unwrap(),
+            r#"{"FallbackText":"Unsupported instruction: 0x0102 (2 bytes)","Label":"Instruction 2","Type":"unknown","Unknown":{"Data":"0x0102","Explanation":"Unsupported instruction"}}"#
+        );
+        assert!(create_unknown_field("Instruction 2", &[0x01], " ").is_err());
+    }
+
+    #[test]
+    fn test_create_warning_field() {
+        let field = create_warning_field(
+            "Nonce Warning",
+            "Reused nonce",
+            "nonce 5 was already used",
+            "Warning: this transaction will be rejected",
+            "Warning: nonce 5 was already used",
+        )
+        .signable_payload_field;
+        assert_eq!(field.label(), "Nonce Warning");
+        assert_eq!(field.fallback_text(), "Warning: nonce 5 was already used");
+
+        let message = warning_message(&field);
+        assert_eq!(message.signable_payload_field.label(), "Reused nonce");
+        assert_eq!(
+            message.signable_payload_field.fallback_text(),
+            "nonce 5 was already used"
+        );
+        assert_eq!(
+            message.static_annotation.as_ref().unwrap().text,
+            "Warning: this transaction will be rejected"
+        );
+    }
+
+    #[test]
+    fn test_sections_append_with_dividers() {
+        let text = |label: &str| {
+            create_text_field(label, "value")
+                .unwrap()
+                .signable_payload_field
+        };
+        let mut network = Section::new("Network");
+        network.push(text("Network"));
+        let empty = Section::new("Destination");
+        let mut fees = Section::new("Fees");
+        fees.extend([text("Gas Limit"), text("Gas Price")]);
+
+        let mut fields = Vec::new();
+        assert_eq!(network.append_to(&mut fields), 0);
+        assert_eq!(empty.append_to(&mut fields), 1);
+        assert_eq!(fees.append_to(&mut fields), 2);
+
+        let labels: Vec<&str> = fields.iter().map(|field| field.label().as_str()).collect();
+        assert_eq!(labels, ["Network", "Fees", "Gas Limit", "Gas Price"]);
+        assert_eq!(
+            serde_json::to_string(&fields[1]).unwrap(),
+            r#"{"Divider":{"Style":""},"FallbackText":"Fees","Label":"Fees","Type":"divider"}"#
+        );
+        assert!(matches!(
+            create_divider_field(" "),
+            Err(VisualSignError::EmptyField(_))
+        ));
+    }
 }
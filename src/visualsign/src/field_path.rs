@@ -0,0 +1,372 @@
+//! Typed addresses of fields within a payload.
+//!
+//! A [`FieldPath`] names one field, top-level or nested in layouts, the way a JSON pointer
+//! names it in the payload's JSON: `Fields/3` for a top-level field and
+//! `Fields/0/PreviewLayout/Expanded/Fields/2` for one inside a layout. Diffs, provenance maps and
+//! policy rules keyed by it refer to the same field however they were produced. The dotted
+//! notation of [`SignablePayload::duplicate_labels`] and [`crate::visit`], e.g.
+//! `Fields[0].PreviewLayout.Expanded[2]`, parses too, and [`FieldPath::to_dotted`] writes it.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::VisualSignError;
+use crate::{SignablePayload, SignablePayloadField, SignablePayloadFieldListLayout};
+
+/// A list of fields nested in a layout field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NestedList {
+    /// The condensed view of a `PreviewLayout`
+    Condensed,
+    /// The expanded view of a `PreviewLayout`
+    Expanded,
+    /// The fields of a `ListLayout`
+    List,
+}
+
+impl NestedList {
+    // Segments naming the list in pointer and dotted notation
+    fn pointer(&self) -> &'static str {
+        match self {
+            NestedList::Condensed => "PreviewLayout/Condensed/Fields",
+            NestedList::Expanded => "PreviewLayout/Expanded/Fields",
+            NestedList::List => "ListLayout/Fields",
+        }
+    }
+
+    fn dotted(&self) -> &'static str {
+        match self {
+            NestedList::Condensed => "PreviewLayout.Condensed",
+            NestedList::Expanded => "PreviewLayout.Expanded",
+            NestedList::List => "ListLayout",
+        }
+    }
+
+    fn of(self, field: &SignablePayloadField) -> Option<&SignablePayloadFieldListLayout> {
+        match (self, field) {
+            (NestedList::Condensed, SignablePayloadField::PreviewLayout { preview_layout, .. }) => {
+                preview_layout.condensed.as_ref()
+            }
+            (NestedList::Expanded, SignablePayloadField::PreviewLayout { preview_layout, .. }) => {
+                preview_layout.expanded.as_ref()
+            }
+            (NestedList::List, SignablePayloadField::ListLayout { list_layout, .. }) => {
+                Some(list_layout)
+            }
+            _ => None,
+        }
+    }
+
+    fn of_mut(
+        self,
+        field: &mut SignablePayloadField,
+    ) -> Option<&mut SignablePayloadFieldListLayout> {
+        match (self, field) {
+            (NestedList::Condensed, SignablePayloadField::PreviewLayout { preview_layout, .. }) => {
+                preview_layout.condensed.as_mut()
+            }
+            (NestedList::Expanded, SignablePayloadField::PreviewLayout { preview_layout, .. }) => {
+                preview_layout.expanded.as_mut()
+            }
+            (NestedList::List, SignablePayloadField::ListLayout { list_layout, .. }) => {
+                Some(list_layout)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The address of one field of a payload. Paths order the way their fields are displayed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FieldPath {
+    index: usize,
+    nested: Vec<(NestedList, usize)>,
+}
+
+impl FieldPath {
+    /// The top-level field at `index`.
+    pub fn top_level(index: usize) -> Self {
+        FieldPath {
+            index,
+            nested: Vec::new(),
+        }
+    }
+
+    /// The field at `index` in the `list` nested in this one.
+    pub fn child(&self, list: NestedList, index: usize) -> Self {
+        let mut child = self.clone();
+        child.nested.push((list, index));
+        child
+    }
+
+    /// The layout field this one is nested in; `None` for a top-level field.
+    pub fn parent(&self) -> Option<Self> {
+        let mut parent = self.clone();
+        parent.nested.pop()?;
+        Some(parent)
+    }
+
+    /// How many layouts the field is nested in; 0 for a top-level field.
+    pub fn depth(&self) -> usize {
+        self.nested.len()
+    }
+
+    /// The path in dotted notation, e.g. `Fields[0].PreviewLayout.Expanded[2]`.
+    pub fn to_dotted(&self) -> String {
+        let mut dotted = format!("Fields[{}]", self.index);
+        for (list, index) in &self.nested {
+            dotted.push_str(&format!(".{}[{index}]", list.dotted()));
+        }
+        dotted
+    }
+
+    fn parse_pointer(path: &str) -> Option<Self> {
+        let mut segments = path.strip_prefix('/').unwrap_or(path).split('/');
+        if segments.next()? != "Fields" {
+            return None;
+        }
+        let mut field_path = FieldPath::top_level(segments.next()?.parse().ok()?);
+        while let Some(segment) = segments.next() {
+            let list = match (segment, segments.next()?) {
+                ("PreviewLayout", "Condensed") => NestedList::Condensed,
+                ("PreviewLayout", "Expanded") => NestedList::Expanded,
+                ("ListLayout", "Fields") => NestedList::List,
+                _ => return None,
+            };
+            if list != NestedList::List && segments.next()? != "Fields" {
+                return None;
+            }
+            field_path = field_path.child(list, segments.next()?.parse().ok()?);
+        }
+        Some(field_path)
+    }
+
+    fn parse_dotted(path: &str) -> Option<Self> {
+        let (index, mut rest) = split_index(path.strip_prefix("Fields")?)?;
+        let mut field_path = FieldPath::top_level(index);
+        while !rest.is_empty() {
+            let rest_of_list = rest.strip_prefix('.')?;
+            let (list, tail) = [
+                NestedList::Condensed,
+                NestedList::Expanded,
+                NestedList::List,
+            ]
+            .into_iter()
+            .find_map(|list| Some((list, rest_of_list.strip_prefix(list.dotted())?)))?;
+            let (index, tail) = split_index(tail)?;
+            field_path = field_path.child(list, index);
+            rest = tail;
+        }
+        Some(field_path)
+    }
+}
+
+// Splits "[3]..." into 3 and the rest
+fn split_index(path: &str) -> Option<(usize, &str)> {
+    let path = path.strip_prefix('[')?;
+    let end = path.find(']')?;
+    Some((path[..end].parse().ok()?, &path[end + 1..]))
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fields/{}", self.index)?;
+        for (list, index) in &self.nested {
+            write!(f, "/{}/{index}", list.pointer())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for FieldPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed = if s.contains('[') {
+            FieldPath::parse_dotted(s)
+        } else {
+            FieldPath::parse_pointer(s)
+        };
+        parsed.ok_or_else(|| format!("Invalid field path: {s}"))
+    }
+}
+
+impl Serialize for FieldPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl SignablePayload {
+    /// The field at `path`, in either notation; `None` when the path does not parse.
+    pub fn field_at_path(&self, path: &str) -> Option<&SignablePayloadField> {
+        self.field(&path.parse().ok()?)
+    }
+
+    /// The field at `path`.
+    pub fn field(&self, path: &FieldPath) -> Option<&SignablePayloadField> {
+        let mut field = self.fields.get(path.index)?;
+        for (list, index) in &path.nested {
+            field = &list.of(field)?.fields.get(*index)?.signable_payload_field;
+        }
+        Some(field)
+    }
+
+    /// The field at `path`, for editing in place.
+    pub fn field_mut(&mut self, path: &FieldPath) -> Option<&mut SignablePayloadField> {
+        let mut field = self.fields.get_mut(path.index)?;
+        for (list, index) in &path.nested {
+            field = &mut list
+                .of_mut(field)?
+                .fields
+                .get_mut(*index)?
+                .signable_payload_field;
+        }
+        Some(field)
+    }
+
+    /// Replaces the field at `path` with `field`, returning the one it replaced.
+    pub fn set_field(
+        &mut self,
+        path: &FieldPath,
+        field: SignablePayloadField,
+    ) -> Result<SignablePayloadField, VisualSignError> {
+        let current = self.field_mut(path).ok_or_else(|| {
+            VisualSignError::ValidationError(format!("{path} is not a field of the payload"))
+        })?;
+        Ok(std::mem::replace(current, field))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::create_text_field;
+    use crate::{SignablePayloadFieldCommon, SignablePayloadFieldPreviewLayout};
+
+    fn payload() -> SignablePayload {
+        let text = |label: &str| create_text_field(label, "value").unwrap();
+        let instructions = SignablePayloadField::ListLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: "Instructions".to_string(),
+                label: "Instructions".to_string(),
+            },
+            list_layout: SignablePayloadFieldListLayout {
+                fields: vec![text("Program")],
+            },
+        };
+        let preview = SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: "Swap".to_string(),
+                label: "Swap".to_string(),
+            },
+            preview_layout: SignablePayloadFieldPreviewLayout {
+                title: None,
+                subtitle: None,
+                condensed: Some(SignablePayloadFieldListLayout {
+                    fields: vec![text("Amount")],
+                }),
+                expanded: Some(SignablePayloadFieldListLayout {
+                    fields: vec![
+                        text("Amount"),
+                        crate::AnnotatedPayloadField {
+                            signable_payload_field: instructions,
+                            static_annotation: None,
+                            dynamic_annotation: None,
+                        },
+                    ],
+                }),
+            },
+        };
+        SignablePayload::new(
+            0,
+            "Swap".to_string(),
+            None,
+            vec![text("Network").signable_payload_field, preview],
+            "Test".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_field_path_notations_round_trip() {
+        let path = FieldPath::top_level(1)
+            .child(NestedList::Expanded, 1)
+            .child(NestedList::List, 0);
+        assert_eq!(
+            path.to_string(),
+            "Fields/1/PreviewLayout/Expanded/Fields/1/ListLayout/Fields/0"
+        );
+        assert_eq!(
+            path.to_dotted(),
+            "Fields[1].PreviewLayout.Expanded[1].ListLayout[0]"
+        );
+        assert_eq!(path.to_string().parse::<FieldPath>(), Ok(path.clone()));
+        assert_eq!(path.to_dotted().parse::<FieldPath>(), Ok(path.clone()));
+        assert_eq!(format!("/{path}").parse::<FieldPath>(), Ok(path.clone()));
+        assert_eq!(
+            serde_json::from_str::<FieldPath>(&serde_json::to_string(&path).unwrap()).unwrap(),
+            path
+        );
+        assert_eq!(path.depth(), 2);
+        assert_eq!(
+            path.parent().and_then(|parent| parent.parent()),
+            Some(FieldPath::top_level(1))
+        );
+        assert!(FieldPath::top_level(1).parent().is_none());
+
+        for invalid in [
+            "",
+            "Fields",
+            "Fields/x",
+            "Fields/1/PreviewLayout/Expanded/2",
+            "Fields/1/ListLayout/0",
+            "Fields[1].Expanded[0]",
+        ] {
+            assert!(invalid.parse::<FieldPath>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_get_and_set_fields_by_path() {
+        let mut payload = payload();
+        let program: FieldPath = "Fields/1/PreviewLayout/Expanded/Fields/1/ListLayout/Fields/0"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            payload.field(&program).map(|field| field.label().as_str()),
+            Some("Program")
+        );
+        assert!(payload
+            .field(&FieldPath::top_level(0).child(NestedList::Condensed, 0))
+            .is_none());
+
+        let replaced = payload
+            .set_field(
+                &program,
+                create_text_field("Memo", "hello")
+                    .unwrap()
+                    .signable_payload_field,
+            )
+            .unwrap();
+        assert_eq!(replaced.label(), "Program");
+        assert_eq!(
+            payload
+                .field_at_path("Fields[1].PreviewLayout.Expanded[1].ListLayout[0]")
+                .map(|field| field.label().as_str()),
+            Some("Memo")
+        );
+        assert!(payload
+            .set_field(&FieldPath::top_level(2), replaced)
+            .is_err());
+    }
+}
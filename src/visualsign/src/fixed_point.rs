@@ -0,0 +1,80 @@
+//! Float-free formatting of scaled integer amounts (wei, lamports, MIST, SUN, ...).
+//!
+//! Amounts are kept as integers in the chain's smallest unit and shifted by `decimals` as
+//! strings, so the rendered value is exact and identical on every platform. Trailing fractional
+//! zeros are removed: `1_500_000` with 6 decimals renders as `"1.5"`.
+
+use crate::errors::VisualSignError;
+
+/// Formats `value` scaled down by `decimals` places.
+pub fn format_fixed_point(value: u128, decimals: u8) -> String {
+    shift_digits(&value.to_string(), decimals)
+}
+
+/// Formats a non-negative base-10 integer string scaled down by `decimals` places.
+///
+/// Used for integers wider than `u128`, such as `U256` wei amounts rendered with `to_string()`.
+pub fn format_fixed_point_digits(digits: &str, decimals: u8) -> Result<String, VisualSignError> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(VisualSignError::InvalidNumberField(digits.to_string()));
+    }
+    Ok(shift_digits(digits, decimals))
+}
+
+// Assumes `digits` contains only ASCII digits
+fn shift_digits(digits: &str, decimals: u8) -> String {
+    let decimals = usize::from(decimals);
+    let digits = digits.trim_start_matches('0');
+
+    let (whole, fraction) = if digits.len() > decimals {
+        let (whole, fraction) = digits.split_at(digits.len() - decimals);
+        (whole.to_string(), fraction.to_string())
+    } else {
+        ("0".to_string(), format!("{digits:0>decimals$}"))
+    };
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_fixed_point() {
+        assert_eq!(format_fixed_point(0, 6), "0");
+        assert_eq!(format_fixed_point(1, 6), "0.000001");
+        assert_eq!(format_fixed_point(1_500_000, 6), "1.5");
+        assert_eq!(format_fixed_point(3_805_000_000, 6), "3805");
+        assert_eq!(format_fixed_point(42, 0), "42");
+        assert_eq!(
+            format_fixed_point(u128::MAX, 18),
+            "340282366920938463463.374607431768211455"
+        );
+    }
+
+    #[test]
+    fn test_format_fixed_point_digits() {
+        // Larger than u128::MAX (U256::MAX wei)
+        assert_eq!(
+            format_fixed_point_digits(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                18
+            )
+            .unwrap(),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+        assert_eq!(format_fixed_point_digits("000120", 2).unwrap(), "1.2");
+        assert!(matches!(
+            format_fixed_point_digits("-1", 2),
+            Err(VisualSignError::InvalidNumberField(_))
+        ));
+        assert!(format_fixed_point_digits("", 2).is_err());
+        assert!(format_fixed_point_digits("1.5", 2).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+//! Verification of a declared [`UserIntent`] against a parsed [`SignablePayload`].
+//!
+//! Each key of the intent payload (e.g. `to`, `amount`, `asset`) is looked up by field label
+//! anywhere in the payload, including inside preview and list layouts, and compared with the
+//! field's rendered values. The result is an [`IntentMatchReport`] with one check per key.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::errors::VisualSignError;
+use crate::{AnnotatedPayloadField, SignablePayload, SignablePayloadField, UserIntent};
+
+/// Outcome of comparing a single intent key with the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntentCheckStatus {
+    /// A field with a matching label shows the expected value
+    Matched { label: String },
+    /// Fields with matching labels exist but none show the expected value
+    Mismatched { found: Vec<String> },
+    /// No field carries a label associated with the key
+    NotFound,
+    /// The expected value is not a string, number or boolean
+    Unsupported,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntentCheck {
+    pub key: String,
+    pub expected: String,
+    pub status: IntentCheckStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntentMatchReport {
+    pub intent_type: String,
+    pub checks: Vec<IntentCheck>,
+}
+
+impl IntentMatchReport {
+    /// True when the intent declared at least one key and every key matched.
+    pub fn is_match(&self) -> bool {
+        !self.checks.is_empty()
+            && self
+                .checks
+                .iter()
+                .all(|check| matches!(check.status, IntentCheckStatus::Matched { .. }))
+    }
+
+    /// Checks that did not match, in intent key order.
+    pub fn failures(&self) -> impl Iterator<Item = &IntentCheck> {
+        self.checks
+            .iter()
+            .filter(|check| !matches!(check.status, IntentCheckStatus::Matched { .. }))
+    }
+}
+
+/// Compares declared intents with parsed payloads using a table of intent key to field labels.
+#[derive(Debug, Clone)]
+pub struct IntentMatcher {
+    // Lowercased intent key -> lowercased field labels that may carry its value
+    aliases: HashMap<String, Vec<String>>,
+}
+
+impl Default for IntentMatcher {
+    fn default() -> Self {
+        let defaults: [(&str, &[&str]); 5] = [
+            (
+                "to",
+                &["recipient", "destination", "receiver", "to address"],
+            ),
+            ("from", &["sender", "source", "from address"]),
+            ("amount", &["value", "you send"]),
+            ("asset", &["token", "coin", "mint"]),
+            ("spender", &["delegate", "approved spender"]),
+        ];
+        let aliases = defaults
+            .into_iter()
+            .map(|(key, labels)| {
+                (
+                    key.to_string(),
+                    labels.iter().map(|label| label.to_string()).collect(),
+                )
+            })
+            .collect();
+        IntentMatcher { aliases }
+    }
+}
+
+impl IntentMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an extra field label that may carry the value for `key`.
+    pub fn with_alias(mut self, key: &str, label: &str) -> Self {
+        self.aliases
+            .entry(key.to_lowercase())
+            .or_default()
+            .push(label.to_lowercase());
+        self
+    }
+
+    /// Checks every key of the intent payload against the fields of `payload`.
+    pub fn check(
+        &self,
+        intent: &UserIntent,
+        payload: &SignablePayload,
+    ) -> Result<IntentMatchReport, VisualSignError> {
+        let Value::Object(expected) = &intent.payload else {
+            return Err(VisualSignError::ValidationError(
+                "UserIntent payload must be an object".to_string(),
+            ));
+        };
+
+        let mut fields = Vec::new();
+        collect_fields(&payload.fields, &mut fields);
+
+        let checks = expected
+            .iter()
+            .map(|(key, value)| self.check_key(key, value, &fields))
+            .collect();
+
+        Ok(IntentMatchReport {
+            intent_type: intent.intent_type.clone(),
+            checks,
+        })
+    }
+
+    fn check_key(&self, key: &str, value: &Value, fields: &[&SignablePayloadField]) -> IntentCheck {
+        let expected = match value {
+            Value::String(text) => Some(text.clone()),
+            Value::Number(number) => Some(number.to_string()),
+            Value::Bool(flag) => Some(flag.to_string()),
+            _ => None,
+        };
+        let Some(expected) = expected else {
+            return IntentCheck {
+                key: key.to_string(),
+                expected: value.to_string(),
+                status: IntentCheckStatus::Unsupported,
+            };
+        };
+
+        let key_lower = key.to_lowercase();
+        let aliases = self.aliases.get(&key_lower);
+        let labelled: Vec<&SignablePayloadField> = fields
+            .iter()
+            .copied()
+            .filter(|field| {
+                let label = field.label().to_lowercase();
+                label == key_lower || aliases.is_some_and(|aliases| aliases.contains(&label))
+            })
+            .collect();
+
+        let wanted = normalize(&expected);
+        let status = if labelled.is_empty() {
+            IntentCheckStatus::NotFound
+        } else if let Some(field) = labelled
+            .iter()
+            .find(|field| field_values(field).iter().any(|v| normalize(v) == wanted))
+        {
+            IntentCheckStatus::Matched {
+                label: field.label().clone(),
+            }
+        } else {
+            let mut found: Vec<String> = labelled
+                .iter()
+                .map(|field| field.fallback_text().clone())
+                .collect();
+            found.dedup();
+            IntentCheckStatus::Mismatched { found }
+        };
+
+        IntentCheck {
+            key: key.to_string(),
+            expected,
+            status,
+        }
+    }
+}
+
+// Flattens layouts so nested fields are matched the same way as top-level ones
+pub(crate) fn collect_fields<'a>(
+    fields: &'a [SignablePayloadField],
+    out: &mut Vec<&'a SignablePayloadField>,
+) {
+    for field in fields {
+        collect_field(field, out);
+    }
+}
+
+fn collect_field<'a>(field: &'a SignablePayloadField, out: &mut Vec<&'a SignablePayloadField>) {
+    out.push(field);
+    let nested: Vec<&'a AnnotatedPayloadField> = match field {
+        SignablePayloadField::PreviewLayout { preview_layout, .. } => preview_layout
+            .condensed
+            .iter()
+            .chain(preview_layout.expanded.iter())
+            .flat_map(|list| list.fields.iter())
+            .collect(),
+        SignablePayloadField::ListLayout { list_layout, .. } => list_layout.fields.iter().collect(),
+        _ => Vec::new(),
+    };
+    for annotated in nested {
+        collect_field(&annotated.signable_payload_field, out);
+    }
+}
+
+// Every rendering of a field's value that an intent value may be compared with
+fn field_values(field: &SignablePayloadField) -> Vec<String> {
+    let mut values = vec![field.fallback_text().clone()];
+    match field {
+        SignablePayloadField::Text { text, .. } => values.push(text.text.clone()),
+        SignablePayloadField::TextV2 { text_v2, .. } => values.push(text_v2.text.clone()),
+        SignablePayloadField::Address { address, .. } => {
+            values.push(address.address.clone());
+            values.push(address.name.clone());
+        }
+        SignablePayloadField::AddressV2 { address_v2, .. } => {
+            values.push(address_v2.address.clone());
+            values.push(address_v2.name.clone());
+        }
+        SignablePayloadField::Number { number, .. } => values.push(number.number.clone()),
+        SignablePayloadField::Amount { amount, .. } => {
+            values.push(amount.amount.clone());
+            values.extend(amount.abbreviation.clone());
+        }
+        SignablePayloadField::AmountV2 { amount_v2, .. } => {
+            values.push(amount_v2.amount.clone());
+            values.extend(amount_v2.abbreviation.clone());
+        }
+        SignablePayloadField::AmountV3 { amount_v3, .. } => {
+            values.push(amount_v3.amount.clone());
+            values.extend(amount_v3.abbreviation.clone());
+        }
+        _ => {}
+    }
+    values
+}
+
+// Case-insensitive comparison with trailing decimal zeros removed, so "1.50" matches "1.5"
+fn normalize(value: &str) -> String {
+    let value = value.trim().to_lowercase();
+    let is_decimal = value.contains('.')
+        && value
+            .trim_start_matches(['-', '+'])
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.');
+    if is_decimal {
+        value
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::{create_address_field, create_amount_field, create_text_field};
+    use crate::{
+        SignablePayloadFieldCommon, SignablePayloadFieldListLayout,
+        SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+    };
+    use serde_json::json;
+
+    const RECIPIENT: &str = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd";
+
+    fn transfer_payload(amount: &str) -> SignablePayload {
+        let details = vec![
+            create_address_field("To", RECIPIENT, None, None, None, None)
+                .unwrap()
+                .signable_payload_field,
+            create_amount_field("Value", amount, "ETH")
+                .unwrap()
+                .signable_payload_field,
+        ];
+        let summary = SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: "Summary".to_string(),
+                label: "Summary".to_string(),
+            },
+            preview_layout: SignablePayloadFieldPreviewLayout {
+                title: Some(SignablePayloadFieldTextV2 {
+                    text: "Summary".to_string(),
+                }),
+                subtitle: None,
+                condensed: None,
+                expanded: Some(SignablePayloadFieldListLayout {
+                    fields: vec![create_text_field("Token", "ETH").unwrap()],
+                }),
+            },
+        };
+        let mut fields = details;
+        fields.push(summary);
+        SignablePayload::new(0, "Transfer".to_string(), None, fields, "Test".to_string())
+    }
+
+    fn transfer_intent(to: &str, amount: Value) -> UserIntent {
+        UserIntent {
+            intent_type: "transfer".to_string(),
+            payload: json!({ "to": to, "amount": amount, "asset": "eth" }),
+        }
+    }
+
+    #[test]
+    fn test_matching_intent() {
+        let payload = transfer_payload("1.50");
+        let checksummed = format!("0x{}", RECIPIENT[2..].to_uppercase());
+        let intent = transfer_intent(&checksummed, json!(1.5));
+
+        let report = IntentMatcher::new().check(&intent, &payload).unwrap();
+        assert!(report.is_match(), "{report:?}");
+        assert_eq!(report.intent_type, "transfer");
+        assert_eq!(
+            report
+                .checks
+                .iter()
+                .find(|check| check.key == "asset")
+                .unwrap()
+                .status,
+            IntentCheckStatus::Matched {
+                label: "Token".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_mismatched_and_missing_keys() {
+        let payload = transfer_payload("2");
+        let mut intent = transfer_intent(RECIPIENT, json!("1"));
+        intent.payload["memo"] = json!("invoice 42");
+        intent.payload["tags"] = json!(["a"]);
+
+        let report = IntentMatcher::new().check(&intent, &payload).unwrap();
+        assert!(!report.is_match());
+
+        let failures: HashMap<&str, &IntentCheckStatus> = report
+            .failures()
+            .map(|check| (check.key.as_str(), &check.status))
+            .collect();
+        assert_eq!(failures.len(), 3);
+        assert_eq!(
+            failures["amount"],
+            &IntentCheckStatus::Mismatched {
+                found: vec!["2 ETH".to_string()]
+            }
+        );
+        assert_eq!(failures["memo"], &IntentCheckStatus::NotFound);
+        assert_eq!(failures["tags"], &IntentCheckStatus::Unsupported);
+    }
+
+    #[test]
+    fn test_custom_alias() {
+        let payload = SignablePayload::new(
+            0,
+            "Stake".to_string(),
+            None,
+            vec![
+                create_text_field("Validator", "node-1")
+                    .unwrap()
+                    .signable_payload_field,
+            ],
+            "Test".to_string(),
+        );
+        let intent = UserIntent {
+            intent_type: "stake".to_string(),
+            payload: json!({ "to": "node-1" }),
+        };
+
+        let report = IntentMatcher::new().check(&intent, &payload).unwrap();
+        assert_eq!(report.checks[0].status, IntentCheckStatus::NotFound);
+
+        let report = IntentMatcher::new()
+            .with_alias("to", "Validator")
+            .check(&intent, &payload)
+            .unwrap();
+        assert!(report.is_match());
+    }
+
+    #[test]
+    fn test_non_object_intent_payload() {
+        let intent = UserIntent {
+            intent_type: "transfer".to_string(),
+            payload: json!("send 1 ETH"),
+        };
+        let result = IntentMatcher::new().check(&intent, &transfer_payload("1"));
+        assert!(matches!(result, Err(VisualSignError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,241 @@
+//! Detection and namespacing of duplicate field labels.
+//!
+//! Two sibling fields with the same label are ambiguous to the signer: a wallet showing
+//! "Recipient" twice gives no hint which one receives what. Labels must be unique within each
+//! list of fields (the top-level fields, and every condensed, expanded and list layout); the
+//! same label may still appear in different lists, such as a field repeated in the condensed
+//! and expanded views of one `PreviewLayout`.
+
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::VisualSignError;
+use crate::{visit, SignablePayload, SignablePayloadField};
+
+/// How [`crate::vsptrait::VisualSignConverter::to_validated_visual_sign_payload`] treats
+/// duplicate labels; leaving it unset keeps payloads as the parser produced them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DuplicateLabelPolicy {
+    /// Fail validation when a list contains the same label twice
+    Reject,
+    /// Suffix repeated labels with their occurrence number, e.g. "Amount (2)"
+    Namespace,
+}
+
+impl SignablePayload {
+    /// Returns each duplicated label with the path of the list it appears in, e.g.
+    /// `("Lending Market", "Fields[1].PreviewLayout.Expanded")`.
+    pub fn duplicate_labels(&self) -> Vec<(String, String)> {
+        let mut finder = DuplicateFinder::default();
+        visit::walk_payload(self, &mut finder).unwrap_or_else(|never| match never {});
+        finder.duplicates
+    }
+
+    /// Fails with a `ValidationError` naming the first duplicated label.
+    pub fn validate_unique_labels(&self) -> Result<(), VisualSignError> {
+        match self.duplicate_labels().into_iter().next() {
+            Some((label, path)) => Err(VisualSignError::ValidationError(format!(
+                "Duplicate label '{label}' in {path}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Renames repeated labels within each list to "Label (2)", "Label (3)", ... while
+    /// keeping the first occurrence unchanged.
+    pub fn namespace_duplicate_labels(&mut self) {
+        namespace_list(self.fields.iter_mut());
+    }
+}
+
+// Labels seen in each list so far, keyed by the path of the list, and the labels already
+// reported. Fields arrive in display order, so a list's duplicates are reported as they occur,
+// interleaved with those of the lists nested in it.
+#[derive(Default)]
+struct DuplicateFinder {
+    lists: HashMap<String, (HashSet<String>, HashSet<String>)>,
+    duplicates: Vec<(String, String)>,
+}
+
+impl visit::PayloadVisitor for DuplicateFinder {
+    type Error = Infallible;
+
+    fn visit_field(&mut self, path: &str, field: &SignablePayloadField) -> Result<(), Infallible> {
+        // A field's path is the path of its list followed by its index
+        let list = path.rsplit_once('[').map_or(path, |(list, _)| list);
+        let (seen, reported) = self.lists.entry(list.to_string()).or_default();
+        let label = field.label();
+        if !seen.insert(label.clone()) && reported.insert(label.clone()) {
+            self.duplicates.push((label.clone(), list.to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn namespace_list<'a>(fields: impl Iterator<Item = &'a mut SignablePayloadField>) {
+    let fields: Vec<&mut SignablePayloadField> = fields.collect();
+    let mut taken: HashSet<String> = fields.iter().map(|field| field.label().clone()).collect();
+    let mut seen = HashSet::new();
+
+    for field in fields {
+        let label = field.label().clone();
+        if !seen.insert(label.clone()) {
+            let renamed = (2..)
+                .map(|occurrence| format!("{label} ({occurrence})"))
+                .find(|candidate| !taken.contains(candidate))
+                .unwrap_or_default();
+            taken.insert(renamed.clone());
+            set_label(field, renamed);
+        }
+
+        match field {
+            SignablePayloadField::PreviewLayout { preview_layout, .. } => {
+                for list in [&mut preview_layout.condensed, &mut preview_layout.expanded]
+                    .into_iter()
+                    .flatten()
+                {
+                    namespace_list(
+                        list.fields
+                            .iter_mut()
+                            .map(|annotated| &mut annotated.signable_payload_field),
+                    );
+                }
+            }
+            SignablePayloadField::ListLayout { list_layout, .. } => namespace_list(
+                list_layout
+                    .fields
+                    .iter_mut()
+                    .map(|annotated| &mut annotated.signable_payload_field),
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn set_label(field: &mut SignablePayloadField, label: String) {
+    let common = match field {
+        SignablePayloadField::Text { common, .. }
+        | SignablePayloadField::TextV2 { common, .. }
+        | SignablePayloadField::Address { common, .. }
+        | SignablePayloadField::AddressV2 { common, .. }
+        | SignablePayloadField::Number { common, .. }
+        | SignablePayloadField::Amount { common, .. }
+        | SignablePayloadField::AmountV2 { common, .. }
+        | SignablePayloadField::AmountV3 { common, .. }
+        | SignablePayloadField::Timestamp { common, .. }
+        | SignablePayloadField::Duration { common, .. }
+        | SignablePayloadField::Percentage { common, .. }
+        | SignablePayloadField::ImageRef { common, .. }
+        | SignablePayloadField::Bytes { common, .. }
+        | SignablePayloadField::Divider { common, .. }
+        | SignablePayloadField::PreviewLayout { common, .. }
+        | SignablePayloadField::ListLayout { common, .. }
+        | SignablePayloadField::Unknown { common, .. } => common,
+    };
+    common.label = label;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::create_text_field;
+    use crate::{
+        AnnotatedPayloadField, SignablePayloadFieldCommon, SignablePayloadFieldListLayout,
+        SignablePayloadFieldPreviewLayout,
+    };
+
+    fn text(label: &str) -> AnnotatedPayloadField {
+        create_text_field(label, "value").unwrap()
+    }
+
+    fn payload() -> SignablePayload {
+        let preview = SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: "Borrow".to_string(),
+                label: "Borrow".to_string(),
+            },
+            preview_layout: SignablePayloadFieldPreviewLayout {
+                title: None,
+                subtitle: None,
+                // The same label in condensed and expanded is not ambiguous
+                condensed: Some(SignablePayloadFieldListLayout {
+                    fields: vec![text("Lending Market")],
+                }),
+                expanded: Some(SignablePayloadFieldListLayout {
+                    fields: vec![
+                        text("Lending Market"),
+                        text("User Address"),
+                        text("Lending Market"),
+                        text("Lending Market (2)"),
+                    ],
+                }),
+            },
+        };
+        SignablePayload::new(
+            0,
+            "Borrow".to_string(),
+            None,
+            vec![
+                text("Network").signable_payload_field,
+                preview,
+                text("Network").signable_payload_field,
+            ],
+            "SuiTx".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_duplicate_labels_are_reported_per_list() {
+        let payload = payload();
+        assert_eq!(
+            payload.duplicate_labels(),
+            vec![
+                (
+                    "Lending Market".to_string(),
+                    "Fields[1].PreviewLayout.Expanded".to_string()
+                ),
+                ("Network".to_string(), "Fields".to_string()),
+            ]
+        );
+        assert_eq!(
+            payload.validate_unique_labels(),
+            Err(VisualSignError::ValidationError(
+                "Duplicate label 'Lending Market' in Fields[1].PreviewLayout.Expanded".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_namespace_duplicate_labels() {
+        let mut payload = payload();
+        payload.namespace_duplicate_labels();
+        assert!(payload.validate_unique_labels().is_ok());
+
+        let labels: Vec<&str> = payload.fields.iter().map(|f| f.label().as_str()).collect();
+        assert_eq!(labels, ["Network", "Borrow", "Network (2)"]);
+
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = &payload.fields[1] else {
+            panic!("Expected PreviewLayout");
+        };
+        let expanded: Vec<&str> = preview_layout
+            .expanded
+            .as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.signable_payload_field.label().as_str())
+            .collect();
+        // "Lending Market (2)" is already taken, so the repeat becomes "(3)"
+        assert_eq!(
+            expanded,
+            [
+                "Lending Market",
+                "User Address",
+                "Lending Market (3)",
+                "Lending Market (2)"
+            ]
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Packaging of flat field lists into a condensed/expanded `PreviewLayout`.
+//!
+//! Chains whose parsers emit one field per transaction property (Ethereum, Tron) describe
+//! their summary with a [`PreviewTemplate`]; [`package_preview_layout`] then builds the same
+//! shape that the Sui and Solana presets produce by hand. The condensed view holds the fields
+//! named by the template, the expanded view holds every field in its original order.
+
+use crate::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+/// Describes how to summarize a flat field list.
+///
+/// Title and subtitle may reference fields as `{Label}`, which is replaced by that field's
+/// fallback text. A line referencing a field that is not present is left out; a missing title
+/// falls back to the layout label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewTemplate {
+    title: String,
+    subtitle: Option<String>,
+    condensed_labels: Vec<String>,
+}
+
+impl PreviewTemplate {
+    pub fn new(title: &str) -> Self {
+        PreviewTemplate {
+            title: title.to_string(),
+            subtitle: None,
+            condensed_labels: Vec::new(),
+        }
+    }
+
+    pub fn with_subtitle(mut self, subtitle: &str) -> Self {
+        self.subtitle = Some(subtitle.to_string());
+        self
+    }
+
+    /// Shows the first field labelled `label` in the condensed view, in the order added.
+    pub fn with_condensed(mut self, label: &str) -> Self {
+        self.condensed_labels.push(label.to_string());
+        self
+    }
+}
+
+/// Wraps `fields` in one `PreviewLayout` field labelled `label` according to `template`.
+pub fn package_preview_layout(
+    label: &str,
+    fields: Vec<SignablePayloadField>,
+    template: &PreviewTemplate,
+) -> SignablePayloadField {
+    let title = render(&template.title, &fields).unwrap_or_else(|| label.to_string());
+    let subtitle = template
+        .subtitle
+        .as_deref()
+        .and_then(|subtitle| render(subtitle, &fields));
+
+    let condensed: Vec<AnnotatedPayloadField> = template
+        .condensed_labels
+        .iter()
+        .filter_map(|wanted| fields.iter().find(|field| field.label() == wanted))
+        .cloned()
+        .map(annotated)
+        .collect();
+    let expanded: Vec<AnnotatedPayloadField> = fields.into_iter().map(annotated).collect();
+
+    let fallback_text = match &subtitle {
+        Some(subtitle) => format!("{title}. {subtitle}"),
+        None => title.clone(),
+    };
+    SignablePayloadField::PreviewLayout {
+        common: SignablePayloadFieldCommon {
+            fallback_text,
+            label: label.to_string(),
+        },
+        preview_layout: SignablePayloadFieldPreviewLayout {
+            title: Some(SignablePayloadFieldTextV2 { text: title }),
+            subtitle: subtitle.map(|text| SignablePayloadFieldTextV2 { text }),
+            condensed: (!condensed.is_empty())
+                .then_some(SignablePayloadFieldListLayout { fields: condensed }),
+            expanded: Some(SignablePayloadFieldListLayout { fields: expanded }),
+        },
+    }
+}
+
+fn annotated(field: SignablePayloadField) -> AnnotatedPayloadField {
+    AnnotatedPayloadField {
+        signable_payload_field: field,
+        static_annotation: None,
+        dynamic_annotation: None,
+    }
+}
+
+// Substitutes `{Label}` placeholders; None when a referenced field is missing
+fn render(template: &str, fields: &[SignablePayloadField]) -> Option<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        let wanted = &rest[start + 1..end];
+        let field = fields.iter().find(|field| field.label() == wanted)?;
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(field.fallback_text());
+        rest = &rest[end + 1..];
+    }
+    rendered.push_str(rest);
+    Some(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::{create_amount_field, create_text_field};
+
+    fn transfer_fields() -> Vec<SignablePayloadField> {
+        [
+            create_text_field("Network", "Tron"),
+            create_text_field("Fee Limit", "10 TRX"),
+            create_text_field("To", "TRecipient"),
+            create_amount_field("Amount", "1.5", "TRX"),
+        ]
+        .into_iter()
+        .map(|field| field.unwrap().signable_payload_field)
+        .collect()
+    }
+
+    #[test]
+    fn test_package_preview_layout() {
+        let template = PreviewTemplate::new("Send {Amount}")
+            .with_subtitle("To {To} on {Network}")
+            .with_condensed("Amount")
+            .with_condensed("To")
+            .with_condensed("Memo");
+        let field = package_preview_layout("Tron Transaction", transfer_fields(), &template);
+
+        assert_eq!(field.fallback_text(), "Send 1.5 TRX. To TRecipient on Tron");
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        let labels = |list: &Option<SignablePayloadFieldListLayout>| -> Vec<String> {
+            list.as_ref()
+                .unwrap()
+                .fields
+                .iter()
+                .map(|f| f.signable_payload_field.label().clone())
+                .collect()
+        };
+        assert_eq!(preview_layout.title.unwrap().text, "Send 1.5 TRX");
+        assert_eq!(labels(&preview_layout.condensed), ["Amount", "To"]);
+        assert_eq!(
+            labels(&preview_layout.expanded),
+            ["Network", "Fee Limit", "To", "Amount"]
+        );
+    }
+
+    #[test]
+    fn test_missing_placeholders_fall_back() {
+        let template = PreviewTemplate::new("Send {Value}")
+            .with_subtitle("to {Recipient}")
+            .with_condensed("Value");
+        let field = package_preview_layout("Tron Transaction", transfer_fields(), &template);
+
+        assert_eq!(field.fallback_text(), "Tron Transaction");
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        assert!(preview_layout.subtitle.is_none());
+        assert!(preview_layout.condensed.is_none());
+        assert_eq!(preview_layout.expanded.unwrap().fields.len(), 4);
+
+        // An unterminated placeholder is treated as missing
+        assert_eq!(render("Send {Amount", &transfer_fields()), None);
+        assert_eq!(
+            render("{Network}", &transfer_fields()).as_deref(),
+            Some("Tron")
+        );
+    }
+}
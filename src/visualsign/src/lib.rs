@@ -1,18 +1,68 @@
+use crate::coverage::Coverage;
 use crate::errors::VisualSignError;
+use crate::parser_info::ParserInfo;
+use serde::de::DeserializeOwned;
 use serde::ser::SerializeMap;
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
+pub mod amount;
+pub mod bundle;
+pub mod capabilities;
+pub mod chain_id;
+pub mod compact;
+pub mod coverage;
 pub mod encodings;
 pub mod errors;
+pub mod expiration;
+pub mod extensions;
+pub mod fee;
 pub mod field_builders;
+pub mod field_path;
+pub mod fixed_point;
+pub mod intent;
+pub mod labels;
+pub mod layout;
+pub mod lossless;
+pub mod memo;
+pub mod message;
+pub mod names;
+pub mod nonce;
+pub mod number;
+pub mod outcome;
+pub mod parser_info;
+pub mod policy;
+pub mod provenance;
+pub mod query;
 pub mod registry;
+pub mod replay;
+pub mod sender;
+pub mod simulation;
+pub mod spec;
+pub mod telemetry;
 pub mod test_utils;
+pub mod text;
+pub mod visit;
 pub mod vsptrait;
 
+/// Version of this crate, reported by the enclave so clients can tell which release rendered
+/// their payloads
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `Version` values of the payloads this crate's converters produce; every chain currently
+/// emits version 0
+pub const PAYLOAD_VERSIONS: &[&str] = &["0"];
+
+mod sealed {
+    // Supertrait of DeterministicOrdering that only this crate can implement, so every type
+    // claiming deterministic ordering has had its Serialize impl audited here
+    pub trait Sealed {}
+}
+
 // Marker trait to ensure types implement deterministic ordering in their serialization
 // Types that implement this trait guarantee their JSON serialization has a deterministic,
 // reproducible field order (currently implemented as alphabetical ordering)
-pub trait DeterministicOrdering: Serialize {
+// The trait is sealed: implement it with `impl_deterministic_serialize!` inside this crate
+pub trait DeterministicOrdering: Serialize + sealed::Sealed {
     // This method can be used to verify at runtime that the implementation maintains
     // deterministic ordering (currently alphabetical, but this is an implementation detail)
     fn verify_deterministic_ordering(&self) -> Result<(), String> {
@@ -25,14 +75,16 @@ pub trait DeterministicOrdering: Serialize {
 // This macro would ideally be a procedural macro that generates both Serialize impl
 // and DeterministicOrdering impl, ensuring they're always in sync
 // For now, this is a declarative macro that helps document the pattern
-#[macro_export]
+// It also implements the sealing supertrait, which is why it is only usable inside this crate
 macro_rules! impl_deterministic_serialize {
     ($type:ty) => {
         // This would be where the procedural macro generates the Serialize impl
         // with guaranteed deterministic ordering
-        impl DeterministicOrdering for $type {}
+        impl $crate::sealed::Sealed for $type {}
+        impl $crate::DeterministicOrdering for $type {}
     };
 }
+pub(crate) use impl_deterministic_serialize;
 
 // Static assertion helper - this ensures at compile time that a type implements the trait
 pub struct StaticAssertDeterministic<T: DeterministicOrdering>(std::marker::PhantomData<T>);
@@ -42,6 +94,27 @@ pub const fn assert_deterministic<T: DeterministicOrdering>() -> StaticAssertDet
     StaticAssertDeterministic(std::marker::PhantomData)
 }
 
+/// Serializes a value whose type implements [`DeterministicOrdering`]
+///
+/// Every field stored in a [`SignablePayloadField`] is serialized through this wrapper, so a
+/// variant holding a type whose ordering has not been audited fails to compile.
+pub struct Deterministic<'a, T: DeterministicOrdering>(&'a T);
+
+impl<'a, T: DeterministicOrdering> Deterministic<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Deterministic(value)
+    }
+}
+
+impl<T: DeterministicOrdering> Serialize for Deterministic<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
 // Helper function to verify JSON has alphabetical ordering (current implementation of deterministic ordering)
 fn verify_json_deterministic(value: &serde_json::Value, path: &str) -> Result<(), String> {
     match value {
@@ -85,14 +158,70 @@ fn is_empty_string(s: &str) -> bool {
     s.is_empty()
 }
 
+// The JSON object read by the custom Deserialize impls, mirroring the maps written by their
+// Serialize counterparts: optional keys may be absent, and every key left over once the type
+// has taken its own is rejected, just as serialization rejects unexpected fields
+struct FieldMap<E> {
+    entries: std::collections::BTreeMap<String, Value>,
+    error: std::marker::PhantomData<E>,
+}
+
+impl<E: serde::de::Error> FieldMap<E> {
+    fn deserialize<'de, D>(deserializer: D) -> Result<Self, E>
+    where
+        D: Deserializer<'de, Error = E>,
+    {
+        Ok(FieldMap {
+            entries: std::collections::BTreeMap::deserialize(deserializer)?,
+            error: std::marker::PhantomData,
+        })
+    }
+
+    fn optional<T: DeserializeOwned>(&mut self, key: &str) -> Result<Option<T>, E> {
+        match self.entries.remove(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => serde_json::from_value(value)
+                .map(Some)
+                .map_err(|e| E::custom(format!("invalid '{key}': {e}"))),
+        }
+    }
+
+    fn required<T: DeserializeOwned>(&mut self, key: &'static str) -> Result<T, E> {
+        self.optional(key)?.ok_or_else(|| E::missing_field(key))
+    }
+
+    fn finish(self, type_name: &str) -> Result<(), E> {
+        match self.entries.keys().next() {
+            Some(key) => Err(E::custom(format!(
+                "Unexpected field '{key}' found in {type_name}"
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
 // A bare bones implementation of the SignablePayload struct and its associated methods
 // The fields are serialized alphabetically to ensure that default serialization works the same
 // and the canonical representation is done by simply sorting the fields first
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayload {
+    /// How much of the raw input the fields decode, when the caller asked for it
+    #[serde(rename = "Coverage", default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<Coverage>,
     #[serde(rename = "Fields")]
     pub fields: Vec<SignablePayloadField>,
-    #[serde(rename = "PayloadType", skip_serializing_if = "is_empty_string")]
+    /// The parser release that rendered this payload, when the caller asked for it
+    #[serde(
+        rename = "ParserInfo",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub parser_info: Option<ParserInfo>,
+    #[serde(
+        rename = "PayloadType",
+        default,
+        skip_serializing_if = "is_empty_string"
+    )]
     pub payload_type: String,
     #[serde(rename = "Subtitle", skip_serializing_if = "Option::is_none")]
     pub subtitle: Option<String>,
@@ -112,97 +241,77 @@ pub struct SignablePayloadFieldCommon {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldCommon
-impl DeterministicOrdering for SignablePayloadFieldCommon {}
+impl_deterministic_serialize!(SignablePayloadFieldCommon);
 
 // Now SignablePayloadField is an enum with variants for each field type
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
-#[serde(tag = "Type")]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SignablePayloadField {
-    #[serde(rename = "text")]
     Text {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "Text")]
         text: SignablePayloadFieldText,
     },
-
-    #[serde(rename = "text_v2")]
     TextV2 {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "TextV2")]
         text_v2: SignablePayloadFieldTextV2,
     },
-
-    #[serde(rename = "address")]
     Address {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "Address")]
         address: SignablePayloadFieldAddress,
     },
-
-    #[serde(rename = "address_v2")]
     AddressV2 {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "AddressV2")]
         address_v2: SignablePayloadFieldAddressV2,
     },
-
-    #[serde(rename = "number")]
     Number {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "Number")]
         number: SignablePayloadFieldNumber,
     },
-
-    #[serde(rename = "amount")]
     Amount {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "Amount")]
         amount: SignablePayloadFieldAmount,
     },
-
-    #[serde(rename = "amount_v2")]
     AmountV2 {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "AmountV2")]
         amount_v2: SignablePayloadFieldAmountV2,
     },
-
-    #[serde(rename = "divider")]
+    AmountV3 {
+        common: SignablePayloadFieldCommon,
+        amount_v3: SignablePayloadFieldAmountV3,
+    },
+    Timestamp {
+        common: SignablePayloadFieldCommon,
+        timestamp: SignablePayloadFieldTimestamp,
+    },
+    Duration {
+        common: SignablePayloadFieldCommon,
+        duration: SignablePayloadFieldDuration,
+    },
+    Percentage {
+        common: SignablePayloadFieldCommon,
+        percentage: SignablePayloadFieldPercentage,
+    },
+    ImageRef {
+        common: SignablePayloadFieldCommon,
+        image_ref: SignablePayloadFieldImageRef,
+    },
+    Bytes {
+        common: SignablePayloadFieldCommon,
+        bytes: SignablePayloadFieldBytes,
+    },
     Divider {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "Divider")]
         divider: SignablePayloadFieldDivider,
     },
-
-    #[serde(rename = "preview_layout")]
     PreviewLayout {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "PreviewLayout")]
         preview_layout: SignablePayloadFieldPreviewLayout,
     },
-
-    #[serde(rename = "list_layout")]
     ListLayout {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "ListLayout")]
         list_layout: SignablePayloadFieldListLayout,
     },
-
-    #[serde(rename = "unknown")]
     Unknown {
-        #[serde(flatten)]
         common: SignablePayloadFieldCommon,
-        #[serde(rename = "Unknown")]
         unknown: SignablePayloadFieldUnknown,
     },
 }
@@ -212,6 +321,7 @@ trait FieldSerializer {
     fn serialize_to_map(
         &self,
     ) -> Result<std::collections::BTreeMap<String, serde_json::Value>, serde_json::Error>;
+    #[cfg(any(test, feature = "strict-verify"))]
     fn get_expected_fields(&self) -> Vec<&'static str>;
 }
 
@@ -225,7 +335,7 @@ macro_rules! serialize_field_variant {
 
         // Add variant-specific fields
         $(
-            $fields.insert($field_name.to_string(), serde_json::to_value($field_value).unwrap());
+            $fields.insert($field_name.to_string(), serde_json::to_value($crate::Deterministic::new($field_value)).unwrap());
         )*
     };
 }
@@ -260,6 +370,24 @@ impl FieldSerializer for SignablePayloadField {
             SignablePayloadField::AmountV2 { common, amount_v2 } => {
                 serialize_field_variant!(fields, "amount_v2", common, ("AmountV2", amount_v2));
             }
+            SignablePayloadField::AmountV3 { common, amount_v3 } => {
+                serialize_field_variant!(fields, "amount_v3", common, ("AmountV3", amount_v3));
+            }
+            SignablePayloadField::Timestamp { common, timestamp } => {
+                serialize_field_variant!(fields, "timestamp", common, ("Timestamp", timestamp));
+            }
+            SignablePayloadField::Duration { common, duration } => {
+                serialize_field_variant!(fields, "duration", common, ("Duration", duration));
+            }
+            SignablePayloadField::Percentage { common, percentage } => {
+                serialize_field_variant!(fields, "percentage", common, ("Percentage", percentage));
+            }
+            SignablePayloadField::ImageRef { common, image_ref } => {
+                serialize_field_variant!(fields, "image_ref", common, ("ImageRef", image_ref));
+            }
+            SignablePayloadField::Bytes { common, bytes } => {
+                serialize_field_variant!(fields, "bytes", common, ("Bytes", bytes));
+            }
             SignablePayloadField::Divider { common, divider } => {
                 serialize_field_variant!(fields, "divider", common, ("Divider", divider));
             }
@@ -294,6 +422,7 @@ impl FieldSerializer for SignablePayloadField {
         Ok(fields.into_iter().collect())
     }
 
+    #[cfg(any(test, feature = "strict-verify"))]
     fn get_expected_fields(&self) -> Vec<&'static str> {
         let mut base_fields = vec!["FallbackText", "Label", "Type"];
 
@@ -305,6 +434,12 @@ impl FieldSerializer for SignablePayloadField {
             SignablePayloadField::Number { .. } => base_fields.push("Number"),
             SignablePayloadField::Amount { .. } => base_fields.push("Amount"),
             SignablePayloadField::AmountV2 { .. } => base_fields.push("AmountV2"),
+            SignablePayloadField::AmountV3 { .. } => base_fields.push("AmountV3"),
+            SignablePayloadField::Timestamp { .. } => base_fields.push("Timestamp"),
+            SignablePayloadField::Duration { .. } => base_fields.push("Duration"),
+            SignablePayloadField::Percentage { .. } => base_fields.push("Percentage"),
+            SignablePayloadField::ImageRef { .. } => base_fields.push("ImageRef"),
+            SignablePayloadField::Bytes { .. } => base_fields.push("Bytes"),
             SignablePayloadField::Divider { .. } => base_fields.push("Divider"),
             SignablePayloadField::PreviewLayout { .. } => base_fields.push("PreviewLayout"),
             SignablePayloadField::ListLayout { .. } => base_fields.push("ListLayout"),
@@ -325,32 +460,36 @@ impl Serialize for SignablePayloadField {
         // Use the trait method to get serialized fields
         let sorted_map = self.serialize_to_map().map_err(serde::ser::Error::custom)?;
 
-        // Verify that all expected fields are present
-        let expected_fields = self.get_expected_fields();
-        let actual_fields: Vec<_> = sorted_map.keys().map(|s| s.as_str()).collect();
-
-        // Check for missing fields
-        for expected in &expected_fields {
-            if !actual_fields.contains(expected) {
-                return Err(serde::ser::Error::custom(format!(
-                    "Missing expected field '{}' in serialization of {:?}. Expected fields: {:?}, Actual fields: {:?}",
-                    expected,
-                    std::mem::discriminant(self),
-                    expected_fields,
-                    actual_fields
-                )));
+        // Verify that all expected fields are present. This runs on every serialize, so hosts
+        // serializing at high throughput can drop it by disabling the `strict-verify` feature
+        #[cfg(feature = "strict-verify")]
+        {
+            let expected_fields = self.get_expected_fields();
+            let actual_fields: Vec<_> = sorted_map.keys().map(|s| s.as_str()).collect();
+
+            // Check for missing fields
+            for expected in &expected_fields {
+                if !actual_fields.contains(expected) {
+                    return Err(serde::ser::Error::custom(format!(
+                        "Missing expected field '{}' in serialization of {:?}. Expected fields: {:?}, Actual fields: {:?}",
+                        expected,
+                        std::mem::discriminant(self),
+                        expected_fields,
+                        actual_fields
+                    )));
+                }
             }
-        }
 
-        // Check for unexpected fields (fields that shouldn't be there)
-        for actual in &actual_fields {
-            if !expected_fields.contains(actual) {
-                return Err(serde::ser::Error::custom(format!(
-                    "Unexpected field '{}' found in serialization of {:?}. Expected fields: {:?}",
-                    actual,
-                    std::mem::discriminant(self),
-                    expected_fields
-                )));
+            // Check for unexpected fields (fields that shouldn't be there)
+            for actual in &actual_fields {
+                if !expected_fields.contains(actual) {
+                    return Err(serde::ser::Error::custom(format!(
+                        "Unexpected field '{}' found in serialization of {:?}. Expected fields: {:?}",
+                        actual,
+                        std::mem::discriminant(self),
+                        expected_fields
+                    )));
+                }
             }
         }
 
@@ -364,7 +503,163 @@ impl Serialize for SignablePayloadField {
 }
 
 // Implement DeterministicOrdering for SignablePayloadField since it has custom Serialize
-impl DeterministicOrdering for SignablePayloadField {}
+impl_deterministic_serialize!(SignablePayloadField);
+
+/// Deepest nesting of layout fields (a `PreviewLayout` or `ListLayout` inside another) accepted
+/// when deserializing; real payloads nest two or three levels at most
+pub const MAX_LAYOUT_DEPTH: usize = 8;
+
+thread_local! {
+    // Layouts being deserialized on this thread. Nested fields are decoded recursively, so
+    // without a bound a hostile payload could exhaust the stack
+    static LAYOUT_DEPTH: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+// Held while a layout's nested fields are deserialized; dropping it leaves the layout
+struct LayoutDepthGuard;
+
+impl LayoutDepthGuard {
+    fn enter<E: serde::de::Error>() -> Result<Self, E> {
+        LAYOUT_DEPTH.with(|depth| {
+            if depth.get() >= MAX_LAYOUT_DEPTH {
+                return Err(E::custom(format!(
+                    "Layouts nested more than {MAX_LAYOUT_DEPTH} levels deep"
+                )));
+            }
+            depth.set(depth.get() + 1);
+            Ok(LayoutDepthGuard)
+        })
+    }
+}
+
+impl Drop for LayoutDepthGuard {
+    fn drop(&mut self) {
+        LAYOUT_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+// The "Type" values written by the Serialize implementation, one per variant
+const FIELD_TYPES: &[&str] = &[
+    "text",
+    "text_v2",
+    "address",
+    "address_v2",
+    "number",
+    "amount",
+    "amount_v2",
+    "amount_v3",
+    "timestamp",
+    "duration",
+    "percentage",
+    "image_ref",
+    "bytes",
+    "divider",
+    "preview_layout",
+    "list_layout",
+    "unknown",
+];
+
+impl SignablePayloadField {
+    // Inverse of serialize_to_map: takes the common keys, "Type" and the variant's own key
+    fn from_map<E: serde::de::Error>(map: &mut FieldMap<E>) -> Result<Self, E> {
+        let field_type: String = map.required("Type")?;
+        let common = SignablePayloadFieldCommon {
+            fallback_text: map.required("FallbackText")?,
+            label: map.required("Label")?,
+        };
+        let field = match field_type.as_str() {
+            "text" => SignablePayloadField::Text {
+                common,
+                text: map.required("Text")?,
+            },
+            "text_v2" => SignablePayloadField::TextV2 {
+                common,
+                text_v2: map.required("TextV2")?,
+            },
+            "address" => SignablePayloadField::Address {
+                common,
+                address: map.required("Address")?,
+            },
+            "address_v2" => SignablePayloadField::AddressV2 {
+                common,
+                address_v2: map.required("AddressV2")?,
+            },
+            "number" => SignablePayloadField::Number {
+                common,
+                number: map.required("Number")?,
+            },
+            "amount" => SignablePayloadField::Amount {
+                common,
+                amount: map.required("Amount")?,
+            },
+            "amount_v2" => SignablePayloadField::AmountV2 {
+                common,
+                amount_v2: map.required("AmountV2")?,
+            },
+            "amount_v3" => SignablePayloadField::AmountV3 {
+                common,
+                amount_v3: map.required("AmountV3")?,
+            },
+            "timestamp" => SignablePayloadField::Timestamp {
+                common,
+                timestamp: map.required("Timestamp")?,
+            },
+            "duration" => SignablePayloadField::Duration {
+                common,
+                duration: map.required("Duration")?,
+            },
+            "percentage" => SignablePayloadField::Percentage {
+                common,
+                percentage: map.required("Percentage")?,
+            },
+            "image_ref" => SignablePayloadField::ImageRef {
+                common,
+                image_ref: map.required("ImageRef")?,
+            },
+            "bytes" => SignablePayloadField::Bytes {
+                common,
+                bytes: map.required("Bytes")?,
+            },
+            "divider" => SignablePayloadField::Divider {
+                common,
+                divider: map.required("Divider")?,
+            },
+            "preview_layout" => {
+                let _depth = LayoutDepthGuard::enter::<E>()?;
+                SignablePayloadField::PreviewLayout {
+                    common,
+                    preview_layout: map.required("PreviewLayout")?,
+                }
+            }
+            "list_layout" => {
+                let _depth = LayoutDepthGuard::enter::<E>()?;
+                SignablePayloadField::ListLayout {
+                    common,
+                    list_layout: map.required("ListLayout")?,
+                }
+            }
+            "unknown" => SignablePayloadField::Unknown {
+                common,
+                unknown: map.required("Unknown")?,
+            },
+            other => return Err(E::unknown_variant(other, FIELD_TYPES)),
+        };
+        Ok(field)
+    }
+}
+
+// Custom Deserialize implementation accepting exactly the fields Serialize writes
+impl<'de> Deserialize<'de> for SignablePayloadField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = FieldMap::deserialize(deserializer)?;
+        let field = SignablePayloadField::from_map(&mut map)?;
+        map.finish("SignablePayloadField")?;
+        Ok(field)
+    }
+}
 
 // Helper methods for the enum
 impl SignablePayloadField {
@@ -377,6 +672,12 @@ impl SignablePayloadField {
             SignablePayloadField::Number { common, .. } => &common.fallback_text,
             SignablePayloadField::Amount { common, .. } => &common.fallback_text,
             SignablePayloadField::AmountV2 { common, .. } => &common.fallback_text,
+            SignablePayloadField::AmountV3 { common, .. } => &common.fallback_text,
+            SignablePayloadField::Timestamp { common, .. } => &common.fallback_text,
+            SignablePayloadField::Duration { common, .. } => &common.fallback_text,
+            SignablePayloadField::Percentage { common, .. } => &common.fallback_text,
+            SignablePayloadField::ImageRef { common, .. } => &common.fallback_text,
+            SignablePayloadField::Bytes { common, .. } => &common.fallback_text,
             SignablePayloadField::Divider { common, .. } => &common.fallback_text,
             SignablePayloadField::PreviewLayout { common, .. } => &common.fallback_text,
             SignablePayloadField::ListLayout { common, .. } => &common.fallback_text,
@@ -393,6 +694,12 @@ impl SignablePayloadField {
             SignablePayloadField::Number { common, .. } => &common.label,
             SignablePayloadField::Amount { common, .. } => &common.label,
             SignablePayloadField::AmountV2 { common, .. } => &common.label,
+            SignablePayloadField::AmountV3 { common, .. } => &common.label,
+            SignablePayloadField::Timestamp { common, .. } => &common.label,
+            SignablePayloadField::Duration { common, .. } => &common.label,
+            SignablePayloadField::Percentage { common, .. } => &common.label,
+            SignablePayloadField::ImageRef { common, .. } => &common.label,
+            SignablePayloadField::Bytes { common, .. } => &common.label,
             SignablePayloadField::Divider { common, .. } => &common.label,
             SignablePayloadField::PreviewLayout { common, .. } => &common.label,
             SignablePayloadField::ListLayout { common, .. } => &common.label,
@@ -409,6 +716,12 @@ impl SignablePayloadField {
             SignablePayloadField::Number { .. } => "number",
             SignablePayloadField::Amount { .. } => "amount",
             SignablePayloadField::AmountV2 { .. } => "amount_v2",
+            SignablePayloadField::AmountV3 { .. } => "amount_v3",
+            SignablePayloadField::Timestamp { .. } => "timestamp",
+            SignablePayloadField::Duration { .. } => "duration",
+            SignablePayloadField::Percentage { .. } => "percentage",
+            SignablePayloadField::ImageRef { .. } => "image_ref",
+            SignablePayloadField::Bytes { .. } => "bytes",
             SignablePayloadField::Divider { .. } => "divider",
             SignablePayloadField::PreviewLayout { .. } => "preview_layout",
             SignablePayloadField::ListLayout { .. } => "list_layout",
@@ -418,20 +731,34 @@ impl SignablePayloadField {
 }
 
 // Update all struct definitions to use String instead of NormalString
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldPreviewLayout {
-    #[serde(rename = "Title", skip_serializing_if = "Option::is_none")]
     pub title: Option<SignablePayloadFieldTextV2>,
-    #[serde(rename = "Subtitle", skip_serializing_if = "Option::is_none")]
     pub subtitle: Option<SignablePayloadFieldTextV2>,
-    #[serde(rename = "Condensed", skip_serializing_if = "Option::is_none")]
     pub condensed: Option<SignablePayloadFieldListLayout>,
-    #[serde(rename = "Expanded", skip_serializing_if = "Option::is_none")]
     pub expanded: Option<SignablePayloadFieldListLayout>,
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldPreviewLayout
-impl DeterministicOrdering for SignablePayloadFieldPreviewLayout {}
+impl_deterministic_serialize!(SignablePayloadFieldPreviewLayout);
+
+// Custom Deserialize implementation mirroring the Serialize implementation below
+impl<'de> Deserialize<'de> for SignablePayloadFieldPreviewLayout {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = FieldMap::deserialize(deserializer)?;
+        let preview_layout = SignablePayloadFieldPreviewLayout {
+            title: map.optional("Title")?,
+            subtitle: map.optional("Subtitle")?,
+            condensed: map.optional("Condensed")?,
+            expanded: map.optional("Expanded")?,
+        };
+        map.finish("PreviewLayout")?;
+        Ok(preview_layout)
+    }
+}
 
 // Custom Serialize implementation for SignablePayloadFieldPreviewLayout to ensure alphabetical ordering
 impl Serialize for SignablePayloadFieldPreviewLayout {
@@ -485,7 +812,7 @@ pub struct SignablePayloadFieldListLayout {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldListLayout
-impl DeterministicOrdering for SignablePayloadFieldListLayout {}
+impl_deterministic_serialize!(SignablePayloadFieldListLayout);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldText {
@@ -494,7 +821,7 @@ pub struct SignablePayloadFieldText {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldText
-impl DeterministicOrdering for SignablePayloadFieldText {}
+impl_deterministic_serialize!(SignablePayloadFieldText);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldTextV2 {
@@ -503,7 +830,7 @@ pub struct SignablePayloadFieldTextV2 {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldTextV2
-impl DeterministicOrdering for SignablePayloadFieldTextV2 {}
+impl_deterministic_serialize!(SignablePayloadFieldTextV2);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldAddress {
@@ -514,24 +841,31 @@ pub struct SignablePayloadFieldAddress {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldAddress
-impl DeterministicOrdering for SignablePayloadFieldAddress {}
+impl_deterministic_serialize!(SignablePayloadFieldAddress);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldAddressV2 {
     #[serde(rename = "Address")]
     pub address: String,
-    #[serde(rename = "Name", skip_serializing_if = "is_empty_string")]
+    #[serde(rename = "Name", default, skip_serializing_if = "is_empty_string")]
     pub name: String,
     #[serde(rename = "Memo", skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
-    #[serde(rename = "AssetLabel", skip_serializing_if = "is_empty_string")]
+    #[serde(
+        rename = "AssetLabel",
+        default,
+        skip_serializing_if = "is_empty_string"
+    )]
     pub asset_label: String,
     #[serde(rename = "BadgeText", skip_serializing_if = "Option::is_none")]
     pub badge_text: Option<String>,
+    // Set only when the host supplied a name resolver: true if `name` came from it
+    #[serde(rename = "NameResolved", skip_serializing_if = "Option::is_none")]
+    pub name_resolved: Option<bool>,
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldAddressV2
-impl DeterministicOrdering for SignablePayloadFieldAddressV2 {}
+impl_deterministic_serialize!(SignablePayloadFieldAddressV2);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldNumber {
@@ -540,7 +874,7 @@ pub struct SignablePayloadFieldNumber {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldNumber
-impl DeterministicOrdering for SignablePayloadFieldNumber {}
+impl_deterministic_serialize!(SignablePayloadFieldNumber);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldAmount {
@@ -551,13 +885,11 @@ pub struct SignablePayloadFieldAmount {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldAmount
-impl DeterministicOrdering for SignablePayloadFieldAmount {}
+impl_deterministic_serialize!(SignablePayloadFieldAmount);
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldAmountV2 {
-    #[serde(rename = "Amount")]
     pub amount: String,
-    #[serde(rename = "Abbreviation", skip_serializing_if = "Option::is_none")]
     pub abbreviation: Option<String>,
 }
 
@@ -578,7 +910,151 @@ impl Serialize for SignablePayloadFieldAmountV2 {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldAmountV2
-impl DeterministicOrdering for SignablePayloadFieldAmountV2 {}
+impl_deterministic_serialize!(SignablePayloadFieldAmountV2);
+
+impl<'de> Deserialize<'de> for SignablePayloadFieldAmountV2 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = FieldMap::deserialize(deserializer)?;
+        let amount = SignablePayloadFieldAmountV2 {
+            amount: map.required("Amount")?,
+            abbreviation: map.optional("Abbreviation")?,
+        };
+        map.finish("AmountV2")?;
+        Ok(amount)
+    }
+}
+
+/// An amount with an optional host-supplied fiat conversion.
+///
+/// The fiat fields are set together and carry the rate's source and observation time, so a
+/// wallet can show "0.5 ETH (~$1,230)" while making clear where the price came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignablePayloadFieldAmountV3 {
+    pub amount: String,
+    pub abbreviation: Option<String>,
+    pub fiat_value: Option<String>,
+    pub fiat_currency: Option<String>,
+    pub rate_source: Option<String>,
+    pub rate_timestamp: Option<String>,
+}
+
+impl Serialize for SignablePayloadFieldAmountV3 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use std::collections::BTreeMap;
+
+        let mut map = BTreeMap::new();
+        map.insert("Amount", &self.amount);
+        let optional = [
+            ("Abbreviation", &self.abbreviation),
+            ("FiatValue", &self.fiat_value),
+            ("FiatCurrency", &self.fiat_currency),
+            ("RateSource", &self.rate_source),
+            ("RateTimestamp", &self.rate_timestamp),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                map.insert(key, value);
+            }
+        }
+        map.serialize(serializer)
+    }
+}
+
+// Implement DeterministicOrdering for SignablePayloadFieldAmountV3
+impl_deterministic_serialize!(SignablePayloadFieldAmountV3);
+
+impl<'de> Deserialize<'de> for SignablePayloadFieldAmountV3 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = FieldMap::deserialize(deserializer)?;
+        let amount = SignablePayloadFieldAmountV3 {
+            amount: map.required("Amount")?,
+            abbreviation: map.optional("Abbreviation")?,
+            fiat_value: map.optional("FiatValue")?,
+            fiat_currency: map.optional("FiatCurrency")?,
+            rate_source: map.optional("RateSource")?,
+            rate_timestamp: map.optional("RateTimestamp")?,
+        };
+        map.finish("AmountV3")?;
+        Ok(amount)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignablePayloadFieldTimestamp {
+    /// RFC 3339 in UTC, e.g. "2024-06-01T12:00:00Z", with milliseconds only when non-zero
+    #[serde(rename = "Timestamp")]
+    pub timestamp: String,
+}
+
+// Implement DeterministicOrdering for SignablePayloadFieldTimestamp
+impl_deterministic_serialize!(SignablePayloadFieldTimestamp);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignablePayloadFieldDuration {
+    /// Whole seconds as a base-10 integer string
+    #[serde(rename = "Seconds")]
+    pub seconds: String,
+}
+
+// Implement DeterministicOrdering for SignablePayloadFieldDuration
+impl_deterministic_serialize!(SignablePayloadFieldDuration);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignablePayloadFieldPercentage {
+    /// Plain decimal in percent, so "0.5" is half a percent (50 bps)
+    #[serde(rename = "Percentage")]
+    pub percentage: String,
+}
+
+// Implement DeterministicOrdering for SignablePayloadFieldPercentage
+impl_deterministic_serialize!(SignablePayloadFieldPercentage);
+
+/// Reference to an icon the display client already has, such as a token logo.
+///
+/// Nothing is fetched while parsing: the client looks the image up by `identifier` in its own
+/// asset store and only shows it if its bytes hash to `content_hash`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignablePayloadFieldImageRef {
+    /// `sha256:` followed by the lowercase hex digest of the image bytes
+    #[serde(rename = "ContentHash")]
+    pub content_hash: String,
+    /// Symbolic name of the asset, e.g. "erc20:1:0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+    #[serde(rename = "Identifier")]
+    pub identifier: String,
+}
+
+// Implement DeterministicOrdering for SignablePayloadFieldImageRef
+impl_deterministic_serialize!(SignablePayloadFieldImageRef);
+
+/// Binary data, such as contract calldata, shown as a short preview.
+///
+/// `hex` keeps every byte, so the payload and the signature over it still commit to the whole
+/// data; clients show `preview` and `length` and only render `hex` on request. Build it with
+/// [`field_builders::create_bytes_field`], which derives the preview canonically.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignablePayloadFieldBytes {
+    /// The full data, lowercase hex prefixed with `0x`
+    #[serde(rename = "Hex")]
+    pub hex: String,
+    /// Number of bytes, e.g. "1028"
+    #[serde(rename = "Length")]
+    pub length: String,
+    /// The first and last bytes joined by `...`, or the full hex when that is as short
+    #[serde(rename = "Preview")]
+    pub preview: String,
+}
+
+// Implement DeterministicOrdering for SignablePayloadFieldBytes
+impl_deterministic_serialize!(SignablePayloadFieldBytes);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldDivider {
@@ -587,7 +1063,7 @@ pub struct SignablePayloadFieldDivider {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldDivider
-impl DeterministicOrdering for SignablePayloadFieldDivider {}
+impl_deterministic_serialize!(SignablePayloadFieldDivider);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldUnknown {
@@ -598,7 +1074,7 @@ pub struct SignablePayloadFieldUnknown {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldUnknown
-impl DeterministicOrdering for SignablePayloadFieldUnknown {}
+impl_deterministic_serialize!(SignablePayloadFieldUnknown);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldStaticAnnotation {
@@ -607,7 +1083,7 @@ pub struct SignablePayloadFieldStaticAnnotation {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldStaticAnnotation
-impl DeterministicOrdering for SignablePayloadFieldStaticAnnotation {}
+impl_deterministic_serialize!(SignablePayloadFieldStaticAnnotation);
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SignablePayloadFieldDynamicAnnotation {
@@ -635,7 +1111,7 @@ impl Serialize for SignablePayloadFieldDynamicAnnotation {
 }
 
 // Implement DeterministicOrdering for SignablePayloadFieldDynamicAnnotation
-impl DeterministicOrdering for SignablePayloadFieldDynamicAnnotation {}
+impl_deterministic_serialize!(SignablePayloadFieldDynamicAnnotation);
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct AnnotatedPayload {
@@ -649,18 +1125,34 @@ pub struct AnnotatedPayload {
     pub fields: Option<Vec<AnnotatedPayloadField>>,
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AnnotatedPayloadField {
-    #[serde(flatten)]
     pub signable_payload_field: SignablePayloadField,
-    #[serde(rename = "StaticAnnotation", skip_serializing_if = "Option::is_none")]
     pub static_annotation: Option<SignablePayloadFieldStaticAnnotation>,
-    #[serde(rename = "DynamicAnnotation", skip_serializing_if = "Option::is_none")]
     pub dynamic_annotation: Option<SignablePayloadFieldDynamicAnnotation>,
 }
 
 // Implement DeterministicOrdering for AnnotatedPayloadField since it has custom Serialize
-impl DeterministicOrdering for AnnotatedPayloadField {}
+impl_deterministic_serialize!(AnnotatedPayloadField);
+
+// Custom Deserialize implementation: the annotations sit next to the flattened field's keys
+impl<'de> Deserialize<'de> for AnnotatedPayloadField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = FieldMap::deserialize(deserializer)?;
+        let static_annotation = map.optional("StaticAnnotation")?;
+        let dynamic_annotation = map.optional("DynamicAnnotation")?;
+        let signable_payload_field = SignablePayloadField::from_map(&mut map)?;
+        map.finish("AnnotatedPayloadField")?;
+        Ok(AnnotatedPayloadField {
+            signable_payload_field,
+            static_annotation,
+            dynamic_annotation,
+        })
+    }
+}
 
 // Custom Serialize implementation for AnnotatedPayloadField to ensure alphabetical ordering
 impl Serialize for AnnotatedPayloadField {
@@ -722,7 +1214,7 @@ impl DividerStyle {
 }
 
 // Implement DeterministicOrdering for SignablePayload
-impl DeterministicOrdering for SignablePayload {}
+impl_deterministic_serialize!(SignablePayload);
 
 impl SignablePayload {
     pub fn new(
@@ -738,6 +1230,8 @@ impl SignablePayload {
             subtitle,
             payload_type,
             fields,
+            parser_info: None,
+            coverage: None,
         }
     }
 
@@ -758,29 +1252,16 @@ impl SignablePayload {
             subtitle,
             payload_type,
             fields: fields.into_iter().map(Into::into).collect(),
+            parser_info: None,
+            coverage: None,
         }
     }
 
     // Helper function that ensures all nested types in a complex field structure implement DeterministicOrdering
     pub fn verify_field_deterministic_ordering(field: &SignablePayloadField) -> Result<(), String> {
         // This function compile-time enforces that all nested types implement DeterministicOrdering
-        // by calling verify_deterministic_ordering on each component
-        match field {
-            SignablePayloadField::PreviewLayout { preview_layout, .. } => {
-                preview_layout.verify_deterministic_ordering()?;
-                if let Some(ref condensed) = preview_layout.condensed {
-                    condensed.verify_deterministic_ordering()?;
-                }
-                if let Some(ref expanded) = preview_layout.expanded {
-                    expanded.verify_deterministic_ordering()?;
-                }
-            }
-            SignablePayloadField::ListLayout { list_layout, .. } => {
-                list_layout.verify_deterministic_ordering()?;
-            }
-            _ => {}
-        }
-        field.verify_deterministic_ordering()
+        // by calling verify_deterministic_ordering on each component, at every level of nesting
+        visit::walk_field("Field", field, &mut OrderingVerifier)
     }
 
     pub fn to_json(&self) -> Result<String, Box<dyn std::error::Error>> {
@@ -800,6 +1281,15 @@ impl SignablePayload {
         Ok(String::from_utf8(buf)?)
     }
 
+    /// Decodes a payload from untrusted JSON.
+    ///
+    /// Nesting is bounded twice: serde_json's recursion limit rejects deeply nested JSON values
+    /// while reading, and layouts nested more than [`MAX_LAYOUT_DEPTH`] levels deep are rejected
+    /// while building fields.
+    pub fn from_json(json: &str) -> Result<Self, VisualSignError> {
+        serde_json::from_str(json).map_err(|e| VisualSignError::SerializationError(e.to_string()))
+    }
+
     // Add this method for debugging
     pub fn to_pretty_json(&self) -> Result<String, Box<dyn std::error::Error>> {
         let value = serde_json::to_value(self)?;
@@ -808,6 +1298,41 @@ impl SignablePayload {
     }
 }
 
+// Verifies the ordering of every field, annotated field and layout it visits
+struct OrderingVerifier;
+
+impl visit::PayloadVisitor for OrderingVerifier {
+    type Error = String;
+
+    fn visit_field(&mut self, _path: &str, field: &SignablePayloadField) -> Result<(), String> {
+        field.verify_deterministic_ordering()
+    }
+
+    fn visit_annotated(
+        &mut self,
+        _path: &str,
+        annotated: &AnnotatedPayloadField,
+    ) -> Result<(), String> {
+        annotated.verify_deterministic_ordering()
+    }
+
+    fn visit_preview_layout(
+        &mut self,
+        _path: &str,
+        preview_layout: &SignablePayloadFieldPreviewLayout,
+    ) -> Result<(), String> {
+        preview_layout.verify_deterministic_ordering()
+    }
+
+    fn visit_list_layout(
+        &mut self,
+        _path: &str,
+        list_layout: &SignablePayloadFieldListLayout,
+    ) -> Result<(), String> {
+        list_layout.verify_deterministic_ordering()
+    }
+}
+
 // Helper function to recursively sort JSON by keys alphabetically
 fn sort_json_alphabetically(value: serde_json::Value) -> serde_json::Value {
     match value {
@@ -832,43 +1357,166 @@ fn sort_json_alphabetically(value: serde_json::Value) -> serde_json::Value {
     }
 }
 
-impl SignablePayload {
-    /// Validates that the payload only contains safe ASCII characters to prevent unicode confusion
-    /// This should be called before returning any SignablePayload to ensure consistent character safety
-    /// I understand that this might be overly cautious, but it's better to be safe at launch and incrementally open up unicode support later
-    pub fn validate_charset(&self) -> Result<(), VisualSignError> {
-        let json_str = self.to_json().map_err(|e| {
-            VisualSignError::SerializationError(format!("Failed to serialize for validation: {e}"))
-        })?;
+/// Which characters a payload may contain, checked by [`SignablePayload::validate_charset_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CharsetPolicy {
+    /// Printable ASCII only
+    #[default]
+    Ascii,
+    /// Any printable Unicode, except format characters (General_Category=Cf) such as
+    /// bidirectional overrides and zero-width spaces that can hide or reorder what the signer
+    /// sees
+    Unicode,
+}
 
-        // Check for unicode escapes
-        if json_str.contains("\\u") {
-            return Err(VisualSignError::ValidationError(
-                "Restricted Characters Detected".to_string(),
-            ));
-        }
+impl CharsetPolicy {
+    /// Every policy, in the order clients should offer them
+    pub const ALL: [CharsetPolicy; 2] = [CharsetPolicy::Ascii, CharsetPolicy::Unicode];
 
-        // Use Rust's built-in ASCII validation
-        if !json_str.is_ascii() {
-            return Err(VisualSignError::ValidationError(
-                "Restricted Characters Detected".to_string(),
-            ));
+    /// The serialized name, as the `Charset` option takes it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CharsetPolicy::Ascii => "Ascii",
+            CharsetPolicy::Unicode => "Unicode",
         }
+    }
+}
+
+// Format characters (General_Category=Cf, Unicode 15): invisible, and some reorder the text
+// around them, e.g. bidi overrides, zero-width spaces, the soft hyphen and the byte order mark
+fn is_format_character(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}'
+            | '\u{0600}'..='\u{0605}'
+            | '\u{061C}'
+            | '\u{06DD}'
+            | '\u{070F}'
+            | '\u{0890}'..='\u{0891}'
+            | '\u{08E2}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+            | '\u{110BD}'
+            | '\u{110CD}'
+            | '\u{13430}'..='\u{1343F}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0001}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
 
-        // Additional validation for printable characters
+// Checks serialized JSON against `policy`, describing the first violation
+fn check_charset(json_str: &str, policy: CharsetPolicy) -> Result<(), String> {
+    if policy == CharsetPolicy::Unicode {
+        // serde_json escapes control characters as \u, so any escape is restricted
+        if json_str.contains("\\u") {
+            return Err("Restricted Characters Detected".to_string());
+        }
         for (i, ch) in json_str.char_indices() {
-            if !ch.is_ascii_graphic() && !ch.is_ascii_whitespace() {
-                return Err(VisualSignError::ValidationError(format!(
+            if (ch.is_control() && !ch.is_ascii_whitespace()) || is_format_character(ch) {
+                return Err(format!(
                     "JSON output contains non-printable character '{}' (U+{:02X}) at position {}",
                     ch.escape_default(),
                     ch as u32,
                     i
-                )));
+                ));
             }
         }
+        return Ok(());
+    }
+
+    // Check for unicode escapes
+    if json_str.contains("\\u") {
+        return Err("Restricted Characters Detected".to_string());
+    }
+
+    // Use Rust's built-in ASCII validation
+    if !json_str.is_ascii() {
+        return Err("Restricted Characters Detected".to_string());
+    }
+
+    // Additional validation for printable characters
+    for (i, ch) in json_str.char_indices() {
+        if !ch.is_ascii_graphic() && !ch.is_ascii_whitespace() {
+            return Err(format!(
+                "JSON output contains non-printable character '{}' (U+{:02X}) at position {}",
+                ch.escape_default(),
+                ch as u32,
+                i
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Finds the deepest field, on the first branch of the payload, whose JSON or annotations
+// violate the policy
+struct CharsetLocator {
+    policy: CharsetPolicy,
+    path: Option<String>,
+}
 
+impl CharsetLocator {
+    fn check<T: Serialize>(&mut self, path: &str, value: &T) -> Result<(), ()> {
+        if let Some(found) = &self.path {
+            if path != found && !path.starts_with(&format!("{found}.")) {
+                return Err(());
+            }
+        }
+        let violates = serde_json::to_string(value)
+            .map(|json| check_charset(&json, self.policy).is_err())
+            .unwrap_or(false);
+        if violates {
+            self.path = Some(path.to_string());
+        }
         Ok(())
     }
+}
+
+impl visit::PayloadVisitor for CharsetLocator {
+    type Error = ();
+
+    fn visit_field(&mut self, path: &str, field: &SignablePayloadField) -> Result<(), ()> {
+        self.check(path, field)
+    }
+
+    fn visit_annotated(&mut self, path: &str, annotated: &AnnotatedPayloadField) -> Result<(), ()> {
+        self.check(path, annotated)
+    }
+}
+
+impl SignablePayload {
+    /// Validates that the payload only contains safe ASCII characters to prevent unicode confusion
+    /// This should be called before returning any SignablePayload to ensure consistent character safety
+    /// I understand that this might be overly cautious, but it's better to be safe at launch and incrementally open up unicode support later
+    pub fn validate_charset(&self) -> Result<(), VisualSignError> {
+        self.validate_charset_with(CharsetPolicy::Ascii)
+    }
+
+    /// Validates the payload's characters against `policy`; the error names the path of the
+    /// field holding a restricted character, when one does
+    pub fn validate_charset_with(&self, policy: CharsetPolicy) -> Result<(), VisualSignError> {
+        let json_str = self.to_json().map_err(|e| {
+            VisualSignError::SerializationError(format!("Failed to serialize for validation: {e}"))
+        })?;
+
+        check_charset(&json_str, policy).map_err(|message| {
+            let mut locator = CharsetLocator { policy, path: None };
+            // The locator stops the walk once it leaves the subtree of the field it found
+            let _ = visit::walk_payload(self, &mut locator);
+            VisualSignError::ValidationError(match locator.path {
+                Some(path) => format!("{message} in {path}"),
+                None => message,
+            })
+        })
+    }
 
     /// Validates and returns the JSON string, ensuring charset safety
     pub fn to_validated_json(&self) -> Result<String, VisualSignError> {
@@ -922,6 +1570,8 @@ mod tests {
     #[test]
     fn test_eth_user_intent_equivalence() {
         // this is a relatively lazy attempt to keep this consistent with the Go implementation at
+        // the value level; byte-for-byte parity is checked by the shared vectors in
+        // tests/vectors/canonical_json, whose eth_withdraw case encodes this same payload
         let from_address = "0xYourFromAddress";
 
         let fields = vec![
@@ -945,6 +1595,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             SignablePayloadField::AddressV2 {
@@ -958,6 +1609,7 @@ mod tests {
                     memo: None,
                     asset_label: "".to_string(),
                     badge_text: None,
+                    name_resolved: None,
                 },
             },
             SignablePayloadField::TextV2 {
@@ -1072,6 +1724,10 @@ mod tests {
             symbol: String,
         }
 
+        // Variant fields are serialized through `Deterministic`, so the new field type needs
+        // the trait too before `serialize_field_variant!` accepts it
+        impl_deterministic_serialize!(TestCurrencyField);
+
         // Create a test enum that extends SignablePayloadField with a new Currency variant
         #[derive(Debug, Clone, PartialEq, Eq)]
         enum ExtendedSignablePayloadField {
@@ -1121,7 +1777,7 @@ mod tests {
         }
 
         // CRITICAL: To use this new type with deterministic ordering, we MUST implement the trait
-        impl DeterministicOrdering for ExtendedSignablePayloadField {}
+        impl_deterministic_serialize!(ExtendedSignablePayloadField);
 
         // This function requires DeterministicOrdering - it won't compile without the impl above
         fn require_deterministic<T: DeterministicOrdering>(field: &T) -> Result<(), String> {
@@ -1210,7 +1866,7 @@ mod tests {
         }
 
         // NOTE: We intentionally DO NOT implement DeterministicOrdering for BadSignablePayloadField
-        // impl DeterministicOrdering for BadSignablePayloadField {} // MISSING!
+        // impl_deterministic_serialize!(BadSignablePayloadField); // MISSING!
 
         // This function requires DeterministicOrdering - used to demonstrate compile-time checking
         // It's intentionally never called because calling it with BadSignablePayloadField would cause a compile error
@@ -1234,6 +1890,8 @@ mod tests {
 
         // The following lines are COMMENTED OUT because they would cause a compile error:
         // process_field(&bad_field);  // COMPILE ERROR: BadSignablePayloadField doesn't implement DeterministicOrdering
+        // tests/ui holds the compile-fail cases that check this, and that the trait can't be
+        // implemented outside this crate
 
         // This demonstrates the compile-time safety:
         // 1. If you forget to implement DeterministicOrdering, you can't use the type where it's required
@@ -1338,7 +1996,7 @@ mod tests {
         }
 
         // Step 4: Implement DeterministicOrdering trait
-        impl DeterministicOrdering for NewFieldVariant {}
+        impl_deterministic_serialize!(NewFieldVariant);
 
         // Step 5: Create a function that requires deterministic ordering (simulating real usage)
         fn add_to_payload<T: DeterministicOrdering>(field: T) -> Result<String, String> {
@@ -1943,6 +2601,44 @@ mod tests {
         };
         assert_deterministic_ordering(&amount_v2);
 
+        let amount_v3 = SignablePayloadFieldAmountV3 {
+            amount: "0.5".to_string(),
+            abbreviation: Some("ETH".to_string()),
+            fiat_value: Some("1230".to_string()),
+            fiat_currency: Some("USD".to_string()),
+            rate_source: Some("Test Feed".to_string()),
+            rate_timestamp: Some("2026-01-01T00:00:00Z".to_string()),
+        };
+        assert_deterministic_ordering(&amount_v3);
+
+        let timestamp = SignablePayloadFieldTimestamp {
+            timestamp: "2024-06-01T12:00:00Z".to_string(),
+        };
+        assert_deterministic_ordering(&timestamp);
+
+        let duration = SignablePayloadFieldDuration {
+            seconds: "3600".to_string(),
+        };
+        assert_deterministic_ordering(&duration);
+
+        let percentage = SignablePayloadFieldPercentage {
+            percentage: "0.5".to_string(),
+        };
+        assert_deterministic_ordering(&percentage);
+
+        let image_ref = SignablePayloadFieldImageRef {
+            content_hash: format!("sha256:{}", "0".repeat(64)),
+            identifier: "erc20:1:0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string(),
+        };
+        assert_deterministic_ordering(&image_ref);
+
+        let bytes = SignablePayloadFieldBytes {
+            hex: "0xa9059cbb".to_string(),
+            length: "4".to_string(),
+            preview: "0xa9059cbb".to_string(),
+        };
+        assert_deterministic_ordering(&bytes);
+
         // Test layout types
         let preview_layout = SignablePayloadFieldPreviewLayout {
             title: Some(text_v2.clone()),
@@ -2353,6 +3049,7 @@ mod tests {
                                 memo: None,
                                 asset_label: "".to_string(),
                                 badge_text: Some("Verified".to_string()),
+                                name_resolved: None,
                             },
                         },
                         static_annotation: None,
@@ -2415,6 +3112,426 @@ mod tests {
         println!("✅ All types consistently implement DeterministicOrdering!");
     }
 
+    #[test]
+    fn test_charset_policy() {
+        let payload_with = |text: &str| {
+            SignablePayload::new(
+                0,
+                "Transfer".to_string(),
+                None,
+                vec![
+                    crate::field_builders::create_text_field("Memo", text)
+                        .unwrap()
+                        .signable_payload_field,
+                ],
+                "Test".to_string(),
+            )
+        };
+
+        let accented = payload_with("Café");
+        let err = accented.validate_charset().unwrap_err();
+        assert!(err.to_string().ends_with("in Fields[0]"), "{err}");
+        assert!(accented
+            .validate_charset_with(CharsetPolicy::Unicode)
+            .is_ok());
+
+        // Control and format characters stay restricted under the Unicode policy
+        for text in ["evil\u{202E}txt.exe", "line\u{0007}bell"] {
+            assert!(payload_with(text)
+                .validate_charset_with(CharsetPolicy::Unicode)
+                .is_err());
+        }
+        for invisible in [
+            '\u{200B}', // zero width space
+            '\u{200C}', // zero width non-joiner
+            '\u{200D}', // zero width joiner
+            '\u{200E}', // left-to-right mark
+            '\u{200F}', // right-to-left mark
+            '\u{2060}', // word joiner
+            '\u{FEFF}', // zero width no-break space
+            '\u{061C}', // arabic letter mark
+            '\u{00AD}', // soft hyphen
+        ] {
+            let text = format!("0xab{invisible}cd");
+            let err = payload_with(&text)
+                .validate_charset_with(CharsetPolicy::Unicode)
+                .unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&format!("U+{:02X}", invisible as u32)),
+                "{err}"
+            );
+        }
+
+        // Advertised names are the ones the `Charset` option deserializes
+        for policy in CharsetPolicy::ALL {
+            assert_eq!(
+                serde_json::to_string(&policy).unwrap(),
+                format!("\"{}\"", policy.as_str())
+            );
+        }
+    }
+
+    mod round_trip {
+        use super::*;
+        use proptest::collection::vec;
+        use proptest::option;
+        use proptest::prelude::*;
+
+        fn text() -> impl Strategy<Value = String> {
+            "\\PC{0,12}"
+        }
+
+        fn common() -> impl Strategy<Value = SignablePayloadFieldCommon> {
+            (text(), text()).prop_map(|(fallback_text, label)| SignablePayloadFieldCommon {
+                fallback_text,
+                label,
+            })
+        }
+
+        fn text_v2() -> impl Strategy<Value = SignablePayloadFieldTextV2> {
+            text().prop_map(|text| SignablePayloadFieldTextV2 { text })
+        }
+
+        fn leaf_field() -> impl Strategy<Value = SignablePayloadField> {
+            prop_oneof![
+                (common(), text()).prop_map(|(common, text)| SignablePayloadField::Text {
+                    common,
+                    text: SignablePayloadFieldText { text },
+                }),
+                (common(), text_v2())
+                    .prop_map(|(common, text_v2)| SignablePayloadField::TextV2 { common, text_v2 }),
+                (common(), text(), text()).prop_map(|(common, address, name)| {
+                    SignablePayloadField::Address {
+                        common,
+                        address: SignablePayloadFieldAddress { address, name },
+                    }
+                }),
+                (
+                    common(),
+                    (text(), text(), option::of(text())),
+                    (text(), option::of(text()), option::of(any::<bool>())),
+                )
+                    .prop_map(
+                        |(
+                            common,
+                            (address, name, memo),
+                            (asset_label, badge_text, name_resolved),
+                        )| {
+                            SignablePayloadField::AddressV2 {
+                                common,
+                                address_v2: SignablePayloadFieldAddressV2 {
+                                    address,
+                                    name,
+                                    memo,
+                                    asset_label,
+                                    badge_text,
+                                    name_resolved,
+                                },
+                            }
+                        }
+                    ),
+                (common(), text()).prop_map(|(common, number)| SignablePayloadField::Number {
+                    common,
+                    number: SignablePayloadFieldNumber { number },
+                }),
+                (common(), text(), option::of(text())).prop_map(
+                    |(common, amount, abbreviation)| SignablePayloadField::Amount {
+                        common,
+                        amount: SignablePayloadFieldAmount {
+                            amount,
+                            abbreviation,
+                        },
+                    }
+                ),
+                (common(), text(), option::of(text())).prop_map(
+                    |(common, amount, abbreviation)| SignablePayloadField::AmountV2 {
+                        common,
+                        amount_v2: SignablePayloadFieldAmountV2 {
+                            amount,
+                            abbreviation,
+                        },
+                    }
+                ),
+                (
+                    common(),
+                    (text(), option::of(text()), option::of(text())),
+                    (option::of(text()), option::of(text()), option::of(text())),
+                )
+                    .prop_map(
+                        |(
+                            common,
+                            (amount, abbreviation, fiat_value),
+                            (fiat_currency, rate_source, rate_timestamp),
+                        )| SignablePayloadField::AmountV3 {
+                            common,
+                            amount_v3: SignablePayloadFieldAmountV3 {
+                                amount,
+                                abbreviation,
+                                fiat_value,
+                                fiat_currency,
+                                rate_source,
+                                rate_timestamp,
+                            },
+                        }
+                    ),
+                (common(), text()).prop_map(|(common, timestamp)| {
+                    SignablePayloadField::Timestamp {
+                        common,
+                        timestamp: SignablePayloadFieldTimestamp { timestamp },
+                    }
+                }),
+                (common(), text()).prop_map(|(common, seconds)| SignablePayloadField::Duration {
+                    common,
+                    duration: SignablePayloadFieldDuration { seconds },
+                }),
+                (common(), text()).prop_map(|(common, percentage)| {
+                    SignablePayloadField::Percentage {
+                        common,
+                        percentage: SignablePayloadFieldPercentage { percentage },
+                    }
+                }),
+                (common(), text(), text()).prop_map(|(common, content_hash, identifier)| {
+                    SignablePayloadField::ImageRef {
+                        common,
+                        image_ref: SignablePayloadFieldImageRef {
+                            content_hash,
+                            identifier,
+                        },
+                    }
+                }),
+                (common(), (text(), text(), text())).prop_map(
+                    |(common, (hex, length, preview))| SignablePayloadField::Bytes {
+                        common,
+                        bytes: SignablePayloadFieldBytes {
+                            hex,
+                            length,
+                            preview,
+                        },
+                    }
+                ),
+                (common(), text()).prop_map(|(common, style)| SignablePayloadField::Divider {
+                    common,
+                    divider: SignablePayloadFieldDivider {
+                        style: DividerStyle(style),
+                    },
+                }),
+                (common(), text(), text()).prop_map(|(common, data, explanation)| {
+                    SignablePayloadField::Unknown {
+                        common,
+                        unknown: SignablePayloadFieldUnknown { data, explanation },
+                    }
+                }),
+            ]
+        }
+
+        fn annotated(
+            field: impl Strategy<Value = SignablePayloadField>,
+        ) -> impl Strategy<Value = AnnotatedPayloadField> {
+            let dynamic_annotation =
+                (text(), text(), vec(text(), 0..3)).prop_map(|(field_type, id, params)| {
+                    SignablePayloadFieldDynamicAnnotation {
+                        field_type,
+                        id,
+                        params,
+                    }
+                });
+            (
+                field,
+                option::of(text().prop_map(|text| SignablePayloadFieldStaticAnnotation { text })),
+                option::of(dynamic_annotation),
+            )
+                .prop_map(
+                    |(signable_payload_field, static_annotation, dynamic_annotation)| {
+                        AnnotatedPayloadField {
+                            signable_payload_field,
+                            static_annotation,
+                            dynamic_annotation,
+                        }
+                    },
+                )
+        }
+
+        fn list_layout(
+            field: impl Strategy<Value = SignablePayloadField>,
+        ) -> impl Strategy<Value = SignablePayloadFieldListLayout> {
+            vec(annotated(field), 0..3).prop_map(|fields| SignablePayloadFieldListLayout { fields })
+        }
+
+        // Every variant, with layouts nesting other fields up to three levels deep
+        fn field() -> impl Strategy<Value = SignablePayloadField> {
+            leaf_field().prop_recursive(3, 24, 3, |inner| {
+                prop_oneof![
+                    (common(), list_layout(inner.clone())).prop_map(|(common, list_layout)| {
+                        SignablePayloadField::ListLayout {
+                            common,
+                            list_layout,
+                        }
+                    }),
+                    (
+                        common(),
+                        (option::of(text_v2()), option::of(text_v2())),
+                        option::of(list_layout(inner.clone())),
+                        option::of(list_layout(inner)),
+                    )
+                        .prop_map(
+                            |(common, (title, subtitle), condensed, expanded)| {
+                                SignablePayloadField::PreviewLayout {
+                                    common,
+                                    preview_layout: SignablePayloadFieldPreviewLayout {
+                                        title,
+                                        subtitle,
+                                        condensed,
+                                        expanded,
+                                    },
+                                }
+                            }
+                        ),
+                ]
+            })
+        }
+
+        fn payload() -> impl Strategy<Value = SignablePayload> {
+            let parser_info = (text(), text(), option::of(text())).prop_map(
+                |(crate_name, version, git_commit)| {
+                    ParserInfo::new(&crate_name, &version, git_commit.as_deref())
+                },
+            );
+            let coverage = (any::<u32>(), any::<u32>())
+                .prop_map(|(total, opaque)| Coverage::new(total as usize, opaque as usize));
+            (
+                vec(field(), 0..4),
+                (option::of(parser_info), option::of(coverage)),
+                (text(), option::of(text()), text()),
+                any::<i64>(),
+            )
+                .prop_map(
+                    |(
+                        fields,
+                        (parser_info, coverage),
+                        (payload_type, subtitle, title),
+                        version,
+                    )| {
+                        let mut payload =
+                            SignablePayload::new(version, title, subtitle, fields, payload_type);
+                        payload.parser_info = parser_info;
+                        payload.coverage = coverage;
+                        payload
+                    },
+                )
+        }
+
+        proptest! {
+            #[test]
+            fn test_field_round_trip(field in field()) {
+                let json = serde_json::to_string(&field).unwrap();
+                let decoded: SignablePayloadField = serde_json::from_str(&json).unwrap();
+                prop_assert_eq!(decoded, field);
+            }
+
+            #[test]
+            fn test_annotated_field_round_trip(field in annotated(field())) {
+                let json = serde_json::to_string(&field).unwrap();
+                let decoded: AnnotatedPayloadField = serde_json::from_str(&json).unwrap();
+                prop_assert_eq!(decoded, field);
+            }
+
+            #[test]
+            fn test_payload_round_trip(payload in payload()) {
+                let json = payload.to_json().unwrap();
+                let decoded: SignablePayload = serde_json::from_str(&json).unwrap();
+                prop_assert_eq!(decoded, payload);
+            }
+        }
+
+        #[test]
+        fn test_deserialize_rejects_fields_serialize_never_writes() {
+            let field = r#"{"FallbackText":"1","Label":"Amount","Number":{"Number":"1"},"Text":{"Text":"1"},"Type":"number"}"#;
+            let err = serde_json::from_str::<SignablePayloadField>(field).unwrap_err();
+            assert!(err.to_string().contains("Unexpected field 'Text'"));
+
+            let layout = r#"{"Condensed":{"Fields":[]},"Footer":{"Text":"x"}}"#;
+            let err =
+                serde_json::from_str::<SignablePayloadFieldPreviewLayout>(layout).unwrap_err();
+            assert!(err.to_string().contains("Unexpected field 'Footer'"));
+
+            let unknown_type = r#"{"FallbackText":"1","Label":"Amount","Type":"amount_v9"}"#;
+            let err = serde_json::from_str::<SignablePayloadField>(unknown_type).unwrap_err();
+            assert!(err.to_string().contains("unknown variant `amount_v9`"));
+        }
+
+        #[test]
+        fn test_deserialize_accepts_omitted_optional_fields() {
+            let layout: SignablePayloadFieldPreviewLayout = serde_json::from_str("{}").unwrap();
+            assert_eq!(
+                layout,
+                SignablePayloadFieldPreviewLayout {
+                    title: None,
+                    subtitle: None,
+                    condensed: None,
+                    expanded: None,
+                }
+            );
+
+            let address: SignablePayloadFieldAddressV2 =
+                serde_json::from_str(r#"{"Address":"0xabc"}"#).unwrap();
+            assert_eq!(address.name, "");
+            assert_eq!(address.asset_label, "");
+
+            let payload: SignablePayload =
+                serde_json::from_str(r#"{"Fields":[],"Title":"Empty","Version":"0"}"#).unwrap();
+            assert_eq!(payload.payload_type, "");
+        }
+    }
+
+    // A payload whose single field is `depth` list layouts nested inside each other
+    fn nested_layouts_json(depth: usize) -> String {
+        let mut field =
+            r#"{"FallbackText":"x","Label":"Leaf","Text":{"Text":"x"},"Type":"text"}"#.to_string();
+        for level in 0..depth {
+            field = format!(
+                r#"{{"FallbackText":"x","Label":"Level {level}","ListLayout":{{"Fields":[{field}]}},"Type":"list_layout"}}"#
+            );
+        }
+        format!(r#"{{"Fields":[{field}],"Title":"Nested","Version":"0"}}"#)
+    }
+
+    #[test]
+    fn test_from_json_bounds_layout_depth() {
+        let payload = SignablePayload::from_json(&nested_layouts_json(MAX_LAYOUT_DEPTH)).unwrap();
+        assert_eq!(
+            payload.to_json().unwrap(),
+            nested_layouts_json(MAX_LAYOUT_DEPTH)
+        );
+
+        let err =
+            SignablePayload::from_json(&nested_layouts_json(MAX_LAYOUT_DEPTH + 1)).unwrap_err();
+        assert!(
+            err.to_string().contains(&format!(
+                "Layouts nested more than {MAX_LAYOUT_DEPTH} levels deep"
+            )),
+            "{err}"
+        );
+
+        // The depth is released on failure, so later payloads decode normally
+        assert!(SignablePayload::from_json(&nested_layouts_json(MAX_LAYOUT_DEPTH)).is_ok());
+    }
+
+    #[test]
+    fn test_from_json_rejects_deeply_nested_values() {
+        let depth = 100_000;
+        let json = format!(
+            r#"{{"Fields":[{{"FallbackText":"x","Label":"Deep","Text":{{"Text":{}"x"{}}},"Type":"text"}}],"Title":"Deep","Version":"0"}}"#,
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+        let err = SignablePayload::from_json(&json).unwrap_err();
+        assert!(
+            err.to_string().contains("recursion limit exceeded"),
+            "{err}"
+        );
+    }
+
     fn assert_sorted_alphabetically(json: String) {
         println!("Sorted JSON: {json}");
         // ensure that ordering si preserved when using to_json()
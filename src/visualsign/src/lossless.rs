@@ -0,0 +1,56 @@
+//! Checks that a decoded transaction re-encodes to exactly the bytes it was read from.
+//!
+//! Decoders commonly accept more than the canonical encoding: bincode and BCS readers stop at
+//! the end of the value and ignore what follows, protobuf readers drop or reorder fields, and
+//! RLP readers may accept a list followed by padding. Bytes that never reach the rendered
+//! payload are still signed, so when [`crate::vsptrait::OutputFlags::verify_lossless`]
+//! is set a parser re-encodes what it decoded and rejects the input unless the two match, via
+//! [`crate::vsptrait::Transaction::verify_lossless`].
+
+use crate::errors::{ParserError, ParserErrorKind};
+
+/// Fails with an `Encoding` error at the first byte where `reencoded` departs from `input`.
+pub fn check_reencoding(chain: &str, input: &[u8], reencoded: &[u8]) -> Result<(), ParserError> {
+    if input == reencoded {
+        return Ok(());
+    }
+    let offset = input
+        .iter()
+        .zip(reencoded)
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| input.len().min(reencoded.len()));
+    let message = if input.len() > reencoded.len() && offset == reencoded.len() {
+        format!(
+            "{} bytes after the end of the transaction are not covered by the decoded fields",
+            input.len() - reencoded.len()
+        )
+    } else {
+        format!("Transaction does not re-encode to its input bytes, differing at byte {offset}")
+    };
+    Err(ParserError::new(
+        ParserErrorKind::Encoding,
+        chain,
+        "ReencodingMismatch",
+        message,
+    )
+    .with_offset(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_reencoding() {
+        assert!(check_reencoding("Test", &[1, 2, 3], &[1, 2, 3]).is_ok());
+
+        let trailing = check_reencoding("Test", &[1, 2, 3, 0], &[1, 2, 3]).unwrap_err();
+        assert_eq!(trailing.reason, "ReencodingMismatch");
+        assert_eq!(trailing.offset, Some(3));
+        assert!(trailing.message.contains("1 bytes after the end"));
+
+        let altered = check_reencoding("Test", &[1, 9, 3], &[1, 2, 3]).unwrap_err();
+        assert_eq!(altered.kind, ParserErrorKind::Encoding);
+        assert_eq!(altered.offset, Some(1));
+    }
+}
@@ -0,0 +1,127 @@
+//! Warnings for transfers to destinations that need a memo or destination tag.
+//!
+//! Exchanges on Tron, XRPL, Stellar and Cosmos chains commonly pool deposits in one address and
+//! tell customers apart by memo. The host knows which destinations behave this way and passes
+//! them as [`MemoRequirements`] through [`crate::vsptrait::VisualSignOptions`]; parsers call
+//! [`missing_memo_warning`] for each transfer so the signer sees the problem before funds are
+//! lost.
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use crate::field_builders::create_warning_field;
+use crate::registry::Chain;
+use crate::SignablePayloadField;
+
+pub const MEMO_WARNING_LABEL: &str = "Memo Warning";
+
+pub trait MemoRequirements: Debug + Send + Sync {
+    /// True when transfers to `destination` on `chain` must carry a memo or destination tag.
+    fn requires_memo(&self, chain: &Chain, destination: &str) -> bool;
+}
+
+/// Fixed set of destinations known to require a memo.
+#[derive(Debug, Clone, Default)]
+pub struct MemoRequiredDestinations {
+    destinations: HashSet<(Chain, String)>,
+}
+
+impl MemoRequiredDestinations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_destination(mut self, chain: Chain, destination: &str) -> Self {
+        self.destinations.insert((chain, destination.to_string()));
+        self
+    }
+}
+
+impl MemoRequirements for MemoRequiredDestinations {
+    fn requires_memo(&self, chain: &Chain, destination: &str) -> bool {
+        self.destinations
+            .contains(&(chain.clone(), destination.to_string()))
+    }
+}
+
+/// Returns a warning field when `destination` requires a memo and `memo` is missing or blank.
+///
+/// `memo_name` is the chain's term for it, e.g. "memo" or "destination tag".
+pub fn missing_memo_warning(
+    requirements: &dyn MemoRequirements,
+    chain: &Chain,
+    destination: &str,
+    memo: Option<&str>,
+    memo_name: &str,
+) -> Option<SignablePayloadField> {
+    let has_memo = memo.is_some_and(|memo| !memo.trim().is_empty());
+    if has_memo || !requirements.requires_memo(chain, destination) {
+        return None;
+    }
+
+    let text = format!(
+        "{destination} requires a {memo_name} but none is set; funds sent without one may be lost"
+    );
+    Some(
+        create_warning_field(
+            MEMO_WARNING_LABEL,
+            &format!("Missing {memo_name}"),
+            &text,
+            &format!("Warning: add the {memo_name} provided by the recipient before signing"),
+            &format!("Warning: {text}"),
+        )
+        .signable_payload_field,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::warning_message;
+
+    const EXCHANGE: &str = "TExchangeDepositAddress111111111111";
+
+    fn requirements() -> MemoRequiredDestinations {
+        MemoRequiredDestinations::new().with_destination(Chain::Tron, EXCHANGE)
+    }
+
+    #[test]
+    fn test_warning_for_missing_memo() {
+        let field = missing_memo_warning(&requirements(), &Chain::Tron, EXCHANGE, None, "memo")
+            .expect("warning expected");
+        assert_eq!(field.label(), MEMO_WARNING_LABEL);
+
+        let warning = warning_message(&field);
+        assert_eq!(warning.signable_payload_field.label(), "Missing memo");
+        assert!(warning
+            .static_annotation
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Warning:"));
+    }
+
+    #[test]
+    fn test_no_warning_when_memo_present_or_not_required() {
+        let requirements = requirements();
+        assert!(
+            missing_memo_warning(&requirements, &Chain::Tron, EXCHANGE, Some("12345"), "memo")
+                .is_none()
+        );
+        assert!(
+            missing_memo_warning(&requirements, &Chain::Tron, EXCHANGE, Some("  "), "memo")
+                .is_some()
+        );
+        assert!(
+            missing_memo_warning(&requirements, &Chain::Tron, "TOther", None, "memo").is_none()
+        );
+        assert!(missing_memo_warning(
+            &requirements,
+            &Chain::Custom("XRPL".to_string()),
+            EXCHANGE,
+            None,
+            "destination tag"
+        )
+        .is_none());
+    }
+}
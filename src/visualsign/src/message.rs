@@ -0,0 +1,184 @@
+//! Fields for off-chain messages: bytes a wallet signs that are not a transaction.
+//!
+//! A signed message moves no funds by itself, but phishing sites ask for message signatures
+//! precisely because signers read them less carefully, and a message can still authorize a
+//! login or an off-chain order. Parsers for message formats (EIP-191 `personal_sign`, Solana
+//! off-chain messages) render them through [`create_message_fields`], so every format opens
+//! with the same "This is a message, not a transaction" indicator and the message itself is
+//! shown under the same rules: text the payload's [`CharsetPolicy`] would reject is shown as
+//! hex instead, and long messages are cut to a fixed number of characters.
+
+use crate::extensions::RenderBudget;
+use crate::vsptrait::VisualSignOptions;
+use crate::{
+    CharsetPolicy, SignablePayloadField, SignablePayloadFieldCommon, SignablePayloadFieldTextV2,
+};
+
+pub const SIGNING_TYPE_LABEL: &str = "Signing Type";
+pub const SIGNING_TYPE_MESSAGE: &str = "This is a message, not a transaction";
+
+/// Characters of message text shown when the host sets no `RenderBudget.MaxTextLength`
+pub const MAX_MESSAGE_CHARS: usize = 1024;
+
+/// How many characters of a message to show under `options`.
+pub fn message_length_cap(options: &VisualSignOptions) -> usize {
+    options
+        .extensions
+        .get::<RenderBudget>()
+        .and_then(|budget| budget.max_text_length)
+        .map_or(MAX_MESSAGE_CHARS, |max| {
+            usize::try_from(max)
+                .unwrap_or(usize::MAX)
+                .min(MAX_MESSAGE_CHARS)
+        })
+}
+
+/// The signing type indicator, the message and its size, in that order.
+///
+/// The message is shown as text when it is UTF-8 that `charset` accepts and as `0x` hex
+/// otherwise; either way at most `max_chars` characters are shown, followed by a note of how
+/// many were left out.
+pub fn create_message_fields(
+    message: &[u8],
+    charset: CharsetPolicy,
+    max_chars: usize,
+) -> Vec<SignablePayloadField> {
+    let (label, rendered) = match std::str::from_utf8(message) {
+        // A literal `\u` would read as an escape to the payload's charset check
+        Ok(text) if !text.contains("\\u") && text.chars().all(|ch| is_displayable(ch, charset)) => {
+            ("Message", text.to_string())
+        }
+        _ => (
+            "Message (hex)",
+            message.iter().fold("0x".to_string(), |mut hex, byte| {
+                hex.push_str(&format!("{byte:02x}"));
+                hex
+            }),
+        ),
+    };
+
+    vec![
+        text_field(SIGNING_TYPE_LABEL, SIGNING_TYPE_MESSAGE),
+        text_field(label, &truncate(&rendered, max_chars)),
+        text_field("Message Size", &format!("{} bytes", message.len())),
+    ]
+}
+
+// Mirrors what `SignablePayload::validate_charset_with` accepts inside a string
+fn is_displayable(ch: char, charset: CharsetPolicy) -> bool {
+    match charset {
+        CharsetPolicy::Ascii => ch.is_ascii_graphic() || ch == ' ' || ch == '\n',
+        CharsetPolicy::Unicode => {
+            (!ch.is_control() || ch == '\n') && !crate::is_format_character(ch)
+        }
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text.to_string();
+    }
+    let shown: String = text.chars().take(max_chars).collect();
+    format!("{shown}... ({} more characters)", total - max_chars)
+}
+
+fn text_field(label: &str, text: &str) -> SignablePayloadField {
+    SignablePayloadField::TextV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: text.to_string(),
+            label: label.to_string(),
+        },
+        text_v2: SignablePayloadFieldTextV2 {
+            text: text.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SignablePayload;
+
+    fn texts(fields: &[SignablePayloadField]) -> Vec<(String, String)> {
+        fields
+            .iter()
+            .map(|field| (field.label().clone(), field.fallback_text().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_message_fields() {
+        let fields = create_message_fields(
+            b"Sign in to example.com\nNonce: 42",
+            CharsetPolicy::Ascii,
+            100,
+        );
+        assert_eq!(
+            texts(&fields),
+            vec![
+                (
+                    SIGNING_TYPE_LABEL.to_string(),
+                    SIGNING_TYPE_MESSAGE.to_string()
+                ),
+                (
+                    "Message".to_string(),
+                    "Sign in to example.com\nNonce: 42".to_string()
+                ),
+                ("Message Size".to_string(), "32 bytes".to_string()),
+            ]
+        );
+
+        let payload = SignablePayload::new(
+            0,
+            "Sign Message".to_string(),
+            None,
+            fields,
+            "Message".to_string(),
+        );
+        assert!(payload.validate_charset().is_ok());
+    }
+
+    #[test]
+    fn test_restricted_text_is_shown_as_hex() {
+        // Non-ASCII text passes only under the Unicode policy
+        let message = "Caf\u{e9}".as_bytes();
+        assert_eq!(
+            create_message_fields(message, CharsetPolicy::Unicode, 100)[1].label(),
+            "Message"
+        );
+        let fields = create_message_fields(message, CharsetPolicy::Ascii, 100);
+        assert_eq!(fields[1].label(), "Message (hex)");
+        assert_eq!(fields[1].fallback_text(), "0x436166c3a9");
+
+        // Format characters, raw bytes and escape look-alikes never render as text
+        for message in [
+            "a\u{202E}b".as_bytes(),
+            "a\u{200B}b".as_bytes(),
+            &[0xff, 0x00][..],
+            b"tab\there",
+            b"C:\\users",
+        ] {
+            let fields = create_message_fields(message, CharsetPolicy::Unicode, 100);
+            assert_eq!(fields[1].label(), "Message (hex)");
+        }
+    }
+
+    #[test]
+    fn test_long_messages_are_capped() {
+        let fields = create_message_fields(&[b'a'; 30], CharsetPolicy::Ascii, 10);
+        assert_eq!(
+            fields[1].fallback_text(),
+            "aaaaaaaaaa... (20 more characters)"
+        );
+        assert_eq!(fields[2].fallback_text(), "30 bytes");
+
+        let mut options = VisualSignOptions::default();
+        assert_eq!(message_length_cap(&options), MAX_MESSAGE_CHARS);
+        options.extensions.insert(RenderBudget {
+            max_fields: None,
+            max_text_length: Some(64),
+        });
+        assert_eq!(message_length_cap(&options), 64);
+    }
+}
@@ -0,0 +1,181 @@
+//! Host-supplied name resolution (ENS, SNS, ...) for addresses shown in a payload.
+//!
+//! Parsers never perform lookups. The host resolves names ahead of time and passes a
+//! [`NameResolver`] through [`crate::vsptrait::VisualSignOptions`]; every `AddressV2` field is
+//! then given the resolved name and marked with `NameResolved` so wallets can tell a resolved
+//! name from one the parser produced.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::{SignablePayload, SignablePayloadField};
+
+pub trait NameResolver: Debug + Send + Sync {
+    /// Returns the pre-resolved name for `address`, if the host has one.
+    fn resolve(&self, address: &str) -> Option<String>;
+}
+
+/// Resolver backed by a fixed table of address to name.
+///
+/// Hex addresses (`0x...`) are matched case-insensitively; other encodings such as base58 are
+/// case-sensitive and matched exactly.
+#[derive(Debug, Clone, Default)]
+pub struct StaticNameResolver {
+    names: HashMap<String, String>,
+}
+
+impl StaticNameResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_name(mut self, address: &str, name: &str) -> Self {
+        self.names.insert(lookup_key(address), name.to_string());
+        self
+    }
+}
+
+impl NameResolver for StaticNameResolver {
+    fn resolve(&self, address: &str) -> Option<String> {
+        self.names.get(&lookup_key(address)).cloned()
+    }
+}
+
+fn lookup_key(address: &str) -> String {
+    let address = address.trim();
+    if address.starts_with("0x") || address.starts_with("0X") {
+        address.to_lowercase()
+    } else {
+        address.to_string()
+    }
+}
+
+/// Fills in resolved names for every `AddressV2` field, including those nested in layouts.
+pub fn apply_name_resolution(payload: &mut SignablePayload, resolver: &dyn NameResolver) {
+    for field in &mut payload.fields {
+        resolve_field(field, resolver);
+    }
+}
+
+fn resolve_field(field: &mut SignablePayloadField, resolver: &dyn NameResolver) {
+    match field {
+        SignablePayloadField::AddressV2 { address_v2, .. } => {
+            match resolver.resolve(&address_v2.address) {
+                Some(name) => {
+                    address_v2.name = name;
+                    address_v2.name_resolved = Some(true);
+                }
+                None => address_v2.name_resolved = Some(false),
+            }
+        }
+        SignablePayloadField::PreviewLayout { preview_layout, .. } => {
+            let lists = preview_layout
+                .condensed
+                .iter_mut()
+                .chain(preview_layout.expanded.iter_mut());
+            for list in lists {
+                for annotated in &mut list.fields {
+                    resolve_field(&mut annotated.signable_payload_field, resolver);
+                }
+            }
+        }
+        SignablePayloadField::ListLayout { list_layout, .. } => {
+            for annotated in &mut list_layout.fields {
+                resolve_field(&mut annotated.signable_payload_field, resolver);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::create_address_field;
+    use crate::{SignablePayloadFieldCommon, SignablePayloadFieldListLayout};
+
+    const VITALIK: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+    const SOLANA_ADDRESS: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    fn name_and_flag(field: &SignablePayloadField) -> (&str, Option<bool>) {
+        let SignablePayloadField::AddressV2 { address_v2, .. } = field else {
+            panic!("Expected AddressV2");
+        };
+        (&address_v2.name, address_v2.name_resolved)
+    }
+
+    #[test]
+    fn test_names_are_applied_with_flag() {
+        let resolver = StaticNameResolver::new()
+            .with_name(VITALIK, "vitalik.eth")
+            .with_name(SOLANA_ADDRESS, "bonfida.sol");
+
+        let nested = SignablePayloadField::ListLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: "Details".to_string(),
+                label: "Details".to_string(),
+            },
+            list_layout: SignablePayloadFieldListLayout {
+                fields: vec![create_address_field(
+                    "Recipient",
+                    SOLANA_ADDRESS,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()],
+            },
+        };
+        let mut payload = SignablePayload::new(
+            0,
+            "Tx".to_string(),
+            None,
+            vec![
+                create_address_field("To", &VITALIK.to_lowercase(), None, None, None, None)
+                    .unwrap()
+                    .signable_payload_field,
+                create_address_field(
+                    "From",
+                    VITALIK.trim_end_matches('5'),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()
+                .signable_payload_field,
+                nested,
+            ],
+            "Test".to_string(),
+        );
+
+        apply_name_resolution(&mut payload, &resolver);
+
+        assert_eq!(
+            name_and_flag(&payload.fields[0]),
+            ("vitalik.eth", Some(true))
+        );
+        assert_eq!(name_and_flag(&payload.fields[1]), ("", Some(false)));
+        let SignablePayloadField::ListLayout { list_layout, .. } = &payload.fields[2] else {
+            panic!("Expected ListLayout");
+        };
+        assert_eq!(
+            name_and_flag(&list_layout.fields[0].signable_payload_field),
+            ("bonfida.sol", Some(true))
+        );
+
+        let json = payload.to_json().unwrap();
+        assert!(json.contains(r#""Name":"vitalik.eth","NameResolved":true"#));
+    }
+
+    #[test]
+    fn test_base58_lookup_is_case_sensitive() {
+        let resolver = StaticNameResolver::new().with_name(SOLANA_ADDRESS, "bonfida.sol");
+        assert_eq!(
+            resolver.resolve(SOLANA_ADDRESS).as_deref(),
+            Some("bonfida.sol")
+        );
+        assert_eq!(resolver.resolve(&SOLANA_ADDRESS.to_lowercase()), None);
+    }
+}
@@ -0,0 +1,82 @@
+//! Warnings for transactions whose nonce does not follow the account's current state.
+//!
+//! A transaction reusing a nonce the account has already consumed is rejected, or replaces a
+//! pending transaction the signer may have forgotten about; one that skips ahead stays stuck
+//! until the gap is filled, and may execute much later than the signer expects. Neither can be
+//! told from the transaction alone, so the host passes the account's next nonce (an Ethereum
+//! nonce, a Cosmos sequence, ...) as the [`crate::extensions::ExpectedNonce`] extension and
+//! parsers call [`nonce_warning`] with the transaction's own.
+
+use crate::field_builders::create_warning_field;
+use crate::SignablePayloadField;
+
+pub const NONCE_WARNING_LABEL: &str = "Nonce Warning";
+
+/// Returns a warning field when `nonce` differs from the `expected` next nonce of the account.
+///
+/// `nonce_name` is the chain's term for it, e.g. "nonce" or "sequence".
+pub fn nonce_warning(expected: u64, nonce: u64, nonce_name: &str) -> Option<SignablePayloadField> {
+    let (label, text, annotation) = if nonce < expected {
+        (
+            format!("Reused {nonce_name}"),
+            format!(
+                "{nonce_name} {nonce} was already used; the account's next {nonce_name} is {expected}"
+            ),
+            "Warning: this transaction will be rejected, or replaces a pending one",
+        )
+    } else if nonce > expected {
+        (
+            format!("Skipped {nonce_name}"),
+            format!(
+                "{nonce_name} {nonce} leaves {} unused {nonce_name}(s) before it; the account's next {nonce_name} is {expected}",
+                nonce - expected
+            ),
+            "Warning: this transaction cannot execute until the earlier ones do",
+        )
+    } else {
+        return None;
+    };
+
+    Some(
+        create_warning_field(
+            NONCE_WARNING_LABEL,
+            &label,
+            &text,
+            annotation,
+            &format!("Warning: {text}"),
+        )
+        .signable_payload_field,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::warning_message;
+
+    #[test]
+    fn test_nonce_warning() {
+        assert!(nonce_warning(7, 7, "nonce").is_none());
+
+        let field = nonce_warning(7, 5, "nonce").expect("warning expected");
+        assert_eq!(field.label(), NONCE_WARNING_LABEL);
+        assert_eq!(
+            field.fallback_text(),
+            "Warning: nonce 5 was already used; the account's next nonce is 7"
+        );
+        assert_eq!(
+            warning_message(&field).signable_payload_field.label(),
+            "Reused nonce"
+        );
+
+        let field = nonce_warning(7, 10, "sequence").expect("warning expected");
+        assert_eq!(
+            field.fallback_text(),
+            "Warning: sequence 10 leaves 3 unused sequence(s) before it; the account's next sequence is 7"
+        );
+        assert_eq!(
+            warning_message(&field).signable_payload_field.label(),
+            "Skipped sequence"
+        );
+    }
+}
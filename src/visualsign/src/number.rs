@@ -0,0 +1,225 @@
+//! Exact decimal numbers with one canonical rendering.
+//!
+//! `Number` fields carry their value as a string, and `"1.50"`, `"+1.5"` and `"01.5"` all name
+//! the same number. A [`Decimal`] keeps the sign, the digits of the unscaled integer and the
+//! scale (the number of fractional digits), normalized so that equal numbers compare equal and
+//! render the same way: no `+` sign, no leading zeros in the whole part, no trailing fractional
+//! zeros and no negative zero. Verifiers parse a field with
+//! [`crate::SignablePayloadFieldNumber::decimal`] and compare values instead of strings.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::VisualSignError;
+use crate::SignablePayloadFieldNumber;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    negative: bool,
+    // Digits of the unscaled integer, without leading zeros; "0" for zero
+    digits: String,
+    scale: u32,
+}
+
+impl Decimal {
+    /// The number `digits` × 10^-`scale`, negated when `negative` is set.
+    pub fn new(negative: bool, digits: &str, scale: u32) -> Result<Self, VisualSignError> {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(VisualSignError::InvalidNumberField(digits.to_string()));
+        }
+        let mut digits = digits.trim_start_matches('0').to_string();
+        let mut scale = scale;
+        // Trailing fractional zeros carry no value
+        while scale > 0 && digits.ends_with('0') {
+            digits.pop();
+            scale -= 1;
+        }
+        if digits.is_empty() {
+            return Ok(Decimal::from(0u64));
+        }
+        Ok(Decimal {
+            negative,
+            digits,
+            scale,
+        })
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits == "0"
+    }
+
+    /// Digits of the unscaled integer, e.g. `"15"` for 1.5.
+    pub fn digits(&self) -> &str {
+        &self.digits
+    }
+
+    /// Number of fractional digits, e.g. 1 for 1.5.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Whether the number has no fractional part.
+    pub fn is_integer(&self) -> bool {
+        self.scale == 0
+    }
+
+    // The whole and fractional digits, the whole part without leading zeros
+    fn parts(&self) -> (String, String) {
+        let scale = self.scale as usize;
+        if self.digits.len() > scale {
+            let (whole, fraction) = self.digits.split_at(self.digits.len() - scale);
+            (whole.to_string(), fraction.to_string())
+        } else {
+            ("0".to_string(), format!("{:0>scale$}", self.digits))
+        }
+    }
+
+    // Compares magnitudes, ignoring signs
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        let (whole, fraction) = self.parts();
+        let (other_whole, other_fraction) = other.parts();
+        whole
+            .len()
+            .cmp(&other_whole.len())
+            .then_with(|| whole.cmp(&other_whole))
+            .then_with(|| {
+                let width = fraction.len().max(other_fraction.len());
+                format!("{fraction:0<width$}").cmp(&format!("{other_fraction:0<width$}"))
+            })
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Self {
+        Decimal {
+            negative: false,
+            digits: value.to_string(),
+            scale: 0,
+        }
+    }
+}
+
+impl From<i128> for Decimal {
+    fn from(value: i128) -> Self {
+        Decimal {
+            negative: value < 0,
+            digits: value.unsigned_abs().to_string(),
+            scale: 0,
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = VisualSignError;
+
+    /// Parses an optionally signed decimal such as `"-123.45"` or `"+7"`; exponents, grouping
+    /// separators and a bare `.` are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || VisualSignError::InvalidNumberField(s.to_string());
+        let (negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (whole, fraction) = match unsigned.split_once('.') {
+            Some((whole, fraction)) if !fraction.is_empty() => (whole, fraction),
+            Some(_) => return Err(invalid()),
+            None => (unsigned, ""),
+        };
+        if whole.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let scale = u32::try_from(fraction.len()).map_err(|_| invalid())?;
+        Decimal::new(negative, &format!("{whole}{fraction}"), scale).map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (whole, fraction) = self.parts();
+        let sign = if self.negative { "-" } else { "" };
+        if fraction.is_empty() {
+            write!(f, "{sign}{whole}")
+        } else {
+            write!(f, "{sign}{whole}.{fraction}")
+        }
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl SignablePayloadFieldNumber {
+    /// The field's value as a [`Decimal`].
+    pub fn decimal(&self) -> Result<Decimal, VisualSignError> {
+        self.number.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_canonical_rendering() {
+        let cases = [
+            ("0", "0"),
+            ("-0.0", "0"),
+            ("+678.90", "678.9"),
+            ("007", "7"),
+            ("-0.050", "-0.05"),
+            ("123.45", "123.45"),
+            ("1000", "1000"),
+        ];
+        for (input, canonical) in cases {
+            let decimal: Decimal = input.parse().unwrap();
+            assert_eq!(decimal.to_string(), canonical, "{input}");
+            assert_eq!(canonical.parse::<Decimal>().unwrap(), decimal);
+        }
+
+        let decimal: Decimal = "-0.050".parse().unwrap();
+        assert!(decimal.is_negative());
+        assert_eq!((decimal.digits(), decimal.scale()), ("5", 2));
+        assert!(!"0.0".parse::<Decimal>().unwrap().is_negative());
+        assert_eq!(Decimal::from(-42i128).to_string(), "-42");
+        assert_eq!(Decimal::new(false, "1500", 3).unwrap().to_string(), "1.5");
+
+        for invalid in ["", "-", ".5", "5.", "1e5", "1,000", "--1", "12.3.4", "NaN"] {
+            assert!(invalid.parse::<Decimal>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_decimal_semantic_comparison() {
+        let parse = |s: &str| s.parse::<Decimal>().unwrap();
+        assert_eq!(parse("1.50"), parse("+1.5"));
+        assert!(parse("1.05") < parse("1.5"));
+        assert!(parse("9.99") < parse("10"));
+        assert!(parse("-10") < parse("-9.99"));
+        assert!(parse("-0.1") < parse("0"));
+
+        let field = SignablePayloadFieldNumber {
+            number: "0123.40".to_string(),
+        };
+        assert_eq!(field.decimal().unwrap(), parse("123.4"));
+    }
+}
@@ -0,0 +1,92 @@
+//! Non-fatal issues reported alongside a converted payload.
+//!
+//! A parser that cannot fully decode a transaction still produces a payload (raw calldata, an
+//! unresolved account placeholder, ...), but the host should know that parts of it are
+//! approximate. [`ParseOutcome`] carries those warnings next to the payload instead of failing
+//! the parse or dropping them silently.
+
+use serde::{Deserialize, Serialize};
+
+use crate::coverage::Coverage;
+use crate::provenance::Provenance;
+use crate::SignablePayload;
+
+/// What kind of issue a [`ParseWarning`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseWarningKind {
+    /// A call targets a contract or program without a decoder; its data is shown raw
+    UnknownContract,
+    /// A value depends on on-chain state that cannot be looked up offline, e.g. an address
+    /// lookup table
+    UnresolvedLookup,
+    /// Input ended early or was cut short, so part of it could not be decoded
+    TruncatedData,
+    /// A sub-component failed to decode and is shown as an `Unknown` field, as requested by
+    /// `PartialParse`
+    DecodeFailed,
+    /// Any other non-fatal issue
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseWarning {
+    #[serde(rename = "Kind")]
+    pub kind: ParseWarningKind,
+    #[serde(rename = "Message")]
+    pub message: String,
+}
+
+impl ParseWarning {
+    pub fn new(kind: ParseWarningKind, message: impl Into<String>) -> Self {
+        ParseWarning {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// A converted payload together with the warnings raised while building it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOutcome {
+    pub payload: SignablePayload,
+    pub warnings: Vec<ParseWarning>,
+    /// Where each field comes from in the raw transaction; empty unless requested
+    pub provenance: Provenance,
+    /// Share of the raw input decoded into structured fields, for converters that measure it
+    pub coverage: Option<Coverage>,
+}
+
+impl ParseOutcome {
+    /// An outcome without warnings.
+    pub fn new(payload: SignablePayload) -> Self {
+        ParseOutcome {
+            payload,
+            warnings: Vec::new(),
+            provenance: Provenance::default(),
+            coverage: None,
+        }
+    }
+
+    pub fn with_warnings(payload: SignablePayload, warnings: Vec<ParseWarning>) -> Self {
+        ParseOutcome {
+            payload,
+            warnings,
+            provenance: Provenance::default(),
+            coverage: None,
+        }
+    }
+
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    pub fn with_coverage(mut self, coverage: Coverage) -> Self {
+        self.coverage = Some(coverage);
+        self
+    }
+
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+}
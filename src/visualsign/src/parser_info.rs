@@ -0,0 +1,70 @@
+//! Which parser release rendered a payload.
+//!
+//! When [`crate::vsptrait::OutputFlags::include_parser_info`] is set, the converter's
+//! [`ParserInfo`] is embedded in the payload as `ParserInfo`, so a signed payload can be tied
+//! back to the exact crate and commit that produced it. Converters describe themselves with
+//! [`crate::parser_info!`], which reads the calling crate's Cargo metadata and the
+//! `VISUALSIGN_GIT_COMMIT` environment variable at build time.
+
+use serde::{Deserialize, Serialize};
+
+/// Crate name, version and commit of a parser.
+///
+/// Fields are declared in alphabetical order of their serialized names, so the derived
+/// serialization is already deterministic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParserInfo {
+    #[serde(rename = "CrateName")]
+    pub crate_name: String,
+    /// Git commit the parser was built from, when the build recorded one
+    #[serde(rename = "GitCommit", default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    #[serde(rename = "Version")]
+    pub version: String,
+}
+
+crate::impl_deterministic_serialize!(ParserInfo);
+
+impl ParserInfo {
+    pub fn new(crate_name: &str, version: &str, git_commit: Option<&str>) -> Self {
+        ParserInfo {
+            crate_name: crate_name.to_string(),
+            git_commit: git_commit.map(str::to_string),
+            version: version.to_string(),
+        }
+    }
+}
+
+/// The [`ParserInfo`] of the crate this macro is invoked from.
+#[macro_export]
+macro_rules! parser_info {
+    () => {
+        $crate::parser_info::ParserInfo::new(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            option_env!("VISUALSIGN_GIT_COMMIT"),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeterministicOrdering;
+
+    #[test]
+    fn test_parser_info_serialization() {
+        let info = crate::parser_info!();
+        assert_eq!(info.crate_name, "visualsign");
+        assert_eq!(info.version, crate::VERSION);
+        assert!(info.verify_deterministic_ordering().is_ok());
+
+        let json =
+            serde_json::to_string(&ParserInfo::new("visualsign-sui", "0.1.0", Some("abc123")))
+                .unwrap();
+        assert_eq!(
+            json,
+            r#"{"CrateName":"visualsign-sui","GitCommit":"abc123","Version":"0.1.0"}"#
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Declarative policy rules evaluated against parsed [`SignablePayload`]s.
+//!
+//! Rules only see what the parser rendered, which keeps evaluation chain-agnostic:
+//! - amounts come from `Amount`/`AmountV2` fields and are compared per field as exact decimals
+//! - destinations come from fields labelled like "To" or "Recipient"
+//! - selectors come from the leading four bytes of hex calldata fields such as "Input Data"
+//!
+//! Policies can be written as JSON, e.g.
+//! `{"Rules":[{"Type":"MaxAmount","Asset":"ETH","Max":"1.5"}]}`.
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::VisualSignError;
+use crate::intent::collect_fields;
+use crate::{SignablePayload, SignablePayloadField};
+
+// Labels whose value is the address funds or calls are sent to
+const DESTINATION_LABELS: [&str; 4] = ["to", "recipient", "destination", "receiver"];
+
+// Labels of fields that carry raw hex calldata
+const CALLDATA_LABELS: [&str; 4] = ["input data", "raw data", "call data", "data"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "Type")]
+pub enum PolicyRule {
+    /// No single amount of `asset` may exceed `max`, given as a decimal in display units
+    MaxAmount {
+        #[serde(rename = "Asset")]
+        asset: String,
+        #[serde(rename = "Max")]
+        max: String,
+    },
+    /// Every destination address must be in `addresses`
+    AllowedDestinations {
+        #[serde(rename = "Addresses")]
+        addresses: Vec<String>,
+    },
+    /// Calldata must not start with any of `selectors` (hex, with or without 0x)
+    DeniedSelectors {
+        #[serde(rename = "Selectors")]
+        selectors: Vec<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Policy {
+    #[serde(rename = "Rules")]
+    pub rules: Vec<PolicyRule>,
+}
+
+/// A rule that a payload field violated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation {
+    /// Position of the rule in [`Policy::rules`]
+    pub rule_index: usize,
+    pub rule: PolicyRule,
+    pub field_label: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyEvaluation {
+    pub violations: Vec<PolicyViolation>,
+}
+
+impl PolicyEvaluation {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Policy {
+    pub fn new(rules: Vec<PolicyRule>) -> Result<Self, VisualSignError> {
+        let policy = Policy { rules };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Parses and validates a JSON policy.
+    pub fn from_json(json: &str) -> Result<Self, VisualSignError> {
+        let policy: Policy = serde_json::from_str(json)
+            .map_err(|e| VisualSignError::ValidationError(format!("Invalid policy: {e}")))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    fn validate(&self) -> Result<(), VisualSignError> {
+        for (index, rule) in self.rules.iter().enumerate() {
+            let invalid = |reason: String| {
+                VisualSignError::ValidationError(format!("Policy rule {index}: {reason}"))
+            };
+            match rule {
+                PolicyRule::MaxAmount { max, .. } => {
+                    if Decimal::parse(max).is_none() {
+                        return Err(invalid(format!("'{max}' is not a decimal amount")));
+                    }
+                }
+                PolicyRule::AllowedDestinations { .. } => {}
+                PolicyRule::DeniedSelectors { selectors } => {
+                    if let Some(bad) = selectors.iter().find(|s| normalize_selector(s).is_none()) {
+                        return Err(invalid(format!("'{bad}' is not a 4-byte hex selector")));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates every rule against every field of `payload`, including nested layout fields.
+    pub fn evaluate(&self, payload: &SignablePayload) -> PolicyEvaluation {
+        let mut fields = Vec::new();
+        collect_fields(&payload.fields, &mut fields);
+
+        let mut violations = Vec::new();
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            for field in &fields {
+                if let Some(detail) = check_rule(rule, field) {
+                    violations.push(PolicyViolation {
+                        rule_index,
+                        rule: rule.clone(),
+                        field_label: field.label().clone(),
+                        detail,
+                    });
+                }
+            }
+        }
+        PolicyEvaluation { violations }
+    }
+}
+
+// Returns a description of the violation when `field` breaks `rule`
+fn check_rule(rule: &PolicyRule, field: &SignablePayloadField) -> Option<String> {
+    match rule {
+        PolicyRule::MaxAmount { asset, max } => {
+            let (amount, abbreviation) = match field {
+                SignablePayloadField::Amount { amount, .. } => {
+                    (&amount.amount, amount.abbreviation.as_ref()?)
+                }
+                SignablePayloadField::AmountV2 { amount_v2, .. } => {
+                    (&amount_v2.amount, amount_v2.abbreviation.as_ref()?)
+                }
+                SignablePayloadField::AmountV3 { amount_v3, .. } => {
+                    (&amount_v3.amount, amount_v3.abbreviation.as_ref()?)
+                }
+                _ => return None,
+            };
+            if !abbreviation.eq_ignore_ascii_case(asset) {
+                return None;
+            }
+            // Amounts the parser could not render as decimals cannot be shown to be within limits
+            let Some(value) = Decimal::parse(amount) else {
+                return Some(format!("{amount} {abbreviation} is not a decimal amount"));
+            };
+            let limit = Decimal::parse(max)?;
+            (value > limit)
+                .then(|| format!("{amount} {abbreviation} exceeds maximum {max} {abbreviation}"))
+        }
+        PolicyRule::AllowedDestinations { addresses } => {
+            let label = field.label().to_lowercase();
+            if !DESTINATION_LABELS.contains(&label.as_str()) {
+                return None;
+            }
+            let destination = field_text(field)?;
+            let allowed = addresses
+                .iter()
+                .any(|address| address.trim().eq_ignore_ascii_case(destination.trim()));
+            (!allowed).then(|| format!("{destination} is not an allowed destination"))
+        }
+        PolicyRule::DeniedSelectors { selectors } => {
+            let label = field.label().to_lowercase();
+            if !CALLDATA_LABELS.contains(&label.as_str()) {
+                return None;
+            }
+            let data = field_text(field)?.to_lowercase();
+            let data = data.strip_prefix("0x").unwrap_or(&data);
+            selectors
+                .iter()
+                .filter_map(|selector| normalize_selector(selector))
+                .find(|selector| data.starts_with(selector.as_str()))
+                .map(|selector| format!("calldata uses denied selector 0x{selector}"))
+        }
+    }
+}
+
+fn field_text(field: &SignablePayloadField) -> Option<&String> {
+    match field {
+        SignablePayloadField::Text { text, .. } => Some(&text.text),
+        SignablePayloadField::TextV2 { text_v2, .. } => Some(&text_v2.text),
+        SignablePayloadField::Address { address, .. } => Some(&address.address),
+        SignablePayloadField::AddressV2 { address_v2, .. } => Some(&address_v2.address),
+        _ => None,
+    }
+}
+
+// Lowercase 8-digit hex without prefix, or None if the selector is malformed
+fn normalize_selector(selector: &str) -> Option<String> {
+    let selector = selector.trim().to_lowercase();
+    let hex = selector.strip_prefix("0x").unwrap_or(&selector);
+    (hex.len() == 8 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| hex.to_string())
+}
+
+// Exact decimal used for comparisons so limits never pass through floating point
+#[derive(Debug, PartialEq, Eq)]
+struct Decimal {
+    negative: bool,
+    // Integer digits without leading zeros
+    integer: String,
+    // Fraction digits without trailing zeros
+    fraction: String,
+}
+
+impl Decimal {
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((_, "")) => return None,
+            Some(parts) => parts,
+            None => (unsigned, ""),
+        };
+        let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if integer.is_empty() || !digits(integer) || !digits(fraction) {
+            return None;
+        }
+        let integer = integer.trim_start_matches('0').to_string();
+        let fraction = fraction.trim_end_matches('0').to_string();
+        // Normalize -0 to 0 so it compares equal to zero
+        let negative = negative && !(integer.is_empty() && fraction.is_empty());
+        Some(Decimal {
+            negative,
+            integer,
+            fraction,
+        })
+    }
+
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        self.integer
+            .len()
+            .cmp(&other.integer.len())
+            .then_with(|| self.integer.cmp(&other.integer))
+            .then_with(|| self.fraction.cmp(&other.fraction))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::{create_address_field, create_amount_field, create_text_field};
+    use crate::{SignablePayloadFieldCommon, SignablePayloadFieldListLayout};
+
+    const ALLOWED: &str = "0xAbCdEf0000000000000000000000000000000001";
+    const OTHER: &str = "0x9999999999999999999999999999999999999999";
+
+    fn payload(to: &str, amount: &str, input: &str) -> SignablePayload {
+        let fields = vec![
+            create_address_field("To", to, None, None, None, None)
+                .unwrap()
+                .signable_payload_field,
+            SignablePayloadField::ListLayout {
+                common: SignablePayloadFieldCommon {
+                    fallback_text: "Details".to_string(),
+                    label: "Details".to_string(),
+                },
+                list_layout: SignablePayloadFieldListLayout {
+                    fields: vec![
+                        create_amount_field("Value", amount, "ETH").unwrap(),
+                        create_text_field("Input Data", input).unwrap(),
+                    ],
+                },
+            },
+        ];
+        SignablePayload::new(0, "Tx".to_string(), None, fields, "Test".to_string())
+    }
+
+    fn policy() -> Policy {
+        Policy::from_json(&format!(
+            r#"{{"Rules":[
+                {{"Type":"MaxAmount","Asset":"eth","Max":"1.5"}},
+                {{"Type":"AllowedDestinations","Addresses":["{ALLOWED}"]}},
+                {{"Type":"DeniedSelectors","Selectors":["0x095EA7B3"]}}
+            ]}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_compliant_payload_passes() {
+        let evaluation =
+            policy().evaluate(&payload(&ALLOWED.to_lowercase(), "1.50", "0xa9059cbb00"));
+        assert!(evaluation.passed(), "{evaluation:?}");
+    }
+
+    #[test]
+    fn test_violations_are_reported_per_rule() {
+        let evaluation = policy().evaluate(&payload(OTHER, "1.500001", "0x095ea7b3ff"));
+        assert!(!evaluation.passed());
+
+        let summary: Vec<(usize, &str, &str)> = evaluation
+            .violations
+            .iter()
+            .map(|v| (v.rule_index, v.field_label.as_str(), v.detail.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, "Value", "1.500001 ETH exceeds maximum 1.5 ETH"),
+                (
+                    1,
+                    "To",
+                    &format!("{OTHER} is not an allowed destination")[..]
+                ),
+                (2, "Input Data", "calldata uses denied selector 0x095ea7b3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_policies_are_rejected() {
+        let result =
+            Policy::from_json(r#"{"Rules":[{"Type":"MaxAmount","Asset":"ETH","Max":"1e3"}]}"#);
+        assert!(matches!(result, Err(VisualSignError::ValidationError(_))));
+
+        let result = Policy::new(vec![PolicyRule::DeniedSelectors {
+            selectors: vec!["0x1234".to_string()],
+        }]);
+        assert!(matches!(result, Err(VisualSignError::ValidationError(_))));
+
+        let result = Policy::from_json(r#"{"Rules":[{"Type":"Unknown"}]}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_decimal_ordering() {
+        let d = |s: &str| Decimal::parse(s).unwrap();
+        assert!(d("10") > d("9.999"));
+        assert!(d("0.1") > d("0.09"));
+        assert!(d("-2") < d("-1.5"));
+        assert!(d("-0.5") < d("0"));
+        assert_eq!(d("-0.0"), d("0"));
+        assert_eq!(d("001.200"), d("1.2"));
+        assert!(Decimal::parse("1.").is_none());
+        assert!(Decimal::parse(".5").is_none());
+        assert!(Decimal::parse("1,000").is_none());
+    }
+}
@@ -0,0 +1,184 @@
+//! Where each rendered field comes from in the raw transaction.
+//!
+//! A [`Provenance`] maps the path of a field to the bytes or the instruction it was decoded
+//! from, so auditors and test tooling can check a payload against the blob that is actually
+//! signed. Paths use the notation of [`SignablePayload::duplicate_labels`]: `Fields[3]` for a
+//! top-level field and `Fields[0].PreviewLayout.Expanded[2]` for one inside a layout.
+//!
+//! Converters fill the map in when
+//! [`crate::vsptrait::OutputFlags::include_provenance`] is set. Fields that are not read
+//! from the transaction, such as a resolved name or a summary combining several values, have
+//! no entry.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::VisualSignError;
+use crate::field_path::FieldPath;
+use crate::SignablePayload;
+
+/// The part of the raw transaction a field is decoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProvenanceSource {
+    /// Bytes `start..end` of the decoded transaction blob, e.g. one RLP item with its header
+    Bytes {
+        #[serde(rename = "Start")]
+        start: usize,
+        #[serde(rename = "End")]
+        end: usize,
+    },
+    /// The instruction or command at `index` in the transaction's list
+    Instruction {
+        #[serde(rename = "Index")]
+        index: usize,
+    },
+}
+
+/// Sources of the fields of one payload, keyed by field path.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Provenance(BTreeMap<String, ProvenanceSource>);
+
+impl Provenance {
+    pub fn new() -> Self {
+        Provenance::default()
+    }
+
+    /// The path of the top-level field at `index`.
+    pub fn field_path(index: usize) -> String {
+        FieldPath::top_level(index).to_dotted()
+    }
+
+    pub fn record(&mut self, path: impl Into<String>, source: ProvenanceSource) {
+        self.0.insert(path.into(), source);
+    }
+
+    pub fn get(&self, path: &str) -> Option<&ProvenanceSource> {
+        self.0.get(path)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &ProvenanceSource)> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Moves the entries of top-level fields under the expanded view of the `PreviewLayout` at
+    /// `index`, for parsers that wrap their fields with [`crate::layout::package_preview_layout`].
+    pub fn within_preview_layout(self, index: usize) -> Self {
+        Provenance(
+            self.0
+                .into_iter()
+                .map(|(path, source)| {
+                    let path = match path.strip_prefix("Fields") {
+                        Some(rest) => format!("Fields[{index}].PreviewLayout.Expanded{rest}"),
+                        None => path,
+                    };
+                    (path, source)
+                })
+                .collect(),
+        )
+    }
+
+    /// The bytes of `raw` the field at `path` is decoded from, when it maps to a byte range.
+    pub fn bytes<'a>(&self, path: &str, raw: &'a [u8]) -> Option<&'a [u8]> {
+        match self.get(path)? {
+            ProvenanceSource::Bytes { start, end } => raw.get(*start..*end),
+            ProvenanceSource::Instruction { .. } => None,
+        }
+    }
+
+    /// Checks that every path names a field of `payload` and every byte range lies within `raw`.
+    /// Instruction indices are not checked, since `raw` is not decoded here.
+    pub fn verify(&self, payload: &SignablePayload, raw: &[u8]) -> Result<(), VisualSignError> {
+        for (path, source) in self.iter() {
+            if payload.field_at_path(path).is_none() {
+                return Err(VisualSignError::ValidationError(format!(
+                    "Provenance names {path}, which is not a field of the payload"
+                )));
+            }
+            if let ProvenanceSource::Bytes { start, end } = source {
+                if start >= end || *end > raw.len() {
+                    return Err(VisualSignError::ValidationError(format!(
+                        "Provenance of {path} is bytes {start}..{end} of a {}-byte transaction",
+                        raw.len()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{package_preview_layout, PreviewTemplate};
+    use crate::{SignablePayloadFieldCommon, SignablePayloadFieldTextV2};
+
+    fn text_field(label: &str) -> SignablePayloadField {
+        SignablePayloadField::TextV2 {
+            common: SignablePayloadFieldCommon {
+                fallback_text: label.to_string(),
+                label: label.to_string(),
+            },
+            text_v2: SignablePayloadFieldTextV2 {
+                text: label.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_provenance_paths_resolve_to_fields() {
+        let raw = [0xc3, 0x01, 0x02, 0x03];
+        let mut provenance = Provenance::new();
+        provenance.record(
+            Provenance::field_path(1),
+            ProvenanceSource::Bytes { start: 2, end: 3 },
+        );
+        provenance.record(
+            Provenance::field_path(0),
+            ProvenanceSource::Instruction { index: 0 },
+        );
+        assert_eq!(provenance.bytes("Fields[1]", &raw), Some(&[0x02][..]));
+        assert_eq!(
+            serde_json::to_string(&provenance).unwrap(),
+            r#"{"Fields[0]":{"Instruction":{"Index":0}},"Fields[1]":{"Bytes":{"Start":2,"End":3}}}"#
+        );
+
+        let fields = vec![text_field("Network"), text_field("Nonce")];
+        let flat = SignablePayload::new(0, "Test".into(), None, fields.clone(), "Test".into());
+        assert!(provenance.verify(&flat, &raw).is_ok());
+        assert!(provenance.verify(&flat, &raw[..2]).is_err());
+
+        // The same fields wrapped in a preview layout move under its expanded view
+        let wrapped = SignablePayload::new(
+            0,
+            "Test".into(),
+            None,
+            vec![package_preview_layout(
+                "Test",
+                fields,
+                &PreviewTemplate::new("{Network}"),
+            )],
+            "Test".into(),
+        );
+        assert!(provenance.verify(&wrapped, &raw).is_err());
+        let provenance = provenance.within_preview_layout(0);
+        assert!(provenance.verify(&wrapped, &raw).is_ok());
+        assert_eq!(
+            wrapped
+                .field_at_path("Fields[0].PreviewLayout.Expanded[1]")
+                .map(|field| field.label().as_str()),
+            Some("Nonce")
+        );
+        assert!(wrapped.field_at_path("Fields[0].ListLayout[0]").is_none());
+    }
+}
@@ -0,0 +1,245 @@
+//! Lookup of fields anywhere in a payload.
+//!
+//! Fields nest inside the condensed and expanded views of a `PreviewLayout` and inside a
+//! `ListLayout`. Consumers such as tests and policy engines usually care about a field wherever
+//! it sits, so these helpers walk every level in display order: a field comes before the fields
+//! nested in it, and a condensed view before the expanded one.
+
+use crate::{SignablePayload, SignablePayloadField, SignablePayloadFieldListLayout};
+
+/// The kind of a [`SignablePayloadField`], without its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    Text,
+    TextV2,
+    Address,
+    AddressV2,
+    Number,
+    Amount,
+    AmountV2,
+    AmountV3,
+    Timestamp,
+    Duration,
+    Percentage,
+    ImageRef,
+    Bytes,
+    Divider,
+    PreviewLayout,
+    ListLayout,
+    Unknown,
+}
+
+impl FieldType {
+    /// The name [`SignablePayloadField::field_type`] reports, e.g. `"amount_v2"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldType::Text => "text",
+            FieldType::TextV2 => "text_v2",
+            FieldType::Address => "address",
+            FieldType::AddressV2 => "address_v2",
+            FieldType::Number => "number",
+            FieldType::Amount => "amount",
+            FieldType::AmountV2 => "amount_v2",
+            FieldType::AmountV3 => "amount_v3",
+            FieldType::Timestamp => "timestamp",
+            FieldType::Duration => "duration",
+            FieldType::Percentage => "percentage",
+            FieldType::ImageRef => "image_ref",
+            FieldType::Bytes => "bytes",
+            FieldType::Divider => "divider",
+            FieldType::PreviewLayout => "preview_layout",
+            FieldType::ListLayout => "list_layout",
+            FieldType::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<&SignablePayloadField> for FieldType {
+    fn from(field: &SignablePayloadField) -> Self {
+        match field {
+            SignablePayloadField::Text { .. } => FieldType::Text,
+            SignablePayloadField::TextV2 { .. } => FieldType::TextV2,
+            SignablePayloadField::Address { .. } => FieldType::Address,
+            SignablePayloadField::AddressV2 { .. } => FieldType::AddressV2,
+            SignablePayloadField::Number { .. } => FieldType::Number,
+            SignablePayloadField::Amount { .. } => FieldType::Amount,
+            SignablePayloadField::AmountV2 { .. } => FieldType::AmountV2,
+            SignablePayloadField::AmountV3 { .. } => FieldType::AmountV3,
+            SignablePayloadField::Timestamp { .. } => FieldType::Timestamp,
+            SignablePayloadField::Duration { .. } => FieldType::Duration,
+            SignablePayloadField::Percentage { .. } => FieldType::Percentage,
+            SignablePayloadField::ImageRef { .. } => FieldType::ImageRef,
+            SignablePayloadField::Bytes { .. } => FieldType::Bytes,
+            SignablePayloadField::Divider { .. } => FieldType::Divider,
+            SignablePayloadField::PreviewLayout { .. } => FieldType::PreviewLayout,
+            SignablePayloadField::ListLayout { .. } => FieldType::ListLayout,
+            SignablePayloadField::Unknown { .. } => FieldType::Unknown,
+        }
+    }
+}
+
+impl SignablePayloadField {
+    /// The fields nested directly in this one, the condensed view before the expanded one.
+    pub fn nested_fields(&self) -> Vec<&SignablePayloadField> {
+        let lists: Vec<&SignablePayloadFieldListLayout> = match self {
+            SignablePayloadField::PreviewLayout { preview_layout, .. } => {
+                [&preview_layout.condensed, &preview_layout.expanded]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            }
+            SignablePayloadField::ListLayout { list_layout, .. } => vec![list_layout],
+            _ => Vec::new(),
+        };
+        lists
+            .into_iter()
+            .flat_map(|list| &list.fields)
+            .map(|annotated| &annotated.signable_payload_field)
+            .collect()
+    }
+}
+
+/// Iterator over every field of a payload, see [`SignablePayload::iter_all_fields`].
+#[derive(Debug, Clone)]
+pub struct AllFields<'a> {
+    // Fields still to visit, the next one last
+    stack: Vec<&'a SignablePayloadField>,
+}
+
+impl<'a> Iterator for AllFields<'a> {
+    type Item = &'a SignablePayloadField;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let field = self.stack.pop()?;
+        self.stack.extend(field.nested_fields().into_iter().rev());
+        Some(field)
+    }
+}
+
+impl SignablePayload {
+    /// Every field of the payload, top-level and nested, in display order.
+    pub fn iter_all_fields(&self) -> AllFields<'_> {
+        AllFields {
+            stack: self.fields.iter().rev().collect(),
+        }
+    }
+
+    /// The first field labelled `label` at any level.
+    pub fn find_field(&self, label: &str) -> Option<&SignablePayloadField> {
+        self.iter_all_fields().find(|field| field.label() == label)
+    }
+
+    /// Every field of kind `field_type` at any level.
+    pub fn fields_of_type(
+        &self,
+        field_type: FieldType,
+    ) -> impl Iterator<Item = &SignablePayloadField> {
+        self.iter_all_fields()
+            .filter(move |field| FieldType::from(*field) == field_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AnnotatedPayloadField, SignablePayloadFieldCommon, SignablePayloadFieldPreviewLayout,
+        SignablePayloadFieldTextV2,
+    };
+
+    fn common(label: &str) -> SignablePayloadFieldCommon {
+        SignablePayloadFieldCommon {
+            fallback_text: label.to_string(),
+            label: label.to_string(),
+        }
+    }
+
+    fn text_field(label: &str) -> SignablePayloadField {
+        SignablePayloadField::TextV2 {
+            common: common(label),
+            text_v2: SignablePayloadFieldTextV2 {
+                text: label.to_string(),
+            },
+        }
+    }
+
+    fn list(fields: Vec<SignablePayloadField>) -> SignablePayloadFieldListLayout {
+        SignablePayloadFieldListLayout {
+            fields: fields
+                .into_iter()
+                .map(|signable_payload_field| AnnotatedPayloadField {
+                    signable_payload_field,
+                    static_annotation: None,
+                    dynamic_annotation: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn nested_payload() -> SignablePayload {
+        let instructions = SignablePayloadField::ListLayout {
+            common: common("Instructions"),
+            list_layout: list(vec![text_field("Program"), text_field("Amount")]),
+        };
+        let preview = SignablePayloadField::PreviewLayout {
+            common: common("Transfer"),
+            preview_layout: SignablePayloadFieldPreviewLayout {
+                title: None,
+                subtitle: None,
+                condensed: Some(list(vec![text_field("Recipient")])),
+                expanded: Some(list(vec![text_field("Recipient"), instructions])),
+            },
+        };
+        SignablePayload::new(
+            0,
+            "Test".into(),
+            None,
+            vec![text_field("Network"), preview],
+            "Test".into(),
+        )
+    }
+
+    #[test]
+    fn test_iter_all_fields_walks_layouts_in_display_order() {
+        let payload = nested_payload();
+        let labels: Vec<&str> = payload
+            .iter_all_fields()
+            .map(|field| field.label().as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Network",
+                "Transfer",
+                "Recipient",
+                "Recipient",
+                "Instructions",
+                "Program",
+                "Amount"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_field_and_fields_of_type() {
+        let payload = nested_payload();
+
+        assert_eq!(
+            payload
+                .find_field("Program")
+                .map(SignablePayloadField::label),
+            Some(&"Program".to_string())
+        );
+        assert!(payload.find_field("Fee").is_none());
+
+        assert_eq!(payload.fields_of_type(FieldType::TextV2).count(), 5);
+        let layouts: Vec<&str> = payload
+            .fields_of_type(FieldType::ListLayout)
+            .map(|field| field.label().as_str())
+            .collect();
+        assert_eq!(layouts, ["Instructions"]);
+        for field in payload.iter_all_fields() {
+            assert_eq!(FieldType::from(field).as_str(), field.field_type());
+        }
+    }
+}
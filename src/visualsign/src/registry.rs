@@ -4,6 +4,9 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::{
+    capabilities::{ChainCapabilities, ParserCapabilities},
+    outcome::{ParseOutcome, ParseWarning, ParseWarningKind},
+    parser_info::ParserInfo,
     vsptrait::{
         Transaction, VisualSignConverter, VisualSignConverterFromString, VisualSignError,
         VisualSignOptions,
@@ -68,7 +71,17 @@ pub trait VisualSignConverterAny: Send + Sync {
         options: VisualSignOptions,
     ) -> Result<SignablePayload, VisualSignError>;
 
+    fn to_visual_sign_outcome_from_string_any(
+        &self,
+        transaction_data: &str,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError>;
+
     fn supports_format(&self, transaction_data: &str) -> bool;
+
+    fn parser_info(&self) -> Option<ParserInfo>;
+
+    fn capabilities(&self) -> ParserCapabilities;
 }
 
 // Create a wrapper type to hold both the converter and a marker for the transaction type
@@ -109,10 +122,36 @@ where
             .to_visual_sign_payload_from_string(transaction_data, options)
     }
 
+    fn to_visual_sign_outcome_from_string_any(
+        &self,
+        transaction_data: &str,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
+        self.converter
+            .to_visual_sign_outcome_from_string(transaction_data, options)
+    }
+
     fn supports_format(&self, transaction_data: &str) -> bool {
         // Try to parse and see if it succeeds
         T::from_string(transaction_data).is_ok()
     }
+
+    fn parser_info(&self) -> Option<ParserInfo> {
+        self.converter.parser_info()
+    }
+
+    fn capabilities(&self) -> ParserCapabilities {
+        self.converter.capabilities()
+    }
+}
+
+// Parsers fill in the fields of `crate::telemetry` while this span is current
+fn convert_span(chain: &Chain) -> tracing::Span {
+    tracing::info_span!(
+        "convert",
+        chain = chain.as_str(),
+        command_count = tracing::field::Empty
+    )
 }
 
 /// Registry for transaction converters
@@ -152,6 +191,7 @@ impl TransactionConverterRegistry {
         transaction_data: &str,
         options: VisualSignOptions,
     ) -> Result<SignablePayload, VisualSignError> {
+        let _span = convert_span(chain).entered();
         match self.get_converter(chain) {
             Some(converter) => {
                 converter.to_visual_sign_payload_from_string_any(transaction_data, options)
@@ -163,6 +203,25 @@ impl TransactionConverterRegistry {
         }
     }
 
+    /// Same as [`Self::convert_transaction`], keeping the converter's non-fatal warnings
+    pub fn convert_transaction_with_warnings(
+        &self,
+        chain: &Chain,
+        transaction_data: &str,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
+        let _span = convert_span(chain).entered();
+        match self.get_converter(chain) {
+            Some(converter) => {
+                converter.to_visual_sign_outcome_from_string_any(transaction_data, options)
+            }
+            None => Err(VisualSignError::ConversionError(format!(
+                "No converter registered for chain: {}",
+                chain.as_str()
+            ))),
+        }
+    }
+
     pub fn auto_detect_and_convert(
         &self,
         transaction_data: &str,
@@ -185,9 +244,106 @@ impl TransactionConverterRegistry {
         ))
     }
 
+    /// Converts `transaction_data` with the converter of `chain` when one is hinted, and detects
+    /// the chain when there is no hint or its converter cannot parse the data. Returns the chain
+    /// that converted it.
+    ///
+    /// Detection tries the built-in chains in name order. Custom chains and
+    /// [`Chain::Unspecified`] are only used when hinted: message formats and the unspecified
+    /// converter accept any input. An outcome from another chain than the hinted one carries a
+    /// warning saying so, and when no chain converts the data the hinted chain's error is
+    /// returned.
+    pub fn parse_any(
+        &self,
+        chain: Option<&Chain>,
+        transaction_data: &str,
+        options: VisualSignOptions,
+    ) -> Result<(Chain, ParseOutcome), VisualSignError> {
+        let hinted_error = match chain {
+            Some(chain) => {
+                match self.convert_transaction_with_warnings(
+                    chain,
+                    transaction_data,
+                    options.clone(),
+                ) {
+                    Ok(outcome) => return Ok((chain.clone(), outcome)),
+                    Err(error) => Some((chain, error)),
+                }
+            }
+            None => None,
+        };
+
+        let mut candidates: Vec<(&Chain, &dyn VisualSignConverterAny)> = self
+            .converters
+            .iter()
+            .filter(|(candidate, _)| {
+                !matches!(candidate, Chain::Unspecified | Chain::Custom(_))
+                    && Some(*candidate) != chain
+            })
+            .map(|(candidate, converter)| (candidate, converter.as_ref()))
+            .collect();
+        candidates.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+        for (candidate, converter) in candidates {
+            if !converter.supports_format(transaction_data) {
+                continue;
+            }
+            let _span = convert_span(candidate).entered();
+            let Ok(mut outcome) =
+                converter.to_visual_sign_outcome_from_string_any(transaction_data, options.clone())
+            else {
+                continue;
+            };
+            if let Some((hinted, error)) = &hinted_error {
+                outcome.warnings.push(ParseWarning::new(
+                    ParseWarningKind::Other,
+                    format!(
+                        "Parsed as {} instead of the requested {}, which could not parse it: {error}",
+                        candidate.as_str(),
+                        hinted.as_str()
+                    ),
+                ));
+            }
+            return Ok((candidate.clone(), outcome));
+        }
+
+        Err(match hinted_error {
+            Some((_, error)) => error,
+            None => VisualSignError::ConversionError(
+                "Could not detect transaction type or no compatible converter found".to_string(),
+            ),
+        })
+    }
+
     pub fn supported_chains(&self) -> Vec<Chain> {
         self.converters.keys().cloned().collect()
     }
+
+    /// The parser release behind each registered chain that reports one, sorted by chain name.
+    pub fn parser_infos(&self) -> Vec<(Chain, ParserInfo)> {
+        let mut infos: Vec<(Chain, ParserInfo)> = self
+            .converters
+            .iter()
+            .filter_map(|(chain, converter)| Some((chain.clone(), converter.parser_info()?)))
+            .collect();
+        infos.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        infos
+    }
+
+    /// What the converter of each registered chain supports, sorted by chain name.
+    pub fn chain_capabilities(&self) -> Vec<ChainCapabilities> {
+        let mut capabilities: Vec<ChainCapabilities> = self
+            .converters
+            .iter()
+            .map(|(chain, converter)| ChainCapabilities {
+                chain: chain.clone(),
+                parser_info: converter.parser_info(),
+                capabilities: converter.capabilities(),
+            })
+            .collect();
+        capabilities.sort_by(|a, b| a.chain.as_str().cmp(b.chain.as_str()));
+        capabilities
+    }
 }
 
 /// Generic layered registry for combining global and request-scoped data.
@@ -395,6 +551,20 @@ mod tests {
         }
     }
 
+    // Accepts any input, like the message formats registered as custom chains
+    #[derive(Debug, Clone)]
+    struct MockMessage;
+
+    impl Transaction for MockMessage {
+        fn from_string(_data: &str) -> Result<Self, TransactionParseError> {
+            Ok(Self)
+        }
+
+        fn transaction_type(&self) -> String {
+            "Message".to_string()
+        }
+    }
+
     // Simple hex decoder function to avoid dependency on hex crate
     fn decode_hex(s: &str) -> Result<Vec<u8>, &'static str> {
         if s.len() % 2 != 0 {
@@ -548,6 +718,95 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_any() {
+        let mut registry = TransactionConverterRegistry::new();
+        registry.register::<MockSolanaTransaction, _>(Chain::Solana, MockSuccessConverter::new());
+        registry
+            .register::<MockEthereumTransaction, _>(Chain::Ethereum, MockSuccessConverter::new());
+        registry.register::<MockMessage, _>(
+            Chain::Custom("Message".to_string()),
+            MockSuccessConverter::new(),
+        );
+
+        // The hinted chain converts the data
+        let (chain, outcome) = registry
+            .parse_any(
+                Some(&Chain::Solana),
+                "01abcdef1234567890",
+                VisualSignOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(chain, Chain::Solana);
+        assert!(outcome.warnings.is_empty());
+
+        // Without a hint the chain is detected
+        let (chain, outcome) = registry
+            .parse_any(None, "02abcdef1234567890", VisualSignOptions::default())
+            .unwrap();
+        assert_eq!(chain, Chain::Ethereum);
+        assert!(outcome.warnings.is_empty());
+
+        // A wrong hint falls back to detection, with a warning
+        let (chain, outcome) = registry
+            .parse_any(
+                Some(&Chain::Solana),
+                "02abcdef1234567890",
+                VisualSignOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(chain, Chain::Ethereum);
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(outcome.warnings[0]
+            .message
+            .starts_with("Parsed as Ethereum instead of the requested Solana"));
+
+        // Custom chains accept anything, so they are only used when hinted
+        let custom = Chain::Custom("Message".to_string());
+        let (chain, _) = registry
+            .parse_any(
+                Some(&custom),
+                "03abcdef1234567890",
+                VisualSignOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(chain, custom);
+
+        // Nothing converts the data: the hinted chain's error, or a detection error
+        let error = registry
+            .parse_any(
+                Some(&Chain::Bitcoin),
+                "03abcdef1234567890",
+                VisualSignOptions::default(),
+            )
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("No converter registered for chain: Bitcoin"));
+        let error = registry
+            .parse_any(None, "03abcdef1234567890", VisualSignOptions::default())
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Could not detect transaction type"));
+    }
+
+    #[test]
+    fn test_chain_capabilities_sorted_by_chain() {
+        let mut registry = TransactionConverterRegistry::new();
+        registry.register::<MockSolanaTransaction, _>(Chain::Solana, MockSuccessConverter::new());
+        registry
+            .register::<MockEthereumTransaction, _>(Chain::Ethereum, MockSuccessConverter::new());
+
+        let capabilities = registry.chain_capabilities();
+        let chains: Vec<&str> = capabilities.iter().map(|c| c.chain.as_str()).collect();
+        assert_eq!(chains, vec!["Ethereum", "Solana"]);
+        // Converters that do not describe themselves report nothing
+        assert!(capabilities
+            .iter()
+            .all(|c| c.parser_info.is_none() && c.capabilities == ParserCapabilities::default()));
+    }
+
     #[test]
     fn test_empty_registry() {
         let registry = TransactionConverterRegistry::new();
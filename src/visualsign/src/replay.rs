@@ -0,0 +1,123 @@
+//! The "Replay Protection" field telling the signer what stops the signed transaction from being
+//! submitted again elsewhere.
+//!
+//! Chains bind a signature to one chain and one stretch of history in different terms: Ethereum
+//! by the chain id it signs over (EIP-155 and every typed transaction), Tron by a reference to a
+//! recent block, Solana by a recent blockhash or a durable nonce, Sui by the epoch it expires
+//! in. A legacy Ethereum transaction signed without a chain id is bound to nothing and can be
+//! replayed on every chain that accepts its format. Parsers describe theirs as a
+//! [`ReplayProtection`] and render it with [`create_replay_protection_field`], so every chain
+//! shows it under the same label and a missing protection carries a warning annotation.
+
+use crate::errors::VisualSignError;
+use crate::field_builders::{create_text_field, create_warning_field};
+use crate::AnnotatedPayloadField;
+
+pub const REPLAY_PROTECTION_LABEL: &str = "Replay Protection";
+
+/// Annotation of the field of a transaction without replay protection
+pub const NO_REPLAY_PROTECTION_WARNING: &str =
+    "Warning: this transaction is not bound to one chain and can be replayed on others";
+
+/// What binds a signed transaction to one chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayProtection {
+    /// Signed over this chain id, e.g. EIP-155
+    ChainId(u64),
+    /// Only accepted on the fork holding this block; both parts in hex, e.g. Tron's
+    /// `ref_block_bytes` and `ref_block_hash`
+    RefBlock { bytes: String, hash: String },
+    /// Only accepted while this blockhash is recent
+    RecentBlockhash(String),
+    /// Only accepted while the nonce account holds this value
+    DurableNonce(String),
+    /// Only accepted until this epoch ends
+    Epoch(u64),
+    /// A binding the parser can only describe
+    Described(String),
+    /// Bound to nothing, e.g. a pre-EIP-155 legacy transaction
+    None,
+}
+
+impl ReplayProtection {
+    /// The protection in words.
+    pub fn text(&self) -> String {
+        match self {
+            ReplayProtection::ChainId(chain_id) => format!("Chain ID {chain_id}"),
+            ReplayProtection::RefBlock { bytes, hash } => {
+                format!("Reference block {bytes} (hash {hash})")
+            }
+            ReplayProtection::RecentBlockhash(blockhash) => format!("Recent blockhash {blockhash}"),
+            ReplayProtection::DurableNonce(nonce) => format!("Durable nonce {nonce}"),
+            ReplayProtection::Epoch(epoch) => format!("Expires after epoch {epoch}"),
+            ReplayProtection::Described(text) => text.clone(),
+            ReplayProtection::None => "None".to_string(),
+        }
+    }
+
+    pub fn is_protected(&self) -> bool {
+        !matches!(self, ReplayProtection::None)
+    }
+}
+
+/// The "Replay Protection" field for `protection`.
+///
+/// Without protection, the field is a [`create_warning_field`] warning annotated with
+/// [`NO_REPLAY_PROTECTION_WARNING`], like the nonce warning.
+pub fn create_replay_protection_field(
+    protection: &ReplayProtection,
+) -> Result<AnnotatedPayloadField, VisualSignError> {
+    let text = protection.text();
+    if protection.is_protected() {
+        return create_text_field(REPLAY_PROTECTION_LABEL, &text);
+    }
+    Ok(create_warning_field(
+        REPLAY_PROTECTION_LABEL,
+        REPLAY_PROTECTION_LABEL,
+        &text,
+        NO_REPLAY_PROTECTION_WARNING,
+        &format!("{text}. {NO_REPLAY_PROTECTION_WARNING}"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::warning_message;
+
+    #[test]
+    fn test_replay_protection_field() {
+        let field = create_replay_protection_field(&ReplayProtection::ChainId(1)).unwrap();
+        assert_eq!(
+            field.signable_payload_field.label(),
+            REPLAY_PROTECTION_LABEL
+        );
+        assert_eq!(field.signable_payload_field.fallback_text(), "Chain ID 1");
+        assert!(field.static_annotation.is_none());
+
+        let field = create_replay_protection_field(&ReplayProtection::None)
+            .unwrap()
+            .signable_payload_field;
+        assert_eq!(field.label(), REPLAY_PROTECTION_LABEL);
+        assert_eq!(
+            field.fallback_text(),
+            &format!("None. {NO_REPLAY_PROTECTION_WARNING}")
+        );
+        let warning = warning_message(&field);
+        assert_eq!(warning.signable_payload_field.fallback_text(), "None");
+        assert_eq!(
+            warning.static_annotation.as_ref().unwrap().text,
+            NO_REPLAY_PROTECTION_WARNING
+        );
+
+        assert_eq!(
+            ReplayProtection::RefBlock {
+                bytes: "2a7c".to_string(),
+                hash: "0b6e0f1c2d3e4f50".to_string(),
+            }
+            .text(),
+            "Reference block 2a7c (hash 0b6e0f1c2d3e4f50)"
+        );
+        assert_eq!(ReplayProtection::Epoch(42).text(), "Expires after epoch 42");
+    }
+}
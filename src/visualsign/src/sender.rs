@@ -0,0 +1,145 @@
+//! The "From" field naming the account that signs a transaction.
+//!
+//! Most chains carry the sender in the unsigned body (Tron's owner address, Solana's fee payer,
+//! Sui's sender), and a signed Ethereum transaction yields it through signature recovery. An
+//! unsigned Ethereum transaction does not name its sender at all, so the host may pass the
+//! account it intends to sign with as [`crate::vsptrait::VisualSignOptions::sender`]. Parsers
+//! call [`resolve_sender`] with both and render the result with [`create_sender_field`], so
+//! every chain shows the sender the same way and a provided sender that contradicts the
+//! transaction fails the conversion instead of being displayed.
+
+use crate::errors::VisualSignError;
+use crate::{SignablePayloadField, SignablePayloadFieldAddressV2, SignablePayloadFieldCommon};
+
+pub const SENDER_LABEL: &str = "From";
+
+/// Badge on a sender the transaction does not confirm, i.e. one only the host provided
+pub const UNVERIFIED_SENDER_BADGE: &str = "Unverified";
+
+/// Where the sender shown in a payload comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SenderSource {
+    /// Read from the transaction or recovered from its signature
+    Derived,
+    /// Supplied by the host through the options only
+    Provided,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sender {
+    pub address: String,
+    pub source: SenderSource,
+}
+
+/// Picks the sender to display from the one found in the transaction and the one the host
+/// provided, preferring the derived address.
+///
+/// `same_address` compares a derived address with a provided one in the chain's own terms,
+/// e.g. ignoring hex case. Fails with a `ValidationError` when both are present and differ.
+pub fn resolve_sender(
+    derived: Option<&str>,
+    provided: Option<&str>,
+    same_address: impl Fn(&str, &str) -> bool,
+) -> Result<Option<Sender>, VisualSignError> {
+    match (derived, provided) {
+        (Some(derived), Some(provided)) if !same_address(derived, provided) => {
+            Err(VisualSignError::ValidationError(format!(
+                "Provided sender {provided} does not match the transaction's sender {derived}"
+            )))
+        }
+        (Some(derived), _) => Ok(Some(Sender {
+            address: derived.to_string(),
+            source: SenderSource::Derived,
+        })),
+        (None, Some(provided)) => Ok(Some(Sender {
+            address: provided.to_string(),
+            source: SenderSource::Provided,
+        })),
+        (None, None) => Ok(None),
+    }
+}
+
+/// The "From" field for `sender`; a provided-only sender is badged as unverified.
+pub fn create_sender_field(sender: &Sender) -> SignablePayloadField {
+    let badge_text = match sender.source {
+        SenderSource::Derived => None,
+        SenderSource::Provided => Some(UNVERIFIED_SENDER_BADGE.to_string()),
+    };
+    SignablePayloadField::AddressV2 {
+        common: SignablePayloadFieldCommon {
+            fallback_text: sender.address.clone(),
+            label: SENDER_LABEL.to_string(),
+        },
+        address_v2: SignablePayloadFieldAddressV2 {
+            address: sender.address.clone(),
+            name: String::new(),
+            memo: None,
+            asset_label: String::new(),
+            badge_text,
+            name_resolved: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same_hex(a: &str, b: &str) -> bool {
+        a.eq_ignore_ascii_case(b)
+    }
+
+    #[test]
+    fn test_resolve_sender_prefers_derived() {
+        let sender = resolve_sender(Some("0xAbC"), Some("0xabc"), same_hex).unwrap();
+        assert_eq!(
+            sender,
+            Some(Sender {
+                address: "0xAbC".to_string(),
+                source: SenderSource::Derived,
+            })
+        );
+
+        let sender = resolve_sender(None, Some("0xabc"), same_hex).unwrap();
+        assert_eq!(sender.unwrap().source, SenderSource::Provided);
+        assert_eq!(resolve_sender(None, None, same_hex).unwrap(), None);
+    }
+
+    #[test]
+    fn test_resolve_sender_rejects_mismatch() {
+        assert_eq!(
+            resolve_sender(Some("0xabc"), Some("0xdef"), same_hex),
+            Err(VisualSignError::ValidationError(
+                "Provided sender 0xdef does not match the transaction's sender 0xabc".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_create_sender_field() {
+        let derived = Sender {
+            address: "0xabc".to_string(),
+            source: SenderSource::Derived,
+        };
+        let SignablePayloadField::AddressV2 { common, address_v2 } = create_sender_field(&derived)
+        else {
+            panic!("Expected AddressV2");
+        };
+        assert_eq!(common.label, SENDER_LABEL);
+        assert_eq!(address_v2.address, "0xabc");
+        assert_eq!(address_v2.badge_text, None);
+
+        let provided = Sender {
+            source: SenderSource::Provided,
+            ..derived
+        };
+        let SignablePayloadField::AddressV2 { address_v2, .. } = create_sender_field(&provided)
+        else {
+            panic!("Expected AddressV2");
+        };
+        assert_eq!(
+            address_v2.badge_text.as_deref(),
+            Some(UNVERIFIED_SENDER_BADGE)
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Caller-supplied simulation results rendered as an advisory "Expected Outcome" section.
+//!
+//! Parsers never run a simulator themselves. When the caller provides a [`SimulationOutcome`]
+//! through [`crate::vsptrait::VisualSignOptions`], it is appended to the payload after the
+//! decoded transaction fields and labelled so a signer cannot mistake it for signed intent.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::VisualSignError;
+use crate::field_builders::create_text_field;
+use crate::{
+    AnnotatedPayloadField, SignablePayloadField, SignablePayloadFieldCommon,
+    SignablePayloadFieldListLayout, SignablePayloadFieldPreviewLayout, SignablePayloadFieldTextV2,
+};
+
+pub const EXPECTED_OUTCOME_LABEL: &str = "Expected Outcome";
+
+const ADVISORY_NOTE: &str = "Provided by the caller's simulator, not part of the signed transaction. Actual results may differ when the transaction executes";
+
+/// Whether the simulated execution succeeded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "Type")]
+pub enum SimulationStatus {
+    #[default]
+    Success,
+    Reverted {
+        #[serde(rename = "Reason", default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+}
+
+impl SimulationStatus {
+    fn describe(&self) -> String {
+        match self {
+            SimulationStatus::Success => "Success".to_string(),
+            SimulationStatus::Reverted {
+                reason: Some(reason),
+            } => format!("Reverted: {reason}"),
+            SimulationStatus::Reverted { reason: None } => "Reverted".to_string(),
+        }
+    }
+}
+
+/// A balance delta reported by the simulator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    /// Account whose balance changes
+    #[serde(rename = "Account")]
+    pub account: String,
+    /// Asset symbol or identifier, e.g. "ETH" or a token address
+    #[serde(rename = "Asset")]
+    pub asset: String,
+    /// Signed decimal amount in display units, e.g. "-1.5" or "+20"
+    #[serde(rename = "Amount")]
+    pub amount: String,
+}
+
+/// An event or log emitted during simulation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedEvent {
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Summary")]
+    pub summary: String,
+}
+
+/// Result of simulating the transaction, supplied by the caller's simulator.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationOutcome {
+    #[serde(rename = "Status")]
+    pub status: SimulationStatus,
+    #[serde(rename = "BalanceChanges", default)]
+    pub balance_changes: Vec<BalanceChange>,
+    #[serde(rename = "Events", default)]
+    pub events: Vec<SimulatedEvent>,
+}
+
+impl SimulationOutcome {
+    /// Builds the "Expected Outcome" preview layout for this simulation.
+    pub fn to_payload_field(&self) -> Result<SignablePayloadField, VisualSignError> {
+        let status = self.status.describe();
+        let title = format!("Simulation (advisory): {status}");
+        let subtitle = format!(
+            "{} balance change(s), {} event(s); not part of the signed transaction",
+            self.balance_changes.len(),
+            self.events.len()
+        );
+
+        let mut condensed_fields: Vec<AnnotatedPayloadField> =
+            vec![create_text_field("Status", &status)?];
+        for (index, change) in self.balance_changes.iter().enumerate() {
+            condensed_fields.push(create_text_field(
+                &format!("Balance Change {}", index + 1),
+                &format!("{} {} for {}", change.amount, change.asset, change.account),
+            )?);
+        }
+
+        let mut expanded_fields = condensed_fields.clone();
+        for (index, event) in self.events.iter().enumerate() {
+            expanded_fields.push(create_text_field(
+                &format!("Event {}", index + 1),
+                &format!("{}: {}", event.name, event.summary),
+            )?);
+        }
+        expanded_fields.push(create_text_field("Note", ADVISORY_NOTE)?);
+
+        Ok(SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: format!("{title}. {ADVISORY_NOTE}"),
+                label: EXPECTED_OUTCOME_LABEL.to_string(),
+            },
+            preview_layout: SignablePayloadFieldPreviewLayout {
+                title: Some(SignablePayloadFieldTextV2 { text: title }),
+                subtitle: Some(SignablePayloadFieldTextV2 { text: subtitle }),
+                condensed: Some(SignablePayloadFieldListLayout {
+                    fields: condensed_fields,
+                }),
+                expanded: Some(SignablePayloadFieldListLayout {
+                    fields: expanded_fields,
+                }),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels_and_text(list: &Option<SignablePayloadFieldListLayout>) -> Vec<String> {
+        list.as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| {
+                format!(
+                    "{}: {}",
+                    f.signable_payload_field.label(),
+                    f.signable_payload_field.fallback_text()
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expected_outcome_layout() {
+        let outcome = SimulationOutcome {
+            status: SimulationStatus::Success,
+            balance_changes: vec![BalanceChange {
+                account: "0xabc".to_string(),
+                asset: "ETH".to_string(),
+                amount: "-1.5".to_string(),
+            }],
+            events: vec![SimulatedEvent {
+                name: "Transfer".to_string(),
+                summary: "1.5 ETH to 0xdef".to_string(),
+            }],
+        };
+
+        let field = outcome.to_payload_field().unwrap();
+        assert_eq!(field.label(), EXPECTED_OUTCOME_LABEL);
+        assert!(field
+            .fallback_text()
+            .contains("not part of the signed transaction"));
+
+        let SignablePayloadField::PreviewLayout { preview_layout, .. } = field else {
+            panic!("Expected PreviewLayout");
+        };
+        assert_eq!(
+            preview_layout.title.unwrap().text,
+            "Simulation (advisory): Success"
+        );
+        assert_eq!(
+            labels_and_text(&preview_layout.condensed),
+            vec!["Status: Success", "Balance Change 1: -1.5 ETH for 0xabc"]
+        );
+        let expanded = labels_and_text(&preview_layout.expanded);
+        assert!(expanded.contains(&"Event 1: Transfer: 1.5 ETH to 0xdef".to_string()));
+        assert_eq!(expanded.last().unwrap(), &format!("Note: {ADVISORY_NOTE}"));
+    }
+
+    #[test]
+    fn test_reverted_status() {
+        let outcome = SimulationOutcome {
+            status: SimulationStatus::Reverted {
+                reason: Some("insufficient balance".to_string()),
+            },
+            ..Default::default()
+        };
+        let field = outcome.to_payload_field().unwrap();
+        assert!(field
+            .fallback_text()
+            .starts_with("Simulation (advisory): Reverted: insufficient balance"));
+
+        let reverted = SimulationStatus::Reverted { reason: None };
+        assert_eq!(reverted.describe(), "Reverted");
+    }
+}
@@ -0,0 +1,542 @@
+//! Machine-readable description of the canonical payload encoding.
+//!
+//! [`canonical_spec`] states the rules that [`SignablePayload::to_json`] and
+//! [`SignablePayload::validate_charset_with`] follow: how keys are ordered and strings escaped,
+//! which keys are left out when unset or empty, what each [`CharsetPolicy`] accepts and which
+//! payload versions exist. Integrators can compare it, serialized, against the copy checked in
+//! at `tests/vectors/canonical_spec.json`, and [`self_test`] checks the implementation against
+//! it on sample payloads, so the description and the encoder cannot drift apart unnoticed.
+
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::coverage::Coverage;
+use crate::parser_info::ParserInfo;
+use crate::{
+    AnnotatedPayloadField, CharsetPolicy, SignablePayload, SignablePayloadField,
+    SignablePayloadFieldAddressV2, SignablePayloadFieldAmount, SignablePayloadFieldAmountV2,
+    SignablePayloadFieldAmountV3, SignablePayloadFieldCommon,
+    SignablePayloadFieldDynamicAnnotation, SignablePayloadFieldListLayout,
+    SignablePayloadFieldPreviewLayout, SignablePayloadFieldStaticAnnotation,
+    SignablePayloadFieldTextV2, FIELD_TYPES, PAYLOAD_VERSIONS,
+};
+
+/// Version of the description itself, bumped whenever a rule in [`canonical_spec`] changes
+pub const SPEC_VERSION: &str = "1";
+
+/// The canonical encoding rules, see [`canonical_spec`].
+///
+/// Fields are declared in alphabetical order of their serialized names, so the derived
+/// serialization is already deterministic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CanonicalSpec {
+    #[serde(rename = "Charset")]
+    pub charset: CharsetSpec,
+    #[serde(rename = "Encoding")]
+    pub encoding: EncodingSpec,
+    /// Every `Type` a field may have
+    #[serde(rename = "FieldTypes")]
+    pub field_types: Vec<&'static str>,
+    #[serde(rename = "SkipRules")]
+    pub skip_rules: Vec<SkipRule>,
+    #[serde(rename = "SpecVersion")]
+    pub spec_version: &'static str,
+    #[serde(rename = "Versions")]
+    pub versions: VersionSpec,
+}
+
+/// How the JSON text is written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EncodingSpec {
+    /// `"Preserved"`: array elements, such as fields, keep their order
+    #[serde(rename = "ArrayOrder")]
+    pub array_order: &'static str,
+    /// `"Minimal"`: only `"`, `\` and control characters are escaped; `/`, `<`, `>`, `&` and
+    /// non-ASCII text are written as-is
+    #[serde(rename = "Escaping")]
+    pub escaping: &'static str,
+    /// `"Utf8Bytes"`: the keys of every object, at every level, sorted by their UTF-8 bytes
+    #[serde(rename = "KeyOrder")]
+    pub key_order: &'static str,
+    /// `"None"`: no whitespace between tokens
+    #[serde(rename = "Whitespace")]
+    pub whitespace: &'static str,
+}
+
+/// When a [`SkipRule`] leaves its key out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum OmittedWhen {
+    /// The optional value is unset
+    Absent,
+    /// The string is empty
+    Empty,
+}
+
+/// A key left out of an object instead of being written with an empty or null value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkipRule {
+    #[serde(rename = "Key")]
+    pub key: &'static str,
+    /// The payload, a field variant such as `AddressV2`, or another named object
+    #[serde(rename = "Object")]
+    pub object: &'static str,
+    #[serde(rename = "OmittedWhen")]
+    pub omitted_when: OmittedWhen,
+}
+
+/// The policies [`SignablePayload::validate_charset_with`] accepts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CharsetSpec {
+    /// The policy [`SignablePayload::to_validated_json`] applies
+    #[serde(rename = "Default")]
+    pub default: CharsetPolicy,
+    #[serde(rename = "Policies")]
+    pub policies: Vec<CharsetRule>,
+}
+
+/// What one [`CharsetPolicy`] accepts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CharsetRule {
+    /// Bidirectional embeddings, overrides and isolates (U+202A..U+202E, U+2066..U+2069)
+    #[serde(rename = "BidiControlsRejected")]
+    pub bidi_controls_rejected: bool,
+    /// Control characters other than whitespace
+    #[serde(rename = "ControlCharactersRejected")]
+    pub control_characters_rejected: bool,
+    /// Printable characters outside ASCII, such as accented letters
+    #[serde(rename = "NonAsciiAccepted")]
+    pub non_ascii_accepted: bool,
+    #[serde(rename = "Policy")]
+    pub policy: CharsetPolicy,
+}
+
+/// The payload `Version` values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionSpec {
+    /// `"DecimalString"`: a base-10 integer without sign or leading zeros, as a JSON string
+    #[serde(rename = "Encoding")]
+    pub encoding: &'static str,
+    /// The versions converters produce
+    #[serde(rename = "Supported")]
+    pub supported: Vec<&'static str>,
+}
+
+const ENCODING: EncodingSpec = EncodingSpec {
+    array_order: "Preserved",
+    escaping: "Minimal",
+    key_order: "Utf8Bytes",
+    whitespace: "None",
+};
+
+const SKIP_RULES: &[(&str, &str, OmittedWhen)] = &[
+    ("SignablePayload", "Coverage", OmittedWhen::Absent),
+    ("SignablePayload", "ParserInfo", OmittedWhen::Absent),
+    ("SignablePayload", "PayloadType", OmittedWhen::Empty),
+    ("SignablePayload", "Subtitle", OmittedWhen::Absent),
+    ("ParserInfo", "GitCommit", OmittedWhen::Absent),
+    (
+        "AnnotatedPayloadField",
+        "DynamicAnnotation",
+        OmittedWhen::Absent,
+    ),
+    (
+        "AnnotatedPayloadField",
+        "StaticAnnotation",
+        OmittedWhen::Absent,
+    ),
+    ("AddressV2", "AssetLabel", OmittedWhen::Empty),
+    ("AddressV2", "BadgeText", OmittedWhen::Absent),
+    ("AddressV2", "Memo", OmittedWhen::Absent),
+    ("AddressV2", "Name", OmittedWhen::Empty),
+    ("AddressV2", "NameResolved", OmittedWhen::Absent),
+    ("Amount", "Abbreviation", OmittedWhen::Absent),
+    ("AmountV2", "Abbreviation", OmittedWhen::Absent),
+    ("AmountV3", "Abbreviation", OmittedWhen::Absent),
+    ("AmountV3", "FiatCurrency", OmittedWhen::Absent),
+    ("AmountV3", "FiatValue", OmittedWhen::Absent),
+    ("AmountV3", "RateSource", OmittedWhen::Absent),
+    ("AmountV3", "RateTimestamp", OmittedWhen::Absent),
+    ("PreviewLayout", "Condensed", OmittedWhen::Absent),
+    ("PreviewLayout", "Expanded", OmittedWhen::Absent),
+    ("PreviewLayout", "Subtitle", OmittedWhen::Absent),
+    ("PreviewLayout", "Title", OmittedWhen::Absent),
+];
+
+/// The rules this crate's canonical encoding follows.
+pub fn canonical_spec() -> CanonicalSpec {
+    CanonicalSpec {
+        charset: CharsetSpec {
+            default: CharsetPolicy::default(),
+            policies: CharsetPolicy::ALL
+                .into_iter()
+                .map(|policy| CharsetRule {
+                    bidi_controls_rejected: true,
+                    control_characters_rejected: true,
+                    non_ascii_accepted: policy == CharsetPolicy::Unicode,
+                    policy,
+                })
+                .collect(),
+        },
+        encoding: ENCODING,
+        field_types: FIELD_TYPES.to_vec(),
+        skip_rules: SKIP_RULES
+            .iter()
+            .map(|&(object, key, omitted_when)| SkipRule {
+                key,
+                object,
+                omitted_when,
+            })
+            .collect(),
+        spec_version: SPEC_VERSION,
+        versions: VersionSpec {
+            encoding: "DecimalString",
+            supported: PAYLOAD_VERSIONS.to_vec(),
+        },
+    }
+}
+
+/// Checks the implementation against [`canonical_spec`] on sample payloads; the error names the
+/// first rule the implementation breaks.
+pub fn self_test() -> Result<(), String> {
+    let spec = canonical_spec();
+    check_encoding()?;
+    check_field_types(&spec.field_types)?;
+    check_skip_rules(&spec.skip_rules)?;
+    check_charset_rules(&spec.charset)?;
+    check_versions(&spec.versions)
+}
+
+fn common(label: &str) -> SignablePayloadFieldCommon {
+    SignablePayloadFieldCommon {
+        fallback_text: label.to_string(),
+        label: label.to_string(),
+    }
+}
+
+fn text_field(label: &str, text: &str) -> SignablePayloadField {
+    SignablePayloadField::TextV2 {
+        common: common(label),
+        text_v2: SignablePayloadFieldTextV2 {
+            text: text.to_string(),
+        },
+    }
+}
+
+fn payload(title: &str, fields: Vec<SignablePayloadField>) -> SignablePayload {
+    SignablePayload::new(0, title.to_string(), None, fields, String::new())
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
+
+fn canonical(payload: &SignablePayload) -> Result<String, String> {
+    payload.to_json().map_err(|e| e.to_string())
+}
+
+fn check_encoding() -> Result<(), String> {
+    let sample = payload(
+        "a/b <c> & \"d\" caf\u{e9}",
+        vec![text_field("Zeta", "1"), text_field("Alpha", "2")],
+    );
+    let json = canonical(&sample)?;
+    let value: Value = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+
+    crate::verify_json_deterministic(&value, "").map_err(|e| format!("KeyOrder: {e}"))?;
+    let labels: Vec<&Value> = ["/Fields/0/Label", "/Fields/1/Label"]
+        .iter()
+        .filter_map(|pointer| value.pointer(pointer))
+        .collect();
+    if labels != ["Zeta", "Alpha"] {
+        return Err(format!("ArrayOrder: fields reordered to {labels:?}"));
+    }
+    if !json.contains("\"Title\":\"a/b <c> & \\\"d\\\" caf\u{e9}\"") {
+        return Err(format!("Escaping: unexpected title escaping in {json}"));
+    }
+    let compact = serde_json::to_string(&value).map_err(|e| e.to_string())?;
+    if json != compact {
+        return Err(format!("Whitespace: {json} is not compact"));
+    }
+
+    let decoded = SignablePayload::from_json(&json).map_err(|e| e.to_string())?;
+    if canonical(&decoded)? != json {
+        return Err("re-encoding the canonical form changed it".to_string());
+    }
+    Ok(())
+}
+
+fn check_field_types(field_types: &[&str]) -> Result<(), String> {
+    let unique: BTreeSet<&str> = field_types.iter().copied().collect();
+    if unique.len() != field_types.len() {
+        return Err("FieldTypes lists a type twice".to_string());
+    }
+    for (_, field) in field_samples() {
+        for field in [field.0, field.1] {
+            let field_type = field.field_type();
+            let serialized = to_value(&field)?;
+            if serialized["Type"] != field_type || !unique.contains(field_type) {
+                return Err(format!("field type {field_type} is not in FieldTypes"));
+            }
+        }
+    }
+    Ok(())
+}
+
+// A field variant with all optional values unset, and with all of them set
+fn field_samples() -> Vec<(&'static str, (SignablePayloadField, SignablePayloadField))> {
+    let address = |name: &str, set: bool| SignablePayloadField::AddressV2 {
+        common: common("To"),
+        address_v2: SignablePayloadFieldAddressV2 {
+            address: "0x3535353535353535353535353535353535353535".to_string(),
+            name: name.to_string(),
+            memo: set.then(|| "Invoice 42".to_string()),
+            asset_label: if set { "ETH" } else { "" }.to_string(),
+            badge_text: set.then(|| "Verified".to_string()),
+            name_resolved: set.then_some(true),
+        },
+    };
+    let abbreviation = |set: bool| set.then(|| "ETH".to_string());
+    let amount = |set: bool| SignablePayloadField::Amount {
+        common: common("Value"),
+        amount: SignablePayloadFieldAmount {
+            amount: "1".to_string(),
+            abbreviation: abbreviation(set),
+        },
+    };
+    let amount_v2 = |set: bool| SignablePayloadField::AmountV2 {
+        common: common("Value"),
+        amount_v2: SignablePayloadFieldAmountV2 {
+            amount: "1".to_string(),
+            abbreviation: abbreviation(set),
+        },
+    };
+    let amount_v3 = |set: bool| SignablePayloadField::AmountV3 {
+        common: common("Value"),
+        amount_v3: SignablePayloadFieldAmountV3 {
+            amount: "1".to_string(),
+            abbreviation: abbreviation(set),
+            fiat_value: set.then(|| "2500.00".to_string()),
+            fiat_currency: set.then(|| "USD".to_string()),
+            rate_source: set.then(|| "host".to_string()),
+            rate_timestamp: set.then(|| "2024-06-01T12:00:00Z".to_string()),
+        },
+    };
+    let text = |text: &str| SignablePayloadFieldTextV2 {
+        text: text.to_string(),
+    };
+    let list = || SignablePayloadFieldListLayout { fields: Vec::new() };
+    let preview = |set: bool| SignablePayloadField::PreviewLayout {
+        common: common("Transfer"),
+        preview_layout: SignablePayloadFieldPreviewLayout {
+            title: set.then(|| text("Transfer")),
+            subtitle: set.then(|| text("1 ETH")),
+            condensed: set.then(list),
+            expanded: set.then(list),
+        },
+    };
+    vec![
+        ("AddressV2", (address("", false), address("Vitalik", true))),
+        ("Amount", (amount(false), amount(true))),
+        ("AmountV2", (amount_v2(false), amount_v2(true))),
+        ("AmountV3", (amount_v3(false), amount_v3(true))),
+        ("PreviewLayout", (preview(false), preview(true))),
+    ]
+}
+
+// The JSON object `object` with all optional values unset, and with all of them set
+fn object_samples(object: &str) -> Result<Option<(Value, Value)>, String> {
+    let samples = match object {
+        "SignablePayload" => {
+            let minimal = payload("Sample", Vec::new());
+            let full = SignablePayload {
+                coverage: Some(Coverage::new(4, 0)),
+                parser_info: Some(crate::parser_info!()),
+                payload_type: "Sample".to_string(),
+                subtitle: Some("Sample".to_string()),
+                ..minimal.clone()
+            };
+            (to_value(&minimal)?, to_value(&full)?)
+        }
+        "ParserInfo" => (
+            to_value(&ParserInfo::new("visualsign", "0.1.0", None))?,
+            to_value(&ParserInfo::new("visualsign", "0.1.0", Some("0123abc")))?,
+        ),
+        "AnnotatedPayloadField" => {
+            let minimal = AnnotatedPayloadField {
+                signable_payload_field: text_field("Note", "Sample"),
+                static_annotation: None,
+                dynamic_annotation: None,
+            };
+            let full = AnnotatedPayloadField {
+                static_annotation: Some(SignablePayloadFieldStaticAnnotation {
+                    text: "Sample".to_string(),
+                }),
+                dynamic_annotation: Some(SignablePayloadFieldDynamicAnnotation {
+                    field_type: "sample".to_string(),
+                    id: "1".to_string(),
+                    params: Vec::new(),
+                }),
+                ..minimal.clone()
+            };
+            (to_value(&minimal)?, to_value(&full)?)
+        }
+        _ => match field_samples()
+            .into_iter()
+            .find(|(name, _)| *name == object)
+        {
+            Some((_, (minimal, full))) => (
+                to_value(&minimal)?[object].take(),
+                to_value(&full)?[object].take(),
+            ),
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(samples))
+}
+
+fn keys(value: &Value) -> BTreeSet<String> {
+    value
+        .as_object()
+        .map(|map| map.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn check_skip_rules(skip_rules: &[SkipRule]) -> Result<(), String> {
+    let objects: BTreeSet<&str> = skip_rules.iter().map(|rule| rule.object).collect();
+    for object in objects {
+        let (minimal, full) =
+            object_samples(object)?.ok_or_else(|| format!("SkipRules: no sample of {object}"))?;
+        // Exactly the keys with a rule disappear once their values are unset or empty
+        let omitted: BTreeSet<String> = keys(&full).difference(&keys(&minimal)).cloned().collect();
+        let expected: BTreeSet<String> = skip_rules
+            .iter()
+            .filter(|rule| rule.object == object)
+            .map(|rule| rule.key.to_string())
+            .collect();
+        if omitted != expected {
+            return Err(format!(
+                "SkipRules: {object} omits {omitted:?}, the spec lists {expected:?}"
+            ));
+        }
+        for rule in skip_rules.iter().filter(|rule| rule.object == object) {
+            let is_string = full[rule.key].is_string();
+            if rule.omitted_when == OmittedWhen::Empty && !is_string {
+                return Err(format!("SkipRules: {object}.{} is not a string", rule.key));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_charset_rules(charset: &CharsetSpec) -> Result<(), String> {
+    if charset.default != CharsetPolicy::default() {
+        return Err(format!(
+            "Charset: default is {:?}",
+            CharsetPolicy::default()
+        ));
+    }
+    let policies: Vec<CharsetPolicy> = charset.policies.iter().map(|rule| rule.policy).collect();
+    if policies != CharsetPolicy::ALL {
+        return Err(format!("Charset: policies are {:?}", CharsetPolicy::ALL));
+    }
+
+    let non_ascii = payload("Caf\u{e9}", Vec::new());
+    let bidi = payload("Send \u{202E}1 ETH", Vec::new());
+    let control = payload("Send\u{7} 1 ETH", Vec::new());
+    for rule in &charset.policies {
+        let accepts = |sample: &SignablePayload| sample.validate_charset_with(rule.policy).is_ok();
+        let checks = [
+            (
+                "NonAsciiAccepted",
+                accepts(&non_ascii),
+                rule.non_ascii_accepted,
+            ),
+            (
+                "BidiControlsRejected",
+                !accepts(&bidi),
+                rule.bidi_controls_rejected,
+            ),
+            (
+                "ControlCharactersRejected",
+                !accepts(&control),
+                rule.control_characters_rejected,
+            ),
+        ];
+        for (name, actual, expected) in checks {
+            if actual != expected {
+                return Err(format!(
+                    "Charset: {name} is {actual} under {}",
+                    rule.policy.as_str()
+                ));
+            }
+        }
+        if rule.policy == charset.default
+            && non_ascii.to_validated_json().is_ok() != rule.non_ascii_accepted
+        {
+            return Err("Charset: validated JSON does not apply the default policy".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn check_versions(versions: &VersionSpec) -> Result<(), String> {
+    if versions.encoding != "DecimalString" {
+        return Err(format!("Versions: unknown encoding {}", versions.encoding));
+    }
+    for version in &versions.supported {
+        let decimal = version
+            .parse::<u64>()
+            .is_ok_and(|number| number.to_string() == *version);
+        if !decimal {
+            return Err(format!("Versions: {version:?} is not a decimal string"));
+        }
+    }
+    let version = payload("Sample", Vec::new()).version;
+    if !versions.supported.contains(&version.as_str()) {
+        return Err(format!("Versions: new payloads have version {version:?}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_passes() {
+        assert_eq!(self_test(), Ok(()));
+    }
+
+    #[test]
+    fn test_self_test_catches_spec_drift() {
+        let mut rules = canonical_spec().skip_rules;
+        rules.pop();
+        assert!(check_skip_rules(&rules)
+            .unwrap_err()
+            .contains("PreviewLayout omits"));
+
+        rules.push(SkipRule {
+            key: "Label",
+            object: "ListLayout",
+            omitted_when: OmittedWhen::Absent,
+        });
+        assert_eq!(
+            check_skip_rules(&rules[rules.len() - 1..]),
+            Err("SkipRules: no sample of ListLayout".to_string())
+        );
+
+        let mut charset = canonical_spec().charset;
+        charset.policies[0].non_ascii_accepted = true;
+        assert!(check_charset_rules(&charset)
+            .unwrap_err()
+            .contains("NonAsciiAccepted is false under Ascii"));
+
+        let versions = VersionSpec {
+            encoding: "DecimalString",
+            supported: vec!["00"],
+        };
+        assert!(check_versions(&versions).is_err());
+    }
+}
@@ -0,0 +1,14 @@
+//! Tracing span fields shared by the host, the enclave app and the chain parsers.
+//!
+//! [`crate::registry::TransactionConverterRegistry`] opens a `convert` span per transaction
+//! declaring these fields; parsers fill in the values only they know, such as how many commands
+//! a transaction contains. Recording on a span that does not declare the field is a no-op, so
+//! parsers called outside the registry are unaffected.
+
+/// Number of top-level commands or instructions in the transaction
+pub const COMMAND_COUNT: &str = "command_count";
+
+/// Records the transaction's command or instruction count on the current span.
+pub fn record_command_count(count: usize) {
+    tracing::Span::current().record(COMMAND_COUNT, count);
+}
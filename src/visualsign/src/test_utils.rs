@@ -105,6 +105,30 @@ pub fn check_signable_payload_field(
             .then(|| amount_v2.amount.to_string())
             .into_iter()
             .collect(),
+        SignablePayloadField::AmountV3 { common, amount_v3 } => (common.label == label)
+            .then(|| amount_v3.amount.to_string())
+            .into_iter()
+            .collect(),
+        SignablePayloadField::Timestamp { common, timestamp } => (common.label == label)
+            .then(|| timestamp.timestamp.to_string())
+            .into_iter()
+            .collect(),
+        SignablePayloadField::Duration { common, duration } => (common.label == label)
+            .then(|| duration.seconds.to_string())
+            .into_iter()
+            .collect(),
+        SignablePayloadField::Percentage { common, percentage } => (common.label == label)
+            .then(|| percentage.percentage.to_string())
+            .into_iter()
+            .collect(),
+        SignablePayloadField::ImageRef { common, image_ref } => (common.label == label)
+            .then(|| image_ref.identifier.to_string())
+            .into_iter()
+            .collect(),
+        SignablePayloadField::Bytes { common, bytes } => (common.label == label)
+            .then(|| bytes.hex.to_string())
+            .into_iter()
+            .collect(),
         SignablePayloadField::PreviewLayout {
             preview_layout,
             common,
@@ -0,0 +1,175 @@
+//! Plain-text summaries and titles.
+//!
+//! Presets build one-line summaries such as `"Swap 1 ETH -> 2,500 USDC"` from token symbols,
+//! names and templates. Wallets show them verbatim, so they must stay plain ASCII text: no
+//! typographic arrows or quotes, which the ASCII [`crate::CharsetPolicy`] rejects, no Markdown
+//! markup a renderer could pick up, no stray line breaks and nothing too long for one line.
+//! [`sanitize_summary`] turns a formatted string into such a summary and [`check_summary`]
+//! reports what keeps a string from being one.
+
+/// Longest summary, in characters, that [`sanitize_summary`] keeps.
+pub const MAX_SUMMARY_LEN: usize = 160;
+
+const ELLIPSIS: &str = "...";
+
+// ASCII spellings of the typographic characters templates and token metadata tend to carry
+fn ascii_replacement(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        '\u{2192}' | '\u{27F6}' | '\u{21D2}' | '\u{27F9}' | '\u{279C}' | '\u{2794}' => "->",
+        '\u{2190}' | '\u{27F5}' | '\u{21D0}' => "<-",
+        '\u{2194}' | '\u{27F7}' | '\u{21D4}' => "<->",
+        '\u{2026}' => ELLIPSIS,
+        '\u{2010}'..='\u{2015}' | '\u{2212}' => "-",
+        '\u{2018}' | '\u{2019}' | '\u{201B}' | '\u{2032}' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201F}' | '\u{2033}' => "\"",
+        '\u{00D7}' => "x",
+        '\u{2022}' | '\u{00B7}' => "-",
+        _ => return None,
+    })
+}
+
+/// `text` as a plain-text summary of at most [`MAX_SUMMARY_LEN`] characters.
+pub fn sanitize_summary(text: &str) -> String {
+    sanitize_summary_to(text, MAX_SUMMARY_LEN)
+}
+
+/// `text` as a plain-text summary of at most `max_len` characters: typographic arrows, dashes,
+/// quotes and ellipses become their ASCII spelling, control and format characters are dropped,
+/// backticks and emphasis markers are removed, whitespace runs collapse to one space, and a
+/// summary over `max_len` is cut at a word boundary where possible and ends in `"..."`.
+///
+/// Other non-ASCII characters, such as letters of a token name, are kept; whether they may be
+/// shown is up to the payload's charset policy.
+pub fn sanitize_summary_to(text: &str, max_len: usize) -> String {
+    let mut plain = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if let Some(replacement) = ascii_replacement(ch) {
+            plain.push_str(replacement);
+        } else if ch.is_whitespace() {
+            plain.push(' ');
+        } else if !ch.is_control() && !is_format_char(ch) && ch != '`' {
+            plain.push(ch);
+        }
+    }
+    let plain = plain.replace("**", "").replace("__", "");
+    truncate(
+        &plain.split_whitespace().collect::<Vec<_>>().join(" "),
+        max_len,
+    )
+}
+
+// Zero-width and bidirectional formatting characters, which change how text is shown
+fn is_format_char(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2069}' | '\u{FEFF}'
+    )
+}
+
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    let keep = max_len.saturating_sub(ELLIPSIS.len());
+    let cut: String = text.chars().take(keep).collect();
+    // Prefer ending on a whole word unless that drops most of the summary
+    let cut = match cut.rfind(' ') {
+        Some(space) if space >= cut.len() / 2 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    let mut truncated = cut.trim_end().to_string();
+    truncated.push_str(&ELLIPSIS[..ELLIPSIS.len().min(max_len)]);
+    truncated
+}
+
+/// `steps` joined with `" -> "`, e.g. the tokens of a swap route.
+pub fn route_summary<I>(steps: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    steps
+        .into_iter()
+        .map(|step| step.as_ref().to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Checks that `text` is a plain-text summary: printable ASCII, whitespace normalized, free of
+/// Markdown markup and at most [`MAX_SUMMARY_LEN`] characters. The error names the first
+/// violation found.
+pub fn check_summary(text: &str) -> Result<(), String> {
+    if let Some((index, ch)) = text
+        .char_indices()
+        .find(|(_, ch)| !(ch.is_ascii_graphic() || *ch == ' '))
+    {
+        return Err(format!(
+            "summary contains '{}' (U+{:04X}) at position {index}",
+            ch.escape_default(),
+            ch as u32
+        ));
+    }
+    if text.starts_with(' ') || text.ends_with(' ') || text.contains("  ") {
+        return Err("summary has leading, trailing or repeated spaces".to_string());
+    }
+    if let Some(markup) = ["**", "__", "`"]
+        .iter()
+        .find(|markup| text.contains(*markup))
+    {
+        return Err(format!("summary contains Markdown markup '{markup}'"));
+    }
+    let len = text.chars().count();
+    if len > MAX_SUMMARY_LEN {
+        return Err(format!(
+            "summary is {len} characters, longer than {MAX_SUMMARY_LEN}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_summary() {
+        let cases = [
+            ("ETH \u{2192} USDC \u{2192} DAI", "ETH -> USDC -> DAI"),
+            (
+                "  Swap\t1 ETH\n\nfor **at least** 2 DAI ",
+                "Swap 1 ETH for at least 2 DAI",
+            ),
+            ("Approve `USDC`\u{2026}", "Approve USDC..."),
+            ("Pay \u{201C}fee\u{201D} \u{2013} 5%", "Pay \"fee\" - 5%"),
+            ("Send\u{200B} 1\u{202E} SOL\u{0007}", "Send 1 SOL"),
+            ("Swap 1 \u{C9}TH", "Swap 1 \u{C9}TH"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(sanitize_summary(input), expected, "{input:?}");
+        }
+
+        assert_eq!(
+            sanitize_summary_to("Swap 1 ETH for at least 2500 USDC", 20),
+            "Swap 1 ETH for..."
+        );
+        assert_eq!(sanitize_summary_to("0x0123456789abcdef", 10), "0x01234...");
+        let long = "word ".repeat(100);
+        assert!(sanitize_summary(&long).chars().count() <= MAX_SUMMARY_LEN);
+        assert!(check_summary(&sanitize_summary(&long)).is_ok());
+    }
+
+    #[test]
+    fn test_route_summary_and_check_summary() {
+        let route = route_summary(["ETH", "USDC", "DAI"]);
+        assert_eq!(route, "ETH -> USDC -> DAI");
+        assert!(check_summary(&route).is_ok());
+
+        assert!(check_summary("ETH \u{2192} USDC").is_err());
+        assert!(check_summary(" Swap").is_err());
+        assert!(check_summary("Swap  1 ETH").is_err());
+        assert!(check_summary("Swap **1 ETH**").is_err());
+        assert!(check_summary("Line\nbreak").is_err());
+        assert!(check_summary(&"x".repeat(MAX_SUMMARY_LEN + 1)).is_err());
+        assert!(check_summary(&"x".repeat(MAX_SUMMARY_LEN)).is_ok());
+    }
+}
@@ -0,0 +1,220 @@
+//! Depth-first traversal of payload trees.
+//!
+//! [`walk_payload`] hands every field of a payload to a [`PayloadVisitor`], together with the
+//! layouts and annotations around it, so checks over nested fields only say what to do with
+//! each node instead of each re-implementing the recursion. Fields are visited in display order,
+//! a field before the fields nested in it and a condensed view before the expanded one, under
+//! paths in the notation of [`SignablePayload::field_at_path`], e.g.
+//! `Fields[1].PreviewLayout.Expanded[0]`.
+
+use crate::{
+    AnnotatedPayloadField, SignablePayload, SignablePayloadField, SignablePayloadFieldListLayout,
+    SignablePayloadFieldPreviewLayout,
+};
+
+/// Callbacks of [`walk_payload`]; each defaults to doing nothing. Returning an error from any of
+/// them stops the walk with that error.
+pub trait PayloadVisitor {
+    type Error;
+
+    /// Called for every field, before the fields nested in it
+    fn visit_field(
+        &mut self,
+        _path: &str,
+        _field: &SignablePayloadField,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called for every field nested in a layout, with its annotations, before
+    /// [`Self::visit_field`]
+    fn visit_annotated(
+        &mut self,
+        _path: &str,
+        _annotated: &AnnotatedPayloadField,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called for the layout of every `PreviewLayout` field, after [`Self::visit_field`]
+    fn visit_preview_layout(
+        &mut self,
+        _path: &str,
+        _preview_layout: &SignablePayloadFieldPreviewLayout,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called for the layout of every `ListLayout` field, and for the condensed and expanded
+    /// views of every `PreviewLayout`, before the fields in it
+    fn visit_list_layout(
+        &mut self,
+        _path: &str,
+        _list_layout: &SignablePayloadFieldListLayout,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Walks every field of `payload` with `visitor`.
+pub fn walk_payload<V: PayloadVisitor + ?Sized>(
+    payload: &SignablePayload,
+    visitor: &mut V,
+) -> Result<(), V::Error> {
+    for (index, field) in payload.fields.iter().enumerate() {
+        walk_field(&format!("Fields[{index}]"), field, visitor)?;
+    }
+    Ok(())
+}
+
+/// Walks `field`, found at `path`, and the fields nested in it with `visitor`.
+pub fn walk_field<V: PayloadVisitor + ?Sized>(
+    path: &str,
+    field: &SignablePayloadField,
+    visitor: &mut V,
+) -> Result<(), V::Error> {
+    visitor.visit_field(path, field)?;
+    match field {
+        SignablePayloadField::PreviewLayout { preview_layout, .. } => {
+            visitor.visit_preview_layout(path, preview_layout)?;
+            if let Some(condensed) = &preview_layout.condensed {
+                walk_list(
+                    &format!("{path}.PreviewLayout.Condensed"),
+                    condensed,
+                    visitor,
+                )?;
+            }
+            if let Some(expanded) = &preview_layout.expanded {
+                walk_list(&format!("{path}.PreviewLayout.Expanded"), expanded, visitor)?;
+            }
+        }
+        SignablePayloadField::ListLayout { list_layout, .. } => {
+            walk_list(&format!("{path}.ListLayout"), list_layout, visitor)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn walk_list<V: PayloadVisitor + ?Sized>(
+    path: &str,
+    list_layout: &SignablePayloadFieldListLayout,
+    visitor: &mut V,
+) -> Result<(), V::Error> {
+    visitor.visit_list_layout(path, list_layout)?;
+    for (index, annotated) in list_layout.fields.iter().enumerate() {
+        let path = format!("{path}[{index}]");
+        visitor.visit_annotated(&path, annotated)?;
+        walk_field(&path, &annotated.signable_payload_field, visitor)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_builders::create_text_field;
+    use crate::SignablePayloadFieldCommon;
+
+    #[derive(Default)]
+    struct Recorder {
+        visits: Vec<String>,
+    }
+
+    impl PayloadVisitor for Recorder {
+        type Error = String;
+
+        fn visit_field(&mut self, path: &str, field: &SignablePayloadField) -> Result<(), String> {
+            if field.label() == "Stop" {
+                return Err(path.to_string());
+            }
+            self.visits.push(format!("field {path}"));
+            Ok(())
+        }
+
+        fn visit_annotated(
+            &mut self,
+            path: &str,
+            _annotated: &AnnotatedPayloadField,
+        ) -> Result<(), String> {
+            self.visits.push(format!("annotated {path}"));
+            Ok(())
+        }
+
+        fn visit_preview_layout(
+            &mut self,
+            path: &str,
+            _preview_layout: &SignablePayloadFieldPreviewLayout,
+        ) -> Result<(), String> {
+            self.visits.push(format!("preview {path}"));
+            Ok(())
+        }
+
+        fn visit_list_layout(
+            &mut self,
+            path: &str,
+            _list_layout: &SignablePayloadFieldListLayout,
+        ) -> Result<(), String> {
+            self.visits.push(format!("list {path}"));
+            Ok(())
+        }
+    }
+
+    fn payload(last_label: &str) -> SignablePayload {
+        let text = |label: &str| create_text_field(label, "value").unwrap();
+        let preview = SignablePayloadField::PreviewLayout {
+            common: SignablePayloadFieldCommon {
+                fallback_text: "Swap".to_string(),
+                label: "Swap".to_string(),
+            },
+            preview_layout: SignablePayloadFieldPreviewLayout {
+                title: None,
+                subtitle: None,
+                condensed: Some(SignablePayloadFieldListLayout {
+                    fields: vec![text("Amount")],
+                }),
+                expanded: Some(SignablePayloadFieldListLayout {
+                    fields: vec![text("Amount"), text("Pool")],
+                }),
+            },
+        };
+        SignablePayload::new(
+            0,
+            "Swap".to_string(),
+            None,
+            vec![preview, text(last_label).signable_payload_field],
+            "Test".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_walk_payload_visits_nodes_in_display_order() {
+        let mut recorder = Recorder::default();
+        walk_payload(&payload("Network"), &mut recorder).unwrap();
+        assert_eq!(
+            recorder.visits,
+            [
+                "field Fields[0]",
+                "preview Fields[0]",
+                "list Fields[0].PreviewLayout.Condensed",
+                "annotated Fields[0].PreviewLayout.Condensed[0]",
+                "field Fields[0].PreviewLayout.Condensed[0]",
+                "list Fields[0].PreviewLayout.Expanded",
+                "annotated Fields[0].PreviewLayout.Expanded[0]",
+                "field Fields[0].PreviewLayout.Expanded[0]",
+                "annotated Fields[0].PreviewLayout.Expanded[1]",
+                "field Fields[0].PreviewLayout.Expanded[1]",
+                "field Fields[1]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_payload_stops_at_the_first_error() {
+        let mut recorder = Recorder::default();
+        assert_eq!(
+            walk_payload(&payload("Stop"), &mut recorder),
+            Err("Fields[1]".to_string())
+        );
+    }
+}
@@ -1,16 +1,117 @@
 use std::fmt::Debug;
+use std::sync::Arc;
 
-use crate::SignablePayload;
+use serde::{Deserialize, Serialize};
+
+use crate::capabilities::ParserCapabilities;
+use crate::extensions::Extensions;
+use crate::labels::DuplicateLabelPolicy;
+use crate::memo::MemoRequirements;
+use crate::names::{apply_name_resolution, NameResolver};
+use crate::outcome::ParseOutcome;
+use crate::parser_info::ParserInfo;
+use crate::provenance::Provenance;
+use crate::simulation::SimulationOutcome;
+use crate::{CharsetPolicy, SignablePayload};
 
 pub use crate::errors::{TransactionParseError, VisualSignError};
 pub use generated::parser::ChainMetadata;
 
-#[derive(Default, Debug, Clone)]
+/// Options for one conversion.
+///
+/// The serializable fields round-trip through JSON (`#[serde(default)]`, PascalCase keys) so a
+/// host can send them across the enclave boundary; host callbacks and the raw gRPC
+/// `ChainMetadata` are process-local and skipped.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VisualSignOptions {
+    #[serde(rename = "DecodeTransfers")]
     pub decode_transfers: bool,
+    #[serde(rename = "TransactionName", skip_serializing_if = "Option::is_none")]
     pub transaction_name: Option<String>,
+    /// Raw gRPC chain metadata; prefer [`Extensions`], which carries the same ABI/IDL typed
+    #[serde(skip)]
     pub metadata: Option<ChainMetadata>,
-    // Add more options as needed - we can extend this struct later
+    /// Advisory simulation results from the caller, appended as an "Expected Outcome" section
+    #[serde(rename = "Simulation", skip_serializing_if = "Option::is_none")]
+    pub simulation: Option<SimulationOutcome>,
+    /// Host-supplied ENS/SNS names applied to every `AddressV2` field
+    #[serde(skip)]
+    pub name_resolver: Option<Arc<dyn NameResolver>>,
+    /// Host-supplied destinations that need a memo or destination tag, checked by parsers
+    #[serde(skip)]
+    pub memo_requirements: Option<Arc<dyn MemoRequirements>>,
+    /// Reject or namespace repeated labels within a list of fields; unset leaves them as is
+    #[serde(rename = "DuplicateLabels", skip_serializing_if = "Option::is_none")]
+    pub duplicate_labels: Option<DuplicateLabelPolicy>,
+    /// Typed host context such as token metadata, address labels, locale and ABI/IDL blobs
+    /// (see [`crate::extensions`])
+    #[serde(rename = "Extensions", skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+    /// Characters the validated payload may contain; printable ASCII by default
+    #[serde(rename = "Charset")]
+    pub charset: CharsetPolicy,
+    /// Account the host intends to sign with, shown as the "From" field when the transaction
+    /// does not name its sender and checked against it when it does (see [`crate::sender`])
+    #[serde(rename = "Sender", skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    /// What the converter adds to the payload and how strictly it decodes; the flags keep
+    /// their top-level JSON keys
+    #[serde(flatten)]
+    pub output: OutputFlags,
+}
+
+/// Per-request switches of [`VisualSignOptions`], all off by default.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputFlags {
+    /// Package the parser's flat field list into a single condensed/expanded `PreviewLayout`
+    /// (see [`crate::layout`]); parsers that already build their own layouts ignore it
+    #[serde(rename = "PreviewLayout")]
+    pub preview_layout: bool,
+    /// Embed the converter's [`ParserInfo`] in the validated payload
+    #[serde(rename = "IncludeParserInfo")]
+    pub include_parser_info: bool,
+    /// Report where each field comes from in the raw transaction (see [`crate::provenance`]);
+    /// converters that cannot map their fields leave the map empty
+    #[serde(rename = "IncludeProvenance")]
+    pub include_provenance: bool,
+    /// Reject inputs that do not re-encode byte for byte from the decoded transaction, such as
+    /// trailing data or non-canonical encodings (see [`crate::lossless`])
+    #[serde(rename = "VerifyLossless")]
+    pub verify_lossless: bool,
+    /// Embed the share of the raw input the fields decode as `Coverage` (see
+    /// [`crate::coverage`]); converters that do not measure it leave it out
+    #[serde(rename = "IncludeCoverage")]
+    pub include_coverage: bool,
+    /// Show a sub-component that fails to decode, such as one Solana instruction or one Sui
+    /// command, as an `Unknown` field explaining the failure instead of failing the whole
+    /// parse; each such field is reported as a warning and its bytes count as undecoded in
+    /// the coverage
+    #[serde(rename = "PartialParse")]
+    pub partial_parse: bool,
+}
+
+impl VisualSignOptions {
+    /// Applies the keys of a JSON options object on top of `self`, as sent in the gRPC
+    /// `ParseRequest.options` field; keys it leaves out keep their current value, and the
+    /// process-local fields are kept as they are.
+    pub fn with_overrides(self, json: &str) -> Result<Self, serde_json::Error> {
+        let serde_json::Value::Object(overrides) = serde_json::from_str(json)? else {
+            return Err(serde::de::Error::custom("options must be a JSON object"));
+        };
+        let mut merged = serde_json::to_value(&self)?;
+        if let serde_json::Value::Object(current) = &mut merged {
+            current.extend(overrides);
+        }
+        let merged: VisualSignOptions = serde_json::from_value(merged)?;
+        Ok(VisualSignOptions {
+            metadata: self.metadata,
+            name_resolver: self.name_resolver,
+            memo_requirements: self.memo_requirements,
+            ..merged
+        })
+    }
 }
 
 pub trait VisualSignConverter<T: Transaction> {
@@ -20,16 +121,85 @@ pub trait VisualSignConverter<T: Transaction> {
         options: VisualSignOptions,
     ) -> Result<SignablePayload, VisualSignError>;
 
+    /// Convert to a payload together with non-fatal warnings, such as calls to unknown
+    /// contracts or accounts that cannot be resolved offline
+    ///
+    /// Converters that detect such issues override this; the default reports no warnings.
+    fn to_visual_sign_outcome(
+        &self,
+        transaction: T,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
+        self.to_visual_sign_payload(transaction, options)
+            .map(ParseOutcome::new)
+    }
+
+    /// The crate and release of this converter, embedded in validated payloads when
+    /// `options.output.include_parser_info` is set; converters usually return
+    /// `Some(parser_info!())`
+    fn parser_info(&self) -> Option<ParserInfo> {
+        None
+    }
+
+    /// The encodings, transaction variants and protocol decoders this converter supports, as
+    /// listed by the registry; the default reports none
+    fn capabilities(&self) -> ParserCapabilities {
+        ParserCapabilities::default()
+    }
+
     /// Convert to VisualSign payload with automatic charset validation
     /// This method should be used instead of to_visual_sign_payload to ensure charset safety
+    ///
+    /// When `options.name_resolver` is set, resolved names are filled into address fields, and
+    /// when `options.simulation` is set, its "Expected Outcome" section is appended after the
+    /// parser's fields, so every chain renders both the same way. `options.duplicate_labels`
+    /// then rejects or namespaces repeated labels.
     fn to_validated_visual_sign_payload(
         &self,
         transaction: T,
         options: VisualSignOptions,
     ) -> Result<SignablePayload, VisualSignError> {
-        let payload = self.to_visual_sign_payload(transaction, options)?;
-        payload.validate_charset()?;
-        Ok(payload)
+        self.to_validated_visual_sign_outcome(transaction, options)
+            .map(|outcome| outcome.payload)
+    }
+
+    /// Same as [`Self::to_validated_visual_sign_payload`], keeping the converter's warnings
+    fn to_validated_visual_sign_outcome(
+        &self,
+        transaction: T,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
+        let simulation = options.simulation.clone();
+        let name_resolver = options.name_resolver.clone();
+        let duplicate_labels = options.duplicate_labels;
+        let charset = options.charset;
+        let include_parser_info = options.output.include_parser_info;
+        let include_provenance = options.output.include_provenance;
+        let include_coverage = options.output.include_coverage;
+        let mut outcome = self.to_visual_sign_outcome(transaction, options)?;
+        if !include_provenance {
+            outcome.provenance = Provenance::default();
+        }
+        let payload = &mut outcome.payload;
+        if let Some(resolver) = name_resolver {
+            apply_name_resolution(payload, resolver.as_ref());
+        }
+        if let Some(simulation) = simulation {
+            payload.fields.push(simulation.to_payload_field()?);
+        }
+        match duplicate_labels {
+            Some(DuplicateLabelPolicy::Reject) => payload.validate_unique_labels()?,
+            Some(DuplicateLabelPolicy::Namespace) => payload.namespace_duplicate_labels(),
+            None => {}
+        }
+        if include_parser_info {
+            payload.parser_info = self.parser_info();
+        }
+        if include_coverage {
+            payload.coverage = outcome.coverage;
+        }
+        payload.validate_charset_with(charset)?;
+        Ok(outcome)
     }
 }
 
@@ -62,6 +232,12 @@ pub trait Transaction: Debug + Clone {
 
     /// Get the transaction type name (e.g., "Solana", "Ethereum", "Bitcoin")
     fn transaction_type(&self) -> String;
+
+    /// Checks that `data`, the string `self` was parsed from, is exactly the encoding of `self`
+    /// (see [`crate::lossless`]). Formats without a canonical encoding accept any input.
+    fn verify_lossless(&self, _data: &str) -> Result<(), TransactionParseError> {
+        Ok(())
+    }
 }
 
 /// Convenience trait for converting from string directly
@@ -73,8 +249,28 @@ pub trait VisualSignConverterFromString<T: Transaction>: VisualSignConverter<T>
         options: VisualSignOptions,
     ) -> Result<SignablePayload, VisualSignError> {
         let transaction = T::from_string(transaction_data).map_err(VisualSignError::ParseError)?;
+        if options.output.verify_lossless {
+            transaction
+                .verify_lossless(transaction_data)
+                .map_err(VisualSignError::ParseError)?;
+        }
         self.to_validated_visual_sign_payload(transaction, options)
     }
+
+    /// Same as [`Self::to_visual_sign_payload_from_string`], keeping the converter's warnings
+    fn to_visual_sign_outcome_from_string(
+        &self,
+        transaction_data: &str,
+        options: VisualSignOptions,
+    ) -> Result<ParseOutcome, VisualSignError> {
+        let transaction = T::from_string(transaction_data).map_err(VisualSignError::ParseError)?;
+        if options.output.verify_lossless {
+            transaction
+                .verify_lossless(transaction_data)
+                .map_err(VisualSignError::ParseError)?;
+        }
+        self.to_validated_visual_sign_outcome(transaction, options)
+    }
 }
 
 #[cfg(test)]
@@ -184,6 +380,10 @@ mod tests {
                 "Test".to_string(),
             ))
         }
+
+        fn parser_info(&self) -> Option<ParserInfo> {
+            Some(crate::parser_info!())
+        }
     }
 
     impl VisualSignConverterFromString<MockTransaction> for MockConverter {
@@ -258,7 +458,7 @@ mod tests {
         let options = VisualSignOptions {
             decode_transfers: true,
             transaction_name: Some("Custom Transaction".to_string()),
-            metadata: None,
+            ..Default::default()
         };
 
         let result = converter.to_visual_sign_payload(transaction, options);
@@ -342,5 +542,180 @@ mod tests {
         let options = VisualSignOptions::default();
         assert!(!options.decode_transfers);
         assert!(options.transaction_name.is_none());
+        assert!(options.simulation.is_none());
+    }
+
+    #[test]
+    fn test_validated_payload_appends_simulation() {
+        use crate::simulation::{SimulationOutcome, EXPECTED_OUTCOME_LABEL};
+
+        let converter = MockConverter;
+        let transaction = MockTransaction {
+            data: "test_tx".to_string(),
+            tx_type: "Solana",
+        };
+
+        let payload = converter
+            .to_validated_visual_sign_payload(transaction.clone(), VisualSignOptions::default())
+            .unwrap();
+        assert_eq!(payload.fields.len(), 1);
+
+        let options = VisualSignOptions {
+            simulation: Some(SimulationOutcome::default()),
+            ..Default::default()
+        };
+        let payload = converter
+            .to_validated_visual_sign_payload(transaction, options)
+            .unwrap();
+        assert_eq!(payload.fields.len(), 2);
+        assert_eq!(payload.fields[1].label(), EXPECTED_OUTCOME_LABEL);
+    }
+
+    #[test]
+    fn test_options_serde_round_trip() {
+        use crate::extensions::{Locale, RenderBudget};
+
+        let mut options = VisualSignOptions {
+            decode_transfers: true,
+            duplicate_labels: Some(DuplicateLabelPolicy::Namespace),
+            ..Default::default()
+        };
+        options.extensions.insert(Locale("en-US".to_string()));
+        options.extensions.insert(RenderBudget {
+            max_fields: Some(8),
+            max_text_length: Some(64),
+        });
+
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(
+            json,
+            r#"{"DecodeTransfers":true,"DuplicateLabels":"Namespace","Extensions":[{"Kind":"Locale","Value":"en-US"},{"Kind":"RenderBudget","Value":{"MaxFields":8,"MaxTextLength":64}}],"Charset":"Ascii","PreviewLayout":false,"IncludeParserInfo":false,"IncludeProvenance":false,"VerifyLossless":false,"IncludeCoverage":false,"PartialParse":false}"#
+        );
+
+        let decoded: VisualSignOptions = serde_json::from_str(&json).unwrap();
+        assert!(decoded.decode_transfers);
+        assert_eq!(decoded.duplicate_labels, options.duplicate_labels);
+        assert_eq!(decoded.extensions, options.extensions);
+
+        // Every key is optional
+        let empty: VisualSignOptions = serde_json::from_str("{}").unwrap();
+        assert!(!empty.decode_transfers);
+        assert!(empty.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_options_with_overrides() {
+        let options = VisualSignOptions {
+            decode_transfers: true,
+            transaction_name: Some("Swap".to_string()),
+            ..Default::default()
+        }
+        .with_overrides(r#"{"Charset":"Unicode","TransactionName":null}"#)
+        .unwrap();
+        assert!(options.decode_transfers);
+        assert_eq!(options.transaction_name, None);
+        assert_eq!(options.charset, CharsetPolicy::Unicode);
+
+        // Output flags are top-level keys and are overridden one at a time
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                include_coverage: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .with_overrides(r#"{"PartialParse":true}"#)
+        .unwrap();
+        assert_eq!(
+            options.output,
+            OutputFlags {
+                include_coverage: true,
+                partial_parse: true,
+                ..Default::default()
+            }
+        );
+
+        assert!(VisualSignOptions::default().with_overrides("[]").is_err());
+        assert!(VisualSignOptions::default()
+            .with_overrides(r#"{"Charset":"Latin1"}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parser_info_is_opt_in() {
+        let converter = MockConverter;
+        let transaction = MockTransaction::from_string("solana_tx").unwrap();
+
+        let payload = converter
+            .to_validated_visual_sign_payload(transaction.clone(), VisualSignOptions::default())
+            .unwrap();
+        assert!(payload.parser_info.is_none());
+        assert!(!payload.to_json().unwrap().contains("ParserInfo"));
+
+        let options = VisualSignOptions {
+            output: OutputFlags {
+                include_parser_info: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let payload = converter
+            .to_validated_visual_sign_payload(transaction, options)
+            .unwrap();
+        assert_eq!(payload.parser_info, Some(crate::parser_info!()));
+        assert!(payload.verify_deterministic_ordering().is_ok());
+    }
+
+    #[test]
+    fn test_validated_outcome_keeps_warnings() {
+        use crate::outcome::{ParseWarning, ParseWarningKind};
+
+        struct WarningConverter;
+
+        impl VisualSignConverter<MockTransaction> for WarningConverter {
+            fn to_visual_sign_payload(
+                &self,
+                transaction: MockTransaction,
+                options: VisualSignOptions,
+            ) -> Result<SignablePayload, VisualSignError> {
+                MockConverter.to_visual_sign_payload(transaction, options)
+            }
+
+            fn to_visual_sign_outcome(
+                &self,
+                transaction: MockTransaction,
+                options: VisualSignOptions,
+            ) -> Result<ParseOutcome, VisualSignError> {
+                let payload = self.to_visual_sign_payload(transaction, options)?;
+                Ok(ParseOutcome::with_warnings(
+                    payload,
+                    vec![ParseWarning::new(
+                        ParseWarningKind::UnknownContract,
+                        "No decoder for 0xabc",
+                    )],
+                ))
+            }
+        }
+
+        let transaction = MockTransaction {
+            data: "test_tx".to_string(),
+            tx_type: "Solana",
+        };
+        let options = VisualSignOptions {
+            simulation: Some(crate::simulation::SimulationOutcome::default()),
+            ..Default::default()
+        };
+        let outcome = WarningConverter
+            .to_validated_visual_sign_outcome(transaction.clone(), options)
+            .unwrap();
+        assert_eq!(outcome.payload.fields.len(), 2);
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(outcome.warnings[0].kind, ParseWarningKind::UnknownContract);
+
+        // Converters that do not override the outcome report no warnings
+        let outcome = MockConverter
+            .to_visual_sign_outcome(transaction, VisualSignOptions::default())
+            .unwrap();
+        assert!(!outcome.has_warnings());
     }
 }
@@ -0,0 +1,88 @@
+//! Canonical JSON conformance vectors shared with the Go implementation.
+//!
+//! Every file in `tests/vectors/canonical_json` pairs a payload with the exact string its
+//! canonical encoding must produce; see the README there for the format. The rules those
+//! vectors exercise are described in `tests/vectors/canonical_spec.json`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use pretty_assertions::assert_eq;
+use serde::Deserialize;
+use visualsign::spec::{canonical_spec, self_test};
+use visualsign::SignablePayload;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Vector {
+    description: String,
+    payload: serde_json::Value,
+    canonical: String,
+}
+
+fn load_vectors() -> Vec<(String, Vector)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/canonical_json");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", dir.display()))
+        .map(|entry| entry.expect("Failed to read vector entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let contents = fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()));
+            let vector = serde_json::from_str(&contents)
+                .unwrap_or_else(|e| panic!("Failed to parse {}: {e}", path.display()));
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            (name, vector)
+        })
+        .collect()
+}
+
+#[test]
+fn test_canonical_json_vectors() {
+    let vectors = load_vectors();
+    assert!(!vectors.is_empty(), "No canonical JSON vectors found");
+
+    for (name, vector) in vectors {
+        let payload: SignablePayload = serde_json::from_value(vector.payload)
+            .unwrap_or_else(|e| panic!("{name}: invalid payload: {e}"));
+        let canonical = payload
+            .to_json()
+            .unwrap_or_else(|e| panic!("{name}: failed to encode: {e}"));
+        assert_eq!(
+            canonical, vector.canonical,
+            "{name}: {}",
+            vector.description
+        );
+
+        // The canonical form is a fixed point of decoding and re-encoding
+        let decoded: SignablePayload = serde_json::from_str(&canonical)
+            .unwrap_or_else(|e| panic!("{name}: canonical form does not decode: {e}"));
+        assert_eq!(
+            decoded, payload,
+            "{name}: canonical form decodes differently"
+        );
+        assert_eq!(
+            decoded.to_json().unwrap(),
+            canonical,
+            "{name}: re-encoding the canonical form changed it"
+        );
+    }
+}
+
+#[test]
+fn test_canonical_spec_snapshot() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/canonical_spec.json");
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()));
+    let snapshot: serde_json::Value = serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("Failed to parse {}: {e}", path.display()));
+
+    // A rule change must bump SpecVersion and update the snapshot in the same change
+    assert_eq!(serde_json::to_value(canonical_spec()).unwrap(), snapshot);
+    assert_eq!(self_test(), Ok(()));
+}
@@ -0,0 +1,11 @@
+//! Compile-fail checks for the deterministic ordering guarantees.
+//!
+//! Each file in `tests/ui` must fail to compile with the error recorded next to it. Regenerate
+//! the `.stderr` files with `TRYBUILD=overwrite cargo test -p visualsign --test compile_fail`
+//! after a deliberate change to the diagnostics.
+
+#[test]
+fn test_unaudited_types_are_rejected() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
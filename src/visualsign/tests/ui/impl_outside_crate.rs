@@ -0,0 +1,17 @@
+// DeterministicOrdering is sealed: a type can only claim deterministic ordering once its
+// Serialize impl has been audited inside visualsign
+
+use serde::Serialize;
+use visualsign::DeterministicOrdering;
+
+#[derive(Serialize)]
+struct Unaudited {
+    #[serde(rename = "Zebra")]
+    zebra: String,
+    #[serde(rename = "Apple")]
+    apple: String,
+}
+
+impl DeterministicOrdering for Unaudited {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// Payload fields are serialized through Deterministic, which only accepts audited types
+
+use serde::Serialize;
+use visualsign::Deterministic;
+
+#[derive(Serialize)]
+struct Unaudited {
+    #[serde(rename = "Zebra")]
+    zebra: String,
+    #[serde(rename = "Apple")]
+    apple: String,
+}
+
+fn main() {
+    let field = Unaudited {
+        zebra: "z".to_string(),
+        apple: "a".to_string(),
+    };
+    let _ = Deterministic::new(&field);
+}